@@ -1,5 +1,5 @@
 import type { ActionRequest, GameState, Player, Table, User } from '../types';
-import type { ApiClient, HandSummary, LatestReplay } from './types';
+import type { ApiClient, HandSummary, LatestReplay, TableMetrics } from './types';
 
 interface HttpApiClientOptions {
   baseUrl: string;
@@ -40,6 +40,15 @@ interface ActionDTO {
   is_fallback: boolean;
 }
 
+interface TableMetricsDTO {
+  overall: {
+    hands: number;
+    avg_pot_bb: number;
+    three_bet_frequency: number;
+    showdown_rate: number;
+  };
+}
+
 interface LatestReplayDTO {
   latest_hand?: {
     hand_id: string;
@@ -193,6 +202,16 @@ export function createHttpApiClient(options: HttpApiClientOptions): ApiClient {
       }));
     },
 
+    async getTableMetrics(tableId: string): Promise<TableMetrics> {
+      const payload = await request<TableMetricsDTO>(`/tables/${tableId}/metrics`, { method: 'GET' });
+      return {
+        hands: payload.overall.hands,
+        avgPotBb: payload.overall.avg_pot_bb,
+        threeBetFrequency: payload.overall.three_bet_frequency,
+        showdownRate: payload.overall.showdown_rate,
+      };
+    },
+
     async getHandActions(handId: string): Promise<string[]> {
       const actions = await request<ActionDTO[]>(`/hands/${handId}/actions`, { method: 'GET' });
       return actions.map((item) => {
@@ -1,5 +1,5 @@
 import type { ActionRequest, Card, GameState, Table, User } from '../types';
-import type { ApiClient, HandSummary, LatestReplay } from './types';
+import type { ApiClient, HandSummary, LatestReplay, TableMetrics } from './types';
 
 const HERO_SEAT = 2;
 const STREET_CARD_COUNT: Record<GameState['street'], number> = {
@@ -235,6 +235,18 @@ class MockApi implements ApiClient {
     ];
   }
 
+  async getTableMetrics(tableId: string): Promise<TableMetrics> {
+    await delay(100);
+    const table = this.tables.find((entry) => entry.id === tableId);
+    const players = table?.players ?? 0;
+    return {
+      hands: players * 12,
+      avgPotBb: 4 + players * 1.5,
+      threeBetFrequency: 0.05 * players,
+      showdownRate: 0.2 + 0.04 * players,
+    };
+  }
+
   async getLatestReplay(tableId: string): Promise<LatestReplay> {
     await delay(100);
     const state = this.tableState.get(tableId);
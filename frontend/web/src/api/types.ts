@@ -11,6 +11,13 @@ export interface LatestReplay {
   actionLog: string[];
 }
 
+export interface TableMetrics {
+  hands: number;
+  avgPotBb: number;
+  threeBetFrequency: number;
+  showdownRate: number;
+}
+
 export interface ApiClient {
   login(username: string): Promise<User>;
   getTables(): Promise<Table[]>;
@@ -19,6 +26,7 @@ export interface ApiClient {
   getTableState(tableId: string): Promise<GameState>;
   getLatestReplay(tableId: string): Promise<LatestReplay>;
   getTableHands(tableId: string): Promise<HandSummary[]>;
+  getTableMetrics(tableId: string): Promise<TableMetrics>;
   getHandActions(handId: string): Promise<string[]>;
   submitAction(tableId: string, action: ActionRequest): Promise<GameState>;
 }
@@ -1,3 +1,5 @@
+import type { TableMetrics } from '../api/types';
+
 export function formatArchiveTableId(id: string): string {
   const digits = id.replace(/\D/g, '');
   if (!digits) {
@@ -6,3 +8,12 @@ export function formatArchiveTableId(id: string): string {
 
   return `TX_${digits.padStart(3, '0')}`;
 }
+
+export function formatTableMetrics(metrics: TableMetrics): string {
+  if (metrics.hands === 0) {
+    return 'NO_HAND_HISTORY';
+  }
+
+  const percent = (value: number) => `${Math.round(value * 100)}%`;
+  return `AVG POT: ${metrics.avgPotBb.toFixed(1)} BB // 3BET: ${percent(metrics.threeBetFrequency)} // SHOWDOWN: ${percent(metrics.showdownRate)}`;
+}
@@ -2,7 +2,8 @@ import { useEffect, useMemo, useState } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { api } from '../api';
 import { filterTables, type LobbyFilters, type StakeFilter, type StatusFilter } from '../lib/pokerLogic';
-import { formatArchiveTableId } from '../lib/presentation';
+import { formatArchiveTableId, formatTableMetrics } from '../lib/presentation';
+import type { TableMetrics } from '../api/types';
 import type { Table } from '../types';
 
 const DEFAULT_FILTERS: LobbyFilters = {
@@ -13,6 +14,7 @@ const DEFAULT_FILTERS: LobbyFilters = {
 
 export function Lobby() {
   const [tables, setTables] = useState<Table[]>([]);
+  const [metrics, setMetrics] = useState<Record<string, TableMetrics>>({});
   const [filters, setFilters] = useState<LobbyFilters>(DEFAULT_FILTERS);
   const [isLoading, setIsLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
@@ -25,6 +27,7 @@ export function Lobby() {
     try {
       const next = await api.getTables();
       setTables(next);
+      void loadMetrics(next);
     } catch (caught) {
       console.error(caught);
       setError('Unable to load archive directory.');
@@ -33,6 +36,17 @@ export function Lobby() {
     }
   };
 
+  const loadMetrics = async (rows: Table[]) => {
+    const results = await Promise.allSettled(rows.map((table) => api.getTableMetrics(table.id)));
+    const next: Record<string, TableMetrics> = {};
+    results.forEach((result, index) => {
+      if (result.status === 'fulfilled') {
+        next[rows[index].id] = result.value;
+      }
+    });
+    setMetrics(next);
+  };
+
   useEffect(() => {
     void loadTables();
   }, []);
@@ -96,6 +110,7 @@ export function Lobby() {
               <span className="table-meta">
                 LOAD: {table.players}/{table.maxSeats} AGENTS // BLINDS: {table.smallBlind}/{table.bigBlind}
               </span>
+              {metrics[table.id] && <span className="table-meta">{formatTableMetrics(metrics[table.id])}</span>}
               <button
                 type="button"
                 className="enter-btn"
@@ -167,4 +167,40 @@ describe('createHttpApiClient', () => {
       globalThis.fetch = originalFetch;
     }
   });
+
+  it('maps overall table metrics from the metrics endpoint', async () => {
+    const originalFetch = globalThis.fetch;
+    let requestedUrl = '';
+    globalThis.fetch = async (input) => {
+      requestedUrl = String(input);
+      return new Response(
+        JSON.stringify({
+          window_seconds: 3600,
+          overall: {
+            hands: 12,
+            avg_pot_bb: 7.5,
+            three_bet_frequency: 0.25,
+            showdown_rate: 0.4,
+          },
+          windows: [],
+        }),
+        { status: 200 },
+      );
+    };
+
+    try {
+      const api = createHttpApiClient({
+        baseUrl: 'http://127.0.0.1:8080',
+        getToken: () => 'admin-token',
+      });
+      const metrics = await api.getTableMetrics('table-4');
+      assert.equal(requestedUrl, 'http://127.0.0.1:8080/tables/table-4/metrics');
+      assert.equal(metrics.hands, 12);
+      assert.equal(metrics.avgPotBb, 7.5);
+      assert.equal(metrics.threeBetFrequency, 0.25);
+      assert.equal(metrics.showdownRate, 0.4);
+    } finally {
+      globalThis.fetch = originalFetch;
+    }
+  });
 });
@@ -1,6 +1,6 @@
 import { describe, it } from 'node:test';
 import assert from 'node:assert/strict';
-import { formatArchiveTableId } from '../src/lib/presentation.ts';
+import { formatArchiveTableId, formatTableMetrics } from '../src/lib/presentation.ts';
 
 describe('formatArchiveTableId', () => {
   it('formats ids into texas archive labels', () => {
@@ -9,3 +9,19 @@ describe('formatArchiveTableId', () => {
     assert.equal(formatArchiveTableId('tx-1234'), 'TX_1234');
   });
 });
+
+describe('formatTableMetrics', () => {
+  it('formats windowed table metrics for the lobby', () => {
+    assert.equal(
+      formatTableMetrics({ hands: 40, avgPotBb: 6.25, threeBetFrequency: 0.125, showdownRate: 0.5 }),
+      'AVG POT: 6.3 BB // 3BET: 13% // SHOWDOWN: 50%',
+    );
+  });
+
+  it('reports tables without hand history', () => {
+    assert.equal(
+      formatTableMetrics({ hands: 0, avgPotBb: 0, threeBetFrequency: 0, showdownRate: 0 }),
+      'NO_HAND_HISTORY',
+    );
+  });
+});
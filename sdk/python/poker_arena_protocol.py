@@ -0,0 +1,63 @@
+# Code generated by protocolgen from schemas/agent-protocol-v1.json. DO NOT EDIT.
+"""Agent protocol types for Python bots."""
+
+from __future__ import annotations
+
+from typing import Literal, NotRequired, TypedDict
+
+
+class DecisionRequest(TypedDict):
+    action_deadline_ms: int
+    analytics: NotRequired[ViewAnalytics]
+    bets: NotRequired[dict[str, int]]
+    board: list[str]
+    hand_id: str
+    hole_cards: list[str]
+    legal_actions: list[Literal["fold", "check", "call", "bet", "raise", "all_in"]]
+    min_raise_to: NotRequired[int | None]
+    pot: int
+    protocol_version: Literal[1]
+    rule_flags: NotRequired[list[Literal["straddle", "run_it_twice", "bomb_pots", "antes", "insurance", "opponent_stats"]]]
+    seat: int
+    sizing_hints: NotRequired[SizingHints]
+    stacks: NotRequired[dict[str, int]]
+    table_id: str
+    time_bank_ms: NotRequired[int]
+    to_call: int
+    view: NotRequired[Literal["minimal", "standard", "verbose"]]
+
+
+class DecisionResponse(TypedDict):
+    action: Literal["fold", "check", "call", "bet", "raise", "all_in"]
+    amount: NotRequired[int]
+    telemetry: NotRequired[Telemetry]
+
+
+class SizingHints(TypedDict):
+    action: Literal["bet", "raise"]
+    all_in_only: bool
+    max: int
+    min: int
+    presets: list[SizingPreset]
+    step: int
+
+
+class SizingPreset(TypedDict):
+    amount: int
+    label: Literal["third_pot", "half_pot", "pot", "all_in"]
+
+
+class Telemetry(TypedDict):
+    equity: NotRequired[float]
+    tags: NotRequired[list[str]]
+
+
+class ViewAnalytics(TypedDict):
+    button_seat: int
+    effective_stack: int
+    hand_category: NotRequired[str]
+    players_in_hand: int
+    pot_odds: float
+    stack_to_pot: float
+    street: Literal["preflop", "flop", "turn", "river"]
+    street_bets: int
@@ -0,0 +1,58 @@
+// Code generated by protocolgen from schemas/agent-protocol-v1.json. DO NOT EDIT.
+
+export interface DecisionRequest {
+  action_deadline_ms: number;
+  analytics?: ViewAnalytics;
+  bets?: Record<string, number>;
+  board: string[];
+  hand_id: string;
+  hole_cards: string[];
+  legal_actions: ("fold" | "check" | "call" | "bet" | "raise" | "all_in")[];
+  min_raise_to?: number | null;
+  pot: number;
+  protocol_version: 1;
+  rule_flags?: ("straddle" | "run_it_twice" | "bomb_pots" | "antes" | "insurance" | "opponent_stats")[];
+  seat: number;
+  sizing_hints?: SizingHints;
+  stacks?: Record<string, number>;
+  table_id: string;
+  time_bank_ms?: number;
+  to_call: number;
+  view?: "minimal" | "standard" | "verbose";
+}
+
+export interface DecisionResponse {
+  action: "fold" | "check" | "call" | "bet" | "raise" | "all_in";
+  amount?: number;
+  telemetry?: Telemetry;
+}
+
+export interface SizingHints {
+  action: "bet" | "raise";
+  all_in_only: boolean;
+  max: number;
+  min: number;
+  presets: SizingPreset[];
+  step: number;
+}
+
+export interface SizingPreset {
+  amount: number;
+  label: "third_pot" | "half_pot" | "pot" | "all_in";
+}
+
+export interface Telemetry {
+  equity?: number;
+  tags?: string[];
+}
+
+export interface ViewAnalytics {
+  button_seat: number;
+  effective_stack: number;
+  hand_category?: string;
+  players_in_hand: number;
+  pot_odds: number;
+  stack_to_pot: number;
+  street: "preflop" | "flop" | "turn" | "river";
+  street_bets: number;
+}
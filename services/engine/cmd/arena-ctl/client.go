@@ -0,0 +1,220 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+	"time"
+)
+
+const (
+	defaultRequestTimeout = 10 * time.Second
+	maxResponseBodyBytes  = 32 << 20
+)
+
+var errRequestFailed = errors.New("control-plane request failed")
+
+type adminClient struct {
+	baseURL    string
+	token      string
+	httpClient *http.Client
+}
+
+type tableSummary struct {
+	ID         string    `json:"id"`
+	Name       string    `json:"name"`
+	MaxSeats   uint8     `json:"max_seats"`
+	SmallBlind uint64    `json:"small_blind"`
+	BigBlind   uint64    `json:"big_blind"`
+	Status     string    `json:"status"`
+	CreatedAt  time.Time `json:"created_at"`
+}
+
+type handSummary struct {
+	HandID string `json:"hand_id"`
+	HandNo uint64 `json:"hand_no"`
+}
+
+type createTablePayload struct {
+	Name       string  `json:"name"`
+	MaxSeats   *uint8  `json:"max_seats,omitempty"`
+	SmallBlind *uint64 `json:"small_blind,omitempty"`
+	BigBlind   *uint64 `json:"big_blind,omitempty"`
+}
+
+type joinTablePayload struct {
+	SeatNo         uint8  `json:"seat_no"`
+	AgentID        string `json:"agent_id"`
+	AgentVersionID string `json:"agent_version_id"`
+	Stack          uint64 `json:"stack"`
+	Status         string `json:"status,omitempty"`
+}
+
+type leaveTablePayload struct {
+	SeatNo uint8   `json:"seat_no"`
+	Stack  *uint64 `json:"stack,omitempty"`
+}
+
+type startTablePayload struct {
+	HandsToRun int `json:"hands_to_run"`
+}
+
+func newAdminClient(baseURL string, token string, httpClient *http.Client) adminClient {
+	if httpClient == nil {
+		httpClient = &http.Client{Timeout: defaultRequestTimeout}
+	}
+	return adminClient{
+		baseURL:    strings.TrimRight(strings.TrimSpace(baseURL), "/"),
+		token:      strings.TrimSpace(token),
+		httpClient: httpClient,
+	}
+}
+
+func (c adminClient) listTables() ([]tableSummary, error) {
+	var tables []tableSummary
+	if err := c.do(http.MethodGet, "/tables", nil, &tables); err != nil {
+		return nil, err
+	}
+	return tables, nil
+}
+
+func (c adminClient) createTable(payload createTablePayload) (tableSummary, error) {
+	var table tableSummary
+	if err := c.do(http.MethodPost, "/tables", payload, &table); err != nil {
+		return tableSummary{}, err
+	}
+	return table, nil
+}
+
+func (c adminClient) joinTable(tableID string, payload joinTablePayload) (json.RawMessage, error) {
+	var seat json.RawMessage
+	if err := c.do(http.MethodPost, "/tables/"+tableID+"/join", payload, &seat); err != nil {
+		return nil, err
+	}
+	return seat, nil
+}
+
+func (c adminClient) startTable(tableID string, payload startTablePayload) (json.RawMessage, error) {
+	var status json.RawMessage
+	if err := c.do(http.MethodPost, "/tables/"+tableID+"/start", payload, &status); err != nil {
+		return nil, err
+	}
+	return status, nil
+}
+
+func (c adminClient) stopTable(tableID string) (json.RawMessage, error) {
+	var status json.RawMessage
+	if err := c.do(http.MethodPost, "/tables/"+tableID+"/stop", nil, &status); err != nil {
+		return nil, err
+	}
+	return status, nil
+}
+
+func (c adminClient) pauseTable(tableID string) (json.RawMessage, error) {
+	var status json.RawMessage
+	if err := c.do(http.MethodPost, "/tables/"+tableID+"/pause", nil, &status); err != nil {
+		return nil, err
+	}
+	return status, nil
+}
+
+func (c adminClient) resumeTable(tableID string) (json.RawMessage, error) {
+	var status json.RawMessage
+	if err := c.do(http.MethodPost, "/tables/"+tableID+"/resume", nil, &status); err != nil {
+		return nil, err
+	}
+	return status, nil
+}
+
+func (c adminClient) leaveTable(tableID string, payload leaveTablePayload) (json.RawMessage, error) {
+	var left json.RawMessage
+	if err := c.do(http.MethodPost, "/tables/"+tableID+"/leave", payload, &left); err != nil {
+		return nil, err
+	}
+	return left, nil
+}
+
+func (c adminClient) createTournamentTemplate(template any) (json.RawMessage, error) {
+	var created json.RawMessage
+	if err := c.do(http.MethodPost, "/tournaments/templates", template, &created); err != nil {
+		return nil, err
+	}
+	return created, nil
+}
+
+func (c adminClient) tableStatus(tableID string) (json.RawMessage, error) {
+	var status json.RawMessage
+	if err := c.do(http.MethodGet, "/tables/"+tableID+"/status", nil, &status); err != nil {
+		return nil, err
+	}
+	return status, nil
+}
+
+func (c adminClient) listHands(tableID string) ([]handSummary, error) {
+	var hands []handSummary
+	if err := c.do(http.MethodGet, "/tables/"+tableID+"/hands", nil, &hands); err != nil {
+		return nil, err
+	}
+	return hands, nil
+}
+
+func (c adminClient) handReplay(handID string) (json.RawMessage, error) {
+	var replay json.RawMessage
+	if err := c.do(http.MethodGet, "/hands/"+handID+"/replay", nil, &replay); err != nil {
+		return nil, err
+	}
+	return replay, nil
+}
+
+func (c adminClient) do(method string, path string, body any, out any) error {
+	var reader io.Reader
+	if body != nil {
+		payload, err := json.Marshal(body)
+		if err != nil {
+			return fmt.Errorf("marshal request: %w", err)
+		}
+		reader = bytes.NewReader(payload)
+	}
+
+	req, err := http.NewRequest(method, c.baseURL+path, reader)
+	if err != nil {
+		return fmt.Errorf("build request: %w", err)
+	}
+	if body != nil {
+		req.Header.Set("Content-Type", "application/json")
+	}
+	if c.token != "" {
+		req.Header.Set("Authorization", "Bearer "+c.token)
+	}
+
+	resp, err := c.httpClient.Do(req)
+	if err != nil {
+		return fmt.Errorf("%w: %s %s: %v", errRequestFailed, method, path, err)
+	}
+	defer resp.Body.Close()
+
+	raw, err := io.ReadAll(io.LimitReader(resp.Body, maxResponseBodyBytes))
+	if err != nil {
+		return fmt.Errorf("%w: read response: %v", errRequestFailed, err)
+	}
+	if resp.StatusCode < 200 || resp.StatusCode >= 300 {
+		var apiErr struct {
+			Error string `json:"error"`
+		}
+		if json.Unmarshal(raw, &apiErr) == nil && apiErr.Error != "" {
+			return fmt.Errorf("%w: %s %s returned %d: %s", errRequestFailed, method, path, resp.StatusCode, apiErr.Error)
+		}
+		return fmt.Errorf("%w: %s %s returned %d", errRequestFailed, method, path, resp.StatusCode)
+	}
+	if out == nil {
+		return nil
+	}
+	if err := json.Unmarshal(raw, out); err != nil {
+		return fmt.Errorf("%w: decode response: %v", errRequestFailed, err)
+	}
+	return nil
+}
@@ -0,0 +1,509 @@
+package main
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+	"text/tabwriter"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/agentclient"
+	"github.com/imaddar/poker-arena/services/engine/internal/anonymize"
+	"github.com/imaddar/poker-arena/services/engine/internal/manifest"
+	"github.com/imaddar/poker-arena/services/engine/internal/tournament"
+)
+
+const defaultServerURL = "http://127.0.0.1:8080"
+
+var errUsage = errors.New("usage error")
+
+type command struct {
+	name    string
+	summary string
+	run     func(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error
+}
+
+type exportBundle struct {
+	TableID    string            `json:"table_id"`
+	ExportedAt time.Time         `json:"exported_at"`
+	Hands      []json.RawMessage `json:"hands"`
+}
+
+func main() {
+	os.Exit(run(os.Args[1:], os.Stdout, os.Stderr, os.Getenv))
+}
+
+func run(args []string, stdout io.Writer, stderr io.Writer, getenv func(string) string) int {
+	global := flag.NewFlagSet("arena-ctl", flag.ContinueOnError)
+	global.SetOutput(stderr)
+	serverURL := global.String("server", envOrDefault(getenv, "ARENA_CTL_SERVER", defaultServerURL), "control-plane base URL (ARENA_CTL_SERVER)")
+	token := global.String("token", getenv("ARENA_CTL_TOKEN"), "admin bearer token (ARENA_CTL_TOKEN)")
+	global.Usage = func() { printUsage(stderr, global) }
+	if err := global.Parse(args); err != nil {
+		if errors.Is(err, flag.ErrHelp) {
+			return 0
+		}
+		return 2
+	}
+
+	rest := global.Args()
+	if len(rest) == 0 {
+		printUsage(stderr, global)
+		return 2
+	}
+
+	cmd, ok := findCommand(rest[0])
+	if !ok {
+		fmt.Fprintf(stderr, "unknown command %q\n", rest[0])
+		printUsage(stderr, global)
+		return 2
+	}
+
+	client := newAdminClient(*serverURL, *token, nil)
+	if err := cmd.run(client, rest[1:], stdout, stderr); err != nil {
+		if errors.Is(err, flag.ErrHelp) {
+			return 0
+		}
+		fmt.Fprintf(stderr, "arena-ctl %s: %v\n", cmd.name, err)
+		if errors.Is(err, errUsage) {
+			return 2
+		}
+		return 1
+	}
+	return 0
+}
+
+func commands() []command {
+	return []command{
+		{name: "tables", summary: "list tables", run: runTables},
+		{name: "create-table", summary: "create a table", run: runCreateTable},
+		{name: "join", summary: "seat an agent version at a table", run: runJoin},
+		{name: "start", summary: "start a table run", run: runStart},
+		{name: "stop", summary: "stop a running table", run: runStop},
+		{name: "pause", summary: "hold a running table before its next deal", run: runPause},
+		{name: "resume", summary: "deal on at a paused table", run: runResume},
+		{name: "kick", summary: "remove a seat from a table that is not running", run: runKick},
+		{name: "create-tournament", summary: "schedule a recurring tournament from a template file", run: runCreateTournament},
+		{name: "status", summary: "show table run status", run: runStatus},
+		{name: "export", summary: "export hand replays for a table as JSON", run: runExport},
+		{name: "run-manifest", summary: "play a competition manifest locally against its bot endpoints", run: runManifest},
+	}
+}
+
+func findCommand(name string) (command, bool) {
+	for _, cmd := range commands() {
+		if cmd.name == name {
+			return cmd, true
+		}
+	}
+	return command{}, false
+}
+
+func printUsage(w io.Writer, global *flag.FlagSet) {
+	fmt.Fprintln(w, "usage: arena-ctl [-server URL] [-token TOKEN] <command> [flags]")
+	fmt.Fprintln(w, "")
+	fmt.Fprintln(w, "commands:")
+	for _, cmd := range commands() {
+		fmt.Fprintf(w, "  %-17s %s\n", cmd.name, cmd.summary)
+	}
+	fmt.Fprintln(w, "")
+	fmt.Fprintln(w, "global flags:")
+	global.PrintDefaults()
+}
+
+func runTables(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("tables", stderr)
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+
+	tables, err := client.listTables()
+	if err != nil {
+		return err
+	}
+
+	tw := tabwriter.NewWriter(stdout, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(tw, "ID\tNAME\tSEATS\tBLINDS\tSTATUS")
+	for _, table := range tables {
+		fmt.Fprintf(tw, "%s\t%s\t%d\t%d/%d\t%s\n", table.ID, table.Name, table.MaxSeats, table.SmallBlind, table.BigBlind, table.Status)
+	}
+	return tw.Flush()
+}
+
+func runCreateTable(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("create-table", stderr)
+	name := fs.String("name", "", "table name (required)")
+	maxSeats := fs.Uint("max-seats", 0, "maximum seats (0 uses the server default)")
+	smallBlind := fs.Uint("small-blind", 0, "small blind (0 uses the server default)")
+	bigBlind := fs.Uint("big-blind", 0, "big blind (0 uses the server default)")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if strings.TrimSpace(*name) == "" {
+		return fmt.Errorf("%w: -name is required", errUsage)
+	}
+	if *maxSeats > 255 {
+		return fmt.Errorf("%w: -max-seats must be <= 255", errUsage)
+	}
+
+	payload := createTablePayload{Name: *name}
+	if *maxSeats > 0 {
+		seats := uint8(*maxSeats)
+		payload.MaxSeats = &seats
+	}
+	if *smallBlind > 0 {
+		sb := uint64(*smallBlind)
+		payload.SmallBlind = &sb
+	}
+	if *bigBlind > 0 {
+		bb := uint64(*bigBlind)
+		payload.BigBlind = &bb
+	}
+
+	table, err := client.createTable(payload)
+	if err != nil {
+		return err
+	}
+	fmt.Fprintln(stdout, table.ID)
+	return nil
+}
+
+func runJoin(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("join", stderr)
+	tableID := fs.String("table", "", "table id (required)")
+	seatNo := fs.Uint("seat", 0, "seat number (required)")
+	agentID := fs.String("agent", "", "agent id (required)")
+	versionID := fs.String("version", "", "agent version id (required)")
+	stack := fs.Uint("stack", 0, "starting stack (required)")
+	status := fs.String("status", "", "seat status (defaults to active)")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if err := requireTableID(*tableID); err != nil {
+		return err
+	}
+	if *seatNo == 0 || *seatNo > 255 {
+		return fmt.Errorf("%w: -seat must be between 1 and 255", errUsage)
+	}
+	if strings.TrimSpace(*agentID) == "" || strings.TrimSpace(*versionID) == "" {
+		return fmt.Errorf("%w: -agent and -version are required", errUsage)
+	}
+	if *stack == 0 {
+		return fmt.Errorf("%w: -stack must be > 0", errUsage)
+	}
+
+	seat, err := client.joinTable(*tableID, joinTablePayload{
+		SeatNo:         uint8(*seatNo),
+		AgentID:        *agentID,
+		AgentVersionID: *versionID,
+		Stack:          uint64(*stack),
+		Status:         *status,
+	})
+	if err != nil {
+		return err
+	}
+	return writeIndentedJSON(stdout, seat)
+}
+
+func runStart(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("start", stderr)
+	tableID := fs.String("table", "", "table id (required)")
+	hands := fs.Int("hands", 0, "number of hands to run (required)")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if err := requireTableID(*tableID); err != nil {
+		return err
+	}
+	if *hands <= 0 {
+		return fmt.Errorf("%w: -hands must be > 0", errUsage)
+	}
+
+	status, err := client.startTable(*tableID, startTablePayload{HandsToRun: *hands})
+	if err != nil {
+		return err
+	}
+	return writeIndentedJSON(stdout, status)
+}
+
+func runStop(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("stop", stderr)
+	tableID := fs.String("table", "", "table id (required)")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if err := requireTableID(*tableID); err != nil {
+		return err
+	}
+
+	status, err := client.stopTable(*tableID)
+	if err != nil {
+		return err
+	}
+	return writeIndentedJSON(stdout, status)
+}
+
+func runPause(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	return runGate("pause", client.pauseTable, args, stdout, stderr)
+}
+
+func runResume(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	return runGate("resume", client.resumeTable, args, stdout, stderr)
+}
+
+func runGate(name string, send func(tableID string) (json.RawMessage, error), args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet(name, stderr)
+	tableID := fs.String("table", "", "table id (required)")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if err := requireTableID(*tableID); err != nil {
+		return err
+	}
+
+	status, err := send(*tableID)
+	if err != nil {
+		return err
+	}
+	return writeIndentedJSON(stdout, status)
+}
+
+func runKick(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("kick", stderr)
+	tableID := fs.String("table", "", "table id (required)")
+	seatNo := fs.Uint("seat", 0, "seat number (required)")
+	stack := fs.Int64("stack", -1, "stack to record the player leaving with (defaults to the seat's stack)")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if err := requireTableID(*tableID); err != nil {
+		return err
+	}
+	if *seatNo == 0 || *seatNo > 255 {
+		return fmt.Errorf("%w: -seat must be between 1 and 255", errUsage)
+	}
+
+	payload := leaveTablePayload{SeatNo: uint8(*seatNo)}
+	if *stack >= 0 {
+		chips := uint64(*stack)
+		payload.Stack = &chips
+	}
+	left, err := client.leaveTable(*tableID, payload)
+	if err != nil {
+		return err
+	}
+	return writeIndentedJSON(stdout, left)
+}
+
+func runCreateTournament(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("create-tournament", stderr)
+	path := fs.String("file", "", "tournament template file (required)")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if strings.TrimSpace(*path) == "" {
+		return fmt.Errorf("%w: -file is required", errUsage)
+	}
+
+	raw, err := os.ReadFile(*path)
+	if err != nil {
+		return fmt.Errorf("read template: %w", err)
+	}
+	decoder := json.NewDecoder(bytes.NewReader(raw))
+	decoder.DisallowUnknownFields()
+	var template tournament.Template
+	if err := decoder.Decode(&template); err != nil {
+		return fmt.Errorf("parse template: %w", err)
+	}
+	if err := template.Validate(); err != nil {
+		return err
+	}
+
+	created, err := client.createTournamentTemplate(template)
+	if err != nil {
+		return err
+	}
+	return writeIndentedJSON(stdout, created)
+}
+
+func runStatus(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("status", stderr)
+	tableID := fs.String("table", "", "table id (required)")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if err := requireTableID(*tableID); err != nil {
+		return err
+	}
+
+	status, err := client.tableStatus(*tableID)
+	if err != nil {
+		return err
+	}
+	return writeIndentedJSON(stdout, status)
+}
+
+func runExport(client adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("export", stderr)
+	tableID := fs.String("table", "", "table id (required)")
+	outPath := fs.String("out", "", "output file (defaults to stdout)")
+	salt := fs.String("anonymize-salt", "", "pseudonymize ids, coarsen timestamps and strip telemetry using this salt")
+	timeBucket := fs.Duration("time-bucket", anonymize.DefaultTimeBucket, "timestamp granularity when anonymizing (0 drops timestamps)")
+	keepTelemetry := fs.Bool("keep-telemetry", false, "keep bot telemetry when anonymizing")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if err := requireTableID(*tableID); err != nil {
+		return err
+	}
+	var anonymizer *anonymize.Anonymizer
+	if *salt != "" {
+		policy := anonymize.DefaultPolicy(*salt)
+		policy.TimeBucket = *timeBucket
+		policy.KeepTelemetry = *keepTelemetry
+		created, err := anonymize.New(policy)
+		if err != nil {
+			return fmt.Errorf("%w: %v", errUsage, err)
+		}
+		anonymizer = created
+	}
+
+	hands, err := client.listHands(*tableID)
+	if err != nil {
+		return err
+	}
+
+	bundle := exportBundle{
+		TableID:    *tableID,
+		ExportedAt: time.Now().UTC(),
+		Hands:      make([]json.RawMessage, 0, len(hands)),
+	}
+	for _, hand := range hands {
+		replay, err := client.handReplay(hand.HandID)
+		if err != nil {
+			return fmt.Errorf("export hand %s: %w", hand.HandID, err)
+		}
+		bundle.Hands = append(bundle.Hands, replay)
+	}
+
+	payload, err := json.MarshalIndent(bundle, "", "  ")
+	if err != nil {
+		return fmt.Errorf("marshal export: %w", err)
+	}
+	if anonymizer != nil {
+		anonymized, err := anonymizer.Document(payload)
+		if err != nil {
+			return fmt.Errorf("anonymize export: %w", err)
+		}
+		var indented bytes.Buffer
+		if err := json.Indent(&indented, anonymized, "", "  "); err != nil {
+			return fmt.Errorf("marshal export: %w", err)
+		}
+		payload = indented.Bytes()
+	}
+	payload = append(payload, '\n')
+
+	if *outPath == "" {
+		_, err := stdout.Write(payload)
+		return err
+	}
+	if err := os.WriteFile(*outPath, payload, 0o644); err != nil {
+		return fmt.Errorf("write export: %w", err)
+	}
+	fmt.Fprintf(stdout, "exported %d hands to %s\n", len(bundle.Hands), *outPath)
+	return nil
+}
+
+func runManifest(_ adminClient, args []string, stdout io.Writer, stderr io.Writer) error {
+	fs := newCommandFlagSet("run-manifest", stderr)
+	path := fs.String("file", "", "manifest file (required)")
+	outPath := fs.String("out", "", "output file (defaults to stdout)")
+	timeout := fs.Duration("http-timeout", 5*time.Second, "bot HTTP client timeout")
+	if err := parseCommandFlags(fs, args); err != nil {
+		return err
+	}
+	if strings.TrimSpace(*path) == "" {
+		return fmt.Errorf("%w: -file is required", errUsage)
+	}
+
+	raw, err := os.ReadFile(*path)
+	if err != nil {
+		return fmt.Errorf("read manifest: %w", err)
+	}
+	m, err := manifest.Parse(raw)
+	if err != nil {
+		return err
+	}
+	result, err := manifest.RunFromManifest(context.Background(), m, manifest.NewHTTPProviderFactory(agentclient.New(*timeout)))
+	if err != nil {
+		return err
+	}
+
+	payload, err := json.MarshalIndent(result, "", "  ")
+	if err != nil {
+		return fmt.Errorf("marshal result: %w", err)
+	}
+	payload = append(payload, '\n')
+
+	if *outPath == "" {
+		_, err := stdout.Write(payload)
+		return err
+	}
+	if err := os.WriteFile(*outPath, payload, 0o644); err != nil {
+		return fmt.Errorf("write result: %w", err)
+	}
+	fmt.Fprintf(stdout, "played %d matches, results in %s\n", len(result.Matches), *outPath)
+	return nil
+}
+
+func newCommandFlagSet(name string, stderr io.Writer) *flag.FlagSet {
+	fs := flag.NewFlagSet("arena-ctl "+name, flag.ContinueOnError)
+	fs.SetOutput(stderr)
+	return fs
+}
+
+func parseCommandFlags(fs *flag.FlagSet, args []string) error {
+	if err := fs.Parse(args); err != nil {
+		if errors.Is(err, flag.ErrHelp) {
+			return err
+		}
+		return fmt.Errorf("%w: %v", errUsage, err)
+	}
+	if fs.NArg() > 0 {
+		return fmt.Errorf("%w: unexpected arguments: %s", errUsage, strings.Join(fs.Args(), " "))
+	}
+	return nil
+}
+
+func requireTableID(tableID string) error {
+	if strings.TrimSpace(tableID) == "" {
+		return fmt.Errorf("%w: -table is required", errUsage)
+	}
+	return nil
+}
+
+func writeIndentedJSON(w io.Writer, raw json.RawMessage) error {
+	var value any
+	if err := json.Unmarshal(raw, &value); err != nil {
+		return fmt.Errorf("decode response: %w", err)
+	}
+	payload, err := json.MarshalIndent(value, "", "  ")
+	if err != nil {
+		return fmt.Errorf("encode response: %w", err)
+	}
+	_, err = fmt.Fprintln(w, string(payload))
+	return err
+}
+
+func envOrDefault(getenv func(string) string, key string, fallback string) string {
+	if value := strings.TrimSpace(getenv(key)); value != "" {
+		return value
+	}
+	return fallback
+}
@@ -0,0 +1,325 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"strings"
+	"sync"
+	"testing"
+)
+
+type recordedRequest struct {
+	Method        string
+	Path          string
+	Authorization string
+	Body          string
+}
+
+type fakeControlPlane struct {
+	mu       sync.Mutex
+	requests []recordedRequest
+}
+
+func (f *fakeControlPlane) handler(t *testing.T) http.Handler {
+	t.Helper()
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		body, _ := io.ReadAll(r.Body)
+		f.mu.Lock()
+		f.requests = append(f.requests, recordedRequest{
+			Method:        r.Method,
+			Path:          r.URL.Path,
+			Authorization: r.Header.Get("Authorization"),
+			Body:          string(body),
+		})
+		f.mu.Unlock()
+
+		w.Header().Set("Content-Type", "application/json")
+		switch {
+		case r.Method == http.MethodGet && r.URL.Path == "/tables":
+			_, _ = w.Write([]byte(`[{"id":"table_1","name":"main","max_seats":6,"small_blind":50,"big_blind":100,"status":"waiting"}]`))
+		case r.Method == http.MethodPost && r.URL.Path == "/tables":
+			w.WriteHeader(http.StatusCreated)
+			_, _ = w.Write([]byte(`{"id":"table_2","name":"new","max_seats":2,"small_blind":50,"big_blind":100,"status":"waiting"}`))
+		case r.Method == http.MethodGet && r.URL.Path == "/tables/table_1/status":
+			_, _ = w.Write([]byte(`{"table_id":"table_1","status":"running","hands_completed":3}`))
+		case r.Method == http.MethodPost && r.URL.Path == "/tables/table_1/pause":
+			_, _ = w.Write([]byte(`{"table_id":"table_1","status":"paused"}`))
+		case r.Method == http.MethodPost && r.URL.Path == "/tables/table_1/leave":
+			_, _ = w.Write([]byte(`{"table_id":"table_1","seat_no":3,"stack":0}`))
+		case r.Method == http.MethodPost && r.URL.Path == "/tournaments/templates":
+			_, _ = w.Write(body)
+		case r.Method == http.MethodGet && r.URL.Path == "/tables/table_1/hands":
+			_, _ = w.Write([]byte(`[{"hand_id":"hand_1","hand_no":1},{"hand_id":"hand_2","hand_no":2}]`))
+		case r.Method == http.MethodGet && strings.HasPrefix(r.URL.Path, "/hands/") && strings.HasSuffix(r.URL.Path, "/replay"):
+			handID := strings.TrimSuffix(strings.TrimPrefix(r.URL.Path, "/hands/"), "/replay")
+			_, _ = w.Write([]byte(`{"hand_id":"` + handID + `"}`))
+		default:
+			w.WriteHeader(http.StatusNotFound)
+			_, _ = w.Write([]byte(`{"error":"table not found"}`))
+		}
+	})
+}
+
+func (f *fakeControlPlane) recorded() []recordedRequest {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	return append([]recordedRequest(nil), f.requests...)
+}
+
+func runCLI(t *testing.T, serverURL string, args ...string) (int, string, string) {
+	t.Helper()
+	var stdout bytes.Buffer
+	var stderr bytes.Buffer
+	env := map[string]string{
+		"ARENA_CTL_SERVER": serverURL,
+		"ARENA_CTL_TOKEN":  "admin-token",
+	}
+	code := run(args, &stdout, &stderr, func(key string) string { return env[key] })
+	return code, stdout.String(), stderr.String()
+}
+
+func TestRunTablesListsTablesWithAdminToken(t *testing.T) {
+	t.Parallel()
+
+	fake := &fakeControlPlane{}
+	srv := httptest.NewServer(fake.handler(t))
+	defer srv.Close()
+
+	code, stdout, stderr := runCLI(t, srv.URL, "tables")
+	if code != 0 {
+		t.Fatalf("expected exit 0, got %d (stderr=%q)", code, stderr)
+	}
+	if !strings.Contains(stdout, "table_1") || !strings.Contains(stdout, "50/100") {
+		t.Fatalf("expected table listing, got %q", stdout)
+	}
+	requests := fake.recorded()
+	if len(requests) != 1 || requests[0].Authorization != "Bearer admin-token" {
+		t.Fatalf("expected one authorized request, got %+v", requests)
+	}
+}
+
+func TestRunCreateTableSendsPayload(t *testing.T) {
+	t.Parallel()
+
+	fake := &fakeControlPlane{}
+	srv := httptest.NewServer(fake.handler(t))
+	defer srv.Close()
+
+	code, stdout, stderr := runCLI(t, srv.URL, "create-table", "-name", "new", "-max-seats", "2")
+	if code != 0 {
+		t.Fatalf("expected exit 0, got %d (stderr=%q)", code, stderr)
+	}
+	if strings.TrimSpace(stdout) != "table_2" {
+		t.Fatalf("expected created table id, got %q", stdout)
+	}
+
+	requests := fake.recorded()
+	if len(requests) != 1 {
+		t.Fatalf("expected one request, got %d", len(requests))
+	}
+	var payload map[string]any
+	if err := json.Unmarshal([]byte(requests[0].Body), &payload); err != nil {
+		t.Fatalf("decode payload failed: %v", err)
+	}
+	if payload["name"] != "new" || payload["max_seats"] != float64(2) {
+		t.Fatalf("unexpected payload: %v", payload)
+	}
+	if _, ok := payload["small_blind"]; ok {
+		t.Fatalf("expected unset blinds to be omitted, got %v", payload)
+	}
+}
+
+func TestRunFlagOverridesEnvironment(t *testing.T) {
+	t.Parallel()
+
+	fake := &fakeControlPlane{}
+	srv := httptest.NewServer(fake.handler(t))
+	defer srv.Close()
+
+	code, _, stderr := runCLI(t, "http://127.0.0.1:1", "-server", srv.URL, "-token", "other", "status", "-table", "table_1")
+	if code != 0 {
+		t.Fatalf("expected exit 0, got %d (stderr=%q)", code, stderr)
+	}
+	requests := fake.recorded()
+	if len(requests) != 1 || requests[0].Authorization != "Bearer other" {
+		t.Fatalf("expected flag token to win, got %+v", requests)
+	}
+}
+
+func TestRunReportsServerErrors(t *testing.T) {
+	t.Parallel()
+
+	fake := &fakeControlPlane{}
+	srv := httptest.NewServer(fake.handler(t))
+	defer srv.Close()
+
+	code, _, stderr := runCLI(t, srv.URL, "stop", "-table", "missing")
+	if code != 1 {
+		t.Fatalf("expected exit 1, got %d", code)
+	}
+	if !strings.Contains(stderr, "404") || !strings.Contains(stderr, "table not found") {
+		t.Fatalf("expected server error in stderr, got %q", stderr)
+	}
+}
+
+func TestRunRejectsMissingRequiredFlags(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name string
+		args []string
+	}{
+		{name: "no command", args: nil},
+		{name: "unknown command", args: []string{"teleport"}},
+		{name: "start without table", args: []string{"start", "-hands", "10"}},
+		{name: "start without hands", args: []string{"start", "-table", "table_1"}},
+		{name: "join without agent", args: []string{"join", "-table", "table_1", "-seat", "1", "-stack", "100"}},
+		{name: "create without name", args: []string{"create-table"}},
+		{name: "run-manifest without file", args: []string{"run-manifest"}},
+		{name: "kick without seat", args: []string{"kick", "-table", "table_1"}},
+		{name: "pause without table", args: []string{"pause"}},
+		{name: "create-tournament without file", args: []string{"create-tournament"}},
+	}
+
+	for _, tc := range tests {
+		tc := tc
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			code, _, _ := runCLI(t, "http://127.0.0.1:1", tc.args...)
+			if code != 2 {
+				t.Fatalf("expected exit 2, got %d", code)
+			}
+		})
+	}
+}
+
+func TestRunOperatorCommandsCallTheAdminAPI(t *testing.T) {
+	t.Parallel()
+
+	fake := &fakeControlPlane{}
+	srv := httptest.NewServer(fake.handler(t))
+	defer srv.Close()
+
+	template := filepath.Join(t.TempDir(), "daily.json")
+	if err := os.WriteFile(template, []byte(`{
+		"id": "daily-turbo",
+		"name": "Daily Turbo",
+		"recurrence": "daily",
+		"start_time": "18:00",
+		"registration_opens_ms": 3600000,
+		"config": {"seats": 6, "starting_stack": 1500, "levels": [{"small_blind": 10, "big_blind": 20}], "hands_per_level": 10}
+	}`), 0o644); err != nil {
+		t.Fatalf("write template failed: %v", err)
+	}
+	for _, args := range [][]string{
+		{"pause", "-table", "table_1"},
+		{"kick", "-table", "table_1", "-seat", "3", "-stack", "0"},
+		{"create-tournament", "-file", template},
+	} {
+		if code, _, stderr := runCLI(t, srv.URL, args...); code != 0 {
+			t.Fatalf("expected exit 0 for %v, got %d (stderr=%q)", args, code, stderr)
+		}
+	}
+
+	requests := fake.recorded()
+	if len(requests) != 3 {
+		t.Fatalf("expected three requests, got %+v", requests)
+	}
+	if requests[0].Method != http.MethodPost || requests[0].Path != "/tables/table_1/pause" {
+		t.Fatalf("expected pause to post to the table, got %+v", requests[0])
+	}
+	if requests[1].Path != "/tables/table_1/leave" || requests[1].Body != `{"seat_no":3,"stack":0}` {
+		t.Fatalf("expected kick to leave seat 3 with no chips, got %+v", requests[1])
+	}
+	var created map[string]any
+	if err := json.Unmarshal([]byte(requests[2].Body), &created); err != nil {
+		t.Fatalf("decode template failed: %v", err)
+	}
+	if requests[2].Path != "/tournaments/templates" || created["id"] != "daily-turbo" {
+		t.Fatalf("expected the template posted, got %+v", requests[2])
+	}
+
+	if err := os.WriteFile(template, []byte(`{"id": "daily-turbo", "start_time": "6pm"}`), 0o644); err != nil {
+		t.Fatalf("write template failed: %v", err)
+	}
+	if code, _, stderr := runCLI(t, srv.URL, "create-tournament", "-file", template); code != 1 || !strings.Contains(stderr, "start_time") {
+		t.Fatalf("expected an invalid template rejected before sending, got %d (stderr=%q)", code, stderr)
+	}
+	if len(fake.recorded()) != 3 {
+		t.Fatalf("expected no request for an invalid template")
+	}
+}
+
+func TestRunExportWritesReplayBundle(t *testing.T) {
+	t.Parallel()
+
+	fake := &fakeControlPlane{}
+	srv := httptest.NewServer(fake.handler(t))
+	defer srv.Close()
+
+	outPath := filepath.Join(t.TempDir(), "export.json")
+	code, stdout, stderr := runCLI(t, srv.URL, "export", "-table", "table_1", "-out", outPath)
+	if code != 0 {
+		t.Fatalf("expected exit 0, got %d (stderr=%q)", code, stderr)
+	}
+	if !strings.Contains(stdout, "exported 2 hands") {
+		t.Fatalf("expected export summary, got %q", stdout)
+	}
+
+	raw, err := os.ReadFile(outPath)
+	if err != nil {
+		t.Fatalf("read export failed: %v", err)
+	}
+	var bundle struct {
+		TableID string `json:"table_id"`
+		Hands   []struct {
+			HandID string `json:"hand_id"`
+		} `json:"hands"`
+	}
+	if err := json.Unmarshal(raw, &bundle); err != nil {
+		t.Fatalf("decode export failed: %v", err)
+	}
+	if bundle.TableID != "table_1" || len(bundle.Hands) != 2 {
+		t.Fatalf("unexpected bundle: %+v", bundle)
+	}
+	if bundle.Hands[0].HandID != "hand_1" || bundle.Hands[1].HandID != "hand_2" {
+		t.Fatalf("expected replays in hand order, got %+v", bundle.Hands)
+	}
+}
+
+func TestRunExportAnonymizesWithSalt(t *testing.T) {
+	t.Parallel()
+
+	fake := &fakeControlPlane{}
+	srv := httptest.NewServer(fake.handler(t))
+	defer srv.Close()
+
+	code, stdout, stderr := runCLI(t, srv.URL, "export", "-table", "table_1", "-anonymize-salt", "s3cret")
+	if code != 0 {
+		t.Fatalf("expected exit 0, got %d (stderr=%q)", code, stderr)
+	}
+	var bundle struct {
+		TableID    string `json:"table_id"`
+		ExportedAt string `json:"exported_at"`
+		Hands      []struct {
+			HandID string `json:"hand_id"`
+		} `json:"hands"`
+	}
+	if err := json.Unmarshal([]byte(stdout), &bundle); err != nil {
+		t.Fatalf("decode export failed: %v", err)
+	}
+	if bundle.TableID == "table_1" || !strings.HasPrefix(bundle.TableID, "table_") {
+		t.Fatalf("expected pseudonymous table id, got %q", bundle.TableID)
+	}
+	if len(bundle.Hands) != 2 || bundle.Hands[0].HandID == "hand_1" || bundle.Hands[0].HandID == bundle.Hands[1].HandID {
+		t.Fatalf("expected distinct pseudonymous hand ids, got %+v", bundle.Hands)
+	}
+	if !strings.HasSuffix(bundle.ExportedAt, ":00:00Z") {
+		t.Fatalf("expected hourly export timestamp, got %q", bundle.ExportedAt)
+	}
+}
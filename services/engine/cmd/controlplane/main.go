@@ -7,11 +7,21 @@ import (
 	"flag"
 	"fmt"
 	"github.com/imaddar/poker-arena/services/engine/internal/api"
+	"github.com/imaddar/poker-arena/services/engine/internal/archive"
+	"github.com/imaddar/poker-arena/services/engine/internal/audience"
+	"github.com/imaddar/poker-arena/services/engine/internal/badbeat"
+	"github.com/imaddar/poker-arena/services/engine/internal/commentary"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
+	"github.com/imaddar/poker-arena/services/engine/internal/ids"
 	"github.com/imaddar/poker-arena/services/engine/internal/persistence"
+	"github.com/imaddar/poker-arena/services/engine/internal/sidemarket"
+	"github.com/imaddar/poker-arena/services/engine/internal/stats"
 	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+	"github.com/imaddar/poker-arena/services/engine/internal/tournament"
 	_ "github.com/lib/pq"
 	"net/http"
+	"net/url"
 	"os"
 	"strconv"
 	"strings"
@@ -41,6 +51,19 @@ func main() {
 		fmt.Fprintln(os.Stderr, "invalid token config: a token cannot be both admin and seat scoped")
 		os.Exit(1)
 	}
+	tenantAdminTokens, err := parseTenantAdminTokens(strings.TrimSpace(os.Getenv("CONTROLPLANE_TENANT_ADMIN_TOKENS")))
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "invalid CONTROLPLANE_TENANT_ADMIN_TOKENS: %v\n", err)
+		os.Exit(1)
+	}
+	for token := range tenantAdminTokens {
+		_, isAdmin := adminTokens[token]
+		_, isSeat := seatTokens[token]
+		if isAdmin || isSeat {
+			fmt.Fprintln(os.Stderr, "invalid token config: a tenant admin token cannot also be an admin or seat token")
+			os.Exit(1)
+		}
+	}
 
 	allowlistRaw := strings.TrimSpace(os.Getenv("AGENT_ENDPOINT_ALLOWLIST"))
 	if allowlistRaw == "" {
@@ -64,6 +87,16 @@ func main() {
 		httpTimeoutMS = parsed
 	}
 
+	role := strings.TrimSpace(os.Getenv("ENGINE_ROLE"))
+	if role == "" {
+		role = "primary"
+	}
+	if role != "primary" && role != "replica" {
+		fmt.Fprintf(os.Stderr, "invalid ENGINE_ROLE value %q; expected primary or replica\n", role)
+		os.Exit(1)
+	}
+	readOnly := role == "replica"
+
 	databaseURL := strings.TrimSpace(os.Getenv("DATABASE_URL"))
 	if databaseURL == "" {
 		fmt.Fprintln(os.Stderr, "missing required env DATABASE_URL")
@@ -95,9 +128,13 @@ func main() {
 		fmt.Fprintf(os.Stderr, "database ping failed: %v\n", err)
 		os.Exit(1)
 	}
-	if err := persistence.MigratePostgres(ctx, db); err != nil {
-		fmt.Fprintf(os.Stderr, "database migration failed: %v\n", err)
-		os.Exit(1)
+	// Replicas point DATABASE_URL at a streaming read replica, which rejects
+	// DDL; the primary owns migrations.
+	if !readOnly {
+		if err := persistence.MigratePostgres(ctx, db); err != nil {
+			fmt.Fprintf(os.Stderr, "database migration failed: %v\n", err)
+			os.Exit(1)
+		}
 	}
 
 	repo := persistence.NewPostgresRepository(db)
@@ -108,7 +145,115 @@ func main() {
 		AllowedCORSOrigins:    corsAllowedOrigins,
 		DefaultAgentTimeoutMS: httpTimeoutMS,
 		AgentHTTPTimeout:      time.Duration(httpTimeoutMS) * time.Millisecond,
+		ReadOnly:              readOnly,
+		TenantAdminTokens:     tenantAdminTokens,
+	}
+	if archiveDir := strings.TrimSpace(os.Getenv("ARCHIVE_DIR")); archiveDir != "" {
+		store, err := archive.NewLocalStore(archiveDir)
+		if err != nil {
+			fmt.Fprintf(os.Stderr, "failed to open archive store: %v\n", err)
+			os.Exit(1)
+		}
+		serverConfig.ArchiveStore = store
+	}
+	if raw := strings.TrimSpace(os.Getenv("RATHOLE_WINDOW")); raw != "" {
+		window, err := time.ParseDuration(raw)
+		if err != nil || window < 0 {
+			fmt.Fprintf(os.Stderr, "invalid RATHOLE_WINDOW value %q\n", raw)
+			os.Exit(1)
+		}
+		serverConfig.RatholeWindow = window
+	}
+	if raw := strings.TrimSpace(os.Getenv("TELEMETRY_EMBARGO")); raw != "" {
+		period, err := time.ParseDuration(raw)
+		if err != nil || period < 0 {
+			fmt.Fprintf(os.Stderr, "invalid TELEMETRY_EMBARGO value %q\n", raw)
+			os.Exit(1)
+		}
+		serverConfig.TelemetryEmbargo.Period = period
+	}
+	for env, period := range map[string]*time.Duration{
+		"RETENTION_HANDS":     &serverConfig.Retention.Hands,
+		"RETENTION_TELEMETRY": &serverConfig.Retention.Telemetry,
+	} {
+		raw := strings.TrimSpace(os.Getenv(env))
+		if raw == "" {
+			continue
+		}
+		parsed, err := time.ParseDuration(raw)
+		if err != nil || parsed < 0 {
+			fmt.Fprintf(os.Stderr, "invalid %s value %q\n", env, raw)
+			os.Exit(1)
+		}
+		*period = parsed
+	}
+	if strings.TrimSpace(os.Getenv("SIDE_MARKETS")) == "1" && !readOnly {
+		serverConfig.SideMarkets = sidemarket.NewBook()
+	}
+	if strings.TrimSpace(os.Getenv("BAD_BEATS")) == "1" {
+		config := badbeat.DefaultConfig()
+		if raw := strings.TrimSpace(os.Getenv("BAD_BEAT_MIN_EQUITY")); raw != "" {
+			minEquity, err := equity.ParseRatio(raw)
+			if err != nil {
+				fmt.Fprintf(os.Stderr, "invalid BAD_BEAT_MIN_EQUITY value %q\n", raw)
+				os.Exit(1)
+			}
+			config.MinEquity = minEquity
+		}
+		config.MinPot = domain.Chips(parsePositiveIntEnvOrDefault("BAD_BEAT_MIN_POT", 0))
+		detector, err := badbeat.NewDetector(config)
+		if err != nil {
+			fmt.Fprintf(os.Stderr, "invalid bad beat config: %v\n", err)
+			os.Exit(1)
+		}
+		serverConfig.BadBeats = detector
+	}
+	if strings.TrimSpace(os.Getenv("STALLING_DETECTION")) == "1" {
+		policy := stats.DefaultStallingPolicy()
+		policy.MinDecisions = parsePositiveIntEnvOrDefault("STALLING_MIN_DECISIONS", policy.MinDecisions)
+		if raw := strings.TrimSpace(os.Getenv("STALLING_PENALTY_MS")); raw != "" {
+			penalty, err := strconv.ParseUint(raw, 10, 64)
+			if err != nil {
+				fmt.Fprintf(os.Stderr, "invalid STALLING_PENALTY_MS value %q\n", raw)
+				os.Exit(1)
+			}
+			policy.PenaltyMS = penalty
+		}
+		serverConfig.Stalling = &policy
+		serverConfig.StallingLog = os.Stdout
+	}
+	if strings.TrimSpace(os.Getenv("COMMENTARY_LOG")) == "1" {
+		serverConfig.CommentaryLog = os.Stdout
+	}
+	if raw := strings.TrimSpace(os.Getenv("COMMENTARY_OVERLAY_URL")); raw != "" {
+		overlayURL, err := url.Parse(raw)
+		if err != nil || (overlayURL.Scheme != "http" && overlayURL.Scheme != "https") || overlayURL.Host == "" {
+			fmt.Fprintf(os.Stderr, "invalid COMMENTARY_OVERLAY_URL value %q\n", raw)
+			os.Exit(1)
+		}
+		serverConfig.CommentaryOverlay = commentary.NewOverlay(raw, commentary.DefaultOverlayTimeout)
 	}
+	if featuredViewers := parsePositiveIntEnvOrDefault("FEATURED_TABLE_VIEWERS", 0); featuredViewers > 0 {
+		serverConfig.Audience = audience.NewTracker(audience.Config{
+			FeaturedViewers: featuredViewers,
+			OnEvent: func(event audience.Event) {
+				fmt.Fprintf(os.Stdout, "table %s: table=%s viewers=%d\n", event.Kind, event.TableID, event.Viewers)
+			},
+		})
+	}
+	if strings.TrimSpace(os.Getenv("TOURNAMENT_SCHEDULER")) == "1" && !readOnly {
+		scheduler := tournament.NewScheduler()
+		go scheduler.Run(context.Background(), time.Minute, func(event tournament.ScheduleEvent) {
+			fmt.Fprintf(os.Stdout, "tournament %s: %s players=%d\n", event.TournamentID, event.Kind, len(event.Players))
+		})
+		serverConfig.Tournaments = scheduler
+	}
+	idGenerator, err := ids.Parse(os.Getenv("ID_FORMAT"))
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "invalid ID_FORMAT: %v\n", err)
+		os.Exit(1)
+	}
+	serverConfig.IDs = idGenerator
 
 	server := api.NewServer(
 		repo,
@@ -119,7 +264,7 @@ func main() {
 		serverConfig,
 	)
 
-	fmt.Fprintf(os.Stdout, "engine control-plane listening on %s\n", *addr)
+	fmt.Fprintf(os.Stdout, "engine control-plane (%s) listening on %s\n", role, *addr)
 	if err := http.ListenAndServe(*addr, server); err != nil {
 		fmt.Fprintf(os.Stderr, "server failed: %v\n", err)
 		os.Exit(1)
@@ -228,6 +373,36 @@ func parseSeatTokens(raw string, maxSeats uint8) (map[string]domain.SeatNo, erro
 	return tokens, nil
 }
 
+// parseTenantAdminTokens reads tenant_id:token entries. A tenant may have
+// several tokens.
+func parseTenantAdminTokens(raw string) (map[string]string, error) {
+	tokens := make(map[string]string)
+	if raw == "" {
+		return tokens, nil
+	}
+
+	for _, part := range strings.Split(raw, ",") {
+		entry := strings.TrimSpace(part)
+		if entry == "" {
+			return nil, errors.New("tenant admin token list contains an empty entry")
+		}
+		pieces := strings.SplitN(entry, ":", 2)
+		if len(pieces) != 2 {
+			return nil, fmt.Errorf("expected tenant_id:token entry, got %q", entry)
+		}
+		tenantID := strings.TrimSpace(pieces[0])
+		token := strings.TrimSpace(pieces[1])
+		if tenantID == "" || token == "" {
+			return nil, fmt.Errorf("tenant admin token entry %q needs a tenant and a token", entry)
+		}
+		if _, exists := tokens[token]; exists {
+			return nil, fmt.Errorf("duplicate token %q in tenant admin token map", token)
+		}
+		tokens[token] = tenantID
+	}
+	return tokens, nil
+}
+
 func hasTokenOverlap(adminTokens map[string]struct{}, seatTokens map[string]domain.SeatNo) bool {
 	for token := range seatTokens {
 		if _, exists := adminTokens[token]; exists {
@@ -76,6 +76,23 @@ func TestParseSeatTokens_RejectsDuplicateToken(t *testing.T) {
 	}
 }
 
+func TestParseTenantAdminTokens(t *testing.T) {
+	t.Parallel()
+
+	tokens, err := parseTenantAdminTokens("club-a:tok-a1, club-a:tok-a2,club-b:tok-b")
+	if err != nil {
+		t.Fatalf("parseTenantAdminTokens failed: %v", err)
+	}
+	if len(tokens) != 3 || tokens["tok-a2"] != "club-a" || tokens["tok-b"] != "club-b" {
+		t.Fatalf("unexpected tenant tokens %v", tokens)
+	}
+	for _, raw := range []string{"tok-a", "club-a:", ":tok", "club-a:tok,club-b:tok"} {
+		if _, err := parseTenantAdminTokens(raw); err == nil {
+			t.Fatalf("expected parseTenantAdminTokens to reject %q", raw)
+		}
+	}
+}
+
 func TestHasTokenOverlap(t *testing.T) {
 	t.Parallel()
 
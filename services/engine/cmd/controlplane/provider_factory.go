@@ -21,6 +21,7 @@ func newProviderFactory(clientTimeout time.Duration) func(tableID string, start
 
 		endpoints := make(map[domain.SeatNo]string, len(start.Seats))
 		seatTimeouts := make(map[domain.SeatNo]uint64, len(start.Seats))
+		seatViews := make(map[domain.SeatNo]domain.ViewLevel, len(start.Seats))
 		for _, seat := range start.Seats {
 			seatNo, err := domain.NewSeatNo(seat.SeatNo, maxSeats)
 			if err != nil {
@@ -36,6 +37,9 @@ func newProviderFactory(clientTimeout time.Duration) func(tableID string, start
 			if seat.AgentTimeoutMS != nil && *seat.AgentTimeoutMS > 0 {
 				seatTimeouts[seatNo] = *seat.AgentTimeoutMS
 			}
+			if seat.View != "" {
+				seatViews[seatNo] = seat.View
+			}
 		}
 
 		defaultTimeout := cfg.DefaultAgentTimeoutMS
@@ -48,6 +52,7 @@ func newProviderFactory(clientTimeout time.Duration) func(tableID string, start
 			endpointLookup: tableSeatEndpointProvider{endpoints: endpoints},
 			defaultTimeout: defaultTimeout,
 			seatTimeouts:   seatTimeouts,
+			seatViews:      seatViews,
 		}, nil
 	}
 }
@@ -57,6 +62,7 @@ type seatTimeoutProvider struct {
 	endpointLookup tableSeatEndpointProvider
 	defaultTimeout uint64
 	seatTimeouts   map[domain.SeatNo]uint64
+	seatViews      map[domain.SeatNo]domain.ViewLevel
 }
 
 func (p seatTimeoutProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
@@ -75,6 +81,7 @@ func (p seatTimeoutProvider) NextAction(ctx context.Context, state domain.HandSt
 		State:           state,
 		ActingSeat:      state.ActingSeat,
 		ActionTimeoutMS: timeout,
+		View:            p.seatViews[state.ActingSeat],
 	})
 }
 
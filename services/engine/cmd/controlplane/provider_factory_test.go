@@ -103,6 +103,47 @@ func TestNewProviderFactory_UsesPerSeatTimeoutOverride(t *testing.T) {
 	}
 }
 
+func TestNewProviderFactory_SendsEachSeatItsView(t *testing.T) {
+	t.Parallel()
+
+	var captured struct {
+		View   domain.ViewLevel        `json:"view"`
+		Stacks map[string]domain.Chips `json:"stacks"`
+	}
+	agent := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		captured.View, captured.Stacks = "", nil
+		if err := json.NewDecoder(r.Body).Decode(&captured); err != nil {
+			t.Fatalf("decode request failed: %v", err)
+		}
+		_ = json.NewEncoder(w).Encode(map[string]any{"action": "check"})
+	}))
+	defer agent.Close()
+
+	factory := newProviderFactory(2 * time.Second)
+	provider, err := factory("table-1", api.StartRequest{
+		Seats: []api.StartSeat{
+			{SeatNo: 1, Stack: 10_000, Status: domain.SeatStatusActive, AgentEndpoint: agent.URL, View: domain.ViewMinimal},
+			{SeatNo: 2, Stack: 10_000, Status: domain.SeatStatusActive, AgentEndpoint: agent.URL},
+		},
+	}, api.ServerConfig{DefaultAgentTimeoutMS: 2000})
+	if err != nil {
+		t.Fatalf("factory failed: %v", err)
+	}
+
+	if _, err := provider.NextAction(context.Background(), buildProviderTestState(t, mustSeatNo(t, 1))); err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+	if captured.View != domain.ViewMinimal || captured.Stacks != nil {
+		t.Fatalf("expected seat 1 to get a minimal view, got %+v", captured)
+	}
+	if _, err := provider.NextAction(context.Background(), buildProviderTestState(t, mustSeatNo(t, 2))); err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+	if captured.View != domain.ViewStandard || len(captured.Stacks) != 2 {
+		t.Fatalf("expected seat 2 to get the standard view, got %+v", captured)
+	}
+}
+
 func buildProviderTestState(t *testing.T, actingSeat domain.SeatNo) domain.HandState {
 	t.Helper()
 	return domain.HandState{
@@ -7,17 +7,37 @@ import (
 	"os"
 
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/soak"
+	"github.com/imaddar/poker-arena/services/engine/internal/stats"
 	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
 )
 
 func main() {
-	mode := flag.String("mode", "sim", "run mode: sim or play")
-	hands := flag.Int("hands", 0, "number of hands to run (defaults: sim=100, play=1)")
+	mode := flag.String("mode", "sim", "run mode: sim, play or soak")
+	hands := flag.Int("hands", 0, "number of hands to run, per table when mode=soak (defaults: sim=100, play=1, soak=100)")
 	players := flag.Int("players", 2, "number of players to seat (2..6)")
 	humanSeatRaw := flag.Int("human-seat", 1, "human-controlled seat number when mode=play")
 	outPath := flag.String("out", "", "optional path to write JSON run report")
+	maxRaises := flag.Int("max-raises", 0, "max bets and raises per street before a raise war is stopped (0 = no cap)")
+	raiseWarAllIn := flag.Bool("raise-war-all-in", false, "play a raise past -max-raises as an all-in instead of failing the run")
+	soakBots := flag.Int("bots", 12, "synthetic bots to seat when mode=soak")
+	soakTables := flag.Int("tables", 2, "tables to spread the bots across when mode=soak")
+	soakTransport := flag.String("transport", string(soak.TransportInProcess), "how soak bots answer: inproc or loopback (HTTP on 127.0.0.1)")
+	effectSize := flag.Float64("effect-size", stats.DefaultEffectSizeBBPer100, "win rate gap in bb/100 a sim run should resolve; the report says how many more hands that takes")
 	flag.Parse()
 
+	if *mode == "soak" {
+		soakConfig := soak.DefaultConfig()
+		soakConfig.Bots = *soakBots
+		soakConfig.Tables = *soakTables
+		soakConfig.Transport = soak.Transport(*soakTransport)
+		if *hands > 0 {
+			soakConfig.HandsPerTable = *hands
+		}
+		runSoak(soakConfig, *outPath)
+		return
+	}
+
 	cfg := domain.DefaultV0TableConfig()
 	seats, err := buildInitialSeats(cfg, *players)
 	if err != nil {
@@ -38,6 +58,10 @@ func main() {
 		}
 	}
 
+	runnerConfig := tablerunner.RunnerConfig{
+		MaxRaisesPerStreet: *maxRaises,
+		RaiseWarAllIn:      *raiseWarAllIn,
+	}
 	events := make([]actionEvent, 0, runHands*8)
 	provider := tablerunner.ActionProvider(recordingProvider{
 		inner: deterministicProvider{},
@@ -73,8 +97,10 @@ func main() {
 			&humanSeat,
 			buttonSeat,
 			cfg,
+			runnerConfig,
 			provider,
 			&events,
+			*effectSize,
 		)
 		return
 	}
@@ -88,8 +114,10 @@ func main() {
 		nil,
 		buttonSeat,
 		cfg,
+		runnerConfig,
 		provider,
 		&events,
+		*effectSize,
 	)
 }
 
@@ -118,15 +146,16 @@ func runWithReport(
 	humanSeat *domain.SeatNo,
 	buttonSeat domain.SeatNo,
 	cfg domain.TableConfig,
+	runnerConfig tablerunner.RunnerConfig,
 	provider tablerunner.ActionProvider,
 	events *[]actionEvent,
+	effectSize float64,
 ) {
-	livePrevious := make(map[domain.SeatNo]uint32, len(initialSeats))
+	livePrevious := make(map[domain.SeatNo]domain.Chips, len(initialSeats))
 	for _, seat := range initialSeats {
 		livePrevious[seat.SeatNo] = seat.Stack
 	}
 
-	runnerConfig := tablerunner.RunnerConfig{}
 	if mode == "play" {
 		runnerConfig.OnHandComplete = func(summary tablerunner.HandSummary) {
 			timeline := timelineForHand(*events, summary.HandNo)
@@ -157,6 +186,9 @@ func runWithReport(
 		InitialSeats:   initialSeats,
 		Result:         result,
 		Timeline:       append([]actionEvent(nil), (*events)...),
+
+		BigBlind:           cfg.BigBlind,
+		EffectSizeBBPer100: effectSize,
 	})
 
 	if mode == "play" {
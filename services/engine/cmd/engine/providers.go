@@ -54,7 +54,7 @@ func (p humanProvider) NextAction(ctx context.Context, state domain.HandState) (
 			return domain.Action{}, err
 		}
 
-		toCall := uint32(0)
+		toCall := domain.Chips(0)
 		for i := range state.Seats {
 			if state.Seats[i].SeatNo == state.ActingSeat {
 				if state.CurrentBet > state.Seats[i].CommittedInRound {
@@ -133,7 +133,7 @@ func (p humanProvider) NextAction(ctx context.Context, state domain.HandState) (
 	}
 }
 
-func buildPromptOptions(hasOpenBet bool, toCall uint32) string {
+func buildPromptOptions(hasOpenBet bool, toCall domain.Chips) string {
 	if !hasOpenBet {
 		return "fold(f)/check(k)/bet(b) <amt>"
 	}
@@ -143,7 +143,7 @@ func buildPromptOptions(hasOpenBet bool, toCall uint32) string {
 	return "fold(f)/check(k)/raise(r) <amt>"
 }
 
-func isActionAllowedForPrompt(kind domain.ActionKind, hasOpenBet bool, toCall uint32) bool {
+func isActionAllowedForPrompt(kind domain.ActionKind, hasOpenBet bool, toCall domain.Chips) bool {
 	if !hasOpenBet {
 		return kind == domain.ActionFold || kind == domain.ActionCheck || kind == domain.ActionBet
 	}
@@ -180,11 +180,11 @@ func parseHumanAction(input string) (domain.Action, error) {
 		if len(parts) != 2 {
 			return domain.Action{}, fmt.Errorf("%w: %s requires an amount", errUnsupportedAction, parts[0])
 		}
-		parsed, err := strconv.ParseUint(parts[1], 10, 32)
+		parsed, err := strconv.ParseUint(parts[1], 10, 64)
 		if err != nil || parsed == 0 {
 			return domain.Action{}, fmt.Errorf("%w: invalid amount %q", errUnsupportedAction, parts[1])
 		}
-		amount := uint32(parsed)
+		amount := domain.Chips(parsed)
 		if parts[0] == "bet" || parts[0] == "b" {
 			return domain.NewAction(domain.ActionBet, &amount)
 		}
@@ -207,7 +207,7 @@ func validateHumanAction(state domain.HandState, action domain.Action) error {
 	}
 
 	acting := state.Seats[actingIdx]
-	toCall := uint32(0)
+	toCall := domain.Chips(0)
 	if state.CurrentBet > acting.CommittedInRound {
 		toCall = state.CurrentBet - acting.CommittedInRound
 	}
@@ -337,7 +337,7 @@ func (p recordingProvider) NextAction(ctx context.Context, state domain.HandStat
 
 const tablePromptWidth = 58
 
-func renderMiniPokerTable(state domain.HandState, toCall uint32, options string) string {
+func renderMiniPokerTable(state domain.HandState, toCall domain.Chips, options string) string {
 	positionBySeat := buildPositionBySeat(state)
 
 	lines := []string{
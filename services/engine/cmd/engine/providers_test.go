@@ -532,7 +532,7 @@ type scriptActionProvider struct {
 	action domain.Action
 }
 
-func newScriptActionProvider(t *testing.T, kind domain.ActionKind, amount *uint32) scriptActionProvider {
+func newScriptActionProvider(t *testing.T, kind domain.ActionKind, amount *domain.Chips) scriptActionProvider {
 	t.Helper()
 	action, err := domain.NewAction(kind, amount)
 	if err != nil {
@@ -9,6 +9,7 @@ import (
 
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
 	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/stats"
 	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
 )
 
@@ -17,7 +18,7 @@ type actionEvent struct {
 	Street domain.Street
 	Seat   domain.SeatNo
 	Action domain.ActionKind
-	Amount *uint32
+	Amount *domain.Chips
 }
 
 type buildRunReportInput struct {
@@ -28,6 +29,10 @@ type buildRunReportInput struct {
 	InitialSeats   []domain.SeatState
 	Result         tablerunner.RunTableResult
 	Timeline       []actionEvent
+	// BigBlind and EffectSizeBBPer100 scale the significance report of a
+	// sim run.
+	BigBlind           domain.Chips
+	EffectSizeBBPer100 float64
 }
 
 type runReport struct {
@@ -42,18 +47,25 @@ type runReport struct {
 	FinalSeats     []runReportSeat `json:"final_seats"`
 	Hands          []runReportHand `json:"hands"`
 	HumanSeat      *domain.SeatNo  `json:"human_seat,omitempty"`
+	// Significance is set for sim runs, one entry per seat.
+	Significance []runReportSignificance `json:"significance,omitempty"`
 }
 
 type runReportSeat struct {
 	SeatNo domain.SeatNo `json:"seat_no"`
-	Stack  uint32        `json:"stack"`
+	Stack  domain.Chips  `json:"stack"`
+}
+
+type runReportSignificance struct {
+	SeatNo domain.SeatNo `json:"seat_no"`
+	stats.Significance
 }
 
 type runReportAction struct {
 	Street domain.Street     `json:"street"`
 	Seat   domain.SeatNo     `json:"seat"`
 	Action domain.ActionKind `json:"action"`
-	Amount *uint32           `json:"amount,omitempty"`
+	Amount *domain.Chips     `json:"amount,omitempty"`
 }
 
 type runReportHand struct {
@@ -61,7 +73,7 @@ type runReportHand struct {
 	Phase           domain.HandPhase    `json:"phase"`
 	Actions         int                 `json:"actions"`
 	Fallbacks       int                 `json:"fallbacks"`
-	PotEnd          uint32              `json:"pot_end"`
+	PotEnd          domain.Chips        `json:"pot_end"`
 	Board           []string            `json:"board"`
 	ShowdownAwards  []runReportAward    `json:"showdown_awards,omitempty"`
 	ShowdownWinners []runReportShowdown `json:"showdown_winners,omitempty"`
@@ -70,14 +82,14 @@ type runReportHand struct {
 }
 
 type runReportAward struct {
-	Amount uint32          `json:"amount"`
+	Amount domain.Chips    `json:"amount"`
 	Seats  []domain.SeatNo `json:"seats"`
 	Reason string          `json:"reason"`
 }
 
 type runReportShowdown struct {
 	Seat      domain.SeatNo `json:"seat"`
-	Won       uint32        `json:"won"`
+	Won       domain.Chips  `json:"won"`
 	HoleCards []string      `json:"hole_cards"`
 	BestHand  string        `json:"best_hand"`
 	HowWon    string        `json:"how_won"`
@@ -106,10 +118,39 @@ func buildRunReport(input buildRunReportInput) runReport {
 	for _, summary := range input.Result.HandSummaries {
 		report.Hands = append(report.Hands, buildRunReportHand(summary, timelineByHand[summary.HandNo]))
 	}
+	if input.Mode == "sim" && input.BigBlind > 0 {
+		report.Significance = seatSignificance(input.InitialSeats, input.Result.HandSummaries, input.BigBlind, input.EffectSizeBBPer100)
+	}
 
 	return report
 }
 
+// seatSignificance measures each seat's win rate from how its stack moved in
+// every hand, in big blinds.
+func seatSignificance(initial []domain.SeatState, hands []tablerunner.HandSummary, bigBlind domain.Chips, effectBBPer100 float64) []runReportSignificance {
+	previous := make(map[domain.SeatNo]domain.Chips, len(initial))
+	results := make(map[domain.SeatNo][]float64, len(initial))
+	for _, seat := range initial {
+		previous[seat.SeatNo] = seat.Stack
+	}
+	for _, hand := range hands {
+		for _, seat := range hand.FinalState.Seats {
+			delta := float64(int64(seat.Stack) - int64(previous[seat.SeatNo]))
+			results[seat.SeatNo] = append(results[seat.SeatNo], delta/float64(bigBlind))
+			previous[seat.SeatNo] = seat.Stack
+		}
+	}
+
+	significance := make([]runReportSignificance, 0, len(initial))
+	for _, seat := range mapSeats(initial) {
+		significance = append(significance, runReportSignificance{
+			SeatNo:       seat.SeatNo,
+			Significance: stats.MeasureSignificance(results[seat.SeatNo], effectBBPer100),
+		})
+	}
+	return significance
+}
+
 func renderRunOutput(report runReport) string {
 	var b strings.Builder
 	w := 50
@@ -127,7 +168,7 @@ func renderRunOutput(report runReport) string {
 	b.WriteString(fmt.Sprintf("  ║  Stacks:  %-*s║\n", w-12, formatStackList(report.StartingSeats)))
 	b.WriteString("  ╚" + strings.Repeat("═", w) + "╝\n\n")
 
-	previous := make(map[domain.SeatNo]uint32)
+	previous := make(map[domain.SeatNo]domain.Chips)
 	for _, seat := range report.StartingSeats {
 		previous[seat.SeatNo] = seat.Stack
 	}
@@ -153,6 +194,16 @@ func renderRunCompletion(report runReport) string {
 	b.WriteString(fmt.Sprintf("  ║  Total Fallbacks:  %-*d║\n", w-22, report.TotalFallbacks))
 	b.WriteString(fmt.Sprintf("  ║  Final Button:     Seat %-*d║\n", w-27, report.FinalButton))
 	b.WriteString(fmt.Sprintf("  ║  Final Stacks:     %-*s║\n", w-22, formatStackList(report.FinalSeats)))
+	if len(report.Significance) > 0 {
+		b.WriteString("  ╠" + strings.Repeat("═", w) + "╣\n")
+		b.WriteString(fmt.Sprintf("  ║%-*s║\n", w, "  Win Rates (bb/100, 95% CI)"))
+		for _, seat := range report.Significance {
+			line := fmt.Sprintf("    Seat %d: %+.1f  [%+.1f, %+.1f]", seat.SeatNo, seat.BBPer100, seat.CI95LowBBPer100, seat.CI95HighBBPer100)
+			b.WriteString(fmt.Sprintf("  ║%-*s║\n", w, line))
+			line = fmt.Sprintf("      sd %.1f, %d more hands for ±%g", seat.StdDevBBPer100, seat.AdditionalHands, seat.EffectSizeBBPer100)
+			b.WriteString(fmt.Sprintf("  ║%-*s║\n", w, line))
+		}
+	}
 	b.WriteString("  ╚" + strings.Repeat("═", w) + "╝\n")
 	return b.String()
 }
@@ -245,7 +296,7 @@ func buildRunReportHand(summary tablerunner.HandSummary, timeline []runReportAct
 	}
 }
 
-func renderHandSection(hand runReportHand, previous map[domain.SeatNo]uint32) string {
+func renderHandSection(hand runReportHand, previous map[domain.SeatNo]domain.Chips) string {
 	var b strings.Builder
 	w := 56
 
@@ -329,17 +380,17 @@ func renderHandSection(hand runReportHand, previous map[domain.SeatNo]uint32) st
 }
 
 func mapShowdownWinners(state domain.HandState, awards []runReportAward) []runReportShowdown {
-	wonBySeat := map[domain.SeatNo]uint32{}
+	wonBySeat := map[domain.SeatNo]domain.Chips{}
 	reasonsBySeat := map[domain.SeatNo]map[string]struct{}{}
 	for _, award := range awards {
 		if len(award.Seats) == 0 {
 			continue
 		}
-		share := award.Amount / uint32(len(award.Seats))
-		odd := award.Amount % uint32(len(award.Seats))
+		share := award.Amount / domain.Chips(len(award.Seats))
+		odd := award.Amount % domain.Chips(len(award.Seats))
 		for i, seat := range award.Seats {
 			wonBySeat[seat] += share
-			if uint32(i) < odd {
+			if domain.Chips(i) < odd {
 				wonBySeat[seat]++
 			}
 			if _, ok := reasonsBySeat[seat]; !ok {
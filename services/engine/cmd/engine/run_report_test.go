@@ -102,6 +102,57 @@ func TestRenderRunOutputIncludesSectionsAndTimeline(t *testing.T) {
 	}
 }
 
+func TestBuildRunReportMeasuresSimWinRates(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	seat1 := mustSeatNo(t, cfg, 1)
+	seat2 := mustSeatNo(t, cfg, 2)
+	hand := func(handNo uint64, stack1, stack2 domain.Chips) tablerunner.HandSummary {
+		return tablerunner.HandSummary{HandNo: handNo, FinalState: domain.HandState{Seats: []domain.SeatState{
+			{SeatNo: seat1, Stack: stack1},
+			{SeatNo: seat2, Stack: stack2},
+		}}}
+	}
+	input := buildRunReportInput{
+		Mode:           "sim",
+		TableID:        "local-table-1",
+		HandsRequested: 2,
+		InitialSeats: []domain.SeatState{
+			{SeatNo: seat2, Stack: cfg.StartingStack},
+			{SeatNo: seat1, Stack: cfg.StartingStack},
+		},
+		Result: tablerunner.RunTableResult{
+			HandsCompleted: 2,
+			HandSummaries:  []tablerunner.HandSummary{hand(1, 9800, 10200), hand(2, 10100, 9900)},
+		},
+
+		BigBlind:           cfg.BigBlind,
+		EffectSizeBBPer100: 10,
+	}
+
+	report := buildRunReport(input)
+	if len(report.Significance) != 2 || report.Significance[0].SeatNo != seat1 {
+		t.Fatalf("expected one entry per seat in seat order, got %+v", report.Significance)
+	}
+	first, second := report.Significance[0], report.Significance[1]
+	if first.Hands != 2 || first.BBPer100 != 50 || second.BBPer100 != -50 || first.EffectSizeBBPer100 != 10 {
+		t.Fatalf("expected seat 1 up 50 bb/100 over two hands, got %+v and %+v", first, second)
+	}
+	if first.CI95LowBBPer100 >= 0 || first.CI95HighBBPer100 <= 100 || first.AdditionalHands == 0 {
+		t.Fatalf("expected two hands to leave the win rate unresolved, got %+v", first)
+	}
+	output := renderRunOutput(report)
+	if !strings.Contains(output, "Win Rates (bb/100, 95% CI)") || !strings.Contains(output, "Seat 1: +50.0") {
+		t.Fatalf("expected the win rates in the run summary, got:\n%s", output)
+	}
+
+	input.Mode = "play"
+	if report := buildRunReport(input); report.Significance != nil {
+		t.Fatalf("expected no significance report outside sim runs, got %+v", report.Significance)
+	}
+}
+
 func TestWriteRunReportJSONWritesValidSchema(t *testing.T) {
 	t.Parallel()
 
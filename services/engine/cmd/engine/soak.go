@@ -0,0 +1,52 @@
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"sort"
+	"strings"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/soak"
+)
+
+func runSoak(cfg soak.Config, outPath string) {
+	report, err := soak.Run(context.Background(), cfg)
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "soak test failed: %v\n", err)
+		os.Exit(1)
+	}
+	fmt.Print(renderSoakReport(report))
+
+	if outPath != "" {
+		payload, err := json.MarshalIndent(report, "", "  ")
+		if err == nil {
+			err = os.WriteFile(outPath, payload, 0o644)
+		}
+		if err != nil {
+			fmt.Fprintf(os.Stderr, "failed to write report json: %v\n", err)
+			os.Exit(1)
+		}
+		fmt.Printf("wrote soak report: %s\n", outPath)
+	}
+}
+
+func renderSoakReport(report soak.Report) string {
+	var b strings.Builder
+	latency := report.DecisionLatency
+	b.WriteString(fmt.Sprintf("soak: %d bots across %d tables over %s\n", report.Bots, report.Tables, report.Transport))
+	b.WriteString(fmt.Sprintf("  hands:     %d/%d in %.0fms (%.1f hands/s)\n", report.HandsCompleted, report.HandsRequested, report.ElapsedMS, report.HandsPerSecond))
+	b.WriteString(fmt.Sprintf("  decisions: %d (%.1f/s), %d fallbacks\n", report.Actions, report.ActionsPerSecond, report.Fallbacks))
+	b.WriteString(fmt.Sprintf("  latency:   mean %.3fms p50 %.3fms p95 %.3fms p99 %.3fms max %.3fms\n", latency.MeanMS, latency.P50MS, latency.P95MS, latency.P99MS, latency.MaxMS))
+
+	tableIDs := make([]string, 0, len(report.TableErrors))
+	for tableID := range report.TableErrors {
+		tableIDs = append(tableIDs, tableID)
+	}
+	sort.Strings(tableIDs)
+	for _, tableID := range tableIDs {
+		b.WriteString(fmt.Sprintf("  %s stopped: %s\n", tableID, report.TableErrors[tableID]))
+	}
+	return b.String()
+}
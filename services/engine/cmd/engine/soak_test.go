@@ -0,0 +1,29 @@
+package main
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/soak"
+)
+
+func TestRenderSoakReportListsThroughputAndStoppedTables(t *testing.T) {
+	t.Parallel()
+
+	out := renderSoakReport(soak.Report{
+		Bots:           4,
+		Tables:         2,
+		Transport:      soak.TransportLoopback,
+		HandsRequested: 20,
+		HandsCompleted: 15,
+		HandsPerSecond: 150,
+		TableErrors: map[string]string{
+			"soak-table-2": "insufficient active seats to start hand",
+		},
+	})
+	for _, want := range []string{"4 bots across 2 tables over loopback", "15/20", "150.0 hands/s", "soak-table-2 stopped"} {
+		if !strings.Contains(out, want) {
+			t.Fatalf("expected %q in soak report, got:\n%s", want, out)
+		}
+	}
+}
@@ -0,0 +1,32 @@
+//go:build js && wasm
+
+// Command enginewasm exposes the rules engine to browsers. Build it with
+//
+//	GOOS=js GOARCH=wasm go build -o engine.wasm ./cmd/enginewasm
+//
+// and load it with Go's wasm_exec.js. It registers two global functions that
+// take and return JSON strings: pokerArenaValidate({state, action}) and
+// pokerArenaReplay(replayRequest). See internal/embedapi for the payloads.
+package main
+
+import (
+	"syscall/js"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/embedapi"
+)
+
+func main() {
+	js.Global().Set("pokerArenaValidate", jsonFunc(embedapi.ValidateJSON))
+	js.Global().Set("pokerArenaReplay", jsonFunc(embedapi.ReplayJSON))
+	// Keep the Go runtime alive so the callbacks stay callable.
+	select {}
+}
+
+func jsonFunc(handle func([]byte) []byte) js.Func {
+	return js.FuncOf(func(_ js.Value, args []js.Value) any {
+		if len(args) != 1 || args[0].Type() != js.TypeString {
+			return `{"error":"expected one JSON string argument"}`
+		}
+		return string(handle([]byte(args[0].String())))
+	})
+}
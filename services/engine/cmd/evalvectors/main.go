@@ -0,0 +1,52 @@
+// Command evalvectors writes the hand evaluator's cross-language test
+// vectors: seven cards per line with the category and rank ordinal the
+// arena's evaluator gives them, so client-side or tooling evaluators can
+// check for exact agreement. Run it from services/engine after changing the
+// evaluator:
+//
+//	go run ./cmd/evalvectors
+//
+// and commit the regenerated file; a test fails while it is stale. See
+// rules.EvalVectors for how the hands are chosen.
+package main
+
+import (
+	"bytes"
+	"flag"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+const vectorsPath = "sdk/testdata/evaluator-vectors.jsonl"
+
+func main() {
+	root := flag.String("root", "../..", "repository root")
+	flag.Parse()
+	if err := run(*root); err != nil {
+		fmt.Fprintf(os.Stderr, "evalvectors: %v\n", err)
+		os.Exit(1)
+	}
+}
+
+func run(root string) error {
+	data, err := generate()
+	if err != nil {
+		return err
+	}
+	target := filepath.Join(root, vectorsPath)
+	if err := os.MkdirAll(filepath.Dir(target), 0o755); err != nil {
+		return err
+	}
+	return os.WriteFile(target, data, 0o644)
+}
+
+func generate() ([]byte, error) {
+	var b bytes.Buffer
+	if err := rules.WriteEvalVectors(&b); err != nil {
+		return nil, err
+	}
+	return b.Bytes(), nil
+}
@@ -0,0 +1,26 @@
+package main
+
+import (
+	"bytes"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+const repoRoot = "../../../.."
+
+func TestGenerate_CheckedInVectorsMatchTheEvaluator(t *testing.T) {
+	t.Parallel()
+
+	want, err := generate()
+	if err != nil {
+		t.Fatalf("generate failed: %v", err)
+	}
+	got, err := os.ReadFile(filepath.Join(repoRoot, vectorsPath))
+	if err != nil {
+		t.Fatalf("read %s: %v", vectorsPath, err)
+	}
+	if !bytes.Equal(got, want) {
+		t.Fatalf("%s is stale; run `go run ./cmd/evalvectors` from services/engine", vectorsPath)
+	}
+}
@@ -0,0 +1,363 @@
+// Command protocolgen generates the bot SDK types from the agent protocol
+// schema, so the Go, Python and TypeScript SDKs change with the wire format
+// instead of drifting from it. Run it from services/engine after editing
+// schemas/agent-protocol-v1.json:
+//
+//	go run ./cmd/protocolgen
+//
+// and commit the regenerated files; a test fails while they are stale.
+//
+// The generator reads a deliberately small subset of JSON Schema: the root
+// object and objects under $defs become named types, and properties may be
+// strings, integers, numbers, booleans, arrays, maps (objects with only
+// additionalProperties), string enums, constants, $refs to $defs, and types
+// made nullable with ["type", "null"].
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"flag"
+	"fmt"
+	"go/format"
+	"maps"
+	"os"
+	"path/filepath"
+	"slices"
+	"strconv"
+	"strings"
+)
+
+const (
+	schemaPath = "schemas/agent-protocol-v1.json"
+	goPath     = "services/engine/pkg/agentprotocol/protocol.go"
+	pythonPath = "sdk/python/poker_arena_protocol.py"
+	tsPath     = "sdk/typescript/protocol.ts"
+	// rootType names the schema's root object, the request a bot receives.
+	rootType = "DecisionRequest"
+	header   = "Code generated by protocolgen from " + schemaPath + ". DO NOT EDIT."
+)
+
+var errUnsupported = errors.New("unsupported schema construct")
+
+type schema struct {
+	Type                 json.RawMessage    `json:"type"`
+	Const                json.RawMessage    `json:"const"`
+	Enum                 []string           `json:"enum"`
+	Ref                  string             `json:"$ref"`
+	Required             []string           `json:"required"`
+	Properties           map[string]*schema `json:"properties"`
+	Items                *schema            `json:"items"`
+	AdditionalProperties json.RawMessage    `json:"additionalProperties"`
+	Defs                 map[string]*schema `json:"$defs"`
+}
+
+// typeRef is a property type before it is spelled in a target language.
+// Kind is string, integer, number, boolean, array, map or ref; literals
+// holds the allowed values of an enum or constant as JSON.
+type typeRef struct {
+	kind     string
+	ref      string
+	literals []string
+	nullable bool
+	elem     *typeRef
+}
+
+type field struct {
+	name     string
+	required bool
+	typ      typeRef
+}
+
+type objectType struct {
+	name   string
+	fields []field
+}
+
+func main() {
+	root := flag.String("root", "../..", "repository root")
+	flag.Parse()
+	if err := run(*root); err != nil {
+		fmt.Fprintf(os.Stderr, "protocolgen: %v\n", err)
+		os.Exit(1)
+	}
+}
+
+func run(root string) error {
+	data, err := os.ReadFile(filepath.Join(root, schemaPath))
+	if err != nil {
+		return err
+	}
+	files, err := generate(data)
+	if err != nil {
+		return err
+	}
+	for _, path := range slices.Sorted(maps.Keys(files)) {
+		target := filepath.Join(root, path)
+		if err := os.MkdirAll(filepath.Dir(target), 0o755); err != nil {
+			return err
+		}
+		if err := os.WriteFile(target, files[path], 0o644); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// generate renders every SDK file from the schema, keyed by its path from
+// the repository root. Types and fields are sorted by name so the output
+// only changes when the schema does.
+func generate(data []byte) (map[string][]byte, error) {
+	var root schema
+	if err := json.Unmarshal(data, &root); err != nil {
+		return nil, fmt.Errorf("parse schema: %w", err)
+	}
+	request, err := parseObject(rootType, &root)
+	if err != nil {
+		return nil, err
+	}
+	objects := []objectType{request}
+	for _, name := range slices.Sorted(maps.Keys(root.Defs)) {
+		object, err := parseObject(name, root.Defs[name])
+		if err != nil {
+			return nil, err
+		}
+		objects = append(objects, object)
+	}
+
+	goSource, err := format.Source(renderGo(objects))
+	if err != nil {
+		return nil, fmt.Errorf("format Go SDK: %w", err)
+	}
+	return map[string][]byte{
+		goPath:     goSource,
+		pythonPath: renderPython(objects),
+		tsPath:     renderTypeScript(objects),
+	}, nil
+}
+
+func parseObject(name string, s *schema) (objectType, error) {
+	if kind, _, err := parseTypeName(s.Type); err != nil || kind != "object" || s.Properties == nil {
+		return objectType{}, fmt.Errorf("%w: %s must be an object with properties", errUnsupported, name)
+	}
+	object := objectType{name: name}
+	for _, prop := range slices.Sorted(maps.Keys(s.Properties)) {
+		typ, err := parseType(s.Properties[prop])
+		if err != nil {
+			return objectType{}, fmt.Errorf("%s.%s: %w", name, prop, err)
+		}
+		object.fields = append(object.fields, field{name: prop, required: slices.Contains(s.Required, prop), typ: typ})
+	}
+	return object, nil
+}
+
+func parseType(s *schema) (typeRef, error) {
+	if s.Ref != "" {
+		name, ok := strings.CutPrefix(s.Ref, "#/$defs/")
+		if !ok {
+			return typeRef{}, fmt.Errorf("%w: $ref %q", errUnsupported, s.Ref)
+		}
+		return typeRef{kind: "ref", ref: name}, nil
+	}
+	kind, nullable, err := parseTypeName(s.Type)
+	if err != nil {
+		return typeRef{}, err
+	}
+	typ := typeRef{kind: kind, nullable: nullable}
+	for _, value := range s.Enum {
+		typ.literals = append(typ.literals, strconv.Quote(value))
+	}
+	if s.Const != nil {
+		typ.literals = append(typ.literals, string(s.Const))
+	}
+
+	switch kind {
+	case "string", "integer", "number", "boolean":
+	case "array":
+		if s.Items == nil {
+			return typeRef{}, fmt.Errorf("%w: array without items", errUnsupported)
+		}
+		elem, err := parseType(s.Items)
+		if err != nil {
+			return typeRef{}, err
+		}
+		typ.elem = &elem
+	case "object":
+		var values schema
+		if s.Properties != nil || json.Unmarshal(s.AdditionalProperties, &values) != nil {
+			return typeRef{}, fmt.Errorf("%w: inline objects must be maps; name the type under $defs", errUnsupported)
+		}
+		elem, err := parseType(&values)
+		if err != nil {
+			return typeRef{}, err
+		}
+		typ.kind, typ.elem = "map", &elem
+	default:
+		return typeRef{}, fmt.Errorf("%w: type %q", errUnsupported, kind)
+	}
+	return typ, nil
+}
+
+// parseTypeName reads "type" as a single name or as a name paired with
+// "null".
+func parseTypeName(raw json.RawMessage) (string, bool, error) {
+	var name string
+	if err := json.Unmarshal(raw, &name); err == nil {
+		return name, false, nil
+	}
+	var names []string
+	if err := json.Unmarshal(raw, &names); err != nil || len(names) != 2 || names[1] != "null" {
+		return "", false, fmt.Errorf("%w: type %s", errUnsupported, raw)
+	}
+	return names[0], true, nil
+}
+
+func renderGo(objects []objectType) []byte {
+	var b bytes.Buffer
+	fmt.Fprintf(&b, "// %s\n\n", header)
+	b.WriteString("// Package agentprotocol holds the agent protocol types for Go bots.\n")
+	b.WriteString("package agentprotocol\n")
+	for _, object := range objects {
+		fmt.Fprintf(&b, "\ntype %s struct {\n", object.name)
+		for _, f := range object.fields {
+			tag := f.name
+			if !f.required {
+				tag += ",omitempty"
+			}
+			fmt.Fprintf(&b, "\t%s %s `json:%q`\n", goName(f.name), goType(f.typ, f.required), tag)
+		}
+		b.WriteString("}\n")
+	}
+	return b.Bytes()
+}
+
+// goType makes optional and nullable scalars and objects pointers, so a
+// zero value can be told apart from a missing one.
+func goType(t typeRef, required bool) string {
+	var base string
+	switch t.kind {
+	case "string":
+		base = "string"
+	case "integer":
+		base = "int64"
+	case "number":
+		base = "float64"
+	case "boolean":
+		base = "bool"
+	case "array":
+		return "[]" + goType(*t.elem, true)
+	case "map":
+		return "map[string]" + goType(*t.elem, true)
+	case "ref":
+		base = t.ref
+	}
+	if t.nullable || !required {
+		return "*" + base
+	}
+	return base
+}
+
+var goInitialisms = map[string]string{"id": "ID", "ms": "MS", "url": "URL"}
+
+func goName(jsonName string) string {
+	var b strings.Builder
+	for _, part := range strings.Split(jsonName, "_") {
+		if initialism, ok := goInitialisms[part]; ok {
+			b.WriteString(initialism)
+			continue
+		}
+		b.WriteString(strings.ToUpper(part[:1]) + part[1:])
+	}
+	return b.String()
+}
+
+func renderPython(objects []objectType) []byte {
+	var b bytes.Buffer
+	fmt.Fprintf(&b, "# %s\n", header)
+	b.WriteString("\"\"\"Agent protocol types for Python bots.\"\"\"\n\n")
+	b.WriteString("from __future__ import annotations\n\n")
+	b.WriteString("from typing import Literal, NotRequired, TypedDict\n")
+	for _, object := range objects {
+		fmt.Fprintf(&b, "\n\nclass %s(TypedDict):\n", object.name)
+		for _, f := range object.fields {
+			typ := pythonType(f.typ)
+			if !f.required {
+				typ = "NotRequired[" + typ + "]"
+			}
+			fmt.Fprintf(&b, "    %s: %s\n", f.name, typ)
+		}
+	}
+	return b.Bytes()
+}
+
+func pythonType(t typeRef) string {
+	var typ string
+	switch {
+	case len(t.literals) > 0:
+		typ = "Literal[" + strings.Join(t.literals, ", ") + "]"
+	case t.kind == "string":
+		typ = "str"
+	case t.kind == "integer":
+		typ = "int"
+	case t.kind == "number":
+		typ = "float"
+	case t.kind == "boolean":
+		typ = "bool"
+	case t.kind == "array":
+		typ = "list[" + pythonType(*t.elem) + "]"
+	case t.kind == "map":
+		typ = "dict[str, " + pythonType(*t.elem) + "]"
+	case t.kind == "ref":
+		typ = t.ref
+	}
+	if t.nullable {
+		typ += " | None"
+	}
+	return typ
+}
+
+func renderTypeScript(objects []objectType) []byte {
+	var b bytes.Buffer
+	fmt.Fprintf(&b, "// %s\n", header)
+	for _, object := range objects {
+		fmt.Fprintf(&b, "\nexport interface %s {\n", object.name)
+		for _, f := range object.fields {
+			optional := ""
+			if !f.required {
+				optional = "?"
+			}
+			fmt.Fprintf(&b, "  %s%s: %s;\n", f.name, optional, tsType(f.typ))
+		}
+		b.WriteString("}\n")
+	}
+	return b.Bytes()
+}
+
+func tsType(t typeRef) string {
+	var typ string
+	switch {
+	case len(t.literals) > 0:
+		typ = strings.Join(t.literals, " | ")
+	case t.kind == "string":
+		typ = "string"
+	case t.kind == "integer", t.kind == "number":
+		typ = "number"
+	case t.kind == "boolean":
+		typ = "boolean"
+	case t.kind == "array":
+		typ = tsType(*t.elem)
+		if strings.Contains(typ, " ") {
+			typ = "(" + typ + ")"
+		}
+		typ += "[]"
+	case t.kind == "map":
+		typ = "Record<string, " + tsType(*t.elem) + ">"
+	case t.kind == "ref":
+		typ = t.ref
+	}
+	if t.nullable {
+		typ += " | null"
+	}
+	return typ
+}
@@ -0,0 +1,45 @@
+package main
+
+import (
+	"bytes"
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+const repoRoot = "../../../.."
+
+func TestGenerate_CheckedInSDKsMatchTheSchema(t *testing.T) {
+	t.Parallel()
+
+	data, err := os.ReadFile(filepath.Join(repoRoot, schemaPath))
+	if err != nil {
+		t.Fatalf("read schema: %v", err)
+	}
+	files, err := generate(data)
+	if err != nil {
+		t.Fatalf("generate failed: %v", err)
+	}
+	if len(files) != 3 {
+		t.Fatalf("expected Go, Python and TypeScript SDKs, got %d files", len(files))
+	}
+	for path, want := range files {
+		got, err := os.ReadFile(filepath.Join(repoRoot, path))
+		if err != nil {
+			t.Fatalf("read %s: %v", path, err)
+		}
+		if !bytes.Equal(got, want) {
+			t.Fatalf("%s is stale; run `go run ./cmd/protocolgen` from services/engine\nwant:\n%s", path, want)
+		}
+	}
+}
+
+func TestGenerate_RejectsUnnamedInlineObjects(t *testing.T) {
+	t.Parallel()
+
+	schema := []byte(`{"type": "object", "properties": {"seat": {"type": "object", "properties": {"no": {"type": "integer"}}}}}`)
+	if _, err := generate(schema); !errors.Is(err, errUnsupported) {
+		t.Fatalf("expected errUnsupported, got %v", err)
+	}
+}
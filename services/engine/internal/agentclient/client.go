@@ -14,6 +14,8 @@ import (
 	"time"
 
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/statemachine"
 )
 
 const (
@@ -34,6 +36,7 @@ var (
 
 type Client struct {
 	httpClient *http.Client
+	latency    *latencyProbe
 }
 
 type Request struct {
@@ -41,34 +44,72 @@ type Request struct {
 	State           domain.HandState
 	ActingSeat      domain.SeatNo
 	ActionTimeoutMS uint64
+	// View picks how much the request describes; empty is standard.
+	View domain.ViewLevel
 }
 
 type protocolRequest struct {
-	ProtocolVersion int               `json:"protocol_version"`
-	HandID          string            `json:"hand_id"`
-	TableID         string            `json:"table_id"`
-	Seat            int               `json:"seat"`
-	HoleCards       []string          `json:"hole_cards"`
-	Board           []string          `json:"board"`
-	Pot             uint32            `json:"pot"`
-	ToCall          uint32            `json:"to_call"`
-	MinRaiseTo      *uint32           `json:"min_raise_to"`
-	Stacks          map[string]uint32 `json:"stacks"`
-	Bets            map[string]uint32 `json:"bets"`
-	LegalActions    []string          `json:"legal_actions"`
-	ActionDeadline  uint64            `json:"action_deadline_ms"`
+	ProtocolVersion int                     `json:"protocol_version"`
+	HandID          string                  `json:"hand_id"`
+	TableID         string                  `json:"table_id"`
+	Seat            int                     `json:"seat"`
+	HoleCards       []string                `json:"hole_cards"`
+	Board           []string                `json:"board"`
+	Pot             domain.Chips            `json:"pot"`
+	ToCall          domain.Chips            `json:"to_call"`
+	MinRaiseTo      *domain.Chips           `json:"min_raise_to"`
+	Stacks          map[string]domain.Chips `json:"stacks,omitempty"`
+	Bets            map[string]domain.Chips `json:"bets,omitempty"`
+	LegalActions    []string                `json:"legal_actions"`
+	SizingHints     *rules.SizingHints      `json:"sizing_hints,omitempty"`
+	ActionDeadline  uint64                  `json:"action_deadline_ms"`
+	TimeBankMS      uint64                  `json:"time_bank_ms,omitempty"`
+	RuleFlags       []string                `json:"rule_flags,omitempty"`
+	View            domain.ViewLevel        `json:"view"`
+	Analytics       *viewAnalytics          `json:"analytics,omitempty"`
+}
+
+// viewAnalytics is what a verbose view works out from the table so the bot
+// does not have to.
+type viewAnalytics struct {
+	Street         domain.Street `json:"street"`
+	ButtonSeat     int           `json:"button_seat"`
+	PlayersInHand  int           `json:"players_in_hand"`
+	StreetBets     int           `json:"street_bets"`
+	EffectiveStack domain.Chips  `json:"effective_stack"`
+	PotOdds        float64       `json:"pot_odds"`
+	StackToPot     float64       `json:"stack_to_pot"`
+	HandCategory   string        `json:"hand_category,omitempty"`
 }
 
 type protocolResponse struct {
-	Action string  `json:"action"`
-	Amount *uint32 `json:"amount,omitempty"`
+	Action    string            `json:"action"`
+	Amount    *domain.Chips     `json:"amount,omitempty"`
+	Telemetry *domain.Telemetry `json:"telemetry,omitempty"`
 }
 
 func New(timeout time.Duration) Client {
 	if timeout <= 0 {
 		timeout = defaultTimeout
 	}
-	return Client{httpClient: &http.Client{Timeout: timeout}}
+	return Client{httpClient: &http.Client{Timeout: timeout}, latency: newLatencyProbe()}
+}
+
+// OneWayLatency is the measured one-way latency to endpointURL, or zero
+// before the client has connected to it.
+func (c Client) OneWayLatency(endpointURL string) time.Duration {
+	if c.latency == nil {
+		return 0
+	}
+	return c.latency.get(endpointURL)
+}
+
+// NewWithTransport is New with a custom round tripper, e.g. a fault injector
+// in tests.
+func NewWithTransport(timeout time.Duration, transport http.RoundTripper) Client {
+	client := New(timeout)
+	client.httpClient.Transport = transport
+	return client
 }
 
 func (c Client) NextAction(ctx context.Context, req Request) (domain.Action, error) {
@@ -79,7 +120,8 @@ func (c Client) NextAction(ctx context.Context, req Request) (domain.Action, err
 		c = New(defaultTimeout)
 	}
 
-	payload, legalActionSet, err := buildProtocolRequest(req.State, req.ActingSeat, chooseActionTimeout(req))
+	timeoutMS := chooseActionTimeout(req)
+	payload, legalActionSet, err := buildProtocolRequest(req.State, req.ActingSeat, timeoutMS, req.View)
 	if err != nil {
 		return domain.Action{}, err
 	}
@@ -89,13 +131,27 @@ func (c Client) NextAction(ctx context.Context, req Request) (domain.Action, err
 		return domain.Action{}, fmt.Errorf("%w: marshal payload: %v", ErrMalformedResponse, err)
 	}
 
-	httpReq, err := http.NewRequestWithContext(ctx, http.MethodPost, req.EndpointURL, bytes.NewReader(body))
+	reqCtx := ctx
+	if c.latency != nil {
+		reqCtx = c.latency.trace(ctx, req.EndpointURL)
+	}
+	httpReq, err := http.NewRequestWithContext(reqCtx, http.MethodPost, req.EndpointURL, bytes.NewReader(body))
 	if err != nil {
 		return domain.Action{}, fmt.Errorf("%w: build request: %v", ErrNetwork, err)
 	}
 	httpReq.Header.Set("Content-Type", "application/json")
 
-	resp, err := c.httpClient.Do(httpReq)
+	httpClient := c.httpClient
+	extraMS := payload.TimeBankMS + req.State.LatencyGraceMS + req.State.LatencyCompensationCapMS
+	if req.State.ThinkTimeBudgetMS > 0 || extraMS > 0 {
+		// A budgeted decision may legitimately outlast the fixed client
+		// timeout, and the time bank, latency grace window and latency
+		// compensation run past the deadline.
+		budgeted := *c.httpClient
+		budgeted.Timeout = time.Duration(timeoutMS+extraMS) * time.Millisecond
+		httpClient = &budgeted
+	}
+	resp, err := httpClient.Do(httpReq)
 	if err != nil {
 		if isTimeoutError(err) || errors.Is(ctx.Err(), context.DeadlineExceeded) {
 			return domain.Action{}, fmt.Errorf("%w: %v", ErrRequestTimeout, err)
@@ -125,17 +181,33 @@ func (c Client) NextAction(ctx context.Context, req Request) (domain.Action, err
 	if err != nil {
 		return domain.Action{}, err
 	}
+	if err := dto.Telemetry.Validate(); err != nil {
+		return domain.Action{}, fmt.Errorf("%w: %v", ErrMalformedResponse, err)
+	}
+	action.Telemetry = dto.Telemetry
 	return action, nil
 }
 
 func chooseActionTimeout(req Request) uint64 {
+	if req.State.ThinkTimeBudgetMS > 0 {
+		if seat, ok := seatByNo(req.State.Seats, req.ActingSeat); ok && seat.ThinkTimeRemainingMS > 0 {
+			return seat.ThinkTimeRemainingMS
+		}
+	}
 	if req.ActionTimeoutMS > 0 {
 		return req.ActionTimeoutMS
 	}
 	return defaultActionTimeout
 }
 
-func buildProtocolRequest(state domain.HandState, actingSeat domain.SeatNo, timeoutMS uint64) (protocolRequest, map[domain.ActionKind]struct{}, error) {
+// buildProtocolRequest renders the decision request at the given view
+// level. Every level carries what is needed to act legally; minimal drops
+// stacks, bets, sizing hints and rule flags, and verbose adds analytics.
+func buildProtocolRequest(state domain.HandState, actingSeat domain.SeatNo, timeoutMS uint64, view domain.ViewLevel) (protocolRequest, map[domain.ActionKind]struct{}, error) {
+	view, err := domain.ParseViewLevel(string(view))
+	if err != nil {
+		return protocolRequest{}, nil, err
+	}
 	acting, ok := seatByNo(state.Seats, actingSeat)
 	if !ok {
 		return protocolRequest{}, nil, fmt.Errorf("%w: acting seat %d not found", ErrMalformedResponse, actingSeat)
@@ -149,24 +221,31 @@ func buildProtocolRequest(state domain.HandState, actingSeat domain.SeatNo, time
 		return protocolRequest{}, nil, fmt.Errorf("%w: seat %d has %d cards", ErrMissingHoleCards, actingSeat, len(holeCards))
 	}
 
-	toCall := uint32(0)
-	if state.CurrentBet > acting.CommittedInRound {
-		toCall = state.CurrentBet - acting.CommittedInRound
+	legal, err := statemachine.LegalActions(state, actingSeat)
+	if err != nil {
+		return protocolRequest{}, nil, fmt.Errorf("%w: %v", ErrMalformedResponse, err)
 	}
-
-	legalKinds := deriveLegalActions(state, acting, toCall)
-	legalActionSet := make(map[domain.ActionKind]struct{}, len(legalKinds))
-	legalActions := make([]string, 0, len(legalKinds))
-	for _, kind := range legalKinds {
+	toCall := legal.ToCall
+	legalActionSet := make(map[domain.ActionKind]struct{}, len(legal.Kinds))
+	legalActions := make([]string, 0, len(legal.Kinds))
+	for _, kind := range legal.Kinds {
 		legalActionSet[kind] = struct{}{}
 		legalActions = append(legalActions, string(kind))
 	}
 
-	var minRaiseTo *uint32
+	var minRaiseTo *domain.Chips
 	if _, ok := legalActionSet[domain.ActionRaise]; ok {
-		value := state.MinRaiseTo
+		value := legal.MinRaiseTo
 		minRaiseTo = &value
 	}
+	var sizingHints *rules.SizingHints
+	_, canBet := legalActionSet[domain.ActionBet]
+	_, canRaise := legalActionSet[domain.ActionRaise]
+	if view != domain.ViewMinimal && (canBet || canRaise) {
+		if hints, ok := rules.ActionSizingHints(state, actingSeat); ok {
+			sizingHints = &hints
+		}
+	}
 
 	payload := protocolRequest{
 		ProtocolVersion: ProtocolVersion,
@@ -181,24 +260,64 @@ func buildProtocolRequest(state domain.HandState, actingSeat domain.SeatNo, time
 		Pot:            state.Pot,
 		ToCall:         toCall,
 		MinRaiseTo:     minRaiseTo,
-		Stacks:         make(map[string]uint32, len(state.Seats)),
-		Bets:           make(map[string]uint32, len(state.Seats)),
 		LegalActions:   legalActions,
+		SizingHints:    sizingHints,
 		ActionDeadline: timeoutMS,
+		TimeBankMS:     acting.TimeBankMS,
+		View:           view,
 	}
 
 	for _, card := range state.Board {
 		payload.Board = append(payload.Board, formatCardASCII(card))
 	}
+	if view == domain.ViewMinimal {
+		return payload, legalActionSet, nil
+	}
+	payload.RuleFlags = state.RuleFlags.Names()
+	payload.Stacks = make(map[string]domain.Chips, len(state.Seats))
+	payload.Bets = make(map[string]domain.Chips, len(state.Seats))
 	for _, seat := range state.Seats {
 		key := strconv.Itoa(int(seat.SeatNo))
 		payload.Stacks[key] = seat.Stack
 		payload.Bets[key] = seat.CommittedInRound
 	}
+	if view == domain.ViewVerbose {
+		analytics := deriveAnalytics(state, acting, holeCards, toCall)
+		payload.Analytics = &analytics
+	}
 
 	return payload, legalActionSet, nil
 }
 
+func deriveAnalytics(state domain.HandState, acting domain.SeatState, holeCards []domain.Card, toCall domain.Chips) viewAnalytics {
+	analytics := viewAnalytics{
+		Street:     state.Street,
+		ButtonSeat: int(state.ButtonSeat),
+		StreetBets: int(state.StreetBets),
+	}
+	var deepestOpponent domain.Chips
+	for _, seat := range state.Seats {
+		if !seat.IsActive() {
+			continue
+		}
+		analytics.PlayersInHand++
+		if seat.SeatNo != acting.SeatNo {
+			deepestOpponent = max(deepestOpponent, seat.Stack)
+		}
+	}
+	analytics.EffectiveStack = min(acting.Stack, deepestOpponent)
+	if toCall > 0 {
+		analytics.PotOdds = float64(toCall) / float64(state.Pot+toCall)
+	}
+	if state.Pot > 0 {
+		analytics.StackToPot = float64(analytics.EffectiveStack) / float64(state.Pot)
+	}
+	if len(state.Board) >= 3 {
+		analytics.HandCategory = rules.EvaluateBestHand(holeCards, state.Board).Category.String()
+	}
+	return analytics
+}
+
 func parseAndValidateProtocolResponse(dto protocolResponse, legal map[domain.ActionKind]struct{}) (domain.Action, error) {
 	kind := domain.ActionKind(dto.Action)
 	if _, ok := legal[kind]; !ok {
@@ -227,23 +346,6 @@ func parseAndValidateProtocolResponse(dto protocolResponse, legal map[domain.Act
 	}
 }
 
-func deriveLegalActions(state domain.HandState, acting domain.SeatState, toCall uint32) []domain.ActionKind {
-	actions := []domain.ActionKind{domain.ActionFold}
-	if toCall == 0 {
-		actions = append(actions, domain.ActionCheck)
-		if acting.Stack > 0 && state.CurrentBet == 0 {
-			actions = append(actions, domain.ActionBet)
-		}
-		return actions
-	}
-
-	actions = append(actions, domain.ActionCall)
-	if state.CurrentBet > 0 && acting.Stack > toCall {
-		actions = append(actions, domain.ActionRaise)
-	}
-	return actions
-}
-
 func formatCardASCII(card domain.Card) string {
 	return formatRankASCII(card.Rank) + formatSuitASCII(card.Suit)
 }
@@ -1,15 +1,20 @@
 package agentclient
 
 import (
+	"bytes"
 	"context"
 	"encoding/json"
 	"errors"
 	"net/http"
 	"net/http/httptest"
+	"reflect"
+	"sync/atomic"
 	"testing"
 	"time"
 
+	"github.com/imaddar/poker-arena/services/engine/internal/chaos"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/pkg/agentprotocol"
 )
 
 func TestBuildProtocolRequestMapsFields(t *testing.T) {
@@ -19,8 +24,9 @@ func TestBuildProtocolRequestMapsFields(t *testing.T) {
 	state.CurrentBet = 100
 	state.MinRaiseTo = 250
 	state.Seats[0].CommittedInRound = 50
+	state.RuleFlags = domain.RuleStraddle | domain.RuleRunItTwice
 
-	payload, legal, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000)
+	payload, legal, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewStandard)
 	if err != nil {
 		t.Fatalf("buildProtocolRequest failed: %v", err)
 	}
@@ -52,6 +58,158 @@ func TestBuildProtocolRequestMapsFields(t *testing.T) {
 	if _, ok := legal[domain.ActionRaise]; !ok {
 		t.Fatalf("expected raise legal actions, got %+v", payload.LegalActions)
 	}
+	if hints := payload.SizingHints; hints == nil || hints.Action != domain.ActionRaise || hints.Min != 250 || hints.Max != 10000 {
+		t.Fatalf("unexpected sizing hints: %+v", payload.SizingHints)
+	}
+	if len(payload.RuleFlags) != 2 || payload.RuleFlags[0] != "straddle" || payload.RuleFlags[1] != "run_it_twice" {
+		t.Fatalf("expected the table's rule flags, got %v", payload.RuleFlags)
+	}
+}
+
+func TestProtocolMessagesFitTheGeneratedSDKTypes(t *testing.T) {
+	t.Parallel()
+
+	state := baseState(t)
+	state.CurrentBet = 100
+	state.MinRaiseTo = 250
+	state.Seats[0].CommittedInRound = 50
+	state.RuleFlags = domain.RuleStraddle
+	state.Street = domain.StreetFlop
+	// Verbose is the fullest view, so it covers every field a bot can get.
+	payload, _, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewVerbose)
+	if err != nil {
+		t.Fatalf("buildProtocolRequest failed: %v", err)
+	}
+	sent, err := json.Marshal(payload)
+	if err != nil {
+		t.Fatalf("marshal request failed: %v", err)
+	}
+	var request agentprotocol.DecisionRequest
+	decoder := json.NewDecoder(bytes.NewReader(sent))
+	decoder.DisallowUnknownFields()
+	if err := decoder.Decode(&request); err != nil {
+		t.Fatalf("engine request does not fit the SDK type: %v", err)
+	}
+	reencoded, err := json.Marshal(request)
+	if err != nil {
+		t.Fatalf("marshal SDK request failed: %v", err)
+	}
+	var want, got map[string]any
+	if err := json.Unmarshal(sent, &want); err != nil {
+		t.Fatalf("decode request failed: %v", err)
+	}
+	if err := json.Unmarshal(reencoded, &got); err != nil {
+		t.Fatalf("decode SDK request failed: %v", err)
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Fatalf("expected the SDK type to carry the whole request\nwant %s\ngot  %s", sent, reencoded)
+	}
+
+	amount, equity := int64(250), 0.6
+	answer, err := json.Marshal(agentprotocol.DecisionResponse{
+		Action:    "raise",
+		Amount:    &amount,
+		Telemetry: &agentprotocol.Telemetry{Equity: &equity, Tags: []string{"value"}},
+	})
+	if err != nil {
+		t.Fatalf("marshal SDK response failed: %v", err)
+	}
+	var response protocolResponse
+	decoder = json.NewDecoder(bytes.NewReader(answer))
+	decoder.DisallowUnknownFields()
+	if err := decoder.Decode(&response); err != nil {
+		t.Fatalf("SDK response does not fit the engine type: %v", err)
+	}
+	if response.Action != "raise" || response.Amount == nil || *response.Amount != 250 || response.Telemetry == nil || *response.Telemetry.Equity != equity {
+		t.Fatalf("unexpected decoded response %+v", response)
+	}
+}
+
+func TestBuildProtocolRequestViewLevels(t *testing.T) {
+	t.Parallel()
+
+	state := baseState(t)
+	state.Street = domain.StreetFlop
+	state.CurrentBet = 100
+	state.MinRaiseTo = 250
+	state.Seats[0].CommittedInRound = 50
+	state.RuleFlags = domain.RuleStraddle
+
+	minimal, legal, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewMinimal)
+	if err != nil {
+		t.Fatalf("buildProtocolRequest failed: %v", err)
+	}
+	if minimal.Stacks != nil || minimal.Bets != nil || minimal.SizingHints != nil || minimal.RuleFlags != nil || minimal.Analytics != nil {
+		t.Fatalf("expected a minimal view without stacks, bets, hints, flags or analytics, got %+v", minimal)
+	}
+	if minimal.ToCall != 50 || minimal.Pot != 150 || len(minimal.HoleCards) != 2 || minimal.MinRaiseTo == nil || len(legal) == 0 {
+		t.Fatalf("expected a minimal view to keep what is needed to act, got %+v", minimal)
+	}
+	encoded, err := json.Marshal(minimal)
+	if err != nil {
+		t.Fatalf("marshal minimal request failed: %v", err)
+	}
+	if bytes.Contains(encoded, []byte(`"stacks"`)) || !bytes.Contains(encoded, []byte(`"view":"minimal"`)) {
+		t.Fatalf("unexpected minimal payload %s", encoded)
+	}
+
+	standard, _, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, "")
+	if err != nil {
+		t.Fatalf("buildProtocolRequest failed: %v", err)
+	}
+	if standard.View != domain.ViewStandard || standard.Stacks == nil || standard.SizingHints == nil || standard.Analytics != nil {
+		t.Fatalf("expected the empty view to be standard, got %+v", standard)
+	}
+
+	verbose, _, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewVerbose)
+	if err != nil {
+		t.Fatalf("buildProtocolRequest failed: %v", err)
+	}
+	want := viewAnalytics{
+		Street:         domain.StreetFlop,
+		PlayersInHand:  2,
+		EffectiveStack: 9900,
+		PotOdds:        0.25,
+		StackToPot:     66,
+		HandCategory:   "high_card",
+	}
+	if verbose.Analytics == nil || *verbose.Analytics != want {
+		t.Fatalf("expected analytics %+v, got %+v", want, verbose.Analytics)
+	}
+
+	if _, _, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, "chatty"); !errors.Is(err, domain.ErrUnknownViewLevel) {
+		t.Fatalf("expected ErrUnknownViewLevel, got %v", err)
+	}
+}
+
+func TestBuildProtocolRequestCarriesTheTimeBank(t *testing.T) {
+	t.Parallel()
+
+	state := baseState(t)
+	state.Seats[0].TimeBankMS = 5000
+	payload, _, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewMinimal)
+	if err != nil {
+		t.Fatalf("buildProtocolRequest failed: %v", err)
+	}
+	encoded, err := json.Marshal(payload)
+	if err != nil {
+		t.Fatalf("marshal request failed: %v", err)
+	}
+	if payload.TimeBankMS != 5000 || !bytes.Contains(encoded, []byte(`"time_bank_ms":5000`)) {
+		t.Fatalf("expected the acting seat's bank in every view, got %s", encoded)
+	}
+
+	state.Seats[0].TimeBankMS = 0
+	payload, _, err = buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewStandard)
+	if err != nil {
+		t.Fatalf("buildProtocolRequest failed: %v", err)
+	}
+	if encoded, err = json.Marshal(payload); err != nil {
+		t.Fatalf("marshal request failed: %v", err)
+	}
+	if bytes.Contains(encoded, []byte(`"time_bank_ms"`)) {
+		t.Fatalf("expected no time_bank_ms without a bank, got %s", encoded)
+	}
 }
 
 func TestBuildProtocolRequestNoBetAddsCheckAndBet(t *testing.T) {
@@ -61,7 +219,7 @@ func TestBuildProtocolRequestNoBetAddsCheckAndBet(t *testing.T) {
 	state.CurrentBet = 0
 	state.Seats[0].CommittedInRound = 0
 
-	payload, legal, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000)
+	payload, legal, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewStandard)
 	if err != nil {
 		t.Fatalf("buildProtocolRequest failed: %v", err)
 	}
@@ -88,7 +246,7 @@ func TestBuildProtocolRequestAllInFacingBetNoRaise(t *testing.T) {
 	state.Seats[0].CommittedInRound = 50
 	state.Seats[0].Stack = 50
 
-	_, legal, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000)
+	_, legal, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewStandard)
 	if err != nil {
 		t.Fatalf("buildProtocolRequest failed: %v", err)
 	}
@@ -99,6 +257,62 @@ func TestBuildProtocolRequestAllInFacingBetNoRaise(t *testing.T) {
 	if _, ok := legal[domain.ActionCall]; !ok {
 		t.Fatal("expected call legal")
 	}
+	if _, ok := legal[domain.ActionAllIn]; !ok {
+		t.Fatal("expected all_in legal while chips remain")
+	}
+}
+
+func TestBuildProtocolRequestCappedStreetOnlyCallsOrFolds(t *testing.T) {
+	t.Parallel()
+
+	state := baseState(t)
+	state.CurrentBet = 400
+	state.RaiseCap = 4
+	state.StreetBets = 4
+	state.Seats = append(state.Seats, domain.SeatState{SeatNo: mustSeatNo(t, 3), Stack: 9600, CommittedInRound: 400, Status: domain.SeatStatusActive})
+
+	payload, legal, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewStandard)
+	if err != nil {
+		t.Fatalf("buildProtocolRequest failed: %v", err)
+	}
+	for _, kind := range []domain.ActionKind{domain.ActionRaise, domain.ActionAllIn} {
+		if _, ok := legal[kind]; ok {
+			t.Fatalf("did not expect %s legal on a capped street, got %+v", kind, payload.LegalActions)
+		}
+	}
+	if _, ok := legal[domain.ActionCall]; !ok {
+		t.Fatal("expected call legal")
+	}
+	if payload.MinRaiseTo != nil {
+		t.Fatalf("expected no min_raise_to on a capped street, got %d", *payload.MinRaiseTo)
+	}
+	if payload.SizingHints != nil {
+		t.Fatalf("expected no sizing hints on a capped street, got %+v", payload.SizingHints)
+	}
+}
+
+func TestParseAndValidateProtocolResponseAllIn(t *testing.T) {
+	t.Parallel()
+
+	legal := map[domain.ActionKind]struct{}{
+		domain.ActionFold:  {},
+		domain.ActionCall:  {},
+		domain.ActionAllIn: {},
+	}
+
+	good, err := parseAndValidateProtocolResponse(protocolResponse{Action: "all_in"}, legal)
+	if err != nil {
+		t.Fatalf("expected valid all_in response, got %v", err)
+	}
+	if good.Kind != domain.ActionAllIn || good.Amount != nil {
+		t.Fatalf("expected bare all_in action, got %+v", good)
+	}
+
+	amount := domain.Chips(500)
+	_, err = parseAndValidateProtocolResponse(protocolResponse{Action: "all_in", Amount: &amount}, legal)
+	if !errors.Is(err, ErrIllegalAgentAction) {
+		t.Fatalf("expected ErrIllegalAgentAction for all_in with amount, got %v", err)
+	}
 }
 
 func TestBuildProtocolRequestMissingHoleCardsFails(t *testing.T) {
@@ -107,7 +321,7 @@ func TestBuildProtocolRequestMissingHoleCardsFails(t *testing.T) {
 	state := baseState(t)
 	state.HoleCards = nil
 
-	_, _, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000)
+	_, _, err := buildProtocolRequest(state, mustSeatNo(t, 1), 2000, domain.ViewStandard)
 	if !errors.Is(err, ErrMissingHoleCards) {
 		t.Fatalf("expected ErrMissingHoleCards, got %v", err)
 	}
@@ -130,7 +344,7 @@ func TestParseAndValidateProtocolResponse(t *testing.T) {
 		t.Fatalf("expected call kind, got %q", good.Kind)
 	}
 
-	amount := uint32(200)
+	amount := domain.Chips(200)
 	good, err = parseAndValidateProtocolResponse(protocolResponse{Action: "raise", Amount: &amount}, legal)
 	if err != nil {
 		t.Fatalf("expected valid raise response, got %v", err)
@@ -211,6 +425,46 @@ func TestClientNextActionTimeout(t *testing.T) {
 	}
 }
 
+func TestClientNextActionUnderTransportChaos(t *testing.T) {
+	t.Parallel()
+
+	var hits atomic.Int32
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		hits.Add(1)
+		_ = json.NewEncoder(w).Encode(protocolResponse{Action: "check"})
+	}))
+	defer server.Close()
+
+	dropAll, err := chaos.NewInjector(chaos.Schedule{Seed: 3, DropRate: 1})
+	if err != nil {
+		t.Fatalf("NewInjector failed: %v", err)
+	}
+	client := NewWithTransport(2*time.Second, chaos.NewTransport(nil, dropAll))
+	req := Request{EndpointURL: server.URL, State: baseState(t), ActingSeat: mustSeatNo(t, 1)}
+	if _, err := client.NextAction(context.Background(), req); !errors.Is(err, ErrNetwork) {
+		t.Fatalf("expected dropped request to surface as ErrNetwork, got %v", err)
+	}
+	if hits.Load() != 0 {
+		t.Fatalf("expected dropped request not to reach the agent, got %d hits", hits.Load())
+	}
+
+	duplicateAll, err := chaos.NewInjector(chaos.Schedule{Seed: 3, DuplicateRate: 1})
+	if err != nil {
+		t.Fatalf("NewInjector failed: %v", err)
+	}
+	client = NewWithTransport(2*time.Second, chaos.NewTransport(nil, duplicateAll))
+	action, err := client.NextAction(context.Background(), req)
+	if err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+	if action.Kind != domain.ActionCheck {
+		t.Fatalf("expected check, got %s", action.Kind)
+	}
+	if hits.Load() != 2 {
+		t.Fatalf("expected duplicated request to reach the agent twice, got %d hits", hits.Load())
+	}
+}
+
 func TestClientNextActionMalformedResponse(t *testing.T) {
 	t.Parallel()
 
@@ -253,6 +507,40 @@ func TestClientNextActionRejectsTrailingResponseData(t *testing.T) {
 	}
 }
 
+func TestClientNextActionCarriesTelemetry(t *testing.T) {
+	t.Parallel()
+
+	var calls atomic.Int32
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if calls.Add(1) == 1 {
+			_, _ = w.Write([]byte(`{"action":"check","telemetry":{"equity":0.62,"tags":["pot-control"]}}`))
+			return
+		}
+		_, _ = w.Write([]byte(`{"action":"check","telemetry":{"equity":1.5}}`))
+	}))
+	defer server.Close()
+
+	state := baseState(t)
+	client := New(2 * time.Second)
+	req := Request{
+		EndpointURL:     server.URL,
+		State:           state,
+		ActingSeat:      mustSeatNo(t, 1),
+		ActionTimeoutMS: 2000,
+	}
+	action, err := client.NextAction(context.Background(), req)
+	if err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+	if action.Telemetry == nil || action.Telemetry.Equity == nil || *action.Telemetry.Equity != 0.62 || len(action.Telemetry.Tags) != 1 {
+		t.Fatalf("expected telemetry on the action, got %+v", action.Telemetry)
+	}
+
+	if _, err := client.NextAction(context.Background(), req); !errors.Is(err, ErrMalformedResponse) {
+		t.Fatalf("expected ErrMalformedResponse for out of range equity, got %v", err)
+	}
+}
+
 func TestClientNextActionNon200Status(t *testing.T) {
 	t.Parallel()
 
@@ -278,7 +566,7 @@ func TestClientNextActionIllegalAgentAction(t *testing.T) {
 	t.Parallel()
 
 	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
-		_ = json.NewEncoder(w).Encode(protocolResponse{Action: "bet", Amount: u32ptr(100)})
+		_ = json.NewEncoder(w).Encode(protocolResponse{Action: "bet", Amount: chipsPtr(100)})
 	}))
 	defer server.Close()
 
@@ -343,6 +631,6 @@ func mustCard(t *testing.T, rank uint8, suit domain.Suit) domain.Card {
 	return domain.NewCard(r, suit)
 }
 
-func u32ptr(v uint32) *uint32 {
+func chipsPtr(v domain.Chips) *domain.Chips {
 	return &v
 }
@@ -0,0 +1,58 @@
+package agentclient
+
+import (
+	"context"
+	"net/http/httptrace"
+	"sync"
+	"time"
+)
+
+// latencyProbe estimates each endpoint's one-way latency as half the time
+// the TCP handshakes of connections to it take, smoothed like TCP's SRTT
+// (RFC 6298). Unlike the time a decision takes, this leaves out the bot's
+// thinking. Reused connections add no samples.
+type latencyProbe struct {
+	mu     sync.Mutex
+	oneWay map[string]time.Duration
+}
+
+func newLatencyProbe() *latencyProbe {
+	return &latencyProbe{oneWay: make(map[string]time.Duration)}
+}
+
+// trace returns ctx with a trace that samples the handshake of every
+// connection dialed for endpoint.
+func (p *latencyProbe) trace(ctx context.Context, endpoint string) context.Context {
+	var mu sync.Mutex
+	starts := make(map[string]time.Time)
+	return httptrace.WithClientTrace(ctx, &httptrace.ClientTrace{
+		ConnectStart: func(network, addr string) {
+			mu.Lock()
+			defer mu.Unlock()
+			starts[network+"/"+addr] = time.Now()
+		},
+		ConnectDone: func(network, addr string, err error) {
+			mu.Lock()
+			start, ok := starts[network+"/"+addr]
+			mu.Unlock()
+			if ok && err == nil {
+				p.observe(endpoint, time.Since(start)/2)
+			}
+		},
+	})
+}
+
+func (p *latencyProbe) observe(endpoint string, oneWay time.Duration) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	if prev, ok := p.oneWay[endpoint]; ok {
+		oneWay = prev + (oneWay-prev)/8
+	}
+	p.oneWay[endpoint] = oneWay
+}
+
+func (p *latencyProbe) get(endpoint string) time.Duration {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	return p.oneWay[endpoint]
+}
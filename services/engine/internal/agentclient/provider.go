@@ -3,6 +3,7 @@ package agentclient
 import (
 	"context"
 	"fmt"
+	"time"
 
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
 )
@@ -47,3 +48,16 @@ func (p ActionProvider) NextAction(ctx context.Context, state domain.HandState)
 		ActionTimeoutMS: timeoutMS,
 	})
 }
+
+// OneWayLatency is the client's measured one-way latency to seat's
+// endpoint, so the table runner can compensate distant bots.
+func (p ActionProvider) OneWayLatency(state domain.HandState, seat domain.SeatNo) time.Duration {
+	if p.Endpoints == nil {
+		return 0
+	}
+	endpoint, err := p.Endpoints.EndpointForSeat(state, seat)
+	if err != nil {
+		return 0
+	}
+	return p.Client.OneWayLatency(endpoint)
+}
@@ -96,3 +96,33 @@ func TestActionProviderPropagatesClientError(t *testing.T) {
 		t.Fatalf("expected ErrMalformedResponse, got %v", err)
 	}
 }
+
+func TestActionProviderMeasuresOneWayLatencyPerSeat(t *testing.T) {
+	t.Parallel()
+
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		_ = json.NewEncoder(w).Encode(protocolResponse{Action: "check"})
+	}))
+	defer server.Close()
+
+	state := baseState(t)
+	provider := ActionProvider{
+		Client:    New(2 * time.Second),
+		Endpoints: staticEndpoints{bySeat: map[domain.SeatNo]string{mustSeatNo(t, 1): server.URL}},
+	}
+	if _, err := provider.NextAction(context.Background(), state); err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+	if _, ok := provider.Client.latency.oneWay[server.URL]; !ok {
+		t.Fatal("expected the connection to the seat's endpoint to be sampled")
+	}
+
+	provider.Client.latency.oneWay[server.URL] = 40 * time.Millisecond
+	provider.Client.latency.observe(server.URL, 120*time.Millisecond)
+	if got := provider.OneWayLatency(state, mustSeatNo(t, 1)); got != 50*time.Millisecond {
+		t.Fatalf("expected the smoothed latency of 50ms, got %v", got)
+	}
+	if got := provider.OneWayLatency(state, mustSeatNo(t, 2)); got != 0 {
+		t.Fatalf("expected an unmeasured seat to report no latency, got %v", got)
+	}
+}
@@ -0,0 +1,175 @@
+// Package anonymize rewrites exported hand histories for public release:
+// identifiers become stable pseudonyms, timestamps are coarsened, and bot
+// telemetry is dropped. It works on the JSON documents the API serves, so it
+// also covers fields added to replays later.
+package anonymize
+
+import (
+	"bytes"
+	"crypto/hmac"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"strings"
+	"time"
+)
+
+const DefaultTimeBucket = time.Hour
+
+var ErrMissingSalt = errors.New("anonymization salt is required")
+
+// Policy controls what the anonymizer rewrites. The same Salt yields the same
+// pseudonyms across exports, so hands from one release can be joined; use a
+// fresh salt per release to prevent linking releases together.
+type Policy struct {
+	Salt string
+	// TimeBucket truncates timestamps; zero drops them entirely.
+	TimeBucket    time.Duration
+	KeepTelemetry bool
+	KeepDeck      bool
+	// IdentifierKeys lists the JSON keys to pseudonymize; empty means every
+	// identifier the API emits.
+	IdentifierKeys []string
+}
+
+// DefaultPolicy pseudonymizes every identifier, buckets timestamps to the
+// hour, and strips telemetry and the undealt deck.
+func DefaultPolicy(salt string) Policy {
+	return Policy{Salt: salt, TimeBucket: DefaultTimeBucket}
+}
+
+var defaultIdentifierKeys = []string{"hand_id", "table_id", "user_id", "agent_id", "agent_version_id", "spectator_id", "market_id"}
+
+// telemetryKeys reveal how a bot behaves rather than what it did at the table.
+var telemetryKeys = map[string]struct{}{
+	"analytics":               {},
+	"is_fallback":             {},
+	"deadline_policy":         {},
+	"think_time_remaining_ms": {},
+	"think_time_budget_ms":    {},
+	"think_time_ms":           {},
+	"time_bank_ms":            {},
+	"telemetry":               {},
+	"timing":                  {},
+	"showdown_preference":     {},
+	"agent_endpoint":          {},
+	"agent_timeout_ms":        {},
+}
+
+var timestampKeys = map[string]struct{}{
+	"at":          {},
+	"started_at":  {},
+	"ended_at":    {},
+	"created_at":  {},
+	"exported_at": {},
+	"left_at":     {},
+	"placed_at":   {},
+	"received_at": {},
+}
+
+type Anonymizer struct {
+	policy      Policy
+	identifiers map[string]struct{}
+}
+
+func New(policy Policy) (*Anonymizer, error) {
+	if strings.TrimSpace(policy.Salt) == "" {
+		return nil, ErrMissingSalt
+	}
+	keys := policy.IdentifierKeys
+	if len(keys) == 0 {
+		keys = defaultIdentifierKeys
+	}
+	identifiers := make(map[string]struct{}, len(keys))
+	for _, key := range keys {
+		identifiers[key] = struct{}{}
+	}
+	return &Anonymizer{policy: policy, identifiers: identifiers}, nil
+}
+
+// Pseudonym returns the stable replacement for one identifier value. The key
+// is mixed in so equal values under different keys do not collide.
+func (a *Anonymizer) Pseudonym(key, value string) string {
+	mac := hmac.New(sha256.New, []byte(a.policy.Salt))
+	mac.Write([]byte(key))
+	mac.Write([]byte{0})
+	mac.Write([]byte(value))
+	prefix := strings.TrimSuffix(key, "_id")
+	return prefix + "_" + hex.EncodeToString(mac.Sum(nil))[:16]
+}
+
+// Document rewrites a single JSON document, e.g. one hand replay.
+func (a *Anonymizer) Document(raw json.RawMessage) (json.RawMessage, error) {
+	decoder := json.NewDecoder(bytes.NewReader(raw))
+	decoder.UseNumber()
+	var doc any
+	if err := decoder.Decode(&doc); err != nil {
+		return nil, fmt.Errorf("decode document: %w", err)
+	}
+	rewritten, err := a.rewrite("", doc)
+	if err != nil {
+		return nil, err
+	}
+	return json.Marshal(rewritten)
+}
+
+func (a *Anonymizer) rewrite(key string, value any) (any, error) {
+	switch typed := value.(type) {
+	case map[string]any:
+		out := make(map[string]any, len(typed))
+		for childKey, child := range typed {
+			if a.drop(childKey) {
+				continue
+			}
+			rewritten, err := a.rewrite(childKey, child)
+			if err != nil {
+				return nil, err
+			}
+			if _, isTimestamp := timestampKeys[childKey]; isTimestamp && rewritten == nil {
+				continue
+			}
+			out[childKey] = rewritten
+		}
+		return out, nil
+	case []any:
+		out := make([]any, 0, len(typed))
+		for _, child := range typed {
+			rewritten, err := a.rewrite(key, child)
+			if err != nil {
+				return nil, err
+			}
+			out = append(out, rewritten)
+		}
+		return out, nil
+	case string:
+		if _, ok := a.identifiers[key]; ok && typed != "" {
+			return a.Pseudonym(key, typed), nil
+		}
+		if _, ok := timestampKeys[key]; ok {
+			return a.bucket(key, typed)
+		}
+		return typed, nil
+	default:
+		return typed, nil
+	}
+}
+
+func (a *Anonymizer) drop(key string) bool {
+	if _, ok := telemetryKeys[key]; ok && !a.policy.KeepTelemetry {
+		return true
+	}
+	return key == "deck" && !a.policy.KeepDeck
+}
+
+func (a *Anonymizer) bucket(key, raw string) (any, error) {
+	if a.policy.TimeBucket <= 0 {
+		return nil, nil
+	}
+	parsed, err := time.Parse(time.RFC3339Nano, raw)
+	if err != nil {
+		return nil, fmt.Errorf("parse %s: %w", key, err)
+	}
+	return parsed.UTC().Truncate(a.policy.TimeBucket).Format(time.RFC3339), nil
+}
@@ -0,0 +1,123 @@
+package anonymize
+
+import (
+	"encoding/json"
+	"errors"
+	"strings"
+	"testing"
+	"time"
+)
+
+const replayFixture = `{
+	"hand_id": "hand-42",
+	"table_id": "table-7",
+	"started_at": "2026-03-01T18:47:12.123Z",
+	"analytics": {"total_actions": 3, "fallback_actions": 1},
+	"final_state": {
+		"hand_id": "hand-42",
+		"pot": 1500,
+		"deck": [{"rank": 2, "suit": "c"}],
+		"think_time_budget_ms": 30000,
+		"seats": [{"seat_no": 1, "stack": 9000, "showdown_preference": "always_show", "think_time_remaining_ms": 1200}]
+	},
+	"actions": [{"hand_id": "hand-42", "action": "call", "is_fallback": true, "at": "2026-03-01T18:47:13Z"}]
+}`
+
+func TestDocument_DefaultPolicy(t *testing.T) {
+	t.Parallel()
+
+	anonymizer := newAnonymizer(t, DefaultPolicy("release-1"))
+	doc := rewriteFixture(t, anonymizer)
+
+	handID := anonymizer.Pseudonym("hand_id", "hand-42")
+	if !strings.HasPrefix(handID, "hand_") || doc["hand_id"] != handID {
+		t.Fatalf("expected pseudonymous hand id %q, got %v", handID, doc["hand_id"])
+	}
+	finalState := doc["final_state"].(map[string]any)
+	if finalState["hand_id"] != handID {
+		t.Fatalf("expected nested hand id to share the pseudonym, got %v", finalState["hand_id"])
+	}
+	if doc["table_id"] == "table-7" {
+		t.Fatal("expected table id to be pseudonymized")
+	}
+	if doc["started_at"] != "2026-03-01T18:00:00Z" {
+		t.Fatalf("expected hourly bucket, got %v", doc["started_at"])
+	}
+
+	if _, ok := doc["analytics"]; ok {
+		t.Fatal("expected analytics to be stripped")
+	}
+	for _, key := range []string{"deck", "think_time_budget_ms"} {
+		if _, ok := finalState[key]; ok {
+			t.Fatalf("expected final_state.%s to be stripped", key)
+		}
+	}
+	seat := finalState["seats"].([]any)[0].(map[string]any)
+	if _, ok := seat["showdown_preference"]; ok {
+		t.Fatal("expected showdown preference to be stripped")
+	}
+	if seat["stack"] != json.Number("9000") {
+		t.Fatalf("expected numbers to survive unchanged, got %v", seat["stack"])
+	}
+	action := doc["actions"].([]any)[0].(map[string]any)
+	if _, ok := action["is_fallback"]; ok {
+		t.Fatal("expected fallback flag to be stripped")
+	}
+}
+
+func TestDocument_PolicyOptions(t *testing.T) {
+	t.Parallel()
+
+	policy := DefaultPolicy("release-1")
+	policy.TimeBucket = 0
+	policy.KeepTelemetry = true
+	policy.IdentifierKeys = []string{"table_id"}
+	doc := rewriteFixture(t, newAnonymizer(t, policy))
+
+	if doc["hand_id"] != "hand-42" {
+		t.Fatalf("expected hand id to be kept, got %v", doc["hand_id"])
+	}
+	if _, ok := doc["started_at"]; ok {
+		t.Fatal("expected timestamps to be dropped with a zero bucket")
+	}
+	if _, ok := doc["analytics"]; !ok {
+		t.Fatal("expected telemetry to be kept")
+	}
+
+	other := newAnonymizer(t, DefaultPolicy("release-2"))
+	if other.Pseudonym("table_id", "table-7") == newAnonymizer(t, policy).Pseudonym("table_id", "table-7") {
+		t.Fatal("expected different salts to produce unlinkable pseudonyms")
+	}
+}
+
+func TestNew_RequiresSalt(t *testing.T) {
+	t.Parallel()
+
+	if _, err := New(Policy{TimeBucket: time.Hour}); !errors.Is(err, ErrMissingSalt) {
+		t.Fatalf("expected ErrMissingSalt, got %v", err)
+	}
+}
+
+func newAnonymizer(t *testing.T, policy Policy) *Anonymizer {
+	t.Helper()
+	anonymizer, err := New(policy)
+	if err != nil {
+		t.Fatalf("New failed: %v", err)
+	}
+	return anonymizer
+}
+
+func rewriteFixture(t *testing.T, anonymizer *Anonymizer) map[string]any {
+	t.Helper()
+	raw, err := anonymizer.Document(json.RawMessage(replayFixture))
+	if err != nil {
+		t.Fatalf("Document failed: %v", err)
+	}
+	var doc map[string]any
+	decoder := json.NewDecoder(strings.NewReader(string(raw)))
+	decoder.UseNumber()
+	if err := decoder.Decode(&doc); err != nil {
+		t.Fatalf("decode failed: %v", err)
+	}
+	return doc
+}
@@ -8,15 +8,35 @@ import (
 	"errors"
 	"fmt"
 	"io"
+	"log"
 	"net/http"
 	"net/url"
+	"sort"
+	"strconv"
 	"strings"
 	"sync"
 	"time"
 
+	"github.com/imaddar/poker-arena/services/engine/internal/agentclient"
+	"github.com/imaddar/poker-arena/services/engine/internal/archive"
+	"github.com/imaddar/poker-arena/services/engine/internal/audience"
+	"github.com/imaddar/poker-arena/services/engine/internal/badbeat"
+	"github.com/imaddar/poker-arena/services/engine/internal/cashier"
+	"github.com/imaddar/poker-arena/services/engine/internal/commentary"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/embargo"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
+	"github.com/imaddar/poker-arena/services/engine/internal/ids"
+	"github.com/imaddar/poker-arena/services/engine/internal/metrics"
 	"github.com/imaddar/poker-arena/services/engine/internal/persistence"
+	"github.com/imaddar/poker-arena/services/engine/internal/retention"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/seatcontrol"
+	"github.com/imaddar/poker-arena/services/engine/internal/sidemarket"
+	"github.com/imaddar/poker-arena/services/engine/internal/statemachine"
+	"github.com/imaddar/poker-arena/services/engine/internal/stats"
 	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+	"github.com/imaddar/poker-arena/services/engine/internal/tournament"
 )
 
 // TODO(postgres): Replace the in-memory repository with a Postgres-backed implementation and migrations.
@@ -26,6 +46,11 @@ import (
 const (
 	maxStartRequestBodyBytes = 1 << 20
 	stopWaitTimeout          = 5 * time.Second
+	defaultStuckHandAfter    = time.Minute
+	maxInterHandDelayMS      = 600_000
+	// controllerGenerationHeader carries the generation a seat's claim
+	// returned, naming the controller a seat request comes from.
+	controllerGenerationHeader = "X-Controller-Generation"
 )
 
 type Runner interface {
@@ -39,6 +64,49 @@ type ServerConfig struct {
 	AllowedCORSOrigins    map[string]struct{}
 	DefaultAgentTimeoutMS uint64
 	AgentHTTPTimeout      time.Duration
+	ArchiveStore          archive.Store
+	Metrics               *metrics.Arena
+	SideMarkets           *sidemarket.Book
+	ReadOnly              bool
+	RatholeWindow         time.Duration
+	// BadBeats checks every all-in hand that reaches showdown and keeps the
+	// bad beats it finds for /tables/{id}/bad-beats.
+	BadBeats *badbeat.Detector
+	// Stalling enables stalling detection at startup; the policy can be
+	// changed at runtime through /stalling/policy. Flagged seats are logged
+	// to StallingLog when it is set.
+	Stalling    *stats.StallingPolicy
+	StallingLog io.Writer
+	// TenantAdminTokens maps admin tokens to the one tenant they manage.
+	// Tenant admins only see and create that tenant's users, agents and
+	// tables; AdminBearerTokens stay deployment-wide.
+	TenantAdminTokens map[string]string
+	// CommentaryLog receives a dealer line for every pot as hands complete,
+	// for chat logs that tail it.
+	CommentaryLog io.Writer
+	// CommentaryOverlay receives a commentary.HandSummary for every completed
+	// hand, pushed in the background so a slow overlay never holds up play.
+	// Pushes are best effort: one that fails or times out is dropped.
+	CommentaryOverlay *commentary.Overlay
+	// IDs names hands and stored resources; nil keeps the built-in random
+	// IDs. Tests pass ids.NewSequence() for stable IDs.
+	IDs ids.Generator
+	// TelemetryEmbargo withholds bot telemetry from action logs, replays and
+	// archives until the period after the table's run has ended.
+	TelemetryEmbargo embargo.Policy
+	// StuckHandAfter is how long a hand may go without an action before the
+	// operator dashboard raises an alert; zero means one minute.
+	StuckHandAfter time.Duration
+	// Retention is how long each class of data is kept once its hand has
+	// ended. POST /retention/purge enforces it; the zero Policy keeps
+	// everything.
+	Retention retention.Policy
+	// Audience counts the spectators watching each table; nil keeps a
+	// tracker that never features a table.
+	Audience *audience.Tracker
+	// Tournaments schedules recurring tournaments for the /tournaments
+	// routes; nil disables them.
+	Tournaments *tournament.Scheduler
 }
 
 type CallerRole string
@@ -49,15 +117,43 @@ const (
 )
 
 type CallerIdentity struct {
-	Role  CallerRole
-	Seat  *domain.SeatNo
-	Token string
+	Role     CallerRole
+	Seat     *domain.SeatNo
+	Token    string
+	TenantID string
 }
 
 type tableRun struct {
 	cancel context.CancelFunc
 	done   chan struct{}
 	status persistence.TableRunRecord
+	// nextHand is set when the run deals on operator trigger; it holds at
+	// most one pending trigger.
+	nextHand chan struct{}
+	// gate holds the run between hands while an operator has it paused.
+	gate *tablerunner.Gate
+	// equity counts the completed hands whose equity sparkline is still
+	// being worked out.
+	equity sync.WaitGroup
+	// live is guarded by Server.mu.
+	live liveHand
+}
+
+// liveHand is what the operator dashboard shows of the hand a run is
+// playing. handID is empty between hands.
+type liveHand struct {
+	handID      string
+	handNo      uint64
+	startedAt   time.Time
+	lastEventAt time.Time
+	// awaiting is set while a decision on state is outstanding; deadline is
+	// zero when the decision has no deadline.
+	awaiting bool
+	state    domain.HandState
+	deadline time.Time
+	// eventLag is how long the latest decision took to reach the event
+	// store after the server received it.
+	eventLag time.Duration
 }
 
 type Server struct {
@@ -65,9 +161,16 @@ type Server struct {
 	runnerFactory   func(provider tablerunner.ActionProvider, cfg tablerunner.RunnerConfig) Runner
 	providerFactory func(tableID string, start StartRequest, cfg ServerConfig) (tablerunner.ActionProvider, error)
 	config          ServerConfig
+	controllers     *seatcontrol.Registry
+	archiver        *archive.Archiver
+	metrics         *metrics.Arena
+	audience        *audience.Tracker
 
-	mu   sync.Mutex
-	runs map[string]*tableRun
+	mu             sync.Mutex
+	runs           map[string]*tableRun
+	stallingPolicy *stats.StallingPolicy
+	stalling       map[string]*stats.StallingTracker
+	connections    map[string]*stats.ConnectionTracker
 }
 
 type StartRequest struct {
@@ -76,14 +179,30 @@ type StartRequest struct {
 	ButtonSeat   *uint8              `json:"button_seat,omitempty"`
 	TableConfig  *domain.TableConfig `json:"table_config,omitempty"`
 	Seats        []StartSeat         `json:"seats"`
+	NextHand     *NextHandRequest    `json:"next_hand,omitempty"`
+	Pacing       *tablerunner.Pacing `json:"pacing,omitempty"`
+}
+
+// NextHandRequest paces a run between hands: "immediate" (the default),
+// "delay" for DelayMS, or "operator", which waits for
+// POST /tables/{id}/next-hand before each hand after the first.
+type NextHandRequest struct {
+	Policy  tablerunner.NextHandPolicy `json:"policy"`
+	DelayMS uint64                     `json:"delay_ms,omitempty"`
 }
 
 type StartSeat struct {
-	SeatNo         uint8             `json:"seat_no"`
-	Stack          uint32            `json:"stack"`
-	Status         domain.SeatStatus `json:"status"`
-	AgentEndpoint  string            `json:"agent_endpoint,omitempty"`
-	AgentTimeoutMS *uint64           `json:"agent_timeout_ms,omitempty"`
+	SeatNo             uint8                     `json:"seat_no"`
+	Stack              domain.Chips              `json:"stack"`
+	Status             domain.SeatStatus         `json:"status"`
+	AgentEndpoint      string                    `json:"agent_endpoint,omitempty"`
+	AgentTimeoutMS     *uint64                   `json:"agent_timeout_ms,omitempty"`
+	ShowdownPreference domain.ShowdownPreference `json:"showdown_preference,omitempty"`
+	BuyInPolicy        *cashier.Policy           `json:"buy_in_policy,omitempty"`
+	View               domain.ViewLevel          `json:"view,omitempty"`
+	// Straddle has the seat post the table's straddle whenever it is in the
+	// straddle position.
+	Straddle bool `json:"straddle,omitempty"`
 }
 
 type tableStatusResponse struct {
@@ -102,13 +221,20 @@ type tableStatusResponse struct {
 }
 
 type tableResponse struct {
-	ID         string    `json:"id"`
-	Name       string    `json:"name"`
-	MaxSeats   uint8     `json:"max_seats"`
-	SmallBlind uint32    `json:"small_blind"`
-	BigBlind   uint32    `json:"big_blind"`
-	Status     string    `json:"status"`
-	CreatedAt  time.Time `json:"created_at"`
+	ID         string           `json:"id"`
+	Name       string           `json:"name"`
+	MaxSeats   uint8            `json:"max_seats"`
+	SmallBlind domain.Chips     `json:"small_blind"`
+	BigBlind   domain.Chips     `json:"big_blind"`
+	Status     string           `json:"status"`
+	CreatedAt  time.Time        `json:"created_at"`
+	TenantID   string           `json:"tenant_id,omitempty"`
+	RuleFlags  domain.RuleFlags `json:"rule_flags,omitempty"`
+	// RulesVersion is the version of the game rules the table is pinned to.
+	RulesVersion domain.RulesVersion `json:"rules_version,omitempty"`
+	Variant      domain.Variant      `json:"variant,omitempty"`
+	// Audience is only filled in for the lobby.
+	Audience *audience.Stats `json:"audience,omitempty"`
 }
 
 type seatResponse struct {
@@ -117,9 +243,12 @@ type seatResponse struct {
 	SeatNo         domain.SeatNo     `json:"seat_no"`
 	AgentID        string            `json:"agent_id"`
 	AgentVersionID string            `json:"agent_version_id"`
-	Stack          uint32            `json:"stack"`
+	Stack          domain.Chips      `json:"stack"`
 	Status         domain.SeatStatus `json:"status"`
+	View           domain.ViewLevel  `json:"view,omitempty"`
 	CreatedAt      time.Time         `json:"created_at"`
+	// RuleFlags advertises the table's house rules to the joining seat.
+	RuleFlags domain.RuleFlags `json:"rule_flags,omitempty"`
 }
 
 type tableRunResponse struct {
@@ -151,29 +280,90 @@ type handResponse struct {
 	EndedAt       *time.Time        `json:"ended_at,omitempty"`
 	FinalPhase    domain.HandPhase  `json:"final_phase"`
 	WinnerSummary []domain.PotAward `json:"winner_summary,omitempty"`
+	IDCreatedAt   *time.Time        `json:"id_created_at,omitempty"`
 }
 
 type actionResponse struct {
-	HandID     string            `json:"hand_id"`
-	Street     domain.Street     `json:"street"`
-	ActingSeat domain.SeatNo     `json:"acting_seat"`
-	Action     domain.ActionKind `json:"action"`
-	Amount     *uint32           `json:"amount,omitempty"`
-	IsFallback bool              `json:"is_fallback"`
-	At         time.Time         `json:"at"`
+	HandID                string                `json:"hand_id"`
+	Street                domain.Street         `json:"street"`
+	ActingSeat            domain.SeatNo         `json:"acting_seat"`
+	Action                domain.ActionKind     `json:"action"`
+	Amount                *domain.Chips         `json:"amount,omitempty"`
+	IsFallback            bool                  `json:"is_fallback"`
+	At                    time.Time             `json:"at"`
+	ReceivedAt            *time.Time            `json:"received_at,omitempty"`
+	DeadlinePolicy        domain.DeadlinePolicy `json:"deadline_policy,omitempty"`
+	ThinkTimeMS           *uint64               `json:"think_time_ms,omitempty"`
+	LatencyCompensationMS uint64                `json:"latency_compensation_ms,omitempty"`
+	Telemetry             *domain.Telemetry     `json:"telemetry,omitempty"`
+}
+
+type validateActionResponse struct {
+	HandID string         `json:"hand_id"`
+	SeatNo domain.SeatNo  `json:"seat_no"`
+	Legal  bool           `json:"legal"`
+	Action *domain.Action `json:"action,omitempty"`
+	Error  string         `json:"error,omitempty"`
+}
+
+// operatorDashboardResponse backs an ops dashboard with one query: every
+// running table, the hands in progress and who they wait on, stuck-hand
+// alerts, event store lag and bot connection health.
+type operatorDashboardResponse struct {
+	GeneratedAt     time.Time               `json:"generated_at"`
+	TablesRunning   int                     `json:"tables_running"`
+	HandsInProgress int                     `json:"hands_in_progress"`
+	EventLagMS      uint64                  `json:"event_lag_ms"`
+	Bots            operatorBotHealth       `json:"bots"`
+	Tables          []operatorTableResponse `json:"tables"`
+	Alerts          []operatorAlert         `json:"alerts"`
+}
+
+type operatorBotHealth struct {
+	Good     int `json:"good"`
+	Degraded int `json:"degraded"`
+	Poor     int `json:"poor"`
+}
+
+type operatorTableResponse struct {
+	TableID        string                    `json:"table_id"`
+	StartedAt      time.Time                 `json:"started_at"`
+	HandsRequested int                       `json:"hands_requested"`
+	HandsCompleted int                       `json:"hands_completed"`
+	Hand           *operatorHandResponse     `json:"hand,omitempty"`
+	Seats          []stats.ConnectionQuality `json:"seats"`
+	Audience       audience.Stats            `json:"audience"`
+}
+
+type operatorHandResponse struct {
+	HandID      string         `json:"hand_id"`
+	HandNo      uint64         `json:"hand_no"`
+	StartedAt   time.Time      `json:"started_at"`
+	LastEventAt time.Time      `json:"last_event_at"`
+	ActingSeat  *domain.SeatNo `json:"acting_seat,omitempty"`
+	// TimeoutRemainingMS goes negative once the deadline has passed.
+	TimeoutRemainingMS *int64 `json:"timeout_remaining_ms,omitempty"`
+}
+
+type operatorAlert struct {
+	TableID string `json:"table_id"`
+	HandID  string `json:"hand_id"`
+	Kind    string `json:"kind"`
+	IdleMS  uint64 `json:"idle_ms"`
 }
 
 type handReplayResponse struct {
-	HandID        string            `json:"hand_id"`
-	TableID       string            `json:"table_id"`
-	HandNo        uint64            `json:"hand_no"`
-	StartedAt     time.Time         `json:"started_at"`
-	EndedAt       *time.Time        `json:"ended_at,omitempty"`
-	FinalPhase    domain.HandPhase  `json:"final_phase"`
-	WinnerSummary []domain.PotAward `json:"winner_summary,omitempty"`
-	FinalState    domain.HandState  `json:"final_state"`
-	Actions       []actionResponse  `json:"actions"`
-	Analytics     replayAnalytics   `json:"analytics"`
+	HandID        string                `json:"hand_id"`
+	TableID       string                `json:"table_id"`
+	HandNo        uint64                `json:"hand_no"`
+	StartedAt     time.Time             `json:"started_at"`
+	EndedAt       *time.Time            `json:"ended_at,omitempty"`
+	FinalPhase    domain.HandPhase      `json:"final_phase"`
+	WinnerSummary []domain.PotAward     `json:"winner_summary,omitempty"`
+	FinalState    domain.HandState      `json:"final_state"`
+	Actions       []actionResponse      `json:"actions"`
+	Analytics     replayAnalytics       `json:"analytics"`
+	Equity        []equity.StreetEquity `json:"equity,omitempty"`
 }
 
 type latestTableReplayResponse struct {
@@ -187,16 +377,29 @@ type replayAnalytics struct {
 	FallbackActions int                   `json:"fallback_actions"`
 	ActionsByStreet map[domain.Street]int `json:"actions_by_street,omitempty"`
 	ActionsBySeat   map[domain.SeatNo]int `json:"actions_by_seat,omitempty"`
+	Timing          []stats.StreetTiming  `json:"timing,omitempty"`
 }
 
 type createUserRequest struct {
-	Name  string `json:"name"`
-	Token string `json:"token"`
+	Name     string `json:"name"`
+	Token    string `json:"token"`
+	TenantID string `json:"tenant_id,omitempty"`
 }
 
-type createAgentRequest struct {
+type deleteUserResponse struct {
 	UserID string `json:"user_id"`
-	Name   string `json:"name"`
+	Alias  string `json:"alias"`
+}
+
+type createAgentRequest struct {
+	UserID   string `json:"user_id"`
+	Name     string `json:"name"`
+	TenantID string `json:"tenant_id,omitempty"`
+}
+
+type agentPreferencesRequest struct {
+	ShowdownPreference string          `json:"showdown_preference"`
+	BuyInPolicy        *cashier.Policy `json:"buy_in_policy,omitempty"`
 }
 
 type createAgentVersionRequest struct {
@@ -205,18 +408,48 @@ type createAgentVersionRequest struct {
 }
 
 type createTableRequest struct {
-	Name       string  `json:"name"`
-	MaxSeats   *uint8  `json:"max_seats,omitempty"`
-	SmallBlind *uint32 `json:"small_blind,omitempty"`
-	BigBlind   *uint32 `json:"big_blind,omitempty"`
+	Name       string           `json:"name"`
+	MaxSeats   *uint8           `json:"max_seats,omitempty"`
+	SmallBlind *domain.Chips    `json:"small_blind,omitempty"`
+	BigBlind   *domain.Chips    `json:"big_blind,omitempty"`
+	TenantID   string           `json:"tenant_id,omitempty"`
+	RuleFlags  domain.RuleFlags `json:"rule_flags,omitempty"`
+	// RulesVersion pins an earlier version of the game rules; the table is
+	// pinned to the current version by default.
+	RulesVersion domain.RulesVersion `json:"rules_version,omitempty"`
+	// Variant is the game the table deals, standard hold'em by default.
+	Variant domain.Variant `json:"variant,omitempty"`
+}
+
+type claimSeatRequest struct {
+	Transport    string `json:"transport"`
+	ControllerID string `json:"controller_id"`
+}
+
+type claimSeatResponse struct {
+	Controller seatcontrol.Controller    `json:"controller"`
+	Handoff    *seatcontrol.HandoffEvent `json:"handoff,omitempty"`
+}
+
+type leaveTableRequest struct {
+	SeatNo uint8         `json:"seat_no"`
+	Stack  *domain.Chips `json:"stack,omitempty"`
+}
+
+type leaveTableResponse struct {
+	TableID string       `json:"table_id"`
+	SeatNo  uint8        `json:"seat_no"`
+	Stack   domain.Chips `json:"stack"`
+	LeftAt  time.Time    `json:"left_at"`
 }
 
 type joinTableRequest struct {
 	SeatNo         uint8             `json:"seat_no"`
 	AgentID        string            `json:"agent_id"`
 	AgentVersionID string            `json:"agent_version_id"`
-	Stack          uint32            `json:"stack"`
+	Stack          domain.Chips      `json:"stack"`
 	Status         domain.SeatStatus `json:"status"`
+	View           domain.ViewLevel  `json:"view,omitempty"`
 }
 
 func NewServer(
@@ -225,13 +458,30 @@ func NewServer(
 	providerFactory func(tableID string, start StartRequest, cfg ServerConfig) (tablerunner.ActionProvider, error),
 	config ServerConfig,
 ) *Server {
-	return &Server{
+	server := &Server{
 		repo:            repo,
 		runnerFactory:   runnerFactory,
 		providerFactory: providerFactory,
 		config:          config,
+		controllers:     seatcontrol.NewRegistry(),
 		runs:            make(map[string]*tableRun),
+		stallingPolicy:  config.Stalling,
+		stalling:        make(map[string]*stats.StallingTracker),
+		connections:     make(map[string]*stats.ConnectionTracker),
+	}
+	if config.ArchiveStore != nil {
+		server.archiver = archive.NewArchiver(config.ArchiveStore, repo)
+		server.archiver.SetEmbargo(config.TelemetryEmbargo)
+	}
+	server.metrics = config.Metrics
+	if server.metrics == nil {
+		server.metrics = metrics.NewArena()
+	}
+	server.audience = config.Audience
+	if server.audience == nil {
+		server.audience = audience.NewTracker(audience.Config{})
 	}
+	return server
 }
 
 func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
@@ -245,6 +495,58 @@ func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
+	if s.config.ReadOnly {
+		// Replicas read a replicated store and never run tables; writes go to
+		// the primary.
+		w.Header().Set("X-Engine-Role", "replica")
+		if r.Method != http.MethodGet {
+			writeError(w, http.StatusMethodNotAllowed, "read-only replica; send writes to the primary")
+			return
+		}
+	}
+
+	if r.URL.Path == "/metrics" {
+		if r.Method != http.MethodGet {
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+			return
+		}
+		if !identity.isDeploymentAdmin() {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		s.handleMetrics(w, r)
+		return
+	}
+
+	if r.URL.Path == "/operator/dashboard" {
+		if r.Method != http.MethodGet {
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+			return
+		}
+		if !identity.isDeploymentAdmin() {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		s.handleOperatorDashboard(w)
+		return
+	}
+
+	if r.URL.Path == "/stalling/policy" {
+		if !identity.isDeploymentAdmin() {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		switch r.Method {
+		case http.MethodGet:
+			writeJSON(w, http.StatusOK, s.currentStallingPolicy())
+		case http.MethodPut:
+			s.handleSetStallingPolicy(w, r)
+		default:
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+		}
+		return
+	}
+
 	if r.URL.Path == "/users" {
 		if r.Method != http.MethodPost {
 			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
@@ -254,7 +556,33 @@ func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 			writeError(w, http.StatusForbidden, "forbidden")
 			return
 		}
-		s.handleCreateUser(w, r)
+		s.handleCreateUser(w, r, identity)
+		return
+	}
+
+	if userID, ok := parseUserRoute(r.URL.Path); ok {
+		if r.Method != http.MethodDelete {
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+			return
+		}
+		if !identity.isDeploymentAdmin() {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		s.handleDeleteUser(w, userID)
+		return
+	}
+
+	if r.URL.Path == "/retention/purge" {
+		if r.Method != http.MethodPost {
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+			return
+		}
+		if !identity.isDeploymentAdmin() {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		s.handleRetentionPurge(w)
 		return
 	}
 
@@ -267,7 +595,7 @@ func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 			writeError(w, http.StatusForbidden, "forbidden")
 			return
 		}
-		s.handleCreateAgent(w, r)
+		s.handleCreateAgent(w, r, identity)
 		return
 	}
 
@@ -280,7 +608,62 @@ func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 			writeError(w, http.StatusForbidden, "forbidden")
 			return
 		}
-		s.handleCreateAgentVersion(w, r, agentID)
+		s.handleCreateAgentVersion(w, r, identity, agentID)
+		return
+	}
+
+	if agentID, ok := parseAgentPreferencesRoute(r.URL.Path); ok {
+		if r.Method != http.MethodPut {
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+			return
+		}
+		if !identity.isAdmin() {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		s.handleAgentPreferences(w, r, identity, agentID)
+		return
+	}
+
+	if r.URL.Path == "/tournaments/templates" {
+		if !identity.isDeploymentAdmin() {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		switch r.Method {
+		case http.MethodPost:
+			s.handleCreateTournamentTemplate(w, r)
+		case http.MethodGet:
+			s.handleListTournamentTemplates(w)
+		default:
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+		}
+		return
+	}
+
+	if r.URL.Path == "/tournaments/calendar" {
+		if r.Method != http.MethodGet {
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+			return
+		}
+		if !identity.isAdmin() {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		s.handleTournamentCalendar(w, r)
+		return
+	}
+
+	if tournamentID, ok := parseTournamentRegistrationRoute(r.URL.Path); ok {
+		if r.Method != http.MethodPost {
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+			return
+		}
+		if !identity.isAdmin() {
+			writeError(w, http.StatusForbidden, "forbidden")
+			return
+		}
+		s.handleTournamentRegistration(w, r, tournamentID)
 		return
 	}
 
@@ -291,25 +674,45 @@ func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 		}
 		switch r.Method {
 		case http.MethodPost:
-			s.handleCreateTable(w, r)
+			s.handleCreateTable(w, r, identity)
 		case http.MethodGet:
-			s.handleListTables(w)
+			s.handleListTables(w, r, identity)
 		default:
 			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
 		}
 		return
 	}
 
+	if spectatorID, ok := parseSpectatorRoute(r.URL.Path); ok {
+		if r.Method != http.MethodGet {
+			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
+			return
+		}
+		s.handleSpectatorAccount(w, spectatorID)
+		return
+	}
+
 	if tableID, ok := parseTableLatestReplayRoute(r.URL.Path); ok {
 		if r.Method != http.MethodGet {
 			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
 			return
 		}
+		if !s.tableInTenant(w, identity, tableID) || !s.seatControllerCurrent(w, r, identity, tableID) {
+			return
+		}
 		s.handleLatestReplay(w, r, identity, tableID)
 		return
 	}
 
 	if tableID, action, ok := parseTableRoute(r.URL.Path); ok {
+		if !s.tableInTenant(w, identity, tableID) {
+			return
+		}
+		// A claim is how a controller takes the seat over, so only a claim
+		// may come from a controller that is not the active one.
+		if action != "claim" && !s.seatControllerCurrent(w, r, identity, tableID) {
+			return
+		}
 		switch {
 		case r.Method == http.MethodPost && action == "start":
 			if !identity.isAdmin() {
@@ -323,6 +726,18 @@ func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 				return
 			}
 			s.handleStop(w, tableID)
+		case r.Method == http.MethodPost && action == "next-hand":
+			if !identity.isAdmin() {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handleNextHand(w, tableID)
+		case r.Method == http.MethodPost && (action == "pause" || action == "resume"):
+			if !identity.isAdmin() {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handlePause(w, tableID, action == "pause")
 		case r.Method == http.MethodGet && action == "status":
 			if !identity.isAdmin() {
 				writeError(w, http.StatusForbidden, "forbidden")
@@ -341,8 +756,68 @@ func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 				return
 			}
 			s.handleJoinTable(w, r, tableID)
+		case r.Method == http.MethodPost && action == "leave":
+			if !identity.isAdmin() {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handleLeaveTable(w, r, tableID)
 		case r.Method == http.MethodGet && action == "hands":
 			s.handleHands(w, identity, tableID)
+		case r.Method == http.MethodGet && action == "rules":
+			s.handleTableRules(w, tableID)
+		case r.Method == http.MethodGet && action == "metrics":
+			s.handleTableMetrics(w, r, tableID)
+		case r.Method == http.MethodGet && action == "opponents":
+			if !identity.isAdmin() && identity.Seat == nil {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handleOpponentStats(w, r, identity, tableID)
+		case r.Method == http.MethodGet && action == "commentary":
+			s.handleTableCommentary(w, r, tableID)
+		case r.Method == http.MethodGet && action == "connections":
+			s.handleTableConnections(w, tableID)
+		case r.Method == http.MethodPost && action == "validate-action":
+			if !identity.isAdmin() && identity.Seat == nil {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handleValidateAction(w, r, identity, tableID)
+		case r.Method == http.MethodPost && action == "claim":
+			if identity.Seat == nil {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handleClaimSeat(w, r, identity, tableID)
+		case r.Method == http.MethodGet && action == "handoffs":
+			if !identity.isAdmin() {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handleHandoffs(w, tableID)
+		case r.Method == http.MethodGet && action == "archive":
+			if !identity.isAdmin() {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handleTableArchive(w, r, tableID)
+		case r.Method == http.MethodGet && action == "markets":
+			s.handleTableMarkets(w, tableID)
+		case r.Method == http.MethodGet && action == "bad-beats":
+			s.handleTableBadBeats(w, tableID)
+		case r.Method == http.MethodPost && action == "wagers":
+			if identity.Seat != nil {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handlePlaceWager(w, r, tableID)
+		case r.Method == http.MethodPost && action == "watch":
+			if identity.Seat != nil {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+			s.handleWatchTable(w, r, tableID)
 		default:
 			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
 		}
@@ -350,11 +825,16 @@ func (s *Server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
 	}
 
 	if handID, action, ok := parseHandRoute(r.URL.Path); ok {
+		if !s.handInTenant(w, identity, handID) || !s.handControllerCurrent(w, r, identity, handID) {
+			return
+		}
 		switch {
 		case r.Method == http.MethodGet && action == "actions":
 			s.handleActions(w, identity, handID)
 		case r.Method == http.MethodGet && action == "replay":
 			s.handleReplay(w, r, identity, handID)
+		case r.Method == http.MethodGet && action == "runout-tree":
+			s.handleRunoutTree(w, identity, handID)
 		default:
 			writeError(w, http.StatusMethodNotAllowed, "method not allowed")
 		}
@@ -405,6 +885,7 @@ func (s *Server) handleStart(w http.ResponseWriter, r *http.Request, tableID str
 		writeError(w, http.StatusBadRequest, err.Error())
 		return
 	}
+	buyIns := startCashier(resolvedReq)
 
 	s.mu.Lock()
 	if _, exists := s.runs[tableID]; exists {
@@ -416,6 +897,7 @@ func (s *Server) handleStart(w http.ResponseWriter, r *http.Request, tableID str
 	run := &tableRun{
 		cancel: cancel,
 		done:   make(chan struct{}),
+		gate:   &tablerunner.Gate{},
 		status: persistence.TableRunRecord{
 			TableID:        tableID,
 			Status:         persistence.TableRunStatusRunning,
@@ -424,6 +906,15 @@ func (s *Server) handleStart(w http.ResponseWriter, r *http.Request, tableID str
 			CurrentHandNo:  input.StartingHand,
 		},
 	}
+	var nextHand tablerunner.NextHandPolicy
+	var interHandDelay time.Duration
+	if resolvedReq.NextHand != nil {
+		nextHand = resolvedReq.NextHand.Policy
+		interHandDelay = time.Duration(resolvedReq.NextHand.DelayMS) * time.Millisecond
+	}
+	if nextHand == tablerunner.NextHandOnTrigger {
+		run.nextHand = make(chan struct{}, 1)
+	}
 	s.runs[tableID] = run
 	s.mu.Unlock()
 
@@ -442,41 +933,108 @@ func (s *Server) handleStart(w http.ResponseWriter, r *http.Request, tableID str
 		return
 	}
 
+	provider = instrumentedProvider{
+		inner:   provider,
+		metrics: s.metrics,
+		timeout: time.Duration(config.ActionTimeoutMS) * time.Millisecond,
+		await: func(state domain.HandState, deadline time.Time) {
+			state = cloneHandStateForReplay(state)
+			s.updateLiveHand(run, func(live *liveHand) {
+				live.awaiting = true
+				live.state = state
+				live.deadline = deadline
+			})
+		},
+	}
+
 	handStartedAtByID := make(map[string]time.Time)
+	allInHands := make(map[string]struct{})
+	foldStreets := make(map[string]map[domain.SeatNo]domain.Street)
 	runner := s.runnerFactory(provider, tablerunner.RunnerConfig{
+		OnAllIn: func(_ uint64, state domain.HandState) {
+			allInHands[state.HandID] = struct{}{}
+			if s.config.SideMarkets != nil {
+				s.config.SideMarkets.Open(state)
+			}
+		},
+		ThinkTimePenaltyMS: func(seat domain.SeatNo) uint64 {
+			return s.stallingPenaltyMS(tableID, seat)
+		},
+		TopUp:           buyIns.TopUp,
+		HandIDs:         s.config.IDs,
+		NextHand:        nextHand,
+		InterHandDelay:  interHandDelay,
+		NextHandTrigger: run.nextHand,
+		Pacing:          resolvedReq.Pacing,
+		Gate:            run.gate,
 		OnHandStart: func(_ tablerunner.RunHandInput, initial domain.HandState) {
+			s.metrics.HandsDealt.Inc()
 			startedAt := time.Now().UTC()
-			if repoErr := s.repo.CreateHand(persistence.HandRecord{
+			repoErr := s.repo.CreateHand(persistence.HandRecord{
 				HandID:     initial.HandID,
 				TableID:    initial.TableID,
 				HandNo:     initial.HandNo,
 				StartedAt:  startedAt,
 				FinalPhase: initial.Phase,
 				FinalState: initial,
-			}); repoErr != nil {
+			})
+			metrics.ObserveSince(s.metrics.EventStoreLatency, startedAt, "create_hand")
+			if repoErr != nil {
 				s.failRun(tableID, run, fmt.Errorf("create hand record: %w", repoErr))
 				return
 			}
 			handStartedAtByID[initial.HandID] = startedAt
+			s.updateLiveHand(run, func(live *liveHand) {
+				*live = liveHand{
+					handID:      initial.HandID,
+					handNo:      initial.HandNo,
+					startedAt:   startedAt,
+					lastEventAt: startedAt,
+					eventLag:    live.eventLag,
+				}
+			})
 			run.status.CurrentHandNo = initial.HandNo
 			if repoErr := s.repo.UpsertTableRun(run.status); repoErr != nil {
 				s.failRun(tableID, run, fmt.Errorf("update run on hand start: %w", repoErr))
 			}
 		},
-		OnAction: func(_ uint64, state domain.HandState, action domain.Action, isFallback bool) {
+		OnAction: func(_ uint64, state domain.HandState, action domain.Action, isFallback bool, timing tablerunner.ActionTiming) {
+			s.metrics.Actions.Inc(string(action.Kind), strconv.FormatBool(isFallback))
+			s.observeStalling(tableID, state, timing)
+			s.observeConnection(tableID, state.ActingSeat, timing)
 			record := persistence.ActionRecord{
-				HandID:     state.HandID,
-				Street:     state.Street,
-				ActingSeat: state.ActingSeat,
-				Action:     action.Kind,
-				IsFallback: isFallback,
-				At:         time.Now().UTC(),
+				HandID:                state.HandID,
+				Street:                state.Street,
+				ActingSeat:            state.ActingSeat,
+				Action:                action.Kind,
+				IsFallback:            isFallback,
+				At:                    time.Now().UTC(),
+				ReceivedAt:            timing.ReceivedAt,
+				DeadlinePolicy:        timing.Policy,
+				ThinkTimeMS:           uint64(max(timing.ThinkTime, 0).Milliseconds()),
+				Telemetry:             action.Telemetry,
+				LatencyCompensationMS: uint64(timing.LatencyCompensation.Milliseconds()),
 			}
 			if action.Amount != nil {
 				amount := *action.Amount
 				record.Amount = &amount
 			}
-			if repoErr := s.repo.AppendAction(record); repoErr != nil {
+			if action.Kind == domain.ActionFold {
+				if foldStreets[state.HandID] == nil {
+					foldStreets[state.HandID] = make(map[domain.SeatNo]domain.Street)
+				}
+				foldStreets[state.HandID][state.ActingSeat] = state.Street
+			}
+			repoErr := s.repo.AppendAction(record)
+			metrics.ObserveSince(s.metrics.EventStoreLatency, record.At, "append_action")
+			s.updateLiveHand(run, func(live *liveHand) {
+				live.awaiting = false
+				live.lastEventAt = record.At
+				if !timing.ReceivedAt.IsZero() {
+					live.eventLag = time.Since(timing.ReceivedAt)
+				}
+			})
+			if repoErr != nil {
 				s.failRun(tableID, run, fmt.Errorf("append action record: %w", repoErr))
 			}
 		},
@@ -487,7 +1045,16 @@ func (s *Server) handleStart(w http.ResponseWriter, r *http.Request, tableID str
 				startedAt = run.status.StartedAt
 			}
 			delete(handStartedAtByID, summary.FinalState.HandID)
-			if repoErr := s.repo.CompleteHand(summary.FinalState.HandID, persistence.HandRecord{
+			s.updateLiveHand(run, func(live *liveHand) {
+				*live = liveHand{eventLag: live.eventLag}
+			})
+			s.metrics.HandsCompleted.Inc(string(summary.FinalPhase))
+			if s.config.SideMarkets != nil {
+				_, _, _ = s.config.SideMarkets.Settle(summary.FinalState)
+			}
+			folds := foldStreets[summary.FinalState.HandID]
+			delete(foldStreets, summary.FinalState.HandID)
+			repoErr := s.repo.CompleteHand(summary.FinalState.HandID, persistence.HandRecord{
 				HandID:        summary.FinalState.HandID,
 				TableID:       summary.FinalState.TableID,
 				HandNo:        summary.HandNo,
@@ -496,10 +1063,26 @@ func (s *Server) handleStart(w http.ResponseWriter, r *http.Request, tableID str
 				FinalPhase:    summary.FinalPhase,
 				FinalState:    summary.FinalState,
 				WinnerSummary: append([]domain.PotAward(nil), summary.FinalState.ShowdownAwards...),
-			}); repoErr != nil {
+			})
+			metrics.ObserveSince(s.metrics.EventStoreLatency, endedAt, "complete_hand")
+			if repoErr != nil {
 				s.failRun(tableID, run, fmt.Errorf("complete hand record: %w", repoErr))
 				return
 			}
+			run.equity.Add(1)
+			go s.storeEquity(run, summary.FinalState, folds)
+			if _, ok := allInHands[summary.FinalState.HandID]; ok && s.config.BadBeats != nil {
+				s.checkBadBeats(summary.FinalState)
+			}
+			delete(allInHands, summary.FinalState.HandID)
+			if s.config.CommentaryLog != nil {
+				for _, line := range commentary.Narrate(summary.FinalState) {
+					fmt.Fprintf(s.config.CommentaryLog, "table=%s hand=%d %s\n", tableID, line.HandNo, line.Text)
+				}
+			}
+			if s.config.CommentaryOverlay != nil {
+				s.pushOverlaySummary(summary.FinalState)
+			}
 			run.status.HandsCompleted++
 			run.status.TotalActions += summary.ActionCount
 			run.status.TotalFallbacks += summary.FallbackCount
@@ -524,11 +1107,16 @@ func (s *Server) handleStart(w http.ResponseWriter, r *http.Request, tableID str
 	})
 }
 
-func (s *Server) handleCreateUser(w http.ResponseWriter, r *http.Request) {
+func (s *Server) handleCreateUser(w http.ResponseWriter, r *http.Request, identity CallerIdentity) {
 	var req createUserRequest
 	if ok := decodeStrictJSON(w, r, &req); !ok {
 		return
 	}
+	tenantID, ok := requestTenant(identity, req.TenantID)
+	if !ok {
+		writeError(w, http.StatusForbidden, "forbidden")
+		return
+	}
 	req.Name = strings.TrimSpace(req.Name)
 	req.Token = strings.TrimSpace(req.Token)
 	if req.Name == "" || req.Token == "" {
@@ -536,7 +1124,8 @@ func (s *Server) handleCreateUser(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 	record := persistence.UserRecord{
-		ID:        newID("user"),
+		ID:        s.newID("user"),
+		TenantID:  tenantID,
 		Name:      req.Name,
 		Token:     req.Token,
 		CreatedAt: time.Now().UTC(),
@@ -548,11 +1137,41 @@ func (s *Server) handleCreateUser(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusOK, record)
 }
 
-func (s *Server) handleCreateAgent(w http.ResponseWriter, r *http.Request) {
+// handleDeleteUser scrubs a user's personal identifiers. The user keeps its
+// ID, under a fresh alias, so the hands and standings it played in still
+// add up.
+func (s *Server) handleDeleteUser(w http.ResponseWriter, userID string) {
+	alias := s.newID("deleted")
+	if err := s.repo.ScrubUser(userID, alias); err != nil {
+		if errors.Is(err, persistence.ErrUserNotFound) {
+			writeError(w, http.StatusNotFound, "user not found")
+			return
+		}
+		writeError(w, http.StatusInternalServerError, "failed to delete user")
+		return
+	}
+	writeJSON(w, http.StatusOK, deleteUserResponse{UserID: userID, Alias: alias})
+}
+
+func (s *Server) handleRetentionPurge(w http.ResponseWriter) {
+	report, err := retention.Enforce(s.repo, s.config.Retention, time.Now().UTC())
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to enforce retention")
+		return
+	}
+	writeJSON(w, http.StatusOK, report)
+}
+
+func (s *Server) handleCreateAgent(w http.ResponseWriter, r *http.Request, identity CallerIdentity) {
 	var req createAgentRequest
 	if ok := decodeStrictJSON(w, r, &req); !ok {
 		return
 	}
+	tenantID, ok := requestTenant(identity, req.TenantID)
+	if !ok {
+		writeError(w, http.StatusForbidden, "forbidden")
+		return
+	}
 	req.UserID = strings.TrimSpace(req.UserID)
 	req.Name = strings.TrimSpace(req.Name)
 	if req.UserID == "" || req.Name == "" {
@@ -560,7 +1179,8 @@ func (s *Server) handleCreateAgent(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 	record := persistence.AgentRecord{
-		ID:        newID("agent"),
+		ID:        s.newID("agent"),
+		TenantID:  tenantID,
 		UserID:    req.UserID,
 		Name:      req.Name,
 		CreatedAt: time.Now().UTC(),
@@ -576,11 +1196,20 @@ func (s *Server) handleCreateAgent(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusOK, record)
 }
 
-func (s *Server) handleCreateAgentVersion(w http.ResponseWriter, r *http.Request, agentID string) {
+func (s *Server) handleCreateAgentVersion(w http.ResponseWriter, r *http.Request, identity CallerIdentity, agentID string) {
 	var req createAgentVersionRequest
 	if ok := decodeStrictJSON(w, r, &req); !ok {
 		return
 	}
+	visible, err := s.agentInTenant(identity, agentID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load agent")
+		return
+	}
+	if !visible {
+		writeError(w, http.StatusBadRequest, "agent not found")
+		return
+	}
 	req.EndpointURL = strings.TrimSpace(req.EndpointURL)
 	parsedEndpoint, err := url.Parse(req.EndpointURL)
 	if err != nil || parsedEndpoint == nil || parsedEndpoint.Host == "" {
@@ -606,7 +1235,7 @@ func (s *Server) handleCreateAgentVersion(w http.ResponseWriter, r *http.Request
 	var created persistence.AgentVersionRecord
 	for version := 1; version <= 10_000; version++ {
 		candidate := persistence.AgentVersionRecord{
-			ID:          newID("version"),
+			ID:          s.newID("version"),
 			AgentID:     agentID,
 			Version:     version,
 			EndpointURL: req.EndpointURL,
@@ -635,11 +1264,61 @@ func (s *Server) handleCreateAgentVersion(w http.ResponseWriter, r *http.Request
 	writeJSON(w, http.StatusOK, created)
 }
 
-func (s *Server) handleCreateTable(w http.ResponseWriter, r *http.Request) {
+func (s *Server) handleAgentPreferences(w http.ResponseWriter, r *http.Request, identity CallerIdentity, agentID string) {
+	var req agentPreferencesRequest
+	if ok := decodeStrictJSON(w, r, &req); !ok {
+		return
+	}
+	visible, err := s.agentInTenant(identity, agentID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load agent")
+		return
+	}
+	if !visible {
+		writeError(w, http.StatusNotFound, "agent not found")
+		return
+	}
+	pref, err := domain.ParseShowdownPreference(strings.TrimSpace(req.ShowdownPreference))
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	if req.BuyInPolicy != nil {
+		if err := req.BuyInPolicy.Validate(); err != nil {
+			writeError(w, http.StatusBadRequest, err.Error())
+			return
+		}
+	}
+	if err := s.repo.SetAgentShowdownPreference(agentID, pref); err != nil {
+		if errors.Is(err, persistence.ErrAgentNotFound) {
+			writeError(w, http.StatusNotFound, "agent not found")
+			return
+		}
+		writeError(w, http.StatusInternalServerError, "failed to update agent preferences")
+		return
+	}
+	if err := s.repo.SetAgentBuyInPolicy(agentID, req.BuyInPolicy); err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to update agent preferences")
+		return
+	}
+	agent, ok, err := s.repo.GetAgent(agentID)
+	if err != nil || !ok {
+		writeError(w, http.StatusInternalServerError, "failed to load agent")
+		return
+	}
+	writeJSON(w, http.StatusOK, agent)
+}
+
+func (s *Server) handleCreateTable(w http.ResponseWriter, r *http.Request, identity CallerIdentity) {
 	var req createTableRequest
 	if ok := decodeStrictJSON(w, r, &req); !ok {
 		return
 	}
+	tenantID, ok := requestTenant(identity, req.TenantID)
+	if !ok {
+		writeError(w, http.StatusForbidden, "forbidden")
+		return
+	}
 
 	cfg := domain.DefaultV0TableConfig()
 	if req.MaxSeats != nil {
@@ -651,19 +1330,26 @@ func (s *Server) handleCreateTable(w http.ResponseWriter, r *http.Request) {
 	if req.BigBlind != nil {
 		cfg.BigBlind = *req.BigBlind
 	}
+	cfg.RuleFlags = req.RuleFlags
+	cfg.RulesVersion = req.RulesVersion.Effective()
+	cfg.Variant = req.Variant.Effective()
 	if err := cfg.Validate(); err != nil {
 		writeError(w, http.StatusBadRequest, err.Error())
 		return
 	}
 
 	record := persistence.TableRecord{
-		ID:         newID("table"),
-		Name:       strings.TrimSpace(req.Name),
-		MaxSeats:   cfg.MaxSeats,
-		SmallBlind: cfg.SmallBlind,
-		BigBlind:   cfg.BigBlind,
-		Status:     string(persistence.TableRunStatusIdle),
-		CreatedAt:  time.Now().UTC(),
+		ID:           s.newID("table"),
+		TenantID:     tenantID,
+		Name:         strings.TrimSpace(req.Name),
+		MaxSeats:     cfg.MaxSeats,
+		SmallBlind:   cfg.SmallBlind,
+		BigBlind:     cfg.BigBlind,
+		RuleFlags:    cfg.RuleFlags,
+		RulesVersion: cfg.RulesVersion,
+		Variant:      cfg.Variant,
+		Status:       string(persistence.TableRunStatusIdle),
+		CreatedAt:    time.Now().UTC(),
 	}
 	if record.Name == "" {
 		record.Name = record.ID
@@ -675,15 +1361,27 @@ func (s *Server) handleCreateTable(w http.ResponseWriter, r *http.Request) {
 	writeJSON(w, http.StatusOK, mapTableRecordToResponse(record))
 }
 
-func (s *Server) handleListTables(w http.ResponseWriter) {
-	tables, err := s.repo.ListTables()
+func (s *Server) handleListTables(w http.ResponseWriter, r *http.Request, identity CallerIdentity) {
+	tenantID, ok := requestTenant(identity, r.URL.Query().Get("tenant_id"))
+	if !ok {
+		writeError(w, http.StatusForbidden, "forbidden")
+		return
+	}
+	tables, err := s.repo.ListTables()
 	if err != nil {
 		writeError(w, http.StatusInternalServerError, "failed to list tables")
 		return
 	}
+	audiences := s.audience.Tables()
 	response := make([]tableResponse, 0, len(tables))
 	for _, table := range tables {
-		response = append(response, mapTableRecordToResponse(table))
+		if tenantID != "" && table.TenantID != tenantID {
+			continue
+		}
+		item := mapTableRecordToResponse(table)
+		watched := audiences[table.ID]
+		item.Audience = &watched
+		response = append(response, item)
 	}
 	writeJSON(w, http.StatusOK, response)
 }
@@ -718,20 +1416,43 @@ func (s *Server) handleJoinTable(w http.ResponseWriter, r *http.Request, tableID
 		writeError(w, http.StatusBadRequest, "stack must be greater than zero")
 		return
 	}
+	if _, err := domain.ParseViewLevel(string(req.View)); err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
 	record := persistence.SeatRecord{
-		ID:             newID("seat"),
+		ID:             s.newID("seat"),
 		TableID:        tableID,
 		SeatNo:         seatNo,
 		AgentID:        strings.TrimSpace(req.AgentID),
 		AgentVersionID: strings.TrimSpace(req.AgentVersionID),
 		Stack:          req.Stack,
 		Status:         req.Status,
+		View:           req.View,
 		CreatedAt:      time.Now().UTC(),
 	}
 	if record.AgentID == "" || record.AgentVersionID == "" {
 		writeError(w, http.StatusBadRequest, "agent_id and agent_version_id are required")
 		return
 	}
+	agent, found, err := s.repo.GetAgent(record.AgentID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load agent")
+		return
+	}
+	if found && agent.TenantID != tableRecord.TenantID {
+		writeError(w, http.StatusBadRequest, "agent not found")
+		return
+	}
+	minimum, err := s.ratholeMinimum(tableRecord, record.AgentID, record.CreatedAt)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load departures")
+		return
+	}
+	if record.Stack < minimum {
+		writeError(w, http.StatusBadRequest, fmt.Sprintf("player left these stakes recently and must buy in for at least %d", minimum))
+		return
+	}
 	if err := s.repo.UpsertSeat(record); err != nil {
 		switch {
 		case errors.Is(err, persistence.ErrTableNotFound):
@@ -745,7 +1466,96 @@ func (s *Server) handleJoinTable(w http.ResponseWriter, r *http.Request, tableID
 		}
 		return
 	}
-	writeJSON(w, http.StatusOK, mapSeatRecordToResponse(record))
+	response := mapSeatRecordToResponse(record)
+	response.RuleFlags = tableRecord.RuleFlags
+	writeJSON(w, http.StatusOK, response)
+}
+
+func (s *Server) handleLeaveTable(w http.ResponseWriter, r *http.Request, tableID string) {
+	var req leaveTableRequest
+	if ok := decodeStrictJSON(w, r, &req); !ok {
+		return
+	}
+	tableRecord, ok, err := s.repo.GetTable(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table")
+		return
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "table not found")
+		return
+	}
+	seatNo, err := domain.NewSeatNo(req.SeatNo, tableRecord.MaxSeats)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	s.mu.Lock()
+	_, running := s.runs[tableID]
+	s.mu.Unlock()
+	if running {
+		writeError(w, http.StatusConflict, "table is running")
+		return
+	}
+
+	seat, err := s.repo.RemoveSeat(tableID, seatNo)
+	if err != nil {
+		if errors.Is(err, persistence.ErrSeatNotFound) {
+			writeError(w, http.StatusNotFound, "seat not found")
+			return
+		}
+		writeError(w, http.StatusInternalServerError, "failed to leave table")
+		return
+	}
+	departure := persistence.DepartureRecord{
+		TableID:    tableID,
+		AgentID:    seat.AgentID,
+		SmallBlind: tableRecord.SmallBlind,
+		BigBlind:   tableRecord.BigBlind,
+		Stack:      seat.Stack,
+		LeftAt:     time.Now().UTC(),
+	}
+	if req.Stack != nil {
+		departure.Stack = *req.Stack
+	}
+	agent, ok, err := s.repo.GetAgent(seat.AgentID)
+	if err != nil || !ok {
+		writeError(w, http.StatusInternalServerError, "failed to load agent")
+		return
+	}
+	departure.UserID = agent.UserID
+	if err := s.repo.RecordDeparture(departure); err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to record departure")
+		return
+	}
+	writeJSON(w, http.StatusOK, leaveTableResponse{
+		TableID: tableID,
+		SeatNo:  uint8(seatNo),
+		Stack:   departure.Stack,
+		LeftAt:  departure.LeftAt,
+	})
+}
+
+// ratholeMinimum is the smallest buy-in allowed for the agent's owner: their
+// last departure stack at the same stakes if it falls inside RatholeWindow,
+// otherwise zero.
+func (s *Server) ratholeMinimum(table persistence.TableRecord, agentID string, now time.Time) (domain.Chips, error) {
+	if s.config.RatholeWindow <= 0 {
+		return 0, nil
+	}
+	agent, ok, err := s.repo.GetAgent(agentID)
+	if err != nil || !ok {
+		// UpsertSeat reports unknown agents.
+		return 0, err
+	}
+	departure, ok, err := s.repo.LatestDeparture(agent.UserID, table.SmallBlind, table.BigBlind)
+	if err != nil || !ok {
+		return 0, err
+	}
+	if now.Sub(departure.LeftAt) >= s.config.RatholeWindow {
+		return 0, nil
+	}
+	return departure.Stack, nil
 }
 
 func (s *Server) handleTableState(w http.ResponseWriter, tableID string) {
@@ -773,15 +1583,12 @@ func (s *Server) handleTableState(w http.ResponseWriter, tableID string) {
 		writeError(w, http.StatusInternalServerError, "failed to load hands")
 		return
 	}
-	actionCount := 0
-	for _, hand := range hands {
-		actions, listErr := s.repo.ListActions(hand.HandID)
-		if listErr != nil {
-			writeError(w, http.StatusInternalServerError, "failed to load actions")
-			return
-		}
-		actionCount += len(actions)
+	actions, err := s.repo.ListTableActions(tableID, time.Time{})
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load actions")
+		return
 	}
+	actionCount := len(actions)
 	seatItems := make([]seatResponse, 0, len(seats))
 	for _, seat := range seats {
 		seatItems = append(seatItems, mapSeatRecordToResponse(seat))
@@ -822,8 +1629,20 @@ func (s *Server) hydrateStartRequest(tableID string, req StartRequest) (StartReq
 		cfg.MaxSeats = tableRecord.MaxSeats
 		cfg.SmallBlind = tableRecord.SmallBlind
 		cfg.BigBlind = tableRecord.BigBlind
+		cfg.RuleFlags = tableRecord.RuleFlags
 		req.TableConfig = &cfg
 	}
+	// A table plays the rules it was created under for as long as it runs.
+	if version := req.TableConfig.RulesVersion; version != 0 && version != tableRecord.RulesVersion.Effective() {
+		return StartRequest{}, http.StatusBadRequest, fmt.Errorf("table is pinned to rules version %d, got %d", tableRecord.RulesVersion.Effective(), version)
+	}
+	if variant := req.TableConfig.Variant; variant != "" && variant != tableRecord.Variant.Effective() {
+		return StartRequest{}, http.StatusBadRequest, fmt.Errorf("table deals %s, got %s", tableRecord.Variant.Effective(), variant)
+	}
+	cfg := *req.TableConfig
+	cfg.RulesVersion = tableRecord.RulesVersion.Effective()
+	cfg.Variant = tableRecord.Variant.Effective()
+	req.TableConfig = &cfg
 	req.Seats = make([]StartSeat, 0, len(seats))
 	for _, seat := range seats {
 		version, ok, getErr := s.repo.GetAgentVersion(seat.AgentVersionID)
@@ -833,16 +1652,38 @@ func (s *Server) hydrateStartRequest(tableID string, req StartRequest) (StartReq
 		if !ok {
 			return StartRequest{}, http.StatusBadRequest, fmt.Errorf("agent version %s not found", seat.AgentVersionID)
 		}
+		agent, ok, getErr := s.repo.GetAgent(seat.AgentID)
+		if getErr != nil {
+			return StartRequest{}, http.StatusInternalServerError, fmt.Errorf("failed to load agent")
+		}
+		if !ok {
+			return StartRequest{}, http.StatusBadRequest, fmt.Errorf("agent %s not found", seat.AgentID)
+		}
 		req.Seats = append(req.Seats, StartSeat{
-			SeatNo:        uint8(seat.SeatNo),
-			Stack:         seat.Stack,
-			Status:        seat.Status,
-			AgentEndpoint: version.EndpointURL,
+			SeatNo:             uint8(seat.SeatNo),
+			Stack:              seat.Stack,
+			Status:             seat.Status,
+			AgentEndpoint:      version.EndpointURL,
+			ShowdownPreference: agent.ShowdownPreference,
+			BuyInPolicy:        agent.BuyInPolicy,
+			View:               seat.View,
 		})
 	}
 	return req, http.StatusOK, nil
 }
 
+// startCashier registers the buy-in policies of the seats being started.
+// validateStartRequest has already rejected invalid policies.
+func startCashier(req StartRequest) *cashier.Cashier {
+	buyIns := cashier.New()
+	for _, seat := range req.Seats {
+		if seat.BuyInPolicy != nil {
+			_ = buyIns.Register(domain.SeatNo(seat.SeatNo), *seat.BuyInPolicy)
+		}
+	}
+	return buyIns
+}
+
 func (s *Server) handleStop(w http.ResponseWriter, tableID string) {
 	s.mu.Lock()
 	run, ok := s.runs[tableID]
@@ -876,6 +1717,54 @@ func (s *Server) handleStop(w http.ResponseWriter, tableID string) {
 	})
 }
 
+func (s *Server) handleNextHand(w http.ResponseWriter, tableID string) {
+	s.mu.Lock()
+	run, ok := s.runs[tableID]
+	s.mu.Unlock()
+	if !ok {
+		writeError(w, http.StatusConflict, "table is not running")
+		return
+	}
+	if run.nextHand == nil {
+		writeError(w, http.StatusConflict, "table does not wait for an operator between hands")
+		return
+	}
+	select {
+	case run.nextHand <- struct{}{}:
+	default:
+		// A trigger is already pending; the next hand will be dealt once.
+	}
+	writeJSON(w, http.StatusAccepted, map[string]string{
+		"table_id": tableID,
+		"status":   "next_hand_queued",
+	})
+}
+
+// handlePause pauses a running table before its next deal, or resumes it.
+// A hand live when the table is paused is played out.
+func (s *Server) handlePause(w http.ResponseWriter, tableID string, pause bool) {
+	s.mu.Lock()
+	run, ok := s.runs[tableID]
+	s.mu.Unlock()
+	if !ok {
+		writeError(w, http.StatusConflict, "table is not running")
+		return
+	}
+	if pause {
+		if !run.gate.Pause() {
+			writeError(w, http.StatusConflict, "table is already paused")
+			return
+		}
+		writeJSON(w, http.StatusOK, map[string]string{"table_id": tableID, "status": "paused"})
+		return
+	}
+	if !run.gate.Resume() {
+		writeError(w, http.StatusConflict, "table is not paused")
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]string{"table_id": tableID, "status": "resumed"})
+}
+
 func (s *Server) handleStatus(w http.ResponseWriter, tableID string) {
 	record, ok, err := s.repo.GetTableRun(tableID)
 	if err != nil {
@@ -883,39 +1772,819 @@ func (s *Server) handleStatus(w http.ResponseWriter, tableID string) {
 		return
 	}
 	if !ok {
-		writeError(w, http.StatusNotFound, "table status not found")
+		writeError(w, http.StatusNotFound, "table status not found")
+		return
+	}
+
+	hands, err := s.repo.ListHands(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load hands")
+		return
+	}
+	actions, err := s.repo.ListTableActions(tableID, time.Time{})
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load actions")
+		return
+	}
+	actionCount := len(actions)
+
+	writeJSON(w, http.StatusOK, tableStatusResponse{
+		TableID:          record.TableID,
+		Status:           record.Status,
+		StartedAt:        record.StartedAt,
+		EndedAt:          record.EndedAt,
+		Error:            record.Error,
+		HandsRequested:   record.HandsRequested,
+		HandsCompleted:   record.HandsCompleted,
+		TotalActions:     record.TotalActions,
+		TotalFallbacks:   record.TotalFallbacks,
+		CurrentHandNo:    record.CurrentHandNo,
+		HandsPersisted:   len(hands),
+		ActionsPersisted: actionCount,
+	})
+}
+
+func (s *Server) handleTableRules(w http.ResponseWriter, tableID string) {
+	tableRecord, ok, err := s.repo.GetTable(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table")
+		return
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "table not found")
+		return
+	}
+
+	ranking, err := rules.RankingRulesFor(tableRecord.Variant.Effective())
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load ranking rules")
+		return
+	}
+	writeJSON(w, http.StatusOK, ranking)
+}
+
+// handleTableCommentary lists dealer lines for the table's completed hands.
+// Overlays poll with since_hand to receive only hands finished after the
+// last one they showed.
+func (s *Server) handleTableCommentary(w http.ResponseWriter, r *http.Request, tableID string) {
+	var sinceHand uint64
+	if raw := strings.TrimSpace(r.URL.Query().Get("since_hand")); raw != "" {
+		parsed, err := strconv.ParseUint(raw, 10, 64)
+		if err != nil {
+			writeError(w, http.StatusBadRequest, "since_hand must be a hand number")
+			return
+		}
+		sinceHand = parsed
+	}
+
+	_, ok, err := s.repo.GetTable(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table")
+		return
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "table not found")
+		return
+	}
+
+	hands, err := s.repo.ListHands(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load hands")
+		return
+	}
+	lines := []commentary.Line{}
+	for _, hand := range hands {
+		if hand.EndedAt == nil || hand.HandNo <= sinceHand {
+			continue
+		}
+		lines = append(lines, commentary.Narrate(hand.FinalState)...)
+	}
+	writeJSON(w, http.StatusOK, lines)
+}
+
+func (s *Server) handleTableMetrics(w http.ResponseWriter, r *http.Request, tableID string) {
+	window := stats.DefaultMetricsWindow
+	if raw := strings.TrimSpace(r.URL.Query().Get("window")); raw != "" {
+		parsed, err := time.ParseDuration(raw)
+		if err != nil || parsed <= 0 {
+			writeError(w, http.StatusBadRequest, "window must be a positive duration")
+			return
+		}
+		window = parsed
+	}
+
+	_, ok, err := s.repo.GetTable(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table")
+		return
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "table not found")
+		return
+	}
+
+	hands, err := s.repo.ListHands(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load hands")
+		return
+	}
+	actions, err := s.repo.ListTableActions(tableID, time.Time{})
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load actions")
+		return
+	}
+	byHand := groupActionsByHand(actions)
+	observations := make([]stats.HandObservation, 0, len(hands))
+	for _, hand := range hands {
+		if hand.EndedAt == nil {
+			continue
+		}
+		preflop := make([]domain.ActionKind, 0, len(byHand[hand.HandID]))
+		for _, action := range byHand[hand.HandID] {
+			if action.Street == domain.StreetPreflop {
+				preflop = append(preflop, action.Action)
+			}
+		}
+		observations = append(observations, stats.HandObservation{
+			StartedAt:      hand.StartedAt,
+			FinalState:     hand.FinalState,
+			PreflopActions: preflop,
+		})
+	}
+
+	metrics, err := stats.AggregateTableMetrics(observations, window)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	s.mu.Lock()
+	tracker := s.stalling[tableID]
+	s.mu.Unlock()
+	if tracker != nil {
+		metrics.Stalling = tracker.Scores()
+	}
+	writeJSON(w, http.StatusOK, metrics)
+}
+
+type opponentStatsResponse struct {
+	TableID   string                `json:"table_id"`
+	Hands     int                   `json:"hands"`
+	Opponents []stats.OpponentStats `json:"opponents"`
+}
+
+// handleOpponentStats serves the opponent stats league rule: preflop
+// tendencies of the seats currently at the table over its latest completed
+// hands, across every run the table has played. Hands are attributed by seat
+// number. A seat token sees everyone but itself.
+func (s *Server) handleOpponentStats(w http.ResponseWriter, r *http.Request, identity CallerIdentity, tableID string) {
+	window := stats.DefaultOpponentStatsHands
+	if raw := strings.TrimSpace(r.URL.Query().Get("hands")); raw != "" {
+		parsed, err := strconv.Atoi(raw)
+		if err != nil || parsed <= 0 || parsed > stats.MaxOpponentStatsHands {
+			writeError(w, http.StatusBadRequest, fmt.Sprintf("hands must be between 1 and %d", stats.MaxOpponentStatsHands))
+			return
+		}
+		window = parsed
+	}
+
+	table, ok, err := s.repo.GetTable(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table")
+		return
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "table not found")
+		return
+	}
+	if !table.RuleFlags.Has(domain.RuleOpponentStats) {
+		writeError(w, http.StatusForbidden, "opponent stats are not enabled at this table")
+		return
+	}
+	seats, err := s.repo.ListSeats(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load seats")
+		return
+	}
+	hands, err := s.repo.ListHands(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load hands")
+		return
+	}
+
+	completed := make([]persistence.HandRecord, 0, len(hands))
+	for _, hand := range hands {
+		if hand.EndedAt != nil {
+			completed = append(completed, hand)
+		}
+	}
+	if len(completed) > window {
+		completed = completed[len(completed)-window:]
+	}
+
+	since := time.Time{}
+	for i, hand := range completed {
+		if i == 0 || hand.StartedAt.Before(since) {
+			since = hand.StartedAt
+		}
+	}
+	actions, err := s.repo.ListTableActions(tableID, since)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load actions")
+		return
+	}
+	byHand := groupActionsByHand(actions)
+	observed := make([]stats.OpponentHand, 0, len(completed))
+	for _, hand := range completed {
+		observation := stats.OpponentHand{}
+		for _, dealt := range hand.FinalState.HoleCards {
+			observation.Seats = append(observation.Seats, dealt.SeatNo)
+		}
+		for _, action := range byHand[hand.HandID] {
+			if action.Street == domain.StreetPreflop {
+				observation.Preflop = append(observation.Preflop, stats.SeatAction{SeatNo: action.ActingSeat, Kind: action.Action})
+			}
+		}
+		observed = append(observed, observation)
+	}
+
+	seated := make(map[domain.SeatNo]bool, len(seats))
+	for _, seat := range seats {
+		seated[seat.SeatNo] = true
+	}
+	response := opponentStatsResponse{TableID: tableID, Hands: len(completed), Opponents: []stats.OpponentStats{}}
+	for _, entry := range stats.AggregateOpponentStats(observed) {
+		if !seated[entry.SeatNo] || (identity.Seat != nil && *identity.Seat == entry.SeatNo) {
+			continue
+		}
+		response.Opponents = append(response.Opponents, entry)
+	}
+	writeJSON(w, http.StatusOK, response)
+}
+
+// groupActionsByHand splits a table's actions, as ListTableActions returns
+// them, into each hand's own.
+func groupActionsByHand(actions []persistence.ActionRecord) map[string][]persistence.ActionRecord {
+	byHand := make(map[string][]persistence.ActionRecord)
+	for _, action := range actions {
+		byHand[action.HandID] = append(byHand[action.HandID], action)
+	}
+	return byHand
+}
+
+type stallingPolicyResponse struct {
+	Enabled bool                 `json:"enabled"`
+	Policy  stats.StallingPolicy `json:"policy"`
+}
+
+type stallingPolicyRequest struct {
+	Enabled bool                  `json:"enabled"`
+	Policy  *stats.StallingPolicy `json:"policy,omitempty"`
+}
+
+func (s *Server) currentStallingPolicy() stallingPolicyResponse {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.stallingPolicy == nil {
+		return stallingPolicyResponse{Policy: stats.DefaultStallingPolicy()}
+	}
+	return stallingPolicyResponse{Enabled: true, Policy: *s.stallingPolicy}
+}
+
+func (s *Server) handleSetStallingPolicy(w http.ResponseWriter, r *http.Request) {
+	var req stallingPolicyRequest
+	if ok := decodeStrictJSON(w, r, &req); !ok {
+		return
+	}
+	policy := stats.DefaultStallingPolicy()
+	if req.Policy != nil {
+		policy = *req.Policy
+	}
+	if err := policy.Validate(); err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+
+	s.mu.Lock()
+	if req.Enabled {
+		s.stallingPolicy = &policy
+		for _, tracker := range s.stalling {
+			tracker.SetPolicy(policy)
+		}
+	} else {
+		s.stallingPolicy = nil
+	}
+	s.mu.Unlock()
+	writeJSON(w, http.StatusOK, s.currentStallingPolicy())
+}
+
+// observeStalling scores a decision against the stalling policy and logs the
+// seat the first time it is flagged. Decisions that never arrived are left
+// to the fallback accounting.
+func (s *Server) observeStalling(tableID string, state domain.HandState, timing tablerunner.ActionTiming) {
+	if timing.Policy == domain.DeadlineNoDecision {
+		return
+	}
+	s.mu.Lock()
+	if s.stallingPolicy == nil {
+		s.mu.Unlock()
+		return
+	}
+	tracker, ok := s.stalling[tableID]
+	if !ok {
+		// The policy was validated when it was set.
+		tracker, _ = stats.NewStallingTracker(*s.stallingPolicy)
+		s.stalling[tableID] = tracker
+	}
+	s.mu.Unlock()
+
+	score, flagged := tracker.Observe(state, timing.ThinkTime, timing.Deadline)
+	if flagged && s.config.StallingLog != nil {
+		fmt.Fprintf(s.config.StallingLog, "stalling suspected: table=%s hand=%s seat=%d score=%.2f slow=%d/%d\n",
+			tableID, state.HandID, score.SeatNo, score.Score, score.SlowTrivialDecisions, score.TrivialDecisions)
+	}
+}
+
+// observeConnection feeds a decision's arrival into the table's connection
+// telemetry.
+func (s *Server) observeConnection(tableID string, seat domain.SeatNo, timing tablerunner.ActionTiming) {
+	if timing.Policy == "" {
+		return
+	}
+	s.mu.Lock()
+	tracker, ok := s.connections[tableID]
+	if !ok {
+		tracker = stats.NewConnectionTracker()
+		s.connections[tableID] = tracker
+	}
+	s.mu.Unlock()
+	tracker.Observe(seat, timing.Policy, timing.ThinkTime)
+}
+
+func (s *Server) handleTableConnections(w http.ResponseWriter, tableID string) {
+	_, ok, err := s.repo.GetTable(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table")
+		return
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "table not found")
+		return
+	}
+	s.mu.Lock()
+	tracker := s.connections[tableID]
+	s.mu.Unlock()
+	if tracker == nil {
+		writeJSON(w, http.StatusOK, []stats.ConnectionQuality{})
+		return
+	}
+	writeJSON(w, http.StatusOK, tracker.Qualities())
+}
+
+func (s *Server) updateLiveHand(run *tableRun, update func(live *liveHand)) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	update(&run.live)
+}
+
+func (s *Server) handleOperatorDashboard(w http.ResponseWriter) {
+	type runningTable struct {
+		tableID     string
+		live        liveHand
+		connections *stats.ConnectionTracker
+	}
+	s.mu.Lock()
+	running := make([]runningTable, 0, len(s.runs))
+	for tableID, run := range s.runs {
+		running = append(running, runningTable{tableID: tableID, live: run.live, connections: s.connections[tableID]})
+	}
+	s.mu.Unlock()
+	sort.Slice(running, func(i, j int) bool { return running[i].tableID < running[j].tableID })
+
+	stuckAfter := s.config.StuckHandAfter
+	if stuckAfter <= 0 {
+		stuckAfter = defaultStuckHandAfter
+	}
+	now := time.Now().UTC()
+	response := operatorDashboardResponse{
+		GeneratedAt: now,
+		Tables:      make([]operatorTableResponse, 0, len(running)),
+		Alerts:      []operatorAlert{},
+	}
+	for _, table := range running {
+		item := operatorTableResponse{TableID: table.tableID, Seats: []stats.ConnectionQuality{}, Audience: s.audience.Table(table.tableID)}
+		run, ok, err := s.repo.GetTableRun(table.tableID)
+		if err != nil {
+			writeError(w, http.StatusInternalServerError, "failed to load table status")
+			return
+		}
+		if ok {
+			item.StartedAt = run.StartedAt
+			item.HandsRequested = run.HandsRequested
+			item.HandsCompleted = run.HandsCompleted
+		}
+		if table.connections != nil {
+			item.Seats = table.connections.Qualities()
+		}
+		for _, seat := range item.Seats {
+			switch seat.Health {
+			case stats.ConnectionDegraded:
+				response.Bots.Degraded++
+			case stats.ConnectionPoor:
+				response.Bots.Poor++
+			default:
+				response.Bots.Good++
+			}
+		}
+		response.EventLagMS = max(response.EventLagMS, uint64(table.live.eventLag.Milliseconds()))
+
+		if live := table.live; live.handID != "" {
+			hand := &operatorHandResponse{
+				HandID:      live.handID,
+				HandNo:      live.handNo,
+				StartedAt:   live.startedAt,
+				LastEventAt: live.lastEventAt,
+			}
+			if live.awaiting {
+				seat := live.state.ActingSeat
+				hand.ActingSeat = &seat
+				if !live.deadline.IsZero() {
+					remaining := live.deadline.Sub(now).Milliseconds()
+					hand.TimeoutRemainingMS = &remaining
+				}
+			}
+			item.Hand = hand
+			response.HandsInProgress++
+			if idle := now.Sub(live.lastEventAt); idle > stuckAfter {
+				response.Alerts = append(response.Alerts, operatorAlert{
+					TableID: table.tableID,
+					HandID:  live.handID,
+					Kind:    "stuck_hand",
+					IdleMS:  uint64(idle.Milliseconds()),
+				})
+			}
+		}
+		response.Tables = append(response.Tables, item)
+	}
+	response.TablesRunning = len(response.Tables)
+	writeJSON(w, http.StatusOK, response)
+}
+
+func (s *Server) stallingPenaltyMS(tableID string, seat domain.SeatNo) uint64 {
+	s.mu.Lock()
+	tracker := s.stalling[tableID]
+	enabled := s.stallingPolicy != nil
+	s.mu.Unlock()
+	if !enabled || tracker == nil {
+		return 0
+	}
+	return tracker.PenaltyMS(seat)
+}
+
+// handleValidateAction dry-runs an action against the decision the table is
+// waiting on and reports the normalized action or the exact rule error.
+// Seat callers may only validate their own decisions.
+func (s *Server) handleValidateAction(w http.ResponseWriter, r *http.Request, identity CallerIdentity, tableID string) {
+	var action domain.Action
+	if ok := decodeStrictJSON(w, r, &action); !ok {
+		return
+	}
+	s.mu.Lock()
+	run, ok := s.runs[tableID]
+	var live liveHand
+	if ok {
+		live = run.live
+	}
+	s.mu.Unlock()
+	if !ok || !live.awaiting {
+		writeError(w, http.StatusConflict, "table is not waiting on a decision")
+		return
+	}
+	if identity.Seat != nil && *identity.Seat != live.state.ActingSeat {
+		writeError(w, http.StatusConflict, fmt.Sprintf("seat %d is not acting", *identity.Seat))
+		return
+	}
+
+	response := validateActionResponse{
+		HandID: live.state.HandID,
+		SeatNo: live.state.ActingSeat,
+	}
+	normalized, err := statemachine.ValidateAction(live.state, action)
+	if err != nil {
+		response.Error = err.Error()
+	} else {
+		response.Legal = true
+		response.Action = &normalized
+	}
+	writeJSON(w, http.StatusOK, response)
+}
+
+func (s *Server) handleClaimSeat(w http.ResponseWriter, r *http.Request, identity CallerIdentity, tableID string) {
+	var req claimSeatRequest
+	if ok := decodeStrictJSON(w, r, &req); !ok {
+		return
+	}
+	transport, err := seatcontrol.ParseTransport(req.Transport)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+
+	controller, handoff, err := s.controllers.Claim(tableID, *identity.Seat, transport, req.ControllerID)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	writeJSON(w, http.StatusOK, claimSeatResponse{
+		Controller: controller,
+		Handoff:    handoff,
+	})
+}
+
+func (s *Server) handleMetrics(w http.ResponseWriter, r *http.Request) {
+	for transport, count := range s.controllers.CountByTransport() {
+		s.metrics.SeatControllers.Set(float64(count), string(transport))
+	}
+	// Runs are removed from s.runs as soon as they finish.
+	s.mu.Lock()
+	running := len(s.runs)
+	s.mu.Unlock()
+	s.metrics.RunningTables.Set(float64(running))
+	for tableID, watched := range s.audience.Tables() {
+		s.metrics.TableViewers.Set(float64(watched.Viewers), tableID)
+	}
+	s.metrics.Registry.Handler().ServeHTTP(w, r)
+}
+
+type placeWagerRequest struct {
+	MarketID    string `json:"market_id"`
+	SpectatorID string `json:"spectator_id"`
+	SeatNo      uint8  `json:"seat_no"`
+	Points      uint32 `json:"points"`
+}
+
+type tableMarketsResponse struct {
+	TableID string              `json:"table_id"`
+	Markets []sidemarket.Market `json:"markets"`
+	Events  []sidemarket.Event  `json:"events"`
+}
+
+func (s *Server) handleTableMarkets(w http.ResponseWriter, tableID string) {
+	if s.config.SideMarkets == nil {
+		writeError(w, http.StatusNotFound, "side markets are disabled")
+		return
+	}
+	writeJSON(w, http.StatusOK, tableMarketsResponse{
+		TableID: tableID,
+		Markets: s.config.SideMarkets.Markets(tableID),
+		Events:  s.config.SideMarkets.Events(tableID),
+	})
+}
+
+func (s *Server) handlePlaceWager(w http.ResponseWriter, r *http.Request, tableID string) {
+	if s.config.SideMarkets == nil {
+		writeError(w, http.StatusNotFound, "side markets are disabled")
+		return
+	}
+	var req placeWagerRequest
+	if ok := decodeStrictJSON(w, r, &req); !ok {
+		return
+	}
+	req.MarketID = strings.TrimSpace(req.MarketID)
+	req.SpectatorID = strings.TrimSpace(req.SpectatorID)
+	if req.MarketID == "" || req.SpectatorID == "" {
+		writeError(w, http.StatusBadRequest, "market_id and spectator_id are required")
+		return
+	}
+	if !marketBelongsToTable(s.config.SideMarkets.Markets(tableID), req.MarketID) {
+		writeError(w, http.StatusNotFound, "market not found")
+		return
+	}
+	wager, err := s.config.SideMarkets.PlaceWager(req.MarketID, req.SpectatorID, domain.SeatNo(req.SeatNo), req.Points)
+	switch {
+	case err == nil:
+		writeJSON(w, http.StatusOK, wager)
+	case errors.Is(err, sidemarket.ErrMarketNotFound):
+		writeError(w, http.StatusNotFound, err.Error())
+	case errors.Is(err, sidemarket.ErrMarketClosed):
+		writeError(w, http.StatusConflict, err.Error())
+	default:
+		writeError(w, http.StatusBadRequest, err.Error())
+	}
+}
+
+type watchTableRequest struct {
+	SpectatorID string `json:"spectator_id"`
+}
+
+// handleWatchTable takes a spectator's heartbeat. Spectators send one while
+// they watch; the response is the table's audience.
+func (s *Server) handleWatchTable(w http.ResponseWriter, r *http.Request, tableID string) {
+	var req watchTableRequest
+	if ok := decodeStrictJSON(w, r, &req); !ok {
+		return
+	}
+	_, ok, err := s.repo.GetTable(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table")
+		return
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "table not found")
+		return
+	}
+	added, err := s.audience.Watch(tableID, strings.TrimSpace(req.SpectatorID))
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	s.metrics.WatchTime.Add(added.Seconds(), tableID)
+	writeJSON(w, http.StatusOK, s.audience.Table(tableID))
+}
+
+func (s *Server) handleCreateTournamentTemplate(w http.ResponseWriter, r *http.Request) {
+	if s.config.Tournaments == nil {
+		writeError(w, http.StatusNotFound, "tournament scheduling is disabled")
+		return
+	}
+	var template tournament.Template
+	if ok := decodeStrictJSON(w, r, &template); !ok {
+		return
+	}
+	err := s.config.Tournaments.AddTemplate(template)
+	switch {
+	case err == nil:
+		writeJSON(w, http.StatusOK, template)
+	case errors.Is(err, tournament.ErrTemplateExists):
+		writeError(w, http.StatusConflict, err.Error())
+	default:
+		writeError(w, http.StatusBadRequest, err.Error())
+	}
+}
+
+func (s *Server) handleListTournamentTemplates(w http.ResponseWriter) {
+	if s.config.Tournaments == nil {
+		writeError(w, http.StatusNotFound, "tournament scheduling is disabled")
+		return
+	}
+	writeJSON(w, http.StatusOK, s.config.Tournaments.Templates())
+}
+
+// handleTournamentCalendar lists the tournaments starting between the from
+// and to query times, RFC 3339, which default to the coming week.
+func (s *Server) handleTournamentCalendar(w http.ResponseWriter, r *http.Request) {
+	if s.config.Tournaments == nil {
+		writeError(w, http.StatusNotFound, "tournament scheduling is disabled")
+		return
+	}
+	from := time.Now().UTC()
+	if raw := strings.TrimSpace(r.URL.Query().Get("from")); raw != "" {
+		parsed, err := time.Parse(time.RFC3339, raw)
+		if err != nil {
+			writeError(w, http.StatusBadRequest, "from must be an RFC 3339 time")
+			return
+		}
+		from = parsed
+	}
+	to := from.Add(7 * 24 * time.Hour)
+	if raw := strings.TrimSpace(r.URL.Query().Get("to")); raw != "" {
+		parsed, err := time.Parse(time.RFC3339, raw)
+		if err != nil {
+			writeError(w, http.StatusBadRequest, "to must be an RFC 3339 time")
+			return
+		}
+		to = parsed
+	}
+	calendar, err := s.config.Tournaments.Calendar(from, to)
+	if err != nil {
+		writeError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	writeJSON(w, http.StatusOK, calendar)
+}
+
+type tournamentRegistrationRequest struct {
+	PlayerID string `json:"player_id"`
+}
+
+func (s *Server) handleTournamentRegistration(w http.ResponseWriter, r *http.Request, tournamentID string) {
+	if s.config.Tournaments == nil {
+		writeError(w, http.StatusNotFound, "tournament scheduling is disabled")
+		return
+	}
+	var req tournamentRegistrationRequest
+	if ok := decodeStrictJSON(w, r, &req); !ok {
+		return
+	}
+	scheduled, err := s.config.Tournaments.Register(tournamentID, strings.TrimSpace(req.PlayerID))
+	switch {
+	case err == nil:
+		writeJSON(w, http.StatusOK, scheduled)
+	case errors.Is(err, tournament.ErrTournamentNotFound):
+		writeError(w, http.StatusNotFound, err.Error())
+	case errors.Is(err, tournament.ErrRegistrationClosed), errors.Is(err, tournament.ErrSitAndGoFull), errors.Is(err, tournament.ErrAlreadyRegistered):
+		writeError(w, http.StatusConflict, err.Error())
+	default:
+		writeError(w, http.StatusBadRequest, err.Error())
+	}
+}
+
+func (s *Server) handleSpectatorAccount(w http.ResponseWriter, spectatorID string) {
+	if s.config.SideMarkets == nil {
+		writeError(w, http.StatusNotFound, "side markets are disabled")
+		return
+	}
+	writeJSON(w, http.StatusOK, s.config.SideMarkets.Account(spectatorID))
+}
+
+type tableBadBeatsResponse struct {
+	TableID  string            `json:"table_id"`
+	BadBeats []badbeat.BadBeat `json:"bad_beats"`
+}
+
+func (s *Server) handleTableBadBeats(w http.ResponseWriter, tableID string) {
+	if s.config.BadBeats == nil {
+		writeError(w, http.StatusNotFound, "bad beat detection is disabled")
+		return
+	}
+	writeJSON(w, http.StatusOK, tableBadBeatsResponse{
+		TableID:  tableID,
+		BadBeats: s.config.BadBeats.BadBeats(tableID),
+	})
+}
+
+// checkBadBeats prices the all-in spot of a completed hand the same way the
+// runout tree does. Detection is best effort and never fails the run.
+func (s *Server) checkBadBeats(final domain.HandState) {
+	if len(final.ShowdownAwards) == 0 {
+		return
+	}
+	actions, err := s.repo.ListActions(final.HandID)
+	if err != nil {
+		return
+	}
+	_, _ = s.config.BadBeats.Check(allInSpot(final, actions), final)
+}
+
+func (s *Server) pushOverlaySummary(final domain.HandState) {
+	actions, err := s.repo.ListActions(final.HandID)
+	if err != nil {
+		return
+	}
+	decisions := make([]commentary.Decision, 0, len(actions))
+	for _, action := range actions {
+		decisions = append(decisions, commentary.Decision{
+			Street: action.Street,
+			SeatNo: action.ActingSeat,
+			Action: action.Action,
+			Amount: action.Amount,
+		})
+	}
+	summary, ok := commentary.Summarize(final, decisions)
+	if !ok {
+		return
+	}
+	go func() {
+		_ = s.config.CommentaryOverlay.Push(context.Background(), summary)
+	}()
+}
+
+func marketBelongsToTable(markets []sidemarket.Market, marketID string) bool {
+	for _, market := range markets {
+		if market.ID == marketID {
+			return true
+		}
+	}
+	return false
+}
+
+func (s *Server) handleHandoffs(w http.ResponseWriter, tableID string) {
+	events := s.controllers.Events(tableID)
+	if events == nil {
+		events = []seatcontrol.HandoffEvent{}
+	}
+	writeJSON(w, http.StatusOK, events)
+}
+
+func (s *Server) handleTableArchive(w http.ResponseWriter, r *http.Request, tableID string) {
+	if s.archiver == nil {
+		writeError(w, http.StatusNotFound, "archive storage not configured")
+		return
+	}
+	entry, ok, err := s.archiver.Latest(r.Context(), tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load archive index")
+		return
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "archive not found")
 		return
 	}
-
-	hands, err := s.repo.ListHands(tableID)
+	bundle, err := s.archiver.Load(r.Context(), entry)
 	if err != nil {
-		writeError(w, http.StatusInternalServerError, "failed to load hands")
+		writeError(w, http.StatusInternalServerError, "failed to load archive")
 		return
 	}
-	actionCount := 0
-	for _, hand := range hands {
-		actions, listErr := s.repo.ListActions(hand.HandID)
-		if listErr != nil {
-			writeError(w, http.StatusInternalServerError, "failed to load actions")
-			return
-		}
-		actionCount += len(actions)
-	}
-
-	writeJSON(w, http.StatusOK, tableStatusResponse{
-		TableID:          record.TableID,
-		Status:           record.Status,
-		StartedAt:        record.StartedAt,
-		EndedAt:          record.EndedAt,
-		Error:            record.Error,
-		HandsRequested:   record.HandsRequested,
-		HandsCompleted:   record.HandsCompleted,
-		TotalActions:     record.TotalActions,
-		TotalFallbacks:   record.TotalFallbacks,
-		CurrentHandNo:    record.CurrentHandNo,
-		HandsPersisted:   len(hands),
-		ActionsPersisted: actionCount,
-	})
+	writeJSON(w, http.StatusOK, bundle)
 }
 
 func (s *Server) handleHands(w http.ResponseWriter, identity CallerIdentity, tableID string) {
@@ -949,15 +2618,7 @@ func (s *Server) handleHands(w http.ResponseWriter, identity CallerIdentity, tab
 
 	response := make([]handResponse, 0, len(filtered))
 	for _, hand := range filtered {
-		response = append(response, handResponse{
-			HandID:        hand.HandID,
-			TableID:       hand.TableID,
-			HandNo:        hand.HandNo,
-			StartedAt:     hand.StartedAt,
-			EndedAt:       hand.EndedAt,
-			FinalPhase:    hand.FinalPhase,
-			WinnerSummary: append([]domain.PotAward(nil), hand.WinnerSummary...),
-		})
+		response = append(response, mapHandRecordToResponse(hand))
 	}
 	writeJSON(w, http.StatusOK, response)
 }
@@ -982,21 +2643,52 @@ func (s *Server) handleActions(w http.ResponseWriter, identity CallerIdentity, h
 		writeError(w, http.StatusInternalServerError, "failed to load actions")
 		return
 	}
+	telemetryLifted, err := s.telemetryLifted(hand.TableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table status")
+		return
+	}
 	response := make([]actionResponse, 0, len(actions))
 	for _, action := range actions {
-		response = append(response, actionResponse{
-			HandID:     action.HandID,
-			Street:     action.Street,
-			ActingSeat: action.ActingSeat,
-			Action:     action.Action,
-			Amount:     action.Amount,
-			IsFallback: action.IsFallback,
-			At:         action.At,
-		})
+		response = append(response, newActionResponse(action, telemetryLifted))
 	}
 	writeJSON(w, http.StatusOK, response)
 }
 
+// telemetryLifted reports whether the embargo on telemetry submitted at
+// tableID has lifted. Telemetry stays embargoed while the table runs.
+func (s *Server) telemetryLifted(tableID string) (bool, error) {
+	run, ok, err := s.repo.GetTableRun(tableID)
+	if err != nil || !ok {
+		return false, err
+	}
+	return s.config.TelemetryEmbargo.Lifted(run.EndedAt, time.Now().UTC()), nil
+}
+
+func newActionResponse(action persistence.ActionRecord, telemetryLifted bool) actionResponse {
+	response := actionResponse{
+		HandID:                action.HandID,
+		Street:                action.Street,
+		ActingSeat:            action.ActingSeat,
+		Action:                action.Action,
+		Amount:                action.Amount,
+		IsFallback:            action.IsFallback,
+		At:                    action.At,
+		DeadlinePolicy:        action.DeadlinePolicy,
+		LatencyCompensationMS: action.LatencyCompensationMS,
+	}
+	if !action.ReceivedAt.IsZero() {
+		receivedAt := action.ReceivedAt
+		thinkTimeMS := action.ThinkTimeMS
+		response.ReceivedAt = &receivedAt
+		response.ThinkTimeMS = &thinkTimeMS
+	}
+	if telemetryLifted {
+		response.Telemetry = action.Telemetry
+	}
+	return response
+}
+
 func (s *Server) handleReplay(w http.ResponseWriter, r *http.Request, identity CallerIdentity, handID string) {
 	redactHoleCards, err := parseRedactHoleCards(r.URL.Query().Get("redact_hole_cards"))
 	if err != nil {
@@ -1026,6 +2718,70 @@ func (s *Server) handleReplay(w http.ResponseWriter, r *http.Request, identity C
 	writeJSON(w, http.StatusOK, replay)
 }
 
+// handleRunoutTree exports every turn and river card for a hand that went
+// all-in on the flop or turn, with the winner and exact equities per branch.
+func (s *Server) handleRunoutTree(w http.ResponseWriter, identity CallerIdentity, handID string) {
+	hand, ok, err := s.repo.GetHand(handID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load hand")
+		return
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "hand not found")
+		return
+	}
+	if identity.Role == CallerRoleSeat && !handIncludesSeat(hand, identity.seatNo()) {
+		writeError(w, http.StatusForbidden, "forbidden")
+		return
+	}
+	actions, err := s.repo.ListActions(handID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load actions")
+		return
+	}
+
+	spot := allInSpot(hand.FinalState, actions)
+	if identity.Role == CallerRoleSeat {
+		// The tree shows every contesting hand, so seats only see it when
+		// nobody else mucked.
+		for seatNo := range muckedSeats(spot) {
+			if seatNo != identity.seatNo() {
+				writeError(w, http.StatusForbidden, "forbidden")
+				return
+			}
+		}
+	}
+	tree, err := equity.BuildRunoutTree(spot)
+	if err != nil {
+		if errors.Is(err, equity.ErrRunoutUnsupportedBoard) || errors.Is(err, equity.ErrRunoutNotContested) {
+			writeError(w, http.StatusConflict, err.Error())
+			return
+		}
+		writeError(w, http.StatusInternalServerError, "failed to build runout tree")
+		return
+	}
+	writeJSON(w, http.StatusOK, tree)
+}
+
+// allInSpot rebuilds the state as betting closed: the final state with the
+// board cut back to the street of the last action.
+func allInSpot(final domain.HandState, actions []persistence.ActionRecord) domain.HandState {
+	boardLen := 0
+	if len(actions) > 0 {
+		switch actions[len(actions)-1].Street {
+		case domain.StreetFlop:
+			boardLen = 3
+		case domain.StreetTurn:
+			boardLen = 4
+		case domain.StreetRiver:
+			boardLen = 5
+		}
+	}
+	spot := final
+	spot.Board = final.Board[:min(boardLen, len(final.Board))]
+	return spot
+}
+
 func (s *Server) handleLatestReplay(w http.ResponseWriter, r *http.Request, identity CallerIdentity, tableID string) {
 	tableRecord, ok, err := s.repo.GetTable(tableID)
 	if err != nil {
@@ -1084,6 +2840,10 @@ func (s *Server) buildHandReplayResponse(identity CallerIdentity, hand persisten
 	if err != nil {
 		return handReplayResponse{}, err
 	}
+	telemetryLifted, err := s.telemetryLifted(hand.TableID)
+	if err != nil {
+		return handReplayResponse{}, err
+	}
 	finalState := cloneHandStateForReplay(hand.FinalState)
 	applyReplayVisibility(identity, hand, &finalState)
 	if redactHoleCards {
@@ -1097,22 +2857,23 @@ func (s *Server) buildHandReplayResponse(identity CallerIdentity, hand persisten
 		ActionsBySeat:   make(map[domain.SeatNo]int),
 	}
 	actionItems := make([]actionResponse, 0, len(actions))
+	var decisions []stats.TimedDecision
 	for _, action := range actions {
 		if action.IsFallback {
 			analytics.FallbackActions++
 		}
 		analytics.ActionsByStreet[action.Street]++
 		analytics.ActionsBySeat[action.ActingSeat]++
-		actionItems = append(actionItems, actionResponse{
-			HandID:     action.HandID,
-			Street:     action.Street,
-			ActingSeat: action.ActingSeat,
-			Action:     action.Action,
-			Amount:     action.Amount,
-			IsFallback: action.IsFallback,
-			At:         action.At,
-		})
+		actionItems = append(actionItems, newActionResponse(action, telemetryLifted))
+		if !action.ReceivedAt.IsZero() {
+			decisions = append(decisions, stats.TimedDecision{
+				Street:    action.Street,
+				SeatNo:    action.ActingSeat,
+				ThinkTime: time.Duration(action.ThinkTimeMS) * time.Millisecond,
+			})
+		}
 	}
+	analytics.Timing = stats.StreetTimingProfile(decisions)
 
 	return handReplayResponse{
 		HandID:        hand.HandID,
@@ -1125,9 +2886,49 @@ func (s *Server) buildHandReplayResponse(identity CallerIdentity, hand persisten
 		FinalState:    finalState,
 		Actions:       actionItems,
 		Analytics:     analytics,
+		Equity:        replayEquity(hand, finalState),
 	}, nil
 }
 
+// replayEquity is the hand's equity sparkline, withheld from a replay that
+// hides any of the hole cards it was worked out from, as the numbers would
+// give them away.
+func replayEquity(hand persistence.HandRecord, shown domain.HandState) []equity.StreetEquity {
+	dealt := make(map[domain.SeatNo]int, len(hand.FinalState.HoleCards))
+	for _, seatCards := range hand.FinalState.HoleCards {
+		dealt[seatCards.SeatNo] = len(seatCards.Cards)
+	}
+	for _, seatCards := range shown.HoleCards {
+		if len(seatCards.Cards) < dealt[seatCards.SeatNo] {
+			return nil
+		}
+		delete(dealt, seatCards.SeatNo)
+	}
+	// A seat left out of the replay altogether is hidden too.
+	for _, cards := range dealt {
+		if cards > 0 {
+			return nil
+		}
+	}
+	return hand.Equity
+}
+
+// storeEquity works out a completed hand's equity sparkline and adds it to
+// the stored hand. It runs off the table loop, which would otherwise wait on
+// thousands of preflop runouts between hands. A sparkline that cannot be
+// worked out is logged and the hand is kept without one.
+func (s *Server) storeEquity(run *tableRun, final domain.HandState, folds map[domain.SeatNo]domain.Street) {
+	defer run.equity.Done()
+	points, err := equity.Sparkline(final, folds)
+	if err != nil {
+		log.Printf("table %s hand %s: compute equity sparkline: %v", final.TableID, final.HandID, err)
+		return
+	}
+	if err := s.repo.SetHandEquity(final.HandID, points); err != nil {
+		log.Printf("table %s hand %s: store equity sparkline: %v", final.TableID, final.HandID, err)
+	}
+}
+
 func (s *Server) runTable(ctx context.Context, tableID string, run *tableRun, runner Runner, input tablerunner.RunTableInput) {
 	defer func() {
 		close(run.done)
@@ -1137,6 +2938,11 @@ func (s *Server) runTable(ctx context.Context, tableID string, run *tableRun, ru
 	}()
 
 	result, err := runner.RunTable(ctx, input)
+	// A run ends once every hand it played is stored in full.
+	run.equity.Wait()
+	if s.config.SideMarkets != nil {
+		s.config.SideMarkets.VoidTable(tableID)
+	}
 	finalStatus := run.status
 	finalStatus.HandsCompleted = result.HandsCompleted
 	finalStatus.TotalActions = result.TotalActions
@@ -1161,6 +2967,13 @@ func (s *Server) runTable(ctx context.Context, tableID string, run *tableRun, ru
 
 	run.status = finalStatus
 	_ = s.repo.UpsertTableRun(finalStatus)
+
+	if s.archiver != nil {
+		// The run context may already be cancelled by a stop request.
+		if _, err := s.archiver.ArchiveRun(context.Background(), tableID, input.Config); err != nil {
+			log.Printf("table %s: archive run: %v", tableID, err)
+		}
+	}
 }
 
 func (s *Server) failBeforeRun(tableID string, run *tableRun, err error) {
@@ -1192,9 +3005,28 @@ func validateStartRequest(tableID string, req StartRequest, serverCfg ServerConf
 	if err := cfg.Validate(); err != nil {
 		return tablerunner.RunTableInput{}, cfg, 0, nil, err
 	}
+	if err := rules.CheckDeckSpec(cfg.Variant.Effective(), cfg.DeckSpec); err != nil {
+		return tablerunner.RunTableInput{}, cfg, 0, nil, err
+	}
+	if variant := cfg.Variant.Effective(); variant != domain.VariantStandard {
+		// Draw and stud games are settled by the rules package but not
+		// dealt by the table runner.
+		return tablerunner.RunTableInput{}, cfg, 0, nil, fmt.Errorf("the table runner deals %s only, not %s", domain.VariantStandard, variant)
+	}
+	if cfg.UndoWindowMS > 0 {
+		// Seats here are played by agents, and ranked results must not wait
+		// on a take-back.
+		return tablerunner.RunTableInput{}, cfg, 0, nil, fmt.Errorf("undo_window_ms is only available on human tables")
+	}
 	if req.HandsToRun <= 0 {
 		return tablerunner.RunTableInput{}, cfg, 0, nil, fmt.Errorf("hands_to_run must be greater than zero")
 	}
+	if err := req.NextHand.validate(); err != nil {
+		return tablerunner.RunTableInput{}, cfg, 0, nil, err
+	}
+	if err := req.Pacing.Validate(); err != nil {
+		return tablerunner.RunTableInput{}, cfg, 0, nil, err
+	}
 	if len(req.Seats) == 0 {
 		return tablerunner.RunTableInput{}, cfg, 0, nil, fmt.Errorf("seats must not be empty")
 	}
@@ -1217,6 +3049,25 @@ func validateStartRequest(tableID string, req StartRequest, serverCfg ServerConf
 			}
 			seatState.Status = seat.Status
 		}
+		if seat.ShowdownPreference != "" {
+			pref, err := domain.ParseShowdownPreference(string(seat.ShowdownPreference))
+			if err != nil {
+				return tablerunner.RunTableInput{}, cfg, 0, nil, fmt.Errorf("seat %d: %w", seatNo, err)
+			}
+			seatState.ShowdownPreference = pref
+		}
+		if seat.Straddle && cfg.Straddle == "" {
+			return tablerunner.RunTableInput{}, cfg, 0, nil, fmt.Errorf("seat %d cannot straddle on a table without straddles", seatNo)
+		}
+		seatState.Straddle = seat.Straddle
+		if seat.BuyInPolicy != nil {
+			if err := seat.BuyInPolicy.Validate(); err != nil {
+				return tablerunner.RunTableInput{}, cfg, 0, nil, fmt.Errorf("seat %d: %w", seatNo, err)
+			}
+		}
+		if _, err := domain.ParseViewLevel(string(seat.View)); err != nil {
+			return tablerunner.RunTableInput{}, cfg, 0, nil, fmt.Errorf("seat %d: %w", seatNo, err)
+		}
 		if isSeatActiveForStart(seat.Status) {
 			parsedEndpoint, err := url.Parse(seat.AgentEndpoint)
 			if err != nil || parsedEndpoint == nil || parsedEndpoint.Host == "" {
@@ -1258,6 +3109,25 @@ func validateStartRequest(tableID string, req StartRequest, serverCfg ServerConf
 	}, cfg, buttonSeat, seats, nil
 }
 
+func (r *NextHandRequest) validate() error {
+	if r == nil {
+		return nil
+	}
+	switch r.Policy {
+	case tablerunner.NextHandImmediate, tablerunner.NextHandOnTrigger:
+		if r.DelayMS != 0 {
+			return fmt.Errorf("next_hand.delay_ms only applies to the %q policy", tablerunner.NextHandAfterDelay)
+		}
+	case tablerunner.NextHandAfterDelay:
+		if r.DelayMS == 0 || r.DelayMS > maxInterHandDelayMS {
+			return fmt.Errorf("next_hand.delay_ms must be between 1 and %d", maxInterHandDelayMS)
+		}
+	default:
+		return fmt.Errorf("unknown next_hand.policy %q", r.Policy)
+	}
+	return nil
+}
+
 func isSeatActiveForStart(status domain.SeatStatus) bool {
 	return status == "" || status == domain.SeatStatusActive
 }
@@ -1272,7 +3142,7 @@ func isSeatStatusAllowed(status domain.SeatStatus) bool {
 }
 
 func (s *Server) authenticate(r *http.Request) (CallerIdentity, bool) {
-	if len(s.config.AdminBearerTokens) == 0 && len(s.config.SeatBearerTokens) == 0 {
+	if len(s.config.AdminBearerTokens) == 0 && len(s.config.SeatBearerTokens) == 0 && len(s.config.TenantAdminTokens) == 0 {
 		return CallerIdentity{Role: CallerRoleAdmin}, true
 	}
 
@@ -1283,6 +3153,9 @@ func (s *Server) authenticate(r *http.Request) (CallerIdentity, bool) {
 	if _, exists := s.config.AdminBearerTokens[token]; exists {
 		return CallerIdentity{Role: CallerRoleAdmin, Token: token}, true
 	}
+	if tenantID, exists := s.config.TenantAdminTokens[token]; exists {
+		return CallerIdentity{Role: CallerRoleAdmin, Token: token, TenantID: tenantID}, true
+	}
 	if seat, exists := s.config.SeatBearerTokens[token]; exists {
 		seatCopy := seat
 		return CallerIdentity{Role: CallerRoleSeat, Seat: &seatCopy, Token: token}, true
@@ -1290,6 +3163,111 @@ func (s *Server) authenticate(r *http.Request) (CallerIdentity, bool) {
 	return CallerIdentity{}, false
 }
 
+// requestTenant picks the tenant for a request. Tenant admins are pinned to
+// their own tenant; deployment admins may name any tenant, or none.
+func requestTenant(identity CallerIdentity, requested string) (string, bool) {
+	requested = strings.TrimSpace(requested)
+	if identity.TenantID == "" {
+		return requested, true
+	}
+	return identity.TenantID, requested == "" || requested == identity.TenantID
+}
+
+// tableInTenant answers 404 for tables outside a tenant-scoped caller's
+// tenant, so other communities' tables look absent rather than forbidden.
+func (s *Server) tableInTenant(w http.ResponseWriter, identity CallerIdentity, tableID string) bool {
+	if identity.TenantID == "" {
+		return true
+	}
+	table, ok, err := s.repo.GetTable(tableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table")
+		return false
+	}
+	if !ok || !identity.inTenant(table.TenantID) {
+		writeError(w, http.StatusNotFound, "table not found")
+		return false
+	}
+	return true
+}
+
+func (s *Server) handInTenant(w http.ResponseWriter, identity CallerIdentity, handID string) bool {
+	if identity.TenantID == "" {
+		return true
+	}
+	hand, ok, err := s.repo.GetHand(handID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load hand")
+		return false
+	}
+	if !ok {
+		writeError(w, http.StatusNotFound, "hand not found")
+		return false
+	}
+	table, ok, err := s.repo.GetTable(hand.TableID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load table")
+		return false
+	}
+	if !ok || !identity.inTenant(table.TenantID) {
+		writeError(w, http.StatusNotFound, "hand not found")
+		return false
+	}
+	return true
+}
+
+// seatControllerCurrent refuses a seat caller whose controller has been
+// superseded. Once a seat is claimed, its requests must carry the active
+// controller's generation; a seat nobody has claimed is driven by its token
+// alone.
+func (s *Server) seatControllerCurrent(w http.ResponseWriter, r *http.Request, identity CallerIdentity, tableID string) bool {
+	if identity.Seat == nil {
+		return true
+	}
+	if _, claimed := s.controllers.Active(tableID, *identity.Seat); !claimed {
+		return true
+	}
+	generation, err := strconv.ParseUint(strings.TrimSpace(r.Header.Get(controllerGenerationHeader)), 10, 64)
+	if err != nil {
+		writeError(w, http.StatusConflict, fmt.Sprintf("seat %d is claimed; send the controller's generation in %s", *identity.Seat, controllerGenerationHeader))
+		return false
+	}
+	if err := s.controllers.Validate(tableID, *identity.Seat, generation); err != nil && !errors.Is(err, seatcontrol.ErrNoActiveController) {
+		writeError(w, http.StatusConflict, err.Error())
+		return false
+	}
+	return true
+}
+
+// handControllerCurrent applies seatControllerCurrent to the table handID
+// was dealt at.
+func (s *Server) handControllerCurrent(w http.ResponseWriter, r *http.Request, identity CallerIdentity, handID string) bool {
+	if identity.Seat == nil {
+		return true
+	}
+	hand, ok, err := s.repo.GetHand(handID)
+	if err != nil {
+		writeError(w, http.StatusInternalServerError, "failed to load hand")
+		return false
+	}
+	if !ok {
+		// The route reports the missing hand.
+		return true
+	}
+	return s.seatControllerCurrent(w, r, identity, hand.TableID)
+}
+
+func (s *Server) agentInTenant(identity CallerIdentity, agentID string) (bool, error) {
+	if identity.TenantID == "" {
+		return true, nil
+	}
+	agent, ok, err := s.repo.GetAgent(agentID)
+	if err != nil {
+		return false, err
+	}
+	return ok && identity.inTenant(agent.TenantID), nil
+}
+
 func parseBearerToken(authorization string) (string, bool) {
 	trimmed := strings.TrimSpace(authorization)
 	if !strings.HasPrefix(trimmed, "Bearer ") {
@@ -1322,7 +3300,7 @@ func (s *Server) handleCORS(w http.ResponseWriter, r *http.Request) bool {
 	}
 
 	w.Header().Set("Access-Control-Allow-Methods", "GET,POST,OPTIONS")
-	w.Header().Set("Access-Control-Allow-Headers", "Authorization,Content-Type")
+	w.Header().Set("Access-Control-Allow-Headers", "Authorization,Content-Type,"+controllerGenerationHeader)
 	w.WriteHeader(http.StatusNoContent)
 	return true
 }
@@ -1382,6 +3360,44 @@ func parseAgentVersionsRoute(path string) (agentID string, ok bool) {
 	return parts[1], true
 }
 
+func parseAgentPreferencesRoute(path string) (agentID string, ok bool) {
+	parts := strings.Split(strings.Trim(path, "/"), "/")
+	if len(parts) != 3 || parts[0] != "agents" || parts[2] != "preferences" {
+		return "", false
+	}
+	if parts[1] == "" {
+		return "", false
+	}
+	return parts[1], true
+}
+
+func parseTournamentRegistrationRoute(path string) (tournamentID string, ok bool) {
+	parts := strings.Split(strings.Trim(path, "/"), "/")
+	if len(parts) != 3 || parts[0] != "tournaments" || parts[2] != "registrations" {
+		return "", false
+	}
+	if parts[1] == "" {
+		return "", false
+	}
+	return parts[1], true
+}
+
+func parseUserRoute(path string) (userID string, ok bool) {
+	parts := strings.Split(strings.Trim(path, "/"), "/")
+	if len(parts) != 2 || parts[0] != "users" || parts[1] == "" {
+		return "", false
+	}
+	return parts[1], true
+}
+
+func parseSpectatorRoute(path string) (spectatorID string, ok bool) {
+	parts := strings.Split(strings.Trim(path, "/"), "/")
+	if len(parts) != 2 || parts[0] != "spectators" || parts[1] == "" {
+		return "", false
+	}
+	return parts[1], true
+}
+
 func parseRedactHoleCards(raw string) (bool, error) {
 	normalized := strings.TrimSpace(raw)
 	if normalized == "" {
@@ -1400,7 +3416,11 @@ func parseRedactHoleCards(raw string) (bool, error) {
 func cloneHandStateForReplay(state domain.HandState) domain.HandState {
 	cloned := state
 	cloned.Board = append([]domain.Card(nil), state.Board...)
+	cloned.Runouts = domain.CloneBoards(state.Runouts)
 	cloned.Deck = append([]domain.Card(nil), state.Deck...)
+	cloned.Discards = append([]domain.Card(nil), state.Discards...)
+	cloned.DealSequence = append([]domain.DealtCard(nil), state.DealSequence...)
+	cloned.ShowdownReveals = append([]domain.ShowdownReveal(nil), state.ShowdownReveals...)
 	cloned.Seats = append([]domain.SeatState(nil), state.Seats...)
 	cloned.ShowdownAwards = clonePotAwardsForReplay(state.ShowdownAwards)
 	if state.LastAggressorSeat != nil {
@@ -1432,8 +3452,10 @@ func clonePotAwardsForReplay(awards []domain.PotAward) []domain.PotAward {
 	return out
 }
 
+// redactFoldedSeatHoleCards hides the hole cards of folded seats and of seats
+// that mucked at showdown.
 func redactFoldedSeatHoleCards(state *domain.HandState) {
-	folded := make(map[domain.SeatNo]struct{}, len(state.Seats))
+	folded := muckedSeats(*state)
 	for _, seat := range state.Seats {
 		if seat.Folded {
 			folded[seat.SeatNo] = struct{}{}
@@ -1444,6 +3466,7 @@ func redactFoldedSeatHoleCards(state *domain.HandState) {
 			state.HoleCards[i].Cards = []domain.Card{}
 		}
 	}
+	redactDealSequence(state)
 }
 
 func applyReplayVisibility(identity CallerIdentity, hand persistence.HandRecord, state *domain.HandState) {
@@ -1452,13 +3475,54 @@ func applyReplayVisibility(identity CallerIdentity, hand persistence.HandRecord,
 	}
 	callerSeat := identity.seatNo()
 	showdown := isShowdownHand(hand)
+	mucked := muckedSeats(*state)
 	for i := range state.HoleCards {
 		seatNo := state.HoleCards[i].SeatNo
-		if seatNo == callerSeat || showdown {
+		if seatNo == callerSeat {
+			continue
+		}
+		if _, ok := mucked[seatNo]; showdown && !ok {
 			continue
 		}
 		state.HoleCards[i].Cards = []domain.Card{}
 	}
+	// The deck lists every card in dealing order, opponents' included, and
+	// the discards are opponents' too.
+	state.Deck = nil
+	state.Discards = nil
+	redactDealSequence(state)
+}
+
+func muckedSeats(state domain.HandState) map[domain.SeatNo]struct{} {
+	mucked := make(map[domain.SeatNo]struct{}, len(state.ShowdownReveals))
+	for _, reveal := range state.ShowdownReveals {
+		if reveal.Action == domain.RevealMuck {
+			mucked[reveal.SeatNo] = struct{}{}
+		}
+	}
+	return mucked
+}
+
+// redactDealSequence drops hole-card deal entries for seats whose hole cards
+// were redacted, keeping the remaining dealing order intact.
+func redactDealSequence(state *domain.HandState) {
+	hidden := make(map[domain.SeatNo]struct{}, len(state.HoleCards))
+	for _, seatCards := range state.HoleCards {
+		if len(seatCards.Cards) == 0 {
+			hidden[seatCards.SeatNo] = struct{}{}
+		}
+	}
+	if len(hidden) == 0 {
+		return
+	}
+	kept := make([]domain.DealtCard, 0, len(state.DealSequence))
+	for _, dealt := range state.DealSequence {
+		if _, ok := hidden[dealt.SeatNo]; ok && dealt.Target == domain.DealTargetHole {
+			continue
+		}
+		kept = append(kept, dealt)
+	}
+	state.DealSequence = kept
 }
 
 func isShowdownHand(hand persistence.HandRecord) bool {
@@ -1477,13 +3541,17 @@ func isShowdownHand(hand persistence.HandRecord) bool {
 
 func mapTableRecordToResponse(record persistence.TableRecord) tableResponse {
 	return tableResponse{
-		ID:         record.ID,
-		Name:       record.Name,
-		MaxSeats:   record.MaxSeats,
-		SmallBlind: record.SmallBlind,
-		BigBlind:   record.BigBlind,
-		Status:     record.Status,
-		CreatedAt:  record.CreatedAt,
+		ID:           record.ID,
+		Name:         record.Name,
+		MaxSeats:     record.MaxSeats,
+		SmallBlind:   record.SmallBlind,
+		BigBlind:     record.BigBlind,
+		Status:       record.Status,
+		CreatedAt:    record.CreatedAt,
+		TenantID:     record.TenantID,
+		RuleFlags:    record.RuleFlags,
+		RulesVersion: record.RulesVersion,
+		Variant:      record.Variant.Effective(),
 	}
 }
 
@@ -1496,6 +3564,7 @@ func mapSeatRecordToResponse(record persistence.SeatRecord) seatResponse {
 		AgentVersionID: record.AgentVersionID,
 		Stack:          record.Stack,
 		Status:         record.Status,
+		View:           record.View,
 		CreatedAt:      record.CreatedAt,
 	}
 }
@@ -1509,7 +3578,18 @@ func mapHandRecordToResponse(record persistence.HandRecord) handResponse {
 		EndedAt:       record.EndedAt,
 		FinalPhase:    record.FinalPhase,
 		WinnerSummary: append([]domain.PotAward(nil), record.WinnerSummary...),
+		IDCreatedAt:   idCreatedAt(record.HandID),
+	}
+}
+
+// idCreatedAt is the creation time embedded in a time-ordered (UUIDv7) ID,
+// or nil for IDs that do not carry one.
+func idCreatedAt(id string) *time.Time {
+	createdAt, ok := ids.Timestamp(id)
+	if !ok {
+		return nil
 	}
+	return &createdAt
 }
 
 func mapTableRunRecordToResponse(record persistence.TableRunRecord) tableRunResponse {
@@ -1540,6 +3620,17 @@ func (i CallerIdentity) isAdmin() bool {
 	return i.Role == CallerRoleAdmin
 }
 
+// isDeploymentAdmin is an admin not scoped to a tenant.
+func (i CallerIdentity) isDeploymentAdmin() bool {
+	return i.isAdmin() && i.TenantID == ""
+}
+
+// inTenant reports whether the caller may see resources owned by tenantID.
+// Callers not scoped to a tenant see every tenant.
+func (i CallerIdentity) inTenant(tenantID string) bool {
+	return i.TenantID == "" || i.TenantID == tenantID
+}
+
 func (i CallerIdentity) seatNo() domain.SeatNo {
 	if i.Seat == nil {
 		return 0
@@ -1549,6 +3640,7 @@ func (i CallerIdentity) seatNo() domain.SeatNo {
 
 func writeJSON(w http.ResponseWriter, status int, payload any) {
 	w.Header().Set("Content-Type", "application/json")
+	w.Header().Set("X-Wire-Schema-Version", strconv.Itoa(domain.WireSchemaVersion))
 	w.WriteHeader(status)
 	_ = json.NewEncoder(w).Encode(payload)
 }
@@ -1573,6 +3665,17 @@ func decodeStrictJSON(w http.ResponseWriter, r *http.Request, dest any) bool {
 	return true
 }
 
+// newID names a stored resource with the configured generator, falling
+// back to the built-in format.
+func (s *Server) newID(prefix string) string {
+	if s.config.IDs != nil {
+		if id, err := s.config.IDs.NewID(); err == nil {
+			return prefix + "-" + id
+		}
+	}
+	return newID(prefix)
+}
+
 func newID(prefix string) string {
 	random := make([]byte, 8)
 	if _, err := cryptorand.Read(random); err != nil {
@@ -1580,3 +3683,50 @@ func newID(prefix string) string {
 	}
 	return fmt.Sprintf("%s-%d-%s", prefix, time.Now().UTC().UnixNano(), hex.EncodeToString(random))
 }
+
+// instrumentedProvider records agent latency and failures; the runner turns
+// failures into fallback actions, so the reason is otherwise lost. It also
+// reports each outstanding decision and its deadline to await: the call's
+// own deadline when it has one, otherwise timeout from now.
+type instrumentedProvider struct {
+	inner   tablerunner.ActionProvider
+	metrics *metrics.Arena
+	timeout time.Duration
+	await   func(state domain.HandState, deadline time.Time)
+}
+
+func (p instrumentedProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	start := time.Now()
+	deadline, ok := ctx.Deadline()
+	if !ok && p.timeout > 0 {
+		deadline = start.Add(p.timeout)
+	}
+	p.await(state, deadline)
+	action, err := p.inner.NextAction(ctx, state)
+	metrics.ObserveSince(p.metrics.AgentLatency, start)
+	if err != nil {
+		reason := agentErrorReason(err)
+		if reason == "timeout" {
+			p.metrics.AgentTimeouts.Inc()
+		}
+		p.metrics.AgentErrors.Inc(reason)
+	}
+	return action, err
+}
+
+func agentErrorReason(err error) string {
+	switch {
+	case errors.Is(err, agentclient.ErrRequestTimeout), errors.Is(err, context.DeadlineExceeded):
+		return "timeout"
+	case errors.Is(err, agentclient.ErrNetwork):
+		return "network"
+	case errors.Is(err, agentclient.ErrMalformedResponse):
+		return "malformed"
+	case errors.Is(err, agentclient.ErrIllegalAgentAction):
+		return "illegal_action"
+	case errors.Is(err, agentclient.ErrEndpointNotConfigured):
+		return "not_configured"
+	default:
+		return "other"
+	}
+}
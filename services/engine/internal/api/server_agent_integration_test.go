@@ -13,6 +13,7 @@ import (
 
 	"github.com/imaddar/poker-arena/services/engine/internal/agentclient"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
 	"github.com/imaddar/poker-arena/services/engine/internal/persistence"
 	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
 )
@@ -436,6 +437,64 @@ func TestSeatToken_ReplayRedactsOpponentHoleCardsForNonShowdown(t *testing.T) {
 	if oppCards != 0 {
 		t.Fatalf("expected opponent hole cards to be redacted in non-showdown hand, got %d", oppCards)
 	}
+	if replay.Equity != nil {
+		t.Fatalf("expected the equity sparkline to be withheld with opponent cards hidden, got %+v", replay.Equity)
+	}
+}
+
+func TestReplay_IncludesEquityAtEveryStreet(t *testing.T) {
+	t.Parallel()
+
+	agentA := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		writeAgentAction(t, w, r, false)
+	}))
+	defer agentA.Close()
+	agentB := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		writeAgentAction(t, w, r, false)
+	}))
+	defer agentB.Close()
+
+	repo := persistence.NewInMemoryRepository()
+	adminToken := "admin-token"
+	server := newIntegrationServer(
+		t,
+		repo,
+		adminToken,
+		[]string{mustHost(t, agentA.URL), mustHost(t, agentB.URL)},
+		250*time.Millisecond,
+	)
+
+	start := StartRequest{
+		HandsToRun: 1,
+		Seats: []StartSeat{
+			{SeatNo: 1, Stack: 10_000, Status: domain.SeatStatusActive, AgentEndpoint: agentA.URL + "/callback"},
+			{SeatNo: 2, Stack: 10_000, Status: domain.SeatStatusActive, AgentEndpoint: agentB.URL + "/callback"},
+		},
+	}
+	startTable(t, server, adminToken, "table-equity", start, http.StatusOK)
+	status := waitForTerminalStatus(t, server, adminToken, "table-equity", 3*time.Second)
+	if status.Status != persistence.TableRunStatusCompleted {
+		t.Fatalf("expected completed status, got %q", status.Status)
+	}
+
+	// Both agents check or call down, so the hand reaches the river.
+	hands := listHands(t, server, adminToken, "table-equity")
+	if len(hands) != 1 {
+		t.Fatalf("expected one hand, got %d", len(hands))
+	}
+	replay := getReplay(t, server, adminToken, hands[0].HandID)
+	streets := []domain.Street{domain.StreetPreflop, domain.StreetFlop, domain.StreetTurn, domain.StreetRiver}
+	if len(replay.Equity) != len(streets) {
+		t.Fatalf("expected a point per street, got %+v", replay.Equity)
+	}
+	for i, point := range replay.Equity {
+		if point.Street != streets[i] || len(point.Equity) != 2 {
+			t.Fatalf("unexpected %s point %+v", streets[i], point)
+		}
+		if total := point.Equity[0].Share.Add(point.Equity[1].Share); total.Cmp(equity.One()) != 0 {
+			t.Fatalf("expected %s shares to sum to 1, got %s", point.Street, total)
+		}
+	}
 }
 
 func TestSeatToken_LatestReplayReturnsTableOnlyWhenSeatNotInAnyHand(t *testing.T) {
@@ -1,18 +1,34 @@
 package api
 
 import (
+	"bytes"
 	"context"
 	"encoding/json"
 	"fmt"
 	"net/http"
 	"net/http/httptest"
+	"os"
+	"path/filepath"
 	"strings"
 	"testing"
 	"time"
 
+	"github.com/imaddar/poker-arena/services/engine/internal/archive"
+	"github.com/imaddar/poker-arena/services/engine/internal/audience"
+	"github.com/imaddar/poker-arena/services/engine/internal/cashier"
+	"github.com/imaddar/poker-arena/services/engine/internal/commentary"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/embargo"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
+	"github.com/imaddar/poker-arena/services/engine/internal/ids"
 	"github.com/imaddar/poker-arena/services/engine/internal/persistence"
+	"github.com/imaddar/poker-arena/services/engine/internal/retention"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/sidemarket"
+	"github.com/imaddar/poker-arena/services/engine/internal/statemachine"
+	"github.com/imaddar/poker-arena/services/engine/internal/stats"
 	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+	"github.com/imaddar/poker-arena/services/engine/internal/tournament"
 )
 
 func TestGetHands_ReturnsPersistedHandsForTable(t *testing.T) {
@@ -76,6 +92,50 @@ func TestGetHands_ReturnsPersistedHandsForTable(t *testing.T) {
 	}
 }
 
+func TestGetHands_ExposesCreationTimeOfTimeOrderedIDs(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	created := time.Date(2026, 10, 16, 12, 0, 0, 0, time.UTC)
+	handID, err := ids.NewV7(func() time.Time { return created }, nil).NewID()
+	if err != nil {
+		t.Fatalf("NewID failed: %v", err)
+	}
+	for i, id := range []string{"hand-" + handID, "hand-legacy"} {
+		if err := repo.CreateHand(persistence.HandRecord{
+			HandID:     id,
+			TableID:    "table-1",
+			HandNo:     uint64(i + 1),
+			StartedAt:  created.Add(time.Duration(i) * time.Second),
+			FinalPhase: domain.HandPhaseComplete,
+		}); err != nil {
+			t.Fatalf("CreateHand %s failed: %v", id, err)
+		}
+	}
+
+	server := NewServer(repo, nil, nil, ServerConfig{})
+	req := httptest.NewRequest(http.MethodGet, "/tables/table-1/hands", nil)
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+
+	var hands []handResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &hands); err != nil {
+		t.Fatalf("failed to decode response: %v", err)
+	}
+	if len(hands) != 2 {
+		t.Fatalf("expected 2 hands, got %d", len(hands))
+	}
+	if hands[0].IDCreatedAt == nil || !hands[0].IDCreatedAt.Equal(created) {
+		t.Fatalf("expected id_created_at %s, got %v", created, hands[0].IDCreatedAt)
+	}
+	if hands[1].IDCreatedAt != nil {
+		t.Fatalf("expected no id_created_at for a legacy id, got %v", hands[1].IDCreatedAt)
+	}
+}
+
 func TestListTables_AdminCanListTables(t *testing.T) {
 	t.Parallel()
 
@@ -131,6 +191,48 @@ func TestListTables_AdminCanListTables(t *testing.T) {
 	}
 }
 
+func TestReadOnlyReplica_ServesReadsAndRejectsWrites(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	if err := repo.CreateTable(persistence.TableRecord{
+		ID:         "table-1",
+		Name:       "Alpha",
+		MaxSeats:   6,
+		SmallBlind: 50,
+		BigBlind:   100,
+		Status:     string(persistence.TableRunStatusIdle),
+		CreatedAt:  time.Now().UTC(),
+	}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		ReadOnly:          true,
+	})
+
+	req := httptest.NewRequest(http.MethodGet, "/tables/table-1/state", nil)
+	req.Header.Set("Authorization", "Bearer admin")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	if got := w.Header().Get("X-Engine-Role"); got != "replica" {
+		t.Fatalf("expected replica role header, got %q", got)
+	}
+
+	for _, path := range []string{"/tables", "/tables/table-1/start", "/tables/table-1/join"} {
+		req = httptest.NewRequest(http.MethodPost, path, strings.NewReader(`{}`))
+		req.Header.Set("Authorization", "Bearer admin")
+		w = httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		if w.Code != http.StatusMethodNotAllowed {
+			t.Fatalf("expected status %d for POST %s, got %d", http.StatusMethodNotAllowed, path, w.Code)
+		}
+	}
+}
+
 func TestListTables_UnauthenticatedReturnsUnauthorized(t *testing.T) {
 	t.Parallel()
 
@@ -235,6 +337,117 @@ func TestCreateUser_Succeeds(t *testing.T) {
 	}
 }
 
+func TestCreateUser_UsesInjectedIDGenerator(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		IDs:               ids.NewSequence(),
+	})
+	req := httptest.NewRequest(http.MethodPost, "/users", strings.NewReader(`{"name":"operator","token":"operator-token"}`))
+	req.Header.Set("Authorization", "Bearer admin")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected %d got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var user persistence.UserRecord
+	if err := json.Unmarshal(w.Body.Bytes(), &user); err != nil {
+		t.Fatalf("decode failed: %v", err)
+	}
+	if want := "user-" + ids.ReservedTestUUID(1); user.ID != want {
+		t.Fatalf("expected id %s, got %s", want, user.ID)
+	}
+}
+
+func TestDeleteUser_ScrubsIdentifiersButKeepsIDs(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.CreateUser(persistence.UserRecord{ID: "u1", Name: "Alice", Token: "tok", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateUser failed: %v", err)
+	}
+	if err := repo.CreateAgent(persistence.AgentRecord{ID: "a1", UserID: "u1", Name: "alice-bot", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgent failed: %v", err)
+	}
+	if err := repo.CreateAgentVersion(persistence.AgentVersionRecord{ID: "v1", AgentID: "a1", Version: 1, EndpointURL: "http://alice.example/act", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgentVersion failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		TenantAdminTokens: map[string]string{"club-admin": "club-a"},
+		IDs:               ids.NewSequence(),
+	})
+	do := func(token string, path string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodDelete, path, nil)
+		req.Header.Set("Authorization", "Bearer "+token)
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+
+	if w := do("club-admin", "/users/u1"); w.Code != http.StatusForbidden {
+		t.Fatalf("expected tenant admins forbidden, got %d body=%s", w.Code, w.Body.String())
+	}
+	w := do("admin", "/users/u1")
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected %d got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var deleted deleteUserResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &deleted); err != nil {
+		t.Fatalf("decode failed: %v", err)
+	}
+	if want := "deleted-" + ids.ReservedTestUUID(1); deleted.UserID != "u1" || deleted.Alias != want {
+		t.Fatalf("expected u1 aliased %s, got %+v", want, deleted)
+	}
+	if agent, _, _ := repo.GetAgent("a1"); agent.Name != deleted.Alias || agent.UserID != "u1" {
+		t.Fatalf("expected the agent renamed under its ID, got %+v", agent)
+	}
+	if version, _, _ := repo.GetAgentVersion("v1"); version.EndpointURL != "" {
+		t.Fatalf("expected the endpoint forgotten, got %q", version.EndpointURL)
+	}
+	if w := do("admin", "/users/missing"); w.Code != http.StatusNotFound {
+		t.Fatalf("expected %d for an unknown user, got %d", http.StatusNotFound, w.Code)
+	}
+}
+
+func TestRetentionPurge_EnforcesTheConfiguredPolicy(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.UpsertTableRun(persistence.TableRunRecord{TableID: "table-1", Status: persistence.TableRunStatusCompleted, StartedAt: now.Add(-3 * time.Hour)}); err != nil {
+		t.Fatalf("UpsertTableRun failed: %v", err)
+	}
+	endedAt := now.Add(-2 * time.Hour)
+	if err := repo.CreateHand(persistence.HandRecord{HandID: "hand-1", TableID: "table-1", HandNo: 1, StartedAt: endedAt, EndedAt: &endedAt}); err != nil {
+		t.Fatalf("CreateHand failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		Retention:         retention.Policy{Hands: time.Hour},
+	})
+	req := httptest.NewRequest(http.MethodPost, "/retention/purge", nil)
+	req.Header.Set("Authorization", "Bearer admin")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected %d got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var report retention.Report
+	if err := json.Unmarshal(w.Body.Bytes(), &report); err != nil {
+		t.Fatalf("decode failed: %v", err)
+	}
+	if report.HandsPurged != 1 {
+		t.Fatalf("expected the expired hand purged, got %+v", report)
+	}
+	if _, ok, _ := repo.GetHand("hand-1"); ok {
+		t.Fatal("expected hand-1 deleted")
+	}
+}
+
 func TestCreateAgent_MissingUserFails(t *testing.T) {
 	t.Parallel()
 
@@ -282,6 +495,215 @@ func TestCreateAgentVersion_ValidatesEndpointAndAllowlist(t *testing.T) {
 	}
 }
 
+func TestAgentPreferences_UpdatesShowdownPreference(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.CreateUser(persistence.UserRecord{ID: "u1", Name: "u", Token: "tok", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateUser failed: %v", err)
+	}
+	if err := repo.CreateAgent(persistence.AgentRecord{ID: "a1", UserID: "u1", Name: "a", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgent failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{AdminBearerTokens: map[string]struct{}{"admin": {}}})
+
+	cases := []struct {
+		path string
+		body string
+		want int
+	}{
+		{path: "/agents/a1/preferences", body: `{"showdown_preference":"sometimes"}`, want: http.StatusBadRequest},
+		{path: "/agents/missing/preferences", body: `{"showdown_preference":"always_show"}`, want: http.StatusNotFound},
+		{path: "/agents/a1/preferences", body: `{"showdown_preference":"always_show"}`, want: http.StatusOK},
+	}
+	for _, tc := range cases {
+		req := httptest.NewRequest(http.MethodPut, tc.path, strings.NewReader(tc.body))
+		req.Header.Set("Authorization", "Bearer admin")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		if w.Code != tc.want {
+			t.Fatalf("%s %s: expected %d got %d body=%s", tc.path, tc.body, tc.want, w.Code, w.Body.String())
+		}
+	}
+
+	agent, ok, err := repo.GetAgent("a1")
+	if err != nil || !ok {
+		t.Fatalf("GetAgent failed: ok=%v err=%v", ok, err)
+	}
+	if agent.ShowdownPreference != domain.ShowdownAlwaysShow {
+		t.Fatalf("expected persisted always_show, got %q", agent.ShowdownPreference)
+	}
+}
+
+func TestAgentPreferences_RegistersBuyInPolicyForJoinedSeats(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.CreateUser(persistence.UserRecord{ID: "u1", Name: "u", Token: "tok", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateUser failed: %v", err)
+	}
+	if err := repo.CreateAgent(persistence.AgentRecord{ID: "a1", UserID: "u1", Name: "a", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgent failed: %v", err)
+	}
+	if err := repo.CreateAgentVersion(persistence.AgentVersionRecord{ID: "v1", AgentID: "a1", Version: 1, EndpointURL: "http://agent.local:9001/cb", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgentVersion failed: %v", err)
+	}
+	if err := repo.CreateTable(persistence.TableRecord{ID: "table-1", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "waiting", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	if err := repo.UpsertSeat(persistence.SeatRecord{ID: "s1", TableID: "table-1", SeatNo: 1, AgentID: "a1", AgentVersionID: "v1", Stack: 10000, Status: domain.SeatStatusActive, CreatedAt: now}); err != nil {
+		t.Fatalf("UpsertSeat failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{AdminBearerTokens: map[string]struct{}{"admin": {}}})
+
+	put := func(body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodPut, "/agents/a1/preferences", strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer admin")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+	if w := put(`{"buy_in_policy":{"buy_in_bb":40,"top_up_below_bb":60,"bankroll":1000}}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected %d got %d body=%s", http.StatusBadRequest, w.Code, w.Body.String())
+	}
+	if w := put(`{"buy_in_policy":{"buy_in_bb":100,"top_up_below_bb":60,"bankroll":50000}}`); w.Code != http.StatusOK {
+		t.Fatalf("expected %d got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+
+	req, _, err := server.hydrateStartRequest("table-1", StartRequest{HandsToRun: 10})
+	if err != nil {
+		t.Fatalf("hydrateStartRequest failed: %v", err)
+	}
+	want := cashier.Policy{BuyInBB: 100, TopUpBelowBB: 60, Bankroll: 50000}
+	if len(req.Seats) != 1 || req.Seats[0].BuyInPolicy == nil || *req.Seats[0].BuyInPolicy != want {
+		t.Fatalf("expected the seat to carry the agent's policy, got %+v", req.Seats)
+	}
+	if got := startCashier(req).Bankroll(1); got != 50000 {
+		t.Fatalf("expected the run's cashier to hold the bankroll, got %d", got)
+	}
+
+	if w := put(`{"showdown_preference":"always_show"}`); w.Code != http.StatusOK {
+		t.Fatalf("expected %d got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	if agent, _, _ := repo.GetAgent("a1"); agent.BuyInPolicy != nil {
+		t.Fatalf("expected a preferences update without a policy to clear it, got %+v", agent.BuyInPolicy)
+	}
+}
+
+func TestTables_PinTheirRulesVersion(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.CreateUser(persistence.UserRecord{ID: "u1", Name: "u", Token: "tok", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateUser failed: %v", err)
+	}
+	if err := repo.CreateAgent(persistence.AgentRecord{ID: "a1", UserID: "u1", Name: "a", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgent failed: %v", err)
+	}
+	if err := repo.CreateAgentVersion(persistence.AgentVersionRecord{ID: "v1", AgentID: "a1", Version: 1, EndpointURL: "http://agent.local:9001/cb", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgentVersion failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{AdminBearerTokens: map[string]struct{}{"admin": {}}})
+	create := func(body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodPost, "/tables", strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer admin")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+
+	var current tableResponse
+	if w := create(`{"name":"current"}`); w.Code != http.StatusOK || json.Unmarshal(w.Body.Bytes(), &current) != nil || current.RulesVersion != domain.CurrentRulesVersion {
+		t.Fatalf("expected a table pinned to the current rules, got %d body=%s", w.Code, w.Body.String())
+	}
+	if w := create(`{"name":"future","rules_version":99}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected an unknown rules version rejected, got %d body=%s", w.Code, w.Body.String())
+	}
+	var legacy tableResponse
+	if w := create(`{"name":"legacy","rules_version":1}`); w.Code != http.StatusOK || json.Unmarshal(w.Body.Bytes(), &legacy) != nil || legacy.RulesVersion != domain.RulesV1 {
+		t.Fatalf("expected a table pinned to rules version 1, got %d body=%s", w.Code, w.Body.String())
+	}
+	if err := repo.UpsertSeat(persistence.SeatRecord{ID: "s1", TableID: legacy.ID, SeatNo: 1, AgentID: "a1", AgentVersionID: "v1", Stack: 10000, Status: domain.SeatStatusActive, CreatedAt: now}); err != nil {
+		t.Fatalf("UpsertSeat failed: %v", err)
+	}
+
+	req, _, err := server.hydrateStartRequest(legacy.ID, StartRequest{HandsToRun: 10})
+	if err != nil {
+		t.Fatalf("hydrateStartRequest failed: %v", err)
+	}
+	if req.TableConfig.RulesVersion != domain.RulesV1 {
+		t.Fatalf("expected the run to play the pinned rules, got %d", req.TableConfig.RulesVersion)
+	}
+	cfg := domain.DefaultV0TableConfig()
+	cfg.RulesVersion = domain.CurrentRulesVersion
+	if _, status, err := server.hydrateStartRequest(legacy.ID, StartRequest{HandsToRun: 10, TableConfig: &cfg}); status != http.StatusBadRequest || err == nil {
+		t.Fatalf("expected a run on other rules rejected, got %d (%v)", status, err)
+	}
+}
+
+func TestLeaveAndRejoin_EnforcesRatholeWindow(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.CreateUser(persistence.UserRecord{ID: "u1", Name: "u", Token: "tok", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateUser failed: %v", err)
+	}
+	if err := repo.CreateAgent(persistence.AgentRecord{ID: "a1", UserID: "u1", Name: "a1", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgent failed: %v", err)
+	}
+	if err := repo.CreateAgentVersion(persistence.AgentVersionRecord{ID: "v1", AgentID: "a1", Version: 1, EndpointURL: "http://agent.local:9001/cb", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgentVersion failed: %v", err)
+	}
+	for _, table := range []persistence.TableRecord{
+		{ID: "t1", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "idle", CreatedAt: now},
+		{ID: "t2", Name: "t2", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "idle", CreatedAt: now},
+		{ID: "t3", Name: "t3", MaxSeats: 6, SmallBlind: 100, BigBlind: 200, Status: "idle", CreatedAt: now},
+	} {
+		if err := repo.CreateTable(table); err != nil {
+			t.Fatalf("CreateTable %s failed: %v", table.ID, err)
+		}
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		RatholeWindow:     time.Hour,
+	})
+
+	post := func(path, body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodPost, path, strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer admin")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+	join := func(tableID string, stack int) *httptest.ResponseRecorder {
+		return post("/tables/"+tableID+"/join", fmt.Sprintf(`{"seat_no":1,"agent_id":"a1","agent_version_id":"v1","stack":%d}`, stack))
+	}
+
+	if w := join("t1", 10000); w.Code != http.StatusOK {
+		t.Fatalf("join t1 failed: %d body=%s", w.Code, w.Body.String())
+	}
+	if w := post("/tables/t1/leave", `{"seat_no":1,"stack":25000}`); w.Code != http.StatusOK {
+		t.Fatalf("leave failed: %d body=%s", w.Code, w.Body.String())
+	}
+	if w := post("/tables/t1/leave", `{"seat_no":1}`); w.Code != http.StatusNotFound {
+		t.Fatalf("expected %d leaving an empty seat, got %d", http.StatusNotFound, w.Code)
+	}
+
+	if w := join("t2", 10000); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected ratholing rejoin to fail with %d, got %d body=%s", http.StatusBadRequest, w.Code, w.Body.String())
+	}
+	if w := join("t3", 10000); w.Code != http.StatusOK {
+		t.Fatalf("expected different stakes to allow a short buy-in, got %d body=%s", w.Code, w.Body.String())
+	}
+	if w := join("t2", 25000); w.Code != http.StatusOK {
+		t.Fatalf("expected buy-in at the departure stack to succeed, got %d body=%s", w.Code, w.Body.String())
+	}
+}
+
 func TestCreateTableJoinAndState(t *testing.T) {
 	t.Parallel()
 
@@ -365,17 +787,743 @@ func TestCreateTableJoinAndState(t *testing.T) {
 	}
 }
 
-func TestGetLatestReplay_NoHistoryReturnsTableOnly(t *testing.T) {
+func TestTenants_IsolateTablesAgentsAndHistory(t *testing.T) {
 	t.Parallel()
 
 	repo := persistence.NewInMemoryRepository()
 	now := time.Now().UTC()
-	if err := repo.CreateTable(persistence.TableRecord{
-		ID:         "table-1",
-		Name:       "table-one",
-		MaxSeats:   6,
-		SmallBlind: 50,
-		BigBlind:   100,
+	if err := repo.CreateUser(persistence.UserRecord{ID: "u1", TenantID: "club-b", Name: "u", Token: "tok", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateUser failed: %v", err)
+	}
+	if err := repo.CreateAgent(persistence.AgentRecord{ID: "a1", TenantID: "club-b", UserID: "u1", Name: "a1", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgent failed: %v", err)
+	}
+	if err := repo.CreateAgentVersion(persistence.AgentVersionRecord{ID: "v1", AgentID: "a1", Version: 1, EndpointURL: "http://agent.local:9001/cb", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgentVersion failed: %v", err)
+	}
+
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		TenantAdminTokens: map[string]string{"club-a-admin": "club-a", "club-b-admin": "club-b"},
+	})
+	do := func(method string, path string, token string, body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(method, path, strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer "+token)
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+	listTables := func(path string, token string) []tableResponse {
+		t.Helper()
+		w := do(http.MethodGet, path, token, "")
+		if w.Code != http.StatusOK {
+			t.Fatalf("list tables failed: %d body=%s", w.Code, w.Body.String())
+		}
+		var tables []tableResponse
+		if err := json.Unmarshal(w.Body.Bytes(), &tables); err != nil {
+			t.Fatalf("decode tables failed: %v", err)
+		}
+		return tables
+	}
+
+	w := do(http.MethodPost, "/tables", "club-a-admin", `{"name":"club a main"}`)
+	if w.Code != http.StatusOK {
+		t.Fatalf("create table failed: %d body=%s", w.Code, w.Body.String())
+	}
+	var table tableResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &table); err != nil {
+		t.Fatalf("decode table failed: %v", err)
+	}
+	if table.TenantID != "club-a" {
+		t.Fatalf("expected the table to belong to club-a, got %q", table.TenantID)
+	}
+	if err := repo.CreateHand(persistence.HandRecord{HandID: "hand-1", TableID: table.ID, HandNo: 1, StartedAt: now}); err != nil {
+		t.Fatalf("CreateHand failed: %v", err)
+	}
+
+	if got := listTables("/tables", "club-a-admin"); len(got) != 1 {
+		t.Fatalf("expected club-a to see its table, got %+v", got)
+	}
+	if got := listTables("/tables", "club-b-admin"); len(got) != 0 {
+		t.Fatalf("expected club-b to see no tables, got %+v", got)
+	}
+	if got := listTables("/tables?tenant_id=club-b", "admin"); len(got) != 0 {
+		t.Fatalf("expected the club-b filter to hide club-a tables, got %+v", got)
+	}
+	if got := listTables("/tables", "admin"); len(got) != 1 {
+		t.Fatalf("expected the deployment admin to see every table, got %+v", got)
+	}
+
+	for _, path := range []string{"/tables/" + table.ID + "/hands", "/hands/hand-1/actions"} {
+		if w := do(http.MethodGet, path, "club-b-admin", ""); w.Code != http.StatusNotFound {
+			t.Fatalf("expected %d for club-b on %s, got %d body=%s", http.StatusNotFound, path, w.Code, w.Body.String())
+		}
+		if w := do(http.MethodGet, path, "club-a-admin", ""); w.Code != http.StatusOK {
+			t.Fatalf("expected %d for club-a on %s, got %d body=%s", http.StatusOK, path, w.Code, w.Body.String())
+		}
+	}
+	if w := do(http.MethodPost, "/tables", "club-b-admin", `{"name":"sneaky","tenant_id":"club-a"}`); w.Code != http.StatusForbidden {
+		t.Fatalf("expected %d creating in another tenant, got %d", http.StatusForbidden, w.Code)
+	}
+	if w := do(http.MethodGet, "/metrics", "club-a-admin", ""); w.Code != http.StatusForbidden {
+		t.Fatalf("expected %d for tenant admin on /metrics, got %d", http.StatusForbidden, w.Code)
+	}
+	if w := do(http.MethodPost, "/tables/"+table.ID+"/join", "admin", `{"seat_no":1,"agent_id":"a1","agent_version_id":"v1","stack":10000}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected %d seating a club-b agent at a club-a table, got %d body=%s", http.StatusBadRequest, w.Code, w.Body.String())
+	}
+	if w := do(http.MethodPost, "/agents/a1/versions", "club-a-admin", `{"endpoint_url":"http://agent.local:9001/cb"}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected %d versioning another tenant's agent, got %d body=%s", http.StatusBadRequest, w.Code, w.Body.String())
+	}
+}
+
+func TestGetTableRules_ReturnsStandardRanking(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	if err := repo.CreateTable(persistence.TableRecord{ID: "table-1", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "waiting", CreatedAt: time.Now().UTC()}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"seat-1": 1},
+	})
+
+	for _, token := range []string{"admin", "seat-1"} {
+		req := httptest.NewRequest(http.MethodGet, "/tables/table-1/rules", nil)
+		req.Header.Set("Authorization", "Bearer "+token)
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		if w.Code != http.StatusOK {
+			t.Fatalf("expected status %d for %s, got %d body=%s", http.StatusOK, token, w.Code, w.Body.String())
+		}
+		var body struct {
+			Variant    string `json:"variant"`
+			DeckSize   int    `json:"deck_size"`
+			Categories []struct {
+				Name string `json:"name"`
+			} `json:"categories"`
+		}
+		if err := json.Unmarshal(w.Body.Bytes(), &body); err != nil {
+			t.Fatalf("decode rules failed: %v", err)
+		}
+		if body.Variant != "standard" || body.DeckSize != 52 {
+			t.Fatalf("unexpected rules header: %+v", body)
+		}
+		if len(body.Categories) == 0 || body.Categories[0].Name != "straight_flush" {
+			t.Fatalf("expected straight_flush first, got %+v", body.Categories)
+		}
+	}
+
+	req := httptest.NewRequest(http.MethodGet, "/tables/missing/rules", nil)
+	req.Header.Set("Authorization", "Bearer admin")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusNotFound {
+		t.Fatalf("expected status %d, got %d", http.StatusNotFound, w.Code)
+	}
+}
+
+func TestGetTableRules_ReportsTheTableVariant(t *testing.T) {
+	t.Parallel()
+
+	server := NewServer(persistence.NewInMemoryRepository(), nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+	})
+	do := func(method, path, body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(method, path, strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer admin")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+
+	if w := do(http.MethodPost, "/tables", `{"name":"badugi","variant":"badugi"}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected status %d for an unknown variant, got %d body=%s", http.StatusBadRequest, w.Code, w.Body.String())
+	}
+	w := do(http.MethodPost, "/tables", `{"name":"razz","variant":"razz"}`)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var table tableResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &table); err != nil {
+		t.Fatalf("decode table failed: %v", err)
+	}
+	if table.Variant != domain.VariantRazz {
+		t.Fatalf("expected a razz table, got %+v", table)
+	}
+
+	w = do(http.MethodGet, "/tables/"+table.ID+"/rules", "")
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var ranking rules.RankingRules
+	if err := json.Unmarshal(w.Body.Bytes(), &ranking); err != nil {
+		t.Fatalf("decode rules failed: %v", err)
+	}
+	want, err := rules.RankingRulesFor(rules.VariantRazz)
+	if err != nil {
+		t.Fatalf("RankingRulesFor failed: %v", err)
+	}
+	if ranking.Variant != rules.VariantRazz || len(ranking.Categories) != len(want.Categories) || ranking.Categories[0].Name != want.Categories[0].Name {
+		t.Fatalf("expected the razz ranking, got %+v", ranking)
+	}
+}
+
+func TestGetTableConnections_ReportsSeatHealth(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	if err := repo.CreateTable(persistence.TableRecord{ID: "table-1", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "waiting", CreatedAt: time.Now().UTC()}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"seat-1": 1},
+	})
+	server.observeConnection("table-1", 1, tablerunner.ActionTiming{Policy: domain.DeadlineOnTime, ThinkTime: 80 * time.Millisecond})
+	for i := 0; i < 3; i++ {
+		server.observeConnection("table-1", 2, tablerunner.ActionTiming{Policy: domain.DeadlineNoDecision})
+	}
+
+	for _, token := range []string{"admin", "seat-1"} {
+		req := httptest.NewRequest(http.MethodGet, "/tables/table-1/connections", nil)
+		req.Header.Set("Authorization", "Bearer "+token)
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		if w.Code != http.StatusOK {
+			t.Fatalf("expected status %d for %s, got %d body=%s", http.StatusOK, token, w.Code, w.Body.String())
+		}
+		var qualities []stats.ConnectionQuality
+		if err := json.Unmarshal(w.Body.Bytes(), &qualities); err != nil {
+			t.Fatalf("decode connections failed: %v", err)
+		}
+		if len(qualities) != 2 || qualities[0].Health != stats.ConnectionGood || qualities[0].PingMS != 80 {
+			t.Fatalf("expected seat 1 healthy at 80ms, got %+v", qualities)
+		}
+		if qualities[1].Health != stats.ConnectionPoor || qualities[1].MissedDeadlines != 3 {
+			t.Fatalf("expected seat 2 poor after three misses, got %+v", qualities[1])
+		}
+	}
+}
+
+func TestGetOperatorDashboard_SummarizesRunningTables(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.UpsertTableRun(persistence.TableRunRecord{
+		TableID:        "table-1",
+		Status:         persistence.TableRunStatusRunning,
+		StartedAt:      now.Add(-10 * time.Minute),
+		HandsRequested: 50,
+		HandsCompleted: 2,
+	}); err != nil {
+		t.Fatalf("UpsertTableRun failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"seat-1": 1},
+		StuckHandAfter:    time.Minute,
+	})
+	server.runs["table-1"] = &tableRun{live: liveHand{
+		handID:      "hand-3",
+		handNo:      3,
+		startedAt:   now.Add(-3 * time.Minute),
+		lastEventAt: now.Add(-2 * time.Minute),
+		awaiting:    true,
+		state:       domain.HandState{HandID: "hand-3", ActingSeat: 2},
+		deadline:    now.Add(time.Hour),
+		eventLag:    40 * time.Millisecond,
+	}}
+	server.runs["table-2"] = &tableRun{live: liveHand{eventLag: 15 * time.Millisecond}}
+	for i := 0; i < 3; i++ {
+		server.observeConnection("table-1", 2, tablerunner.ActionTiming{Policy: domain.DeadlineNoDecision})
+	}
+	server.observeConnection("table-2", 1, tablerunner.ActionTiming{Policy: domain.DeadlineOnTime, ThinkTime: 50 * time.Millisecond})
+
+	req := httptest.NewRequest(http.MethodGet, "/operator/dashboard", nil)
+	req.Header.Set("Authorization", "Bearer seat-1")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusForbidden {
+		t.Fatalf("expected status %d for a seat token, got %d", http.StatusForbidden, w.Code)
+	}
+
+	req = httptest.NewRequest(http.MethodGet, "/operator/dashboard", nil)
+	req.Header.Set("Authorization", "Bearer admin")
+	w = httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var dashboard operatorDashboardResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &dashboard); err != nil {
+		t.Fatalf("decode dashboard failed: %v", err)
+	}
+	if dashboard.TablesRunning != 2 || dashboard.HandsInProgress != 1 || dashboard.EventLagMS != 40 {
+		t.Fatalf("unexpected totals: %+v", dashboard)
+	}
+	if dashboard.Bots != (operatorBotHealth{Good: 1, Poor: 1}) {
+		t.Fatalf("expected one good and one poor bot, got %+v", dashboard.Bots)
+	}
+	table := dashboard.Tables[0]
+	if table.TableID != "table-1" || table.HandsCompleted != 2 || table.HandsRequested != 50 || len(table.Seats) != 1 {
+		t.Fatalf("unexpected table summary: %+v", table)
+	}
+	hand := table.Hand
+	if hand == nil || hand.HandID != "hand-3" || hand.ActingSeat == nil || *hand.ActingSeat != 2 {
+		t.Fatalf("expected hand-3 waiting on seat 2, got %+v", hand)
+	}
+	if hand.TimeoutRemainingMS == nil || *hand.TimeoutRemainingMS <= 0 {
+		t.Fatalf("expected time left on the decision, got %v", hand.TimeoutRemainingMS)
+	}
+	if dashboard.Tables[1].TableID != "table-2" || dashboard.Tables[1].Hand != nil {
+		t.Fatalf("expected table-2 between hands, got %+v", dashboard.Tables[1])
+	}
+	if len(dashboard.Alerts) != 1 || dashboard.Alerts[0].Kind != "stuck_hand" || dashboard.Alerts[0].HandID != "hand-3" {
+		t.Fatalf("expected a stuck hand alert for hand-3, got %+v", dashboard.Alerts)
+	}
+}
+
+func TestValidateAction_DryRunsAgainstPendingDecision(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	state, err := statemachine.StartNewHand(statemachine.StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      []domain.SeatState{domain.NewSeatState(1, cfg.StartingStack), domain.NewSeatState(2, cfg.StartingStack)},
+		ButtonSeat: 1,
+		Config:     cfg,
+		HandID:     "hand-1",
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+	other := domain.SeatNo(1)
+	if state.ActingSeat == other {
+		other = 2
+	}
+	server := NewServer(persistence.NewInMemoryRepository(), nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"acting": state.ActingSeat, "other": other},
+	})
+	server.runs["table-1"] = &tableRun{live: liveHand{handID: "hand-1", awaiting: true, state: state}}
+
+	validate := func(tableID, token, body string) (int, validateActionResponse) {
+		t.Helper()
+		req := httptest.NewRequest(http.MethodPost, "/tables/"+tableID+"/validate-action", strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer "+token)
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		var response validateActionResponse
+		if w.Code == http.StatusOK {
+			if err := json.Unmarshal(w.Body.Bytes(), &response); err != nil {
+				t.Fatalf("decode response failed: %v", err)
+			}
+		}
+		return w.Code, response
+	}
+
+	code, response := validate("table-1", "acting", `{"kind": "all_in"}`)
+	if code != http.StatusOK || !response.Legal || response.Action == nil || response.Action.Kind != domain.ActionRaise || *response.Action.Amount != cfg.StartingStack {
+		t.Fatalf("expected all-in to normalize to a raise to %d, got %d %+v", cfg.StartingStack, code, response)
+	}
+	code, response = validate("table-1", "admin", `{"kind": "raise", "amount": 150}`)
+	want := fmt.Sprintf("illegal action: raise to 150 is below the minimum raise to %d and is not all-in", state.MinRaiseTo)
+	if code != http.StatusOK || response.Legal || response.Error != want || response.SeatNo != state.ActingSeat {
+		t.Fatalf("expected %q, got %d %+v", want, code, response)
+	}
+	if code, _ := validate("table-1", "other", `{"kind": "fold"}`); code != http.StatusConflict {
+		t.Fatalf("expected status %d for a seat that is not acting, got %d", http.StatusConflict, code)
+	}
+	if code, _ := validate("table-2", "admin", `{"kind": "fold"}`); code != http.StatusConflict {
+		t.Fatalf("expected status %d for an idle table, got %d", http.StatusConflict, code)
+	}
+	if state.Pot != server.runs["table-1"].live.state.Pot {
+		t.Fatal("expected the dry run to leave the pending state untouched")
+	}
+}
+
+func TestGetTableMetrics_AggregatesCompletedHands(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	base := time.Date(2026, 1, 1, 10, 0, 0, 0, time.UTC)
+	if err := repo.CreateTable(persistence.TableRecord{ID: "table-1", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "waiting", CreatedAt: base}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	endedAt := base.Add(time.Minute)
+	if err := repo.CreateHand(persistence.HandRecord{
+		HandID:     "hand-1",
+		TableID:    "table-1",
+		HandNo:     1,
+		StartedAt:  base,
+		EndedAt:    &endedAt,
+		FinalPhase: domain.HandPhaseComplete,
+		FinalState: domain.HandState{
+			BigBlind: 100,
+			Seats: []domain.SeatState{
+				{SeatNo: 1, TotalCommitted: 300},
+				{SeatNo: 2, TotalCommitted: 300},
+			},
+			ShowdownAwards: []domain.PotAward{{Amount: 600, Seats: []domain.SeatNo{1}, Reason: "main_pot"}},
+		},
+	}); err != nil {
+		t.Fatalf("CreateHand hand-1 failed: %v", err)
+	}
+	if err := repo.CreateHand(persistence.HandRecord{
+		HandID:     "hand-2",
+		TableID:    "table-1",
+		HandNo:     2,
+		StartedAt:  base.Add(2 * time.Minute),
+		FinalPhase: domain.HandPhaseBetting,
+	}); err != nil {
+		t.Fatalf("CreateHand hand-2 failed: %v", err)
+	}
+	raiseTo := domain.Chips(300)
+	for _, action := range []persistence.ActionRecord{
+		{HandID: "hand-1", Street: domain.StreetPreflop, ActingSeat: 1, Action: domain.ActionRaise, Amount: &raiseTo, At: base},
+		{HandID: "hand-1", Street: domain.StreetPreflop, ActingSeat: 2, Action: domain.ActionCall, At: base},
+	} {
+		if err := repo.AppendAction(action); err != nil {
+			t.Fatalf("AppendAction failed: %v", err)
+		}
+	}
+
+	server := NewServer(repo, nil, nil, ServerConfig{})
+	req := httptest.NewRequest(http.MethodGet, "/tables/table-1/metrics?window=30m", nil)
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var metrics struct {
+		WindowSeconds int64 `json:"window_seconds"`
+		Overall       struct {
+			Hands        int     `json:"hands"`
+			AvgPotBB     float64 `json:"avg_pot_bb"`
+			ShowdownRate float64 `json:"showdown_rate"`
+		} `json:"overall"`
+		Windows []json.RawMessage `json:"windows"`
+	}
+	if err := json.Unmarshal(w.Body.Bytes(), &metrics); err != nil {
+		t.Fatalf("decode metrics failed: %v", err)
+	}
+	if metrics.WindowSeconds != 1800 || len(metrics.Windows) != 1 {
+		t.Fatalf("unexpected window layout: %s", w.Body.String())
+	}
+	if metrics.Overall.Hands != 1 || metrics.Overall.AvgPotBB != 6 || metrics.Overall.ShowdownRate != 1 {
+		t.Fatalf("expected only the completed hand to count, got %+v", metrics.Overall)
+	}
+
+	req = httptest.NewRequest(http.MethodGet, "/tables/table-1/metrics?window=soon", nil)
+	w = httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusBadRequest {
+		t.Fatalf("expected status %d, got %d", http.StatusBadRequest, w.Code)
+	}
+}
+
+func TestGetOpponentStats_GatedByLeagueRuleAndExcludesCaller(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	base := time.Date(2026, 1, 1, 10, 0, 0, 0, time.UTC)
+	if err := repo.CreateUser(persistence.UserRecord{ID: "u1", Name: "u", Token: "tok", CreatedAt: base}); err != nil {
+		t.Fatalf("CreateUser failed: %v", err)
+	}
+	if err := repo.CreateTable(persistence.TableRecord{ID: "table-1", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, RuleFlags: domain.RuleOpponentStats, Status: "waiting", CreatedAt: base}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	if err := repo.CreateTable(persistence.TableRecord{ID: "table-2", Name: "t2", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "waiting", CreatedAt: base}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	for seat := domain.SeatNo(1); seat <= 3; seat++ {
+		agentID, versionID := fmt.Sprintf("a%d", seat), fmt.Sprintf("v%d", seat)
+		if err := repo.CreateAgent(persistence.AgentRecord{ID: agentID, UserID: "u1", Name: agentID, CreatedAt: base}); err != nil {
+			t.Fatalf("CreateAgent failed: %v", err)
+		}
+		if err := repo.CreateAgentVersion(persistence.AgentVersionRecord{ID: versionID, AgentID: agentID, Version: 1, EndpointURL: "http://agent.local:9001/cb", CreatedAt: base}); err != nil {
+			t.Fatalf("CreateAgentVersion failed: %v", err)
+		}
+		if err := repo.UpsertSeat(persistence.SeatRecord{ID: fmt.Sprintf("s%d", seat), TableID: "table-1", SeatNo: seat, AgentID: agentID, AgentVersionID: versionID, Stack: 10000, Status: domain.SeatStatusActive, CreatedAt: base}); err != nil {
+			t.Fatalf("UpsertSeat failed: %v", err)
+		}
+	}
+
+	hands := []struct {
+		id      string
+		handNo  uint64
+		started time.Time
+		preflop []persistence.ActionRecord
+	}{
+		{id: "hand-1", handNo: 1, started: base, preflop: []persistence.ActionRecord{
+			{ActingSeat: 1, Action: domain.ActionRaise},
+			{ActingSeat: 2, Action: domain.ActionCall},
+			{ActingSeat: 3, Action: domain.ActionFold},
+		}},
+		{id: "hand-2", handNo: 2, started: base.Add(time.Minute), preflop: []persistence.ActionRecord{
+			{ActingSeat: 1, Action: domain.ActionFold},
+			{ActingSeat: 2, Action: domain.ActionRaise},
+			{ActingSeat: 3, Action: domain.ActionRaise},
+			{ActingSeat: 2, Action: domain.ActionFold},
+		}},
+		{id: "hand-3", handNo: 3, started: base.Add(time.Hour), preflop: []persistence.ActionRecord{
+			{ActingSeat: 3, Action: domain.ActionFold},
+			{ActingSeat: 1, Action: domain.ActionCall},
+			{ActingSeat: 2, Action: domain.ActionCheck},
+		}},
+	}
+	for _, hand := range hands {
+		endedAt := hand.started.Add(time.Minute)
+		if err := repo.CreateHand(persistence.HandRecord{
+			HandID:     hand.id,
+			TableID:    "table-1",
+			HandNo:     hand.handNo,
+			StartedAt:  hand.started,
+			EndedAt:    &endedAt,
+			FinalPhase: domain.HandPhaseComplete,
+			FinalState: domain.HandState{HoleCards: []domain.SeatCards{{SeatNo: 1}, {SeatNo: 2}, {SeatNo: 3}}},
+		}); err != nil {
+			t.Fatalf("CreateHand %s failed: %v", hand.id, err)
+		}
+		for _, action := range hand.preflop {
+			action.HandID = hand.id
+			action.Street = domain.StreetPreflop
+			action.At = hand.started
+			if err := repo.AppendAction(action); err != nil {
+				t.Fatalf("AppendAction failed: %v", err)
+			}
+		}
+	}
+
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"seat-2": 2},
+	})
+	get := func(path string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodGet, path, nil)
+		req.Header.Set("Authorization", "Bearer seat-2")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+
+	w := get("/tables/table-1/opponents?hands=2")
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var resp struct {
+		Hands     int                   `json:"hands"`
+		Opponents []stats.OpponentStats `json:"opponents"`
+	}
+	if err := json.Unmarshal(w.Body.Bytes(), &resp); err != nil {
+		t.Fatalf("decode opponent stats failed: %v", err)
+	}
+	want := []stats.OpponentStats{
+		{SeatNo: 1, Hands: 2, VPIP: 0.5},
+		{SeatNo: 3, Hands: 2, VPIP: 0.5, PFR: 0.5, ThreeBet: 1, ThreeBetSpots: 1},
+	}
+	if resp.Hands != 2 || len(resp.Opponents) != len(want) {
+		t.Fatalf("expected the latest two hands for two opponents, got %s", w.Body.String())
+	}
+	for i := range want {
+		if resp.Opponents[i] != want[i] {
+			t.Fatalf("expected %+v, got %+v", want[i], resp.Opponents[i])
+		}
+	}
+
+	if w := get("/tables/table-1/opponents?hands=0"); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected status %d for an empty window, got %d", http.StatusBadRequest, w.Code)
+	}
+	if w := get("/tables/table-2/opponents"); w.Code != http.StatusForbidden {
+		t.Fatalf("expected status %d without the league rule, got %d", http.StatusForbidden, w.Code)
+	}
+}
+
+func TestGetTableCommentary_NarratesCompletedHands(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	base := time.Date(2026, 1, 1, 10, 0, 0, 0, time.UTC)
+	if err := repo.CreateTable(persistence.TableRecord{ID: "table-1", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "waiting", CreatedAt: base}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	for _, handNo := range []uint64{1, 2} {
+		endedAt := base.Add(time.Duration(handNo) * time.Minute)
+		if err := repo.CreateHand(persistence.HandRecord{
+			HandID:     fmt.Sprintf("hand-%d", handNo),
+			TableID:    "table-1",
+			HandNo:     handNo,
+			StartedAt:  base,
+			EndedAt:    &endedAt,
+			FinalPhase: domain.HandPhaseComplete,
+			FinalState: domain.HandState{
+				HandNo:         handNo,
+				Phase:          domain.HandPhaseComplete,
+				ShowdownAwards: []domain.PotAward{{Amount: 1500 * domain.Chips(handNo), Seats: []domain.SeatNo{domain.SeatNo(handNo)}, Reason: "uncontested"}},
+			},
+		}); err != nil {
+			t.Fatalf("CreateHand failed: %v", err)
+		}
+	}
+
+	server := NewServer(repo, nil, nil, ServerConfig{})
+	get := func(path string) *httptest.ResponseRecorder {
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, httptest.NewRequest(http.MethodGet, path, nil))
+		return w
+	}
+
+	w := get("/tables/table-1/commentary?since_hand=1")
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var lines []commentary.Line
+	if err := json.Unmarshal(w.Body.Bytes(), &lines); err != nil {
+		t.Fatalf("decode commentary failed: %v", err)
+	}
+	if len(lines) != 1 || lines[0].HandNo != 2 || lines[0].Text != "Seat 2 wins 3,000 uncontested" {
+		t.Fatalf("expected only hand 2's line, got %+v", lines)
+	}
+
+	if w := get("/tables/table-1/commentary?since_hand=last"); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected status %d, got %d", http.StatusBadRequest, w.Code)
+	}
+	if w := get("/tables/missing/commentary"); w.Code != http.StatusNotFound {
+		t.Fatalf("expected status %d, got %d", http.StatusNotFound, w.Code)
+	}
+}
+
+func TestClaimSeat_HandoffBetweenTransports(t *testing.T) {
+	t.Parallel()
+
+	server := NewServer(persistence.NewInMemoryRepository(), nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"seat-1": 1},
+	})
+	claim := func(body string) (int, map[string]any) {
+		req := httptest.NewRequest(http.MethodPost, "/tables/table-1/claim", strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer seat-1")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		var payload map[string]any
+		_ = json.Unmarshal(w.Body.Bytes(), &payload)
+		return w.Code, payload
+	}
+
+	code, first := claim(`{"transport":"websocket","controller_id":"bot-a"}`)
+	if code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d payload=%v", http.StatusOK, code, first)
+	}
+	code, second := claim(`{"transport":"grpc","controller_id":"bot-a-2"}`)
+	if code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d payload=%v", http.StatusOK, code, second)
+	}
+	handoff, ok := second["handoff"].(map[string]any)
+	if !ok {
+		t.Fatalf("expected handoff in second claim, got %v", second)
+	}
+	from, ok := handoff["from"].(map[string]any)
+	if !ok || from["transport"] != "websocket" {
+		t.Fatalf("expected handoff from websocket controller, got %v", handoff)
+	}
+	controller, ok := second["controller"].(map[string]any)
+	if !ok || controller["generation"] != float64(2) || controller["seat_no"] != float64(1) {
+		t.Fatalf("expected generation 2 controller for seat 1, got %v", second["controller"])
+	}
+
+	if code, _ := claim(`{"transport":"smoke-signal","controller_id":"bot-a"}`); code != http.StatusBadRequest {
+		t.Fatalf("expected status %d for unknown transport, got %d", http.StatusBadRequest, code)
+	}
+
+	req := httptest.NewRequest(http.MethodPost, "/tables/table-1/claim", strings.NewReader(`{"transport":"grpc","controller_id":"admin"}`))
+	req.Header.Set("Authorization", "Bearer admin")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusForbidden {
+		t.Fatalf("expected admin claim to be forbidden, got %d", w.Code)
+	}
+
+	req = httptest.NewRequest(http.MethodGet, "/tables/table-1/handoffs", nil)
+	req.Header.Set("Authorization", "Bearer admin")
+	w = httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d", http.StatusOK, w.Code)
+	}
+	var events []map[string]any
+	if err := json.Unmarshal(w.Body.Bytes(), &events); err != nil {
+		t.Fatalf("decode handoffs failed: %v", err)
+	}
+	if len(events) != 2 {
+		t.Fatalf("expected 2 handoff events, got %d", len(events))
+	}
+}
+
+func TestClaimSeat_RefusesTheSupersededController(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	if err := repo.UpsertTableRun(persistence.TableRunRecord{TableID: "table-1", Status: persistence.TableRunStatusCompleted, StartedAt: time.Now().UTC()}); err != nil {
+		t.Fatalf("UpsertTableRun failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{
+		SeatBearerTokens: map[string]domain.SeatNo{"seat-1": 1},
+	})
+	send := func(method, path, body, generation string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(method, path, strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer seat-1")
+		if generation != "" {
+			req.Header.Set(controllerGenerationHeader, generation)
+		}
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+
+	// Before any claim the seat token alone reads the seat's hands.
+	if w := send(http.MethodGet, "/tables/table-1/hands", "", ""); w.Code != http.StatusOK {
+		t.Fatalf("expected an unclaimed seat to read its hands, got %d body=%s", w.Code, w.Body.String())
+	}
+	if w := send(http.MethodPost, "/tables/table-1/claim", `{"transport":"websocket","controller_id":"bot-a"}`, ""); w.Code != http.StatusOK {
+		t.Fatalf("expected the first claim to succeed, got %d body=%s", w.Code, w.Body.String())
+	}
+	if w := send(http.MethodGet, "/tables/table-1/hands", "", "1"); w.Code != http.StatusOK {
+		t.Fatalf("expected the active controller to read its hands, got %d body=%s", w.Code, w.Body.String())
+	}
+	if w := send(http.MethodPost, "/tables/table-1/claim", `{"transport":"grpc","controller_id":"bot-b"}`, "1"); w.Code != http.StatusOK {
+		t.Fatalf("expected the handoff claim to succeed, got %d body=%s", w.Code, w.Body.String())
+	}
+
+	for _, request := range []struct{ method, path, body string }{
+		{http.MethodGet, "/tables/table-1/hands", ""},
+		{http.MethodPost, "/tables/table-1/validate-action", `{"kind":"check"}`},
+	} {
+		if w := send(request.method, request.path, request.body, "1"); w.Code != http.StatusConflict || !strings.Contains(w.Body.String(), "superseded") {
+			t.Fatalf("expected %s %s from the old controller to be refused, got %d body=%s", request.method, request.path, w.Code, w.Body.String())
+		}
+	}
+	if w := send(http.MethodGet, "/tables/table-1/hands", "", ""); w.Code != http.StatusConflict {
+		t.Fatalf("expected a claimed seat to require the controller generation, got %d", w.Code)
+	}
+	if w := send(http.MethodGet, "/tables/table-1/hands", "", "2"); w.Code != http.StatusOK {
+		t.Fatalf("expected the new controller to read its hands, got %d body=%s", w.Code, w.Body.String())
+	}
+}
+
+func TestGetLatestReplay_NoHistoryReturnsTableOnly(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.CreateTable(persistence.TableRecord{
+		ID:         "table-1",
+		Name:       "table-one",
+		MaxSeats:   6,
+		SmallBlind: 50,
+		BigBlind:   100,
 		Status:     string(persistence.TableRunStatusIdle),
 		CreatedAt:  now,
 	}); err != nil {
@@ -668,6 +1816,72 @@ func TestGetActions_ReturnsPersistedActionsForHand(t *testing.T) {
 	}
 }
 
+func TestGetActions_EmbargoesTelemetryUntilAfterTheRunEnds(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	run := persistence.TableRunRecord{TableID: "table-1", Status: persistence.TableRunStatusRunning, StartedAt: now.Add(-time.Hour)}
+	if err := repo.UpsertTableRun(run); err != nil {
+		t.Fatalf("UpsertTableRun failed: %v", err)
+	}
+	if err := repo.CreateHand(persistence.HandRecord{HandID: "hand-1", TableID: "table-1", HandNo: 1, StartedAt: now.Add(-time.Hour)}); err != nil {
+		t.Fatalf("CreateHand failed: %v", err)
+	}
+	equity := 0.8
+	if err := repo.AppendAction(persistence.ActionRecord{
+		HandID:     "hand-1",
+		Street:     domain.StreetPreflop,
+		ActingSeat: 1,
+		Action:     domain.ActionCall,
+		At:         now.Add(-time.Hour),
+		Telemetry:  &domain.Telemetry{Equity: &equity, Tags: []string{"value"}},
+	}); err != nil {
+		t.Fatalf("AppendAction failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{TelemetryEmbargo: embargo.Policy{Period: 30 * time.Minute}})
+
+	listTelemetry := func() *domain.Telemetry {
+		t.Helper()
+		req := httptest.NewRequest(http.MethodGet, "/hands/hand-1/actions", nil)
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		if w.Code != http.StatusOK {
+			t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+		}
+		var actions []actionResponse
+		if err := json.Unmarshal(w.Body.Bytes(), &actions); err != nil {
+			t.Fatalf("failed to decode response: %v", err)
+		}
+		if len(actions) != 1 {
+			t.Fatalf("expected 1 action, got %d", len(actions))
+		}
+		return actions[0].Telemetry
+	}
+
+	if got := listTelemetry(); got != nil {
+		t.Fatalf("expected telemetry to be embargoed while the table runs, got %+v", got)
+	}
+	for _, tc := range []struct {
+		endedAgo time.Duration
+		released bool
+	}{
+		{endedAgo: time.Minute, released: false},
+		{endedAgo: 45 * time.Minute, released: true},
+	} {
+		endedAt := now.Add(-tc.endedAgo)
+		run.Status = persistence.TableRunStatusCompleted
+		run.EndedAt = &endedAt
+		if err := repo.UpsertTableRun(run); err != nil {
+			t.Fatalf("UpsertTableRun failed: %v", err)
+		}
+		got := listTelemetry()
+		if released := got != nil && got.Equity != nil && *got.Equity == 0.8; released != tc.released {
+			t.Fatalf("run ended %s ago: expected released=%v, got %+v", tc.endedAgo, tc.released, got)
+		}
+	}
+}
+
 func TestGetReplay_ReturnsHandAndOrderedActions(t *testing.T) {
 	t.Parallel()
 
@@ -705,11 +1919,13 @@ func TestGetReplay_ReturnsHandAndOrderedActions(t *testing.T) {
 		t.Fatalf("CreateHand failed: %v", err)
 	}
 	if err := repo.AppendAction(persistence.ActionRecord{
-		HandID:     "hand-1",
-		Street:     domain.StreetPreflop,
-		ActingSeat: 1,
-		Action:     domain.ActionCall,
-		At:         now,
+		HandID:      "hand-1",
+		Street:      domain.StreetPreflop,
+		ActingSeat:  1,
+		Action:      domain.ActionCall,
+		At:          now,
+		ReceivedAt:  now,
+		ThinkTimeMS: 850,
 	}); err != nil {
 		t.Fatalf("AppendAction #1 failed: %v", err)
 	}
@@ -762,6 +1978,14 @@ func TestGetReplay_ReturnsHandAndOrderedActions(t *testing.T) {
 	if replay.Analytics.ActionsBySeat[1] != 1 || replay.Analytics.ActionsBySeat[2] != 1 {
 		t.Fatalf("unexpected actions_by_seat: %+v", replay.Analytics.ActionsBySeat)
 	}
+	// Only the timed decision contributes to the timing profile.
+	timing := replay.Analytics.Timing
+	if len(timing) != 1 || timing[0].Street != domain.StreetPreflop || timing[0].SeatNo != 1 || timing[0].TotalMS != 850 {
+		t.Fatalf("unexpected timing profile: %+v", timing)
+	}
+	if replay.Actions[0].ThinkTimeMS == nil || *replay.Actions[0].ThinkTimeMS != 850 || replay.Actions[1].ThinkTimeMS != nil {
+		t.Fatalf("unexpected per-action think times: %+v", replay.Actions)
+	}
 }
 
 func TestGetReplay_RedactHoleCardsRedactsOnlyFoldedSeats(t *testing.T) {
@@ -873,6 +2097,70 @@ func TestAuth_ValidSeatTokenCanAccessHistoryRoute(t *testing.T) {
 	}
 }
 
+func TestRunoutTree_ExportsTurnAllInAndRejectsPreflopHands(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	board := []domain.Card{
+		domain.NewCard(2, domain.SuitClubs), domain.NewCard(7, domain.SuitDiamonds), domain.NewCard(9, domain.SuitHearts),
+		domain.NewCard(11, domain.SuitClubs), domain.NewCard(4, domain.SuitDiamonds),
+	}
+	finalState := domain.HandState{
+		HandID: "hand-1",
+		Board:  board,
+		Seats: []domain.SeatState{
+			{SeatNo: 1, Stack: 200, TotalCommitted: 100, Status: domain.SeatStatusActive},
+			{SeatNo: 2, TotalCommitted: 100, Status: domain.SeatStatusActive},
+		},
+		HoleCards: []domain.SeatCards{
+			{SeatNo: 1, Cards: []domain.Card{domain.NewCard(14, domain.SuitSpades), domain.NewCard(14, domain.SuitHearts)}},
+			{SeatNo: 2, Cards: []domain.Card{domain.NewCard(13, domain.SuitSpades), domain.NewCard(13, domain.SuitHearts)}},
+		},
+	}
+	preflopState := finalState
+	preflopState.HandID = "hand-2"
+	for _, hand := range []persistence.HandRecord{
+		{HandID: "hand-1", TableID: "table-1", HandNo: 1, StartedAt: now, FinalState: finalState},
+		{HandID: "hand-2", TableID: "table-1", HandNo: 2, StartedAt: now, FinalState: preflopState},
+	} {
+		if err := repo.CreateHand(hand); err != nil {
+			t.Fatalf("CreateHand failed: %v", err)
+		}
+	}
+	for _, action := range []persistence.ActionRecord{
+		{HandID: "hand-1", Street: domain.StreetTurn, ActingSeat: 1, Action: domain.ActionAllIn, At: now},
+		{HandID: "hand-1", Street: domain.StreetTurn, ActingSeat: 2, Action: domain.ActionCall, At: now},
+		{HandID: "hand-2", Street: domain.StreetPreflop, ActingSeat: 1, Action: domain.ActionAllIn, At: now},
+	} {
+		if err := repo.AppendAction(action); err != nil {
+			t.Fatalf("AppendAction failed: %v", err)
+		}
+	}
+
+	server := NewServer(repo, nil, nil, ServerConfig{})
+	req := httptest.NewRequest(http.MethodGet, "/hands/hand-1/runout-tree", nil)
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var tree equity.RunoutTree
+	if err := json.Unmarshal(w.Body.Bytes(), &tree); err != nil {
+		t.Fatalf("failed to decode response: %v", err)
+	}
+	if len(tree.Board) != 4 || tree.Runouts != 44 || tree.Equity[1].Share.String() != "1/22" {
+		t.Fatalf("expected the turn spot with kings drawing to two outs, got board=%d runouts=%d equity=%+v", len(tree.Board), tree.Runouts, tree.Equity)
+	}
+
+	req = httptest.NewRequest(http.MethodGet, "/hands/hand-2/runout-tree", nil)
+	w = httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusConflict {
+		t.Fatalf("expected status %d for a preflop all-in, got %d body=%s", http.StatusConflict, w.Code, w.Body.String())
+	}
+}
+
 func TestAuth_SeatTokenForbiddenOnControlRoutes(t *testing.T) {
 	t.Parallel()
 
@@ -942,20 +2230,73 @@ func TestReplay_SeatTokenNonShowdownRedactsOpponents(t *testing.T) {
 	if w.Code != http.StatusOK {
 		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
 	}
-
+
+	var replay handReplayResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &replay); err != nil {
+		t.Fatalf("decode replay failed: %v", err)
+	}
+	if len(replay.FinalState.HoleCards[0].Cards) != 2 {
+		t.Fatalf("expected own cards visible, got %d", len(replay.FinalState.HoleCards[0].Cards))
+	}
+	if len(replay.FinalState.HoleCards[1].Cards) != 0 {
+		t.Fatalf("expected opponent cards redacted, got %d", len(replay.FinalState.HoleCards[1].Cards))
+	}
+}
+
+func TestReplay_SeatTokenShowdownRevealsOpponents(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.UpsertTableRun(persistence.TableRunRecord{
+		TableID:   "table-1",
+		Status:    persistence.TableRunStatusCompleted,
+		StartedAt: now.Add(-time.Minute),
+	}); err != nil {
+		t.Fatalf("UpsertTableRun failed: %v", err)
+	}
+	if err := repo.CreateHand(persistence.HandRecord{
+		HandID:    "hand-1",
+		TableID:   "table-1",
+		HandNo:    1,
+		StartedAt: now.Add(-30 * time.Second),
+		FinalState: domain.HandState{
+			HandID: "hand-1",
+			Seats: []domain.SeatState{
+				{SeatNo: 1}, {SeatNo: 2},
+			},
+			ShowdownAwards: []domain.PotAward{{Amount: 200, Seats: []domain.SeatNo{1}, Reason: "showdown"}},
+			HoleCards: []domain.SeatCards{
+				{SeatNo: 1, Cards: []domain.Card{{Rank: 14, Suit: domain.SuitSpades}, {Rank: 13, Suit: domain.SuitSpades}}},
+				{SeatNo: 2, Cards: []domain.Card{{Rank: 2, Suit: domain.SuitClubs}, {Rank: 7, Suit: domain.SuitDiamonds}}},
+			},
+		},
+		WinnerSummary: []domain.PotAward{{Amount: 200, Seats: []domain.SeatNo{1}, Reason: "showdown"}},
+	}); err != nil {
+		t.Fatalf("CreateHand failed: %v", err)
+	}
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"seat1": 1},
+	})
+
+	req := httptest.NewRequest(http.MethodGet, "/hands/hand-1/replay", nil)
+	req.Header.Set("Authorization", "Bearer seat1")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
 	var replay handReplayResponse
 	if err := json.Unmarshal(w.Body.Bytes(), &replay); err != nil {
 		t.Fatalf("decode replay failed: %v", err)
 	}
-	if len(replay.FinalState.HoleCards[0].Cards) != 2 {
-		t.Fatalf("expected own cards visible, got %d", len(replay.FinalState.HoleCards[0].Cards))
-	}
-	if len(replay.FinalState.HoleCards[1].Cards) != 0 {
-		t.Fatalf("expected opponent cards redacted, got %d", len(replay.FinalState.HoleCards[1].Cards))
+	if len(replay.FinalState.HoleCards[1].Cards) != 2 {
+		t.Fatalf("expected showdown opponent cards visible, got %d", len(replay.FinalState.HoleCards[1].Cards))
 	}
 }
 
-func TestReplay_SeatTokenShowdownRevealsOpponents(t *testing.T) {
+func TestReplay_SeatTokenShowdownHidesMuckedOpponents(t *testing.T) {
 	t.Parallel()
 
 	repo := persistence.NewInMemoryRepository()
@@ -975,15 +2316,21 @@ func TestReplay_SeatTokenShowdownRevealsOpponents(t *testing.T) {
 		FinalState: domain.HandState{
 			HandID: "hand-1",
 			Seats: []domain.SeatState{
-				{SeatNo: 1}, {SeatNo: 2},
+				{SeatNo: 1}, {SeatNo: 2}, {SeatNo: 3},
+			},
+			ShowdownAwards: []domain.PotAward{{Amount: 300, Seats: []domain.SeatNo{2}, Reason: "showdown"}},
+			ShowdownReveals: []domain.ShowdownReveal{
+				{SeatNo: 2, Action: domain.RevealShow, Reason: "winner"},
+				{SeatNo: 3, Action: domain.RevealMuck, Reason: "auto_muck"},
+				{SeatNo: 1, Action: domain.RevealMuck, Reason: "auto_muck"},
 			},
-			ShowdownAwards: []domain.PotAward{{Amount: 200, Seats: []domain.SeatNo{1}, Reason: "showdown"}},
 			HoleCards: []domain.SeatCards{
-				{SeatNo: 1, Cards: []domain.Card{{Rank: 14, Suit: domain.SuitSpades}, {Rank: 13, Suit: domain.SuitSpades}}},
-				{SeatNo: 2, Cards: []domain.Card{{Rank: 2, Suit: domain.SuitClubs}, {Rank: 7, Suit: domain.SuitDiamonds}}},
+				{SeatNo: 1, Cards: []domain.Card{{Rank: 3, Suit: domain.SuitSpades}, {Rank: 4, Suit: domain.SuitSpades}}},
+				{SeatNo: 2, Cards: []domain.Card{{Rank: 14, Suit: domain.SuitClubs}, {Rank: 14, Suit: domain.SuitDiamonds}}},
+				{SeatNo: 3, Cards: []domain.Card{{Rank: 2, Suit: domain.SuitClubs}, {Rank: 7, Suit: domain.SuitDiamonds}}},
 			},
 		},
-		WinnerSummary: []domain.PotAward{{Amount: 200, Seats: []domain.SeatNo{1}, Reason: "showdown"}},
+		WinnerSummary: []domain.PotAward{{Amount: 300, Seats: []domain.SeatNo{2}, Reason: "showdown"}},
 	}); err != nil {
 		t.Fatalf("CreateHand failed: %v", err)
 	}
@@ -1003,8 +2350,53 @@ func TestReplay_SeatTokenShowdownRevealsOpponents(t *testing.T) {
 	if err := json.Unmarshal(w.Body.Bytes(), &replay); err != nil {
 		t.Fatalf("decode replay failed: %v", err)
 	}
+	if len(replay.FinalState.HoleCards[0].Cards) != 2 {
+		t.Fatalf("expected caller's own mucked cards visible, got %d", len(replay.FinalState.HoleCards[0].Cards))
+	}
 	if len(replay.FinalState.HoleCards[1].Cards) != 2 {
-		t.Fatalf("expected showdown opponent cards visible, got %d", len(replay.FinalState.HoleCards[1].Cards))
+		t.Fatalf("expected shown winner cards visible, got %d", len(replay.FinalState.HoleCards[1].Cards))
+	}
+	if len(replay.FinalState.HoleCards[2].Cards) != 0 {
+		t.Fatalf("expected mucked opponent cards hidden, got %d", len(replay.FinalState.HoleCards[2].Cards))
+	}
+}
+
+func TestReplayEquity_MatchesHiddenCardsBySeat(t *testing.T) {
+	t.Parallel()
+
+	ace, king := domain.NewCard(14, domain.SuitSpades), domain.NewCard(13, domain.SuitSpades)
+	hand := persistence.HandRecord{
+		FinalState: domain.HandState{HoleCards: []domain.SeatCards{
+			{SeatNo: 1, Cards: []domain.Card{ace, king}},
+			{SeatNo: 2, Cards: []domain.Card{}},
+			{SeatNo: 3, Cards: []domain.Card{king, ace}},
+		}},
+		Equity: []equity.StreetEquity{{Street: domain.StreetPreflop}},
+	}
+	cases := map[string]struct {
+		shown []domain.SeatCards
+		want  bool
+	}{
+		"every card shown": {shown: hand.FinalState.HoleCards, want: true},
+		"seats reordered": {shown: []domain.SeatCards{
+			{SeatNo: 3, Cards: []domain.Card{king, ace}},
+			{SeatNo: 1, Cards: []domain.Card{ace, king}},
+			{SeatNo: 2, Cards: []domain.Card{}},
+		}, want: true},
+		"one seat hidden": {shown: []domain.SeatCards{
+			{SeatNo: 3, Cards: []domain.Card{}},
+			{SeatNo: 2, Cards: []domain.Card{}},
+			{SeatNo: 1, Cards: []domain.Card{ace, king}},
+		}},
+		"one seat left out": {shown: []domain.SeatCards{
+			{SeatNo: 1, Cards: []domain.Card{ace, king}},
+		}},
+	}
+	for name, tc := range cases {
+		got := replayEquity(hand, domain.HandState{HoleCards: tc.shown})
+		if (got != nil) != tc.want {
+			t.Fatalf("%s: expected equity released=%v, got %+v", name, tc.want, got)
+		}
 	}
 }
 
@@ -1077,95 +2469,459 @@ func TestActions_SeatTokenForbiddenWhenSeatNotInHand(t *testing.T) {
 		t.Fatalf("CreateHand failed: %v", err)
 	}
 	server := NewServer(repo, nil, nil, ServerConfig{
-		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"seat1": 1},
+	})
+
+	req := httptest.NewRequest(http.MethodGet, "/hands/hand-1/actions", nil)
+	req.Header.Set("Authorization", "Bearer seat1")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusForbidden {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusForbidden, w.Code, w.Body.String())
+	}
+}
+
+func TestHands_SeatTokenReturnsOnlyParticipatingHands(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	now := time.Now().UTC()
+	if err := repo.UpsertTableRun(persistence.TableRunRecord{
+		TableID:   "table-1",
+		Status:    persistence.TableRunStatusCompleted,
+		StartedAt: now.Add(-time.Minute),
+	}); err != nil {
+		t.Fatalf("UpsertTableRun failed: %v", err)
+	}
+	for _, hand := range []persistence.HandRecord{
+		{
+			HandID: "hand-1", TableID: "table-1", HandNo: 1, StartedAt: now.Add(-40 * time.Second),
+			FinalState: domain.HandState{HandID: "hand-1", Seats: []domain.SeatState{{SeatNo: 1}, {SeatNo: 2}}},
+		},
+		{
+			HandID: "hand-2", TableID: "table-1", HandNo: 2, StartedAt: now.Add(-30 * time.Second),
+			FinalState: domain.HandState{HandID: "hand-2", Seats: []domain.SeatState{{SeatNo: 3}, {SeatNo: 4}}},
+		},
+	} {
+		if err := repo.CreateHand(hand); err != nil {
+			t.Fatalf("CreateHand failed: %v", err)
+		}
+	}
+
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"admin": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"seat1": 1},
+	})
+	req := httptest.NewRequest(http.MethodGet, "/tables/table-1/hands", nil)
+	req.Header.Set("Authorization", "Bearer seat1")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var hands []handResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &hands); err != nil {
+		t.Fatalf("decode hands failed: %v", err)
+	}
+	if len(hands) != 1 || hands[0].HandID != "hand-1" {
+		t.Fatalf("expected only hand-1 for seat token, got %+v", hands)
+	}
+}
+
+func TestGetReplay_UnknownHandReturnsNotFound(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	server := NewServer(repo, nil, nil, ServerConfig{})
+	req := httptest.NewRequest(http.MethodGet, "/hands/missing/replay", nil)
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusNotFound {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusNotFound, w.Code, w.Body.String())
+	}
+}
+
+func TestGetReplay_RequiresAuth(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	server := NewServer(repo, nil, nil, ServerConfig{AdminBearerTokens: map[string]struct{}{"secret": {}}})
+	req := httptest.NewRequest(http.MethodGet, "/hands/hand-1/replay", nil)
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusUnauthorized {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusUnauthorized, w.Code, w.Body.String())
+	}
+}
+
+func TestStartRunPersistsHandStartedAtIndependentlyFromRunStartedAt(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	server := NewServer(
+		repo,
+		func(_ tablerunner.ActionProvider, cfg tablerunner.RunnerConfig) Runner {
+			return fakeRunner{cfg: cfg}
+		},
+		func(tableID string, _ StartRequest, _ ServerConfig) (tablerunner.ActionProvider, error) {
+			return fakeProvider{}, nil
+		},
+		ServerConfig{
+			AdminBearerTokens: map[string]struct{}{"test-token": {}},
+			AllowedAgentHosts: map[string]struct{}{"agent.local:9001": {}, "agent.local:9002": {}},
+		},
+	)
+
+	req := httptest.NewRequest(http.MethodPost, "/tables/table-1/start", strings.NewReader(`{
+		"hands_to_run": 1,
+		"seats": [
+			{"seat_no": 1, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9001/callback"},
+			{"seat_no": 2, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9002/callback"}
+		]
+	}`))
+	req.Header.Set("Authorization", "Bearer test-token")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+
+	waitForTableRunStatus(t, repo, "table-1", persistence.TableRunStatusCompleted)
+
+	runRecord, ok, err := repo.GetTableRun("table-1")
+	if err != nil {
+		t.Fatalf("GetTableRun failed: %v", err)
+	}
+	if !ok {
+		t.Fatal("expected run record")
+	}
+
+	hands, err := repo.ListHands("table-1")
+	if err != nil {
+		t.Fatalf("ListHands failed: %v", err)
+	}
+	if len(hands) != 1 {
+		t.Fatalf("expected one hand, got %d", len(hands))
+	}
+	if hands[0].StartedAt.Equal(runRecord.StartedAt) {
+		t.Fatalf("expected hand start time (%s) to differ from run start time (%s)", hands[0].StartedAt, runRecord.StartedAt)
+	}
+	if hands[0].StartedAt.Before(runRecord.StartedAt) {
+		t.Fatalf("expected hand start time (%s) not before run start (%s)", hands[0].StartedAt, runRecord.StartedAt)
+	}
+}
+
+func TestStallingPolicy_AdminCanEnableAndTune(t *testing.T) {
+	t.Parallel()
+
+	server := NewServer(
+		persistence.NewInMemoryRepository(),
+		func(_ tablerunner.ActionProvider, cfg tablerunner.RunnerConfig) Runner {
+			return fakeRunner{cfg: cfg}
+		},
+		func(tableID string, _ StartRequest, _ ServerConfig) (tablerunner.ActionProvider, error) {
+			return fakeProvider{}, nil
+		},
+		ServerConfig{
+			AdminBearerTokens: map[string]struct{}{"test-token": {}},
+			SeatBearerTokens:  map[string]domain.SeatNo{"seat1": 1},
+		},
+	)
+	do := func(method string, token string, body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(method, "/stalling/policy", strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer "+token)
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+
+	if w := do(http.MethodGet, "seat1", ""); w.Code != http.StatusForbidden {
+		t.Fatalf("expected status %d for seat token, got %d", http.StatusForbidden, w.Code)
+	}
+	w := do(http.MethodGet, "test-token", "")
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	var got stallingPolicyResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &got); err != nil {
+		t.Fatalf("decode policy failed: %v", err)
+	}
+	if got.Enabled || got.Policy != stats.DefaultStallingPolicy() {
+		t.Fatalf("expected detection disabled with the default policy, got %+v", got)
+	}
+
+	w = do(http.MethodPut, "test-token", `{"enabled": true, "policy": {"slow_fraction": 0.8, "min_decisions": 5, "flag_ratio": 0.6, "penalty_ms": 500}}`)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	if err := json.Unmarshal(w.Body.Bytes(), &got); err != nil {
+		t.Fatalf("decode policy failed: %v", err)
+	}
+	want := stats.StallingPolicy{SlowFraction: 0.8, MinDecisions: 5, FlagRatio: 0.6, PenaltyMS: 500}
+	if !got.Enabled || got.Policy != want {
+		t.Fatalf("expected enabled policy %+v, got %+v", want, got)
+	}
+
+	if w := do(http.MethodPut, "test-token", `{"enabled": true, "policy": {"slow_fraction": 2, "min_decisions": 5}}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected status %d for invalid policy, got %d", http.StatusBadRequest, w.Code)
+	}
+}
+
+func TestMetrics_ExportsRunCounters(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	server := NewServer(
+		repo,
+		func(_ tablerunner.ActionProvider, cfg tablerunner.RunnerConfig) Runner {
+			return fakeRunner{cfg: cfg}
+		},
+		func(tableID string, _ StartRequest, _ ServerConfig) (tablerunner.ActionProvider, error) {
+			return fakeProvider{}, nil
+		},
+		ServerConfig{
+			AdminBearerTokens: map[string]struct{}{"test-token": {}},
+			SeatBearerTokens:  map[string]domain.SeatNo{"seat1": 1},
+			AllowedAgentHosts: map[string]struct{}{"agent.local:9001": {}, "agent.local:9002": {}},
+		},
+	)
+
+	req := httptest.NewRequest(http.MethodPost, "/tables/table-1/start", strings.NewReader(`{
+		"hands_to_run": 1,
+		"seats": [
+			{"seat_no": 1, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9001/callback"},
+			{"seat_no": 2, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9002/callback"}
+		]
+	}`))
+	req.Header.Set("Authorization", "Bearer test-token")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	waitForTableRunStatus(t, repo, "table-1", persistence.TableRunStatusCompleted)
+
+	req = httptest.NewRequest(http.MethodGet, "/metrics", nil)
+	req.Header.Set("Authorization", "Bearer seat1")
+	w = httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusForbidden {
+		t.Fatalf("expected status %d for seat token, got %d", http.StatusForbidden, w.Code)
+	}
+
+	req = httptest.NewRequest(http.MethodGet, "/metrics", nil)
+	req.Header.Set("Authorization", "Bearer test-token")
+	w = httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	if got := w.Header().Get("Content-Type"); !strings.HasPrefix(got, "text/plain; version=0.0.4") {
+		t.Fatalf("unexpected content type %q", got)
+	}
+	body := w.Body.String()
+	for _, want := range []string{
+		"# TYPE poker_arena_hands_dealt_total counter",
+		"poker_arena_hands_dealt_total 1\n",
+		`poker_arena_hands_completed_total{phase="complete"} 1`,
+		`poker_arena_event_store_seconds_count{operation="create_hand"} 1`,
+		`poker_arena_event_store_seconds_count{operation="complete_hand"} 1`,
+		`poker_arena_seat_controllers{transport="websocket"} 0`,
+		"# TYPE poker_arena_running_tables gauge",
+	} {
+		if !strings.Contains(body, want) {
+			t.Fatalf("expected metrics to contain %q\n%s", want, body)
+		}
+	}
+}
+
+func TestAudience_WatchHeartbeatsReachTheLobbyAndMetrics(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	if err := repo.CreateTable(persistence.TableRecord{ID: "table-1", Name: "Alpha", MaxSeats: 6, SmallBlind: 50, BigBlind: 100}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	var featured []audience.Event
+	server := NewServer(repo, nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"test-token": {}},
 		SeatBearerTokens:  map[string]domain.SeatNo{"seat1": 1},
+		Audience: audience.NewTracker(audience.Config{FeaturedViewers: 2, OnEvent: func(event audience.Event) {
+			featured = append(featured, event)
+		}}),
 	})
+	watch := func(token, tableID, body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodPost, "/tables/"+tableID+"/watch", strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer "+token)
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+	if w := watch("seat1", "table-1", `{"spectator_id":"viewer-1"}`); w.Code != http.StatusForbidden {
+		t.Fatalf("expected status %d for seat token, got %d", http.StatusForbidden, w.Code)
+	}
+	if w := watch("test-token", "table-1", `{}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected status %d without a spectator, got %d", http.StatusBadRequest, w.Code)
+	}
+	if w := watch("test-token", "table-9", `{"spectator_id":"viewer-1"}`); w.Code != http.StatusNotFound {
+		t.Fatalf("expected status %d for an unknown table, got %d", http.StatusNotFound, w.Code)
+	}
+	watch("test-token", "table-1", `{"spectator_id":"viewer-1"}`)
+	w := watch("test-token", "table-1", `{"spectator_id":"viewer-2"}`)
+	var watched audience.Stats
+	if err := json.Unmarshal(w.Body.Bytes(), &watched); err != nil {
+		t.Fatalf("Unmarshal failed: %v", err)
+	}
+	if watched.Viewers != 2 || watched.PeakViewers != 2 || !watched.Featured || len(featured) != 1 {
+		t.Fatalf("expected two viewers featuring the table, got %+v events=%+v", watched, featured)
+	}
 
-	req := httptest.NewRequest(http.MethodGet, "/hands/hand-1/actions", nil)
-	req.Header.Set("Authorization", "Bearer seat1")
-	w := httptest.NewRecorder()
+	req := httptest.NewRequest(http.MethodGet, "/tables", nil)
+	req.Header.Set("Authorization", "Bearer test-token")
+	w = httptest.NewRecorder()
 	server.ServeHTTP(w, req)
-	if w.Code != http.StatusForbidden {
-		t.Fatalf("expected status %d, got %d body=%s", http.StatusForbidden, w.Code, w.Body.String())
+	var tables []tableResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &tables); err != nil {
+		t.Fatalf("Unmarshal failed: %v", err)
+	}
+	if len(tables) != 1 || tables[0].Audience == nil || tables[0].Audience.Viewers != 2 {
+		t.Fatalf("expected the lobby to show two viewers, got %s", w.Body.String())
+	}
+
+	req = httptest.NewRequest(http.MethodGet, "/metrics", nil)
+	req.Header.Set("Authorization", "Bearer test-token")
+	w = httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if body := w.Body.String(); !strings.Contains(body, `poker_arena_table_viewers{table="table-1"} 2`) {
+		t.Fatalf("expected the viewer gauge in metrics\n%s", body)
 	}
 }
 
-func TestHands_SeatTokenReturnsOnlyParticipatingHands(t *testing.T) {
+func TestTournaments_TemplateCalendarAndRegistration(t *testing.T) {
 	t.Parallel()
 
-	repo := persistence.NewInMemoryRepository()
-	now := time.Now().UTC()
-	if err := repo.UpsertTableRun(persistence.TableRunRecord{
-		TableID:   "table-1",
-		Status:    persistence.TableRunStatusCompleted,
-		StartedAt: now.Add(-time.Minute),
-	}); err != nil {
-		t.Fatalf("UpsertTableRun failed: %v", err)
-	}
-	for _, hand := range []persistence.HandRecord{
-		{
-			HandID: "hand-1", TableID: "table-1", HandNo: 1, StartedAt: now.Add(-40 * time.Second),
-			FinalState: domain.HandState{HandID: "hand-1", Seats: []domain.SeatState{{SeatNo: 1}, {SeatNo: 2}}},
-		},
-		{
-			HandID: "hand-2", TableID: "table-1", HandNo: 2, StartedAt: now.Add(-30 * time.Second),
-			FinalState: domain.HandState{HandID: "hand-2", Seats: []domain.SeatState{{SeatNo: 3}, {SeatNo: 4}}},
-		},
-	} {
-		if err := repo.CreateHand(hand); err != nil {
-			t.Fatalf("CreateHand failed: %v", err)
-		}
-	}
-
-	server := NewServer(repo, nil, nil, ServerConfig{
-		AdminBearerTokens: map[string]struct{}{"admin": {}},
+	server := NewServer(persistence.NewInMemoryRepository(), nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"test-token": {}},
 		SeatBearerTokens:  map[string]domain.SeatNo{"seat1": 1},
+		Tournaments:       tournament.NewScheduler(),
 	})
-	req := httptest.NewRequest(http.MethodGet, "/tables/table-1/hands", nil)
-	req.Header.Set("Authorization", "Bearer seat1")
-	w := httptest.NewRecorder()
-	server.ServeHTTP(w, req)
-	if w.Code != http.StatusOK {
+	do := func(method, path, token, body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(method, path, strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer "+token)
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+	// Registration opens two days ahead, so the next midnight's tournament
+	// is always open.
+	template := `{"id":"nightly","name":"Nightly","recurrence":"daily","start_time":"00:00","registration_opens_ms":172800000,` +
+		`"config":{"seats":2,"starting_stack":1000,"levels":[{"small_blind":10,"big_blind":20}],"hands_per_level":10}}`
+	if w := do(http.MethodPost, "/tournaments/templates", "test-token", template); w.Code != http.StatusOK {
 		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
 	}
-	var hands []handResponse
-	if err := json.Unmarshal(w.Body.Bytes(), &hands); err != nil {
-		t.Fatalf("decode hands failed: %v", err)
+	if w := do(http.MethodPost, "/tournaments/templates", "test-token", template); w.Code != http.StatusConflict {
+		t.Fatalf("expected status %d for a duplicate template, got %d", http.StatusConflict, w.Code)
 	}
-	if len(hands) != 1 || hands[0].HandID != "hand-1" {
-		t.Fatalf("expected only hand-1 for seat token, got %+v", hands)
+	if w := do(http.MethodGet, "/tournaments/calendar", "seat1", ""); w.Code != http.StatusForbidden {
+		t.Fatalf("expected status %d for seat token, got %d", http.StatusForbidden, w.Code)
+	}
+
+	w := do(http.MethodGet, "/tournaments/calendar", "test-token", "")
+	var calendar []tournament.ScheduledTournament
+	if err := json.Unmarshal(w.Body.Bytes(), &calendar); err != nil {
+		t.Fatalf("Unmarshal failed: %v body=%s", err, w.Body.String())
+	}
+	if len(calendar) < 7 || calendar[0].Status != tournament.ScheduleStatusRegistrationOpen {
+		t.Fatalf("expected a week of nightly tournaments, the first open, got %s", w.Body.String())
+	}
+	w = do(http.MethodPost, "/tournaments/"+calendar[0].ID+"/registrations", "test-token", `{"player_id":"bot-1"}`)
+	var scheduled tournament.ScheduledTournament
+	if err := json.Unmarshal(w.Body.Bytes(), &scheduled); err != nil {
+		t.Fatalf("Unmarshal failed: %v body=%s", err, w.Body.String())
+	}
+	if len(scheduled.Players) != 1 || scheduled.Players[0] != "bot-1" {
+		t.Fatalf("expected bot-1 registered, got %s", w.Body.String())
+	}
+	if w := do(http.MethodPost, "/tournaments/nightly@20000101T0000/registrations", "test-token", `{"player_id":"bot-1"}`); w.Code != http.StatusConflict {
+		t.Fatalf("expected status %d once registration has closed, got %d", http.StatusConflict, w.Code)
+	}
+	if w := do(http.MethodGet, "/tournaments/calendar?from=soon", "test-token", ""); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected status %d for a bad time, got %d", http.StatusBadRequest, w.Code)
 	}
 }
 
-func TestGetReplay_UnknownHandReturnsNotFound(t *testing.T) {
+func TestSideMarkets_WagerAndAccountRoutes(t *testing.T) {
 	t.Parallel()
 
-	repo := persistence.NewInMemoryRepository()
-	server := NewServer(repo, nil, nil, ServerConfig{})
-	req := httptest.NewRequest(http.MethodGet, "/hands/missing/replay", nil)
+	book := sidemarket.NewBook()
+	server := NewServer(persistence.NewInMemoryRepository(), nil, nil, ServerConfig{
+		AdminBearerTokens: map[string]struct{}{"test-token": {}},
+		SeatBearerTokens:  map[string]domain.SeatNo{"seat1": 1},
+		SideMarkets:       book,
+	})
+	seats := []domain.SeatState{domain.NewSeatState(1, 0), domain.NewSeatState(2, 0)}
+	seats[0].CommittedInRound = 500
+	seats[1].CommittedInRound = 500
+	if _, ok := book.Open(domain.HandState{
+		HandID:     "hand-1",
+		TableID:    "table-1",
+		Phase:      domain.HandPhaseBetting,
+		CurrentBet: 500,
+		Seats:      seats,
+	}); !ok {
+		t.Fatal("expected market to open")
+	}
+
+	wagerBody := `{"market_id":"hand-1","spectator_id":"viewer-1","seat_no":2,"points":300}`
+	req := httptest.NewRequest(http.MethodPost, "/tables/table-1/wagers", strings.NewReader(wagerBody))
+	req.Header.Set("Authorization", "Bearer seat1")
 	w := httptest.NewRecorder()
 	server.ServeHTTP(w, req)
-	if w.Code != http.StatusNotFound {
-		t.Fatalf("expected status %d, got %d body=%s", http.StatusNotFound, w.Code, w.Body.String())
+	if w.Code != http.StatusForbidden {
+		t.Fatalf("expected status %d for seat token, got %d", http.StatusForbidden, w.Code)
 	}
-}
 
-func TestGetReplay_RequiresAuth(t *testing.T) {
-	t.Parallel()
+	req = httptest.NewRequest(http.MethodPost, "/tables/table-1/wagers", strings.NewReader(wagerBody))
+	req.Header.Set("Authorization", "Bearer test-token")
+	w = httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
 
-	repo := persistence.NewInMemoryRepository()
-	server := NewServer(repo, nil, nil, ServerConfig{AdminBearerTokens: map[string]struct{}{"secret": {}}})
-	req := httptest.NewRequest(http.MethodGet, "/hands/hand-1/replay", nil)
-	w := httptest.NewRecorder()
+	req = httptest.NewRequest(http.MethodGet, "/tables/table-1/markets", nil)
+	req.Header.Set("Authorization", "Bearer test-token")
+	w = httptest.NewRecorder()
 	server.ServeHTTP(w, req)
-	if w.Code != http.StatusUnauthorized {
-		t.Fatalf("expected status %d, got %d body=%s", http.StatusUnauthorized, w.Code, w.Body.String())
+	var markets tableMarketsResponse
+	if err := json.Unmarshal(w.Body.Bytes(), &markets); err != nil {
+		t.Fatalf("Unmarshal failed: %v", err)
+	}
+	if len(markets.Markets) != 1 || len(markets.Markets[0].Wagers) != 1 || len(markets.Events) != 2 {
+		t.Fatalf("unexpected markets response: %s", w.Body.String())
+	}
+
+	req = httptest.NewRequest(http.MethodGet, "/spectators/viewer-1", nil)
+	req.Header.Set("Authorization", "Bearer test-token")
+	w = httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	var account sidemarket.Account
+	if err := json.Unmarshal(w.Body.Bytes(), &account); err != nil {
+		t.Fatalf("Unmarshal failed: %v", err)
+	}
+	if account.Points != sidemarket.DefaultStartingPoints-300 {
+		t.Fatalf("unexpected balance %d", account.Points)
 	}
 }
 
-func TestStartRunPersistsHandStartedAtIndependentlyFromRunStartedAt(t *testing.T) {
+func TestCompletedRunIsArchivedAndServed(t *testing.T) {
 	t.Parallel()
 
+	store, err := archive.NewLocalStore(t.TempDir())
+	if err != nil {
+		t.Fatalf("NewLocalStore failed: %v", err)
+	}
 	repo := persistence.NewInMemoryRepository()
 	server := NewServer(
 		repo,
@@ -1178,9 +2934,21 @@ func TestStartRunPersistsHandStartedAtIndependentlyFromRunStartedAt(t *testing.T
 		ServerConfig{
 			AdminBearerTokens: map[string]struct{}{"test-token": {}},
 			AllowedAgentHosts: map[string]struct{}{"agent.local:9001": {}, "agent.local:9002": {}},
+			ArchiveStore:      store,
 		},
 	)
 
+	getArchive := func() *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodGet, "/tables/table-1/archive", nil)
+		req.Header.Set("Authorization", "Bearer test-token")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+	if w := getArchive(); w.Code != http.StatusNotFound {
+		t.Fatalf("expected status %d before run, got %d body=%s", http.StatusNotFound, w.Code, w.Body.String())
+	}
+
 	req := httptest.NewRequest(http.MethodPost, "/tables/table-1/start", strings.NewReader(`{
 		"hands_to_run": 1,
 		"seats": [
@@ -1194,29 +2962,27 @@ func TestStartRunPersistsHandStartedAtIndependentlyFromRunStartedAt(t *testing.T
 	if w.Code != http.StatusOK {
 		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
 	}
-
 	waitForTableRunStatus(t, repo, "table-1", persistence.TableRunStatusCompleted)
 
-	runRecord, ok, err := repo.GetTableRun("table-1")
-	if err != nil {
-		t.Fatalf("GetTableRun failed: %v", err)
-	}
-	if !ok {
-		t.Fatal("expected run record")
+	// Archiving happens right after the final run status is persisted.
+	deadline := time.Now().Add(2 * time.Second)
+	w = getArchive()
+	for w.Code != http.StatusOK && time.Now().Before(deadline) {
+		time.Sleep(10 * time.Millisecond)
+		w = getArchive()
 	}
-
-	hands, err := repo.ListHands("table-1")
-	if err != nil {
-		t.Fatalf("ListHands failed: %v", err)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
 	}
-	if len(hands) != 1 {
-		t.Fatalf("expected one hand, got %d", len(hands))
+	var bundle archive.Bundle
+	if err := json.Unmarshal(w.Body.Bytes(), &bundle); err != nil {
+		t.Fatalf("decode archive failed: %v", err)
 	}
-	if hands[0].StartedAt.Equal(runRecord.StartedAt) {
-		t.Fatalf("expected hand start time (%s) to differ from run start time (%s)", hands[0].StartedAt, runRecord.StartedAt)
+	if bundle.TableID != "table-1" || len(bundle.Hands) != 1 {
+		t.Fatalf("expected archived table with one hand, got %+v", bundle)
 	}
-	if hands[0].StartedAt.Before(runRecord.StartedAt) {
-		t.Fatalf("expected hand start time (%s) not before run start (%s)", hands[0].StartedAt, runRecord.StartedAt)
+	if bundle.Run == nil || bundle.Run.Status != persistence.TableRunStatusCompleted {
+		t.Fatalf("expected completed run in archive, got %+v", bundle.Run)
 	}
 }
 
@@ -1261,6 +3027,35 @@ func TestAuth_CorrectBearerTokenAllowsRequest(t *testing.T) {
 	}
 }
 
+func TestResponses_CarryWireSchemaVersion(t *testing.T) {
+	t.Parallel()
+
+	repo := persistence.NewInMemoryRepository()
+	server := NewServer(repo, nil, nil, ServerConfig{AdminBearerTokens: map[string]struct{}{"secret": {}}})
+	req := httptest.NewRequest(http.MethodGet, "/tables", nil)
+	req.Header.Set("Authorization", "Bearer secret")
+	w := httptest.NewRecorder()
+	server.ServeHTTP(w, req)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	if got, want := w.Header().Get("X-Wire-Schema-Version"), fmt.Sprint(domain.WireSchemaVersion); got != want {
+		t.Fatalf("expected wire schema version %q, got %q", want, got)
+	}
+}
+
+func TestWireGolden_HandViews(t *testing.T) {
+	t.Parallel()
+
+	var hand handResponse
+	assertWireGolden(t, "hand.golden.json", &hand)
+	var actions []actionResponse
+	assertWireGolden(t, "actions.golden.json", &actions)
+	if actions[0].DeadlinePolicy != domain.DeadlineOnTime || actions[1].ReceivedAt != nil {
+		t.Fatalf("unexpected decoded actions %+v", actions)
+	}
+}
+
 func TestStartRejectsActiveSeatMissingAgentEndpoint(t *testing.T) {
 	t.Parallel()
 
@@ -1410,6 +3205,171 @@ func TestStartAcceptsValidAgentEndpoints(t *testing.T) {
 	}
 }
 
+func TestNextHand_TriggersRunWaitingOnOperator(t *testing.T) {
+	t.Parallel()
+
+	configs := make(chan tablerunner.RunnerConfig, 1)
+	finished := make(chan struct{})
+	server := NewServer(
+		persistence.NewInMemoryRepository(),
+		func(_ tablerunner.ActionProvider, cfg tablerunner.RunnerConfig) Runner {
+			configs <- cfg
+			return triggeredRunner{cfg: cfg, finished: finished}
+		},
+		func(_ string, _ StartRequest, _ ServerConfig) (tablerunner.ActionProvider, error) {
+			return fakeProvider{}, nil
+		},
+		ServerConfig{AdminBearerTokens: map[string]struct{}{"secret": {}}},
+	)
+	post := func(path, body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodPost, path, strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer secret")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+	start := func(nextHand string) *httptest.ResponseRecorder {
+		return post("/tables/table-1/start", `{
+			"hands_to_run": 2,
+			"next_hand": `+nextHand+`,
+			"seats": [
+				{"seat_no": 1, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9001/callback"},
+				{"seat_no": 2, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9002/callback"}
+			]
+		}`)
+	}
+
+	if w := post("/tables/table-1/next-hand", ""); w.Code != http.StatusConflict {
+		t.Fatalf("expected status %d before the run starts, got %d", http.StatusConflict, w.Code)
+	}
+	for _, nextHand := range []string{`{"policy": "later"}`, `{"policy": "delay"}`, `{"policy": "operator", "delay_ms": 5}`} {
+		if w := start(nextHand); w.Code != http.StatusBadRequest {
+			t.Fatalf("expected status %d for next_hand %s, got %d body=%s", http.StatusBadRequest, nextHand, w.Code, w.Body.String())
+		}
+	}
+
+	if w := start(`{"policy": "operator"}`); w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	if cfg := <-configs; cfg.NextHand != tablerunner.NextHandOnTrigger || cfg.NextHandTrigger == nil {
+		t.Fatalf("expected the runner to wait on an operator trigger, got %q", cfg.NextHand)
+	}
+	if w := post("/tables/table-1/next-hand", ""); w.Code != http.StatusAccepted {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusAccepted, w.Code, w.Body.String())
+	}
+	select {
+	case <-finished:
+	case <-time.After(2 * time.Second):
+		t.Fatal("expected the trigger to reach the runner")
+	}
+}
+
+func TestPause_HoldsRunningTableBetweenHands(t *testing.T) {
+	t.Parallel()
+
+	configs := make(chan tablerunner.RunnerConfig, 1)
+	finished := make(chan struct{})
+	server := NewServer(
+		persistence.NewInMemoryRepository(),
+		func(_ tablerunner.ActionProvider, cfg tablerunner.RunnerConfig) Runner {
+			configs <- cfg
+			return triggeredRunner{cfg: cfg, finished: finished}
+		},
+		func(_ string, _ StartRequest, _ ServerConfig) (tablerunner.ActionProvider, error) {
+			return fakeProvider{}, nil
+		},
+		ServerConfig{AdminBearerTokens: map[string]struct{}{"secret": {}}},
+	)
+	post := func(path, body string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodPost, path, strings.NewReader(body))
+		req.Header.Set("Authorization", "Bearer secret")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+
+	if w := post("/tables/table-1/pause", ""); w.Code != http.StatusConflict {
+		t.Fatalf("expected status %d before the run starts, got %d", http.StatusConflict, w.Code)
+	}
+	w := post("/tables/table-1/start", `{
+		"hands_to_run": 2,
+		"next_hand": {"policy": "operator"},
+		"seats": [
+			{"seat_no": 1, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9001/callback"},
+			{"seat_no": 2, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9002/callback"}
+		]
+	}`)
+	if w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	cfg := <-configs
+	if cfg.Gate == nil || cfg.Gate.Paused() {
+		t.Fatalf("expected the runner to start with an open gate, got %+v", cfg.Gate)
+	}
+	if w := post("/tables/table-1/pause", ""); w.Code != http.StatusOK || !cfg.Gate.Paused() {
+		t.Fatalf("expected the table paused, got %d body=%s", w.Code, w.Body.String())
+	}
+	if w := post("/tables/table-1/pause", ""); w.Code != http.StatusConflict {
+		t.Fatalf("expected status %d pausing twice, got %d", http.StatusConflict, w.Code)
+	}
+	if w := post("/tables/table-1/resume", ""); w.Code != http.StatusOK || cfg.Gate.Paused() {
+		t.Fatalf("expected the table resumed, got %d body=%s", w.Code, w.Body.String())
+	}
+	if w := post("/tables/table-1/resume", ""); w.Code != http.StatusConflict {
+		t.Fatalf("expected status %d resuming an open table, got %d", http.StatusConflict, w.Code)
+	}
+	if w := post("/tables/table-1/next-hand", ""); w.Code != http.StatusAccepted {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusAccepted, w.Code, w.Body.String())
+	}
+	select {
+	case <-finished:
+	case <-time.After(2 * time.Second):
+		t.Fatal("expected the run to finish")
+	}
+}
+
+func TestStart_PassesPacingToTheRunner(t *testing.T) {
+	t.Parallel()
+
+	configs := make(chan tablerunner.RunnerConfig, 1)
+	server := NewServer(
+		persistence.NewInMemoryRepository(),
+		func(_ tablerunner.ActionProvider, cfg tablerunner.RunnerConfig) Runner {
+			configs <- cfg
+			return fakeRunner{cfg: cfg}
+		},
+		func(_ string, _ StartRequest, _ ServerConfig) (tablerunner.ActionProvider, error) {
+			return fakeProvider{}, nil
+		},
+		ServerConfig{AdminBearerTokens: map[string]struct{}{"secret": {}}},
+	)
+	start := func(tableID, pacing string) *httptest.ResponseRecorder {
+		req := httptest.NewRequest(http.MethodPost, "/tables/"+tableID+"/start", strings.NewReader(`{
+			"hands_to_run": 1,
+			"pacing": `+pacing+`,
+			"seats": [
+				{"seat_no": 1, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9001/callback"},
+				{"seat_no": 2, "stack": 10000, "status": "active", "agent_endpoint": "http://agent.local:9002/callback"}
+			]
+		}`))
+		req.Header.Set("Authorization", "Bearer secret")
+		w := httptest.NewRecorder()
+		server.ServeHTTP(w, req)
+		return w
+	}
+
+	if w := start("table-1", `{"cooldowns": [{"every_ms": 1000, "duration_ms": 1000}]}`); w.Code != http.StatusBadRequest {
+		t.Fatalf("expected status %d for a cooldown filling its period, got %d body=%s", http.StatusBadRequest, w.Code, w.Body.String())
+	}
+	if w := start("table-2", `{"hands_per_hour": 60, "cooldowns": [{"every_ms": 3600000, "duration_ms": 300000}]}`); w.Code != http.StatusOK {
+		t.Fatalf("expected status %d, got %d body=%s", http.StatusOK, w.Code, w.Body.String())
+	}
+	cfg := <-configs
+	if cfg.Pacing == nil || cfg.Pacing.HandsPerHour != 60 || len(cfg.Pacing.Cooldowns) != 1 || cfg.Pacing.Cooldowns[0].DurationMS != 300000 {
+		t.Fatalf("expected the runner to get the requested pacing, got %+v", cfg.Pacing)
+	}
+}
+
 func TestStartRejectsOversizedRequestBody(t *testing.T) {
 	t.Parallel()
 
@@ -1436,6 +3396,22 @@ func TestStartRejectsOversizedRequestBody(t *testing.T) {
 	}
 }
 
+// triggeredRunner returns once the operator triggers the next hand.
+type triggeredRunner struct {
+	cfg      tablerunner.RunnerConfig
+	finished chan struct{}
+}
+
+func (r triggeredRunner) RunTable(ctx context.Context, _ tablerunner.RunTableInput) (tablerunner.RunTableResult, error) {
+	defer close(r.finished)
+	select {
+	case <-r.cfg.NextHandTrigger:
+		return tablerunner.RunTableResult{HandsCompleted: 2}, nil
+	case <-ctx.Done():
+		return tablerunner.RunTableResult{}, ctx.Err()
+	}
+}
+
 type fakeRunner struct {
 	cfg tablerunner.RunnerConfig
 }
@@ -1536,3 +3512,25 @@ func waitForTableRunStatus(t *testing.T, repo persistence.Repository, tableID st
 	body, _ := json.Marshal(record)
 	t.Fatalf("timed out waiting for table %s to reach status %q; latest=%s", tableID, want, string(body))
 }
+
+// assertWireGolden decodes testdata/name into target, rejecting unknown
+// fields, and checks that encoding it again reproduces the file exactly.
+func assertWireGolden(t *testing.T, name string, target any) {
+	t.Helper()
+	golden, err := os.ReadFile(filepath.Join("testdata", name))
+	if err != nil {
+		t.Fatalf("read golden failed: %v", err)
+	}
+	decoder := json.NewDecoder(bytes.NewReader(golden))
+	decoder.DisallowUnknownFields()
+	if err := decoder.Decode(target); err != nil {
+		t.Fatalf("decode %s failed: %v", name, err)
+	}
+	encoded, err := json.MarshalIndent(target, "", "  ")
+	if err != nil {
+		t.Fatalf("encode %s failed: %v", name, err)
+	}
+	if want := bytes.TrimSpace(golden); !bytes.Equal(encoded, want) {
+		t.Fatalf("%s no longer round-trips; wire changes need a docs/wire-schema.md update\nwant=%s\ngot=%s", name, want, encoded)
+	}
+}
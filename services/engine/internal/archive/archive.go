@@ -0,0 +1,401 @@
+package archive
+
+import (
+	"bytes"
+	"compress/gzip"
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"sync"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/embargo"
+	"github.com/imaddar/poker-arena/services/engine/internal/persistence"
+	"github.com/imaddar/poker-arena/services/engine/internal/stats"
+)
+
+const (
+	BundleVersion = 1
+	indexKey      = "index.json"
+)
+
+var (
+	ErrArchiverMisconfigured = errors.New("archiver misconfigured")
+	ErrChecksumMismatch      = errors.New("archive checksum mismatch")
+)
+
+type Bundle struct {
+	Version    int          `json:"version"`
+	TableID    string       `json:"table_id"`
+	ArchivedAt time.Time    `json:"archived_at"`
+	Table      *TableInfo   `json:"table,omitempty"`
+	Seats      []SeatInfo   `json:"seats"`
+	Run        *RunInfo     `json:"run,omitempty"`
+	Hands      []HandBundle `json:"hands"`
+	// Config is the full configuration the run was played under, for
+	// bundles archived through ArchiveRun.
+	Config *domain.TableConfig `json:"config,omitempty"`
+}
+
+type TableInfo struct {
+	Name       string       `json:"name"`
+	MaxSeats   uint8        `json:"max_seats"`
+	SmallBlind domain.Chips `json:"small_blind"`
+	BigBlind   domain.Chips `json:"big_blind"`
+	Status     string       `json:"status"`
+	CreatedAt  time.Time    `json:"created_at"`
+}
+
+type SeatInfo struct {
+	SeatNo         domain.SeatNo     `json:"seat_no"`
+	AgentID        string            `json:"agent_id"`
+	AgentVersionID string            `json:"agent_version_id"`
+	Stack          domain.Chips      `json:"stack"`
+	Status         domain.SeatStatus `json:"status"`
+}
+
+type RunInfo struct {
+	Status         persistence.TableRunStatus `json:"status"`
+	StartedAt      time.Time                  `json:"started_at"`
+	EndedAt        *time.Time                 `json:"ended_at,omitempty"`
+	Error          string                     `json:"error,omitempty"`
+	HandsRequested int                        `json:"hands_requested"`
+	HandsCompleted int                        `json:"hands_completed"`
+	TotalActions   int                        `json:"total_actions"`
+	TotalFallbacks int                        `json:"total_fallbacks"`
+}
+
+type HandBundle struct {
+	HandID        string            `json:"hand_id"`
+	HandNo        uint64            `json:"hand_no"`
+	StartedAt     time.Time         `json:"started_at"`
+	EndedAt       *time.Time        `json:"ended_at,omitempty"`
+	FinalPhase    domain.HandPhase  `json:"final_phase"`
+	FinalState    domain.HandState  `json:"final_state"`
+	WinnerSummary []domain.PotAward `json:"winner_summary,omitempty"`
+	Actions       []ActionEntry     `json:"actions"`
+	// DeckCommitment is the hand's commitment to its shuffled deck, which
+	// rules.CommitDeck of FinalState.Deck's first cards reproduces.
+	DeckCommitment string `json:"deck_commitment,omitempty"`
+	// Timing profiles the timed decisions per street and seat.
+	Timing      []stats.StreetTiming `json:"timing,omitempty"`
+	Annotations []string             `json:"annotations,omitempty"`
+}
+
+type ActionEntry struct {
+	Street                domain.Street         `json:"street"`
+	ActingSeat            domain.SeatNo         `json:"acting_seat"`
+	Action                domain.ActionKind     `json:"action"`
+	Amount                *domain.Chips         `json:"amount,omitempty"`
+	IsFallback            bool                  `json:"is_fallback"`
+	At                    time.Time             `json:"at"`
+	ReceivedAt            *time.Time            `json:"received_at,omitempty"`
+	DeadlinePolicy        domain.DeadlinePolicy `json:"deadline_policy,omitempty"`
+	ThinkTimeMS           *uint64               `json:"think_time_ms,omitempty"`
+	LatencyCompensationMS uint64                `json:"latency_compensation_ms,omitempty"`
+	// Telemetry is only bundled once its embargo has lifted.
+	Telemetry *domain.Telemetry `json:"telemetry,omitempty"`
+}
+
+type IndexEntry struct {
+	TableID    string    `json:"table_id"`
+	Key        string    `json:"key"`
+	ArchivedAt time.Time `json:"archived_at"`
+	HandCount  int       `json:"hand_count"`
+	SizeBytes  int       `json:"size_bytes"`
+	SHA256     string    `json:"sha256"`
+}
+
+type Archiver struct {
+	store   Store
+	repo    persistence.Repository
+	now     func() time.Time
+	embargo embargo.Policy
+
+	// Serializes index read-modify-write cycles.
+	mu sync.Mutex
+}
+
+func NewArchiver(store Store, repo persistence.Repository) *Archiver {
+	return &Archiver{
+		store: store,
+		repo:  repo,
+		now:   func() time.Time { return time.Now().UTC() },
+	}
+}
+
+// SetEmbargo sets the policy that keeps bot telemetry out of bundles
+// archived before it lifts.
+func (a *Archiver) SetEmbargo(policy embargo.Policy) {
+	a.embargo = policy
+}
+
+// ArchiveTable bundles the table's config, seats, latest run and hand logs
+// into a gzip-compressed JSON object and records it in the index.
+func (a *Archiver) ArchiveTable(ctx context.Context, tableID string) (IndexEntry, error) {
+	return a.archive(ctx, tableID, nil)
+}
+
+// ArchiveRun archives the table as ArchiveTable does, and bundles config as
+// the full configuration its run was played under.
+func (a *Archiver) ArchiveRun(ctx context.Context, tableID string, config domain.TableConfig) (IndexEntry, error) {
+	return a.archive(ctx, tableID, &config)
+}
+
+func (a *Archiver) archive(ctx context.Context, tableID string, config *domain.TableConfig) (IndexEntry, error) {
+	if a == nil || a.store == nil || a.repo == nil {
+		return IndexEntry{}, ErrArchiverMisconfigured
+	}
+
+	bundle, err := a.buildBundle(tableID)
+	if err != nil {
+		return IndexEntry{}, err
+	}
+	bundle.Config = config
+	payload, err := encodeBundle(bundle)
+	if err != nil {
+		return IndexEntry{}, err
+	}
+
+	sum := sha256.Sum256(payload)
+	entry := IndexEntry{
+		TableID:    tableID,
+		Key:        fmt.Sprintf("tables/%s/%d.json.gz", tableID, bundle.ArchivedAt.UnixNano()),
+		ArchivedAt: bundle.ArchivedAt,
+		HandCount:  len(bundle.Hands),
+		SizeBytes:  len(payload),
+		SHA256:     hex.EncodeToString(sum[:]),
+	}
+	if err := a.store.Put(ctx, entry.Key, payload); err != nil {
+		return IndexEntry{}, fmt.Errorf("store archive: %w", err)
+	}
+
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	index, err := a.readIndex(ctx)
+	if err != nil {
+		return IndexEntry{}, err
+	}
+	index = append(index, entry)
+	if err := a.writeIndex(ctx, index); err != nil {
+		return IndexEntry{}, err
+	}
+	return entry, nil
+}
+
+func (a *Archiver) Index(ctx context.Context) ([]IndexEntry, error) {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	return a.readIndex(ctx)
+}
+
+// Latest returns the most recent archive entry for a table.
+func (a *Archiver) Latest(ctx context.Context, tableID string) (IndexEntry, bool, error) {
+	index, err := a.Index(ctx)
+	if err != nil {
+		return IndexEntry{}, false, err
+	}
+	var latest IndexEntry
+	found := false
+	for _, entry := range index {
+		if entry.TableID != tableID {
+			continue
+		}
+		if !found || entry.ArchivedAt.After(latest.ArchivedAt) {
+			latest = entry
+			found = true
+		}
+	}
+	return latest, found, nil
+}
+
+func (a *Archiver) Load(ctx context.Context, entry IndexEntry) (Bundle, error) {
+	payload, err := a.store.Get(ctx, entry.Key)
+	if err != nil {
+		return Bundle{}, err
+	}
+	sum := sha256.Sum256(payload)
+	if entry.SHA256 != "" && hex.EncodeToString(sum[:]) != entry.SHA256 {
+		return Bundle{}, fmt.Errorf("%w: %s", ErrChecksumMismatch, entry.Key)
+	}
+	return decodeBundle(payload)
+}
+
+func (a *Archiver) buildBundle(tableID string) (Bundle, error) {
+	bundle := Bundle{
+		Version:    BundleVersion,
+		TableID:    tableID,
+		ArchivedAt: a.now(),
+		Seats:      []SeatInfo{},
+		Hands:      []HandBundle{},
+	}
+
+	table, ok, err := a.repo.GetTable(tableID)
+	if err != nil {
+		return Bundle{}, fmt.Errorf("load table: %w", err)
+	}
+	if ok {
+		bundle.Table = &TableInfo{
+			Name:       table.Name,
+			MaxSeats:   table.MaxSeats,
+			SmallBlind: table.SmallBlind,
+			BigBlind:   table.BigBlind,
+			Status:     table.Status,
+			CreatedAt:  table.CreatedAt,
+		}
+		seats, err := a.repo.ListSeats(tableID)
+		if err != nil {
+			return Bundle{}, fmt.Errorf("load seats: %w", err)
+		}
+		for _, seat := range seats {
+			bundle.Seats = append(bundle.Seats, SeatInfo{
+				SeatNo:         seat.SeatNo,
+				AgentID:        seat.AgentID,
+				AgentVersionID: seat.AgentVersionID,
+				Stack:          seat.Stack,
+				Status:         seat.Status,
+			})
+		}
+	}
+
+	run, ok, err := a.repo.GetTableRun(tableID)
+	if err != nil {
+		return Bundle{}, fmt.Errorf("load table run: %w", err)
+	}
+	var endedAt *time.Time
+	if ok {
+		endedAt = run.EndedAt
+		bundle.Run = &RunInfo{
+			Status:         run.Status,
+			StartedAt:      run.StartedAt,
+			EndedAt:        run.EndedAt,
+			Error:          run.Error,
+			HandsRequested: run.HandsRequested,
+			HandsCompleted: run.HandsCompleted,
+			TotalActions:   run.TotalActions,
+			TotalFallbacks: run.TotalFallbacks,
+		}
+	}
+
+	hands, err := a.repo.ListHands(tableID)
+	if err != nil {
+		return Bundle{}, fmt.Errorf("load hands: %w", err)
+	}
+	for _, hand := range hands {
+		handBundle, err := a.bundleHand(hand, endedAt, bundle.ArchivedAt)
+		if err != nil {
+			return Bundle{}, err
+		}
+		bundle.Hands = append(bundle.Hands, handBundle)
+	}
+	return bundle, nil
+}
+
+// bundleHand loads a stored hand's action log. Telemetry is released as of
+// archivedAt for a run that ended at endedAt.
+func (a *Archiver) bundleHand(hand persistence.HandRecord, endedAt *time.Time, archivedAt time.Time) (HandBundle, error) {
+	actions, err := a.repo.ListActions(hand.HandID)
+	if err != nil {
+		return HandBundle{}, fmt.Errorf("load actions for %s: %w", hand.HandID, err)
+	}
+	entries := make([]ActionEntry, 0, len(actions))
+	var decisions []stats.TimedDecision
+	for _, action := range actions {
+		entry := ActionEntry{
+			Street:                action.Street,
+			ActingSeat:            action.ActingSeat,
+			Action:                action.Action,
+			Amount:                action.Amount,
+			IsFallback:            action.IsFallback,
+			At:                    action.At,
+			DeadlinePolicy:        action.DeadlinePolicy,
+			Telemetry:             a.embargo.Release(action.Telemetry, endedAt, archivedAt),
+			LatencyCompensationMS: action.LatencyCompensationMS,
+		}
+		if !action.ReceivedAt.IsZero() {
+			receivedAt := action.ReceivedAt
+			thinkTimeMS := action.ThinkTimeMS
+			entry.ReceivedAt = &receivedAt
+			entry.ThinkTimeMS = &thinkTimeMS
+			decisions = append(decisions, stats.TimedDecision{
+				Street:    action.Street,
+				SeatNo:    action.ActingSeat,
+				ThinkTime: time.Duration(action.ThinkTimeMS) * time.Millisecond,
+			})
+		}
+		entries = append(entries, entry)
+	}
+	return HandBundle{
+		HandID:         hand.HandID,
+		HandNo:         hand.HandNo,
+		StartedAt:      hand.StartedAt,
+		EndedAt:        hand.EndedAt,
+		FinalPhase:     hand.FinalPhase,
+		FinalState:     hand.FinalState,
+		WinnerSummary:  hand.WinnerSummary,
+		Actions:        entries,
+		DeckCommitment: hand.FinalState.DeckCommitment,
+		Timing:         stats.StreetTimingProfile(decisions),
+		Annotations:    hand.Annotations,
+	}, nil
+}
+
+func (a *Archiver) readIndex(ctx context.Context) ([]IndexEntry, error) {
+	raw, err := a.store.Get(ctx, indexKey)
+	if err != nil {
+		if errors.Is(err, ErrObjectNotFound) {
+			return []IndexEntry{}, nil
+		}
+		return nil, fmt.Errorf("read archive index: %w", err)
+	}
+	var index []IndexEntry
+	if err := json.Unmarshal(raw, &index); err != nil {
+		return nil, fmt.Errorf("decode archive index: %w", err)
+	}
+	return index, nil
+}
+
+func (a *Archiver) writeIndex(ctx context.Context, index []IndexEntry) error {
+	raw, err := json.MarshalIndent(index, "", "  ")
+	if err != nil {
+		return fmt.Errorf("encode archive index: %w", err)
+	}
+	if err := a.store.Put(ctx, indexKey, raw); err != nil {
+		return fmt.Errorf("write archive index: %w", err)
+	}
+	return nil
+}
+
+func encodeBundle(bundle Bundle) ([]byte, error) {
+	var buf bytes.Buffer
+	gz := gzip.NewWriter(&buf)
+	if err := json.NewEncoder(gz).Encode(bundle); err != nil {
+		return nil, fmt.Errorf("encode archive bundle: %w", err)
+	}
+	if err := gz.Close(); err != nil {
+		return nil, fmt.Errorf("compress archive bundle: %w", err)
+	}
+	return buf.Bytes(), nil
+}
+
+func decodeBundle(payload []byte) (Bundle, error) {
+	gz, err := gzip.NewReader(bytes.NewReader(payload))
+	if err != nil {
+		return Bundle{}, fmt.Errorf("decompress archive bundle: %w", err)
+	}
+	defer gz.Close()
+	raw, err := io.ReadAll(gz)
+	if err != nil {
+		return Bundle{}, fmt.Errorf("decompress archive bundle: %w", err)
+	}
+	var bundle Bundle
+	if err := json.Unmarshal(raw, &bundle); err != nil {
+		return Bundle{}, fmt.Errorf("decode archive bundle: %w", err)
+	}
+	return bundle, nil
+}
@@ -0,0 +1,287 @@
+package archive
+
+import (
+	"context"
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/embargo"
+	"github.com/imaddar/poker-arena/services/engine/internal/persistence"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+func TestArchiveTable_WritesCompressedBundleAndIndex(t *testing.T) {
+	t.Parallel()
+
+	store := mustLocalStore(t)
+	repo := seededRepository(t, "table-1")
+	archiver := NewArchiver(store, repo)
+
+	entry, err := archiver.ArchiveTable(context.Background(), "table-1")
+	if err != nil {
+		t.Fatalf("ArchiveTable failed: %v", err)
+	}
+	if entry.HandCount != 1 || entry.SizeBytes == 0 || entry.SHA256 == "" {
+		t.Fatalf("unexpected index entry: %+v", entry)
+	}
+
+	latest, ok, err := archiver.Latest(context.Background(), "table-1")
+	if err != nil {
+		t.Fatalf("Latest failed: %v", err)
+	}
+	if !ok || latest.Key != entry.Key {
+		t.Fatalf("expected latest entry %q, got %+v ok=%v", entry.Key, latest, ok)
+	}
+
+	bundle, err := archiver.Load(context.Background(), latest)
+	if err != nil {
+		t.Fatalf("Load failed: %v", err)
+	}
+	if bundle.Version != BundleVersion || bundle.TableID != "table-1" {
+		t.Fatalf("unexpected bundle header: %+v", bundle)
+	}
+	if bundle.Table == nil || bundle.Table.BigBlind != 10 {
+		t.Fatalf("expected table config in bundle, got %+v", bundle.Table)
+	}
+	if len(bundle.Seats) != 1 || bundle.Seats[0].AgentID != "agent-1" {
+		t.Fatalf("expected seat in bundle, got %+v", bundle.Seats)
+	}
+	if bundle.Run == nil || bundle.Run.HandsCompleted != 1 {
+		t.Fatalf("expected run summary in bundle, got %+v", bundle.Run)
+	}
+	if len(bundle.Hands) != 1 || len(bundle.Hands[0].Actions) != 1 {
+		t.Fatalf("expected hand with action log, got %+v", bundle.Hands)
+	}
+	if timing := bundle.Hands[0].Timing; len(timing) != 1 || timing[0].MaxMS != 420 {
+		t.Fatalf("expected the hand's timing profile, got %+v", timing)
+	}
+}
+
+func TestArchiveRun_BundlesTheRunConfigAndDeckCommitments(t *testing.T) {
+	t.Parallel()
+
+	repo := seededRepository(t, "table-1")
+	dealt, err := rules.NewDealer(rules.NewSeededShuffler(5)).InitHand(domain.HandState{HandID: "hand-1"})
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	if err := repo.CompleteHand("hand-1", persistence.HandRecord{
+		TableID:    "table-1",
+		HandNo:     1,
+		FinalPhase: domain.HandPhaseComplete,
+		FinalState: dealt,
+	}); err != nil {
+		t.Fatalf("CompleteHand failed: %v", err)
+	}
+	archiver := NewArchiver(mustLocalStore(t), repo)
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.BettingStructure = domain.BettingFixedLimit
+	cfg.Ante = 2
+	entry, err := archiver.ArchiveRun(context.Background(), "table-1", cfg)
+	if err != nil {
+		t.Fatalf("ArchiveRun failed: %v", err)
+	}
+	bundle, err := archiver.Load(context.Background(), entry)
+	if err != nil {
+		t.Fatalf("Load failed: %v", err)
+	}
+	if bundle.Config == nil || bundle.Config.BettingStructure != domain.BettingFixedLimit || bundle.Config.Ante != 2 || bundle.Config.StartingStack != cfg.StartingStack {
+		t.Fatalf("expected the run's full config in the bundle, got %+v", bundle.Config)
+	}
+	if len(bundle.Hands) != 1 {
+		t.Fatalf("expected one hand, got %d", len(bundle.Hands))
+	}
+	hand := bundle.Hands[0]
+	if hand.DeckCommitment == "" || hand.DeckCommitment != dealt.DeckCommitment || rules.CommitDeck(hand.FinalState.Deck) != hand.DeckCommitment {
+		t.Fatalf("expected the hand's deck commitment to check out against its deck, got %q", hand.DeckCommitment)
+	}
+}
+
+func TestArchiveTable_IndexKeepsEveryTable(t *testing.T) {
+	t.Parallel()
+
+	store := mustLocalStore(t)
+	repo := seededRepository(t, "table-1")
+	archiver := NewArchiver(store, repo)
+
+	for _, tableID := range []string{"table-1", "table-2"} {
+		if _, err := archiver.ArchiveTable(context.Background(), tableID); err != nil {
+			t.Fatalf("ArchiveTable failed: %v", err)
+		}
+	}
+	index, err := archiver.Index(context.Background())
+	if err != nil {
+		t.Fatalf("Index failed: %v", err)
+	}
+	if len(index) != 2 {
+		t.Fatalf("expected 2 index entries, got %d", len(index))
+	}
+	if _, ok, err := archiver.Latest(context.Background(), "table-3"); err != nil || ok {
+		t.Fatalf("expected no archive for unknown table, ok=%v err=%v", ok, err)
+	}
+}
+
+func TestArchiveTable_BundlesTelemetryOnlyAfterItsEmbargo(t *testing.T) {
+	t.Parallel()
+
+	repo := seededRepository(t, "table-1")
+	endedAt := time.Date(2026, 1, 1, 13, 0, 0, 0, time.UTC)
+	if err := repo.UpsertTableRun(persistence.TableRunRecord{
+		TableID:   "table-1",
+		Status:    persistence.TableRunStatusCompleted,
+		StartedAt: endedAt.Add(-time.Hour),
+		EndedAt:   &endedAt,
+	}); err != nil {
+		t.Fatalf("UpsertTableRun failed: %v", err)
+	}
+	if err := repo.AppendAction(persistence.ActionRecord{
+		HandID:     "hand-1",
+		Street:     domain.StreetPreflop,
+		ActingSeat: 1,
+		Action:     domain.ActionCheck,
+		At:         endedAt,
+		Telemetry:  &domain.Telemetry{Tags: []string{"trap"}},
+	}); err != nil {
+		t.Fatalf("AppendAction failed: %v", err)
+	}
+
+	archiver := NewArchiver(mustLocalStore(t), repo)
+	archiver.SetEmbargo(embargo.Policy{Period: 24 * time.Hour})
+	for _, tc := range []struct {
+		archivedAt time.Time
+		released   bool
+	}{
+		{archivedAt: endedAt.Add(time.Minute), released: false},
+		{archivedAt: endedAt.Add(24 * time.Hour), released: true},
+	} {
+		archiver.now = func() time.Time { return tc.archivedAt }
+		entry, err := archiver.ArchiveTable(context.Background(), "table-1")
+		if err != nil {
+			t.Fatalf("ArchiveTable failed: %v", err)
+		}
+		bundle, err := archiver.Load(context.Background(), entry)
+		if err != nil {
+			t.Fatalf("Load failed: %v", err)
+		}
+		telemetry := bundle.Hands[0].Actions[1].Telemetry
+		if released := telemetry != nil && telemetry.Tags[0] == "trap"; released != tc.released {
+			t.Fatalf("archived at %s: expected released=%v, got %+v", tc.archivedAt, tc.released, telemetry)
+		}
+	}
+}
+
+func TestLoad_RejectsChecksumMismatch(t *testing.T) {
+	t.Parallel()
+
+	store := mustLocalStore(t)
+	archiver := NewArchiver(store, seededRepository(t, "table-1"))
+	entry, err := archiver.ArchiveTable(context.Background(), "table-1")
+	if err != nil {
+		t.Fatalf("ArchiveTable failed: %v", err)
+	}
+	entry.SHA256 = "deadbeef"
+	if _, err := archiver.Load(context.Background(), entry); !errors.Is(err, ErrChecksumMismatch) {
+		t.Fatalf("expected ErrChecksumMismatch, got %v", err)
+	}
+}
+
+func TestLocalStore_RejectsEscapingKeys(t *testing.T) {
+	t.Parallel()
+
+	store := mustLocalStore(t)
+	for _, key := range []string{"", "../outside", "/etc/passwd"} {
+		if err := store.Put(context.Background(), key, []byte("x")); !errors.Is(err, ErrInvalidKey) {
+			t.Fatalf("expected ErrInvalidKey for %q, got %v", key, err)
+		}
+	}
+	if _, err := store.Get(context.Background(), "missing.json"); !errors.Is(err, ErrObjectNotFound) {
+		t.Fatalf("expected ErrObjectNotFound, got %v", err)
+	}
+}
+
+func mustLocalStore(t *testing.T) *LocalStore {
+	t.Helper()
+	store, err := NewLocalStore(t.TempDir())
+	if err != nil {
+		t.Fatalf("NewLocalStore failed: %v", err)
+	}
+	return store
+}
+
+func seededRepository(t *testing.T, tableID string) persistence.Repository {
+	t.Helper()
+	repo := persistence.NewInMemoryRepository()
+	now := time.Date(2026, 1, 1, 12, 0, 0, 0, time.UTC)
+
+	if err := repo.CreateTable(persistence.TableRecord{
+		ID:         tableID,
+		Name:       "archive table",
+		MaxSeats:   6,
+		SmallBlind: 5,
+		BigBlind:   10,
+		Status:     "completed",
+		CreatedAt:  now,
+	}); err != nil {
+		t.Fatalf("CreateTable failed: %v", err)
+	}
+	if err := repo.CreateUser(persistence.UserRecord{ID: "user-1", Name: "owner", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateUser failed: %v", err)
+	}
+	if err := repo.CreateAgent(persistence.AgentRecord{ID: "agent-1", UserID: "user-1", Name: "bot", CreatedAt: now}); err != nil {
+		t.Fatalf("CreateAgent failed: %v", err)
+	}
+	if err := repo.CreateAgentVersion(persistence.AgentVersionRecord{
+		ID:          "version-1",
+		AgentID:     "agent-1",
+		Version:     1,
+		EndpointURL: "http://bot.local/act",
+		CreatedAt:   now,
+	}); err != nil {
+		t.Fatalf("CreateAgentVersion failed: %v", err)
+	}
+	if err := repo.UpsertSeat(persistence.SeatRecord{
+		ID:             "seat-1",
+		TableID:        tableID,
+		SeatNo:         1,
+		AgentID:        "agent-1",
+		AgentVersionID: "version-1",
+		Stack:          1000,
+		Status:         domain.SeatStatusActive,
+		CreatedAt:      now,
+	}); err != nil {
+		t.Fatalf("UpsertSeat failed: %v", err)
+	}
+	if err := repo.UpsertTableRun(persistence.TableRunRecord{
+		TableID:        tableID,
+		Status:         persistence.TableRunStatusCompleted,
+		StartedAt:      now,
+		HandsRequested: 1,
+		HandsCompleted: 1,
+		TotalActions:   1,
+	}); err != nil {
+		t.Fatalf("UpsertTableRun failed: %v", err)
+	}
+	if err := repo.CreateHand(persistence.HandRecord{
+		HandID:    "hand-1",
+		TableID:   tableID,
+		HandNo:    1,
+		StartedAt: now,
+	}); err != nil {
+		t.Fatalf("CreateHand failed: %v", err)
+	}
+	if err := repo.AppendAction(persistence.ActionRecord{
+		HandID:      "hand-1",
+		Street:      domain.StreetPreflop,
+		ActingSeat:  1,
+		Action:      domain.ActionFold,
+		At:          now,
+		ReceivedAt:  now,
+		ThinkTimeMS: 420,
+	}); err != nil {
+		t.Fatalf("AppendAction failed: %v", err)
+	}
+	return repo
+}
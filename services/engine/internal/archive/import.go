@@ -0,0 +1,280 @@
+package archive
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"reflect"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/persistence"
+)
+
+var ErrHandConflict = errors.New("imported hand conflicts with stored hand")
+
+// ImportReport lists what ImportBundle did with each hand in the bundle.
+// Duplicates are bundled hands already in the history, by ID or by
+// content; Annotated are the stored hands whose annotations they extended.
+type ImportReport struct {
+	Imported   []string `json:"imported"`
+	Duplicates []string `json:"duplicates"`
+	Annotated  []string `json:"annotated"`
+}
+
+// ImportBundle re-ingests an archived bundle's hands into the history, for
+// example after restoring a lost database. A hand already stored under the
+// same ID, or with the same content under another ID, is a duplicate: it is
+// not written again, but annotations it carries that the stored hand lacks
+// are merged in. A hand whose ID is stored with different content fails
+// the whole import with ErrHandConflict and a diff of the two versions,
+// before anything is written.
+func (a *Archiver) ImportBundle(bundle Bundle) (ImportReport, error) {
+	report := ImportReport{Imported: []string{}, Duplicates: []string{}, Annotated: []string{}}
+	if a == nil || a.repo == nil {
+		return report, ErrArchiverMisconfigured
+	}
+
+	stored, err := a.repo.ListHands(bundle.TableID)
+	if err != nil {
+		return report, fmt.Errorf("load hands: %w", err)
+	}
+	hands := make(map[string]HandBundle, len(stored)+len(bundle.Hands))
+	byContent := make(map[string]string, len(stored)+len(bundle.Hands))
+	for _, record := range stored {
+		hand, err := a.bundleHand(record, nil, a.now())
+		if err != nil {
+			return report, err
+		}
+		sum, err := HandHash(hand)
+		if err != nil {
+			return report, err
+		}
+		hands[hand.HandID] = hand
+		byContent[sum] = hand.HandID
+	}
+
+	created := make(map[string]bool, len(bundle.Hands))
+	for _, hand := range bundle.Hands {
+		sum, err := HandHash(hand)
+		if err != nil {
+			return report, err
+		}
+		existing, ok := hands[hand.HandID]
+		if !ok {
+			if id, dup := byContent[sum]; dup {
+				existing, ok = hands[id], true
+			}
+		}
+		if ok {
+			existingSum, err := HandHash(existing)
+			if err != nil {
+				return report, err
+			}
+			if existingSum != sum {
+				return report, fmt.Errorf("%w: hand %s: %s", ErrHandConflict, hand.HandID, strings.Join(diffHands(existing, hand), "; "))
+			}
+			report.Duplicates = append(report.Duplicates, hand.HandID)
+			merged := mergeAnnotations(existing.Annotations, hand.Annotations)
+			if len(merged) > len(existing.Annotations) {
+				existing.Annotations = merged
+				hands[existing.HandID] = existing
+				if !created[existing.HandID] && !slices.Contains(report.Annotated, existing.HandID) {
+					report.Annotated = append(report.Annotated, existing.HandID)
+				}
+			}
+			continue
+		}
+
+		other, found, err := a.repo.GetHand(hand.HandID)
+		if err != nil {
+			return report, fmt.Errorf("load hand %s: %w", hand.HandID, err)
+		}
+		if found {
+			return report, fmt.Errorf("%w: hand %s: table_id: stored %s, imported %s", ErrHandConflict, hand.HandID, other.TableID, bundle.TableID)
+		}
+		hand.Annotations = mergeAnnotations(nil, hand.Annotations)
+		hands[hand.HandID] = hand
+		byContent[sum] = hand.HandID
+		created[hand.HandID] = true
+		report.Imported = append(report.Imported, hand.HandID)
+	}
+
+	if len(report.Imported) > 0 && bundle.Run != nil {
+		if err := a.restoreRun(bundle.TableID, *bundle.Run); err != nil {
+			return report, err
+		}
+	}
+	for _, id := range report.Imported {
+		if err := a.storeHand(bundle.TableID, hands[id]); err != nil {
+			return report, err
+		}
+	}
+	for _, id := range report.Annotated {
+		if err := a.repo.AnnotateHand(id, hands[id].Annotations); err != nil {
+			return report, fmt.Errorf("annotate hand %s: %w", id, err)
+		}
+	}
+	return report, nil
+}
+
+// HandHash fingerprints a hand's content: what was dealt and played, not
+// the ID it was stored under or the notes on it. Telemetry is left out as
+// the embargo may have kept it out of either copy, and so is the timing
+// profile, which the actions already determine.
+func HandHash(hand HandBundle) (string, error) {
+	raw, err := json.Marshal(handContent(hand))
+	if err != nil {
+		return "", fmt.Errorf("encode hand %s: %w", hand.HandID, err)
+	}
+	sum := sha256.Sum256(raw)
+	return hex.EncodeToString(sum[:]), nil
+}
+
+// handContent is hand without the fields HandHash ignores, and with its
+// times in UTC so a round trip through the database does not change it.
+func handContent(hand HandBundle) HandBundle {
+	hand.HandID = ""
+	hand.FinalState.HandID = ""
+	hand.Annotations = nil
+	hand.Timing = nil
+	hand.StartedAt = hand.StartedAt.UTC()
+	hand.EndedAt = utcTime(hand.EndedAt)
+	hand.Actions = slices.Clone(hand.Actions)
+	for i := range hand.Actions {
+		hand.Actions[i].At = hand.Actions[i].At.UTC()
+		hand.Actions[i].ReceivedAt = utcTime(hand.Actions[i].ReceivedAt)
+		hand.Actions[i].Telemetry = nil
+	}
+	return hand
+}
+
+func utcTime(t *time.Time) *time.Time {
+	if t == nil {
+		return nil
+	}
+	utc := t.UTC()
+	return &utc
+}
+
+// diffHands lists each field in which imported differs from stored.
+func diffHands(stored HandBundle, imported HandBundle) []string {
+	stored, imported = handContent(stored), handContent(imported)
+	var diff []string
+	field := func(name string, a any, b any) {
+		if !reflect.DeepEqual(a, b) {
+			diff = append(diff, fmt.Sprintf("%s: stored %s, imported %s", name, describe(a), describe(b)))
+		}
+	}
+	field("hand_no", stored.HandNo, imported.HandNo)
+	field("started_at", stored.StartedAt, imported.StartedAt)
+	field("ended_at", stored.EndedAt, imported.EndedAt)
+	field("final_phase", stored.FinalPhase, imported.FinalPhase)
+	field("winner_summary", stored.WinnerSummary, imported.WinnerSummary)
+	if len(stored.Actions) != len(imported.Actions) {
+		diff = append(diff, fmt.Sprintf("actions: stored %d, imported %d", len(stored.Actions), len(imported.Actions)))
+	} else {
+		for i := range stored.Actions {
+			field(fmt.Sprintf("action %d", i+1), stored.Actions[i], imported.Actions[i])
+		}
+	}
+	if len(stored.FinalState.Seats) == len(imported.FinalState.Seats) {
+		for i, seat := range stored.FinalState.Seats {
+			field(fmt.Sprintf("seat %d stack", seat.SeatNo), seat.Stack, imported.FinalState.Seats[i].Stack)
+		}
+	}
+	if len(diff) == 0 {
+		field("final_state", stored.FinalState, imported.FinalState)
+	}
+	return diff
+}
+
+func describe(v any) string {
+	raw, err := json.Marshal(v)
+	if err != nil {
+		return fmt.Sprint(v)
+	}
+	return string(raw)
+}
+
+// mergeAnnotations appends the annotations in extra that base lacks,
+// dropping blanks and repeats.
+func mergeAnnotations(base []string, extra []string) []string {
+	merged := slices.Clone(base)
+	for _, note := range extra {
+		if note = strings.TrimSpace(note); note != "" && !slices.Contains(merged, note) {
+			merged = append(merged, note)
+		}
+	}
+	return merged
+}
+
+// restoreRun stores the bundle's run for a table that has none, so hands
+// imported into an empty history have a run to belong to.
+func (a *Archiver) restoreRun(tableID string, run RunInfo) error {
+	_, found, err := a.repo.GetTableRun(tableID)
+	if err != nil {
+		return fmt.Errorf("load table run: %w", err)
+	}
+	if found {
+		return nil
+	}
+	if err := a.repo.UpsertTableRun(persistence.TableRunRecord{
+		TableID:        tableID,
+		Status:         run.Status,
+		StartedAt:      run.StartedAt,
+		EndedAt:        run.EndedAt,
+		Error:          run.Error,
+		HandsRequested: run.HandsRequested,
+		HandsCompleted: run.HandsCompleted,
+		TotalActions:   run.TotalActions,
+		TotalFallbacks: run.TotalFallbacks,
+	}); err != nil {
+		return fmt.Errorf("restore table run: %w", err)
+	}
+	return nil
+}
+
+// storeHand writes an imported hand and its action log.
+func (a *Archiver) storeHand(tableID string, hand HandBundle) error {
+	if err := a.repo.CreateHand(persistence.HandRecord{
+		HandID:        hand.HandID,
+		TableID:       tableID,
+		HandNo:        hand.HandNo,
+		StartedAt:     hand.StartedAt,
+		EndedAt:       hand.EndedAt,
+		FinalPhase:    hand.FinalPhase,
+		FinalState:    hand.FinalState,
+		WinnerSummary: hand.WinnerSummary,
+		Annotations:   hand.Annotations,
+	}); err != nil {
+		return fmt.Errorf("import hand %s: %w", hand.HandID, err)
+	}
+	for _, action := range hand.Actions {
+		record := persistence.ActionRecord{
+			HandID:                hand.HandID,
+			Street:                action.Street,
+			ActingSeat:            action.ActingSeat,
+			Action:                action.Action,
+			Amount:                action.Amount,
+			IsFallback:            action.IsFallback,
+			At:                    action.At,
+			DeadlinePolicy:        action.DeadlinePolicy,
+			Telemetry:             action.Telemetry,
+			LatencyCompensationMS: action.LatencyCompensationMS,
+		}
+		if action.ReceivedAt != nil {
+			record.ReceivedAt = *action.ReceivedAt
+		}
+		if action.ThinkTimeMS != nil {
+			record.ThinkTimeMS = *action.ThinkTimeMS
+		}
+		if err := a.repo.AppendAction(record); err != nil {
+			return fmt.Errorf("import actions for %s: %w", hand.HandID, err)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,93 @@
+package archive
+
+import (
+	"context"
+	"errors"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/persistence"
+)
+
+func TestImportBundle_RestoresHandsAndMergesDuplicateAnnotations(t *testing.T) {
+	t.Parallel()
+
+	bundle := archivedBundle(t)
+	restored := persistence.NewInMemoryRepository()
+	importer := NewArchiver(mustLocalStore(t), restored)
+
+	report, err := importer.ImportBundle(bundle)
+	if err != nil {
+		t.Fatalf("ImportBundle failed: %v", err)
+	}
+	if !slices.Equal(report.Imported, []string{"hand-1"}) || len(report.Duplicates) != 0 {
+		t.Fatalf("expected hand-1 imported, got %+v", report)
+	}
+	if actions, err := restored.ListActions("hand-1"); err != nil || len(actions) != 1 || actions[0].ThinkTimeMS != 420 {
+		t.Fatalf("expected the action log restored, got %+v err=%v", actions, err)
+	}
+
+	bundle.Hands[0].Annotations = []string{"misplayed river"}
+	renamed := bundle.Hands[0]
+	renamed.HandID = "hand-1-recovered"
+	renamed.FinalState.HandID = renamed.HandID
+	renamed.Annotations = []string{"misplayed river", "flagged by ops"}
+	bundle.Hands = append(bundle.Hands, renamed)
+	report, err = importer.ImportBundle(bundle)
+	if err != nil {
+		t.Fatalf("re-import failed: %v", err)
+	}
+	if len(report.Imported) != 0 || !slices.Equal(report.Duplicates, []string{"hand-1", "hand-1-recovered"}) || !slices.Equal(report.Annotated, []string{"hand-1"}) {
+		t.Fatalf("expected both hands recognised as hand-1, got %+v", report)
+	}
+	hand, ok, err := restored.GetHand("hand-1")
+	if err != nil || !ok || !slices.Equal(hand.Annotations, []string{"misplayed river", "flagged by ops"}) {
+		t.Fatalf("expected merged annotations, got %+v ok=%v err=%v", hand.Annotations, ok, err)
+	}
+	if hands, _ := restored.ListHands("table-1"); len(hands) != 1 {
+		t.Fatalf("expected no duplicate hand stored, got %d hands", len(hands))
+	}
+}
+
+func TestImportBundle_RefusesConflictingVersionsWithADiff(t *testing.T) {
+	t.Parallel()
+
+	repo := seededRepository(t, "table-1")
+	archiver := NewArchiver(mustLocalStore(t), repo)
+	bundle := archivedBundle(t)
+	fresh := bundle.Hands[0]
+	fresh.HandID = "hand-2"
+	fresh.HandNo = 2
+	conflicting := bundle.Hands[0]
+	conflicting.Actions = slices.Clone(conflicting.Actions)
+	conflicting.Actions[0].Action = domain.ActionCheck
+	bundle.Hands = []HandBundle{fresh, conflicting}
+
+	_, err := archiver.ImportBundle(bundle)
+	if !errors.Is(err, ErrHandConflict) {
+		t.Fatalf("expected ErrHandConflict, got %v", err)
+	}
+	if !strings.Contains(err.Error(), `action 1: stored {"street":"preflop","acting_seat":1,"action":"fold"`) {
+		t.Fatalf("expected the diff to name the changed action, got %v", err)
+	}
+	if _, ok, _ := repo.GetHand("hand-2"); ok {
+		t.Fatal("expected nothing written when the import conflicts")
+	}
+}
+
+// archivedBundle archives the seeded table and loads the bundle back.
+func archivedBundle(t *testing.T) Bundle {
+	t.Helper()
+	archiver := NewArchiver(mustLocalStore(t), seededRepository(t, "table-1"))
+	entry, err := archiver.ArchiveTable(context.Background(), "table-1")
+	if err != nil {
+		t.Fatalf("ArchiveTable failed: %v", err)
+	}
+	bundle, err := archiver.Load(context.Background(), entry)
+	if err != nil {
+		t.Fatalf("Load failed: %v", err)
+	}
+	return bundle
+}
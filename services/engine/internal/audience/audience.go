@@ -0,0 +1,273 @@
+// Package audience counts the spectators watching each table and how long
+// they watch, for the lobby, operator metrics and broadcast automation.
+// Spectators poll rather than hold a connection, so a viewer counts as
+// watching while its heartbeats keep arriving within the Timeout. Viewers
+// whose heartbeats stop are dropped the next time the Tracker is used.
+package audience
+
+import (
+	"errors"
+	"slices"
+	"sync"
+	"time"
+)
+
+const DefaultTimeout = 30 * time.Second
+
+var ErrMissingViewer = errors.New("viewer id is required")
+
+type Config struct {
+	// Timeout is how long a viewer counts as watching after its last
+	// heartbeat; zero means DefaultTimeout.
+	Timeout time.Duration
+	// FeaturedViewers features a table once that many viewers watch it;
+	// zero never does.
+	FeaturedViewers int
+	// OnEvent receives every Event, after the Tracker has released its
+	// lock.
+	OnEvent func(Event)
+}
+
+type EventKind string
+
+const (
+	// EventFeatured fires when a table's viewers reach FeaturedViewers.
+	EventFeatured EventKind = "featured"
+	// EventUnfeatured fires when they fall back below it.
+	EventUnfeatured EventKind = "unfeatured"
+)
+
+type Event struct {
+	Kind    EventKind `json:"kind"`
+	TableID string    `json:"table_id"`
+	Viewers int       `json:"viewers"`
+	At      time.Time `json:"at"`
+}
+
+// Stats describe the audience of a table or tournament. WatchTimeMS adds up
+// the time every viewer has spent watching.
+type Stats struct {
+	Viewers     int    `json:"viewers"`
+	PeakViewers int    `json:"peak_viewers"`
+	WatchTimeMS uint64 `json:"watch_time_ms"`
+	Featured    bool   `json:"featured,omitempty"`
+}
+
+// Tracker holds the audience of every table it has seen. It is safe for
+// concurrent use.
+type Tracker struct {
+	config Config
+
+	mu          sync.Mutex
+	now         func() time.Time
+	tables      map[string]*tableAudience
+	tournaments map[string]*tournamentAudience
+}
+
+type tableAudience struct {
+	lastSeen   map[string]time.Time
+	watched    time.Duration
+	peak       int
+	featured   bool
+	tournament string
+}
+
+type tournamentAudience struct {
+	tables []string
+	peak   int
+}
+
+func NewTracker(config Config) *Tracker {
+	if config.Timeout <= 0 {
+		config.Timeout = DefaultTimeout
+	}
+	return &Tracker{
+		config:      config,
+		now:         func() time.Time { return time.Now().UTC() },
+		tables:      make(map[string]*tableAudience),
+		tournaments: make(map[string]*tournamentAudience),
+	}
+}
+
+// AddTable counts tableID's audience towards tournamentID's, and no longer
+// towards any tournament it was in before.
+func (t *Tracker) AddTable(tournamentID, tableID string) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	table := t.table(tableID)
+	if table.tournament == tournamentID {
+		return
+	}
+	if previous, ok := t.tournaments[table.tournament]; ok {
+		previous.tables = slices.DeleteFunc(previous.tables, func(id string) bool { return id == tableID })
+	}
+	table.tournament = tournamentID
+	tournament, ok := t.tournaments[tournamentID]
+	if !ok {
+		tournament = &tournamentAudience{}
+		t.tournaments[tournamentID] = tournament
+	}
+	tournament.tables = append(tournament.tables, tableID)
+}
+
+// Watch records a heartbeat from viewerID watching tableID. It returns the
+// watch time the heartbeat adds: the time since the viewer's last one, if
+// the viewer was still watching.
+func (t *Tracker) Watch(tableID, viewerID string) (time.Duration, error) {
+	if viewerID == "" {
+		return 0, ErrMissingViewer
+	}
+	t.mu.Lock()
+	now := t.now()
+	table := t.table(tableID)
+	events := t.expire(tableID, table, now)
+	var added time.Duration
+	if last, ok := table.lastSeen[viewerID]; ok {
+		added = now.Sub(last)
+		table.watched += added
+	}
+	table.lastSeen[viewerID] = now
+	table.peak = max(table.peak, len(table.lastSeen))
+	events = append(events, t.feature(tableID, table, now)...)
+	if tournament, ok := t.tournaments[table.tournament]; ok {
+		for _, id := range tournament.tables {
+			events = append(events, t.expire(id, t.tables[id], now)...)
+		}
+		tournament.peak = max(tournament.peak, t.uniqueViewers(tournament))
+	}
+	t.mu.Unlock()
+
+	t.emit(events)
+	return added, nil
+}
+
+// Leave stops counting viewerID at tableID straight away.
+func (t *Tracker) Leave(tableID, viewerID string) {
+	t.mu.Lock()
+	now := t.now()
+	table := t.table(tableID)
+	delete(table.lastSeen, viewerID)
+	events := t.expire(tableID, table, now)
+	t.mu.Unlock()
+
+	t.emit(events)
+}
+
+// Table is tableID's audience.
+func (t *Tracker) Table(tableID string) Stats {
+	t.mu.Lock()
+	now := t.now()
+	table := t.table(tableID)
+	events := t.expire(tableID, table, now)
+	stats := table.stats()
+	t.mu.Unlock()
+
+	t.emit(events)
+	return stats
+}
+
+// Tables is the audience of every table with one so far.
+func (t *Tracker) Tables() map[string]Stats {
+	t.mu.Lock()
+	now := t.now()
+	var events []Event
+	out := make(map[string]Stats, len(t.tables))
+	for tableID, table := range t.tables {
+		events = append(events, t.expire(tableID, table, now)...)
+		out[tableID] = table.stats()
+	}
+	t.mu.Unlock()
+
+	t.emit(events)
+	return out
+}
+
+// Tournament is the audience across tournamentID's tables. A viewer
+// watching several of them counts once.
+func (t *Tracker) Tournament(tournamentID string) Stats {
+	t.mu.Lock()
+	now := t.now()
+	var events []Event
+	var stats Stats
+	if tournament, ok := t.tournaments[tournamentID]; ok {
+		for _, tableID := range tournament.tables {
+			table := t.tables[tableID]
+			events = append(events, t.expire(tableID, table, now)...)
+			stats.WatchTimeMS += uint64(table.watched.Milliseconds())
+			stats.Featured = stats.Featured || table.featured
+		}
+		stats.Viewers = t.uniqueViewers(tournament)
+		stats.PeakViewers = tournament.peak
+	}
+	t.mu.Unlock()
+
+	t.emit(events)
+	return stats
+}
+
+func (t *Tracker) table(tableID string) *tableAudience {
+	table, ok := t.tables[tableID]
+	if !ok {
+		table = &tableAudience{lastSeen: make(map[string]time.Time)}
+		t.tables[tableID] = table
+	}
+	return table
+}
+
+// expire drops the viewers whose last heartbeat is older than the Timeout,
+// unfeaturing the table if that leaves too few.
+func (t *Tracker) expire(tableID string, table *tableAudience, now time.Time) []Event {
+	for viewerID, last := range table.lastSeen {
+		if now.Sub(last) > t.config.Timeout {
+			delete(table.lastSeen, viewerID)
+		}
+	}
+	return t.feature(tableID, table, now)
+}
+
+// feature features or unfeatures table as its viewers cross
+// FeaturedViewers.
+func (t *Tracker) feature(tableID string, table *tableAudience, now time.Time) []Event {
+	if t.config.FeaturedViewers <= 0 {
+		return nil
+	}
+	viewers := len(table.lastSeen)
+	featured := viewers >= t.config.FeaturedViewers
+	if featured == table.featured {
+		return nil
+	}
+	table.featured = featured
+	kind := EventUnfeatured
+	if featured {
+		kind = EventFeatured
+	}
+	return []Event{{Kind: kind, TableID: tableID, Viewers: viewers, At: now}}
+}
+
+func (t *Tracker) uniqueViewers(tournament *tournamentAudience) int {
+	viewers := make(map[string]struct{})
+	for _, tableID := range tournament.tables {
+		for viewerID := range t.tables[tableID].lastSeen {
+			viewers[viewerID] = struct{}{}
+		}
+	}
+	return len(viewers)
+}
+
+func (t *Tracker) emit(events []Event) {
+	if t.config.OnEvent == nil {
+		return
+	}
+	for _, event := range events {
+		t.config.OnEvent(event)
+	}
+}
+
+func (a *tableAudience) stats() Stats {
+	return Stats{
+		Viewers:     len(a.lastSeen),
+		PeakViewers: a.peak,
+		WatchTimeMS: uint64(a.watched.Milliseconds()),
+		Featured:    a.featured,
+	}
+}
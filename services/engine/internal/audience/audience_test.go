@@ -0,0 +1,70 @@
+package audience
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+	"time"
+)
+
+func TestTracker_CountsViewersAndWatchTime(t *testing.T) {
+	t.Parallel()
+
+	var events []Event
+	tracker := NewTracker(Config{Timeout: 30 * time.Second, FeaturedViewers: 2, OnEvent: func(event Event) {
+		events = append(events, event)
+	}})
+	now := time.Date(2026, 6, 1, 12, 0, 0, 0, time.UTC)
+	tracker.now = func() time.Time { return now }
+	tracker.AddTable("cup", "table-1")
+	tracker.AddTable("cup", "table-2")
+
+	watch := func(tableID, viewerID string) time.Duration {
+		t.Helper()
+		added, err := tracker.Watch(tableID, viewerID)
+		if err != nil {
+			t.Fatalf("Watch failed: %v", err)
+		}
+		return added
+	}
+	watch("table-1", "alice")
+	watch("table-1", "bob")
+	watch("table-2", "alice")
+	now = now.Add(20 * time.Second)
+	if added := watch("table-1", "alice"); added != 20*time.Second {
+		t.Fatalf("expected the heartbeat to add 20s of watch time, got %s", added)
+	}
+	if got := tracker.Table("table-1"); got != (Stats{Viewers: 2, PeakViewers: 2, WatchTimeMS: 20_000, Featured: true}) {
+		t.Fatalf("unexpected table-1 audience %+v", got)
+	}
+	if got := tracker.Tournament("cup"); got != (Stats{Viewers: 2, PeakViewers: 2, WatchTimeMS: 20_000, Featured: true}) {
+		t.Fatalf("expected alice counted once across the tournament, got %+v", got)
+	}
+
+	// Bob's heartbeats stop, so he drops out and the table is no longer
+	// featured. Alice's next heartbeat after a gap starts a new watch.
+	now = now.Add(25 * time.Second)
+	if got := tracker.Table("table-1"); got.Viewers != 1 || got.Featured {
+		t.Fatalf("expected bob to time out, got %+v", got)
+	}
+	now = now.Add(time.Minute)
+	if added := watch("table-1", "alice"); added != 0 {
+		t.Fatalf("expected no watch time across a gap, got %s", added)
+	}
+	tracker.Leave("table-1", "alice")
+	if got := tracker.Tables()["table-1"]; got.Viewers != 0 || got.WatchTimeMS != 20_000 {
+		t.Fatalf("expected alice gone and the watch time kept, got %+v", got)
+	}
+
+	want := []EventKind{EventFeatured, EventUnfeatured}
+	var kinds []EventKind
+	for _, event := range events {
+		kinds = append(kinds, event.Kind)
+	}
+	if !reflect.DeepEqual(kinds, want) || events[0].TableID != "table-1" || events[0].Viewers != 2 {
+		t.Fatalf("expected table-1 featured then unfeatured, got %+v", events)
+	}
+	if _, err := tracker.Watch("table-1", ""); !errors.Is(err, ErrMissingViewer) {
+		t.Fatalf("expected ErrMissingViewer, got %v", err)
+	}
+}
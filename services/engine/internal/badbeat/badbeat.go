@@ -0,0 +1,162 @@
+// Package badbeat flags hands where a seat lost at showdown after getting
+// its chips in as a big favorite. Equity is exact: it comes from the runout
+// tree of the spot where betting closed, so only flop and turn all-ins can
+// qualify.
+package badbeat
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+	"sync"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
+)
+
+var ErrInvalidConfig = errors.New("invalid bad beat config")
+
+// Config sets what counts as a bad beat. MinEquity is the loser's expected
+// share of the chips awarded at showdown when the chips went in; MinPot
+// ignores smaller pots, e.g. for jackpot qualification.
+type Config struct {
+	MinEquity equity.Ratio `json:"min_equity"`
+	MinPot    domain.Chips `json:"min_pot"`
+}
+
+// DefaultConfig flags a loss after being at least a 4-to-1 favorite.
+func DefaultConfig() Config {
+	minEquity, _ := equity.NewRatio(4, 5)
+	return Config{MinEquity: minEquity}
+}
+
+func (c Config) Validate() error {
+	if c.MinEquity.Sign() <= 0 || c.MinEquity.Cmp(equity.One()) > 0 {
+		return fmt.Errorf("%w: min equity must be above 0 and at most 1, got %s", ErrInvalidConfig, c.MinEquity)
+	}
+	return nil
+}
+
+// BadBeat is one seat's losing hand. Street and Board are where betting
+// closed; Equities holds every contesting seat's share at that point and
+// Equity is the loser's. Winners are the seats awarded chips at showdown.
+type BadBeat struct {
+	TableID    string              `json:"table_id"`
+	HandID     string              `json:"hand_id"`
+	HandNo     uint64              `json:"hand_no"`
+	SeatNo     domain.SeatNo       `json:"seat_no"`
+	Street     domain.Street       `json:"street"`
+	Board      []domain.Card       `json:"board"`
+	FinalBoard []domain.Card       `json:"final_board"`
+	Equity     equity.Ratio        `json:"equity"`
+	Equities   []equity.SeatEquity `json:"equities"`
+	Winners    []domain.SeatNo     `json:"winners"`
+	Pot        domain.Chips        `json:"pot"`
+	At         time.Time           `json:"at"`
+}
+
+// Detect compares spot, the hand as betting closed, with final, the settled
+// hand, and returns a BadBeat for every seat at or above cfg.MinEquity that
+// won no chips. Hands that do not qualify under the table's
+// domain.QualifiedHandRule, and spots the runout tree cannot price, such as
+// preflop all-ins, are not bad beats.
+func Detect(spot, final domain.HandState, cfg Config) ([]BadBeat, error) {
+	if err := cfg.Validate(); err != nil {
+		return nil, err
+	}
+	if !final.Qualified() {
+		return nil, nil
+	}
+	var pot domain.Chips
+	var winners []domain.SeatNo
+	for _, award := range final.ShowdownAwards {
+		pot += award.Amount
+		for _, seatNo := range award.Seats {
+			if !slices.Contains(winners, seatNo) {
+				winners = append(winners, seatNo)
+			}
+		}
+	}
+	if pot == 0 || pot < cfg.MinPot {
+		return nil, nil
+	}
+	slices.Sort(winners)
+
+	tree, err := equity.BuildRunoutTree(spot)
+	if errors.Is(err, equity.ErrRunoutUnsupportedBoard) || errors.Is(err, equity.ErrRunoutNotContested) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	street := domain.StreetFlop
+	if len(spot.Board) == 4 {
+		street = domain.StreetTurn
+	}
+
+	var out []BadBeat
+	for _, seat := range tree.Equity {
+		if slices.Contains(winners, seat.SeatNo) || seat.Share.Cmp(cfg.MinEquity) < 0 {
+			continue
+		}
+		out = append(out, BadBeat{
+			TableID:    final.TableID,
+			HandID:     final.HandID,
+			HandNo:     final.HandNo,
+			SeatNo:     seat.SeatNo,
+			Street:     street,
+			Board:      slices.Clone(spot.Board),
+			FinalBoard: slices.Clone(final.Board),
+			Equity:     seat.Share,
+			Equities:   slices.Clone(tree.Equity),
+			Winners:    slices.Clone(winners),
+			Pot:        pot,
+		})
+	}
+	return out, nil
+}
+
+// Detector keeps the bad beats found on each table, in the order the hands
+// completed.
+type Detector struct {
+	config Config
+	now    func() time.Time
+
+	mu     sync.Mutex
+	events map[string][]BadBeat
+}
+
+func NewDetector(config Config) (*Detector, error) {
+	if err := config.Validate(); err != nil {
+		return nil, err
+	}
+	return &Detector{
+		config: config,
+		now:    func() time.Time { return time.Now().UTC() },
+		events: make(map[string][]BadBeat),
+	}, nil
+}
+
+// Check runs Detect with the detector's thresholds, records what it finds and
+// returns it.
+func (d *Detector) Check(spot, final domain.HandState) ([]BadBeat, error) {
+	beats, err := Detect(spot, final, d.config)
+	if err != nil || len(beats) == 0 {
+		return nil, err
+	}
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	at := d.now()
+	for i := range beats {
+		beats[i].At = at
+	}
+	d.events[final.TableID] = append(d.events[final.TableID], beats...)
+	return slices.Clone(beats), nil
+}
+
+func (d *Detector) BadBeats(tableID string) []BadBeat {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	return slices.Clone(d.events[tableID])
+}
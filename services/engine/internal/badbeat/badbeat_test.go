@@ -0,0 +1,127 @@
+package badbeat
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
+)
+
+func card(rank domain.Rank, suit domain.Suit) domain.Card {
+	return domain.NewCard(rank, suit)
+}
+
+// turnSpot is aces against kings, both all-in for 100 on the turn.
+func turnSpot() domain.HandState {
+	return domain.HandState{
+		HandID:     "hand-1",
+		TableID:    "table-1",
+		HandNo:     7,
+		ButtonSeat: 1,
+		Board: []domain.Card{
+			card(2, domain.SuitClubs), card(7, domain.SuitDiamonds), card(9, domain.SuitHearts), card(11, domain.SuitClubs),
+		},
+		Seats: []domain.SeatState{
+			{SeatNo: 1, TotalCommitted: 100, Status: domain.SeatStatusActive},
+			{SeatNo: 2, TotalCommitted: 100, Status: domain.SeatStatusActive},
+		},
+		HoleCards: []domain.SeatCards{
+			{SeatNo: 1, Cards: []domain.Card{card(14, domain.SuitSpades), card(14, domain.SuitHearts)}},
+			{SeatNo: 2, Cards: []domain.Card{card(13, domain.SuitSpades), card(13, domain.SuitHearts)}},
+		},
+	}
+}
+
+func settled(spot domain.HandState, river domain.Card, winner domain.SeatNo) domain.HandState {
+	final := spot
+	final.Board = append(append([]domain.Card(nil), spot.Board...), river)
+	final.Phase = domain.HandPhaseComplete
+	final.ShowdownAwards = []domain.PotAward{{Amount: 200, Seats: []domain.SeatNo{winner}, Reason: "showdown"}}
+	return final
+}
+
+func TestDetect_FlagsTheFavoriteWhoLost(t *testing.T) {
+	t.Parallel()
+
+	spot := turnSpot()
+	beats, err := Detect(spot, settled(spot, card(13, domain.SuitDiamonds), 2), DefaultConfig())
+	if err != nil {
+		t.Fatalf("Detect failed: %v", err)
+	}
+	if len(beats) != 1 {
+		t.Fatalf("expected one bad beat, got %+v", beats)
+	}
+	beat := beats[0]
+	if beat.SeatNo != 1 || beat.Street != domain.StreetTurn || beat.Pot != 200 || beat.HandNo != 7 {
+		t.Fatalf("unexpected bad beat %+v", beat)
+	}
+	if beat.Equity.String() != "21/22" || len(beat.Equities) != 2 || beat.Equities[1].Share.String() != "1/22" {
+		t.Fatalf("expected aces at 21/22 against kings at 1/22, got %+v", beat.Equities)
+	}
+	if len(beat.Winners) != 1 || beat.Winners[0] != 2 || len(beat.Board) != 4 || len(beat.FinalBoard) != 5 {
+		t.Fatalf("expected the turn board and the river winner, got %+v", beat)
+	}
+
+	// The favorite holding up, a pot under MinPot, an unqualified hand and a
+	// threshold above the loser's equity are not bad beats.
+	if beats, err := Detect(spot, settled(spot, card(3, domain.SuitDiamonds), 1), DefaultConfig()); err != nil || len(beats) != 0 {
+		t.Fatalf("expected no bad beat when aces hold, got %+v, %v", beats, err)
+	}
+	config := DefaultConfig()
+	config.MinPot = 201
+	if beats, err := Detect(spot, settled(spot, card(13, domain.SuitDiamonds), 2), config); err != nil || len(beats) != 0 {
+		t.Fatalf("expected no bad beat below the min pot, got %+v, %v", beats, err)
+	}
+	unqualified := settled(spot, card(13, domain.SuitDiamonds), 2)
+	unqualified.Qualification = &domain.QualifiedHandRule{SawFlop: true, MinPot: 500}
+	if beats, err := Detect(spot, unqualified, DefaultConfig()); err != nil || len(beats) != 0 {
+		t.Fatalf("expected no bad beat in a hand the table does not qualify, got %+v, %v", beats, err)
+	}
+	config.MinPot = 0
+	config.MinEquity, _ = equity.NewRatio(22, 23)
+	if beats, err := Detect(spot, settled(spot, card(13, domain.SuitDiamonds), 2), config); err != nil || len(beats) != 0 {
+		t.Fatalf("expected no bad beat below the threshold, got %+v, %v", beats, err)
+	}
+}
+
+func TestDetect_SkipsPreflopSpotsAndRejectsBadThresholds(t *testing.T) {
+	t.Parallel()
+
+	spot := turnSpot()
+	final := settled(spot, card(13, domain.SuitDiamonds), 2)
+	spot.Board = nil
+	if beats, err := Detect(spot, final, DefaultConfig()); err != nil || len(beats) != 0 {
+		t.Fatalf("expected preflop all-ins to be skipped, got %+v, %v", beats, err)
+	}
+
+	for _, raw := range []string{"0", "3/2"} {
+		minEquity, err := equity.ParseRatio(raw)
+		if err != nil {
+			t.Fatalf("ParseRatio(%q) failed: %v", raw, err)
+		}
+		if _, err := NewDetector(Config{MinEquity: minEquity}); !errors.Is(err, ErrInvalidConfig) {
+			t.Fatalf("expected ErrInvalidConfig for %s, got %v", raw, err)
+		}
+	}
+}
+
+func TestDetector_RecordsBadBeatsPerTable(t *testing.T) {
+	t.Parallel()
+
+	detector, err := NewDetector(DefaultConfig())
+	if err != nil {
+		t.Fatalf("NewDetector failed: %v", err)
+	}
+	spot := turnSpot()
+	beats, err := detector.Check(spot, settled(spot, card(13, domain.SuitDiamonds), 2))
+	if err != nil || len(beats) != 1 || beats[0].At.IsZero() {
+		t.Fatalf("expected one timestamped bad beat, got %+v, %v", beats, err)
+	}
+	if got := detector.BadBeats("table-1"); len(got) != 1 || got[0].HandID != "hand-1" {
+		t.Fatalf("expected the bad beat recorded for table-1, got %+v", got)
+	}
+	if got := detector.BadBeats("table-2"); len(got) != 0 {
+		t.Fatalf("expected no bad beats on table-2, got %+v", got)
+	}
+}
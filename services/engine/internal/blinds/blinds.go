@@ -0,0 +1,169 @@
+// Package blinds places the button and blinds for each hand. Assign uses a
+// moving button: it seats the blinds to the button's left, and NextButton
+// moves the button to the next seat still playing. Advance implements the
+// dead-button rule instead, where the big blind always moves one seat on so
+// nobody skips a big blind when a player busts, and the button or small
+// blind may fall on a seat that is no longer dealt in.
+package blinds
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var (
+	ErrNotEnoughPlayers = errors.New("blinds need at least two seats dealt in")
+	ErrUnknownSeat      = errors.New("seat is not at the table")
+)
+
+// Positions are a hand's button and blind seats. A dead button or small
+// blind sits on a seat that is not dealt in, and a dead small blind is not
+// posted. Heads-up the button posts the small blind and acts first
+// preflop.
+type Positions struct {
+	Button         domain.SeatNo `json:"button"`
+	SmallBlind     domain.SeatNo `json:"small_blind"`
+	BigBlind       domain.SeatNo `json:"big_blind"`
+	DeadButton     bool          `json:"dead_button,omitempty"`
+	DeadSmallBlind bool          `json:"dead_small_blind,omitempty"`
+}
+
+// DealtIn reports whether seat takes part in the next hand.
+func DealtIn(seat domain.SeatState) bool {
+	return seat.Status == domain.SeatStatusActive && !seat.Folded && seat.Stack > 0
+}
+
+// Assign places the blinds for a hand with the button on button. The small
+// and big blinds are the next two seats dealt in to its left, except
+// heads-up, where a button that is dealt in posts the small blind. A button
+// sitting out is marked dead.
+func Assign(seats []domain.SeatState, button domain.SeatNo) (Positions, error) {
+	dealt := dealtIn(seats)
+	if len(dealt) < 2 {
+		return Positions{}, fmt.Errorf("%w: got %d", ErrNotEnoughPlayers, len(dealt))
+	}
+	if !hasSeat(seats, button) {
+		return Positions{}, fmt.Errorf("%w: button seat %d", ErrUnknownSeat, button)
+	}
+	if len(dealt) == 2 && slices.Contains(dealt, button) {
+		return Positions{Button: button, SmallBlind: button, BigBlind: after(dealt, button)}, nil
+	}
+	sb := after(dealt, button)
+	return Positions{
+		Button:     button,
+		SmallBlind: sb,
+		BigBlind:   after(dealt, sb),
+		DeadButton: !slices.Contains(dealt, button),
+	}, nil
+}
+
+// NextButton moves a moving button clockwise from current to the next seat
+// that has chips and has not busted. Seats sitting out keep their turn on
+// the button.
+func NextButton(seats []domain.SeatState, current domain.SeatNo) (domain.SeatNo, error) {
+	var eligible []domain.SeatNo
+	for _, seat := range seats {
+		if seat.Status != domain.SeatStatusBusted && seat.Stack > 0 {
+			eligible = append(eligible, seat.SeatNo)
+		}
+	}
+	if len(eligible) == 0 {
+		return 0, fmt.Errorf("%w: got 0", ErrNotEnoughPlayers)
+	}
+	slices.Sort(eligible)
+	return after(eligible, current), nil
+}
+
+// Advance places the next hand's blinds under the dead-button rule: the big
+// blind moves to the next seat dealt in after the last big blind, the small
+// blind to the last big blind's seat and the button to the last small
+// blind's seat, either of which may now be dead. Once only two seats are
+// dealt in, the seat that is not the big blind takes the button and posts
+// the small blind.
+func Advance(prev Positions, seats []domain.SeatState) (Positions, error) {
+	dealt := dealtIn(seats)
+	if len(dealt) < 2 {
+		return Positions{}, fmt.Errorf("%w: got %d", ErrNotEnoughPlayers, len(dealt))
+	}
+	for _, seatNo := range []domain.SeatNo{prev.SmallBlind, prev.BigBlind} {
+		if !hasSeat(seats, seatNo) {
+			return Positions{}, fmt.Errorf("%w: seat %d", ErrUnknownSeat, seatNo)
+		}
+	}
+
+	bb := after(dealt, prev.BigBlind)
+	if len(dealt) == 2 {
+		button := after(dealt, bb)
+		return Positions{Button: button, SmallBlind: button, BigBlind: bb}, nil
+	}
+	return Positions{
+		Button:         prev.SmallBlind,
+		SmallBlind:     prev.BigBlind,
+		BigBlind:       bb,
+		DeadButton:     !slices.Contains(dealt, prev.SmallBlind),
+		DeadSmallBlind: !slices.Contains(dealt, prev.BigBlind),
+	}, nil
+}
+
+// MarkMissed records on each seat sitting out the blinds that pass it in a
+// hand with the button on button: whichever blind it would have posted had
+// it been dealt in, along with every other seat sitting out. It updates
+// seats in place.
+func MarkMissed(seats []domain.SeatState, button domain.SeatNo) {
+	var candidates []domain.SeatNo
+	for _, seat := range seats {
+		if DealtIn(seat) || sittingOut(seat) {
+			candidates = append(candidates, seat.SeatNo)
+		}
+	}
+	if len(candidates) < 2 {
+		return
+	}
+	slices.Sort(candidates)
+	sb := after(candidates, button)
+	bb := after(candidates, sb)
+	for i := range seats {
+		if !sittingOut(seats[i]) {
+			continue
+		}
+		switch seats[i].SeatNo {
+		case sb:
+			seats[i].MissedSmallBlind = true
+		case bb:
+			seats[i].MissedBigBlind = true
+		}
+	}
+}
+
+func sittingOut(seat domain.SeatState) bool {
+	return seat.Status == domain.SeatStatusSittingOut && seat.Stack > 0
+}
+
+func dealtIn(seats []domain.SeatState) []domain.SeatNo {
+	var dealt []domain.SeatNo
+	for _, seat := range seats {
+		if DealtIn(seat) {
+			dealt = append(dealt, seat.SeatNo)
+		}
+	}
+	slices.Sort(dealt)
+	return dealt
+}
+
+func hasSeat(seats []domain.SeatState, seatNo domain.SeatNo) bool {
+	return slices.ContainsFunc(seats, func(seat domain.SeatState) bool { return seat.SeatNo == seatNo })
+}
+
+// after is the first of seats, which are in seat order, clockwise after
+// seatNo, which need not be among them.
+func after(seats []domain.SeatNo, seatNo domain.SeatNo) domain.SeatNo {
+	for _, candidate := range seats {
+		if candidate > seatNo {
+			return candidate
+		}
+	}
+	return seats[0]
+}
@@ -0,0 +1,141 @@
+package blinds
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func seats(seatNumbers ...domain.SeatNo) []domain.SeatState {
+	out := make([]domain.SeatState, 0, len(seatNumbers))
+	for _, seatNo := range seatNumbers {
+		out = append(out, domain.NewSeatState(seatNo, 100))
+	}
+	return out
+}
+
+func bust(seats []domain.SeatState, seatNo domain.SeatNo) {
+	for i := range seats {
+		if seats[i].SeatNo == seatNo {
+			seats[i].Stack = 0
+			seats[i].Status = domain.SeatStatusBusted
+		}
+	}
+}
+
+func TestAssign_PlacesBlindsLeftOfTheButton(t *testing.T) {
+	t.Parallel()
+
+	got, err := Assign(seats(1, 2, 3), 3)
+	if err != nil {
+		t.Fatalf("Assign failed: %v", err)
+	}
+	if want := (Positions{Button: 3, SmallBlind: 1, BigBlind: 2}); got != want {
+		t.Fatalf("expected %+v, got %+v", want, got)
+	}
+
+	headsUp, err := Assign(seats(2, 5), 5)
+	if err != nil {
+		t.Fatalf("Assign heads-up failed: %v", err)
+	}
+	if want := (Positions{Button: 5, SmallBlind: 5, BigBlind: 2}); headsUp != want {
+		t.Fatalf("expected the button to post the small blind heads-up, got %+v", headsUp)
+	}
+
+	sittingOut := seats(1, 2, 3, 4)
+	sittingOut[0].Status = domain.SeatStatusSittingOut
+	dead, err := Assign(sittingOut, 1)
+	if err != nil {
+		t.Fatalf("Assign with the button sitting out failed: %v", err)
+	}
+	if want := (Positions{Button: 1, SmallBlind: 2, BigBlind: 3, DeadButton: true}); dead != want {
+		t.Fatalf("expected %+v, got %+v", want, dead)
+	}
+
+	if _, err := Assign(seats(1, 2), 4); !errors.Is(err, ErrUnknownSeat) {
+		t.Fatalf("expected ErrUnknownSeat, got %v", err)
+	}
+	lone := seats(1, 2)
+	bust(lone, 2)
+	if _, err := Assign(lone, 1); !errors.Is(err, ErrNotEnoughPlayers) {
+		t.Fatalf("expected ErrNotEnoughPlayers, got %v", err)
+	}
+}
+
+func TestNextButton_SkipsBustedSeats(t *testing.T) {
+	t.Parallel()
+
+	table := seats(1, 2, 3)
+	bust(table, 2)
+	if got, err := NextButton(table, 1); err != nil || got != 3 {
+		t.Fatalf("expected the button to skip busted seat 2, got %d, %v", got, err)
+	}
+	if got, err := NextButton(table, 3); err != nil || got != 1 {
+		t.Fatalf("expected the button to wrap to seat 1, got %d, %v", got, err)
+	}
+}
+
+func TestAdvance_KeepsTheBigBlindMovingWhenAPlayerBusts(t *testing.T) {
+	t.Parallel()
+
+	prev := Positions{Button: 1, SmallBlind: 2, BigBlind: 3}
+
+	table := seats(1, 2, 3, 4)
+	if got, err := Advance(prev, table); err != nil || got != (Positions{Button: 2, SmallBlind: 3, BigBlind: 4}) {
+		t.Fatalf("expected every position to move one seat, got %+v, %v", got, err)
+	}
+
+	bust(table, 2)
+	got, err := Advance(prev, table)
+	if err != nil {
+		t.Fatalf("Advance failed: %v", err)
+	}
+	if want := (Positions{Button: 2, SmallBlind: 3, BigBlind: 4, DeadButton: true}); got != want {
+		t.Fatalf("expected a dead button on busted seat 2, got %+v", got)
+	}
+
+	table = seats(1, 2, 3, 4)
+	bust(table, 3)
+	got, err = Advance(prev, table)
+	if err != nil {
+		t.Fatalf("Advance failed: %v", err)
+	}
+	if want := (Positions{Button: 2, SmallBlind: 3, BigBlind: 4, DeadSmallBlind: true}); got != want {
+		t.Fatalf("expected a dead small blind on busted seat 3, got %+v", got)
+	}
+}
+
+func TestAdvance_SwitchesToHeadsUpButtonRules(t *testing.T) {
+	t.Parallel()
+
+	table := seats(1, 2, 3)
+	bust(table, 1)
+	got, err := Advance(Positions{Button: 1, SmallBlind: 2, BigBlind: 3}, table)
+	if err != nil {
+		t.Fatalf("Advance failed: %v", err)
+	}
+	if want := (Positions{Button: 3, SmallBlind: 3, BigBlind: 2}); got != want {
+		t.Fatalf("expected the last big blind to take the button heads-up, got %+v", got)
+	}
+}
+
+func TestMarkMissed_RecordsTheBlindsThatPassASeatSittingOut(t *testing.T) {
+	t.Parallel()
+
+	table := seats(1, 2, 3, 4)
+	table[2].Status = domain.SeatStatusSittingOut
+	MarkMissed(table, 1)
+	if !table[2].MissedBigBlind || table[2].MissedSmallBlind {
+		t.Fatalf("expected seat 3 to miss the big blind, got %+v", table[2])
+	}
+	MarkMissed(table, 2)
+	if !table[2].MissedSmallBlind {
+		t.Fatalf("expected seat 3 to miss the small blind next, got %+v", table[2])
+	}
+	for _, seat := range []domain.SeatState{table[0], table[1], table[3]} {
+		if seat.OwesBlinds() {
+			t.Fatalf("expected seats dealt in to owe nothing, got %+v", seat)
+		}
+	}
+}
@@ -0,0 +1,127 @@
+// Package cashier refills bot stacks between hands from a bankroll, following
+// the buy-in policy each bot registers, so long unattended sessions keep the
+// stack depths the bots were built for.
+package cashier
+
+import (
+	"errors"
+	"fmt"
+	"math"
+	"sync"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var ErrInvalidPolicy = errors.New("invalid buy-in policy")
+
+// Policy is expressed in big blinds so it survives blind changes. The cashier
+// refills a seat to BuyInBB whenever its stack falls below TopUpBelowBB; with
+// TopUpBelowBB unset it only rebuys a seat that has busted. Bankroll is all
+// the cashier may ever add for the seat.
+type Policy struct {
+	BuyInBB      uint32       `json:"buy_in_bb"`
+	TopUpBelowBB uint32       `json:"top_up_below_bb,omitempty"`
+	Bankroll     domain.Chips `json:"bankroll"`
+}
+
+func (p Policy) Validate() error {
+	if p.BuyInBB == 0 {
+		return fmt.Errorf("%w: buy_in_bb must be greater than zero", ErrInvalidPolicy)
+	}
+	if p.TopUpBelowBB > p.BuyInBB {
+		return fmt.Errorf("%w: top_up_below_bb %d exceeds buy_in_bb %d", ErrInvalidPolicy, p.TopUpBelowBB, p.BuyInBB)
+	}
+	return nil
+}
+
+// TopUp records chips added to a seat after HandNo. Bankroll is what the seat
+// has left afterwards.
+type TopUp struct {
+	HandNo   uint64        `json:"hand_no"`
+	SeatNo   domain.SeatNo `json:"seat_no"`
+	Amount   domain.Chips  `json:"amount"`
+	Bankroll domain.Chips  `json:"bankroll"`
+}
+
+// Cashier holds the policies and remaining bankrolls for one table run. It is
+// safe for concurrent use.
+type Cashier struct {
+	mu       sync.Mutex
+	policies map[domain.SeatNo]Policy
+	bankroll map[domain.SeatNo]domain.Chips
+	topUps   []TopUp
+}
+
+func New() *Cashier {
+	return &Cashier{
+		policies: make(map[domain.SeatNo]Policy),
+		bankroll: make(map[domain.SeatNo]domain.Chips),
+	}
+}
+
+// Register sets a seat's policy and resets its bankroll to the policy's.
+func (c *Cashier) Register(seatNo domain.SeatNo, policy Policy) error {
+	if err := policy.Validate(); err != nil {
+		return err
+	}
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.policies[seatNo] = policy
+	c.bankroll[seatNo] = policy.Bankroll
+	return nil
+}
+
+// TopUp returns the chips to add to seat before the hand after handNo and
+// debits them from its bankroll. Seats without a policy, seats sitting out
+// and seats already deep enough get nothing. It matches
+// tablerunner.RunnerConfig.TopUp.
+func (c *Cashier) TopUp(handNo uint64, seat domain.SeatState, config domain.TableConfig) domain.Chips {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	policy, ok := c.policies[seat.SeatNo]
+	if !ok || seat.Status == domain.SeatStatusSittingOut {
+		return 0
+	}
+	stack := seat.Stack
+	target := bigBlinds(policy.BuyInBB, config.BigBlind)
+	threshold := bigBlinds(policy.TopUpBelowBB, config.BigBlind)
+	if stack >= target || (stack > 0 && stack >= threshold) {
+		return 0
+	}
+	amount := min(target-stack, c.bankroll[seat.SeatNo])
+	if amount == 0 {
+		return 0
+	}
+	c.bankroll[seat.SeatNo] -= amount
+	c.topUps = append(c.topUps, TopUp{
+		HandNo:   handNo,
+		SeatNo:   seat.SeatNo,
+		Amount:   amount,
+		Bankroll: c.bankroll[seat.SeatNo],
+	})
+	return amount
+}
+
+// bigBlinds is n big blinds in chips, or the most chips there can be when
+// that overflows.
+func bigBlinds(n uint32, bigBlind domain.Chips) domain.Chips {
+	if bigBlind != 0 && domain.Chips(n) > math.MaxUint64/bigBlind {
+		return math.MaxUint64
+	}
+	return domain.Chips(n) * bigBlind
+}
+
+// Bankroll is what the cashier may still add for a seat.
+func (c *Cashier) Bankroll(seatNo domain.SeatNo) domain.Chips {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return c.bankroll[seatNo]
+}
+
+// TopUps lists every top-up so far, oldest first.
+func (c *Cashier) TopUps() []TopUp {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return append([]TopUp(nil), c.topUps...)
+}
@@ -0,0 +1,75 @@
+package cashier
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestCashier_TopsUpBelowThresholdWithinBankroll(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.SmallBlind, cfg.BigBlind = 50, 100
+	cashier := New()
+	if err := cashier.Register(1, Policy{BuyInBB: 100, TopUpBelowBB: 60, Bankroll: 7_000}); err != nil {
+		t.Fatalf("Register failed: %v", err)
+	}
+	if err := cashier.Register(2, Policy{BuyInBB: 100, Bankroll: 20_000}); err != nil {
+		t.Fatalf("Register failed: %v", err)
+	}
+	seat := func(seatNo domain.SeatNo, stack domain.Chips) domain.SeatState {
+		return domain.NewSeatState(seatNo, stack)
+	}
+
+	if got := cashier.TopUp(1, seat(1, 6_000), cfg); got != 0 {
+		t.Fatalf("expected no top-up at 60bb, got %d", got)
+	}
+	if got := cashier.TopUp(1, seat(1, 5_900), cfg); got != 4_100 {
+		t.Fatalf("expected a refill to 100bb, got %d", got)
+	}
+	if got := cashier.TopUp(2, seat(1, 0), cfg); got != 2_900 {
+		t.Fatalf("expected the rest of the bankroll, got %d", got)
+	}
+	if got := cashier.TopUp(3, seat(1, 0), cfg); got != 0 || cashier.Bankroll(1) != 0 {
+		t.Fatalf("expected an empty bankroll to stop top-ups, got %d with %d left", got, cashier.Bankroll(1))
+	}
+
+	// Without a threshold the seat is only rebought once it busts.
+	if got := cashier.TopUp(3, seat(2, 100), cfg); got != 0 {
+		t.Fatalf("expected no top-up before busting, got %d", got)
+	}
+	if got := cashier.TopUp(4, seat(2, 0), cfg); got != 10_000 {
+		t.Fatalf("expected a full rebuy, got %d", got)
+	}
+	sittingOut := seat(2, 0)
+	sittingOut.Status = domain.SeatStatusSittingOut
+	if got := cashier.TopUp(5, sittingOut, cfg); got != 0 {
+		t.Fatalf("expected no top-up while sitting out, got %d", got)
+	}
+	if got := cashier.TopUp(5, seat(3, 0), cfg); got != 0 {
+		t.Fatalf("expected no top-up without a policy, got %d", got)
+	}
+
+	topUps := cashier.TopUps()
+	if len(topUps) != 3 {
+		t.Fatalf("expected 3 top-ups, got %+v", topUps)
+	}
+	if topUps[1] != (TopUp{HandNo: 2, SeatNo: 1, Amount: 2_900, Bankroll: 0}) {
+		t.Fatalf("unexpected top-up %+v", topUps[1])
+	}
+}
+
+func TestPolicy_Validate(t *testing.T) {
+	t.Parallel()
+
+	for _, policy := range []Policy{{}, {BuyInBB: 50, TopUpBelowBB: 60}} {
+		if err := policy.Validate(); !errors.Is(err, ErrInvalidPolicy) {
+			t.Fatalf("expected ErrInvalidPolicy for %+v, got %v", policy, err)
+		}
+	}
+	if err := New().Register(1, Policy{BuyInBB: 100, TopUpBelowBB: 100}); err != nil {
+		t.Fatalf("Register failed: %v", err)
+	}
+}
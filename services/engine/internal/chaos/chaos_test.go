@@ -0,0 +1,169 @@
+package chaos
+
+import (
+	"context"
+	"errors"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"strings"
+	"sync"
+	"testing"
+	"time"
+)
+
+func TestInjector_SameSeedSameFaults(t *testing.T) {
+	t.Parallel()
+
+	schedule := Schedule{
+		Seed:          99,
+		DropRate:      0.2,
+		DelayRate:     0.2,
+		DuplicateRate: 0.2,
+		ReorderRate:   0.2,
+		MaxDelay:      time.Millisecond,
+	}
+	first := mustInjector(t, schedule)
+	second := mustInjector(t, schedule)
+	seen := map[FaultKind]bool{}
+	for i := 0; i < 200; i++ {
+		fault := first.Next()
+		second.Next()
+		seen[fault.Kind] = true
+		if fault.Kind == FaultDelay && (fault.Delay < 0 || fault.Delay > schedule.MaxDelay) {
+			t.Fatalf("delay %v outside 0..=%v", fault.Delay, schedule.MaxDelay)
+		}
+	}
+	if !reflect.DeepEqual(first.Log(), second.Log()) {
+		t.Fatal("expected identical fault logs for the same seed")
+	}
+	for _, kind := range []FaultKind{FaultNone, FaultDrop, FaultDelay, FaultDuplicate, FaultReorder} {
+		if !seen[kind] {
+			t.Fatalf("expected %s to be drawn in 200 messages", kind)
+		}
+	}
+}
+
+func TestSchedule_Validate(t *testing.T) {
+	t.Parallel()
+
+	for _, schedule := range []Schedule{
+		{DropRate: -0.1},
+		{DropRate: 0.6, DuplicateRate: 0.6},
+		{DelayRate: 0.5},
+	} {
+		if _, err := NewInjector(schedule); !errors.Is(err, ErrInvalidSchedule) {
+			t.Fatalf("expected ErrInvalidSchedule for %+v, got %v", schedule, err)
+		}
+	}
+}
+
+func TestTransport_AppliesFaults(t *testing.T) {
+	t.Parallel()
+
+	var mu sync.Mutex
+	var received []string
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		body, _ := io.ReadAll(r.Body)
+		mu.Lock()
+		received = append(received, string(body))
+		mu.Unlock()
+		_, _ = w.Write(body)
+	}))
+	defer server.Close()
+
+	cases := []struct {
+		name string
+		rate func(*Schedule)
+		want []string
+		err  error
+	}{
+		{name: "drop", rate: func(s *Schedule) { s.DropRate = 1 }, want: nil, err: ErrDropped},
+		{name: "duplicate", rate: func(s *Schedule) { s.DuplicateRate = 1 }, want: []string{"m1", "m1", "m2", "m2"}},
+		{name: "reorder", rate: func(s *Schedule) { s.ReorderRate = 1 }, want: []string{"m1", "m2", "m1"}},
+		{name: "delay", rate: func(s *Schedule) { s.DelayRate, s.MaxDelay = 1, time.Millisecond }, want: []string{"m1", "m2"}},
+	}
+	for _, tc := range cases {
+		mu.Lock()
+		received = nil
+		mu.Unlock()
+
+		schedule := Schedule{Seed: 1}
+		tc.rate(&schedule)
+		client := &http.Client{Transport: NewTransport(nil, mustInjector(t, schedule))}
+		for _, msg := range []string{"m1", "m2"} {
+			resp, err := client.Post(server.URL, "text/plain", strings.NewReader(msg))
+			if tc.err != nil {
+				if !errors.Is(err, tc.err) {
+					t.Fatalf("%s: expected %v, got %v", tc.name, tc.err, err)
+				}
+				continue
+			}
+			if err != nil {
+				t.Fatalf("%s: Post failed: %v", tc.name, err)
+			}
+			body, _ := io.ReadAll(resp.Body)
+			resp.Body.Close()
+			if string(body) != msg {
+				t.Fatalf("%s: expected response %q, got %q", tc.name, msg, body)
+			}
+		}
+
+		mu.Lock()
+		got := append([]string(nil), received...)
+		mu.Unlock()
+		if !reflect.DeepEqual(got, tc.want) {
+			t.Fatalf("%s: expected server to see %v, got %v", tc.name, tc.want, got)
+		}
+	}
+}
+
+func TestFrameWriter_DropsDuplicatesAndReorders(t *testing.T) {
+	t.Parallel()
+
+	cases := []struct {
+		name string
+		rate func(*Schedule)
+		want []string
+	}{
+		{name: "drop", rate: func(s *Schedule) { s.DropRate = 1 }, want: nil},
+		{name: "duplicate", rate: func(s *Schedule) { s.DuplicateRate = 1 }, want: []string{"f1", "f1", "f2", "f2", "f3", "f3"}},
+		{name: "reorder", rate: func(s *Schedule) { s.ReorderRate = 1 }, want: []string{"f2", "f1", "f3"}},
+	}
+	for _, tc := range cases {
+		schedule := Schedule{Seed: 1}
+		tc.rate(&schedule)
+		sink := &recordingSink{}
+		writer := NewFrameWriter(sink, mustInjector(t, schedule))
+		for _, frame := range []string{"f1", "f2", "f3"} {
+			if err := writer.WriteFrame(context.Background(), []byte(frame)); err != nil {
+				t.Fatalf("%s: WriteFrame failed: %v", tc.name, err)
+			}
+		}
+		if err := writer.Flush(context.Background()); err != nil {
+			t.Fatalf("%s: Flush failed: %v", tc.name, err)
+		}
+		if !reflect.DeepEqual(sink.frames, tc.want) {
+			t.Fatalf("%s: expected %v, got %v", tc.name, tc.want, sink.frames)
+		}
+	}
+}
+
+type recordingSink struct {
+	frames []string
+}
+
+func (s *recordingSink) WriteFrame(_ context.Context, frame []byte) error {
+	s.frames = append(s.frames, string(frame))
+	return nil
+}
+
+func mustInjector(t *testing.T, schedule Schedule) *Injector {
+	t.Helper()
+	injector, err := NewInjector(schedule)
+	if err != nil {
+		t.Fatalf("NewInjector failed: %v", err)
+	}
+	return injector
+}
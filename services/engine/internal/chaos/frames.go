@@ -0,0 +1,71 @@
+package chaos
+
+import (
+	"context"
+	"sync"
+)
+
+// FrameSink is the write side of a framed connection such as a WebSocket.
+type FrameSink interface {
+	WriteFrame(ctx context.Context, frame []byte) error
+}
+
+// FrameWriter applies injector faults to outbound frames. Dropped frames are
+// lost silently, as on a real network. A reordered frame is held back and
+// written after the next frame; Flush releases a frame still held when the
+// stream ends.
+type FrameWriter struct {
+	sink     FrameSink
+	injector *Injector
+
+	mu   sync.Mutex
+	held []byte
+}
+
+func NewFrameWriter(sink FrameSink, injector *Injector) *FrameWriter {
+	return &FrameWriter{sink: sink, injector: injector}
+}
+
+func (w *FrameWriter) WriteFrame(ctx context.Context, frame []byte) error {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+
+	fault := w.injector.Next()
+	switch fault.Kind {
+	case FaultDrop:
+		return nil
+	case FaultDelay:
+		if err := sleep(ctx, fault.Delay); err != nil {
+			return err
+		}
+	case FaultDuplicate:
+		if err := w.sink.WriteFrame(ctx, frame); err != nil {
+			return err
+		}
+	case FaultReorder:
+		if w.held == nil {
+			w.held = append([]byte(nil), frame...)
+			return nil
+		}
+	}
+
+	if err := w.sink.WriteFrame(ctx, frame); err != nil {
+		return err
+	}
+	return w.flushLocked(ctx)
+}
+
+func (w *FrameWriter) Flush(ctx context.Context) error {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	return w.flushLocked(ctx)
+}
+
+func (w *FrameWriter) flushLocked(ctx context.Context) error {
+	if w.held == nil {
+		return nil
+	}
+	held := w.held
+	w.held = nil
+	return w.sink.WriteFrame(ctx, held)
+}
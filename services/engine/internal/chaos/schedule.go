@@ -0,0 +1,124 @@
+// Package chaos injects transport faults for tests. Faults are drawn from a
+// seeded schedule so a failing run can be reproduced from its seed.
+package chaos
+
+import (
+	"errors"
+	"fmt"
+	"math/rand"
+	"sync"
+	"time"
+)
+
+var (
+	ErrInvalidSchedule = errors.New("invalid chaos schedule")
+	ErrDropped         = errors.New("chaos: message dropped")
+)
+
+type FaultKind string
+
+const (
+	FaultNone      FaultKind = "none"
+	FaultDrop      FaultKind = "drop"
+	FaultDelay     FaultKind = "delay"
+	FaultDuplicate FaultKind = "duplicate"
+	FaultReorder   FaultKind = "reorder"
+)
+
+// Schedule sets the probability of each fault per message. At most one fault
+// applies to a message; rates must sum to at most 1.
+type Schedule struct {
+	Seed          int64         `json:"seed"`
+	DropRate      float64       `json:"drop_rate"`
+	DelayRate     float64       `json:"delay_rate"`
+	DuplicateRate float64       `json:"duplicate_rate"`
+	ReorderRate   float64       `json:"reorder_rate"`
+	MaxDelay      time.Duration `json:"max_delay"`
+}
+
+func (s Schedule) Validate() error {
+	rates := []struct {
+		name string
+		rate float64
+	}{
+		{"drop_rate", s.DropRate},
+		{"delay_rate", s.DelayRate},
+		{"duplicate_rate", s.DuplicateRate},
+		{"reorder_rate", s.ReorderRate},
+	}
+	total := 0.0
+	for _, r := range rates {
+		if r.rate < 0 || r.rate > 1 {
+			return fmt.Errorf("%w: %s must be in range 0..=1, got %v", ErrInvalidSchedule, r.name, r.rate)
+		}
+		total += r.rate
+	}
+	if total > 1 {
+		return fmt.Errorf("%w: fault rates sum to %v", ErrInvalidSchedule, total)
+	}
+	if s.DelayRate > 0 && s.MaxDelay <= 0 {
+		return fmt.Errorf("%w: max_delay is required when delay_rate is set", ErrInvalidSchedule)
+	}
+	return nil
+}
+
+// Fault is the decision for one message.
+type Fault struct {
+	Seq   uint64        `json:"seq"`
+	Kind  FaultKind     `json:"kind"`
+	Delay time.Duration `json:"delay,omitempty"`
+}
+
+// Injector hands out faults in a fixed order for a given schedule and keeps
+// a log of every decision.
+type Injector struct {
+	mu       sync.Mutex
+	schedule Schedule
+	rng      *rand.Rand
+	seq      uint64
+	log      []Fault
+}
+
+func NewInjector(schedule Schedule) (*Injector, error) {
+	if err := schedule.Validate(); err != nil {
+		return nil, err
+	}
+	return &Injector{
+		schedule: schedule,
+		rng:      rand.New(rand.NewSource(schedule.Seed)),
+	}, nil
+}
+
+// Next draws the fault for the next message. Every message consumes exactly
+// two random draws, so the nth decision depends only on the schedule.
+func (i *Injector) Next() Fault {
+	i.mu.Lock()
+	defer i.mu.Unlock()
+
+	roll := i.rng.Float64()
+	jitter := i.rng.Float64()
+	i.seq++
+	fault := Fault{Seq: i.seq, Kind: FaultNone}
+
+	s := i.schedule
+	switch {
+	case roll < s.DropRate:
+		fault.Kind = FaultDrop
+	case roll < s.DropRate+s.DelayRate:
+		fault.Kind = FaultDelay
+		fault.Delay = time.Duration(jitter * float64(s.MaxDelay))
+	case roll < s.DropRate+s.DelayRate+s.DuplicateRate:
+		fault.Kind = FaultDuplicate
+	case roll < s.DropRate+s.DelayRate+s.DuplicateRate+s.ReorderRate:
+		fault.Kind = FaultReorder
+	}
+	i.log = append(i.log, fault)
+	return fault
+}
+
+// Log returns every fault drawn so far, in order.
+func (i *Injector) Log() []Fault {
+	i.mu.Lock()
+	defer i.mu.Unlock()
+	return append([]Fault(nil), i.log...)
+}
@@ -0,0 +1,116 @@
+package chaos
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"sync"
+	"time"
+)
+
+// Transport is an http.RoundTripper that applies injector faults to bot
+// protocol requests:
+//   - drop fails the request before it is sent;
+//   - delay holds the request for the drawn delay;
+//   - duplicate delivers the request twice and returns the second response;
+//   - reorder re-delivers the previous request after the current one, as a
+//     late arrival of an older message.
+type Transport struct {
+	base     http.RoundTripper
+	injector *Injector
+
+	mu       sync.Mutex
+	previous *bufferedRequest
+}
+
+type bufferedRequest struct {
+	req  *http.Request
+	body []byte
+}
+
+func NewTransport(base http.RoundTripper, injector *Injector) *Transport {
+	if base == nil {
+		base = http.DefaultTransport
+	}
+	return &Transport{base: base, injector: injector}
+}
+
+func (t *Transport) RoundTrip(req *http.Request) (*http.Response, error) {
+	current, err := bufferRequest(req)
+	if err != nil {
+		return nil, err
+	}
+	fault := t.injector.Next()
+
+	t.mu.Lock()
+	previous := t.previous
+	t.previous = current
+	t.mu.Unlock()
+
+	switch fault.Kind {
+	case FaultDrop:
+		return nil, fmt.Errorf("%w: request %d to %s", ErrDropped, fault.Seq, req.URL)
+	case FaultDelay:
+		if err := sleep(req.Context(), fault.Delay); err != nil {
+			return nil, err
+		}
+	case FaultDuplicate:
+		if err := t.deliverAndDiscard(req.Context(), current); err != nil {
+			return nil, err
+		}
+	}
+
+	resp, err := t.base.RoundTrip(current.clone(req.Context()))
+	if err != nil {
+		return nil, err
+	}
+	if fault.Kind == FaultReorder && previous != nil {
+		if err := t.deliverAndDiscard(req.Context(), previous); err != nil {
+			resp.Body.Close()
+			return nil, err
+		}
+	}
+	return resp, nil
+}
+
+func (t *Transport) deliverAndDiscard(ctx context.Context, buffered *bufferedRequest) error {
+	resp, err := t.base.RoundTrip(buffered.clone(ctx))
+	if err != nil {
+		return err
+	}
+	_, _ = io.Copy(io.Discard, resp.Body)
+	return resp.Body.Close()
+}
+
+func bufferRequest(req *http.Request) (*bufferedRequest, error) {
+	var body []byte
+	if req.Body != nil {
+		var err error
+		body, err = io.ReadAll(req.Body)
+		if err != nil {
+			return nil, err
+		}
+		_ = req.Body.Close()
+	}
+	return &bufferedRequest{req: req, body: body}, nil
+}
+
+func (b *bufferedRequest) clone(ctx context.Context) *http.Request {
+	out := b.req.Clone(ctx)
+	out.Body = io.NopCloser(bytes.NewReader(b.body))
+	out.ContentLength = int64(len(b.body))
+	return out
+}
+
+func sleep(ctx context.Context, d time.Duration) error {
+	timer := time.NewTimer(d)
+	defer timer.Stop()
+	select {
+	case <-ctx.Done():
+		return ctx.Err()
+	case <-timer.C:
+		return nil
+	}
+}
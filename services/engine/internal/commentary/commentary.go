@@ -0,0 +1,161 @@
+// Package commentary turns finished hands into dealer lines such as
+// "Seat 2 wins 4,350 with a flush, nine high" for broadcast overlays and
+// chat logs. Lines only use what the table saw: winners' shown hands, never
+// mucked cards.
+package commentary
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+// Line is one pot's result. Pot is the award reason, such as "main_pot",
+// "side_pot_1" or "uncontested".
+type Line struct {
+	HandID string          `json:"hand_id"`
+	HandNo uint64          `json:"hand_no"`
+	Pot    string          `json:"pot"`
+	Seats  []domain.SeatNo `json:"seats"`
+	Amount domain.Chips    `json:"amount"`
+	Text   string          `json:"text"`
+}
+
+var rankNames = map[uint8][2]string{
+	2:  {"two", "twos"},
+	3:  {"three", "threes"},
+	4:  {"four", "fours"},
+	5:  {"five", "fives"},
+	6:  {"six", "sixes"},
+	7:  {"seven", "sevens"},
+	8:  {"eight", "eights"},
+	9:  {"nine", "nines"},
+	10: {"ten", "tens"},
+	11: {"jack", "jacks"},
+	12: {"queen", "queens"},
+	13: {"king", "kings"},
+	14: {"ace", "aces"},
+}
+
+// Narrate returns a line per pot awarded in a completed hand, in award
+// order. Hands that are not complete have nothing to say yet.
+func Narrate(state domain.HandState) []Line {
+	if state.Phase != domain.HandPhaseComplete {
+		return nil
+	}
+	lines := make([]Line, 0, len(state.ShowdownAwards))
+	for _, award := range state.ShowdownAwards {
+		lines = append(lines, Line{
+			HandID: state.HandID,
+			HandNo: state.HandNo,
+			Pot:    award.Reason,
+			Seats:  append([]domain.SeatNo(nil), award.Seats...),
+			Amount: award.Amount,
+			Text:   awardText(state, award),
+		})
+	}
+	return lines
+}
+
+func awardText(state domain.HandState, award domain.PotAward) string {
+	var text strings.Builder
+	text.WriteString(seatList(award.Seats))
+	if len(award.Seats) > 1 {
+		text.WriteString(" split ")
+	} else {
+		text.WriteString(" wins ")
+	}
+	text.WriteString(FormatChips(award.Amount))
+	if sidePot, ok := strings.CutPrefix(award.Reason, "side_pot_"); ok {
+		text.WriteString(" from side pot " + sidePot)
+	}
+	if award.Reason == "uncontested" {
+		text.WriteString(" uncontested")
+		return text.String()
+	}
+	if hand, ok := shownHand(state, award.Seats); ok {
+		text.WriteString(" with " + DescribeHand(hand))
+	}
+	return text.String()
+}
+
+// shownHand is the winners' hand when it can be read off the board. Split
+// pot winners hold hands of equal rank, so the first winner's stands for all.
+func shownHand(state domain.HandState, winners []domain.SeatNo) (rules.HandRank, bool) {
+	if len(state.Board) != 5 || len(winners) == 0 {
+		return rules.HandRank{}, false
+	}
+	for _, seatCards := range state.HoleCards {
+		if seatCards.SeatNo == winners[0] && len(seatCards.Cards) == 2 {
+			return rules.EvaluateBestHand(seatCards.Cards, state.Board), true
+		}
+	}
+	return rules.HandRank{}, false
+}
+
+// DescribeHand names a hand the way a dealer calls it, for example "two
+// pair, kings and fours" or "a full house, sevens full of twos".
+func DescribeHand(hand rules.HandRank) string {
+	if len(hand.Tiebreak) == 0 {
+		return hand.Category.String()
+	}
+	rank := func(i int) string { return rankNames[hand.Tiebreak[i]][0] }
+	ranks := func(i int) string { return rankNames[hand.Tiebreak[i]][1] }
+	switch hand.Category {
+	case rules.HandCategoryHighCard:
+		return rank(0) + " high"
+	case rules.HandCategoryOnePair:
+		return "a pair of " + ranks(0)
+	case rules.HandCategoryTwoPair:
+		return "two pair, " + ranks(0) + " and " + ranks(1)
+	case rules.HandCategoryThreeOfAKind:
+		return "three of a kind, " + ranks(0)
+	case rules.HandCategoryStraight:
+		return "a straight, " + rank(0) + " high"
+	case rules.HandCategoryFlush:
+		return "a flush, " + rank(0) + " high"
+	case rules.HandCategoryFullHouse:
+		return "a full house, " + ranks(0) + " full of " + ranks(1)
+	case rules.HandCategoryFourOfAKind:
+		return "four of a kind, " + ranks(0)
+	case rules.HandCategoryStraightFlush:
+		if hand.Tiebreak[0] == 14 {
+			return "a royal flush"
+		}
+		return "a straight flush, " + rank(0) + " high"
+	case rules.HandCategoryFiveOfAKind:
+		return "five of a kind, " + ranks(0)
+	default:
+		return hand.Category.String()
+	}
+}
+
+// FormatChips groups thousands with commas: 4350 is "4,350".
+func FormatChips(amount domain.Chips) string {
+	digits := strconv.FormatUint(uint64(amount), 10)
+	var out strings.Builder
+	for i, digit := range digits {
+		if i > 0 && (len(digits)-i)%3 == 0 {
+			out.WriteByte(',')
+		}
+		out.WriteRune(digit)
+	}
+	return out.String()
+}
+
+func seatList(seats []domain.SeatNo) string {
+	switch len(seats) {
+	case 0:
+		return "Nobody"
+	case 1:
+		return fmt.Sprintf("Seat %d", seats[0])
+	}
+	names := make([]string, len(seats))
+	for i, seat := range seats {
+		names[i] = strconv.Itoa(int(seat))
+	}
+	return "Seats " + strings.Join(names[:len(names)-1], ", ") + " and " + names[len(names)-1]
+}
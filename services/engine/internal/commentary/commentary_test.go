@@ -0,0 +1,90 @@
+package commentary
+
+import (
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+func card(rank domain.Rank, suit domain.Suit) domain.Card {
+	return domain.NewCard(rank, suit)
+}
+
+func TestNarrate_DescribesEachPot(t *testing.T) {
+	t.Parallel()
+
+	state := domain.HandState{
+		HandID: "hand-7",
+		HandNo: 7,
+		Phase:  domain.HandPhaseComplete,
+		Board: []domain.Card{
+			card(9, domain.SuitHearts), card(7, domain.SuitHearts), card(4, domain.SuitHearts),
+			card(2, domain.SuitClubs), card(13, domain.SuitDiamonds),
+		},
+		HoleCards: []domain.SeatCards{
+			{SeatNo: 1, Cards: []domain.Card{card(13, domain.SuitSpades), card(13, domain.SuitClubs)}},
+			{SeatNo: 2, Cards: []domain.Card{card(3, domain.SuitHearts), card(5, domain.SuitHearts)}},
+			{SeatNo: 3, Cards: []domain.Card{card(13, domain.SuitHearts), card(2, domain.SuitSpades)}},
+		},
+		ShowdownAwards: []domain.PotAward{
+			{Amount: 4350, Seats: []domain.SeatNo{2}, Reason: "main_pot"},
+			{Amount: 1200, Seats: []domain.SeatNo{1, 3}, Reason: "side_pot_1"},
+		},
+	}
+
+	lines := Narrate(state)
+	want := []string{
+		"Seat 2 wins 4,350 with a flush, nine high",
+		"Seats 1 and 3 split 1,200 from side pot 1 with three of a kind, kings",
+	}
+	if len(lines) != len(want) {
+		t.Fatalf("expected %d lines, got %+v", len(want), lines)
+	}
+	for i, line := range lines {
+		if line.Text != want[i] || line.HandID != "hand-7" || line.HandNo != 7 {
+			t.Fatalf("line %d: expected %q, got %+v", i, want[i], line)
+		}
+	}
+
+	state.Phase = domain.HandPhaseShowdown
+	if lines := Narrate(state); lines != nil {
+		t.Fatalf("expected no lines before the hand completes, got %+v", lines)
+	}
+
+	uncontested := domain.HandState{
+		Phase:          domain.HandPhaseComplete,
+		ShowdownAwards: []domain.PotAward{{Amount: 150, Seats: []domain.SeatNo{4}, Reason: "uncontested"}},
+	}
+	if got := Narrate(uncontested)[0].Text; got != "Seat 4 wins 150 uncontested" {
+		t.Fatalf("unexpected uncontested line %q", got)
+	}
+}
+
+func TestDescribeHand_NamesEveryCategory(t *testing.T) {
+	t.Parallel()
+
+	for _, tc := range []struct {
+		hand rules.HandRank
+		want string
+	}{
+		{hand: rules.HandRank{Category: rules.HandCategoryHighCard, Tiebreak: []uint8{14, 9, 7, 4, 2}}, want: "ace high"},
+		{hand: rules.HandRank{Category: rules.HandCategoryOnePair, Tiebreak: []uint8{6, 14, 9, 2}}, want: "a pair of sixes"},
+		{hand: rules.HandRank{Category: rules.HandCategoryTwoPair, Tiebreak: []uint8{13, 4, 9}}, want: "two pair, kings and fours"},
+		{hand: rules.HandRank{Category: rules.HandCategoryStraight, Tiebreak: []uint8{5}}, want: "a straight, five high"},
+		{hand: rules.HandRank{Category: rules.HandCategoryFullHouse, Tiebreak: []uint8{7, 2}}, want: "a full house, sevens full of twos"},
+		{hand: rules.HandRank{Category: rules.HandCategoryFourOfAKind, Tiebreak: []uint8{12, 3}}, want: "four of a kind, queens"},
+		{hand: rules.HandRank{Category: rules.HandCategoryStraightFlush, Tiebreak: []uint8{9}}, want: "a straight flush, nine high"},
+		{hand: rules.HandRank{Category: rules.HandCategoryStraightFlush, Tiebreak: []uint8{14}}, want: "a royal flush"},
+	} {
+		if got := DescribeHand(tc.hand); got != tc.want {
+			t.Fatalf("expected %q, got %q", tc.want, got)
+		}
+	}
+
+	for amount, want := range map[domain.Chips]string{0: "0", 999: "999", 1000: "1,000", 4350: "4,350", 1234567: "1,234,567"} {
+		if got := FormatChips(amount); got != want {
+			t.Fatalf("FormatChips(%d): expected %q, got %q", amount, want, got)
+		}
+	}
+}
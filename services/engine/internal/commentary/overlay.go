@@ -0,0 +1,182 @@
+package commentary
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"slices"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+// DefaultOverlayTimeout bounds each overlay push, so a summary reaches the
+// overlay within a second of the hand completing or is dropped.
+const DefaultOverlayTimeout = time.Second
+
+var ErrOverlayRejected = errors.New("overlay rejected hand summary")
+
+// Decision is one action as the table saw it.
+type Decision struct {
+	Street domain.Street     `json:"street"`
+	SeatNo domain.SeatNo     `json:"seat_no"`
+	Action domain.ActionKind `json:"action"`
+	Amount *domain.Chips     `json:"amount,omitempty"`
+}
+
+// Winner is what one seat took from the pots.
+type Winner struct {
+	SeatNo domain.SeatNo `json:"seat_no"`
+	Amount domain.Chips  `json:"amount"`
+}
+
+// ShownHand is a hand turned face up at showdown. Hand names it the way
+// Narrate does, once the board is complete.
+type ShownHand struct {
+	SeatNo domain.SeatNo `json:"seat_no"`
+	Cards  []domain.Card `json:"cards"`
+	Hand   string        `json:"hand,omitempty"`
+}
+
+// HandSummary is the payload a broadcast overlay receives per hand.
+// KeyDecisions are every bet, raise and all-in plus the action that ended
+// the betting; ShownHands never include mucked or folded cards.
+type HandSummary struct {
+	TableID       string         `json:"table_id"`
+	HandID        string         `json:"hand_id"`
+	HandNo        uint64         `json:"hand_no"`
+	PreflopRaiser *domain.SeatNo `json:"preflop_raiser,omitempty"`
+	KeyDecisions  []Decision     `json:"key_decisions"`
+	Board         []domain.Card  `json:"board"`
+	FinalPot      domain.Chips   `json:"final_pot"`
+	Winners       []Winner       `json:"winners"`
+	ShownHands    []ShownHand    `json:"shown_hands"`
+	Lines         []Line         `json:"lines"`
+}
+
+// Summarize builds the overlay payload for a completed hand from its final
+// state and its decisions in the order they were made. It reports false for
+// hands that are not complete.
+func Summarize(state domain.HandState, decisions []Decision) (HandSummary, bool) {
+	if state.Phase != domain.HandPhaseComplete {
+		return HandSummary{}, false
+	}
+	summary := HandSummary{
+		TableID:      state.TableID,
+		HandID:       state.HandID,
+		HandNo:       state.HandNo,
+		KeyDecisions: []Decision{},
+		Board:        append([]domain.Card{}, state.Board...),
+		Winners:      []Winner{},
+		ShownHands:   []ShownHand{},
+		Lines:        Narrate(state),
+	}
+	for i, decision := range decisions {
+		aggressive := decision.Action == domain.ActionBet || decision.Action == domain.ActionRaise || decision.Action == domain.ActionAllIn
+		if summary.PreflopRaiser == nil && aggressive && decision.Street == domain.StreetPreflop {
+			seat := decision.SeatNo
+			summary.PreflopRaiser = &seat
+		}
+		if aggressive || i == len(decisions)-1 {
+			summary.KeyDecisions = append(summary.KeyDecisions, decision)
+		}
+	}
+
+	won := make(map[domain.SeatNo]int)
+	for _, award := range state.ShowdownAwards {
+		summary.FinalPot += award.Amount
+		shares := awardShares(state.ButtonSeat, award)
+		for _, seatNo := range award.Seats {
+			idx, ok := won[seatNo]
+			if !ok {
+				idx = len(summary.Winners)
+				won[seatNo] = idx
+				summary.Winners = append(summary.Winners, Winner{SeatNo: seatNo})
+			}
+			summary.Winners[idx].Amount += shares[seatNo]
+		}
+	}
+
+	for _, reveal := range state.ShowdownReveals {
+		if reveal.Action != domain.RevealShow {
+			continue
+		}
+		for _, seatCards := range state.HoleCards {
+			if seatCards.SeatNo != reveal.SeatNo || len(seatCards.Cards) == 0 {
+				continue
+			}
+			shown := ShownHand{SeatNo: reveal.SeatNo, Cards: append([]domain.Card(nil), seatCards.Cards...)}
+			if len(state.Board) == 5 {
+				shown.Hand = DescribeHand(rules.EvaluateBestHand(seatCards.Cards, state.Board))
+			}
+			summary.ShownHands = append(summary.ShownHands, shown)
+		}
+	}
+	return summary, true
+}
+
+// awardShares splits award between its seats the way the pot was paid:
+// evenly, with odd chips going to the winners closest to the button's left.
+func awardShares(button domain.SeatNo, award domain.PotAward) map[domain.SeatNo]domain.Chips {
+	shares := make(map[domain.SeatNo]domain.Chips, len(award.Seats))
+	if len(award.Seats) == 0 {
+		return shares
+	}
+	seats := slices.Sorted(slices.Values(award.Seats))
+	start, _ := slices.BinarySearch(seats, button+1)
+	seats = slices.Concat(seats[start:], seats[:start])
+	n := domain.Chips(len(seats))
+	for i, seatNo := range seats {
+		shares[seatNo] = award.Amount / n
+		if domain.Chips(i) < award.Amount%n {
+			shares[seatNo]++
+		}
+	}
+	return shares
+}
+
+// Overlay pushes hand summaries to a broadcast overlay endpoint as JSON.
+type Overlay struct {
+	url     string
+	timeout time.Duration
+	client  *http.Client
+}
+
+// NewOverlay posts to url, giving up on each push after timeout; zero means
+// DefaultOverlayTimeout.
+func NewOverlay(url string, timeout time.Duration) *Overlay {
+	if timeout <= 0 {
+		timeout = DefaultOverlayTimeout
+	}
+	return &Overlay{url: url, timeout: timeout, client: &http.Client{Timeout: timeout}}
+}
+
+// Push posts summary and waits for the overlay to accept it with a 2xx.
+func (o *Overlay) Push(ctx context.Context, summary HandSummary) error {
+	payload, err := json.Marshal(summary)
+	if err != nil {
+		return err
+	}
+	ctx, cancel := context.WithTimeout(ctx, o.timeout)
+	defer cancel()
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, o.url, bytes.NewReader(payload))
+	if err != nil {
+		return err
+	}
+	req.Header.Set("Content-Type", "application/json")
+	resp, err := o.client.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	_, _ = io.Copy(io.Discard, io.LimitReader(resp.Body, 1<<16))
+	if resp.StatusCode < 200 || resp.StatusCode > 299 {
+		return fmt.Errorf("%w: hand %s got status %d", ErrOverlayRejected, summary.HandID, resp.StatusCode)
+	}
+	return nil
+}
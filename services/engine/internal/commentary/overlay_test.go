@@ -0,0 +1,124 @@
+package commentary
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"reflect"
+	"sync/atomic"
+	"testing"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func amount(v domain.Chips) *domain.Chips {
+	return &v
+}
+
+func splitShowdownHand() domain.HandState {
+	return domain.HandState{
+		TableID:    "table-1",
+		HandID:     "hand-3",
+		HandNo:     3,
+		ButtonSeat: 3,
+		Phase:      domain.HandPhaseComplete,
+		Board: []domain.Card{
+			card(14, domain.SuitSpades), card(13, domain.SuitSpades), card(12, domain.SuitDiamonds),
+			card(11, domain.SuitClubs), card(10, domain.SuitClubs),
+		},
+		HoleCards: []domain.SeatCards{
+			{SeatNo: 2, Cards: []domain.Card{card(2, domain.SuitClubs), card(3, domain.SuitDiamonds)}},
+			{SeatNo: 5, Cards: []domain.Card{card(4, domain.SuitClubs), card(5, domain.SuitDiamonds)}},
+			{SeatNo: 6, Cards: []domain.Card{card(9, domain.SuitHearts), card(9, domain.SuitDiamonds)}},
+		},
+		ShowdownAwards: []domain.PotAward{{Amount: 301, Seats: []domain.SeatNo{2, 5}, Reason: "main_pot"}},
+		ShowdownReveals: []domain.ShowdownReveal{
+			{SeatNo: 5, Action: domain.RevealShow, Reason: "first_to_show"},
+			{SeatNo: 2, Action: domain.RevealShow, Reason: "winner"},
+			{SeatNo: 6, Action: domain.RevealMuck, Reason: "auto_muck"},
+		},
+	}
+}
+
+func TestSummarize_PicksOutTheRaiserDecisionsWinnersAndShownHands(t *testing.T) {
+	t.Parallel()
+
+	decisions := []Decision{
+		{Street: domain.StreetPreflop, SeatNo: 6, Action: domain.ActionRaise, Amount: amount(60)},
+		{Street: domain.StreetPreflop, SeatNo: 2, Action: domain.ActionCall},
+		{Street: domain.StreetPreflop, SeatNo: 5, Action: domain.ActionCall},
+		{Street: domain.StreetFlop, SeatNo: 2, Action: domain.ActionCheck},
+		{Street: domain.StreetFlop, SeatNo: 5, Action: domain.ActionBet, Amount: amount(100)},
+		{Street: domain.StreetFlop, SeatNo: 6, Action: domain.ActionFold},
+		{Street: domain.StreetFlop, SeatNo: 2, Action: domain.ActionCall},
+		{Street: domain.StreetTurn, SeatNo: 2, Action: domain.ActionCheck},
+		{Street: domain.StreetTurn, SeatNo: 5, Action: domain.ActionCheck},
+		{Street: domain.StreetRiver, SeatNo: 2, Action: domain.ActionCheck},
+		{Street: domain.StreetRiver, SeatNo: 5, Action: domain.ActionCheck},
+	}
+	summary, ok := Summarize(splitShowdownHand(), decisions)
+	if !ok {
+		t.Fatalf("expected a summary for a completed hand")
+	}
+	if summary.PreflopRaiser == nil || *summary.PreflopRaiser != 6 {
+		t.Fatalf("expected seat 6 as the preflop raiser, got %v", summary.PreflopRaiser)
+	}
+	wantKey := []Decision{decisions[0], decisions[4], decisions[10]}
+	if !reflect.DeepEqual(summary.KeyDecisions, wantKey) {
+		t.Fatalf("expected key decisions %+v, got %+v", wantKey, summary.KeyDecisions)
+	}
+	// Seat 5 is first left of the button, so it takes the odd chip.
+	wantWinners := []Winner{{SeatNo: 2, Amount: 150}, {SeatNo: 5, Amount: 151}}
+	if summary.FinalPot != 301 || !reflect.DeepEqual(summary.Winners, wantWinners) {
+		t.Fatalf("expected 301 split as %+v, got %d %+v", wantWinners, summary.FinalPot, summary.Winners)
+	}
+	if len(summary.ShownHands) != 2 || summary.ShownHands[0].SeatNo != 5 || summary.ShownHands[1].SeatNo != 2 {
+		t.Fatalf("expected only the two shown hands in reveal order, got %+v", summary.ShownHands)
+	}
+	if hand := summary.ShownHands[0].Hand; hand != "a straight, ace high" {
+		t.Fatalf("unexpected shown hand description %q", hand)
+	}
+	if len(summary.Lines) != 1 || summary.Lines[0].Text != "Seats 2 and 5 split 301 with a straight, ace high" {
+		t.Fatalf("unexpected lines %+v", summary.Lines)
+	}
+
+	live := splitShowdownHand()
+	live.Phase = domain.HandPhaseShowdown
+	if _, ok := Summarize(live, decisions); ok {
+		t.Fatalf("expected no summary before the hand completes")
+	}
+}
+
+func TestOverlay_PushPostsTheSummaryAndReportsRejections(t *testing.T) {
+	t.Parallel()
+
+	received := make(chan HandSummary, 1)
+	var status atomic.Int32
+	status.Store(http.StatusNoContent)
+	overlay := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var summary HandSummary
+		if r.Method != http.MethodPost || json.NewDecoder(r.Body).Decode(&summary) != nil {
+			w.WriteHeader(http.StatusBadRequest)
+			return
+		}
+		received <- summary
+		w.WriteHeader(int(status.Load()))
+	}))
+	defer overlay.Close()
+
+	summary, _ := Summarize(splitShowdownHand(), nil)
+	if err := NewOverlay(overlay.URL, time.Second).Push(context.Background(), summary); err != nil {
+		t.Fatalf("Push failed: %v", err)
+	}
+	if got := <-received; got.HandID != "hand-3" || got.FinalPot != 301 || len(got.ShownHands) != 2 {
+		t.Fatalf("unexpected summary at the overlay: %+v", got)
+	}
+
+	status.Store(http.StatusServiceUnavailable)
+	if err := NewOverlay(overlay.URL, time.Second).Push(context.Background(), summary); !errors.Is(err, ErrOverlayRejected) {
+		t.Fatalf("expected ErrOverlayRejected, got %v", err)
+	}
+}
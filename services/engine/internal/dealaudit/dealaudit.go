@@ -0,0 +1,122 @@
+// Package dealaudit checks that mirrored tables in a duplicate match dealt
+// what the rotation intends: the same hole cards to the same seat in every
+// hand, with only the bot in the seat moving between mirrors. Anything else
+// means a mirror drew from a different shuffle and the match is suspect.
+package dealaudit
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+	"sync"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var ErrUnknownMirror = errors.New("unknown mirror")
+
+// Divergence is a seat whose hole cards on Mirror differ from mirror 0's in
+// the same hand. BotID is the bot holding the seat on Mirror.
+type Divergence struct {
+	HandNo   uint64        `json:"hand_no"`
+	Mirror   int           `json:"mirror"`
+	SeatNo   domain.SeatNo `json:"seat_no"`
+	BotID    string        `json:"bot_id"`
+	Expected []domain.Card `json:"expected"`
+	Dealt    []domain.Card `json:"dealt"`
+}
+
+type deal struct {
+	button domain.SeatNo
+	cards  map[domain.SeatNo][]domain.Card
+}
+
+// Auditor collects hands from every mirror of one match. Mirror 0 is the
+// reference. It is safe for concurrent use, so mirrors may run in parallel.
+type Auditor struct {
+	mu      sync.Mutex
+	seating []map[domain.SeatNo]string
+	deals   []map[uint64]deal
+}
+
+// New takes, for each mirror, the bot seated in each seat.
+func New(seating []map[domain.SeatNo]string) *Auditor {
+	deals := make([]map[uint64]deal, len(seating))
+	for i := range deals {
+		deals[i] = make(map[uint64]deal)
+	}
+	return &Auditor{seating: seating, deals: deals}
+}
+
+// Record stores the hole cards a mirror dealt in a hand.
+func (a *Auditor) Record(mirror int, state domain.HandState) error {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+
+	if mirror < 0 || mirror >= len(a.deals) {
+		return fmt.Errorf("%w: %d of %d", ErrUnknownMirror, mirror, len(a.deals))
+	}
+	cards := make(map[domain.SeatNo][]domain.Card, len(state.HoleCards))
+	for _, seatCards := range state.HoleCards {
+		cards[seatCards.SeatNo] = append([]domain.Card(nil), seatCards.Cards...)
+	}
+	a.deals[mirror][state.HandNo] = deal{button: state.ButtonSeat, cards: cards}
+	return nil
+}
+
+// Divergences compares every mirror with mirror 0, ordered by hand, mirror
+// and seat. Hands are only comparable while the mirrors share a button and
+// the same dealt-in seats; once a bot busts on one mirror and not another
+// the deals legitimately drift apart and the hand is skipped.
+func (a *Auditor) Divergences() []Divergence {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+
+	var out []Divergence
+	if len(a.deals) == 0 {
+		return out
+	}
+	handNos := make([]uint64, 0, len(a.deals[0]))
+	for handNo := range a.deals[0] {
+		handNos = append(handNos, handNo)
+	}
+	slices.Sort(handNos)
+
+	for _, handNo := range handNos {
+		reference := a.deals[0][handNo]
+		for mirror := 1; mirror < len(a.deals); mirror++ {
+			mirrored, ok := a.deals[mirror][handNo]
+			if !ok || !sameDealOrder(reference, mirrored) {
+				continue
+			}
+			for _, seatNo := range sortedSeats(reference.cards) {
+				expected, dealt := reference.cards[seatNo], mirrored.cards[seatNo]
+				if slices.Equal(expected, dealt) {
+					continue
+				}
+				out = append(out, Divergence{
+					HandNo:   handNo,
+					Mirror:   mirror,
+					SeatNo:   seatNo,
+					BotID:    a.seating[mirror][seatNo],
+					Expected: expected,
+					Dealt:    dealt,
+				})
+			}
+		}
+	}
+	return out
+}
+
+func sameDealOrder(a deal, b deal) bool {
+	return a.button == b.button && slices.Equal(sortedSeats(a.cards), sortedSeats(b.cards))
+}
+
+func sortedSeats(cards map[domain.SeatNo][]domain.Card) []domain.SeatNo {
+	seats := make([]domain.SeatNo, 0, len(cards))
+	for seatNo := range cards {
+		seats = append(seats, seatNo)
+	}
+	slices.Sort(seats)
+	return seats
+}
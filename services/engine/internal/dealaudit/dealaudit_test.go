@@ -0,0 +1,56 @@
+package dealaudit
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func dealtHand(handNo uint64, button domain.SeatNo, holes ...[]domain.Card) domain.HandState {
+	state := domain.HandState{HandNo: handNo, ButtonSeat: button}
+	for i, cards := range holes {
+		state.HoleCards = append(state.HoleCards, domain.SeatCards{SeatNo: domain.SeatNo(i + 1), Cards: cards})
+	}
+	return state
+}
+
+func TestAuditor_FlagsSeatsDealtDifferentCards(t *testing.T) {
+	t.Parallel()
+
+	aces := []domain.Card{domain.NewCard(14, domain.SuitSpades), domain.NewCard(14, domain.SuitHearts)}
+	kings := []domain.Card{domain.NewCard(13, domain.SuitSpades), domain.NewCard(13, domain.SuitHearts)}
+	auditor := New([]map[domain.SeatNo]string{
+		{1: "alpha", 2: "beta"},
+		{1: "beta", 2: "alpha"},
+	})
+	record := func(mirror int, state domain.HandState) {
+		t.Helper()
+		if err := auditor.Record(mirror, state); err != nil {
+			t.Fatalf("Record failed: %v", err)
+		}
+	}
+
+	record(0, dealtHand(1, 1, aces, kings))
+	record(1, dealtHand(1, 1, aces, kings))
+	record(0, dealtHand(2, 2, aces, kings))
+	record(1, dealtHand(2, 2, kings, aces))
+	// A different button means the mirrors drifted apart legitimately.
+	record(0, dealtHand(3, 1, aces, kings))
+	record(1, dealtHand(3, 2, kings, aces))
+
+	got := auditor.Divergences()
+	if len(got) != 2 {
+		t.Fatalf("expected both seats of hand 2 to diverge, got %+v", got)
+	}
+	if got[0].HandNo != 2 || got[0].Mirror != 1 || got[0].SeatNo != 1 || got[0].BotID != "beta" {
+		t.Fatalf("unexpected first divergence %+v", got[0])
+	}
+	if got[0].Expected[0] != aces[0] || got[0].Dealt[0] != kings[0] {
+		t.Fatalf("expected aces in place of kings, got %+v", got[0])
+	}
+
+	if err := auditor.Record(2, dealtHand(1, 1)); !errors.Is(err, ErrUnknownMirror) {
+		t.Fatalf("expected ErrUnknownMirror, got %v", err)
+	}
+}
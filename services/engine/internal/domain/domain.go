@@ -3,26 +3,94 @@ package domain
 import (
 	"crypto/rand"
 	"encoding/hex"
+	"encoding/json"
 	"errors"
 	"fmt"
+	"math"
+	"slices"
 )
 
 const (
-	DefaultMaxSeats          uint8  = 6
-	DefaultMinPlayersToStart uint8  = 2
-	DefaultStartingStack     uint32 = 10_000
-	DefaultSmallBlind        uint32 = 50
-	DefaultBigBlind          uint32 = 100
-	DefaultActionTimeoutMS   uint64 = 2_000
+	DefaultMaxSeats           uint8  = 6
+	DefaultMinPlayersToStart  uint8  = 2
+	DefaultStartingStack      Chips  = 10_000
+	DefaultSmallBlind         Chips  = 50
+	DefaultBigBlind           Chips  = 100
+	DefaultActionTimeoutMS    uint64 = 2_000
+	DefaultFixedLimitRaiseCap uint8  = 4
+	MaxUndoWindowMS           uint64 = 5_000
+	MaxLatencyCompensationMS  uint64 = 1_000
 )
 
+// WireSchemaVersion identifies the JSON encoding of HandState, TableConfig
+// and the API views built on them, as described in docs/wire-schema.md.
+// Adding an optional field keeps the version; renaming, removing or
+// repurposing one bumps it.
+const WireSchemaVersion = 1
+
 var (
 	ErrInvalidMinPlayersToStart = errors.New("min players to start must be at least 2 and <= max seats")
 	ErrInvalidBlindStructure    = errors.New("big blind must be greater than or equal to small blind")
 	ErrInvalidBlindAmount       = errors.New("small blind and big blind must both be greater than zero")
 	ErrDuplicateSeat            = errors.New("duplicate seat numbers are not allowed")
+	ErrUnknownBettingStructure  = errors.New("unknown betting structure")
+	ErrUnknownVariant           = errors.New("unknown variant")
+	ErrUnknownAnteMode          = errors.New("unknown ante mode")
+	ErrUnknownTimeoutAction     = errors.New("unknown timeout action")
+	ErrUnknownTimeBankRefill    = errors.New("unknown time bank refill")
+	ErrInvalidTimeBank          = errors.New("invalid time bank")
+	ErrUnknownShowdownPref      = errors.New("unknown showdown preference")
+	ErrUnknownViewLevel         = errors.New("unknown view level")
+	ErrUndoWindowTooLong        = errors.New("undo window is too long")
+	ErrLatencyCapTooLong        = errors.New("latency compensation cap is too long")
+	ErrInvalidBuyInRange        = errors.New("min buy-in exceeds max buy-in")
+	ErrUnknownRuleFlag          = errors.New("unknown rule flag")
+	ErrInvalidDeckSpec          = errors.New("invalid deck spec")
+	ErrInvalidTelemetry         = errors.New("invalid telemetry")
+	ErrInvalidRake              = errors.New("invalid rake")
+	ErrInvalidStraddle          = errors.New("invalid straddle")
+	ErrInvalidRunItTimes        = errors.New("invalid run it times")
+	ErrUnknownRulesVersion      = errors.New("unknown rules version")
+	ErrChipOverflow             = errors.New("chip amount overflows")
+	ErrChipUnderflow            = errors.New("chip amount goes below zero")
 )
 
+// Chips is an amount of chips. It is 64 bits wide so deep-stack games have
+// room to spare, and encodes as a plain JSON number. Pot math that could
+// leave that range uses CheckedAdd and CheckedSub, which report the overflow
+// instead of wrapping.
+type Chips uint64
+
+func (c Chips) CheckedAdd(other Chips) (Chips, error) {
+	if c > math.MaxUint64-other {
+		return 0, fmt.Errorf("%w: %d + %d", ErrChipOverflow, c, other)
+	}
+	return c + other, nil
+}
+
+func (c Chips) CheckedSub(other Chips) (Chips, error) {
+	if other > c {
+		return 0, fmt.Errorf("%w: %d - %d", ErrChipUnderflow, c, other)
+	}
+	return c - other, nil
+}
+
+// SaturatingAdd is c + other, or the largest amount when that overflows.
+func (c Chips) SaturatingAdd(other Chips) Chips {
+	if c > math.MaxUint64-other {
+		return math.MaxUint64
+	}
+	return c + other
+}
+
+// SaturatingSub is c - other, or zero when other is larger.
+func (c Chips) SaturatingSub(other Chips) Chips {
+	if other > c {
+		return 0
+	}
+	return c - other
+}
+
 type Suit string
 
 const (
@@ -67,6 +135,127 @@ func Standard52Deck() Deck {
 	return Deck{Cards: cards}
 }
 
+// MaxDecks bounds how many standard decks a DeckSpec may shuffle together.
+const MaxDecks = 4
+
+// DeckSpec customizes the deck for teaching and novelty tables. Decks
+// shuffles that many standard decks together, zero meaning one, and Remove
+// takes the listed cards out of every one of them. A nil spec is the
+// standard 52-card deck.
+type DeckSpec struct {
+	Decks  uint8  `json:"decks,omitempty"`
+	Remove []Card `json:"remove,omitempty"`
+}
+
+// DeckCount is the number of standard decks the spec shuffles together.
+func (s *DeckSpec) DeckCount() int {
+	if s == nil || s.Decks == 0 {
+		return 1
+	}
+	return int(s.Decks)
+}
+
+// Build returns the spec's cards in a fixed order, ready to shuffle.
+func (s *DeckSpec) Build() Deck {
+	if s == nil {
+		return Standard52Deck()
+	}
+	removed := make(map[Card]struct{}, len(s.Remove))
+	for _, card := range s.Remove {
+		removed[card] = struct{}{}
+	}
+	cards := make([]Card, 0, 52*s.DeckCount())
+	for i := 0; i < s.DeckCount(); i++ {
+		for _, card := range Standard52Deck().Cards {
+			if _, ok := removed[card]; !ok {
+				cards = append(cards, card)
+			}
+		}
+	}
+	return Deck{Cards: cards}
+}
+
+// Size is the number of cards Build returns for a valid spec.
+func (s *DeckSpec) Size() int {
+	if s == nil {
+		return 52
+	}
+	return (52 - len(s.Remove)) * s.DeckCount()
+}
+
+// Validate checks the spec and that it leaves enough cards to deal a hand at
+// a full table of maxSeats: two hole cards each, five board cards and three
+// burns.
+func (s *DeckSpec) Validate(maxSeats uint8) error {
+	if s == nil {
+		return nil
+	}
+	if s.Decks > MaxDecks {
+		return fmt.Errorf("%w: decks must be at most %d, got %d", ErrInvalidDeckSpec, MaxDecks, s.Decks)
+	}
+	seen := make(map[Card]struct{}, len(s.Remove))
+	for _, card := range s.Remove {
+		if _, err := NewRank(uint8(card.Rank)); err != nil || !isKnownSuit(card.Suit) {
+			return fmt.Errorf("%w: cannot remove unknown card %d of %q", ErrInvalidDeckSpec, card.Rank, card.Suit)
+		}
+		if _, dup := seen[card]; dup {
+			return fmt.Errorf("%w: card %d of %s removed twice", ErrInvalidDeckSpec, card.Rank, card.Suit)
+		}
+		seen[card] = struct{}{}
+	}
+	if need := 2*int(maxSeats) + 8; s.Size() < need {
+		return fmt.Errorf("%w: %d cards cannot deal %d seats, need %d", ErrInvalidDeckSpec, s.Size(), maxSeats, need)
+	}
+	return nil
+}
+
+func isKnownSuit(suit Suit) bool {
+	switch suit {
+	case SuitClubs, SuitDiamonds, SuitHearts, SuitSpades:
+		return true
+	default:
+		return false
+	}
+}
+
+// QualifiedHandRule decides which of a table's hands count for rake,
+// promotions and stats, so every module agrees on them. SawFlop requires a
+// flop to have been dealt, including on an all-in runout, and MinPot a pot
+// of at least that many chips. A nil rule qualifies every hand.
+type QualifiedHandRule struct {
+	SawFlop bool  `json:"saw_flop,omitempty"`
+	MinPot  Chips `json:"min_pot,omitempty"`
+}
+
+// Qualifies reports whether a completed hand meets the rule.
+func (r *QualifiedHandRule) Qualifies(state HandState) bool {
+	if r == nil {
+		return true
+	}
+	if r.SawFlop && len(state.Board) < 3 {
+		return false
+	}
+	return state.TotalPot() >= r.MinPot
+}
+
+// MaxRunItTimes is the most boards an all-in hand can be run out on.
+const MaxRunItTimes = 4
+
+// MaxRakeBasisPoints is a rake of the whole pot.
+const MaxRakeBasisPoints = 10_000
+
+// RakeRule is the house's cut of a table's pots, taken as they are awarded:
+// BasisPoints hundredths of a percent of the pot, rounded down, and never
+// more than Cap when Cap is set. NoFlopNoDrop leaves a hand that ends
+// before a flop is dealt unraked, as does a table's Qualification for the
+// hands it does not count, and chips no other seat called are never raked.
+// Package rake works out the amount.
+type RakeRule struct {
+	BasisPoints  uint16 `json:"basis_points"`
+	Cap          Chips  `json:"cap,omitempty"`
+	NoFlopNoDrop bool   `json:"no_flop_no_drop,omitempty"`
+}
+
 type Street string
 
 const (
@@ -84,14 +273,68 @@ const (
 	ActionCall  ActionKind = "call"
 	ActionBet   ActionKind = "bet"
 	ActionRaise ActionKind = "raise"
+	ActionAllIn ActionKind = "all_in"
+	// ActionDiscard and ActionStandPat are a seat's play in a draw round of
+	// a draw game: throwing away the Action's Discards for replacements, or
+	// keeping the whole hand.
+	ActionDiscard  ActionKind = "discard"
+	ActionStandPat ActionKind = "stand_pat"
+)
+
+// DeadlinePolicy records how the engine judged an action's arrival against
+// the seat's deadline.
+type DeadlinePolicy string
+
+const (
+	DeadlineOnTime DeadlinePolicy = "on_time"
+	// DeadlineLatencyGrace marks a decision that arrived after the deadline
+	// but inside the table's latency grace window, and was honored.
+	DeadlineLatencyGrace DeadlinePolicy = "latency_grace"
+	DeadlineNoDecision   DeadlinePolicy = "no_decision"
+	// DeadlineTimeBank marks a decision that outlasted the action timeout
+	// and was paid for from the seat's time bank.
+	DeadlineTimeBank DeadlinePolicy = "time_bank"
 )
 
 type Action struct {
 	Kind   ActionKind `json:"kind"`
-	Amount *uint32    `json:"amount,omitempty"`
+	Amount *Chips     `json:"amount,omitempty"`
+	// Discards are the cards thrown away by an ActionDiscard.
+	Discards []Card `json:"discards,omitempty"`
+	// Telemetry is what the bot reported about the decision, if anything.
+	Telemetry *Telemetry `json:"telemetry,omitempty"`
 }
 
-func NewAction(kind ActionKind, amount *uint32) (Action, error) {
+// MaxTelemetryTags bounds Telemetry.Tags.
+const MaxTelemetryTags = 8
+
+// Telemetry is a bot's own account of a decision: its equity estimate and
+// strategy tags such as "bluff" or "value". It is stored with the action and
+// embargoed from readers until the competition is over.
+type Telemetry struct {
+	Equity *float64 `json:"equity,omitempty"`
+	Tags   []string `json:"tags,omitempty"`
+}
+
+func (t *Telemetry) Validate() error {
+	if t == nil {
+		return nil
+	}
+	if t.Equity != nil && (*t.Equity < 0 || *t.Equity > 1) {
+		return fmt.Errorf("%w: equity must be in [0, 1], got %v", ErrInvalidTelemetry, *t.Equity)
+	}
+	if len(t.Tags) > MaxTelemetryTags {
+		return fmt.Errorf("%w: at most %d tags, got %d", ErrInvalidTelemetry, MaxTelemetryTags, len(t.Tags))
+	}
+	for _, tag := range t.Tags {
+		if tag == "" || len(tag) > 32 {
+			return fmt.Errorf("%w: tags must be 1 to 32 bytes, got %q", ErrInvalidTelemetry, tag)
+		}
+	}
+	return nil
+}
+
+func NewAction(kind ActionKind, amount *Chips) (Action, error) {
 	needsAmount := kind == ActionBet || kind == ActionRaise
 
 	if needsAmount && amount == nil {
@@ -122,17 +365,74 @@ const (
 	SeatStatusBusted     SeatStatus = "busted"
 )
 
+// ShowdownPreference controls whether a seat that cannot win reveals its
+// hand at showdown. The empty value behaves as ShowdownAutoMuck.
+type ShowdownPreference string
+
+const (
+	ShowdownAutoMuck   ShowdownPreference = "auto_muck"
+	ShowdownAlwaysShow ShowdownPreference = "always_show"
+)
+
+func ParseShowdownPreference(raw string) (ShowdownPreference, error) {
+	switch pref := ShowdownPreference(raw); pref {
+	case ShowdownAutoMuck, ShowdownAlwaysShow:
+		return pref, nil
+	case "":
+		return ShowdownAutoMuck, nil
+	default:
+		return "", fmt.Errorf("%w: %q", ErrUnknownShowdownPref, raw)
+	}
+}
+
+// ViewLevel is how much a seat's decision requests describe. Minimal carries
+// the cards, pot and amount to call with just enough to act legally,
+// standard adds stacks, bets, sizing hints and house rules, and verbose adds
+// derived analytics on top. The empty value behaves as ViewStandard.
+type ViewLevel string
+
+const (
+	ViewMinimal  ViewLevel = "minimal"
+	ViewStandard ViewLevel = "standard"
+	ViewVerbose  ViewLevel = "verbose"
+)
+
+func ParseViewLevel(raw string) (ViewLevel, error) {
+	switch level := ViewLevel(raw); level {
+	case ViewMinimal, ViewStandard, ViewVerbose:
+		return level, nil
+	case "":
+		return ViewStandard, nil
+	default:
+		return "", fmt.Errorf("%w: %q", ErrUnknownViewLevel, raw)
+	}
+}
+
 type SeatState struct {
-	SeatNo            SeatNo     `json:"seat_no"`
-	Stack             uint32     `json:"stack"`
-	TotalCommitted    uint32     `json:"total_committed"`
-	CommittedInRound  uint32     `json:"committed_in_round"`
-	Folded            bool       `json:"folded"`
-	HasActedThisRound bool       `json:"has_acted_this_round"`
-	Status            SeatStatus `json:"status"`
+	SeatNo               SeatNo             `json:"seat_no"`
+	Stack                Chips              `json:"stack"`
+	TotalCommitted       Chips              `json:"total_committed"`
+	CommittedInRound     Chips              `json:"committed_in_round"`
+	Folded               bool               `json:"folded"`
+	HasActedThisRound    bool               `json:"has_acted_this_round"`
+	Status               SeatStatus         `json:"status"`
+	ShowdownPreference   ShowdownPreference `json:"showdown_preference,omitempty"`
+	ThinkTimeRemainingMS uint64             `json:"think_time_remaining_ms,omitempty"`
+	TimeBankMS           uint64             `json:"time_bank_ms,omitempty"`
+	// ConsecutiveTimeouts counts the seat's missed action deadlines in a
+	// row, across hands; a decision that arrives in time clears it.
+	ConsecutiveTimeouts uint8 `json:"consecutive_timeouts,omitempty"`
+	// MissedSmallBlind and MissedBigBlind record the blinds that passed the
+	// seat while it sat out. It owes them when it is next dealt in anywhere
+	// but the big blind.
+	MissedSmallBlind bool `json:"missed_small_blind,omitempty"`
+	MissedBigBlind   bool `json:"missed_big_blind,omitempty"`
+	// Straddle asks to post the table's straddle in every hand that finds
+	// the seat in the straddle position with chips to cover it.
+	Straddle bool `json:"straddle,omitempty"`
 }
 
-func NewSeatState(seatNo SeatNo, stack uint32) SeatState {
+func NewSeatState(seatNo SeatNo, stack Chips) SeatState {
 	return SeatState{
 		SeatNo:            seatNo,
 		Stack:             stack,
@@ -148,13 +448,281 @@ func (s SeatState) IsActive() bool {
 	return s.Status == SeatStatusActive && !s.Folded
 }
 
+func (s SeatState) OwesBlinds() bool {
+	return s.MissedSmallBlind || s.MissedBigBlind
+}
+
+type BettingStructure string
+
+const (
+	BettingNoLimit BettingStructure = "no_limit"
+	// BettingPotLimit caps every bet and raise at the pot after the bettor
+	// calls: a raise may go to the current bet plus that pot.
+	BettingPotLimit   BettingStructure = "pot_limit"
+	BettingFixedLimit BettingStructure = "fixed_limit"
+)
+
+// Variant is the poker game a table deals, which decides how hands rank.
+type Variant string
+
+const (
+	VariantStandard  Variant = "standard"
+	VariantShortDeck Variant = "short_deck"
+	// VariantDeuceToSevenTripleDraw is a draw game: five private cards, no
+	// board, three draws and lowball ranking.
+	VariantDeuceToSevenTripleDraw Variant = "deuce_to_seven_triple_draw"
+	// VariantRazz is seven card stud played for ace-to-five low.
+	VariantRazz Variant = "razz"
+)
+
+// Effective is the variant v deals, VariantStandard when unset.
+func (v Variant) Effective() Variant {
+	if v == "" {
+		return VariantStandard
+	}
+	return v
+}
+
+func (v Variant) Validate() error {
+	switch v {
+	case "", VariantStandard, VariantShortDeck, VariantDeuceToSevenTripleDraw, VariantRazz:
+		return nil
+	default:
+		return fmt.Errorf("%w: %q", ErrUnknownVariant, v)
+	}
+}
+
+// AnteMode sets who pays a table's ante. Per seat, the default, every seat
+// dealt in antes; with a button ante the button pays the ante for every seat
+// dealt in, or the big blind does when the button is dead.
+type AnteMode string
+
+const (
+	AntePerSeat AnteMode = "per_seat"
+	AnteButton  AnteMode = "button"
+)
+
+// StraddleMode is where a table lets a seat post a live straddle before
+// the deal. The empty value plays without straddles.
+type StraddleMode string
+
+const (
+	// StraddleUTG is posted by the first seat left of the big blind.
+	StraddleUTG StraddleMode = "utg"
+	// StraddleButton is posted by the button. Preflop action then starts
+	// with the small blind.
+	StraddleButton StraddleMode = "button"
+)
+
+// TimeoutAction is the default decision for a seat whose time runs out.
+type TimeoutAction string
+
+const (
+	// TimeoutCheckFold checks when checking is legal and folds otherwise. It
+	// is the default.
+	TimeoutCheckFold TimeoutAction = "check_fold"
+	// TimeoutFold folds even when the seat could check.
+	TimeoutFold TimeoutAction = "fold"
+)
+
+// TimeBankRefill sets how a seat's time bank is replenished between hands.
+type TimeBankRefill string
+
+const (
+	// TimeBankRefillNone, the default, never replenishes the bank: it lasts
+	// the whole run.
+	TimeBankRefillNone TimeBankRefill = "none"
+	// TimeBankRefillPerHand adds TimeBankRefillMS after every hand, up to the
+	// full bank.
+	TimeBankRefillPerHand TimeBankRefill = "per_hand"
+	// TimeBankRefillFull restores the full bank after every hand.
+	TimeBankRefillFull TimeBankRefill = "full"
+)
+
+// RuleFlags is the set of house rules a table plays beyond standard
+// hold'em. Tables advertise them to every seat so bots can adapt or refuse
+// the table. In JSON the set is a list of flag names.
+type RuleFlags uint32
+
+const (
+	RuleStraddle RuleFlags = 1 << iota
+	RuleRunItTwice
+	RuleBombPots
+	RuleAntes
+	RuleInsurance
+	// RuleOpponentStats is a league opt-in letting seats query aggregated
+	// public stats of the opponents at their table.
+	RuleOpponentStats
+
+	knownRuleFlags = RuleStraddle | RuleRunItTwice | RuleBombPots | RuleAntes | RuleInsurance | RuleOpponentStats
+)
+
+var ruleFlagNames = []struct {
+	flag RuleFlags
+	name string
+}{
+	{flag: RuleStraddle, name: "straddle"},
+	{flag: RuleRunItTwice, name: "run_it_twice"},
+	{flag: RuleBombPots, name: "bomb_pots"},
+	{flag: RuleAntes, name: "antes"},
+	{flag: RuleInsurance, name: "insurance"},
+	{flag: RuleOpponentStats, name: "opponent_stats"},
+}
+
+// ParseRuleFlags builds a set from flag names, ignoring duplicates.
+func ParseRuleFlags(names []string) (RuleFlags, error) {
+	var flags RuleFlags
+	for _, raw := range names {
+		flag, ok := ruleFlagByName(raw)
+		if !ok {
+			return 0, fmt.Errorf("%w: %q", ErrUnknownRuleFlag, raw)
+		}
+		flags |= flag
+	}
+	return flags, nil
+}
+
+func ruleFlagByName(name string) (RuleFlags, bool) {
+	for _, known := range ruleFlagNames {
+		if known.name == name {
+			return known.flag, true
+		}
+	}
+	return 0, false
+}
+
+func (f RuleFlags) Has(flag RuleFlags) bool {
+	return f&flag == flag
+}
+
+// Names lists the set flags in declaration order.
+func (f RuleFlags) Names() []string {
+	names := make([]string, 0, len(ruleFlagNames))
+	for _, known := range ruleFlagNames {
+		if f.Has(known.flag) {
+			names = append(names, known.name)
+		}
+	}
+	return names
+}
+
+func (f RuleFlags) MarshalJSON() ([]byte, error) {
+	return json.Marshal(f.Names())
+}
+
+func (f *RuleFlags) UnmarshalJSON(data []byte) error {
+	var names []string
+	if err := json.Unmarshal(data, &names); err != nil {
+		return err
+	}
+	flags, err := ParseRuleFlags(names)
+	if err != nil {
+		return err
+	}
+	*f = flags
+	return nil
+}
+
+// RulesVersion numbers the engine's game rules. Every change to how a hand
+// plays, such as a fix to a min-raise edge case, ships as a new version
+// and keeps the old behavior for tables pinned to an earlier one, so
+// upgrading the engine mid-competition never changes the game being
+// played. The zero value plays CurrentRulesVersion.
+type RulesVersion uint16
+
+const (
+	// RulesV1 is the rules as first released: any all-in raise reopens the
+	// betting.
+	RulesV1 RulesVersion = 1
+	// RulesV2 applies the full-raise rule: an all-in short of a full raise
+	// does not reopen the betting to seats that have already acted.
+	RulesV2 RulesVersion = 2
+
+	CurrentRulesVersion = RulesV2
+)
+
+// Effective is the version v plays, CurrentRulesVersion when unset. Tables
+// and tournaments store it when created to pin their rules.
+func (v RulesVersion) Effective() RulesVersion {
+	if v == 0 {
+		return CurrentRulesVersion
+	}
+	return v
+}
+
+func (v RulesVersion) Validate() error {
+	if v > CurrentRulesVersion {
+		return fmt.Errorf("%w: %d, this engine plays up to %d", ErrUnknownRulesVersion, v, CurrentRulesVersion)
+	}
+	return nil
+}
+
+// FullRaiseRule reports whether an incomplete all-in raise leaves the
+// betting closed to seats that have acted, from RulesV2.
+func (v RulesVersion) FullRaiseRule() bool {
+	return v.Effective() >= RulesV2
+}
+
 type TableConfig struct {
-	MaxSeats          uint8  `json:"max_seats"`
-	MinPlayersToStart uint8  `json:"min_players_to_start"`
-	StartingStack     uint32 `json:"starting_stack"`
-	SmallBlind        uint32 `json:"small_blind"`
-	BigBlind          uint32 `json:"big_blind"`
-	ActionTimeoutMS   uint64 `json:"action_timeout_ms"`
+	MaxSeats          uint8            `json:"max_seats"`
+	MinPlayersToStart uint8            `json:"min_players_to_start"`
+	StartingStack     Chips            `json:"starting_stack"`
+	SmallBlind        Chips            `json:"small_blind"`
+	BigBlind          Chips            `json:"big_blind"`
+	ActionTimeoutMS   uint64           `json:"action_timeout_ms"`
+	BettingStructure  BettingStructure `json:"betting_structure,omitempty"`
+	RaiseCap          uint8            `json:"raise_cap,omitempty"`
+	ThinkTimeBudgetMS uint64           `json:"think_time_budget_ms,omitempty"`
+	LatencyGraceMS    uint64           `json:"latency_grace_ms,omitempty"`
+	// UndoWindowMS holds each decision uncommitted so a human player can
+	// take it back. Zero, the default, commits at once.
+	UndoWindowMS uint64    `json:"undo_window_ms,omitempty"`
+	RuleFlags    RuleFlags `json:"rule_flags,omitempty"`
+	DeckSpec     *DeckSpec `json:"deck_spec,omitempty"`
+	// RulesVersion pins the table's game rules; zero plays the current ones.
+	RulesVersion RulesVersion `json:"rules_version,omitempty"`
+	// Variant is the game the table deals; empty deals standard hold'em.
+	Variant Variant `json:"variant,omitempty"`
+	// Qualification is the table's QualifiedHandRule; nil counts every hand.
+	Qualification *QualifiedHandRule `json:"qualification,omitempty"`
+	// Rake is the house's cut of every pot; nil plays without rake.
+	Rake *RakeRule `json:"rake,omitempty"`
+	// Ante is collected at the start of every hand, after the blinds, in
+	// AnteMode. Zero, the default, plays without antes.
+	Ante     Chips    `json:"ante,omitempty"`
+	AnteMode AnteMode `json:"ante_mode,omitempty"`
+	// DeadButton moves the blinds under the dead-button rule across hands, so
+	// a player busting can leave the button or small blind on an empty seat.
+	// The default moves the button to the next seat still playing.
+	DeadButton bool `json:"dead_button,omitempty"`
+	// Straddle lets the seat in the StraddleMode position post a live
+	// straddle of StraddleMultiple big blinds, two when zero, whenever its
+	// SeatState.Straddle is set. Straddles need no-limit betting.
+	Straddle         StraddleMode `json:"straddle,omitempty"`
+	StraddleMultiple uint8        `json:"straddle_multiple,omitempty"`
+	// RunItTimes runs out the rest of the board that many times when the
+	// players are all in before the river, splitting every pot evenly
+	// between the runouts. Zero and one deal a single board.
+	RunItTimes uint8 `json:"run_it_times,omitempty"`
+	// TimeoutAction is played for a seat that misses its action deadline.
+	// SitOutAfterTimeouts sits a seat out once the hand ends after that many
+	// timeouts in a row; zero, the default, never does.
+	TimeoutAction       TimeoutAction `json:"timeout_action,omitempty"`
+	SitOutAfterTimeouts uint8         `json:"sit_out_after_timeouts,omitempty"`
+	// TimeBankMS gives every seat a bank of extra time, drawn on whenever a
+	// decision outlasts ActionTimeoutMS, and TimeBankRefill replenishes it.
+	TimeBankMS       uint64         `json:"time_bank_ms,omitempty"`
+	TimeBankRefill   TimeBankRefill `json:"time_bank_refill,omitempty"`
+	TimeBankRefillMS uint64         `json:"time_bank_refill_ms,omitempty"`
+	// LatencyCompensationCapMS turns on latency compensation: each seat's
+	// deadline is extended by its measured one-way latency, up to this many
+	// milliseconds, so a bot far from the table gets as long to think as one
+	// next to it. Zero, the default, compensates no one.
+	LatencyCompensationCapMS uint64 `json:"latency_compensation_cap_ms,omitempty"`
+	// MinBuyIn and MaxBuyIn bound what a player sits down with between
+	// hands. Zero leaves that end of the range open.
+	MinBuyIn Chips `json:"min_buy_in,omitempty"`
+	MaxBuyIn Chips `json:"max_buy_in,omitempty"`
 }
 
 func DefaultV0TableConfig() TableConfig {
@@ -185,9 +753,144 @@ func (c TableConfig) Validate() error {
 		return ErrInvalidBlindAmount
 	}
 
+	switch c.BettingStructure {
+	case "", BettingNoLimit, BettingPotLimit, BettingFixedLimit:
+	default:
+		return fmt.Errorf("%w: %q", ErrUnknownBettingStructure, c.BettingStructure)
+	}
+	if err := c.Variant.Validate(); err != nil {
+		return err
+	}
+
+	switch c.AnteMode {
+	case "", AntePerSeat, AnteButton:
+	default:
+		return fmt.Errorf("%w: %q", ErrUnknownAnteMode, c.AnteMode)
+	}
+
+	switch c.TimeoutAction {
+	case "", TimeoutCheckFold, TimeoutFold:
+	default:
+		return fmt.Errorf("%w: %q", ErrUnknownTimeoutAction, c.TimeoutAction)
+	}
+
+	switch c.TimeBankRefill {
+	case "", TimeBankRefillNone, TimeBankRefillPerHand, TimeBankRefillFull:
+	default:
+		return fmt.Errorf("%w: %q", ErrUnknownTimeBankRefill, c.TimeBankRefill)
+	}
+
+	if err := c.validateTimeBank(); err != nil {
+		return err
+	}
+
+	if c.RuleFlags&^knownRuleFlags != 0 {
+		return fmt.Errorf("%w: bits %#x", ErrUnknownRuleFlag, uint32(c.RuleFlags&^knownRuleFlags))
+	}
+
+	if c.UndoWindowMS > MaxUndoWindowMS {
+		return fmt.Errorf("%w: %dms exceeds %dms", ErrUndoWindowTooLong, c.UndoWindowMS, MaxUndoWindowMS)
+	}
+
+	if c.LatencyCompensationCapMS > MaxLatencyCompensationMS {
+		return fmt.Errorf("%w: %dms exceeds %dms", ErrLatencyCapTooLong, c.LatencyCompensationCapMS, MaxLatencyCompensationMS)
+	}
+
+	if c.MaxBuyIn > 0 && c.MinBuyIn > c.MaxBuyIn {
+		return fmt.Errorf("%w: %d > %d", ErrInvalidBuyInRange, c.MinBuyIn, c.MaxBuyIn)
+	}
+
+	if err := c.DeckSpec.Validate(c.MaxSeats); err != nil {
+		return err
+	}
+
+	if err := c.RulesVersion.Validate(); err != nil {
+		return err
+	}
+
+	if err := c.validateStraddle(); err != nil {
+		return err
+	}
+
+	if c.RunItTimes > MaxRunItTimes {
+		return fmt.Errorf("%w: %d exceeds %d", ErrInvalidRunItTimes, c.RunItTimes, MaxRunItTimes)
+	}
+	// Every extra board burns and deals eight more cards.
+	if need := 2*int(c.MaxSeats) + 8*int(c.RunItTimes); c.RunItTimes > 1 && c.DeckSpec.Size() < need {
+		return fmt.Errorf("%w: %d cards cannot run %d boards at %d seats, need %d", ErrInvalidRunItTimes, c.DeckSpec.Size(), c.RunItTimes, c.MaxSeats, need)
+	}
+
+	if c.Rake != nil && c.Rake.BasisPoints > MaxRakeBasisPoints {
+		return fmt.Errorf("%w: %d basis points exceeds %d", ErrInvalidRake, c.Rake.BasisPoints, MaxRakeBasisPoints)
+	}
+
+	return nil
+}
+
+// validateStraddle checks that a straddle has a known position, is worth
+// more than the big blind and is played no-limit, since fixed-limit bets
+// are sized from the big blind.
+func (c TableConfig) validateStraddle() error {
+	switch c.Straddle {
+	case "":
+		if c.StraddleMultiple > 0 {
+			return fmt.Errorf("%w: straddle_multiple without a straddle", ErrInvalidStraddle)
+		}
+		return nil
+	case StraddleUTG, StraddleButton:
+	default:
+		return fmt.Errorf("%w: unknown straddle %q", ErrInvalidStraddle, c.Straddle)
+	}
+	if c.StraddleMultiple == 1 {
+		return fmt.Errorf("%w: straddle_multiple must be at least 2", ErrInvalidStraddle)
+	}
+	if c.BettingStructure == BettingFixedLimit {
+		return fmt.Errorf("%w: straddles need no-limit betting", ErrInvalidStraddle)
+	}
 	return nil
 }
 
+// StraddleAmount is what a straddle on the table puts in, or zero on a
+// table without straddles.
+func (c TableConfig) StraddleAmount() Chips {
+	if c.Straddle == "" {
+		return 0
+	}
+	return c.BigBlind * Chips(max(c.StraddleMultiple, 2))
+}
+
+// validateTimeBank checks that a time bank has a per-action timeout to
+// extend, which a think-time budget replaces, and that a per-hand refill
+// adds something.
+func (c TableConfig) validateTimeBank() error {
+	if c.TimeBankMS == 0 {
+		if c.TimeBankRefillMS > 0 {
+			return fmt.Errorf("%w: time_bank_refill_ms without a time bank", ErrInvalidTimeBank)
+		}
+		return nil
+	}
+	if c.ActionTimeoutMS == 0 || c.ThinkTimeBudgetMS > 0 {
+		return fmt.Errorf("%w: a time bank extends action_timeout_ms and cannot be combined with a think-time budget", ErrInvalidTimeBank)
+	}
+	if c.TimeBankRefill == TimeBankRefillPerHand && c.TimeBankRefillMS == 0 {
+		return fmt.Errorf("%w: a per_hand refill needs time_bank_refill_ms", ErrInvalidTimeBank)
+	}
+	return nil
+}
+
+// EffectiveRaiseCap returns the number of bets allowed per street, counting
+// the opening bet, or 0 when betting is uncapped. Fixed-limit tables default
+// to DefaultFixedLimitRaiseCap.
+func (c TableConfig) EffectiveRaiseCap() uint8 {
+	if c.BettingStructure != BettingFixedLimit {
+		return 0
+	}
+	if c.RaiseCap == 0 {
+		return DefaultFixedLimitRaiseCap
+	}
+	return c.RaiseCap
+}
+
 type HandPhase string
 
 const (
@@ -198,27 +901,198 @@ const (
 )
 
 type HandState struct {
-	HandID               string      `json:"hand_id"`
-	TableID              string      `json:"table_id"`
-	HandNo               uint64      `json:"hand_no"`
-	ButtonSeat           SeatNo      `json:"button_seat"`
-	SmallBlind           uint32      `json:"small_blind"`
-	BigBlind             uint32      `json:"big_blind"`
-	ActingSeat           SeatNo      `json:"acting_seat"`
-	ActionOrderStartSeat SeatNo      `json:"action_order_start_seat"`
-	LastAggressorSeat    *SeatNo     `json:"last_aggressor_seat,omitempty"`
-	Phase                HandPhase   `json:"phase"`
-	Street               Street      `json:"street"`
-	Pot                  uint32      `json:"pot"`
-	CurrentBet           uint32      `json:"current_bet"`
-	MinRaiseTo           uint32      `json:"min_raise_to"`
-	LastFullRaise        uint32      `json:"last_full_raise"`
-	Board                []Card      `json:"board"`
-	Deck                 []Card      `json:"deck"`
-	NextCardIndex        int         `json:"next_card_index"`
-	HoleCards            []SeatCards `json:"hole_cards"`
-	ShowdownAwards       []PotAward  `json:"showdown_awards"`
-	Seats                []SeatState `json:"seats"`
+	HandID                   string             `json:"hand_id"`
+	TableID                  string             `json:"table_id"`
+	HandNo                   uint64             `json:"hand_no"`
+	ButtonSeat               SeatNo             `json:"button_seat"`
+	SmallBlind               Chips              `json:"small_blind"`
+	BigBlind                 Chips              `json:"big_blind"`
+	Ante                     Chips              `json:"ante,omitempty"`
+	AnteMode                 AnteMode           `json:"ante_mode,omitempty"`
+	ActingSeat               SeatNo             `json:"acting_seat"`
+	ActionOrderStartSeat     SeatNo             `json:"action_order_start_seat"`
+	LastAggressorSeat        *SeatNo            `json:"last_aggressor_seat,omitempty"`
+	StraddleSeat             *SeatNo            `json:"straddle_seat,omitempty"`
+	Phase                    HandPhase          `json:"phase"`
+	Street                   Street             `json:"street"`
+	Pot                      Chips              `json:"pot"`
+	DeadMoney                Chips              `json:"dead_money,omitempty"`
+	CurrentBet               Chips              `json:"current_bet"`
+	MinRaiseTo               Chips              `json:"min_raise_to"`
+	LastFullRaise            Chips              `json:"last_full_raise"`
+	BettingStructure         BettingStructure   `json:"betting_structure,omitempty"`
+	RaiseCap                 uint8              `json:"raise_cap,omitempty"`
+	StreetBets               uint8              `json:"street_bets"`
+	ThinkTimeBudgetMS        uint64             `json:"think_time_budget_ms,omitempty"`
+	LatencyGraceMS           uint64             `json:"latency_grace_ms,omitempty"`
+	LatencyCompensationCapMS uint64             `json:"latency_compensation_cap_ms,omitempty"`
+	RuleFlags                RuleFlags          `json:"rule_flags,omitempty"`
+	RulesVersion             RulesVersion       `json:"rules_version,omitempty"`
+	DeckSpec                 *DeckSpec          `json:"deck_spec,omitempty"`
+	Qualification            *QualifiedHandRule `json:"qualification,omitempty"`
+	Rake                     *RakeRule          `json:"rake,omitempty"`
+	RakeTaken                Chips              `json:"rake_taken,omitempty"`
+	RunItTimes               uint8              `json:"run_it_times,omitempty"`
+	Board                    []Card             `json:"board"`
+	Runouts                  [][]Card           `json:"runouts,omitempty"`
+	Deck                     []Card             `json:"deck"`
+	DeckCommitment           string             `json:"deck_commitment,omitempty"`
+	NextCardIndex            int                `json:"next_card_index"`
+	Discards                 []Card             `json:"discards,omitempty"`
+	HoleCards                []SeatCards        `json:"hole_cards"`
+	DealSequence             []DealtCard        `json:"deal_sequence"`
+	ShowdownAwards           []PotAward         `json:"showdown_awards"`
+	ShowdownReveals          []ShowdownReveal   `json:"showdown_reveals,omitempty"`
+	Seats                    []SeatState        `json:"seats"`
+}
+
+// RaiseCapReached reports whether no further bet or raise is allowed on the
+// current street. The cap is lifted once only two players remain.
+func (s HandState) RaiseCapReached() bool {
+	if s.RaiseCap == 0 || s.StreetBets < s.RaiseCap {
+		return false
+	}
+	contesting := 0
+	for _, seat := range s.Seats {
+		if seat.IsActive() {
+			contesting++
+		}
+	}
+	return contesting > 2
+}
+
+// FixedLimitBet is the size of every bet and raise on the current street of
+// a fixed-limit hand: the small bet, one big blind, preflop and on the
+// flop, and the big bet, two big blinds, on the turn and river. It reports
+// false on other tables.
+func (s HandState) FixedLimitBet() (Chips, bool) {
+	if s.BettingStructure != BettingFixedLimit {
+		return 0, false
+	}
+	if s.Street == StreetTurn || s.Street == StreetRiver {
+		return 2 * s.BigBlind, true
+	}
+	return s.BigBlind, true
+}
+
+// PotLimit is the most seat may bet, or raise to, on a pot-limit table:
+// the current bet plus the pot after seat calls it. It reports false on
+// other tables.
+func (s HandState) PotLimit(seat SeatState) (Chips, bool) {
+	if s.BettingStructure != BettingPotLimit {
+		return 0, false
+	}
+	toCall := Chips(0)
+	if s.CurrentBet > seat.CommittedInRound {
+		toCall = s.CurrentBet - seat.CommittedInRound
+	}
+	return s.CurrentBet + s.Pot + toCall, true
+}
+
+// TotalPot is every chip committed to the hand, whether still in the pot or
+// already awarded.
+func (s HandState) TotalPot() Chips {
+	total := s.DeadMoney
+	for _, seat := range s.Seats {
+		total += seat.TotalCommitted
+	}
+	return total
+}
+
+// Qualified reports whether the hand counts under its table's
+// QualifiedHandRule.
+func (s HandState) Qualified() bool {
+	return s.Qualification.Qualifies(s)
+}
+
+// AllInLocked reports whether betting is over with at least two players
+// still contesting the pot, leaving only the runout to decide the hand.
+func (s HandState) AllInLocked() bool {
+	if s.Phase == HandPhaseComplete {
+		return false
+	}
+	contesting, withChips := 0, 0
+	for _, seat := range s.Seats {
+		if !seat.IsActive() {
+			continue
+		}
+		contesting++
+		if seat.Stack > 0 {
+			withChips++
+		}
+	}
+	if contesting < 2 || withChips > 1 {
+		return false
+	}
+	if withChips == 0 {
+		return true
+	}
+	// The one seat with chips left must already have matched the bet.
+	for _, seat := range s.Seats {
+		if seat.IsActive() && seat.Stack > 0 && seat.CommittedInRound < s.CurrentBet {
+			return false
+		}
+	}
+	return true
+}
+
+// ForSeat is the hand as seatNo may see it: its own hole cards, other
+// seats' only once they were shown at showdown, the board, and none of the
+// undealt deck, burn cards or discards. Seat zero gets a spectator's view. The view
+// copies the cards, seats and awards it keeps, so editing it leaves s alone.
+func (s HandState) ForSeat(seatNo SeatNo) HandState {
+	shown := make(map[SeatNo]bool, len(s.ShowdownReveals))
+	for _, reveal := range s.ShowdownReveals {
+		if reveal.Action == RevealShow {
+			shown[reveal.SeatNo] = true
+		}
+	}
+	visible := func(owner SeatNo) bool { return owner == seatNo || shown[owner] }
+
+	view := s
+	view.Deck = nil
+	view.Discards = nil
+	view.Board = slices.Clone(s.Board)
+	view.Runouts = CloneBoards(s.Runouts)
+	view.Seats = slices.Clone(s.Seats)
+	view.ShowdownReveals = slices.Clone(s.ShowdownReveals)
+	if s.LastAggressorSeat != nil {
+		seat := *s.LastAggressorSeat
+		view.LastAggressorSeat = &seat
+	}
+	view.ShowdownAwards = make([]PotAward, 0, len(s.ShowdownAwards))
+	for _, award := range s.ShowdownAwards {
+		award.Seats = slices.Clone(award.Seats)
+		view.ShowdownAwards = append(view.ShowdownAwards, award)
+	}
+	view.HoleCards = make([]SeatCards, 0, len(s.HoleCards))
+	for _, seatCards := range s.HoleCards {
+		cards := []Card{}
+		if visible(seatCards.SeatNo) {
+			cards = slices.Clone(seatCards.Cards)
+		}
+		view.HoleCards = append(view.HoleCards, SeatCards{SeatNo: seatCards.SeatNo, Cards: cards})
+	}
+	view.DealSequence = make([]DealtCard, 0, len(s.DealSequence))
+	for _, dealt := range s.DealSequence {
+		if dealt.Target == DealTargetBurn || (dealt.Target == DealTargetHole && !visible(dealt.SeatNo)) {
+			continue
+		}
+		view.DealSequence = append(view.DealSequence, dealt)
+	}
+	return view
+}
+
+// CloneBoards deep-copies a hand's Runouts.
+func CloneBoards(boards [][]Card) [][]Card {
+	if boards == nil {
+		return nil
+	}
+	out := make([][]Card, 0, len(boards))
+	for _, board := range boards {
+		out = append(out, slices.Clone(board))
+	}
+	return out
 }
 
 type SeatCards struct {
@@ -226,12 +1100,49 @@ type SeatCards struct {
 	Cards  []Card `json:"cards"`
 }
 
+type DealTarget string
+
+const (
+	DealTargetHole  DealTarget = "hole"
+	DealTargetBurn  DealTarget = "burn"
+	DealTargetBoard DealTarget = "board"
+	// DealTargetUp is a stud card dealt face up to SeatNo, in view of every
+	// seat.
+	DealTargetUp DealTarget = "up"
+)
+
+// DealtCard records one card leaving the deck, in the order it was dealt.
+// Runout numbers the extra board a burn or board card went to when the
+// hand is run more than once, from 2; it is zero for the first board.
+type DealtCard struct {
+	DeckIndex int        `json:"deck_index"`
+	Target    DealTarget `json:"target"`
+	SeatNo    SeatNo     `json:"seat_no,omitempty"`
+	Runout    int        `json:"runout,omitempty"`
+	Card      Card       `json:"card"`
+}
+
 type PotAward struct {
-	Amount uint32   `json:"amount"`
+	Amount Chips    `json:"amount"`
 	Seats  []SeatNo `json:"seats"`
 	Reason string   `json:"reason"`
 }
 
+type RevealAction string
+
+const (
+	RevealShow RevealAction = "show"
+	RevealMuck RevealAction = "muck"
+)
+
+// ShowdownReveal is one seat's show or muck, in the order seats were called
+// on to reveal.
+type ShowdownReveal struct {
+	SeatNo SeatNo       `json:"seat_no"`
+	Action RevealAction `json:"action"`
+	Reason string       `json:"reason"`
+}
+
 func NewHandState(
 	tableID string,
 	handNo uint64,
@@ -240,6 +1151,28 @@ func NewHandState(
 	seats []SeatState,
 	config TableConfig,
 ) (HandState, error) {
+	handID, err := newHandID()
+	if err != nil {
+		return HandState{}, err
+	}
+	return NewHandStateWithID(handID, tableID, handNo, buttonSeat, actingSeat, seats, config)
+}
+
+// NewHandStateWithID is NewHandState with a caller-chosen hand ID. It never
+// draws on OS entropy, so embedders without one (such as the browser build)
+// can use it.
+func NewHandStateWithID(
+	handID string,
+	tableID string,
+	handNo uint64,
+	buttonSeat SeatNo,
+	actingSeat SeatNo,
+	seats []SeatState,
+	config TableConfig,
+) (HandState, error) {
+	if handID == "" {
+		return HandState{}, errors.New("hand id must not be empty")
+	}
 	if err := config.Validate(); err != nil {
 		return HandState{}, err
 	}
@@ -272,33 +1205,48 @@ func NewHandState(
 		return HandState{}, fmt.Errorf("acting seat %d must exist in hand seats", actingSeat)
 	}
 
-	handID, err := newHandID()
-	if err != nil {
-		return HandState{}, err
+	handSeats := append([]SeatState(nil), seats...)
+	for i := range handSeats {
+		handSeats[i].ThinkTimeRemainingMS = config.ThinkTimeBudgetMS
 	}
 
 	return HandState{
-		HandID:               handID,
-		TableID:              tableID,
-		HandNo:               handNo,
-		ButtonSeat:           buttonSeat,
-		SmallBlind:           config.SmallBlind,
-		BigBlind:             config.BigBlind,
-		ActingSeat:           actingSeat,
-		ActionOrderStartSeat: actingSeat,
-		LastAggressorSeat:    nil,
-		Phase:                HandPhaseDealing,
-		Street:               StreetPreflop,
-		Pot:                  0,
-		CurrentBet:           0,
-		MinRaiseTo:           config.BigBlind,
-		LastFullRaise:        0,
-		Board:                make([]Card, 0, 5),
-		Deck:                 make([]Card, 0, 52),
-		NextCardIndex:        0,
-		HoleCards:            make([]SeatCards, 0, len(seats)),
-		ShowdownAwards:       make([]PotAward, 0, 4),
-		Seats:                append([]SeatState(nil), seats...),
+		HandID:                   handID,
+		TableID:                  tableID,
+		HandNo:                   handNo,
+		ButtonSeat:               buttonSeat,
+		SmallBlind:               config.SmallBlind,
+		BigBlind:                 config.BigBlind,
+		Ante:                     config.Ante,
+		AnteMode:                 config.AnteMode,
+		ActingSeat:               actingSeat,
+		ActionOrderStartSeat:     actingSeat,
+		LastAggressorSeat:        nil,
+		Phase:                    HandPhaseDealing,
+		Street:                   StreetPreflop,
+		Pot:                      0,
+		CurrentBet:               0,
+		MinRaiseTo:               config.BigBlind,
+		LastFullRaise:            0,
+		BettingStructure:         config.BettingStructure,
+		RaiseCap:                 config.EffectiveRaiseCap(),
+		StreetBets:               0,
+		ThinkTimeBudgetMS:        config.ThinkTimeBudgetMS,
+		LatencyGraceMS:           config.LatencyGraceMS,
+		LatencyCompensationCapMS: config.LatencyCompensationCapMS,
+		RuleFlags:                config.RuleFlags,
+		RulesVersion:             config.RulesVersion.Effective(),
+		DeckSpec:                 config.DeckSpec,
+		Qualification:            config.Qualification,
+		Rake:                     config.Rake,
+		RunItTimes:               config.RunItTimes,
+		Board:                    make([]Card, 0, 5),
+		Deck:                     make([]Card, 0, config.DeckSpec.Size()),
+		NextCardIndex:            0,
+		HoleCards:                make([]SeatCards, 0, len(seats)),
+		DealSequence:             make([]DealtCard, 0, 2*len(seats)+8),
+		ShowdownAwards:           make([]PotAward, 0, 4),
+		Seats:                    handSeats,
 	}, nil
 }
 
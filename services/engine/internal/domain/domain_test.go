@@ -1,7 +1,13 @@
 package domain
 
 import (
+	"bytes"
+	"encoding/json"
 	"errors"
+	"math"
+	"os"
+	"path/filepath"
+	"slices"
 	"testing"
 )
 
@@ -58,3 +64,365 @@ func TestTableConfigValidateRejectsZeroBlindAmounts(t *testing.T) {
 		}
 	})
 }
+
+func TestTableConfigValidateChecksAnteMode(t *testing.T) {
+	t.Parallel()
+
+	cfg := DefaultV0TableConfig()
+	cfg.Ante = 10
+	cfg.AnteMode = AnteButton
+	if err := cfg.Validate(); err != nil {
+		t.Fatalf("expected a button ante to validate, got %v", err)
+	}
+	cfg.AnteMode = "big_blind"
+	if err := cfg.Validate(); !errors.Is(err, ErrUnknownAnteMode) {
+		t.Fatalf("expected ErrUnknownAnteMode, got %v", err)
+	}
+}
+
+func TestTableConfigValidateChecksTimeoutAction(t *testing.T) {
+	t.Parallel()
+
+	cfg := DefaultV0TableConfig()
+	cfg.TimeoutAction = TimeoutFold
+	cfg.SitOutAfterTimeouts = 3
+	if err := cfg.Validate(); err != nil {
+		t.Fatalf("expected a fold timeout action to validate, got %v", err)
+	}
+	cfg.TimeoutAction = "call"
+	if err := cfg.Validate(); !errors.Is(err, ErrUnknownTimeoutAction) {
+		t.Fatalf("expected ErrUnknownTimeoutAction, got %v", err)
+	}
+}
+
+func TestTableConfigValidateChecksTimeBank(t *testing.T) {
+	t.Parallel()
+
+	cfg := DefaultV0TableConfig()
+	cfg.TimeBankMS = 30000
+	cfg.TimeBankRefill = TimeBankRefillPerHand
+	cfg.TimeBankRefillMS = 2000
+	if err := cfg.Validate(); err != nil {
+		t.Fatalf("expected a per-hand time bank to validate, got %v", err)
+	}
+	cfg.TimeBankRefill = "weekly"
+	if err := cfg.Validate(); !errors.Is(err, ErrUnknownTimeBankRefill) {
+		t.Fatalf("expected ErrUnknownTimeBankRefill, got %v", err)
+	}
+	cfg.TimeBankRefill = TimeBankRefillPerHand
+	cfg.TimeBankRefillMS = 0
+	if err := cfg.Validate(); !errors.Is(err, ErrInvalidTimeBank) {
+		t.Fatalf("expected a per-hand refill of nothing to fail, got %v", err)
+	}
+	cfg.TimeBankRefill = TimeBankRefillFull
+	cfg.ThinkTimeBudgetMS = 10000
+	if err := cfg.Validate(); !errors.Is(err, ErrInvalidTimeBank) {
+		t.Fatalf("expected a time bank on a budgeted table to fail, got %v", err)
+	}
+}
+
+func TestTableConfigValidateCapsLatencyCompensation(t *testing.T) {
+	t.Parallel()
+
+	cfg := DefaultV0TableConfig()
+	cfg.LatencyCompensationCapMS = MaxLatencyCompensationMS
+	if err := cfg.Validate(); err != nil {
+		t.Fatalf("expected the maximum cap to validate, got %v", err)
+	}
+	cfg.LatencyCompensationCapMS++
+	if err := cfg.Validate(); !errors.Is(err, ErrLatencyCapTooLong) {
+		t.Fatalf("expected ErrLatencyCapTooLong, got %v", err)
+	}
+}
+
+func TestTableConfigValidateRejectsAnInvertedBuyInRange(t *testing.T) {
+	t.Parallel()
+
+	cfg := DefaultV0TableConfig()
+	cfg.MinBuyIn = 2000
+	if err := cfg.Validate(); err != nil {
+		t.Fatalf("expected a minimum without a maximum to validate, got %v", err)
+	}
+	cfg.MaxBuyIn = 1000
+	if err := cfg.Validate(); !errors.Is(err, ErrInvalidBuyInRange) {
+		t.Fatalf("expected ErrInvalidBuyInRange, got %v", err)
+	}
+}
+
+func TestTableConfigValidateRejectsARakeAboveThePot(t *testing.T) {
+	t.Parallel()
+
+	cfg := DefaultV0TableConfig()
+	cfg.Rake = &RakeRule{BasisPoints: MaxRakeBasisPoints}
+	if err := cfg.Validate(); err != nil {
+		t.Fatalf("expected a rake of the whole pot to validate, got %v", err)
+	}
+	cfg.Rake.BasisPoints++
+	if err := cfg.Validate(); !errors.Is(err, ErrInvalidRake) {
+		t.Fatalf("expected ErrInvalidRake, got %v", err)
+	}
+}
+
+func TestTableConfigValidateChecksStraddles(t *testing.T) {
+	t.Parallel()
+
+	cfg := DefaultV0TableConfig()
+	cfg.Straddle = StraddleButton
+	if err := cfg.Validate(); err != nil || cfg.StraddleAmount() != 2*cfg.BigBlind {
+		t.Fatalf("expected a two big blind straddle, got %d (%v)", cfg.StraddleAmount(), err)
+	}
+	for _, broken := range []TableConfig{
+		{Straddle: "sleeper"},
+		{Straddle: StraddleUTG, StraddleMultiple: 1},
+		{Straddle: StraddleUTG, BettingStructure: BettingFixedLimit},
+		{StraddleMultiple: 3},
+	} {
+		cfg := DefaultV0TableConfig()
+		cfg.Straddle, cfg.StraddleMultiple = broken.Straddle, broken.StraddleMultiple
+		if broken.BettingStructure != "" {
+			cfg.BettingStructure = broken.BettingStructure
+		}
+		if err := cfg.Validate(); !errors.Is(err, ErrInvalidStraddle) {
+			t.Fatalf("expected ErrInvalidStraddle for %+v, got %v", broken, err)
+		}
+	}
+}
+
+func TestTableConfigValidateChecksRunItTimes(t *testing.T) {
+	t.Parallel()
+
+	cfg := DefaultV0TableConfig()
+	cfg.RunItTimes = MaxRunItTimes
+	if err := cfg.Validate(); err != nil {
+		t.Fatalf("expected %d runouts to validate, got %v", MaxRunItTimes, err)
+	}
+	cfg.RunItTimes = MaxRunItTimes + 1
+	if err := cfg.Validate(); !errors.Is(err, ErrInvalidRunItTimes) {
+		t.Fatalf("expected ErrInvalidRunItTimes above the max, got %v", err)
+	}
+
+	// A short deck without its deuces through fives cannot run four boards
+	// at a full table.
+	short := &DeckSpec{}
+	for rank := Rank(2); rank <= 5; rank++ {
+		for _, suit := range []Suit{SuitClubs, SuitDiamonds, SuitHearts, SuitSpades} {
+			short.Remove = append(short.Remove, NewCard(rank, suit))
+		}
+	}
+	cfg.RunItTimes, cfg.DeckSpec = MaxRunItTimes, short
+	if err := cfg.Validate(); !errors.Is(err, ErrInvalidRunItTimes) {
+		t.Fatalf("expected ErrInvalidRunItTimes for a %d card deck, got %v", short.Size(), err)
+	}
+}
+
+func TestParseRuleFlags_RoundTripsNames(t *testing.T) {
+	t.Parallel()
+
+	flags, err := ParseRuleFlags([]string{"antes", "straddle", "antes"})
+	if err != nil {
+		t.Fatalf("ParseRuleFlags failed: %v", err)
+	}
+	if !flags.Has(RuleAntes) || !flags.Has(RuleStraddle) || flags.Has(RuleInsurance) {
+		t.Fatalf("unexpected flags %v", flags.Names())
+	}
+	if names := flags.Names(); len(names) != 2 || names[0] != "straddle" || names[1] != "antes" {
+		t.Fatalf("expected names in declaration order, got %v", names)
+	}
+	if _, err := ParseRuleFlags([]string{"mixed_games"}); !errors.Is(err, ErrUnknownRuleFlag) {
+		t.Fatalf("expected ErrUnknownRuleFlag, got %v", err)
+	}
+
+	cfg := DefaultV0TableConfig()
+	cfg.RuleFlags = 1 << 20
+	if err := cfg.Validate(); !errors.Is(err, ErrUnknownRuleFlag) {
+		t.Fatalf("expected ErrUnknownRuleFlag for an unassigned bit, got %v", err)
+	}
+}
+
+func TestChips_CheckedAndSaturatingArithmetic(t *testing.T) {
+	t.Parallel()
+
+	var deep Chips = math.MaxUint32
+	if sum, err := deep.CheckedAdd(deep); err != nil || sum != 2*math.MaxUint32 {
+		t.Fatalf("expected a deep-stack sum past 32 bits, got %d, %v", sum, err)
+	}
+	if _, err := Chips(math.MaxUint64).CheckedAdd(1); !errors.Is(err, ErrChipOverflow) {
+		t.Fatalf("expected ErrChipOverflow, got %v", err)
+	}
+	if _, err := Chips(100).CheckedSub(101); !errors.Is(err, ErrChipUnderflow) {
+		t.Fatalf("expected ErrChipUnderflow, got %v", err)
+	}
+	if got := Chips(math.MaxUint64 - 1).SaturatingAdd(5); got != math.MaxUint64 {
+		t.Fatalf("expected SaturatingAdd to clamp, got %d", got)
+	}
+	if got := Chips(100).SaturatingSub(101); got != 0 {
+		t.Fatalf("expected SaturatingSub to clamp at zero, got %d", got)
+	}
+
+	var seat SeatState
+	if err := json.Unmarshal([]byte(`{"stack":8589934590}`), &seat); err != nil || seat.Stack != 2*math.MaxUint32 {
+		t.Fatalf("expected a deep stack to decode, got %d, %v", seat.Stack, err)
+	}
+	if err := json.Unmarshal([]byte(`{"stack":-1}`), &seat); err == nil {
+		t.Fatalf("expected a negative stack to be rejected")
+	}
+}
+
+func TestDeckSpec_BuildsAndValidates(t *testing.T) {
+	t.Parallel()
+
+	var nilSpec *DeckSpec
+	if nilSpec.Size() != 52 || len(nilSpec.Build().Cards) != 52 {
+		t.Fatal("expected a nil spec to be the standard deck")
+	}
+
+	shortDeck := &DeckSpec{}
+	for _, suit := range []Suit{SuitClubs, SuitDiamonds, SuitHearts, SuitSpades} {
+		for rank := Rank(2); rank <= 5; rank++ {
+			shortDeck.Remove = append(shortDeck.Remove, NewCard(rank, suit))
+		}
+	}
+	cards := shortDeck.Build().Cards
+	if len(cards) != 36 || shortDeck.Size() != 36 {
+		t.Fatalf("expected 36 cards, got %d (size %d)", len(cards), shortDeck.Size())
+	}
+	for _, card := range cards {
+		if card.Rank < 6 {
+			t.Fatalf("expected deuces through fives removed, found %+v", card)
+		}
+	}
+	if doubled := (&DeckSpec{Decks: 2}).Build().Cards; len(doubled) != 104 || doubled[0] != doubled[52] {
+		t.Fatalf("expected two standard decks back to back, got %d cards", len(doubled))
+	}
+
+	for name, spec := range map[string]*DeckSpec{
+		"too many decks": {Decks: MaxDecks + 1},
+		"unknown card":   {Remove: []Card{{Rank: 1, Suit: SuitClubs}}},
+		"duplicate":      {Remove: []Card{NewCard(14, SuitSpades), NewCard(14, SuitSpades)}},
+		"too few cards":  {Remove: cards},
+	} {
+		if err := spec.Validate(DefaultMaxSeats); !errors.Is(err, ErrInvalidDeckSpec) {
+			t.Fatalf("%s: expected ErrInvalidDeckSpec, got %v", name, err)
+		}
+	}
+
+	cfg := DefaultV0TableConfig()
+	cfg.DeckSpec = shortDeck
+	if err := cfg.Validate(); err != nil {
+		t.Fatalf("expected a short deck to seat %d players, got %v", cfg.MaxSeats, err)
+	}
+	cfg.DeckSpec = &DeckSpec{Decks: MaxDecks + 1}
+	if err := cfg.Validate(); !errors.Is(err, ErrInvalidDeckSpec) {
+		t.Fatalf("expected TableConfig.Validate to check the deck spec, got %v", err)
+	}
+}
+
+func TestWireGolden_HandState(t *testing.T) {
+	t.Parallel()
+
+	var state HandState
+	assertWireGolden(t, "hand_state.golden.json", &state)
+	if state.LastAggressorSeat == nil || *state.LastAggressorSeat != 1 {
+		t.Fatalf("expected last aggressor seat 1, got %v", state.LastAggressorSeat)
+	}
+}
+
+func TestWireGolden_TableConfig(t *testing.T) {
+	t.Parallel()
+
+	var cfg TableConfig
+	assertWireGolden(t, "table_config.golden.json", &cfg)
+	if err := cfg.Validate(); err != nil {
+		t.Fatalf("golden table config no longer validates: %v", err)
+	}
+}
+
+// assertWireGolden decodes testdata/name into target, rejecting unknown
+// fields, and checks that encoding it again reproduces the file exactly.
+func assertWireGolden(t *testing.T, name string, target any) {
+	t.Helper()
+	golden, err := os.ReadFile(filepath.Join("testdata", name))
+	if err != nil {
+		t.Fatalf("read golden failed: %v", err)
+	}
+	decoder := json.NewDecoder(bytes.NewReader(golden))
+	decoder.DisallowUnknownFields()
+	if err := decoder.Decode(target); err != nil {
+		t.Fatalf("decode %s failed: %v", name, err)
+	}
+	encoded, err := json.MarshalIndent(target, "", "  ")
+	if err != nil {
+		t.Fatalf("encode %s failed: %v", name, err)
+	}
+	if want := bytes.TrimSpace(golden); !bytes.Equal(encoded, want) {
+		t.Fatalf("%s no longer round-trips; wire changes need a docs/wire-schema.md update\nwant=%s\ngot=%s", name, want, encoded)
+	}
+}
+
+func TestHandState_ForSeatShowsOnlyWhatTheSeatMaySee(t *testing.T) {
+	t.Parallel()
+
+	hole := map[SeatNo][]Card{
+		1: {NewCard(14, SuitSpades), NewCard(13, SuitSpades)},
+		2: {NewCard(2, SuitClubs), NewCard(7, SuitDiamonds)},
+		3: {NewCard(10, SuitHearts), NewCard(10, SuitClubs)},
+	}
+	state := HandState{
+		Board: []Card{NewCard(4, SuitHearts)},
+		Deck:  []Card{NewCard(9, SuitSpades)},
+		HoleCards: []SeatCards{
+			{SeatNo: 1, Cards: hole[1]},
+			{SeatNo: 2, Cards: hole[2]},
+			{SeatNo: 3, Cards: hole[3]},
+		},
+		DealSequence: []DealtCard{
+			{DeckIndex: 0, Target: DealTargetHole, SeatNo: 1, Card: hole[1][0]},
+			{DeckIndex: 1, Target: DealTargetHole, SeatNo: 2, Card: hole[2][0]},
+			{DeckIndex: 2, Target: DealTargetHole, SeatNo: 3, Card: hole[3][0]},
+			{DeckIndex: 3, Target: DealTargetBurn, Card: NewCard(5, SuitClubs)},
+			{DeckIndex: 4, Target: DealTargetBoard, Card: NewCard(4, SuitHearts)},
+		},
+		ShowdownReveals: []ShowdownReveal{
+			{SeatNo: 3, Action: RevealShow, Reason: "first_to_show"},
+			{SeatNo: 2, Action: RevealMuck, Reason: "auto_muck"},
+		},
+	}
+
+	cases := []struct {
+		seat    SeatNo
+		visible []SeatNo
+	}{
+		{seat: 1, visible: []SeatNo{1, 3}},
+		{seat: 2, visible: []SeatNo{2, 3}},
+		{seat: 0, visible: []SeatNo{3}},
+	}
+	for _, tc := range cases {
+		view := state.ForSeat(tc.seat)
+		if view.Deck != nil {
+			t.Fatalf("seat %d: expected no deck, got %v", tc.seat, view.Deck)
+		}
+		for _, seatCards := range view.HoleCards {
+			want := 0
+			if slices.Contains(tc.visible, seatCards.SeatNo) {
+				want = 2
+			}
+			if len(seatCards.Cards) != want {
+				t.Fatalf("seat %d: expected %d cards for seat %d, got %v", tc.seat, want, seatCards.SeatNo, seatCards.Cards)
+			}
+		}
+		for _, dealt := range view.DealSequence {
+			if dealt.Target == DealTargetBurn || (dealt.Target == DealTargetHole && !slices.Contains(tc.visible, dealt.SeatNo)) {
+				t.Fatalf("seat %d: expected %+v hidden from the deal sequence", tc.seat, dealt)
+			}
+		}
+		if len(view.DealSequence) != len(tc.visible)+1 {
+			t.Fatalf("seat %d: expected the visible hole cards and the board dealt, got %+v", tc.seat, view.DealSequence)
+		}
+	}
+
+	view := state.ForSeat(1)
+	view.HoleCards[0].Cards[0] = NewCard(2, SuitHearts)
+	if state.HoleCards[0].Cards[0] != NewCard(14, SuitSpades) || len(state.Deck) != 1 {
+		t.Fatalf("expected the view not to write through to the hand, got %+v", state)
+	}
+}
@@ -0,0 +1,34 @@
+// Package embargo withholds bot telemetry from reads and exports until a
+// configurable period after the competition it was submitted in has ended,
+// so equity estimates and strategy tags cannot inform live opponents.
+package embargo
+
+import (
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// Policy embargoes telemetry until Period after the competition ends. The
+// zero Policy releases telemetry as soon as the competition is over.
+type Policy struct {
+	Period time.Duration
+}
+
+// Lifted reports whether telemetry from a competition that ended at endedAt
+// may be released at now. A competition that has not ended stays embargoed.
+func (p Policy) Lifted(endedAt *time.Time, now time.Time) bool {
+	if endedAt == nil {
+		return false
+	}
+	return !now.Before(endedAt.Add(max(p.Period, 0)))
+}
+
+// Release returns telemetry when the embargo on it has lifted and nil
+// otherwise.
+func (p Policy) Release(telemetry *domain.Telemetry, endedAt *time.Time, now time.Time) *domain.Telemetry {
+	if !p.Lifted(endedAt, now) {
+		return nil
+	}
+	return telemetry
+}
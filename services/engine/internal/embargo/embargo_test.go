@@ -0,0 +1,29 @@
+package embargo
+
+import (
+	"testing"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestPolicy_ReleasesTelemetryOnlyAfterThePeriod(t *testing.T) {
+	t.Parallel()
+
+	endedAt := time.Date(2026, 10, 16, 12, 0, 0, 0, time.UTC)
+	telemetry := &domain.Telemetry{Tags: []string{"value"}}
+	policy := Policy{Period: time.Hour}
+
+	if policy.Release(telemetry, nil, endedAt.Add(24*time.Hour)) != nil {
+		t.Fatal("expected telemetry to stay embargoed while the competition runs")
+	}
+	if policy.Release(telemetry, &endedAt, endedAt.Add(59*time.Minute)) != nil {
+		t.Fatal("expected telemetry to stay embargoed inside the period")
+	}
+	if policy.Release(telemetry, &endedAt, endedAt.Add(time.Hour)) != telemetry {
+		t.Fatal("expected telemetry to be released once the period has elapsed")
+	}
+	if !(Policy{}).Lifted(&endedAt, endedAt) {
+		t.Fatal("expected the zero policy to lift as soon as the competition ends")
+	}
+}
@@ -0,0 +1,138 @@
+// Package embedapi is the rules engine surface for embedders such as the
+// browser build in cmd/enginewasm. It only uses the pure domain, rules and
+// statemachine packages: callers supply the hand ID and shuffle seed, and
+// nothing reads the clock, the network or OS entropy.
+package embedapi
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/statemachine"
+)
+
+var ErrMissingHandID = errors.New("hand_id is required")
+
+// ReplayRequest deals a hand from Seed and plays Actions against it. An
+// imported hand passes its recorded Deal, which is dealt from the top of the
+// deck with Seed shuffling the rest; RepairDeal re-deals any impossible or
+// duplicate cards in it instead of rejecting the replay.
+type ReplayRequest struct {
+	HandID     string             `json:"hand_id"`
+	TableID    string             `json:"table_id"`
+	HandNo     uint64             `json:"hand_no"`
+	ButtonSeat domain.SeatNo      `json:"button_seat"`
+	Seats      []domain.SeatState `json:"seats"`
+	Config     domain.TableConfig `json:"config"`
+	Seed       int64              `json:"seed"`
+	Actions    []domain.Action    `json:"actions"`
+	Deal       []domain.DealtCard `json:"deal,omitempty"`
+	RepairDeal bool               `json:"repair_deal,omitempty"`
+}
+
+// ReplayResult holds the state after the deal and after each action. When
+// betting closes the last state is the resolved showdown, with the board run
+// out after an all-in. Repairs lists the cards a repaired deal changed,
+// which make the replay approximate.
+type ReplayResult struct {
+	States  []domain.HandState `json:"states"`
+	Repairs []rules.CardRepair `json:"repairs,omitempty"`
+	Error   string             `json:"error,omitempty"`
+}
+
+// ValidateResult is the state after a legal action, or why it is illegal.
+type ValidateResult struct {
+	Legal bool              `json:"legal"`
+	State *domain.HandState `json:"state,omitempty"`
+	Error string            `json:"error,omitempty"`
+}
+
+func Validate(state domain.HandState, action domain.Action) ValidateResult {
+	next, err := statemachine.ApplyAction(state, action)
+	if err != nil {
+		return ValidateResult{Error: err.Error()}
+	}
+	return ValidateResult{Legal: true, State: &next}
+}
+
+// Replay stops at the first illegal action. The result keeps the states
+// reached so far, and the error names the action's index.
+func Replay(req ReplayRequest) (ReplayResult, error) {
+	if req.HandID == "" {
+		return ReplayResult{}, ErrMissingHandID
+	}
+	var result ReplayResult
+	shuffler := rules.NewSeededShuffler(req.Seed)
+	if req.Deal != nil {
+		deal := req.Deal
+		if req.RepairDeal {
+			var err error
+			if deal, result.Repairs, err = rules.RepairDealScript(req.Config.DeckSpec, req.Deal, req.Seed); err != nil {
+				return ReplayResult{}, err
+			}
+		} else if err := rules.ValidateDealScript(req.Config.DeckSpec, deal); err != nil {
+			return ReplayResult{}, err
+		}
+		shuffler = rules.NewScriptedShuffler(deal, req.Seed)
+	}
+	engine, err := statemachine.NewHandEngine(statemachine.StartNewHandInput{
+		TableID:    req.TableID,
+		HandNo:     req.HandNo,
+		Seats:      req.Seats,
+		ButtonSeat: req.ButtonSeat,
+		Config:     req.Config,
+		Shuffler:   shuffler,
+		HandID:     req.HandID,
+	})
+	if err != nil {
+		return ReplayResult{}, err
+	}
+
+	result.States = []domain.HandState{engine.State()}
+	for i, action := range req.Actions {
+		if err := engine.Apply(action); err != nil {
+			return result, fmt.Errorf("action %d: %w", i, err)
+		}
+		result.States = append(result.States, engine.State())
+	}
+	return result, nil
+}
+
+// ValidateJSON takes {"state": HandState, "action": Action} and returns a
+// ValidateResult. Malformed input is reported in the result, never as a Go
+// error, so JavaScript callers only ever see JSON.
+func ValidateJSON(input []byte) []byte {
+	var req struct {
+		State  domain.HandState `json:"state"`
+		Action domain.Action    `json:"action"`
+	}
+	if err := json.Unmarshal(input, &req); err != nil {
+		return marshalResult(ValidateResult{Error: fmt.Sprintf("invalid request: %v", err)})
+	}
+	return marshalResult(Validate(req.State, req.Action))
+}
+
+// ReplayJSON takes a ReplayRequest and returns a ReplayResult, with any
+// failure in its error field.
+func ReplayJSON(input []byte) []byte {
+	var req ReplayRequest
+	if err := json.Unmarshal(input, &req); err != nil {
+		return marshalResult(ReplayResult{Error: fmt.Sprintf("invalid request: %v", err)})
+	}
+	result, err := Replay(req)
+	if err != nil {
+		result.Error = err.Error()
+	}
+	return marshalResult(result)
+}
+
+func marshalResult(v any) []byte {
+	out, err := json.Marshal(v)
+	if err != nil {
+		return []byte(`{"error":"failed to encode result"}`)
+	}
+	return out
+}
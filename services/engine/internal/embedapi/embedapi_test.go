@@ -0,0 +1,167 @@
+package embedapi
+
+import (
+	"encoding/json"
+	"errors"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/statemachine"
+)
+
+func headsUpReplay(t *testing.T, kinds ...domain.ActionKind) ReplayRequest {
+	t.Helper()
+	cfg := domain.DefaultV0TableConfig()
+	req := ReplayRequest{
+		HandID:     "hand-browser-1",
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: 1,
+		Seats:      []domain.SeatState{domain.NewSeatState(1, cfg.StartingStack), domain.NewSeatState(2, cfg.StartingStack)},
+		Config:     cfg,
+		Seed:       42,
+	}
+	for _, kind := range kinds {
+		action, err := domain.NewAction(kind, nil)
+		if err != nil {
+			t.Fatalf("NewAction failed: %v", err)
+		}
+		req.Actions = append(req.Actions, action)
+	}
+	return req
+}
+
+func TestReplay_IsDeterministicAndPlaysToShowdown(t *testing.T) {
+	t.Parallel()
+
+	req := headsUpReplay(t, domain.ActionCall, domain.ActionCheck,
+		domain.ActionCheck, domain.ActionCheck,
+		domain.ActionCheck, domain.ActionCheck,
+		domain.ActionCheck, domain.ActionCheck,
+	)
+	first, err := Replay(req)
+	if err != nil {
+		t.Fatalf("Replay failed: %v", err)
+	}
+	second, err := Replay(req)
+	if err != nil {
+		t.Fatalf("Replay failed: %v", err)
+	}
+	if !reflect.DeepEqual(first, second) {
+		t.Fatal("expected the same seed and hand ID to replay identically")
+	}
+
+	if len(first.States) != len(req.Actions)+1 {
+		t.Fatalf("expected %d states, got %d", len(req.Actions)+1, len(first.States))
+	}
+	if first.States[0].HandID != "hand-browser-1" {
+		t.Fatalf("expected the supplied hand ID, got %q", first.States[0].HandID)
+	}
+	final := first.States[len(first.States)-1]
+	if final.Phase != domain.HandPhaseComplete || len(final.Board) != 5 || len(final.ShowdownAwards) == 0 {
+		t.Fatalf("expected a resolved showdown, got phase=%s board=%d awards=%+v", final.Phase, len(final.Board), final.ShowdownAwards)
+	}
+}
+
+func TestReplay_StopsAtFirstIllegalAction(t *testing.T) {
+	t.Parallel()
+
+	// The small blind faces the big blind and cannot check.
+	result, err := Replay(headsUpReplay(t, domain.ActionCheck))
+	if !errors.Is(err, statemachine.ErrIllegalAction) || !strings.Contains(err.Error(), "action 0") {
+		t.Fatalf("expected an illegal action at index 0, got %v", err)
+	}
+	if len(result.States) != 1 {
+		t.Fatalf("expected only the dealt state, got %d states", len(result.States))
+	}
+
+	req := headsUpReplay(t)
+	req.HandID = ""
+	if _, err := Replay(req); !errors.Is(err, ErrMissingHandID) {
+		t.Fatalf("expected ErrMissingHandID, got %v", err)
+	}
+}
+
+func TestValidateJSON_ReportsLegalityAsJSON(t *testing.T) {
+	t.Parallel()
+
+	dealt, err := Replay(headsUpReplay(t))
+	if err != nil {
+		t.Fatalf("Replay failed: %v", err)
+	}
+	for _, tc := range []struct {
+		action domain.ActionKind
+		legal  bool
+	}{
+		{action: domain.ActionCall, legal: true},
+		{action: domain.ActionCheck, legal: false},
+	} {
+		input, err := json.Marshal(map[string]any{"state": dealt.States[0], "action": domain.Action{Kind: tc.action}})
+		if err != nil {
+			t.Fatalf("marshal request failed: %v", err)
+		}
+		var result ValidateResult
+		if err := json.Unmarshal(ValidateJSON(input), &result); err != nil {
+			t.Fatalf("decode result failed: %v", err)
+		}
+		if result.Legal != tc.legal || (result.State != nil) != tc.legal {
+			t.Fatalf("expected %s legal=%v, got %+v", tc.action, tc.legal, result)
+		}
+	}
+
+	var result ValidateResult
+	if err := json.Unmarshal(ValidateJSON([]byte(`{"state":`)), &result); err != nil {
+		t.Fatalf("decode result failed: %v", err)
+	}
+	if result.Legal || !strings.HasPrefix(result.Error, "invalid request") {
+		t.Fatalf("expected malformed input reported as JSON, got %+v", result)
+	}
+}
+
+func TestReplay_DealsAnImportedDealAndRepairsDuplicates(t *testing.T) {
+	t.Parallel()
+
+	req := headsUpReplay(t, domain.ActionCall, domain.ActionCheck,
+		domain.ActionCheck, domain.ActionCheck,
+		domain.ActionCheck, domain.ActionCheck,
+		domain.ActionCheck, domain.ActionCheck,
+	)
+	original, err := Replay(req)
+	if err != nil {
+		t.Fatalf("Replay failed: %v", err)
+	}
+	final := original.States[len(original.States)-1]
+
+	req.Deal = final.DealSequence
+	req.Seed = 7
+	imported, err := Replay(req)
+	if err != nil {
+		t.Fatalf("Replay of the imported deal failed: %v", err)
+	}
+	got := imported.States[len(imported.States)-1]
+	if !reflect.DeepEqual(got.Board, final.Board) || !reflect.DeepEqual(got.HoleCards, final.HoleCards) {
+		t.Fatalf("expected the imported deal to replay the same cards, got board %v", got.Board)
+	}
+
+	req.Deal = append([]domain.DealtCard(nil), final.DealSequence...)
+	req.Deal[5].Card = req.Deal[0].Card
+	var scriptErr *rules.DealScriptError
+	if _, err := Replay(req); !errors.As(err, &scriptErr) || scriptErr.Index != 5 || scriptErr.Card != req.Deal[0].Card {
+		t.Fatalf("expected a duplicate card at event 5, got %v", err)
+	}
+
+	req.RepairDeal = true
+	repaired, err := Replay(req)
+	if err != nil {
+		t.Fatalf("Replay with repair failed: %v", err)
+	}
+	if len(repaired.Repairs) != 1 || repaired.Repairs[0].Index != 5 || repaired.Repairs[0].To == req.Deal[0].Card {
+		t.Fatalf("expected event 5 re-dealt, got %+v", repaired.Repairs)
+	}
+	if board := repaired.States[len(repaired.States)-1].Board; board[0] != repaired.Repairs[0].To {
+		t.Fatalf("expected the repaired card to open the flop, got %v", board)
+	}
+}
@@ -0,0 +1,146 @@
+package equity
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"math/big"
+	"strings"
+)
+
+var (
+	ErrZeroDenominator = errors.New("ratio denominator must be non-zero")
+	ErrInvalidRatio    = errors.New("invalid ratio")
+)
+
+// Ratio is an exact, immutable rational number. Probabilities and equities
+// that feed chip settlement are kept as Ratios so results never depend on
+// floating-point rounding. The zero value is 0.
+type Ratio struct {
+	r *big.Rat
+}
+
+func NewRatio(num, den int64) (Ratio, error) {
+	if den == 0 {
+		return Ratio{}, ErrZeroDenominator
+	}
+	return Ratio{r: big.NewRat(num, den)}, nil
+}
+
+// FromCounts returns hits/total, e.g. the share of enumerated runouts a hand
+// wins.
+func FromCounts(hits, total uint64) (Ratio, error) {
+	if total == 0 {
+		return Ratio{}, ErrZeroDenominator
+	}
+	r := new(big.Rat).SetFrac(new(big.Int).SetUint64(hits), new(big.Int).SetUint64(total))
+	return Ratio{r: r}, nil
+}
+
+func Zero() Ratio {
+	return Ratio{}
+}
+
+func One() Ratio {
+	return Ratio{r: big.NewRat(1, 1)}
+}
+
+// ParseRatio accepts "num/den" or an integer.
+func ParseRatio(raw string) (Ratio, error) {
+	raw = strings.TrimSpace(raw)
+	if raw == "" || strings.ContainsAny(raw, ".eE") {
+		return Ratio{}, fmt.Errorf("%w: %q", ErrInvalidRatio, raw)
+	}
+	if strings.HasSuffix(raw, "/0") {
+		return Ratio{}, ErrZeroDenominator
+	}
+	r, ok := new(big.Rat).SetString(raw)
+	if !ok {
+		return Ratio{}, fmt.Errorf("%w: %q", ErrInvalidRatio, raw)
+	}
+	return Ratio{r: r}, nil
+}
+
+func (a Ratio) rat() *big.Rat {
+	if a.r == nil {
+		return new(big.Rat)
+	}
+	return a.r
+}
+
+func (a Ratio) Add(b Ratio) Ratio {
+	return Ratio{r: new(big.Rat).Add(a.rat(), b.rat())}
+}
+
+func (a Ratio) Sub(b Ratio) Ratio {
+	return Ratio{r: new(big.Rat).Sub(a.rat(), b.rat())}
+}
+
+func (a Ratio) Mul(b Ratio) Ratio {
+	return Ratio{r: new(big.Rat).Mul(a.rat(), b.rat())}
+}
+
+func (a Ratio) Quo(b Ratio) (Ratio, error) {
+	if b.IsZero() {
+		return Ratio{}, ErrZeroDenominator
+	}
+	return Ratio{r: new(big.Rat).Quo(a.rat(), b.rat())}, nil
+}
+
+func (a Ratio) Cmp(b Ratio) int {
+	return a.rat().Cmp(b.rat())
+}
+
+func (a Ratio) IsZero() bool {
+	return a.rat().Sign() == 0
+}
+
+func (a Ratio) Sign() int {
+	return a.rat().Sign()
+}
+
+// Num and Denom return the reduced numerator and positive denominator.
+func (a Ratio) Num() *big.Int {
+	return new(big.Int).Set(a.rat().Num())
+}
+
+func (a Ratio) Denom() *big.Int {
+	return new(big.Int).Set(a.rat().Denom())
+}
+
+// String renders the reduced fraction, e.g. "3/8"; integers render as "n/1".
+func (a Ratio) String() string {
+	return a.rat().String()
+}
+
+// Decimal renders the value rounded half away from zero to the given number
+// of places. It is for display only; settlement must use the Ratio itself.
+func (a Ratio) Decimal(places int) string {
+	return a.rat().FloatString(places)
+}
+
+// MulChips returns floor(a * chips) and the exact remainder fraction that was
+// dropped. a must be non-negative.
+func (a Ratio) MulChips(chips uint32) (uint32, Ratio) {
+	product := new(big.Rat).Mul(a.rat(), new(big.Rat).SetInt64(int64(chips)))
+	whole := new(big.Int).Quo(product.Num(), product.Denom())
+	remainder := new(big.Rat).Sub(product, new(big.Rat).SetInt(whole))
+	return uint32(whole.Uint64()), Ratio{r: remainder}
+}
+
+func (a Ratio) MarshalJSON() ([]byte, error) {
+	return json.Marshal(a.String())
+}
+
+func (a *Ratio) UnmarshalJSON(data []byte) error {
+	var raw string
+	if err := json.Unmarshal(data, &raw); err != nil {
+		return fmt.Errorf("%w: ratios are encoded as \"num/den\" strings", ErrInvalidRatio)
+	}
+	parsed, err := ParseRatio(raw)
+	if err != nil {
+		return err
+	}
+	*a = parsed
+	return nil
+}
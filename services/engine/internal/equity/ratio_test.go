@@ -0,0 +1,124 @@
+package equity
+
+import (
+	"encoding/json"
+	"errors"
+	"testing"
+)
+
+func TestRatioArithmeticIsExact(t *testing.T) {
+	t.Parallel()
+
+	third := mustRatio(t, 1, 3)
+	sum := third.Add(third).Add(third)
+	if sum.Cmp(One()) != 0 {
+		t.Fatalf("expected 1/3 * 3 to equal 1 exactly, got %s", sum)
+	}
+
+	tenth := mustRatio(t, 1, 10)
+	total := Zero()
+	for i := 0; i < 10; i++ {
+		total = total.Add(tenth)
+	}
+	if total.Cmp(One()) != 0 {
+		t.Fatalf("expected ten tenths to equal 1 exactly, got %s", total)
+	}
+
+	if got := mustRatio(t, 6, 16).String(); got != "3/8" {
+		t.Fatalf("expected reduced 3/8, got %s", got)
+	}
+	if got := mustRatio(t, 2, 3).Decimal(4); got != "0.6667" {
+		t.Fatalf("expected 0.6667, got %s", got)
+	}
+	if _, err := NewRatio(1, 0); !errors.Is(err, ErrZeroDenominator) {
+		t.Fatalf("expected ErrZeroDenominator, got %v", err)
+	}
+	if _, err := third.Quo(Zero()); !errors.Is(err, ErrZeroDenominator) {
+		t.Fatalf("expected ErrZeroDenominator for division by zero, got %v", err)
+	}
+}
+
+func TestRatioJSONRoundTrip(t *testing.T) {
+	t.Parallel()
+
+	in := struct {
+		Equity Ratio `json:"equity"`
+	}{Equity: mustRatio(t, 990, 1980)}
+	raw, err := json.Marshal(in)
+	if err != nil {
+		t.Fatalf("Marshal failed: %v", err)
+	}
+	if string(raw) != `{"equity":"1/2"}` {
+		t.Fatalf("unexpected encoding %s", raw)
+	}
+
+	var out struct {
+		Equity Ratio `json:"equity"`
+	}
+	if err := json.Unmarshal(raw, &out); err != nil {
+		t.Fatalf("Unmarshal failed: %v", err)
+	}
+	if out.Equity.Cmp(in.Equity) != 0 {
+		t.Fatalf("expected %s after round trip, got %s", in.Equity, out.Equity)
+	}
+
+	for _, bad := range []string{`0.5`, `"0.5"`, `"1e-1"`, `"1/0"`, `"abc"`} {
+		var r Ratio
+		if err := json.Unmarshal([]byte(bad), &r); err == nil {
+			t.Fatalf("expected error decoding %s", bad)
+		}
+	}
+}
+
+func TestSplitChipsIsDeterministic(t *testing.T) {
+	t.Parallel()
+
+	third := mustRatio(t, 1, 3)
+	got, err := SplitChips(100, []Ratio{third, third, third})
+	if err != nil {
+		t.Fatalf("SplitChips failed: %v", err)
+	}
+	if got[0] != 34 || got[1] != 33 || got[2] != 33 {
+		t.Fatalf("expected odd chip to lowest index, got %v", got)
+	}
+
+	// 0.7 and 0.3 of 15 are 10.5 and 4.5; the tie goes to the first share.
+	got, err = SplitChips(15, []Ratio{mustRatio(t, 7, 10), mustRatio(t, 3, 10)})
+	if err != nil {
+		t.Fatalf("SplitChips failed: %v", err)
+	}
+	if got[0] != 11 || got[1] != 4 {
+		t.Fatalf("expected [11 4], got %v", got)
+	}
+
+	got, err = SplitChips(10, []Ratio{mustRatio(t, 1, 8), mustRatio(t, 7, 8)})
+	if err != nil {
+		t.Fatalf("SplitChips failed: %v", err)
+	}
+	if got[0] != 1 || got[1] != 9 {
+		t.Fatalf("expected largest remainder to take the odd chip, got %v", got)
+	}
+}
+
+func TestSplitChipsRejectsInvalidShares(t *testing.T) {
+	t.Parallel()
+
+	if _, err := SplitChips(10, nil); !errors.Is(err, ErrNoShares) {
+		t.Fatalf("expected ErrNoShares, got %v", err)
+	}
+	if _, err := SplitChips(10, []Ratio{mustRatio(t, 1, 2), mustRatio(t, 1, 3)}); !errors.Is(err, ErrSharesNotNormalized) {
+		t.Fatalf("expected ErrSharesNotNormalized, got %v", err)
+	}
+	if _, err := SplitChips(10, []Ratio{mustRatio(t, 3, 2), mustRatio(t, -1, 2)}); !errors.Is(err, ErrNegativeShare) {
+		t.Fatalf("expected ErrNegativeShare, got %v", err)
+	}
+}
+
+func mustRatio(t *testing.T, num, den int64) Ratio {
+	t.Helper()
+	r, err := NewRatio(num, den)
+	if err != nil {
+		t.Fatalf("NewRatio failed: %v", err)
+	}
+	return r
+}
@@ -0,0 +1,231 @@
+package equity
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+var (
+	ErrRunoutUnsupportedBoard = errors.New("runout tree needs a flop or turn board")
+	ErrRunoutNotContested     = errors.New("runout tree needs at least two contesting seats")
+)
+
+// RunoutTree is every way the board can finish from an all-in spot. Cards
+// are drawn from those not on the board or in a contesting seat's hand, so
+// the branches under any node are equally likely.
+type RunoutTree struct {
+	HandID   string         `json:"hand_id"`
+	Board    []domain.Card  `json:"board"`
+	Runouts  int            `json:"runouts"`
+	Equity   []SeatEquity   `json:"equity"`
+	Branches []RunoutBranch `json:"branches"`
+}
+
+// RunoutBranch is one turn or river card. Probability is conditional on the
+// parent branch. River branches carry the pots exactly as the showdown would
+// award them, and Winners are the main pot winners.
+type RunoutBranch struct {
+	Street      domain.Street     `json:"street"`
+	Card        domain.Card       `json:"card"`
+	Probability Ratio             `json:"probability"`
+	Equity      []SeatEquity      `json:"equity"`
+	Winners     []domain.SeatNo   `json:"winners,omitempty"`
+	Awards      []domain.PotAward `json:"awards,omitempty"`
+	Branches    []RunoutBranch    `json:"branches,omitempty"`
+}
+
+// SeatEquity is a contesting seat's expected share of the chips awarded at
+// showdown. Shares at a node sum to 1 and can be passed to SplitChips.
+type SeatEquity struct {
+	SeatNo domain.SeatNo `json:"seat_no"`
+	Share  Ratio         `json:"share"`
+}
+
+// BuildRunoutTree enumerates the remaining turn and river cards for state,
+// which must have betting closed on the flop or turn.
+func BuildRunoutTree(state domain.HandState) (RunoutTree, error) {
+	if len(state.Board) < 3 || len(state.Board) > 4 {
+		return RunoutTree{}, fmt.Errorf("%w: board has %d cards", ErrRunoutUnsupportedBoard, len(state.Board))
+	}
+	contesting := contestingSeats(state)
+	if len(contesting) < 2 {
+		return RunoutTree{}, fmt.Errorf("%w: got %d", ErrRunoutNotContested, len(contesting))
+	}
+
+	unseen := unseenCards(state, contesting)
+	branches, won, err := expandRunout(state, contesting, partialHands(state, contesting), unseen)
+	if err != nil {
+		return RunoutTree{}, err
+	}
+	shares, err := seatShares(contesting, won)
+	if err != nil {
+		return RunoutTree{}, err
+	}
+	runouts := len(unseen)
+	if len(state.Board) == 3 {
+		runouts *= len(unseen) - 1
+	}
+	return RunoutTree{
+		HandID:   state.HandID,
+		Board:    append([]domain.Card(nil), state.Board...),
+		Runouts:  runouts,
+		Equity:   shares,
+		Branches: branches,
+	}, nil
+}
+
+// expandRunout deals each unseen card onto the board in turn. Alongside the
+// branches it returns the chips each contesting seat wins summed over every
+// river below them; all siblings have the same number of rivers, so the sums
+// weight the branches equally. hands carries each contesting seat's
+// evaluation of the board so far, so a river only scores the hands that use
+// its card.
+func expandRunout(state domain.HandState, contesting []domain.SeatNo, hands []rules.PartialHand, unseen []domain.Card) ([]RunoutBranch, []uint64, error) {
+	street := domain.StreetTurn
+	if len(state.Board) == 4 {
+		street = domain.StreetRiver
+	}
+	probability, err := FromCounts(1, uint64(len(unseen)))
+	if err != nil {
+		return nil, nil, err
+	}
+
+	branches := make([]RunoutBranch, 0, len(unseen))
+	totals := make([]uint64, len(contesting))
+	for i, card := range unseen {
+		next := state
+		next.Board = append(append([]domain.Card(nil), state.Board...), card)
+		branch := RunoutBranch{Street: street, Card: card, Probability: probability}
+		nextHands := make([]rules.PartialHand, len(hands))
+		for j, hand := range hands {
+			nextHands[j] = hand.Add(card)
+		}
+
+		var won []uint64
+		if street == domain.StreetRiver {
+			won, branch.Winners, branch.Awards, err = showdownChips(next, contesting, nextHands)
+		} else {
+			rest := append(append([]domain.Card(nil), unseen[:i]...), unseen[i+1:]...)
+			branch.Branches, won, err = expandRunout(next, contesting, nextHands, rest)
+		}
+		if err != nil {
+			return nil, nil, err
+		}
+		if branch.Equity, err = seatShares(contesting, won); err != nil {
+			return nil, nil, err
+		}
+		for j := range totals {
+			totals[j] += won[j]
+		}
+		branches = append(branches, branch)
+	}
+	return branches, totals, nil
+}
+
+func showdownChips(state domain.HandState, contesting []domain.SeatNo, hands []rules.PartialHand) ([]uint64, []domain.SeatNo, []domain.PotAward, error) {
+	input := state
+	input.Seats = append([]domain.SeatState(nil), state.Seats...)
+	// Every extra board has the same equity as the first, so the tree runs
+	// one board even for a hand run out more than once.
+	input.Runouts = nil
+	ranks := make(map[domain.SeatNo]rules.HandRank, len(contesting))
+	for i, seatNo := range contesting {
+		ranks[seatNo] = hands[i].Best()
+	}
+	resolved, awards, err := rules.ResolvePotsWithRanks(input, ranks)
+	if err != nil {
+		return nil, nil, nil, err
+	}
+
+	won := make([]uint64, len(contesting))
+	for i, seat := range resolved.Seats {
+		if j := slices.Index(contesting, seat.SeatNo); j >= 0 {
+			won[j] = uint64(seat.Stack - state.Seats[i].Stack)
+		}
+	}
+	var winners []domain.SeatNo
+	for _, award := range awards {
+		if award.Reason == "main_pot" {
+			winners = append([]domain.SeatNo(nil), award.Seats...)
+		}
+	}
+	return won, winners, awards, nil
+}
+
+func seatShares(contesting []domain.SeatNo, won []uint64) ([]SeatEquity, error) {
+	total := uint64(0)
+	for _, chips := range won {
+		total += chips
+	}
+	shares := make([]SeatEquity, len(contesting))
+	for i, seatNo := range contesting {
+		share, err := FromCounts(won[i], total)
+		if err != nil {
+			return nil, err
+		}
+		shares[i] = SeatEquity{SeatNo: seatNo, Share: share}
+	}
+	return shares, nil
+}
+
+// contestingSeats lists, in seat order, the live seats holding two hole
+// cards.
+func contestingSeats(state domain.HandState) []domain.SeatNo {
+	holeCount := make(map[domain.SeatNo]int, len(state.HoleCards))
+	for _, seatCards := range state.HoleCards {
+		holeCount[seatCards.SeatNo] = len(seatCards.Cards)
+	}
+	seats := make([]domain.SeatNo, 0, len(state.Seats))
+	for _, seat := range state.Seats {
+		if seat.IsActive() && holeCount[seat.SeatNo] == 2 {
+			seats = append(seats, seat.SeatNo)
+		}
+	}
+	slices.Sort(seats)
+	return seats
+}
+
+// partialHands evaluates each contesting seat's hole cards with the board
+// dealt so far, in contesting order.
+func partialHands(state domain.HandState, contesting []domain.SeatNo) []rules.PartialHand {
+	hands := make([]rules.PartialHand, len(contesting))
+	for _, seatCards := range state.HoleCards {
+		if i := slices.Index(contesting, seatCards.SeatNo); i >= 0 {
+			hands[i] = rules.NewPartialHand(seatCards.Cards, state.Board)
+		}
+	}
+	return hands
+}
+
+// unseenCards is the table's deck minus the board and the hole cards of the
+// live seats, in deck order. Folded hands were never shown, so their cards stay
+// in. A multi-deck spec holds several copies of a card, so only as many
+// copies as were seen are taken out.
+func unseenCards(state domain.HandState, live []domain.SeatNo) []domain.Card {
+	seen := make(map[domain.Card]int, len(state.Board)+2*len(state.HoleCards))
+	for _, card := range state.Board {
+		seen[card]++
+	}
+	for _, seatCards := range state.HoleCards {
+		if !slices.Contains(live, seatCards.SeatNo) {
+			continue
+		}
+		for _, card := range seatCards.Cards {
+			seen[card]++
+		}
+	}
+	deck := state.DeckSpec.Build().Cards
+	unseen := make([]domain.Card, 0, len(deck))
+	for _, card := range deck {
+		if seen[card] > 0 {
+			seen[card]--
+			continue
+		}
+		unseen = append(unseen, card)
+	}
+	return unseen
+}
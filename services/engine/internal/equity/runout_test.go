@@ -0,0 +1,110 @@
+package equity
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func card(rank domain.Rank, suit domain.Suit) domain.Card {
+	return domain.NewCard(rank, suit)
+}
+
+// allInSpot is aces against kings, both all-in for 100, on the given board.
+func allInSpot(board ...domain.Card) domain.HandState {
+	return domain.HandState{
+		HandID:     "hand-1",
+		ButtonSeat: 1,
+		Board:      board,
+		Seats: []domain.SeatState{
+			{SeatNo: 1, TotalCommitted: 100, Status: domain.SeatStatusActive},
+			{SeatNo: 2, TotalCommitted: 100, Status: domain.SeatStatusActive},
+			{SeatNo: 3, TotalCommitted: 0, Stack: 500, Folded: true, Status: domain.SeatStatusActive},
+		},
+		HoleCards: []domain.SeatCards{
+			{SeatNo: 1, Cards: []domain.Card{card(14, domain.SuitSpades), card(14, domain.SuitHearts)}},
+			{SeatNo: 2, Cards: []domain.Card{card(13, domain.SuitSpades), card(13, domain.SuitHearts)}},
+			{SeatNo: 3, Cards: []domain.Card{card(2, domain.SuitSpades), card(3, domain.SuitHearts)}},
+		},
+	}
+}
+
+func TestBuildRunoutTree_TurnSpotCountsOuts(t *testing.T) {
+	t.Parallel()
+
+	tree, err := BuildRunoutTree(allInSpot(
+		card(2, domain.SuitClubs), card(7, domain.SuitDiamonds), card(9, domain.SuitHearts), card(11, domain.SuitClubs),
+	))
+	if err != nil {
+		t.Fatalf("BuildRunoutTree failed: %v", err)
+	}
+	// The folded seat's cards were never shown, so they stay in the deck.
+	if tree.Runouts != 44 || len(tree.Branches) != 44 {
+		t.Fatalf("expected 44 rivers, got runouts=%d branches=%d", tree.Runouts, len(tree.Branches))
+	}
+	if got := tree.Equity[1].Share.String(); got != "1/22" {
+		t.Fatalf("expected kings to hit two outs in 44, got %s", got)
+	}
+
+	kings := 0
+	for _, branch := range tree.Branches {
+		if branch.Street != domain.StreetRiver || branch.Probability.String() != "1/44" {
+			t.Fatalf("unexpected river branch %+v", branch)
+		}
+		if len(branch.Awards) != 1 || branch.Awards[0].Amount != 200 || len(branch.Winners) != 1 {
+			t.Fatalf("expected one 200-chip main pot with one winner, got %+v", branch)
+		}
+		if branch.Winners[0] == 2 {
+			kings++
+			if branch.Card.Rank != 13 || branch.Equity[1].Share.Cmp(One()) != 0 {
+				t.Fatalf("expected only a king to win for seat 2, got %+v", branch)
+			}
+		}
+	}
+	if kings != 2 {
+		t.Fatalf("expected two winning rivers for kings, got %d", kings)
+	}
+}
+
+func TestBuildRunoutTree_FlopSpotAveragesTurnBranches(t *testing.T) {
+	t.Parallel()
+
+	tree, err := BuildRunoutTree(allInSpot(
+		card(2, domain.SuitClubs), card(7, domain.SuitDiamonds), card(9, domain.SuitHearts),
+	))
+	if err != nil {
+		t.Fatalf("BuildRunoutTree failed: %v", err)
+	}
+	if tree.Runouts != 45*44 || len(tree.Branches) != 45 {
+		t.Fatalf("expected 45 turns of 44 rivers, got runouts=%d branches=%d", tree.Runouts, len(tree.Branches))
+	}
+
+	mean := Zero()
+	for _, branch := range tree.Branches {
+		if branch.Street != domain.StreetTurn || len(branch.Branches) != 44 || branch.Awards != nil {
+			t.Fatalf("unexpected turn branch street=%s rivers=%d", branch.Street, len(branch.Branches))
+		}
+		mean = mean.Add(branch.Probability.Mul(branch.Equity[0].Share))
+	}
+	if mean.Cmp(tree.Equity[0].Share) != 0 {
+		t.Fatalf("expected root equity %s to be the mean of the turns, got %s", tree.Equity[0].Share, mean)
+	}
+	if sum := tree.Equity[0].Share.Add(tree.Equity[1].Share); sum.Cmp(One()) != 0 {
+		t.Fatalf("expected shares to sum to 1, got %s", sum)
+	}
+}
+
+func TestBuildRunoutTree_RejectsSpotsWithoutARunout(t *testing.T) {
+	t.Parallel()
+
+	if _, err := BuildRunoutTree(allInSpot()); !errors.Is(err, ErrRunoutUnsupportedBoard) {
+		t.Fatalf("expected ErrRunoutUnsupportedBoard for a preflop spot, got %v", err)
+	}
+
+	state := allInSpot(card(2, domain.SuitClubs), card(7, domain.SuitDiamonds), card(9, domain.SuitHearts))
+	state.Seats[1].Folded = true
+	if _, err := BuildRunoutTree(state); !errors.Is(err, ErrRunoutNotContested) {
+		t.Fatalf("expected ErrRunoutNotContested, got %v", err)
+	}
+}
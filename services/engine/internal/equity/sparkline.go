@@ -0,0 +1,173 @@
+package equity
+
+import (
+	"hash/fnv"
+	"math/rand"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+// SparklineSamples is how many boards a preflop sparkline point deals.
+const SparklineSamples = 2000
+
+// sparklineSplit is what one board is worth to its winners; it divides
+// evenly between any number of tied seats up to sixteen.
+const sparklineSplit = 720720
+
+// sparklineStreets are the streets a sparkline has a point for, with the
+// board cards dealt when each one starts.
+type sparklineStreet struct {
+	street domain.Street
+	board  int
+}
+
+var sparklineStreets = []sparklineStreet{
+	{domain.StreetPreflop, 0},
+	{domain.StreetFlop, 3},
+	{domain.StreetTurn, 4},
+	{domain.StreetRiver, 5},
+}
+
+// StreetEquity is every live seat's chance of winning the hand, ties split,
+// as a street starts. Runouts is how many boards the point was scored on:
+// every way the board can finish once the flop is out, so Exact, and
+// SparklineSamples random boards before it.
+type StreetEquity struct {
+	Street  domain.Street `json:"street"`
+	Board   []domain.Card `json:"board"`
+	Runouts int           `json:"runouts"`
+	Exact   bool          `json:"exact,omitempty"`
+	Equity  []SeatEquity  `json:"equity"`
+}
+
+// Sparkline scores a finished hand at the start of every street it dealt,
+// for replays to chart without redoing the sums. A seat is live on a street
+// it was dealt into and had not folded before; folds holds the street each
+// folded seat folded on. Cards of seats that had already folded stay in the
+// deck, as they were never shown. The points stop at the first street with
+// fewer than two live seats, and a hand run out more than once is scored
+// on its first board. Samples are drawn from a source seeded by the
+// hand ID, so a hand always scores the same.
+func Sparkline(final domain.HandState, folds map[domain.SeatNo]domain.Street) ([]StreetEquity, error) {
+	hash := fnv.New64a()
+	_, _ = hash.Write([]byte(final.HandID))
+	rng := rand.New(rand.NewSource(int64(hash.Sum64())))
+
+	holes := make(map[domain.SeatNo][]domain.Card, len(final.HoleCards))
+	for _, seatCards := range final.HoleCards {
+		if len(seatCards.Cards) == 2 {
+			holes[seatCards.SeatNo] = seatCards.Cards
+		}
+	}
+
+	var points []StreetEquity
+	for i, point := range sparklineStreets {
+		if len(final.Board) < point.board {
+			break
+		}
+		live := liveSeats(final, holes, folds, i)
+		if len(live) < 2 {
+			break
+		}
+		spot := final
+		spot.Board = final.Board[:point.board]
+		unseen := unseenCards(spot, live)
+
+		won := make([]uint64, len(live))
+		runouts := 0
+		score := func(board []domain.Card) {
+			runouts++
+			scoreBoard(won, live, holes, board)
+		}
+		board := make([]domain.Card, point.board, 5)
+		copy(board, spot.Board)
+		if point.board == 0 {
+			for range SparklineSamples {
+				score(sampleBoard(rng, unseen, board))
+			}
+		} else {
+			eachBoard(unseen, 5-point.board, board, score)
+		}
+
+		shares, err := seatShares(live, won)
+		if err != nil {
+			return nil, err
+		}
+		points = append(points, StreetEquity{
+			Street:  point.street,
+			Board:   append([]domain.Card{}, spot.Board...),
+			Runouts: runouts,
+			Exact:   point.board > 0,
+			Equity:  shares,
+		})
+	}
+	return points, nil
+}
+
+// liveSeats lists, in seat order, the seats with hole cards still in the
+// hand when sparklineStreets[street] starts. A folded seat missing from
+// folds counts as folded preflop.
+func liveSeats(final domain.HandState, holes map[domain.SeatNo][]domain.Card, folds map[domain.SeatNo]domain.Street, street int) []domain.SeatNo {
+	live := make([]domain.SeatNo, 0, len(holes))
+	for _, seat := range final.Seats {
+		if _, ok := holes[seat.SeatNo]; !ok {
+			continue
+		}
+		folded := slices.IndexFunc(sparklineStreets, func(s sparklineStreet) bool {
+			return s.street == folds[seat.SeatNo]
+		})
+		if seat.Folded && folded < street {
+			continue
+		}
+		live = append(live, seat.SeatNo)
+	}
+	slices.Sort(live)
+	return live
+}
+
+// scoreBoard splits sparklineSplit between the best live hands on a full
+// board.
+func scoreBoard(won []uint64, live []domain.SeatNo, holes map[domain.SeatNo][]domain.Card, board []domain.Card) {
+	ranks := make([]rules.HandRank, len(live))
+	best := 0
+	for i, seatNo := range live {
+		ranks[i] = rules.EvaluateBestHand(holes[seatNo], board)
+		if rules.CompareHandRank(ranks[i], ranks[best]) > 0 {
+			best = i
+		}
+	}
+	var winners []int
+	for i := range live {
+		if rules.CompareHandRank(ranks[i], ranks[best]) == 0 {
+			winners = append(winners, i)
+		}
+	}
+	for _, i := range winners {
+		won[i] += sparklineSplit / uint64(len(winners))
+	}
+}
+
+// eachBoard calls visit with board finished by every need-card combination
+// of unseen. visit must not keep the board.
+func eachBoard(unseen []domain.Card, need int, board []domain.Card, visit func([]domain.Card)) {
+	if need == 0 {
+		visit(board)
+		return
+	}
+	for i := 0; i+need <= len(unseen); i++ {
+		eachBoard(unseen[i+1:], need-1, append(board, unseen[i]), visit)
+	}
+}
+
+// sampleBoard finishes board with random cards from deck, which it
+// shuffles as it deals so no card comes out twice.
+func sampleBoard(rng *rand.Rand, deck []domain.Card, board []domain.Card) []domain.Card {
+	for i := 0; len(board) < 5; i++ {
+		j := i + rng.Intn(len(deck)-i)
+		deck[i], deck[j] = deck[j], deck[i]
+		board = append(board, deck[i])
+	}
+	return board
+}
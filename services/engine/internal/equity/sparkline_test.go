@@ -0,0 +1,55 @@
+package equity
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestSparkline_ScoresEveryStreetTheHandDealt(t *testing.T) {
+	t.Parallel()
+
+	final := allInSpot(
+		card(2, domain.SuitClubs), card(7, domain.SuitDiamonds), card(9, domain.SuitHearts), card(11, domain.SuitClubs), card(3, domain.SuitSpades),
+	)
+	folds := map[domain.SeatNo]domain.Street{3: domain.StreetFlop}
+	points, err := Sparkline(final, folds)
+	if err != nil {
+		t.Fatalf("Sparkline failed: %v", err)
+	}
+	if len(points) != 4 {
+		t.Fatalf("expected a point per street, got %+v", points)
+	}
+
+	preflop, flop, turn, river := points[0], points[1], points[2], points[3]
+	if preflop.Street != domain.StreetPreflop || preflop.Exact || preflop.Runouts != SparklineSamples || len(preflop.Equity) != 3 {
+		t.Fatalf("expected a sampled three-way preflop point, got %+v", preflop)
+	}
+	if preflop.Equity[0].Share.Cmp(preflop.Equity[1].Share) <= 0 {
+		t.Fatalf("expected aces ahead of kings preflop, got %+v", preflop.Equity)
+	}
+	// Seat 3 folded on the flop, so it is live as the flop starts.
+	if flop.Street != domain.StreetFlop || !flop.Exact || flop.Runouts != 903 || len(flop.Equity) != 3 {
+		t.Fatalf("expected every turn and river from 43 cards three ways, got %+v", flop)
+	}
+	// Its cards were never shown, so they stay in the deck after it folds.
+	if turn.Runouts != 44 || len(turn.Equity) != 2 || turn.Equity[1].Share.String() != "1/22" {
+		t.Fatalf("expected kings to hit two outs in 44, got %+v", turn)
+	}
+	if river.Runouts != 1 || river.Equity[0].Share.Cmp(One()) != 0 || len(river.Board) != 5 {
+		t.Fatalf("expected aces to hold on the river, got %+v", river)
+	}
+
+	again, err := Sparkline(final, folds)
+	if err != nil || !reflect.DeepEqual(again, points) {
+		t.Fatalf("expected the same hand to score the same, got %+v (%v)", again, err)
+	}
+
+	// A fold with no street recorded counts as preflop.
+	final.Seats[1].Folded = true
+	points, err = Sparkline(final, folds)
+	if err != nil || len(points) != 2 || points[0].Equity[0].SeatNo != 1 || points[0].Equity[1].SeatNo != 3 {
+		t.Fatalf("expected aces against seat 3 until seat 3 folds, got %+v (%v)", points, err)
+	}
+}
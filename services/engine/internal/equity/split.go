@@ -0,0 +1,53 @@
+package equity
+
+import (
+	"errors"
+	"fmt"
+	"sort"
+)
+
+var (
+	ErrNoShares            = errors.New("no shares to settle")
+	ErrNegativeShare       = errors.New("share must be non-negative")
+	ErrSharesNotNormalized = errors.New("shares must sum to exactly 1")
+)
+
+// SplitChips divides chips according to exact shares that sum to 1. Each
+// share first receives the floor of its exact amount; leftover chips go one
+// at a time to the largest dropped remainders, ties broken by lower index, so
+// the result is identical on every platform.
+func SplitChips(chips uint32, shares []Ratio) ([]uint32, error) {
+	if len(shares) == 0 {
+		return nil, ErrNoShares
+	}
+	total := Zero()
+	for i, share := range shares {
+		if share.Sign() < 0 {
+			return nil, fmt.Errorf("%w: share %d is %s", ErrNegativeShare, i, share)
+		}
+		total = total.Add(share)
+	}
+	if total.Cmp(One()) != 0 {
+		return nil, fmt.Errorf("%w: got %s", ErrSharesNotNormalized, total)
+	}
+
+	out := make([]uint32, len(shares))
+	remainders := make([]Ratio, len(shares))
+	allocated := uint32(0)
+	for i, share := range shares {
+		out[i], remainders[i] = share.MulChips(chips)
+		allocated += out[i]
+	}
+
+	order := make([]int, len(shares))
+	for i := range order {
+		order[i] = i
+	}
+	sort.SliceStable(order, func(i, j int) bool {
+		return remainders[order[i]].Cmp(remainders[order[j]]) > 0
+	})
+	for k := uint32(0); k < chips-allocated; k++ {
+		out[order[int(k)%len(order)]]++
+	}
+	return out, nil
+}
@@ -0,0 +1,414 @@
+// Package fastfold runs a fast-fold cash pool. Every hand is dealt at a
+// fresh table drawn from the pool, and a player who folds goes straight back
+// to the pool with the chips behind them, to be dealt into another table
+// while the hand they left plays out without them.
+package fastfold
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"sync"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+)
+
+var (
+	ErrInvalidConfig  = errors.New("invalid fast-fold config")
+	ErrAlreadyInPool  = errors.New("player already in pool")
+	ErrPlayerNotFound = errors.New("player not in pool")
+	ErrPlayerSeated   = errors.New("player is seated at a table")
+	ErrPoolRunning    = errors.New("fast-fold pool already running")
+	ErrPoolStalled    = errors.New("not enough players in the pool to deal")
+)
+
+type Config struct {
+	TableSize  uint8        `json:"table_size"`
+	SmallBlind domain.Chips `json:"small_blind"`
+	BigBlind   domain.Chips `json:"big_blind"`
+}
+
+func (c Config) Validate() error {
+	if c.TableSize < 2 || c.TableSize > domain.DefaultMaxSeats {
+		return fmt.Errorf("%w: table_size must be in range 2..=%d, got %d", ErrInvalidConfig, domain.DefaultMaxSeats, c.TableSize)
+	}
+	if c.SmallBlind == 0 || c.BigBlind < c.SmallBlind {
+		return fmt.Errorf("%w: blinds %d/%d", ErrInvalidConfig, c.SmallBlind, c.BigBlind)
+	}
+	return nil
+}
+
+// PlayerProvider decides for players rather than seats, since a player sits
+// in a different seat at every table.
+type PlayerProvider interface {
+	NextAction(ctx context.Context, playerID string, state domain.HandState) (domain.Action, error)
+}
+
+type EventKind string
+
+const (
+	EventSeated EventKind = "seated"
+	// EventFastFolded is a player leaving a hand they folded. Their chips in
+	// the pot stay there; Stack is what they take back to the pool.
+	EventFastFolded EventKind = "fast_folded"
+	EventBusted     EventKind = "busted"
+)
+
+type Event struct {
+	Kind     EventKind     `json:"kind"`
+	PlayerID string        `json:"player_id"`
+	TableID  string        `json:"table_id"`
+	HandNo   uint64        `json:"hand_no"`
+	SeatNo   domain.SeatNo `json:"seat_no"`
+	Stack    domain.Chips  `json:"stack"`
+}
+
+type Result struct {
+	HandsDealt int
+	// Stacks holds every player still in the pool.
+	Stacks map[string]domain.Chips
+}
+
+type poolPlayer struct {
+	stack  domain.Chips
+	seated bool
+	// lastHand is the hand the player was last dealt into; the matchmaker
+	// keeps its tablemates apart when it can.
+	lastHand uint64
+}
+
+// Pool is one fast-fold pool. Join and Leave may be called while Run is in
+// progress.
+type Pool struct {
+	ID     string
+	Config Config
+	// Shuffler is shared across tables; nil uses the crypto shuffler.
+	Shuffler rules.Shuffler
+
+	mu       sync.Mutex
+	players  map[string]*poolPlayer
+	waiting  []string
+	inFlight int
+	running  bool
+	// handNo numbers hands across runs, so table ids never repeat.
+	handNo   uint64
+	events   []Event
+	released chan struct{}
+}
+
+func NewPool(id string, config Config) (*Pool, error) {
+	if err := config.Validate(); err != nil {
+		return nil, err
+	}
+	return &Pool{
+		ID:       id,
+		Config:   config,
+		players:  make(map[string]*poolPlayer),
+		released: make(chan struct{}, 1),
+	}, nil
+}
+
+// Join adds a player to the back of the waiting line with stack chips.
+func (p *Pool) Join(playerID string, stack domain.Chips) error {
+	if playerID == "" || stack == 0 {
+		return fmt.Errorf("%w: a player id and chips are required", ErrInvalidConfig)
+	}
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	if _, ok := p.players[playerID]; ok {
+		return fmt.Errorf("%w: %s", ErrAlreadyInPool, playerID)
+	}
+	p.players[playerID] = &poolPlayer{stack: stack}
+	p.waiting = append(p.waiting, playerID)
+	p.notify()
+	return nil
+}
+
+// Leave takes a waiting player out of the pool and returns their chips.
+// A player at a table must wait until they fold or the hand ends.
+func (p *Pool) Leave(playerID string) (domain.Chips, error) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	player, ok := p.players[playerID]
+	if !ok {
+		return 0, fmt.Errorf("%w: %s", ErrPlayerNotFound, playerID)
+	}
+	if player.seated {
+		return 0, fmt.Errorf("%w: %s", ErrPlayerSeated, playerID)
+	}
+	delete(p.players, playerID)
+	p.removeWaitingLocked(playerID)
+	return player.stack, nil
+}
+
+// Events returns the pool log in the order it was written. Tables run in
+// parallel, so events from different hands interleave.
+func (p *Pool) Events() []Event {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	return append([]Event(nil), p.events...)
+}
+
+// Run deals hands until it has dealt hands of them and every table has
+// finished. A table is dealt as soon as TableSize players are waiting; when
+// no hand is in play it is dealt short-handed instead, and with fewer than
+// two players waiting Run stops with ErrPoolStalled. The first hand to fail
+// stops the pool.
+func (p *Pool) Run(ctx context.Context, provider PlayerProvider, hands int) (Result, error) {
+	if hands <= 0 {
+		return Result{}, fmt.Errorf("%w: hands must be positive", ErrInvalidConfig)
+	}
+	p.mu.Lock()
+	if p.running {
+		p.mu.Unlock()
+		return Result{}, ErrPoolRunning
+	}
+	p.running = true
+	p.mu.Unlock()
+
+	ctx, cancel := context.WithCancel(ctx)
+	defer cancel()
+	var (
+		wg       sync.WaitGroup
+		errMu    sync.Mutex
+		firstErr error
+	)
+	fail := func(err error) {
+		errMu.Lock()
+		defer errMu.Unlock()
+		if firstErr == nil {
+			firstErr = err
+			cancel()
+		}
+	}
+
+	dealt := 0
+	for dealt < hands && ctx.Err() == nil {
+		p.mu.Lock()
+		handNo, table := p.drawLocked()
+		if table == nil {
+			idle := p.inFlight == 0
+			p.mu.Unlock()
+			if idle {
+				fail(fmt.Errorf("%w: after %d hands", ErrPoolStalled, dealt))
+				break
+			}
+			select {
+			case <-p.released:
+			case <-ctx.Done():
+			}
+			continue
+		}
+		dealt++
+		p.inFlight++
+		p.mu.Unlock()
+
+		wg.Add(1)
+		go func(handNo uint64) {
+			defer wg.Done()
+			if err := p.playHand(ctx, provider, handNo, table); err != nil {
+				fail(err)
+			}
+		}(handNo)
+	}
+	wg.Wait()
+	if ctx.Err() != nil {
+		fail(ctx.Err())
+	}
+
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.running = false
+	result := Result{HandsDealt: dealt, Stacks: make(map[string]domain.Chips, len(p.players))}
+	for playerID, player := range p.players {
+		result.Stacks[playerID] = player.stack
+	}
+	errMu.Lock()
+	defer errMu.Unlock()
+	return result, firstErr
+}
+
+// drawLocked seats the next hand's table: TableSize players, or everyone
+// waiting when no hand is in play.
+func (p *Pool) drawLocked() (uint64, []string) {
+	size := int(p.Config.TableSize)
+	if len(p.waiting) < size {
+		if p.inFlight > 0 || len(p.waiting) < 2 {
+			return 0, nil
+		}
+		size = len(p.waiting)
+	}
+	lastHand := make(map[string]uint64, len(p.waiting))
+	for _, playerID := range p.waiting {
+		lastHand[playerID] = p.players[playerID].lastHand
+	}
+	p.handNo++
+	table := drawTable(p.waiting, size, lastHand)
+	for _, playerID := range table {
+		p.removeWaitingLocked(playerID)
+		player := p.players[playerID]
+		player.seated = true
+		player.lastHand = p.handNo
+	}
+	return p.handNo, table
+}
+
+// drawTable picks size players from waiting, which is in arrival order. The
+// longest-waiting player always sits; the rest are taken in order, first
+// passing over anyone who shared their last table with a player already
+// picked, so tables mix fresh opponents whenever the line allows.
+func drawTable(waiting []string, size int, lastHand map[string]uint64) []string {
+	table := make([]string, 0, size)
+	taken := make(map[string]bool, size)
+	hands := make(map[uint64]bool, size)
+	for _, playerID := range waiting {
+		if len(table) == size {
+			break
+		}
+		last := lastHand[playerID]
+		if len(table) > 0 && last != 0 && hands[last] {
+			continue
+		}
+		table = append(table, playerID)
+		taken[playerID] = true
+		if last != 0 {
+			hands[last] = true
+		}
+	}
+	for _, playerID := range waiting {
+		if len(table) == size {
+			break
+		}
+		if !taken[playerID] {
+			table = append(table, playerID)
+		}
+	}
+	return table
+}
+
+// playHand deals handNo at a table of its own. Folding players are handed
+// back to the pool as the fold is applied; everyone else returns with their
+// final stack when the hand ends. A failed hand hands back the players still
+// in it with the chips they sat down with.
+func (p *Pool) playHand(ctx context.Context, provider PlayerProvider, handNo uint64, table []string) error {
+	tableID := fmt.Sprintf("%s-t%d", p.ID, handNo)
+	bySeat := make(map[domain.SeatNo]string, len(table))
+	seats := make([]domain.SeatState, 0, len(table))
+	p.mu.Lock()
+	for i, playerID := range table {
+		seatNo := domain.SeatNo(i + 1)
+		bySeat[seatNo] = playerID
+		stack := p.players[playerID].stack
+		seats = append(seats, domain.NewSeatState(seatNo, stack))
+		p.events = append(p.events, Event{Kind: EventSeated, PlayerID: playerID, TableID: tableID, HandNo: handNo, SeatNo: seatNo, Stack: stack})
+	}
+	p.mu.Unlock()
+
+	// OnAction runs on this goroutine, so folded needs no lock.
+	folded := make(map[domain.SeatNo]bool, len(table))
+	runner := tablerunner.New(seatedProvider{players: bySeat, inner: provider}, tablerunner.RunnerConfig{
+		Shuffler: p.Shuffler,
+		OnAction: func(_ uint64, state domain.HandState, action domain.Action, _ bool, _ tablerunner.ActionTiming) {
+			if action.Kind != domain.ActionFold {
+				return
+			}
+			for _, seat := range state.Seats {
+				if seat.SeatNo == state.ActingSeat {
+					folded[seat.SeatNo] = true
+					p.release(bySeat[seat.SeatNo], seat.Stack, Event{Kind: EventFastFolded, TableID: tableID, HandNo: handNo, SeatNo: seat.SeatNo})
+				}
+			}
+		},
+	})
+	result, err := runner.RunHand(ctx, tablerunner.RunHandInput{
+		TableID:    tableID,
+		HandNo:     handNo,
+		ButtonSeat: domain.SeatNo((handNo-1)%uint64(len(table)) + 1),
+		Seats:      seats,
+		Config:     p.Config.tableConfig(len(table)),
+	})
+
+	final := make(map[domain.SeatNo]domain.Chips, len(seats))
+	source := result.FinalState.Seats
+	if err != nil {
+		source = seats
+	}
+	for _, seat := range source {
+		final[seat.SeatNo] = seat.Stack
+	}
+	for seatNo, playerID := range bySeat {
+		if !folded[seatNo] {
+			p.release(playerID, final[seatNo], Event{TableID: tableID, HandNo: handNo, SeatNo: seatNo})
+		}
+	}
+
+	p.mu.Lock()
+	p.inFlight--
+	p.notify()
+	p.mu.Unlock()
+	if err != nil {
+		return fmt.Errorf("hand %d at %s: %w", handNo, tableID, err)
+	}
+	return nil
+}
+
+// release hands a player back to the pool with stack, logging event when it
+// has a kind. A player with no chips left busts out of the pool.
+func (p *Pool) release(playerID string, stack domain.Chips, event Event) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	player := p.players[playerID]
+	player.stack = stack
+	player.seated = false
+	event.PlayerID = playerID
+	event.Stack = stack
+	if event.Kind != "" {
+		p.events = append(p.events, event)
+	}
+	if stack == 0 {
+		delete(p.players, playerID)
+		event.Kind = EventBusted
+		p.events = append(p.events, event)
+		return
+	}
+	p.waiting = append(p.waiting, playerID)
+	p.notify()
+}
+
+func (p *Pool) removeWaitingLocked(playerID string) {
+	for i, waiting := range p.waiting {
+		if waiting == playerID {
+			p.waiting = append(p.waiting[:i], p.waiting[i+1:]...)
+			return
+		}
+	}
+}
+
+// notify wakes Run without blocking; one pending wake-up is enough.
+func (p *Pool) notify() {
+	select {
+	case p.released <- struct{}{}:
+	default:
+	}
+}
+
+func (c Config) tableConfig(players int) domain.TableConfig {
+	cfg := domain.DefaultV0TableConfig()
+	cfg.MaxSeats = uint8(players)
+	cfg.MinPlayersToStart = 2
+	cfg.SmallBlind = c.SmallBlind
+	cfg.BigBlind = c.BigBlind
+	return cfg
+}
+
+// seatedProvider asks inner on behalf of the player in the acting seat.
+type seatedProvider struct {
+	players map[domain.SeatNo]string
+	inner   PlayerProvider
+}
+
+func (p seatedProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	return p.inner.NextAction(ctx, p.players[state.ActingSeat], state)
+}
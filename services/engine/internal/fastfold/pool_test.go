@@ -0,0 +1,96 @@
+package fastfold
+
+import (
+	"context"
+	"reflect"
+	"sync"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestPool_FoldedPlayerIsReseatedBeforeTheirHandEnds(t *testing.T) {
+	t.Parallel()
+
+	pool, err := NewPool("zoom", Config{TableSize: 3, SmallBlind: 10, BigBlind: 20})
+	if err != nil {
+		t.Fatalf("NewPool failed: %v", err)
+	}
+	for _, playerID := range []string{"a", "b", "c", "d", "e"} {
+		if err := pool.Join(playerID, 1000); err != nil {
+			t.Fatalf("Join failed: %v", err)
+		}
+	}
+
+	// The small blind in hand 1 does not act until hand 2 has started,
+	// which only happens once the button's fold sends them back to the pool.
+	provider := &foldingProvider{secondHand: make(chan struct{})}
+	result, err := pool.Run(context.Background(), provider, 2)
+	if err != nil {
+		t.Fatalf("Run failed: %v", err)
+	}
+	want := map[string]domain.Chips{"a": 990, "b": 990, "c": 1010, "d": 1010, "e": 1000}
+	if result.HandsDealt != 2 || !reflect.DeepEqual(result.Stacks, want) {
+		t.Fatalf("expected stacks %v after 2 hands, got %+v", want, result)
+	}
+
+	var reseated bool
+	for _, event := range pool.Events() {
+		if event.Kind == EventSeated && event.PlayerID == "a" && event.TableID == "zoom-t2" {
+			reseated = true
+			if event.Stack != 1000 || event.SeatNo != 3 {
+				t.Fatalf("expected a to bring every chip to seat 3, got %+v", event)
+			}
+		}
+		if event.Kind == EventFastFolded && event.PlayerID == "b" && !reseated {
+			t.Fatalf("expected a to be reseated before b folded, got %+v", pool.Events())
+		}
+	}
+	if !reseated {
+		t.Fatalf("expected a to be seated at the second table, got %+v", pool.Events())
+	}
+
+	if _, err := pool.Leave("a"); err != nil {
+		t.Fatalf("Leave failed: %v", err)
+	}
+	if _, err := pool.Run(context.Background(), provider, 1); err != nil {
+		t.Fatalf("expected the four remaining players to deal, got %v", err)
+	}
+}
+
+func TestDrawTable_PrefersFreshOpponents(t *testing.T) {
+	t.Parallel()
+
+	lastHand := map[string]uint64{"a": 1, "b": 1, "d": 2}
+	if got := drawTable([]string{"a", "b", "c", "d"}, 2, lastHand); !reflect.DeepEqual(got, []string{"a", "c"}) {
+		t.Fatalf("expected a to skip their tablemate b, got %v", got)
+	}
+	if got := drawTable([]string{"a", "b", "c"}, 3, lastHand); !reflect.DeepEqual(got, []string{"a", "c", "b"}) {
+		t.Fatalf("expected b to fill the last seat, got %v", got)
+	}
+}
+
+// foldingProvider folds to any bet and checks otherwise.
+type foldingProvider struct {
+	once       sync.Once
+	secondHand chan struct{}
+}
+
+func (p *foldingProvider) NextAction(ctx context.Context, playerID string, state domain.HandState) (domain.Action, error) {
+	switch {
+	case state.HandNo == 1 && playerID == "b":
+		select {
+		case <-p.secondHand:
+		case <-ctx.Done():
+			return domain.Action{}, ctx.Err()
+		}
+	case state.HandNo == 2:
+		p.once.Do(func() { close(p.secondHand) })
+	}
+	for _, seat := range state.Seats {
+		if seat.SeatNo == state.ActingSeat && seat.CommittedInRound < state.CurrentBet {
+			return domain.Action{Kind: domain.ActionFold}, nil
+		}
+	}
+	return domain.Action{Kind: domain.ActionCheck}, nil
+}
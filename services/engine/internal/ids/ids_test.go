@@ -0,0 +1,112 @@
+package ids
+
+import (
+	"bytes"
+	"errors"
+	"regexp"
+	"sort"
+	"testing"
+	"time"
+)
+
+var canonicalUUID = regexp.MustCompile(`^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$`)
+
+func TestSequence_YieldsReservedTestUUIDs(t *testing.T) {
+	t.Parallel()
+
+	seq := NewSequence()
+	for _, want := range []string{
+		"00000000-0000-4000-8000-000000000001",
+		"00000000-0000-4000-8000-000000000002",
+	} {
+		got, err := seq.NewID()
+		if err != nil {
+			t.Fatalf("NewID failed: %v", err)
+		}
+		if got != want {
+			t.Fatalf("expected %s, got %s", want, got)
+		}
+	}
+	if got := ReservedTestUUID(0x1234); got != "00000000-0000-4000-8000-000000001234" {
+		t.Fatalf("unexpected reserved uuid %s", got)
+	}
+}
+
+func TestNewV4_StampsVersionAndVariant(t *testing.T) {
+	t.Parallel()
+
+	id, err := NewV4(bytes.NewReader(bytes.Repeat([]byte{0xff}, 16))).NewID()
+	if err != nil {
+		t.Fatalf("NewID failed: %v", err)
+	}
+	if id != "ffffffff-ffff-4fff-bfff-ffffffffffff" {
+		t.Fatalf("unexpected uuid %s", id)
+	}
+	if _, err := NewV4(bytes.NewReader(nil)).NewID(); err == nil {
+		t.Fatal("expected an error when entropy runs out")
+	}
+	random, err := NewV4(nil).NewID()
+	if err != nil || !canonicalUUID.MatchString(random) {
+		t.Fatalf("expected a canonical v4 uuid, got %q err=%v", random, err)
+	}
+}
+
+func TestNewV7_SortsInIssueOrderAndEmbedsTimestamp(t *testing.T) {
+	t.Parallel()
+
+	start := time.Date(2026, 10, 16, 12, 0, 0, 0, time.UTC)
+	clock := []time.Time{start, start, start.Add(-time.Second), start.Add(time.Millisecond)}
+	calls := 0
+	gen := NewV7(func() time.Time {
+		now := clock[calls]
+		calls++
+		return now
+	}, bytes.NewReader(bytes.Repeat([]byte{0xff}, 40)))
+
+	var issued []string
+	for range clock {
+		id, err := gen.NewID()
+		if err != nil {
+			t.Fatalf("NewID failed: %v", err)
+		}
+		issued = append(issued, id)
+	}
+	if issued[0] != "01a14495-5600-77ff-bfff-ffffffffffff" {
+		t.Fatalf("unexpected first uuid %s", issued[0])
+	}
+	if !sort.StringsAreSorted(issued) {
+		t.Fatalf("expected ids in issue order, got %v", issued)
+	}
+
+	for i, id := range issued[:3] {
+		got, ok := Timestamp("hand-" + id)
+		if !ok || !got.Equal(start) {
+			t.Fatalf("id %d: expected timestamp %s, got %s ok=%v", i, start, got, ok)
+		}
+	}
+	if got, _ := Timestamp(issued[3]); !got.Equal(start.Add(time.Millisecond)) {
+		t.Fatalf("expected the later millisecond, got %s", got)
+	}
+	for _, id := range []string{ReservedTestUUID(1), "hand-1", "userx" + issued[0]} {
+		if _, ok := Timestamp(id); ok {
+			t.Fatalf("expected no timestamp for %q", id)
+		}
+	}
+}
+
+func TestParse(t *testing.T) {
+	t.Parallel()
+
+	if gen, err := Parse(""); err != nil || gen != nil {
+		t.Fatalf("expected no generator for an empty format, got %v err=%v", gen, err)
+	}
+	if gen, err := Parse("UUIDv4"); err != nil || gen == nil {
+		t.Fatalf("expected a v4 generator, got %v err=%v", gen, err)
+	}
+	if gen, err := Parse("uuidv7"); err != nil || gen == nil {
+		t.Fatalf("expected a v7 generator, got %v err=%v", gen, err)
+	}
+	if _, err := Parse("snowflake"); !errors.Is(err, ErrUnknownFormat) {
+		t.Fatalf("expected ErrUnknownFormat, got %v", err)
+	}
+}
@@ -0,0 +1,90 @@
+// Package invariants checks that a hand's chips add up, so a bug in the
+// betting or payout code stops the hand at the step that caused it with a
+// structured error, instead of leaving corrupt stacks to carry into later
+// hands.
+package invariants
+
+import (
+	"fmt"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// Rule names an invariant.
+type Rule string
+
+const (
+	// RuleChipsConserved holds while the seats' stacks, the pot and the
+	// rake taken add up to the stacks the seats brought to the hand.
+	// Committed chips are already in the pot, so they are not counted
+	// again.
+	RuleChipsConserved Rule = "chips_conserved"
+	// RulePotMatchesCommitments holds while the pot, until it is paid out,
+	// is exactly what the seats committed plus dead money.
+	RulePotMatchesCommitments Rule = "pot_matches_commitments"
+	// RuleRoundWithinHand holds while no seat has committed more on the
+	// current street than in the whole hand.
+	RuleRoundWithinHand Rule = "round_within_hand"
+)
+
+// InvariantViolation reports a broken Rule: Want is the amount the rule
+// requires and Got what the hand shows. SeatNo is set for rules about a
+// single seat.
+type InvariantViolation struct {
+	Rule   Rule          `json:"rule"`
+	HandNo uint64        `json:"hand_no"`
+	SeatNo domain.SeatNo `json:"seat_no,omitempty"`
+	Want   domain.Chips  `json:"want"`
+	Got    domain.Chips  `json:"got"`
+}
+
+func (v *InvariantViolation) Error() string {
+	if v.SeatNo != 0 {
+		return fmt.Sprintf("hand %d breaks %s at seat %d: want %d, got %d", v.HandNo, v.Rule, v.SeatNo, v.Want, v.Got)
+	}
+	return fmt.Sprintf("hand %d breaks %s: want %d, got %d", v.HandNo, v.Rule, v.Want, v.Got)
+}
+
+// Check reports the first invariant state breaks on its own, as an
+// *InvariantViolation, or nil. Conservation needs the hand's starting
+// stacks; see Checker.
+func Check(state domain.HandState) error {
+	for _, seat := range state.Seats {
+		if seat.CommittedInRound > seat.TotalCommitted {
+			return &InvariantViolation{Rule: RuleRoundWithinHand, HandNo: state.HandNo, SeatNo: seat.SeatNo, Want: seat.TotalCommitted, Got: seat.CommittedInRound}
+		}
+	}
+	if state.Phase != domain.HandPhaseComplete && state.Pot != state.TotalPot() {
+		return &InvariantViolation{Rule: RulePotMatchesCommitments, HandNo: state.HandNo, Want: state.TotalPot(), Got: state.Pot}
+	}
+	return nil
+}
+
+// Checker holds the chips a hand started with, so every later state of the
+// hand can be checked against them.
+type Checker struct {
+	chips domain.Chips
+}
+
+// NewChecker starts checking a hand dealt to seats, before any blind is
+// posted.
+func NewChecker(seats []domain.SeatState) Checker {
+	var chips domain.Chips
+	for _, seat := range seats {
+		chips += seat.Stack
+	}
+	return Checker{chips: chips}
+}
+
+// Check is the package's Check, and also reports a hand whose stacks and
+// pot no longer add up to the chips it started with.
+func (c Checker) Check(state domain.HandState) error {
+	held := state.Pot + state.RakeTaken
+	for _, seat := range state.Seats {
+		held += seat.Stack
+	}
+	if held != c.chips {
+		return &InvariantViolation{Rule: RuleChipsConserved, HandNo: state.HandNo, Want: c.chips, Got: held}
+	}
+	return Check(state)
+}
@@ -0,0 +1,75 @@
+package invariants
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func blindsPosted() domain.HandState {
+	state := domain.HandState{HandNo: 3, Phase: domain.HandPhaseBetting, Pot: 150}
+	for i, blind := range []domain.Chips{50, 100} {
+		seat := domain.NewSeatState(domain.SeatNo(i+1), 1000-blind)
+		seat.CommittedInRound = blind
+		seat.TotalCommitted = blind
+		state.Seats = append(state.Seats, seat)
+	}
+	return state
+}
+
+func TestChecker_PassesAConsistentHand(t *testing.T) {
+	t.Parallel()
+
+	state := blindsPosted()
+	checker := NewChecker([]domain.SeatState{domain.NewSeatState(1, 1000), domain.NewSeatState(2, 1000)})
+	if err := checker.Check(state); err != nil {
+		t.Fatalf("expected the blinds to hold, got %v", err)
+	}
+
+	state.Phase = domain.HandPhaseComplete
+	state.Pot = 0
+	state.Seats[1].Stack += 150
+	if err := checker.Check(state); err != nil {
+		t.Fatalf("expected the paid-out hand to hold, got %v", err)
+	}
+}
+
+func TestChecker_ReportsStructuredViolations(t *testing.T) {
+	t.Parallel()
+
+	checker := NewChecker([]domain.SeatState{domain.NewSeatState(1, 1000), domain.NewSeatState(2, 1000)})
+	cases := []struct {
+		name    string
+		corrupt func(*domain.HandState)
+		want    InvariantViolation
+	}{
+		{
+			name:    "chips appear",
+			corrupt: func(state *domain.HandState) { state.Seats[0].Stack += 25 },
+			want:    InvariantViolation{Rule: RuleChipsConserved, HandNo: 3, Want: 2000, Got: 2025},
+		},
+		{
+			name: "pot drifts from commitments",
+			corrupt: func(state *domain.HandState) {
+				state.Seats[0].TotalCommitted = 0
+				state.Seats[0].CommittedInRound = 0
+			},
+			want: InvariantViolation{Rule: RulePotMatchesCommitments, HandNo: 3, Want: 100, Got: 150},
+		},
+		{
+			name:    "round exceeds hand",
+			corrupt: func(state *domain.HandState) { state.Seats[1].TotalCommitted = 40 },
+			want:    InvariantViolation{Rule: RuleRoundWithinHand, HandNo: 3, SeatNo: 2, Want: 40, Got: 100},
+		},
+	}
+	for _, tc := range cases {
+		state := blindsPosted()
+		tc.corrupt(&state)
+		err := checker.Check(state)
+		var violation *InvariantViolation
+		if !errors.As(err, &violation) || *violation != tc.want {
+			t.Fatalf("%s: expected %+v, got %v", tc.name, tc.want, err)
+		}
+	}
+}
@@ -0,0 +1,315 @@
+// Package ledger keeps a double-entry record of every chip that moves at a
+// table. Each entry takes chips out of one account and puts the same chips
+// into another, so nothing is created or lost along the way: chips come into
+// play from the bank as buy-ins and rebuys, move between the seats and the
+// pot as blinds, bets and awards, leave the pot for the house as rake, and
+// go back to the bank when a seat cashes out.
+// The ledger checks its balances against the hand after every hand and
+// against the seats at the end of a session.
+package ledger
+
+import (
+	"errors"
+	"fmt"
+	"sync"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var (
+	ErrUnbalanced     = errors.New("ledger out of balance")
+	ErrUnknownAccount = errors.New("unknown ledger account")
+)
+
+// Account names somewhere chips can be. Each seat has its own; see
+// SeatAccount.
+type Account string
+
+const (
+	// AccountBank is where buy-ins and rebuys come from. Its balance is
+	// what it has put into play rather than chips it holds.
+	AccountBank Account = "bank"
+	// AccountPot holds the chips committed to the hand being played. It is
+	// empty between hands.
+	AccountPot Account = "pot"
+	// AccountHouse collects rake.
+	AccountHouse Account = "house"
+)
+
+func SeatAccount(seatNo domain.SeatNo) Account {
+	return Account(fmt.Sprintf("seat:%d", seatNo))
+}
+
+type EntryKind string
+
+const (
+	// EntryBuyIn brings a seat's starting stack into play.
+	EntryBuyIn EntryKind = "buy_in"
+	// EntryBlind is a blind or ante posted before the deal.
+	EntryBlind EntryKind = "blind"
+	// EntryBet is a call, bet or raise.
+	EntryBet EntryKind = "bet"
+	// EntryAward pays a seat its share of the pot.
+	EntryAward EntryKind = "award"
+	// EntryRake takes the house's cut out of the pot.
+	EntryRake EntryKind = "rake"
+	// EntryRebuy adds chips to a seat between hands.
+	EntryRebuy EntryKind = "rebuy"
+	// EntryCashOut takes a leaving seat's stack out of play.
+	EntryCashOut EntryKind = "cash_out"
+)
+
+// Entry moves Amount out of Credit and into Debit. HandNo is the hand the
+// chips moved in, or for a rebuy the hand before it; buy-ins and cash-outs
+// carry zero.
+type Entry struct {
+	HandNo uint64       `json:"hand_no"`
+	Kind   EntryKind    `json:"kind"`
+	Debit  Account      `json:"debit"`
+	Credit Account      `json:"credit"`
+	Amount domain.Chips `json:"amount"`
+}
+
+// Ledger records the chip movements of one table session. It is safe for
+// concurrent use.
+type Ledger struct {
+	mu       sync.Mutex
+	entries  []Entry
+	balances map[Account]domain.Chips
+	issued   domain.Chips
+}
+
+func New() *Ledger {
+	return &Ledger{balances: map[Account]domain.Chips{AccountPot: 0, AccountHouse: 0}}
+}
+
+// Open buys in every seat the ledger has not seen with its stack. A seat
+// already open must still have the stack its account shows.
+func (l *Ledger) Open(seats []domain.SeatState) error {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+
+	for _, seat := range seats {
+		account := SeatAccount(seat.SeatNo)
+		balance, ok := l.balances[account]
+		if ok {
+			if balance != seat.Stack {
+				return fmt.Errorf("%w: seat %d has %d, ledger shows %d", ErrUnbalanced, seat.SeatNo, seat.Stack, balance)
+			}
+			continue
+		}
+		l.balances[account] = 0
+		if err := l.post(Entry{Kind: EntryBuyIn, Debit: account, Credit: AccountBank, Amount: seat.Stack}); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// PostBlinds records the blinds and antes of a hand that has just been
+// dealt: whatever each seat's stack is short of its account went to the
+// pot. A hand the blinds already decided is paid out as SettleHand would.
+func (l *Ledger) PostBlinds(state domain.HandState) error {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	return l.commit(state, EntryBlind)
+}
+
+// RecordBets records the chips each seat has put into the pot since the
+// ledger last saw the hand, and pays out the pot if the hand is complete,
+// as it is when a fold leaves one seat.
+func (l *Ledger) RecordBets(state domain.HandState) error {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	return l.commit(state, EntryBet)
+}
+
+// Rake moves the house's cut of a hand out of the pot. A hand's RakeTaken
+// is recorded when the ledger sees the hand complete; Rake is for a cut
+// taken some other way, and belongs before then, since that is when the
+// rest of the pot is paid out to the seats.
+func (l *Ledger) Rake(handNo uint64, amount domain.Chips) error {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	return l.post(Entry{HandNo: handNo, Kind: EntryRake, Debit: AccountHouse, Credit: AccountPot, Amount: amount})
+}
+
+// SettleHand records any bets and pot awards of a completed hand the
+// ledger has not seen yet and checks the hand's balance: the pot must be
+// empty and every seat's account must match its stack.
+func (l *Ledger) SettleHand(state domain.HandState) error {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+
+	if state.Phase != domain.HandPhaseComplete {
+		return fmt.Errorf("%w: hand %d is not complete", ErrUnbalanced, state.HandNo)
+	}
+	if err := l.commit(state, EntryBet); err != nil {
+		return err
+	}
+	if pot := l.balances[AccountPot]; pot != 0 {
+		return fmt.Errorf("%w: hand %d left %d in the pot", ErrUnbalanced, state.HandNo, pot)
+	}
+	return l.checkSeats(state.Seats)
+}
+
+// Rebuy adds amount to a seat's account from the bank, after handNo.
+func (l *Ledger) Rebuy(handNo uint64, seatNo domain.SeatNo, amount domain.Chips) error {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	return l.post(Entry{HandNo: handNo, Kind: EntryRebuy, Debit: SeatAccount(seatNo), Credit: AccountBank, Amount: amount})
+}
+
+// CashOut returns a leaving seat's stack to the bank and closes its account,
+// so the seat can be bought into afresh. It returns the stack paid out.
+func (l *Ledger) CashOut(seatNo domain.SeatNo) (domain.Chips, error) {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+
+	account := SeatAccount(seatNo)
+	balance, ok := l.balances[account]
+	if !ok {
+		return 0, fmt.Errorf("%w: %s", ErrUnknownAccount, account)
+	}
+	if balance > 0 {
+		issued, err := l.issued.CheckedSub(balance)
+		if err != nil {
+			return 0, fmt.Errorf("%w: %s to the bank: %w", ErrUnbalanced, EntryCashOut, err)
+		}
+		l.issued = issued
+		l.entries = append(l.entries, Entry{Kind: EntryCashOut, Debit: AccountBank, Credit: account, Amount: balance})
+	}
+	delete(l.balances, account)
+	return balance, nil
+}
+
+// Reconcile checks the session's balance against the seats: every seat's
+// account matches its stack, the pot is empty, and the seats and the house
+// between them hold exactly what the bank has put into play.
+func (l *Ledger) Reconcile(seats []domain.SeatState) error {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+
+	if err := l.checkSeats(seats); err != nil {
+		return err
+	}
+	var held domain.Chips
+	for account, balance := range l.balances {
+		var err error
+		if held, err = held.CheckedAdd(balance); err != nil {
+			return fmt.Errorf("%w: totalling %s: %w", ErrUnbalanced, account, err)
+		}
+	}
+	if pot := l.balances[AccountPot]; pot != 0 {
+		return fmt.Errorf("%w: %d left in the pot", ErrUnbalanced, pot)
+	}
+	if held != l.issued {
+		return fmt.Errorf("%w: table holds %d, bank issued %d", ErrUnbalanced, held, l.issued)
+	}
+	return nil
+}
+
+// Balance is what account holds, or for AccountBank what it has issued.
+func (l *Ledger) Balance(account Account) domain.Chips {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	if account == AccountBank {
+		return l.issued
+	}
+	return l.balances[account]
+}
+
+// Entries lists every entry so far, oldest first.
+func (l *Ledger) Entries() []Entry {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	return append([]Entry(nil), l.entries...)
+}
+
+// commit moves into the pot, as kind, whatever each seat's stack is short
+// of its account, then, once the hand is complete, takes the hand's
+// RakeTaken for the house and pays each seat holding more than its account
+// out of the pot. A seat gaining chips mid-hand is an error.
+func (l *Ledger) commit(state domain.HandState, kind EntryKind) error {
+	var won []domain.SeatState
+	for _, seat := range state.Seats {
+		account := SeatAccount(seat.SeatNo)
+		balance, ok := l.balances[account]
+		if !ok {
+			return fmt.Errorf("%w: %s", ErrUnknownAccount, account)
+		}
+		if seat.Stack > balance {
+			won = append(won, seat)
+			continue
+		}
+		if err := l.post(Entry{HandNo: state.HandNo, Kind: kind, Debit: AccountPot, Credit: account, Amount: balance - seat.Stack}); err != nil {
+			return err
+		}
+	}
+	if len(won) > 0 && state.Phase != domain.HandPhaseComplete {
+		return fmt.Errorf("%w: seat %d gained chips mid-hand", ErrUnbalanced, won[0].SeatNo)
+	}
+	if len(won) > 0 && state.RakeTaken > 0 {
+		if err := l.post(Entry{HandNo: state.HandNo, Kind: EntryRake, Debit: AccountHouse, Credit: AccountPot, Amount: state.RakeTaken}); err != nil {
+			return err
+		}
+	}
+	for _, seat := range won {
+		account := SeatAccount(seat.SeatNo)
+		if err := l.post(Entry{HandNo: state.HandNo, Kind: EntryAward, Debit: account, Credit: AccountPot, Amount: seat.Stack - l.balances[account]}); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func (l *Ledger) checkSeats(seats []domain.SeatState) error {
+	for _, seat := range seats {
+		account := SeatAccount(seat.SeatNo)
+		balance, ok := l.balances[account]
+		if !ok {
+			return fmt.Errorf("%w: %s", ErrUnknownAccount, account)
+		}
+		if balance != seat.Stack {
+			return fmt.Errorf("%w: seat %d has %d, ledger shows %d", ErrUnbalanced, seat.SeatNo, seat.Stack, balance)
+		}
+	}
+	return nil
+}
+
+// post applies one entry, leaving the balances untouched when it cannot.
+// Only the bank may pay out more than it holds.
+func (l *Ledger) post(entry Entry) error {
+	if entry.Amount == 0 {
+		return nil
+	}
+	debit, ok := l.balances[entry.Debit]
+	if !ok {
+		return fmt.Errorf("%w: %s", ErrUnknownAccount, entry.Debit)
+	}
+	debit, err := debit.CheckedAdd(entry.Amount)
+	if err != nil {
+		return fmt.Errorf("%w: %s into %s: %w", ErrUnbalanced, entry.Kind, entry.Debit, err)
+	}
+
+	if entry.Credit == AccountBank {
+		issued, err := l.issued.CheckedAdd(entry.Amount)
+		if err != nil {
+			return fmt.Errorf("%w: %s from the bank: %w", ErrUnbalanced, entry.Kind, err)
+		}
+		l.issued = issued
+	} else {
+		credit, ok := l.balances[entry.Credit]
+		if !ok {
+			return fmt.Errorf("%w: %s", ErrUnknownAccount, entry.Credit)
+		}
+		credit, err := credit.CheckedSub(entry.Amount)
+		if err != nil {
+			return fmt.Errorf("%w: %s out of %s: %w", ErrUnbalanced, entry.Kind, entry.Credit, err)
+		}
+		l.balances[entry.Credit] = credit
+	}
+	l.balances[entry.Debit] = debit
+	l.entries = append(l.entries, entry)
+	return nil
+}
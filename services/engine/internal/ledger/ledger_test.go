@@ -0,0 +1,144 @@
+package ledger
+
+import (
+	"errors"
+	"slices"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func hand(phase domain.HandPhase, stacks ...domain.Chips) domain.HandState {
+	state := domain.HandState{HandNo: 1, Phase: phase}
+	for i, stack := range stacks {
+		state.Seats = append(state.Seats, domain.NewSeatState(domain.SeatNo(i+1), stack))
+	}
+	return state
+}
+
+func TestLedger_RecordsAHandAndBalances(t *testing.T) {
+	t.Parallel()
+
+	l := New()
+	if err := l.Open(hand(domain.HandPhaseBetting, 1000, 1000).Seats); err != nil {
+		t.Fatalf("Open failed: %v", err)
+	}
+	if err := l.PostBlinds(hand(domain.HandPhaseBetting, 950, 900)); err != nil {
+		t.Fatalf("PostBlinds failed: %v", err)
+	}
+	if err := l.RecordBets(hand(domain.HandPhaseBetting, 900, 900)); err != nil {
+		t.Fatalf("RecordBets failed: %v", err)
+	}
+	if err := l.Rake(1, 10); err != nil {
+		t.Fatalf("Rake failed: %v", err)
+	}
+	final := hand(domain.HandPhaseComplete, 1090, 900)
+	if err := l.SettleHand(final); err != nil {
+		t.Fatalf("SettleHand failed: %v", err)
+	}
+
+	var kinds []EntryKind
+	for _, entry := range l.Entries() {
+		kinds = append(kinds, entry.Kind)
+	}
+	want := []EntryKind{EntryBuyIn, EntryBuyIn, EntryBlind, EntryBlind, EntryBet, EntryRake, EntryAward}
+	if !slices.Equal(kinds, want) {
+		t.Fatalf("expected entries %v, got %v", want, kinds)
+	}
+	if l.Balance(AccountPot) != 0 || l.Balance(AccountHouse) != 10 || l.Balance(SeatAccount(1)) != 1090 {
+		t.Fatalf("unexpected balances: pot %d, house %d, seat 1 %d", l.Balance(AccountPot), l.Balance(AccountHouse), l.Balance(SeatAccount(1)))
+	}
+	if err := l.Reconcile(final.Seats); err != nil {
+		t.Fatalf("Reconcile failed: %v", err)
+	}
+
+	if err := l.Rebuy(1, 2, 100); err != nil {
+		t.Fatalf("Rebuy failed: %v", err)
+	}
+	if err := l.Reconcile(final.Seats); !errors.Is(err, ErrUnbalanced) {
+		t.Fatalf("expected seat 2 to be 100 short of its rebuy, got %v", err)
+	}
+	final.Seats[1].Stack = 1000
+	if err := l.Reconcile(final.Seats); err != nil || l.Balance(AccountBank) != 2100 {
+		t.Fatalf("expected the rebuy drawn from the bank, got %d issued, %v", l.Balance(AccountBank), err)
+	}
+}
+
+func TestLedger_PaysOutAHandAFoldEnds(t *testing.T) {
+	t.Parallel()
+
+	l := New()
+	if err := l.Open(hand(domain.HandPhaseBetting, 1000, 1000).Seats); err != nil {
+		t.Fatalf("Open failed: %v", err)
+	}
+	if err := l.PostBlinds(hand(domain.HandPhaseBetting, 950, 900)); err != nil {
+		t.Fatalf("PostBlinds failed: %v", err)
+	}
+	final := hand(domain.HandPhaseComplete, 950, 1050)
+	if err := l.RecordBets(final); err != nil {
+		t.Fatalf("RecordBets failed: %v", err)
+	}
+	if err := l.SettleHand(final); err != nil {
+		t.Fatalf("SettleHand failed: %v", err)
+	}
+	entries := l.Entries()
+	if last := entries[len(entries)-1]; last.Kind != EntryAward || last.Debit != SeatAccount(2) || last.Amount != 150 {
+		t.Fatalf("expected seat 2 awarded the 150 pot, got %+v", last)
+	}
+}
+
+func TestLedger_CashOutClosesTheSeat(t *testing.T) {
+	t.Parallel()
+
+	l := New()
+	if err := l.Open(hand(domain.HandPhaseBetting, 1000, 1000).Seats); err != nil {
+		t.Fatalf("Open failed: %v", err)
+	}
+	if stack, err := l.CashOut(2); err != nil || stack != 1000 {
+		t.Fatalf("expected seat 2 cashed out for 1000, got %d, %v", stack, err)
+	}
+	if err := l.Reconcile(hand(domain.HandPhaseComplete, 1000).Seats); err != nil || l.Balance(AccountBank) != 1000 {
+		t.Fatalf("expected the cash-out returned to the bank, got %d issued, %v", l.Balance(AccountBank), err)
+	}
+	if _, err := l.CashOut(2); !errors.Is(err, ErrUnknownAccount) {
+		t.Fatalf("expected ErrUnknownAccount cashing out a closed seat, got %v", err)
+	}
+	rejoined := hand(domain.HandPhaseBetting, 1000, 400)
+	if err := l.Open(rejoined.Seats); err != nil {
+		t.Fatalf("expected seat 2 bought into afresh, got %v", err)
+	}
+	if err := l.Reconcile(rejoined.Seats); err != nil || l.Balance(AccountBank) != 1400 {
+		t.Fatalf("expected 1400 in play, got %d issued, %v", l.Balance(AccountBank), err)
+	}
+}
+
+func TestLedger_RejectsChipsThatDoNotBalance(t *testing.T) {
+	t.Parallel()
+
+	open := func(t *testing.T) *Ledger {
+		l := New()
+		if err := l.Open(hand(domain.HandPhaseBetting, 1000, 1000).Seats); err != nil {
+			t.Fatalf("Open failed: %v", err)
+		}
+		return l
+	}
+
+	if err := open(t).RecordBets(hand(domain.HandPhaseBetting, 1100, 900)); !errors.Is(err, ErrUnbalanced) {
+		t.Fatalf("expected a seat gaining chips mid-hand to be rejected, got %v", err)
+	}
+	if err := open(t).SettleHand(hand(domain.HandPhaseComplete, 1000, 900)); !errors.Is(err, ErrUnbalanced) {
+		t.Fatalf("expected chips left in the pot to be rejected, got %v", err)
+	}
+	if err := open(t).SettleHand(hand(domain.HandPhaseBetting, 1000, 1000)); !errors.Is(err, ErrUnbalanced) {
+		t.Fatalf("expected an unfinished hand to be rejected, got %v", err)
+	}
+	if err := open(t).Rake(1, 10); !errors.Is(err, ErrUnbalanced) {
+		t.Fatalf("expected rake from an empty pot to be rejected, got %v", err)
+	}
+	if err := open(t).Open(hand(domain.HandPhaseBetting, 1000, 900).Seats); !errors.Is(err, ErrUnbalanced) {
+		t.Fatalf("expected reopening a seat with a different stack to be rejected, got %v", err)
+	}
+	if err := open(t).PostBlinds(hand(domain.HandPhaseBetting, 1000, 1000, 1000)); !errors.Is(err, ErrUnknownAccount) {
+		t.Fatalf("expected ErrUnknownAccount for a seat never opened, got %v", err)
+	}
+}
@@ -0,0 +1,225 @@
+package manifest
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"strings"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+const CurrentVersion = 1
+
+var (
+	ErrInvalidManifest     = errors.New("invalid manifest")
+	ErrUnsupportedVersion  = errors.New("unsupported manifest version")
+	ErrUnknownScoringRule  = errors.New("unknown scoring method")
+	ErrProviderUnavailable = errors.New("bot provider unavailable")
+)
+
+type ScoringMethod string
+
+const (
+	ScoringNetChips ScoringMethod = "net_chips"
+	ScoringBBPer100 ScoringMethod = "bb_per_100"
+	// ScoringPoints awards Scoring.PointsTable by finishing order in each
+	// match, as tournament series do.
+	ScoringPoints ScoringMethod = "points"
+	// ScoringWeighted adds up the other methods' scores, each multiplied by
+	// its Scoring.Weights entry.
+	ScoringWeighted ScoringMethod = "weighted"
+)
+
+// Manifest describes a whole competition so it can be replayed from a single
+// versioned file instead of a sequence of admin calls.
+type Manifest struct {
+	Version        int             `json:"version"`
+	Name           string          `json:"name"`
+	Seed           int64           `json:"seed"`
+	Bots           []Bot           `json:"bots"`
+	TableTemplates []TableTemplate `json:"table_templates"`
+	Schedule       []Match         `json:"schedule"`
+	Scoring        Scoring         `json:"scoring"`
+}
+
+type Bot struct {
+	ID          string `json:"id"`
+	EndpointURL string `json:"endpoint_url"`
+	TimeoutMS   uint64 `json:"timeout_ms,omitempty"`
+}
+
+type TableTemplate struct {
+	Name                     string       `json:"name"`
+	MaxSeats                 uint8        `json:"max_seats"`
+	StartingStack            domain.Chips `json:"starting_stack"`
+	SmallBlind               domain.Chips `json:"small_blind"`
+	BigBlind                 domain.Chips `json:"big_blind"`
+	ActionTimeoutMS          uint64       `json:"action_timeout_ms,omitempty"`
+	ThinkTimeBudgetMS        uint64       `json:"think_time_budget_ms,omitempty"`
+	LatencyGraceMS           uint64       `json:"latency_grace_ms,omitempty"`
+	LatencyCompensationCapMS uint64       `json:"latency_compensation_cap_ms,omitempty"`
+}
+
+// Match seats Bots in order starting at seat 1. Seed overrides the
+// manifest-level seed, which otherwise is offset by the match index.
+// Duplicate matches play one mirrored table per bot with the same seed,
+// moving every bot one seat on at each mirror so each plays every seat's
+// cards.
+type Match struct {
+	ID        string   `json:"id"`
+	Template  string   `json:"template"`
+	Bots      []string `json:"bots"`
+	Hands     int      `json:"hands"`
+	Seed      *int64   `json:"seed,omitempty"`
+	Duplicate bool     `json:"duplicate,omitempty"`
+}
+
+// Scoring picks how standings are ranked. PointsTable[i] is what the bot
+// finishing i+1 in a match earns, by net chips in that match, with tied bots
+// sharing the places they span; Weights maps net_chips, bb_per_100 and
+// points to their multiplier in a weighted score.
+type Scoring struct {
+	Method      ScoringMethod             `json:"method"`
+	PointsTable []float64                 `json:"points_table,omitempty"`
+	Weights     map[ScoringMethod]float64 `json:"weights,omitempty"`
+}
+
+// Parse decodes a JSON manifest, rejecting unknown fields, and validates it.
+func Parse(data []byte) (Manifest, error) {
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.DisallowUnknownFields()
+
+	var manifest Manifest
+	if err := decoder.Decode(&manifest); err != nil {
+		return Manifest{}, fmt.Errorf("%w: %v", ErrInvalidManifest, err)
+	}
+	if err := manifest.Validate(); err != nil {
+		return Manifest{}, err
+	}
+	return manifest, nil
+}
+
+func (m Manifest) Validate() error {
+	if m.Version != CurrentVersion {
+		return fmt.Errorf("%w: %d", ErrUnsupportedVersion, m.Version)
+	}
+	if strings.TrimSpace(m.Name) == "" {
+		return fmt.Errorf("%w: name is required", ErrInvalidManifest)
+	}
+	if err := m.Scoring.Validate(); err != nil {
+		return err
+	}
+
+	bots := make(map[string]struct{}, len(m.Bots))
+	for _, bot := range m.Bots {
+		if strings.TrimSpace(bot.ID) == "" {
+			return fmt.Errorf("%w: bot id is required", ErrInvalidManifest)
+		}
+		if _, exists := bots[bot.ID]; exists {
+			return fmt.Errorf("%w: duplicate bot %q", ErrInvalidManifest, bot.ID)
+		}
+		if strings.TrimSpace(bot.EndpointURL) == "" {
+			return fmt.Errorf("%w: bot %q has no endpoint_url", ErrInvalidManifest, bot.ID)
+		}
+		bots[bot.ID] = struct{}{}
+	}
+
+	templates := make(map[string]TableTemplate, len(m.TableTemplates))
+	for _, template := range m.TableTemplates {
+		if strings.TrimSpace(template.Name) == "" {
+			return fmt.Errorf("%w: table template name is required", ErrInvalidManifest)
+		}
+		if _, exists := templates[template.Name]; exists {
+			return fmt.Errorf("%w: duplicate table template %q", ErrInvalidManifest, template.Name)
+		}
+		if template.StartingStack == 0 {
+			return fmt.Errorf("%w: table template %q needs a starting_stack", ErrInvalidManifest, template.Name)
+		}
+		if err := template.TableConfig().Validate(); err != nil {
+			return fmt.Errorf("%w: table template %q: %v", ErrInvalidManifest, template.Name, err)
+		}
+		templates[template.Name] = template
+	}
+
+	if len(m.Schedule) == 0 {
+		return fmt.Errorf("%w: schedule is empty", ErrInvalidManifest)
+	}
+	matches := make(map[string]struct{}, len(m.Schedule))
+	for _, match := range m.Schedule {
+		if strings.TrimSpace(match.ID) == "" {
+			return fmt.Errorf("%w: match id is required", ErrInvalidManifest)
+		}
+		if _, exists := matches[match.ID]; exists {
+			return fmt.Errorf("%w: duplicate match %q", ErrInvalidManifest, match.ID)
+		}
+		matches[match.ID] = struct{}{}
+
+		template, ok := templates[match.Template]
+		if !ok {
+			return fmt.Errorf("%w: match %q uses unknown template %q", ErrInvalidManifest, match.ID, match.Template)
+		}
+		if match.Hands <= 0 {
+			return fmt.Errorf("%w: match %q must run at least one hand", ErrInvalidManifest, match.ID)
+		}
+		if len(match.Bots) < 2 || len(match.Bots) > int(template.MaxSeats) {
+			return fmt.Errorf("%w: match %q seats %d bots, template allows 2..=%d", ErrInvalidManifest, match.ID, len(match.Bots), template.MaxSeats)
+		}
+		seated := make(map[string]struct{}, len(match.Bots))
+		for _, botID := range match.Bots {
+			if _, ok := bots[botID]; !ok {
+				return fmt.Errorf("%w: match %q uses unknown bot %q", ErrInvalidManifest, match.ID, botID)
+			}
+			if _, dup := seated[botID]; dup {
+				return fmt.Errorf("%w: match %q seats bot %q twice", ErrInvalidManifest, match.ID, botID)
+			}
+			seated[botID] = struct{}{}
+		}
+	}
+	return nil
+}
+
+func (t TableTemplate) TableConfig() domain.TableConfig {
+	timeout := t.ActionTimeoutMS
+	if timeout == 0 {
+		timeout = domain.DefaultActionTimeoutMS
+	}
+	return domain.TableConfig{
+		MaxSeats:                 t.MaxSeats,
+		MinPlayersToStart:        domain.DefaultMinPlayersToStart,
+		StartingStack:            t.StartingStack,
+		SmallBlind:               t.SmallBlind,
+		BigBlind:                 t.BigBlind,
+		ActionTimeoutMS:          timeout,
+		ThinkTimeBudgetMS:        t.ThinkTimeBudgetMS,
+		LatencyGraceMS:           t.LatencyGraceMS,
+		LatencyCompensationCapMS: t.LatencyCompensationCapMS,
+	}
+}
+
+func (m Manifest) template(name string) (TableTemplate, bool) {
+	for _, template := range m.TableTemplates {
+		if template.Name == name {
+			return template, true
+		}
+	}
+	return TableTemplate{}, false
+}
+
+func (m Manifest) bot(id string) (Bot, bool) {
+	for _, bot := range m.Bots {
+		if bot.ID == id {
+			return bot, true
+		}
+	}
+	return Bot{}, false
+}
+
+// MatchSeed is the shuffle seed used for the match at index i of the schedule.
+func (m Manifest) MatchSeed(i int) int64 {
+	if m.Schedule[i].Seed != nil {
+		return *m.Schedule[i].Seed
+	}
+	return m.Seed + int64(i)
+}
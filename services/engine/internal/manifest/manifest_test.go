@@ -0,0 +1,298 @@
+package manifest
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+)
+
+const validManifest = `{
+	"version": 1,
+	"name": "spring-cup",
+	"seed": 42,
+	"bots": [
+		{"id": "alpha", "endpoint_url": "http://alpha.local/act"},
+		{"id": "beta", "endpoint_url": "http://beta.local/act"},
+		{"id": "gamma", "endpoint_url": "http://gamma.local/act"}
+	],
+	"table_templates": [
+		{"name": "hu", "max_seats": 2, "starting_stack": 1000, "small_blind": 5, "big_blind": 10},
+		{"name": "ring", "max_seats": 6, "starting_stack": 2000, "small_blind": 10, "big_blind": 20}
+	],
+	"schedule": [
+		{"id": "r1", "template": "hu", "bots": ["alpha", "beta"], "hands": 20},
+		{"id": "r2", "template": "ring", "bots": ["alpha", "beta", "gamma"], "hands": 15, "seed": 7}
+	],
+	"scoring": {"method": "bb_per_100"}
+}`
+
+func TestParse_ValidManifest(t *testing.T) {
+	t.Parallel()
+
+	manifest, err := Parse([]byte(validManifest))
+	if err != nil {
+		t.Fatalf("Parse failed: %v", err)
+	}
+	if manifest.Name != "spring-cup" || len(manifest.Schedule) != 2 {
+		t.Fatalf("unexpected manifest: %+v", manifest)
+	}
+	if seed := manifest.MatchSeed(0); seed != 42 {
+		t.Fatalf("expected default seed 42 for first match, got %d", seed)
+	}
+	if seed := manifest.MatchSeed(1); seed != 7 {
+		t.Fatalf("expected seed override 7 for second match, got %d", seed)
+	}
+}
+
+func TestParse_RejectsInvalidManifests(t *testing.T) {
+	t.Parallel()
+
+	cases := []struct {
+		name    string
+		edit    func(string) string
+		wantErr error
+	}{
+		{
+			name:    "unsupported version",
+			edit:    func(s string) string { return strings.Replace(s, `"version": 1`, `"version": 2`, 1) },
+			wantErr: ErrUnsupportedVersion,
+		},
+		{
+			name:    "unknown field",
+			edit:    func(s string) string { return strings.Replace(s, `"seed": 42`, `"seed": 42, "rake": 5`, 1) },
+			wantErr: ErrInvalidManifest,
+		},
+		{
+			name:    "unknown bot",
+			edit:    func(s string) string { return strings.Replace(s, `["alpha", "beta"]`, `["alpha", "delta"]`, 1) },
+			wantErr: ErrInvalidManifest,
+		},
+		{
+			name:    "unknown template",
+			edit:    func(s string) string { return strings.Replace(s, `"template": "hu"`, `"template": "mtt"`, 1) },
+			wantErr: ErrInvalidManifest,
+		},
+		{
+			name:    "too many bots for template",
+			edit:    func(s string) string { return strings.Replace(s, `["alpha", "beta"]`, `["alpha", "beta", "gamma"]`, 1) },
+			wantErr: ErrInvalidManifest,
+		},
+		{
+			name:    "unknown scoring",
+			edit:    func(s string) string { return strings.Replace(s, `"bb_per_100"`, `"elo"`, 1) },
+			wantErr: ErrUnknownScoringRule,
+		},
+		{
+			name:    "points without a table",
+			edit:    func(s string) string { return strings.Replace(s, `"bb_per_100"`, `"points"`, 1) },
+			wantErr: ErrInvalidManifest,
+		},
+		{
+			name: "points table on chip scoring",
+			edit: func(s string) string {
+				return strings.Replace(s, `"bb_per_100"}`, `"bb_per_100", "points_table": [3, 1]}`, 1)
+			},
+			wantErr: ErrInvalidManifest,
+		},
+		{
+			name:    "weighted without weights",
+			edit:    func(s string) string { return strings.Replace(s, `"bb_per_100"`, `"weighted"`, 1) },
+			wantErr: ErrInvalidManifest,
+		},
+		{
+			name: "weighted on an unknown metric",
+			edit: func(s string) string {
+				return strings.Replace(s, `"bb_per_100"}`, `"weighted", "weights": {"elo": 1}}`, 1)
+			},
+			wantErr: ErrUnknownScoringRule,
+		},
+	}
+
+	for _, tc := range cases {
+		tc := tc
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			if _, err := Parse([]byte(tc.edit(validManifest))); !errors.Is(err, tc.wantErr) {
+				t.Fatalf("expected %v, got %v", tc.wantErr, err)
+			}
+		})
+	}
+}
+
+func TestScore_RanksByTheConfiguredMethod(t *testing.T) {
+	t.Parallel()
+
+	matches := []MatchResult{
+		{HandsCompleted: 10, BigBlind: 10, Seats: []SeatResult{
+			{BotID: "alpha", NetChips: 300},
+			{BotID: "beta", NetChips: -150},
+			{BotID: "gamma", NetChips: -150},
+		}},
+		{HandsCompleted: 10, BigBlind: 100, Seats: []SeatResult{
+			{BotID: "alpha", NetChips: -400},
+			{BotID: "beta", NetChips: 100},
+			{BotID: "gamma", NetChips: 300},
+		}},
+	}
+	table := []float64{10, 4, 1}
+	cases := []struct {
+		name    string
+		scoring Scoring
+		want    []string
+	}{
+		{name: "net chips", scoring: Scoring{Method: ScoringNetChips}, want: []string{"gamma", "beta", "alpha"}},
+		{name: "bb per 100", scoring: Scoring{Method: ScoringBBPer100}, want: []string{"alpha", "gamma", "beta"}},
+		// Beta and gamma split 4+1 in the first match: alpha 11, gamma 12.5, beta 6.5.
+		{name: "points", scoring: Scoring{Method: ScoringPoints, PointsTable: table}, want: []string{"gamma", "alpha", "beta"}},
+		{
+			name: "weighted",
+			scoring: Scoring{
+				Method:      ScoringWeighted,
+				PointsTable: table,
+				Weights:     map[ScoringMethod]float64{ScoringPoints: 1, ScoringNetChips: -0.1},
+			},
+			want: []string{"alpha", "beta", "gamma"},
+		},
+	}
+
+	for _, tc := range cases {
+		tc := tc
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+			if err := tc.scoring.Validate(); err != nil {
+				t.Fatalf("Validate failed: %v", err)
+			}
+			standings := score(tc.scoring, matches)
+			got := make([]string, 0, len(standings))
+			for _, standing := range standings {
+				got = append(got, standing.BotID)
+			}
+			if !reflect.DeepEqual(got, tc.want) {
+				t.Fatalf("expected order %v, got %+v", tc.want, standings)
+			}
+		})
+	}
+}
+
+func TestRunFromManifest_IsReproducible(t *testing.T) {
+	t.Parallel()
+
+	manifest, err := Parse([]byte(validManifest))
+	if err != nil {
+		t.Fatalf("Parse failed: %v", err)
+	}
+	providers := func(Bot) (tablerunner.ActionProvider, error) {
+		return passiveProvider{}, nil
+	}
+
+	first, err := RunFromManifest(context.Background(), manifest, providers)
+	if err != nil {
+		t.Fatalf("RunFromManifest failed: %v", err)
+	}
+	second, err := RunFromManifest(context.Background(), manifest, providers)
+	if err != nil {
+		t.Fatalf("RunFromManifest failed: %v", err)
+	}
+	if !reflect.DeepEqual(first, second) {
+		t.Fatalf("expected identical results for the same manifest\nfirst=%+v\nsecond=%+v", first, second)
+	}
+
+	if len(first.Matches) != 2 || first.Matches[0].HandsCompleted != 20 || first.Matches[1].HandsCompleted != 15 {
+		t.Fatalf("unexpected match results: %+v", first.Matches)
+	}
+	for _, match := range first.Matches {
+		var net int64
+		for _, seat := range match.Seats {
+			net += seat.NetChips
+		}
+		if net != 0 {
+			t.Fatalf("expected chips to be conserved in match %s, net=%d", match.MatchID, net)
+		}
+	}
+	if len(first.Standings) != 3 {
+		t.Fatalf("expected standings for 3 bots, got %+v", first.Standings)
+	}
+	for i := 1; i < len(first.Standings); i++ {
+		if first.Standings[i-1].Score < first.Standings[i].Score {
+			t.Fatalf("expected standings sorted by score, got %+v", first.Standings)
+		}
+	}
+	// Bots that only check and call are perfectly predictable and never
+	// fold to a bluff.
+	if len(first.BotReports) != 3 || first.BotReports[0].BotID != "alpha" || first.BotReports[2].BotID != "gamma" {
+		t.Fatalf("expected a report per bot in id order, got %+v", first.BotReports)
+	}
+	for _, report := range first.BotReports {
+		if report.Decisions == 0 || report.ActionEntropyBits != 0 || report.ExploitabilityBBPer100 != 0 {
+			t.Fatalf("unexpected report for a passive bot: %+v", report)
+		}
+	}
+}
+
+func TestRunFromManifest_DuplicateMatchRotatesSeatsOverMirroredDeals(t *testing.T) {
+	t.Parallel()
+
+	manifest, err := Parse([]byte(strings.Replace(validManifest, `"seed": 7}`, `"seed": 7, "duplicate": true}`, 1)))
+	if err != nil {
+		t.Fatalf("Parse failed: %v", err)
+	}
+	result, err := RunFromManifest(context.Background(), manifest, func(Bot) (tablerunner.ActionProvider, error) {
+		return passiveProvider{}, nil
+	})
+	if err != nil {
+		t.Fatalf("RunFromManifest failed: %v", err)
+	}
+
+	match := result.Matches[1]
+	if match.Mirrors != 3 || match.HandsCompleted != 45 {
+		t.Fatalf("expected 3 mirrors of 15 hands, got mirrors=%d hands=%d", match.Mirrors, match.HandsCompleted)
+	}
+	if len(match.DealDivergences) != 0 {
+		t.Fatalf("expected mirrored deals to match, got %+v", match.DealDivergences)
+	}
+	var net int64
+	for _, seat := range match.Seats {
+		if seat.StartingStack != 3*2000 {
+			t.Fatalf("expected stacks summed over mirrors, got %+v", seat)
+		}
+		net += seat.NetChips
+	}
+	if net != 0 {
+		t.Fatalf("expected chips to be conserved across mirrors, net=%d", net)
+	}
+	if result.Matches[0].Mirrors != 0 || result.Matches[0].HandsCompleted != 20 {
+		t.Fatalf("expected the plain match to play one table, got %+v", result.Matches[0])
+	}
+}
+
+func TestRunFromManifest_ProviderFactoryError(t *testing.T) {
+	t.Parallel()
+
+	manifest, err := Parse([]byte(validManifest))
+	if err != nil {
+		t.Fatalf("Parse failed: %v", err)
+	}
+	_, err = RunFromManifest(context.Background(), manifest, func(bot Bot) (tablerunner.ActionProvider, error) {
+		return nil, fmt.Errorf("dial %s refused", bot.EndpointURL)
+	})
+	if !errors.Is(err, ErrProviderUnavailable) {
+		t.Fatalf("expected ErrProviderUnavailable, got %v", err)
+	}
+}
+
+type passiveProvider struct{}
+
+func (passiveProvider) NextAction(_ context.Context, state domain.HandState) (domain.Action, error) {
+	for _, seat := range state.Seats {
+		if seat.SeatNo == state.ActingSeat && state.CurrentBet > seat.CommittedInRound {
+			return domain.Action{Kind: domain.ActionCall}, nil
+		}
+	}
+	return domain.Action{Kind: domain.ActionCheck}, nil
+}
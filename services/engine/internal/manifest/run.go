@@ -0,0 +1,257 @@
+package manifest
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"sort"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/agentclient"
+	"github.com/imaddar/poker-arena/services/engine/internal/dealaudit"
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/stats"
+	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+)
+
+// ProviderFactory returns the action provider that plays for a bot.
+type ProviderFactory func(bot Bot) (tablerunner.ActionProvider, error)
+
+// NewHTTPProviderFactory plays each bot through its endpoint_url using the
+// agent protocol.
+func NewHTTPProviderFactory(client agentclient.Client) ProviderFactory {
+	return func(bot Bot) (tablerunner.ActionProvider, error) {
+		return httpBotProvider{client: client, bot: bot}, nil
+	}
+}
+
+// Result is a whole competition. BotReports, one per bot in bot ID order,
+// estimate from each bot's own decisions how predictable and exploitable
+// its play was, for research leaderboards.
+type Result struct {
+	Name       string            `json:"name"`
+	Version    int               `json:"version"`
+	Scoring    ScoringMethod     `json:"scoring"`
+	Matches    []MatchResult     `json:"matches"`
+	Standings  []Standing        `json:"standings"`
+	BotReports []stats.BotReport `json:"bot_reports"`
+}
+
+// MatchResult sums every mirror of a duplicate match: hands and stacks are
+// totals across mirrors, and each bot's seat is its seat on the first
+// mirror. DealDivergences lists mirrored seats whose hole cards did not
+// match the first mirror's.
+type MatchResult struct {
+	MatchID         string                 `json:"match_id"`
+	Seed            int64                  `json:"seed"`
+	HandsRequested  int                    `json:"hands_requested"`
+	HandsCompleted  int                    `json:"hands_completed"`
+	BigBlind        domain.Chips           `json:"big_blind"`
+	Seats           []SeatResult           `json:"seats"`
+	Mirrors         int                    `json:"mirrors,omitempty"`
+	DealDivergences []dealaudit.Divergence `json:"deal_divergences,omitempty"`
+}
+
+type SeatResult struct {
+	SeatNo        domain.SeatNo `json:"seat_no"`
+	BotID         string        `json:"bot_id"`
+	StartingStack domain.Chips  `json:"starting_stack"`
+	FinalStack    domain.Chips  `json:"final_stack"`
+	NetChips      int64         `json:"net_chips"`
+}
+
+// Standing is one bot's record across the competition. Points is its total
+// from the scoring's points table, when it has one; Score is whatever the
+// scoring method ranks bots by.
+type Standing struct {
+	BotID    string  `json:"bot_id"`
+	Matches  int     `json:"matches"`
+	Hands    int     `json:"hands"`
+	NetChips int64   `json:"net_chips"`
+	Points   float64 `json:"points,omitempty"`
+	Score    float64 `json:"score"`
+}
+
+// RunFromManifest plays every scheduled match in order with seeded shuffles
+// and scores the results, so the same manifest and bots reproduce the same
+// competition.
+func RunFromManifest(ctx context.Context, manifest Manifest, providers ProviderFactory) (Result, error) {
+	if err := manifest.Validate(); err != nil {
+		return Result{}, err
+	}
+	if providers == nil {
+		return Result{}, fmt.Errorf("%w: provider factory is nil", ErrProviderUnavailable)
+	}
+
+	result := Result{
+		Name:    manifest.Name,
+		Version: manifest.Version,
+		Scoring: manifest.Scoring.Method,
+		Matches: make([]MatchResult, 0, len(manifest.Schedule)),
+	}
+	decisions := make(map[string][]stats.Decision, len(manifest.Bots))
+	for i, match := range manifest.Schedule {
+		matchResult, err := runMatch(ctx, manifest, i, providers, decisions)
+		if err != nil {
+			return result, fmt.Errorf("match %q: %w", match.ID, err)
+		}
+		result.Matches = append(result.Matches, matchResult)
+	}
+	result.Standings = score(manifest.Scoring, result.Matches)
+	result.BotReports = make([]stats.BotReport, 0, len(result.Standings))
+	for _, standing := range result.Standings {
+		result.BotReports = append(result.BotReports, stats.AnalyzeBot(standing.BotID, standing.Hands, decisions[standing.BotID]))
+	}
+	sort.Slice(result.BotReports, func(i, j int) bool { return result.BotReports[i].BotID < result.BotReports[j].BotID })
+	return result, nil
+}
+
+// runMatch plays one scheduled match, adding each bot's own decisions, not
+// the runner's fallbacks, to decisions.
+func runMatch(ctx context.Context, manifest Manifest, index int, providers ProviderFactory, decisions map[string][]stats.Decision) (MatchResult, error) {
+	match := manifest.Schedule[index]
+	template, _ := manifest.template(match.Template)
+	seed := manifest.MatchSeed(index)
+
+	mirrors := 1
+	if match.Duplicate {
+		mirrors = len(match.Bots)
+	}
+	seating := make([]map[domain.SeatNo]string, mirrors)
+	for mirror := range seating {
+		seating[mirror] = make(map[domain.SeatNo]string, len(match.Bots))
+		for i, botID := range match.Bots {
+			seating[mirror][domain.SeatNo((i+mirror)%len(match.Bots)+1)] = botID
+		}
+	}
+	auditor := dealaudit.New(seating)
+
+	out := MatchResult{
+		MatchID:        match.ID,
+		Seed:           seed,
+		HandsRequested: match.Hands * mirrors,
+		BigBlind:       template.BigBlind,
+		Seats:          make([]SeatResult, 0, len(match.Bots)),
+	}
+	finalByBot := make(map[string]domain.Chips, len(match.Bots))
+	for mirror, botBySeat := range seating {
+		tableID := fmt.Sprintf("%s/%s", manifest.Name, match.ID)
+		if match.Duplicate {
+			tableID = fmt.Sprintf("%s/mirror-%d", tableID, mirror)
+		}
+		run, err := runMirror(ctx, tableID, seed, template, match.Hands, botBySeat, manifest, providers, func(state domain.HandState) {
+			_ = auditor.Record(mirror, state)
+		}, func(state domain.HandState, action domain.Action) {
+			if decision, ok := stats.ObserveDecision(state, action); ok {
+				botID := botBySeat[state.ActingSeat]
+				decisions[botID] = append(decisions[botID], decision)
+			}
+		})
+		if err != nil {
+			return MatchResult{}, err
+		}
+		out.HandsCompleted += run.HandsCompleted
+		for _, seat := range run.FinalSeats {
+			finalByBot[botBySeat[seat.SeatNo]] += seat.Stack
+		}
+	}
+
+	for i, botID := range match.Bots {
+		starting := template.StartingStack * domain.Chips(mirrors)
+		final := finalByBot[botID]
+		out.Seats = append(out.Seats, SeatResult{
+			SeatNo:        domain.SeatNo(i + 1),
+			BotID:         botID,
+			StartingStack: starting,
+			FinalStack:    final,
+			NetChips:      int64(final) - int64(starting),
+		})
+	}
+	if match.Duplicate {
+		out.Mirrors = mirrors
+		out.DealDivergences = auditor.Divergences()
+	}
+	return out, nil
+}
+
+// runMirror plays one table of a match with a fresh shuffler, so every
+// mirror of a duplicate match draws the same decks.
+func runMirror(
+	ctx context.Context,
+	tableID string,
+	seed int64,
+	template TableTemplate,
+	hands int,
+	botBySeat map[domain.SeatNo]string,
+	manifest Manifest,
+	providers ProviderFactory,
+	onHand func(domain.HandState),
+	onDecision func(domain.HandState, domain.Action),
+) (tablerunner.RunTableResult, error) {
+	router := seatRouter{providers: make(map[domain.SeatNo]tablerunner.ActionProvider, len(botBySeat))}
+	seats := make([]domain.SeatState, 0, len(botBySeat))
+	for seatNo := domain.SeatNo(1); int(seatNo) <= len(botBySeat); seatNo++ {
+		botID := botBySeat[seatNo]
+		bot, _ := manifest.bot(botID)
+		provider, err := providers(bot)
+		if err != nil {
+			return tablerunner.RunTableResult{}, fmt.Errorf("%w: %s: %v", ErrProviderUnavailable, botID, err)
+		}
+		if provider == nil {
+			return tablerunner.RunTableResult{}, fmt.Errorf("%w: %s", ErrProviderUnavailable, botID)
+		}
+		router.providers[seatNo] = provider
+		seats = append(seats, domain.NewSeatState(seatNo, template.StartingStack))
+	}
+
+	runner := tablerunner.New(router, tablerunner.RunnerConfig{
+		Shuffler: rules.NewSeededShuffler(seed),
+		OnHandComplete: func(summary tablerunner.HandSummary) {
+			onHand(summary.FinalState)
+		},
+		OnAction: func(_ uint64, state domain.HandState, action domain.Action, isFallback bool, _ tablerunner.ActionTiming) {
+			if !isFallback {
+				onDecision(state, action)
+			}
+		},
+	})
+	run, err := runner.RunTable(ctx, tablerunner.RunTableInput{
+		TableID:      tableID,
+		StartingHand: 1,
+		HandsToRun:   hands,
+		ButtonSeat:   1,
+		Seats:        seats,
+		Config:       template.TableConfig(),
+	})
+	// A match ends early once all but one bot has busted.
+	if err != nil && !errors.Is(err, tablerunner.ErrInsufficientActiveSeats) {
+		return run, err
+	}
+	return run, nil
+}
+
+type seatRouter struct {
+	providers map[domain.SeatNo]tablerunner.ActionProvider
+}
+
+func (r seatRouter) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	provider, ok := r.providers[state.ActingSeat]
+	if !ok {
+		return domain.Action{}, fmt.Errorf("%w: seat %d", ErrProviderUnavailable, state.ActingSeat)
+	}
+	return provider.NextAction(ctx, state)
+}
+
+type httpBotProvider struct {
+	client agentclient.Client
+	bot    Bot
+}
+
+func (p httpBotProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	return p.client.NextAction(ctx, agentclient.Request{
+		EndpointURL:     p.bot.EndpointURL,
+		State:           state,
+		ActingSeat:      state.ActingSeat,
+		ActionTimeoutMS: p.bot.TimeoutMS,
+	})
+}
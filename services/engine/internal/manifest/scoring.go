@@ -0,0 +1,132 @@
+package manifest
+
+import (
+	"fmt"
+	"maps"
+	"math"
+	"slices"
+	"sort"
+)
+
+// scoreFuncs rank one bot's record. ScoringWeighted combines them, so every
+// method here can be weighted.
+var scoreFuncs = map[ScoringMethod]func(record botRecord) float64{
+	ScoringNetChips: func(record botRecord) float64 { return float64(record.NetChips) },
+	ScoringBBPer100: func(record botRecord) float64 {
+		if record.Hands == 0 {
+			return 0
+		}
+		return record.bigBlinds / float64(record.Hands) * 100
+	},
+	ScoringPoints: func(record botRecord) float64 { return record.Points },
+}
+
+func (s Scoring) Validate() error {
+	usesPoints := s.Method == ScoringPoints
+	switch s.Method {
+	case ScoringNetChips, ScoringBBPer100, ScoringPoints:
+		if len(s.Weights) > 0 {
+			return fmt.Errorf("%w: weights only apply to %q scoring", ErrInvalidManifest, ScoringWeighted)
+		}
+	case ScoringWeighted:
+		if len(s.Weights) == 0 {
+			return fmt.Errorf("%w: %q scoring needs weights", ErrInvalidManifest, ScoringWeighted)
+		}
+		for method, weight := range s.Weights {
+			if _, ok := scoreFuncs[method]; !ok {
+				return fmt.Errorf("%w: cannot weight %q", ErrUnknownScoringRule, method)
+			}
+			if math.IsNaN(weight) || math.IsInf(weight, 0) {
+				return fmt.Errorf("%w: weight for %q must be finite", ErrInvalidManifest, method)
+			}
+			usesPoints = usesPoints || method == ScoringPoints
+		}
+	default:
+		return fmt.Errorf("%w: %q", ErrUnknownScoringRule, s.Method)
+	}
+	if usesPoints != (len(s.PointsTable) > 0) {
+		return fmt.Errorf("%w: points_table is required by, and only allowed with, points scoring", ErrInvalidManifest)
+	}
+	for i, points := range s.PointsTable {
+		if math.IsNaN(points) || math.IsInf(points, 0) {
+			return fmt.Errorf("%w: points_table entry %d must be finite", ErrInvalidManifest, i+1)
+		}
+	}
+	return nil
+}
+
+// botRecord is a Standing with what scoring needs on top: net chips in big
+// blinds of the match they were won in.
+type botRecord struct {
+	Standing
+	bigBlinds float64
+}
+
+func score(scoring Scoring, matches []MatchResult) []Standing {
+	byBot := make(map[string]*botRecord)
+	for _, match := range matches {
+		points := matchPoints(scoring.PointsTable, match.Seats)
+		for i, seat := range match.Seats {
+			record, ok := byBot[seat.BotID]
+			if !ok {
+				record = &botRecord{Standing: Standing{BotID: seat.BotID}}
+				byBot[seat.BotID] = record
+			}
+			record.Matches++
+			record.Hands += match.HandsCompleted
+			record.NetChips += seat.NetChips
+			record.Points += points[i]
+			record.bigBlinds += float64(seat.NetChips) / float64(match.BigBlind)
+		}
+	}
+
+	standings := make([]Standing, 0, len(byBot))
+	for _, record := range byBot {
+		if scoring.Method == ScoringWeighted {
+			// Adding in a fixed order keeps scores identical across runs.
+			for _, method := range slices.Sorted(maps.Keys(scoring.Weights)) {
+				record.Score += scoring.Weights[method] * scoreFuncs[method](*record)
+			}
+		} else {
+			record.Score = scoreFuncs[scoring.Method](*record)
+		}
+		standings = append(standings, record.Standing)
+	}
+	sort.Slice(standings, func(i, j int) bool {
+		if standings[i].Score == standings[j].Score {
+			return standings[i].BotID < standings[j].BotID
+		}
+		return standings[i].Score > standings[j].Score
+	})
+	return standings
+}
+
+// matchPoints awards table by net chips, most first, for each of seats.
+// Bots level on chips split the points of the places they span evenly, and
+// places past the end of table earn nothing.
+func matchPoints(table []float64, seats []SeatResult) []float64 {
+	points := make([]float64, len(seats))
+	if len(table) == 0 {
+		return points
+	}
+	order := make([]int, len(seats))
+	for i := range order {
+		order[i] = i
+	}
+	sort.SliceStable(order, func(a, b int) bool { return seats[order[a]].NetChips > seats[order[b]].NetChips })
+	for start := 0; start < len(order); {
+		end := start + 1
+		for end < len(order) && seats[order[end]].NetChips == seats[order[start]].NetChips {
+			end++
+		}
+		var shared float64
+		for place := start; place < end && place < len(table); place++ {
+			shared += table[place]
+		}
+		for _, i := range order[start:end] {
+			points[i] = shared / float64(end-start)
+		}
+		start = end
+	}
+	return points
+}
@@ -0,0 +1,43 @@
+package metrics
+
+import "time"
+
+// Arena is the set of metrics exported by the control plane.
+type Arena struct {
+	Registry *Registry
+
+	HandsDealt        *Counter
+	HandsCompleted    *Counter
+	Actions           *Counter
+	AgentLatency      *Histogram
+	AgentTimeouts     *Counter
+	AgentErrors       *Counter
+	SeatControllers   *Gauge
+	RunningTables     *Gauge
+	EventStoreLatency *Histogram
+	TableViewers      *Gauge
+	WatchTime         *Counter
+}
+
+func NewArena() *Arena {
+	reg := NewRegistry()
+	return &Arena{
+		Registry:          reg,
+		HandsDealt:        reg.NewCounter("poker_arena_hands_dealt_total", "Hands dealt."),
+		HandsCompleted:    reg.NewCounter("poker_arena_hands_completed_total", "Hands played to completion, by final phase.", "phase"),
+		Actions:           reg.NewCounter("poker_arena_actions_total", "Actions applied, by kind and whether the engine substituted a fallback.", "kind", "fallback"),
+		AgentLatency:      reg.NewHistogram("poker_arena_agent_request_seconds", "Time for an agent to answer an action request.", nil),
+		AgentTimeouts:     reg.NewCounter("poker_arena_agent_timeouts_total", "Agent action requests that timed out."),
+		AgentErrors:       reg.NewCounter("poker_arena_agent_errors_total", "Failed agent action requests, by reason.", "reason"),
+		SeatControllers:   reg.NewGauge("poker_arena_seat_controllers", "Seats with an active controller, by transport.", "transport"),
+		RunningTables:     reg.NewGauge("poker_arena_running_tables", "Tables with a run in progress."),
+		EventStoreLatency: reg.NewHistogram("poker_arena_event_store_seconds", "Latency of hand and action history writes, by operation.", nil, "operation"),
+		TableViewers:      reg.NewGauge("poker_arena_table_viewers", "Spectators watching a table.", "table"),
+		WatchTime:         reg.NewCounter("poker_arena_table_watch_seconds_total", "Time spectators have spent watching a table.", "table"),
+	}
+}
+
+// ObserveSince records the time elapsed since start in h.
+func ObserveSince(h *Histogram, start time.Time, labelValues ...string) {
+	h.Observe(time.Since(start).Seconds(), labelValues...)
+}
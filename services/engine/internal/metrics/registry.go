@@ -0,0 +1,286 @@
+// Package metrics implements the subset of the Prometheus data model the
+// arena exports (counters, gauges and histograms with labels) and renders it
+// in the text exposition format.
+package metrics
+
+import (
+	"fmt"
+	"io"
+	"math"
+	"net/http"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+)
+
+const contentType = "text/plain; version=0.0.4; charset=utf-8"
+
+// DefBuckets mirrors the Prometheus client default, in seconds.
+var DefBuckets = []float64{.005, .01, .025, .05, .1, .25, .5, 1, 2.5, 5, 10}
+
+type metricType string
+
+const (
+	typeCounter   metricType = "counter"
+	typeGauge     metricType = "gauge"
+	typeHistogram metricType = "histogram"
+)
+
+type collector interface {
+	write(w io.Writer) error
+}
+
+type Registry struct {
+	mu      sync.Mutex
+	byName  map[string]collector
+	ordered []string
+}
+
+func NewRegistry() *Registry {
+	return &Registry{byName: make(map[string]collector)}
+}
+
+func (r *Registry) register(name string, c collector) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	if _, exists := r.byName[name]; exists {
+		panic(fmt.Sprintf("metrics: duplicate registration of %q", name))
+	}
+	r.byName[name] = c
+	r.ordered = append(r.ordered, name)
+	sort.Strings(r.ordered)
+}
+
+// WriteText renders every registered metric, sorted by name.
+func (r *Registry) WriteText(w io.Writer) error {
+	r.mu.Lock()
+	names := append([]string(nil), r.ordered...)
+	collectors := make([]collector, 0, len(names))
+	for _, name := range names {
+		collectors = append(collectors, r.byName[name])
+	}
+	r.mu.Unlock()
+
+	for _, c := range collectors {
+		if err := c.write(w); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func (r *Registry) Handler() http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		w.Header().Set("Content-Type", contentType)
+		_ = r.WriteText(w)
+	})
+}
+
+// family holds one value slot per distinct label-value tuple.
+type family[T any] struct {
+	name   string
+	help   string
+	kind   metricType
+	labels []string
+
+	mu     sync.Mutex
+	series map[string]*T
+	values map[string][]string
+	newT   func() *T
+}
+
+func newFamily[T any](name, help string, kind metricType, labels []string, newT func() *T) *family[T] {
+	return &family[T]{
+		name:   name,
+		help:   help,
+		kind:   kind,
+		labels: append([]string(nil), labels...),
+		series: make(map[string]*T),
+		values: make(map[string][]string),
+		newT:   newT,
+	}
+}
+
+func (f *family[T]) with(labelValues []string, fn func(*T)) {
+	if len(labelValues) != len(f.labels) {
+		panic(fmt.Sprintf("metrics: %s expects %d label values, got %d", f.name, len(f.labels), len(labelValues)))
+	}
+	key := strings.Join(labelValues, "\xff")
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	slot, ok := f.series[key]
+	if !ok {
+		slot = f.newT()
+		f.series[key] = slot
+		f.values[key] = append([]string(nil), labelValues...)
+	}
+	fn(slot)
+}
+
+func (f *family[T]) each(fn func(labelValues []string, slot *T) error) error {
+	f.mu.Lock()
+	defer f.mu.Unlock()
+	keys := make([]string, 0, len(f.series))
+	for key := range f.series {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	for _, key := range keys {
+		if err := fn(f.values[key], f.series[key]); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func (f *family[T]) header(w io.Writer) error {
+	_, err := fmt.Fprintf(w, "# HELP %s %s\n# TYPE %s %s\n", f.name, escapeHelp(f.help), f.name, f.kind)
+	return err
+}
+
+type Counter struct {
+	f *family[float64]
+}
+
+func (r *Registry) NewCounter(name, help string, labels ...string) *Counter {
+	c := &Counter{f: newFamily(name, help, typeCounter, labels, func() *float64 { return new(float64) })}
+	r.register(name, c)
+	return c
+}
+
+func (c *Counter) Inc(labelValues ...string) {
+	c.Add(1, labelValues...)
+}
+
+// Add increases the counter; negative deltas are ignored.
+func (c *Counter) Add(delta float64, labelValues ...string) {
+	if delta < 0 {
+		return
+	}
+	c.f.with(labelValues, func(v *float64) { *v += delta })
+}
+
+func (c *Counter) write(w io.Writer) error {
+	if err := c.f.header(w); err != nil {
+		return err
+	}
+	return c.f.each(func(values []string, v *float64) error {
+		return writeSample(w, c.f.name, c.f.labels, values, "", "", *v)
+	})
+}
+
+type Gauge struct {
+	f *family[float64]
+}
+
+func (r *Registry) NewGauge(name, help string, labels ...string) *Gauge {
+	g := &Gauge{f: newFamily(name, help, typeGauge, labels, func() *float64 { return new(float64) })}
+	r.register(name, g)
+	return g
+}
+
+func (g *Gauge) Set(value float64, labelValues ...string) {
+	g.f.with(labelValues, func(v *float64) { *v = value })
+}
+
+func (g *Gauge) write(w io.Writer) error {
+	if err := g.f.header(w); err != nil {
+		return err
+	}
+	return g.f.each(func(values []string, v *float64) error {
+		return writeSample(w, g.f.name, g.f.labels, values, "", "", *v)
+	})
+}
+
+type histogramData struct {
+	counts []uint64
+	sum    float64
+	count  uint64
+}
+
+type Histogram struct {
+	f       *family[histogramData]
+	buckets []float64
+}
+
+// NewHistogram registers a histogram with the given upper bounds, which must
+// be sorted ascending; nil uses DefBuckets.
+func (r *Registry) NewHistogram(name, help string, buckets []float64, labels ...string) *Histogram {
+	if buckets == nil {
+		buckets = DefBuckets
+	}
+	bounds := append([]float64(nil), buckets...)
+	h := &Histogram{
+		buckets: bounds,
+		f: newFamily(name, help, typeHistogram, labels, func() *histogramData {
+			return &histogramData{counts: make([]uint64, len(bounds))}
+		}),
+	}
+	r.register(name, h)
+	return h
+}
+
+func (h *Histogram) Observe(value float64, labelValues ...string) {
+	h.f.with(labelValues, func(d *histogramData) {
+		for i, bound := range h.buckets {
+			if value <= bound {
+				d.counts[i]++
+			}
+		}
+		d.sum += value
+		d.count++
+	})
+}
+
+func (h *Histogram) write(w io.Writer) error {
+	if err := h.f.header(w); err != nil {
+		return err
+	}
+	return h.f.each(func(values []string, d *histogramData) error {
+		for i, bound := range h.buckets {
+			if err := writeSample(w, h.f.name+"_bucket", h.f.labels, values, "le", formatFloat(bound), float64(d.counts[i])); err != nil {
+				return err
+			}
+		}
+		if err := writeSample(w, h.f.name+"_bucket", h.f.labels, values, "le", "+Inf", float64(d.count)); err != nil {
+			return err
+		}
+		if err := writeSample(w, h.f.name+"_sum", h.f.labels, values, "", "", d.sum); err != nil {
+			return err
+		}
+		return writeSample(w, h.f.name+"_count", h.f.labels, values, "", "", float64(d.count))
+	})
+}
+
+func writeSample(w io.Writer, name string, labels, values []string, extraLabel, extraValue string, value float64) error {
+	pairs := make([]string, 0, len(labels)+1)
+	for i, label := range labels {
+		pairs = append(pairs, fmt.Sprintf("%s=%q", label, values[i]))
+	}
+	if extraLabel != "" {
+		pairs = append(pairs, fmt.Sprintf("%s=%q", extraLabel, extraValue))
+	}
+	if len(pairs) == 0 {
+		_, err := fmt.Fprintf(w, "%s %s\n", name, formatFloat(value))
+		return err
+	}
+	_, err := fmt.Fprintf(w, "%s{%s} %s\n", name, strings.Join(pairs, ","), formatFloat(value))
+	return err
+}
+
+func formatFloat(v float64) string {
+	switch {
+	case math.IsInf(v, 1):
+		return "+Inf"
+	case math.IsInf(v, -1):
+		return "-Inf"
+	case math.IsNaN(v):
+		return "NaN"
+	}
+	return strconv.FormatFloat(v, 'g', -1, 64)
+}
+
+func escapeHelp(s string) string {
+	return strings.NewReplacer(`\`, `\\`, "\n", `\n`).Replace(s)
+}
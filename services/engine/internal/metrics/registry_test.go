@@ -0,0 +1,46 @@
+package metrics
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestRegistry_WritesTextExposition(t *testing.T) {
+	t.Parallel()
+
+	reg := NewRegistry()
+	actions := reg.NewCounter("arena_actions_total", "Actions applied.", "kind")
+	latency := reg.NewHistogram("arena_latency_seconds", "Latency.", []float64{0.1, 1})
+	tables := reg.NewGauge("arena_tables", "Tables.")
+
+	actions.Inc("fold")
+	actions.Add(2, "call")
+	actions.Add(-5, "call")
+	latency.Observe(0.0625)
+	latency.Observe(0.5)
+	latency.Observe(3)
+	tables.Set(4)
+
+	var out strings.Builder
+	if err := reg.WriteText(&out); err != nil {
+		t.Fatalf("WriteText failed: %v", err)
+	}
+	want := `# HELP arena_actions_total Actions applied.
+# TYPE arena_actions_total counter
+arena_actions_total{kind="call"} 2
+arena_actions_total{kind="fold"} 1
+# HELP arena_latency_seconds Latency.
+# TYPE arena_latency_seconds histogram
+arena_latency_seconds_bucket{le="0.1"} 1
+arena_latency_seconds_bucket{le="1"} 2
+arena_latency_seconds_bucket{le="+Inf"} 3
+arena_latency_seconds_sum 3.5625
+arena_latency_seconds_count 3
+# HELP arena_tables Tables.
+# TYPE arena_tables gauge
+arena_tables 4
+`
+	if out.String() != want {
+		t.Fatalf("unexpected exposition\nwant:\n%s\ngot:\n%s", want, out.String())
+	}
+}
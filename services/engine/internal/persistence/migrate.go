@@ -12,6 +12,36 @@ var (
 	migration0001Up string
 	//go:embed migrations/0002_resources.up.sql
 	migration0002Up string
+	//go:embed migrations/0003_showdown_preference.up.sql
+	migration0003Up string
+	//go:embed migrations/0004_seat_departures.up.sql
+	migration0004Up string
+	//go:embed migrations/0005_action_timing.up.sql
+	migration0005Up string
+	//go:embed migrations/0006_tenants.up.sql
+	migration0006Up string
+	//go:embed migrations/0007_table_rule_flags.up.sql
+	migration0007Up string
+	//go:embed migrations/0008_agent_buy_in_policy.up.sql
+	migration0008Up string
+	//go:embed migrations/0009_action_think_time.up.sql
+	migration0009Up string
+	//go:embed migrations/0010_action_telemetry.up.sql
+	migration0010Up string
+	//go:embed migrations/0011_chips_bigint.up.sql
+	migration0011Up string
+	//go:embed migrations/0012_seat_view_level.up.sql
+	migration0012Up string
+	//go:embed migrations/0013_hand_annotations.up.sql
+	migration0013Up string
+	//go:embed migrations/0014_action_latency_compensation.up.sql
+	migration0014Up string
+	//go:embed migrations/0015_table_rules_version.up.sql
+	migration0015Up string
+	//go:embed migrations/0016_hand_equity.up.sql
+	migration0016Up string
+	//go:embed migrations/0017_table_variant.up.sql
+	migration0017Up string
 )
 
 func MigratePostgres(ctx context.Context, db *sql.DB) error {
@@ -33,5 +63,50 @@ func MigratePostgres(ctx context.Context, db *sql.DB) error {
 	if _, err := db.ExecContext(ctx, migration0002Up); err != nil {
 		return fmt.Errorf("apply migration 0002_resources.up.sql: %w", err)
 	}
+	if _, err := db.ExecContext(ctx, migration0003Up); err != nil {
+		return fmt.Errorf("apply migration 0003_showdown_preference.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0004Up); err != nil {
+		return fmt.Errorf("apply migration 0004_seat_departures.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0005Up); err != nil {
+		return fmt.Errorf("apply migration 0005_action_timing.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0006Up); err != nil {
+		return fmt.Errorf("apply migration 0006_tenants.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0007Up); err != nil {
+		return fmt.Errorf("apply migration 0007_table_rule_flags.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0008Up); err != nil {
+		return fmt.Errorf("apply migration 0008_agent_buy_in_policy.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0009Up); err != nil {
+		return fmt.Errorf("apply migration 0009_action_think_time.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0010Up); err != nil {
+		return fmt.Errorf("apply migration 0010_action_telemetry.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0011Up); err != nil {
+		return fmt.Errorf("apply migration 0011_chips_bigint.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0012Up); err != nil {
+		return fmt.Errorf("apply migration 0012_seat_view_level.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0013Up); err != nil {
+		return fmt.Errorf("apply migration 0013_hand_annotations.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0014Up); err != nil {
+		return fmt.Errorf("apply migration 0014_action_latency_compensation.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0015Up); err != nil {
+		return fmt.Errorf("apply migration 0015_table_rules_version.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0016Up); err != nil {
+		return fmt.Errorf("apply migration 0016_hand_equity.up.sql: %w", err)
+	}
+	if _, err := db.ExecContext(ctx, migration0017Up); err != nil {
+		return fmt.Errorf("apply migration 0017_table_variant.up.sql: %w", err)
+	}
 	return nil
 }
@@ -0,0 +1 @@
+ALTER TABLE agents ADD COLUMN IF NOT EXISTS showdown_preference TEXT NOT NULL DEFAULT 'auto_muck';
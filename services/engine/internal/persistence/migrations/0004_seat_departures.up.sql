@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS seat_departures (
+  id BIGSERIAL PRIMARY KEY,
+  table_id TEXT NOT NULL,
+  user_id TEXT NOT NULL,
+  agent_id TEXT NOT NULL,
+  small_blind INTEGER NOT NULL,
+  big_blind INTEGER NOT NULL,
+  stack INTEGER NOT NULL,
+  left_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_seat_departures_user_stakes ON seat_departures(user_id, small_blind, big_blind, left_at DESC);
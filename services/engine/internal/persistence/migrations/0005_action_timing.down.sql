@@ -0,0 +1,2 @@
+ALTER TABLE actions DROP COLUMN IF EXISTS deadline_policy;
+ALTER TABLE actions DROP COLUMN IF EXISTS received_at;
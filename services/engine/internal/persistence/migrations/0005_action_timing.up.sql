@@ -0,0 +1,2 @@
+ALTER TABLE actions ADD COLUMN IF NOT EXISTS received_at TIMESTAMPTZ;
+ALTER TABLE actions ADD COLUMN IF NOT EXISTS deadline_policy TEXT NOT NULL DEFAULT '';
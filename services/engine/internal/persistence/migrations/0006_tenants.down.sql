@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_tables_tenant;
+ALTER TABLE tables DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE agents DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE users DROP COLUMN IF EXISTS tenant_id;
@@ -0,0 +1 @@
+ALTER TABLE tables ADD COLUMN IF NOT EXISTS rule_flags BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+ALTER TABLE agents DROP COLUMN IF EXISTS buy_in_policy;
@@ -0,0 +1 @@
+ALTER TABLE agents ADD COLUMN IF NOT EXISTS buy_in_policy JSONB;
@@ -0,0 +1 @@
+ALTER TABLE actions DROP COLUMN IF EXISTS think_time_ms;
@@ -0,0 +1 @@
+ALTER TABLE actions ADD COLUMN IF NOT EXISTS think_time_ms BIGINT NOT NULL DEFAULT 0;
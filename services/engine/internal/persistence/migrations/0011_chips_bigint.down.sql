@@ -0,0 +1,4 @@
+ALTER TABLE seat_departures ALTER COLUMN small_blind TYPE INTEGER, ALTER COLUMN big_blind TYPE INTEGER, ALTER COLUMN stack TYPE INTEGER;
+ALTER TABLE seats ALTER COLUMN stack TYPE INTEGER;
+ALTER TABLE tables ALTER COLUMN small_blind TYPE INTEGER, ALTER COLUMN big_blind TYPE INTEGER;
+ALTER TABLE actions ALTER COLUMN amount TYPE INTEGER;
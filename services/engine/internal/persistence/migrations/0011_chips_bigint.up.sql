@@ -0,0 +1,4 @@
+ALTER TABLE actions ALTER COLUMN amount TYPE BIGINT;
+ALTER TABLE tables ALTER COLUMN small_blind TYPE BIGINT, ALTER COLUMN big_blind TYPE BIGINT;
+ALTER TABLE seats ALTER COLUMN stack TYPE BIGINT;
+ALTER TABLE seat_departures ALTER COLUMN small_blind TYPE BIGINT, ALTER COLUMN big_blind TYPE BIGINT, ALTER COLUMN stack TYPE BIGINT;
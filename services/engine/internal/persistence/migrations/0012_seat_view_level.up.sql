@@ -0,0 +1 @@
+ALTER TABLE seats ADD COLUMN IF NOT EXISTS view_level TEXT NOT NULL DEFAULT '';
@@ -0,0 +1 @@
+ALTER TABLE hands DROP COLUMN IF EXISTS annotations;
@@ -0,0 +1 @@
+ALTER TABLE hands ADD COLUMN IF NOT EXISTS annotations JSONB NOT NULL DEFAULT '[]'::jsonb;
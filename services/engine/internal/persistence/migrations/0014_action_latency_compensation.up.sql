@@ -0,0 +1 @@
+ALTER TABLE actions ADD COLUMN IF NOT EXISTS latency_compensation_ms BIGINT NOT NULL DEFAULT 0;
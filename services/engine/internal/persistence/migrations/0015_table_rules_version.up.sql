@@ -0,0 +1,2 @@
+ALTER TABLE tables ADD COLUMN IF NOT EXISTS rules_version SMALLINT NOT NULL DEFAULT 1;
+ALTER TABLE tables ALTER COLUMN rules_version DROP DEFAULT;
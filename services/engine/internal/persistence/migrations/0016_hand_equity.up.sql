@@ -0,0 +1 @@
+ALTER TABLE hands ADD COLUMN IF NOT EXISTS equity JSONB NOT NULL DEFAULT '[]'::jsonb;
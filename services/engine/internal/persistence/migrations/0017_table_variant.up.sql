@@ -0,0 +1 @@
+ALTER TABLE tables ADD COLUMN IF NOT EXISTS variant TEXT NOT NULL DEFAULT 'standard';
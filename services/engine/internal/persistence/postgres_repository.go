@@ -7,8 +7,11 @@ import (
 	"errors"
 	"fmt"
 	"strings"
+	"time"
 
+	"github.com/imaddar/poker-arena/services/engine/internal/cashier"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
 )
 
 type postgresRepository struct {
@@ -90,10 +93,14 @@ func (r *postgresRepository) CreateHand(record HandRecord) error {
 	if err != nil {
 		return fmt.Errorf("marshal winner summary: %w", err)
 	}
+	annotations, err := marshalAnnotations(record.Annotations)
+	if err != nil {
+		return err
+	}
 	const q = `
 INSERT INTO hands (
-  hand_id, table_id, hand_no, started_at, ended_at, final_phase, final_state, winner_summary
-) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
+  hand_id, table_id, hand_no, started_at, ended_at, final_phase, final_state, winner_summary, annotations
+) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9)
 `
 	_, err = r.db.ExecContext(context.Background(), q,
 		record.HandID,
@@ -104,6 +111,7 @@ INSERT INTO hands (
 		string(record.FinalPhase),
 		finalState,
 		winnerSummary,
+		annotations,
 	)
 	if isUniqueViolation(err) {
 		return ErrHandAlreadyExists
@@ -113,7 +121,7 @@ INSERT INTO hands (
 
 func (r *postgresRepository) GetHand(handID string) (HandRecord, bool, error) {
 	const q = `
-SELECT hand_id, table_id, hand_no, started_at, ended_at, final_phase, final_state, winner_summary
+SELECT hand_id, table_id, hand_no, started_at, ended_at, final_phase, final_state, winner_summary, annotations, equity
 FROM hands
 WHERE hand_id = $1
 `
@@ -121,6 +129,8 @@ WHERE hand_id = $1
 	var finalPhase string
 	var finalStateRaw []byte
 	var winnerSummaryRaw []byte
+	var annotationsRaw []byte
+	var equityRaw []byte
 	err := r.db.QueryRowContext(context.Background(), q, handID).Scan(
 		&rec.HandID,
 		&rec.TableID,
@@ -130,6 +140,8 @@ WHERE hand_id = $1
 		&finalPhase,
 		&finalStateRaw,
 		&winnerSummaryRaw,
+		&annotationsRaw,
+		&equityRaw,
 	)
 	if errors.Is(err, sql.ErrNoRows) {
 		return HandRecord{}, false, nil
@@ -148,6 +160,16 @@ WHERE hand_id = $1
 			return HandRecord{}, false, fmt.Errorf("unmarshal winner_summary for hand %s: %w", rec.HandID, err)
 		}
 	}
+	if len(annotationsRaw) > 0 {
+		if err := json.Unmarshal(annotationsRaw, &rec.Annotations); err != nil {
+			return HandRecord{}, false, fmt.Errorf("unmarshal annotations for hand %s: %w", rec.HandID, err)
+		}
+	}
+	if len(equityRaw) > 0 {
+		if err := json.Unmarshal(equityRaw, &rec.Equity); err != nil {
+			return HandRecord{}, false, fmt.Errorf("unmarshal equity for hand %s: %w", rec.HandID, err)
+		}
+	}
 	return rec, true, nil
 }
 
@@ -160,9 +182,13 @@ func (r *postgresRepository) CompleteHand(handID string, final HandRecord) error
 	if err != nil {
 		return fmt.Errorf("marshal winner summary: %w", err)
 	}
+	equityRaw, err := marshalStreetEquity(final.Equity)
+	if err != nil {
+		return err
+	}
 	const q = `
 UPDATE hands
-SET table_id=$2, hand_no=$3, started_at=$4, ended_at=$5, final_phase=$6, final_state=$7, winner_summary=$8
+SET table_id=$2, hand_no=$3, started_at=$4, ended_at=$5, final_phase=$6, final_state=$7, winner_summary=$8, equity=$9
 WHERE hand_id = $1
 `
 	result, err := r.db.ExecContext(context.Background(), q,
@@ -174,6 +200,7 @@ WHERE hand_id = $1
 		string(final.FinalPhase),
 		finalState,
 		winnerSummary,
+		equityRaw,
 	)
 	if err != nil {
 		return err
@@ -188,11 +215,67 @@ WHERE hand_id = $1
 	return nil
 }
 
+func (r *postgresRepository) AnnotateHand(handID string, annotations []string) error {
+	raw, err := marshalAnnotations(annotations)
+	if err != nil {
+		return err
+	}
+	const q = `
+UPDATE hands
+SET annotations = $2
+WHERE hand_id = $1
+`
+	result, err := r.db.ExecContext(context.Background(), q, handID, raw)
+	if err != nil {
+		return err
+	}
+	rows, err := result.RowsAffected()
+	if err != nil {
+		return err
+	}
+	if rows == 0 {
+		return ErrHandNotFound
+	}
+	return nil
+}
+
+func (r *postgresRepository) SetHandEquity(handID string, points []equity.StreetEquity) error {
+	raw, err := marshalStreetEquity(points)
+	if err != nil {
+		return err
+	}
+	const q = `
+UPDATE hands
+SET equity = $2
+WHERE hand_id = $1
+`
+	result, err := r.db.ExecContext(context.Background(), q, handID, raw)
+	if err != nil {
+		return err
+	}
+	rows, err := result.RowsAffected()
+	if err != nil {
+		return err
+	}
+	if rows == 0 {
+		return ErrHandNotFound
+	}
+	return nil
+}
+
 func (r *postgresRepository) AppendAction(record ActionRecord) error {
+	var telemetry any
+	if record.Telemetry != nil {
+		raw, err := json.Marshal(record.Telemetry)
+		if err != nil {
+			return fmt.Errorf("encode telemetry: %w", err)
+		}
+		telemetry = raw
+	}
 	const q = `
 INSERT INTO actions (
-  hand_id, street, acting_seat, action, amount, is_fallback, at
-) VALUES ($1,$2,$3,$4,$5,$6,$7)
+  hand_id, street, acting_seat, action, amount, is_fallback, at, received_at, deadline_policy, think_time_ms, telemetry, latency_compensation_ms
+) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12)
 `
 	_, err := r.db.ExecContext(context.Background(), q,
 		record.HandID,
@@ -202,6 +285,11 @@ INSERT INTO actions (
 		record.Amount,
 		record.IsFallback,
 		record.At,
+		nullableTime(record.ReceivedAt),
+		string(record.DeadlinePolicy),
+		int64(record.ThinkTimeMS),
+		telemetry,
+		int64(record.LatencyCompensationMS),
 	)
 	if isForeignKeyViolation(err) {
 		return ErrHandNotFound
@@ -211,7 +299,7 @@ INSERT INTO actions (
 
 func (r *postgresRepository) ListHands(tableID string) ([]HandRecord, error) {
 	const q = `
-SELECT hand_id, table_id, hand_no, started_at, ended_at, final_phase, final_state, winner_summary
+SELECT hand_id, table_id, hand_no, started_at, ended_at, final_phase, final_state, winner_summary, annotations, equity
 FROM hands
 WHERE table_id = $1
 ORDER BY hand_no ASC, hand_id ASC
@@ -228,6 +316,8 @@ ORDER BY hand_no ASC, hand_id ASC
 		var finalPhase string
 		var finalStateRaw []byte
 		var winnerSummaryRaw []byte
+		var annotationsRaw []byte
+		var equityRaw []byte
 		if err := rows.Scan(
 			&rec.HandID,
 			&rec.TableID,
@@ -237,6 +327,8 @@ ORDER BY hand_no ASC, hand_id ASC
 			&finalPhase,
 			&finalStateRaw,
 			&winnerSummaryRaw,
+			&annotationsRaw,
+			&equityRaw,
 		); err != nil {
 			return nil, err
 		}
@@ -251,6 +343,16 @@ ORDER BY hand_no ASC, hand_id ASC
 				return nil, fmt.Errorf("unmarshal winner_summary for hand %s: %w", rec.HandID, err)
 			}
 		}
+		if len(annotationsRaw) > 0 {
+			if err := json.Unmarshal(annotationsRaw, &rec.Annotations); err != nil {
+				return nil, fmt.Errorf("unmarshal annotations for hand %s: %w", rec.HandID, err)
+			}
+		}
+		if len(equityRaw) > 0 {
+			if err := json.Unmarshal(equityRaw, &rec.Equity); err != nil {
+				return nil, fmt.Errorf("unmarshal equity for hand %s: %w", rec.HandID, err)
+			}
+		}
 		out = append(out, rec)
 	}
 	if err := rows.Err(); err != nil {
@@ -261,7 +363,7 @@ ORDER BY hand_no ASC, hand_id ASC
 
 func (r *postgresRepository) ListActions(handID string) ([]ActionRecord, error) {
 	const q = `
-SELECT hand_id, street, acting_seat, action, amount, is_fallback, at
+SELECT hand_id, street, acting_seat, action, amount, is_fallback, at, received_at, deadline_policy, think_time_ms, telemetry, latency_compensation_ms
 FROM actions
 WHERE hand_id = $1
 ORDER BY id ASC
@@ -271,14 +373,41 @@ ORDER BY id ASC
 		return nil, err
 	}
 	defer rows.Close()
+	return scanActions(rows)
+}
 
+// ListTableActions loads the actions of the table's hands that started at or
+// after since in one query, rather than one ListActions call per hand.
+func (r *postgresRepository) ListTableActions(tableID string, since time.Time) ([]ActionRecord, error) {
+	const q = `
+SELECT a.hand_id, a.street, a.acting_seat, a.action, a.amount, a.is_fallback, a.at, a.received_at, a.deadline_policy, a.think_time_ms, a.telemetry, a.latency_compensation_ms
+FROM actions a
+JOIN hands h ON h.hand_id = a.hand_id
+WHERE h.table_id = $1 AND h.started_at >= $2
+ORDER BY h.hand_no ASC, h.hand_id ASC, a.id ASC
+`
+	rows, err := r.db.QueryContext(context.Background(), q, tableID, since)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	return scanActions(rows)
+}
+
+// scanActions reads action rows selected in ListActions' column order.
+func scanActions(rows *sql.Rows) ([]ActionRecord, error) {
 	out := make([]ActionRecord, 0, 64)
 	for rows.Next() {
 		var rec ActionRecord
 		var street string
 		var action string
 		var actingSeat int16
-		var amount sql.NullInt32
+		var amount sql.NullInt64
+		var receivedAt sql.NullTime
+		var deadlinePolicy string
+		var thinkTimeMS int64
+		var telemetry []byte
+		var latencyCompensationMS int64
 		if err := rows.Scan(
 			&rec.HandID,
 			&street,
@@ -287,16 +416,33 @@ ORDER BY id ASC
 			&amount,
 			&rec.IsFallback,
 			&rec.At,
+			&receivedAt,
+			&deadlinePolicy,
+			&thinkTimeMS,
+			&telemetry,
+			&latencyCompensationMS,
 		); err != nil {
 			return nil, err
 		}
 		rec.Street = domain.Street(street)
 		rec.ActingSeat = domain.SeatNo(actingSeat)
 		rec.Action = domain.ActionKind(action)
+		rec.DeadlinePolicy = domain.DeadlinePolicy(deadlinePolicy)
+		rec.ThinkTimeMS = uint64(thinkTimeMS)
+		rec.LatencyCompensationMS = uint64(latencyCompensationMS)
+		if receivedAt.Valid {
+			rec.ReceivedAt = receivedAt.Time
+		}
 		if amount.Valid {
-			value := uint32(amount.Int32)
+			value := domain.Chips(amount.Int64)
 			rec.Amount = &value
 		}
+		if telemetry != nil {
+			rec.Telemetry = &domain.Telemetry{}
+			if err := json.Unmarshal(telemetry, rec.Telemetry); err != nil {
+				return nil, fmt.Errorf("decode telemetry: %w", err)
+			}
+		}
 		out = append(out, rec)
 	}
 	if err := rows.Err(); err != nil {
@@ -307,33 +453,52 @@ ORDER BY id ASC
 
 func (r *postgresRepository) CreateUser(record UserRecord) error {
 	const q = `
-INSERT INTO users (id, name, token, created_at)
-VALUES ($1,$2,$3,$4)
+INSERT INTO users (id, name, token, created_at, tenant_id)
+VALUES ($1,$2,$3,$4,$5)
 `
 	_, err := r.db.ExecContext(context.Background(), q,
 		record.ID,
 		record.Name,
 		record.Token,
 		record.CreatedAt,
+		record.TenantID,
 	)
 	return err
 }
 
 func (r *postgresRepository) CreateAgent(record AgentRecord) error {
 	const q = `
-INSERT INTO agents (id, user_id, name, created_at)
-VALUES ($1,$2,$3,$4)
+INSERT INTO agents (id, user_id, name, showdown_preference, created_at, tenant_id)
+SELECT $1::text, $2::text, $3::text, $4::text, $5::timestamptz, $6::text
+WHERE EXISTS (SELECT 1 FROM users WHERE id = $2 AND tenant_id = $6)
 `
-	_, err := r.db.ExecContext(context.Background(), q,
+	pref := record.ShowdownPreference
+	if pref == "" {
+		pref = domain.ShowdownAutoMuck
+	}
+	result, err := r.db.ExecContext(context.Background(), q,
 		record.ID,
 		record.UserID,
 		record.Name,
+		string(pref),
 		record.CreatedAt,
+		record.TenantID,
 	)
 	if isForeignKeyViolation(err) {
 		return ErrUserNotFound
 	}
-	return err
+	if err != nil {
+		return err
+	}
+	// A user in another tenant is as good as missing.
+	rows, err := result.RowsAffected()
+	if err != nil {
+		return err
+	}
+	if rows == 0 {
+		return ErrUserNotFound
+	}
+	return nil
 }
 
 func (r *postgresRepository) CreateAgentVersion(record AgentVersionRecord) error {
@@ -360,17 +525,21 @@ VALUES ($1,$2,$3,$4,$5,$6)
 
 func (r *postgresRepository) CreateTable(record TableRecord) error {
 	const q = `
-INSERT INTO tables (id, name, max_seats, small_blind, big_blind, status, created_at)
-VALUES ($1,$2,$3,$4,$5,$6,$7)
+INSERT INTO tables (id, name, max_seats, small_blind, big_blind, status, created_at, tenant_id, rule_flags, rules_version, variant)
+VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
 `
 	_, err := r.db.ExecContext(context.Background(), q,
 		record.ID,
 		record.Name,
 		int16(record.MaxSeats),
-		int32(record.SmallBlind),
-		int32(record.BigBlind),
+		int64(record.SmallBlind),
+		int64(record.BigBlind),
 		record.Status,
 		record.CreatedAt,
+		record.TenantID,
+		int64(record.RuleFlags),
+		int16(record.RulesVersion.Effective()),
+		string(record.Variant.Effective()),
 	)
 	return err
 }
@@ -385,15 +554,16 @@ func (r *postgresRepository) UpsertSeat(record SeatRecord) error {
 	}
 
 	const q = `
-INSERT INTO seats (id, table_id, seat_no, agent_id, agent_version_id, stack, status, created_at)
-VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
+INSERT INTO seats (id, table_id, seat_no, agent_id, agent_version_id, stack, status, created_at, view_level)
+VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9)
 ON CONFLICT (table_id, seat_no) DO UPDATE SET
   id = EXCLUDED.id,
   agent_id = EXCLUDED.agent_id,
   agent_version_id = EXCLUDED.agent_version_id,
   stack = EXCLUDED.stack,
   status = EXCLUDED.status,
-  created_at = EXCLUDED.created_at
+  created_at = EXCLUDED.created_at,
+  view_level = EXCLUDED.view_level
 `
 	_, err = r.db.ExecContext(context.Background(), q,
 		record.ID,
@@ -401,9 +571,10 @@ ON CONFLICT (table_id, seat_no) DO UPDATE SET
 		int16(record.SeatNo),
 		record.AgentID,
 		record.AgentVersionID,
-		int32(record.Stack),
+		int64(record.Stack),
 		string(record.Status),
 		record.CreatedAt,
+		string(record.View),
 	)
 	if isForeignKeyViolation(err) {
 		if strings.Contains(err.Error(), "table_id") {
@@ -421,14 +592,16 @@ ON CONFLICT (table_id, seat_no) DO UPDATE SET
 
 func (r *postgresRepository) GetTable(tableID string) (TableRecord, bool, error) {
 	const q = `
-SELECT id, name, max_seats, small_blind, big_blind, status, created_at
+SELECT id, name, max_seats, small_blind, big_blind, status, created_at, tenant_id, rule_flags, rules_version, variant
 FROM tables
 WHERE id = $1
 `
 	var rec TableRecord
 	var maxSeats int16
-	var smallBlind int32
-	var bigBlind int32
+	var smallBlind int64
+	var bigBlind int64
+	var ruleFlags int64
+	var rulesVersion int16
 	err := r.db.QueryRowContext(context.Background(), q, tableID).Scan(
 		&rec.ID,
 		&rec.Name,
@@ -437,6 +610,10 @@ WHERE id = $1
 		&bigBlind,
 		&rec.Status,
 		&rec.CreatedAt,
+		&rec.TenantID,
+		&ruleFlags,
+		&rulesVersion,
+		&rec.Variant,
 	)
 	if errors.Is(err, sql.ErrNoRows) {
 		return TableRecord{}, false, nil
@@ -445,14 +622,16 @@ WHERE id = $1
 		return TableRecord{}, false, err
 	}
 	rec.MaxSeats = uint8(maxSeats)
-	rec.SmallBlind = uint32(smallBlind)
-	rec.BigBlind = uint32(bigBlind)
+	rec.SmallBlind = domain.Chips(smallBlind)
+	rec.BigBlind = domain.Chips(bigBlind)
+	rec.RuleFlags = domain.RuleFlags(ruleFlags)
+	rec.RulesVersion = domain.RulesVersion(rulesVersion)
 	return rec, true, nil
 }
 
 func (r *postgresRepository) ListTables() ([]TableRecord, error) {
 	const q = `
-SELECT id, name, max_seats, small_blind, big_blind, status, created_at
+SELECT id, name, max_seats, small_blind, big_blind, status, created_at, tenant_id, rule_flags, rules_version, variant
 FROM tables
 ORDER BY id ASC
 `
@@ -466,8 +645,10 @@ ORDER BY id ASC
 	for rows.Next() {
 		var rec TableRecord
 		var maxSeats int16
-		var smallBlind int32
-		var bigBlind int32
+		var smallBlind int64
+		var bigBlind int64
+		var ruleFlags int64
+		var rulesVersion int16
 		if err := rows.Scan(
 			&rec.ID,
 			&rec.Name,
@@ -476,12 +657,18 @@ ORDER BY id ASC
 			&bigBlind,
 			&rec.Status,
 			&rec.CreatedAt,
+			&rec.TenantID,
+			&ruleFlags,
+			&rulesVersion,
+			&rec.Variant,
 		); err != nil {
 			return nil, err
 		}
 		rec.MaxSeats = uint8(maxSeats)
-		rec.SmallBlind = uint32(smallBlind)
-		rec.BigBlind = uint32(bigBlind)
+		rec.SmallBlind = domain.Chips(smallBlind)
+		rec.BigBlind = domain.Chips(bigBlind)
+		rec.RuleFlags = domain.RuleFlags(ruleFlags)
+		rec.RulesVersion = domain.RulesVersion(rulesVersion)
 		out = append(out, rec)
 	}
 	if err := rows.Err(); err != nil {
@@ -492,7 +679,7 @@ ORDER BY id ASC
 
 func (r *postgresRepository) ListSeats(tableID string) ([]SeatRecord, error) {
 	const q = `
-SELECT id, table_id, seat_no, agent_id, agent_version_id, stack, status, created_at
+SELECT id, table_id, seat_no, agent_id, agent_version_id, stack, status, created_at, view_level
 FROM seats
 WHERE table_id = $1
 ORDER BY seat_no ASC
@@ -507,7 +694,7 @@ ORDER BY seat_no ASC
 	for rows.Next() {
 		var rec SeatRecord
 		var seatNo int16
-		var stack int32
+		var stack int64
 		if err := rows.Scan(
 			&rec.ID,
 			&rec.TableID,
@@ -517,11 +704,12 @@ ORDER BY seat_no ASC
 			&stack,
 			&rec.Status,
 			&rec.CreatedAt,
+			&rec.View,
 		); err != nil {
 			return nil, err
 		}
 		rec.SeatNo = domain.SeatNo(seatNo)
-		rec.Stack = uint32(stack)
+		rec.Stack = domain.Chips(stack)
 		out = append(out, rec)
 	}
 	if err := rows.Err(); err != nil {
@@ -556,6 +744,218 @@ WHERE id = $1
 	return rec, true, nil
 }
 
+func (r *postgresRepository) GetAgent(agentID string) (AgentRecord, bool, error) {
+	const q = `
+SELECT id, user_id, name, showdown_preference, created_at, tenant_id, buy_in_policy
+FROM agents
+WHERE id = $1
+`
+	var rec AgentRecord
+	var pref string
+	var policy []byte
+	err := r.db.QueryRowContext(context.Background(), q, agentID).Scan(
+		&rec.ID,
+		&rec.UserID,
+		&rec.Name,
+		&pref,
+		&rec.CreatedAt,
+		&rec.TenantID,
+		&policy,
+	)
+	if errors.Is(err, sql.ErrNoRows) {
+		return AgentRecord{}, false, nil
+	}
+	if err != nil {
+		return AgentRecord{}, false, err
+	}
+	rec.ShowdownPreference = domain.ShowdownPreference(pref)
+	if policy != nil {
+		rec.BuyInPolicy = &cashier.Policy{}
+		if err := json.Unmarshal(policy, rec.BuyInPolicy); err != nil {
+			return AgentRecord{}, false, fmt.Errorf("decode buy-in policy: %w", err)
+		}
+	}
+	return rec, true, nil
+}
+
+func (r *postgresRepository) SetAgentBuyInPolicy(agentID string, policy *cashier.Policy) error {
+	const q = `
+UPDATE agents
+SET buy_in_policy = $2
+WHERE id = $1
+`
+	var encoded any
+	if policy != nil {
+		raw, err := json.Marshal(policy)
+		if err != nil {
+			return fmt.Errorf("encode buy-in policy: %w", err)
+		}
+		encoded = raw
+	}
+	result, err := r.db.ExecContext(context.Background(), q, agentID, encoded)
+	if err != nil {
+		return err
+	}
+	rows, err := result.RowsAffected()
+	if err != nil {
+		return err
+	}
+	if rows == 0 {
+		return ErrAgentNotFound
+	}
+	return nil
+}
+
+func (r *postgresRepository) SetAgentShowdownPreference(agentID string, pref domain.ShowdownPreference) error {
+	const q = `
+UPDATE agents
+SET showdown_preference = $2
+WHERE id = $1
+`
+	result, err := r.db.ExecContext(context.Background(), q, agentID, string(pref))
+	if err != nil {
+		return err
+	}
+	rows, err := result.RowsAffected()
+	if err != nil {
+		return err
+	}
+	if rows == 0 {
+		return ErrAgentNotFound
+	}
+	return nil
+}
+
+func (r *postgresRepository) RemoveSeat(tableID string, seatNo domain.SeatNo) (SeatRecord, error) {
+	const q = `
+DELETE FROM seats
+WHERE table_id = $1 AND seat_no = $2
+RETURNING id, table_id, seat_no, agent_id, agent_version_id, stack, status, created_at, view_level
+`
+	var rec SeatRecord
+	var seat int16
+	var stack int64
+	err := r.db.QueryRowContext(context.Background(), q, tableID, int16(seatNo)).Scan(
+		&rec.ID,
+		&rec.TableID,
+		&seat,
+		&rec.AgentID,
+		&rec.AgentVersionID,
+		&stack,
+		&rec.Status,
+		&rec.CreatedAt,
+		&rec.View,
+	)
+	if errors.Is(err, sql.ErrNoRows) {
+		return SeatRecord{}, ErrSeatNotFound
+	}
+	if err != nil {
+		return SeatRecord{}, err
+	}
+	rec.SeatNo = domain.SeatNo(seat)
+	rec.Stack = domain.Chips(stack)
+	return rec, nil
+}
+
+func (r *postgresRepository) RecordDeparture(record DepartureRecord) error {
+	const q = `
+INSERT INTO seat_departures (table_id, user_id, agent_id, small_blind, big_blind, stack, left_at)
+VALUES ($1,$2,$3,$4,$5,$6,$7)
+`
+	_, err := r.db.ExecContext(context.Background(), q,
+		record.TableID,
+		record.UserID,
+		record.AgentID,
+		int64(record.SmallBlind),
+		int64(record.BigBlind),
+		int64(record.Stack),
+		record.LeftAt,
+	)
+	return err
+}
+
+func (r *postgresRepository) LatestDeparture(userID string, smallBlind, bigBlind domain.Chips) (DepartureRecord, bool, error) {
+	const q = `
+SELECT table_id, user_id, agent_id, small_blind, big_blind, stack, left_at
+FROM seat_departures
+WHERE user_id = $1 AND small_blind = $2 AND big_blind = $3
+ORDER BY left_at DESC
+LIMIT 1
+`
+	var rec DepartureRecord
+	var sb, bb, stack int64
+	err := r.db.QueryRowContext(context.Background(), q, userID, int64(smallBlind), int64(bigBlind)).Scan(
+		&rec.TableID,
+		&rec.UserID,
+		&rec.AgentID,
+		&sb,
+		&bb,
+		&stack,
+		&rec.LeftAt,
+	)
+	if errors.Is(err, sql.ErrNoRows) {
+		return DepartureRecord{}, false, nil
+	}
+	if err != nil {
+		return DepartureRecord{}, false, err
+	}
+	rec.SmallBlind = domain.Chips(sb)
+	rec.BigBlind = domain.Chips(bb)
+	rec.Stack = domain.Chips(stack)
+	return rec, true, nil
+}
+
+func (r *postgresRepository) ScrubUser(userID string, alias string) error {
+	const q = `
+WITH scrubbed_user AS (
+  UPDATE users SET name = $2, token = '' WHERE id = $1 RETURNING id
+), scrubbed_agents AS (
+  UPDATE agents SET name = $2 WHERE user_id IN (SELECT id FROM scrubbed_user) RETURNING id
+), scrubbed_versions AS (
+  UPDATE agent_versions SET endpoint_url = '' WHERE agent_id IN (SELECT id FROM scrubbed_agents)
+)
+SELECT count(*) FROM scrubbed_user
+`
+	var users int
+	if err := r.db.QueryRowContext(context.Background(), q, userID, alias).Scan(&users); err != nil {
+		return err
+	}
+	if users == 0 {
+		return ErrUserNotFound
+	}
+	return nil
+}
+
+func (r *postgresRepository) PurgeHands(cutoff time.Time) (int, error) {
+	const q = `
+DELETE FROM hands
+WHERE ended_at < $1
+`
+	return r.execCount(q, cutoff)
+}
+
+func (r *postgresRepository) PurgeTelemetry(cutoff time.Time) (int, error) {
+	const q = `
+UPDATE actions
+SET telemetry = NULL
+FROM hands
+WHERE actions.hand_id = hands.hand_id AND hands.ended_at < $1 AND actions.telemetry IS NOT NULL
+`
+	return r.execCount(q, cutoff)
+}
+
+func (r *postgresRepository) execCount(q string, args ...any) (int, error) {
+	result, err := r.db.ExecContext(context.Background(), q, args...)
+	if err != nil {
+		return 0, err
+	}
+	rows, err := result.RowsAffected()
+	if err != nil {
+		return 0, err
+	}
+	return int(rows), nil
+}
+
 func isUniqueViolation(err error) bool {
 	return hasSQLState(err, "23505")
 }
@@ -579,3 +979,31 @@ func hasSQLState(err error, code string) bool {
 	// Fallback for drivers that only surface SQLSTATE in error text.
 	return strings.Contains(err.Error(), "SQLSTATE "+code)
 }
+
+// marshalAnnotations stores no annotations as an empty array, not null.
+func marshalStreetEquity(points []equity.StreetEquity) ([]byte, error) {
+	if points == nil {
+		points = []equity.StreetEquity{}
+	}
+	raw, err := json.Marshal(points)
+	if err != nil {
+		return nil, fmt.Errorf("marshal equity: %w", err)
+	}
+	return raw, nil
+}
+
+func marshalAnnotations(annotations []string) ([]byte, error) {
+	if annotations == nil {
+		annotations = []string{}
+	}
+	raw, err := json.Marshal(annotations)
+	if err != nil {
+		return nil, fmt.Errorf("marshal annotations: %w", err)
+	}
+	return raw, nil
+}
+
+// nullableTime stores the zero time as NULL.
+func nullableTime(t time.Time) sql.NullTime {
+	return sql.NullTime{Time: t, Valid: !t.IsZero()}
+}
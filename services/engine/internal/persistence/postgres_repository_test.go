@@ -174,7 +174,7 @@ func resetPostgresTables(t *testing.T, db *sql.DB) {
 	t.Helper()
 	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
 	defer cancel()
-	if _, err := db.ExecContext(ctx, `TRUNCATE TABLE actions, hands, table_runs, seats, tables, agent_versions, agents, users RESTART IDENTITY CASCADE`); err != nil {
+	if _, err := db.ExecContext(ctx, `TRUNCATE TABLE actions, hands, table_runs, seat_departures, seats, tables, agent_versions, agents, users RESTART IDENTITY CASCADE`); err != nil {
 		t.Fatalf("truncate tables failed: %v", err)
 	}
 }
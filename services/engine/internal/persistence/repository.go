@@ -7,7 +7,9 @@ import (
 	"sync"
 	"time"
 
+	"github.com/imaddar/poker-arena/services/engine/internal/cashier"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
 )
 
 var (
@@ -19,6 +21,7 @@ var (
 	ErrAgentVersionExists   = errors.New("agent version already exists")
 	ErrAgentVersionNotFound = errors.New("agent version not found")
 	ErrTableNotFound        = errors.New("table not found")
+	ErrSeatNotFound         = errors.New("seat not found")
 )
 
 type TableRunStatus string
@@ -40,6 +43,12 @@ type HandRecord struct {
 	FinalPhase    domain.HandPhase
 	FinalState    domain.HandState
 	WinnerSummary []domain.PotAward
+	// Annotations are reviewers' notes on the hand. CompleteHand leaves
+	// them as they are; AnnotateHand replaces them.
+	Annotations []string
+	// Equity is each live seat's equity at the start of every street,
+	// worked out once as the hand completes so replays can chart it.
+	Equity []equity.StreetEquity
 }
 
 type ActionRecord struct {
@@ -47,9 +56,19 @@ type ActionRecord struct {
 	Street     domain.Street
 	ActingSeat domain.SeatNo
 	Action     domain.ActionKind
-	Amount     *uint32
+	Amount     *domain.Chips
 	IsFallback bool
 	At         time.Time
+	// ReceivedAt is when the server received the decision (or gave up on
+	// it); DeadlinePolicy records how that was judged against the deadline
+	// and ThinkTimeMS how long the seat took. LatencyCompensationMS is how
+	// far the deadline was extended for the seat's latency.
+	ReceivedAt            time.Time
+	DeadlinePolicy        domain.DeadlinePolicy
+	ThinkTimeMS           uint64
+	LatencyCompensationMS uint64
+	// Telemetry is stored as submitted; readers apply the embargo.
+	Telemetry *domain.Telemetry
 }
 
 type TableRunRecord struct {
@@ -65,18 +84,26 @@ type TableRunRecord struct {
 	CurrentHandNo  uint64
 }
 
+// UserRecord, AgentRecord and TableRecord carry a TenantID naming the
+// community that owns them. The empty tenant is the deployment default.
 type UserRecord struct {
 	ID        string
+	TenantID  string
 	Name      string
 	Token     string
 	CreatedAt time.Time
 }
 
 type AgentRecord struct {
-	ID        string
-	UserID    string
-	Name      string
-	CreatedAt time.Time
+	ID                 string
+	TenantID           string
+	UserID             string
+	Name               string
+	ShowdownPreference domain.ShowdownPreference
+	// BuyInPolicy, when set, is applied by the cashier whenever the agent
+	// is seated in a run started from the table's seats.
+	BuyInPolicy *cashier.Policy
+	CreatedAt   time.Time
 }
 
 type AgentVersionRecord struct {
@@ -89,13 +116,17 @@ type AgentVersionRecord struct {
 }
 
 type TableRecord struct {
-	ID         string
-	Name       string
-	MaxSeats   uint8
-	SmallBlind uint32
-	BigBlind   uint32
-	Status     string
-	CreatedAt  time.Time
+	ID           string
+	TenantID     string
+	Name         string
+	MaxSeats     uint8
+	SmallBlind   domain.Chips
+	BigBlind     domain.Chips
+	RuleFlags    domain.RuleFlags
+	RulesVersion domain.RulesVersion
+	Variant      domain.Variant
+	Status       string
+	CreatedAt    time.Time
 }
 
 type SeatRecord struct {
@@ -104,20 +135,36 @@ type SeatRecord struct {
 	SeatNo         domain.SeatNo
 	AgentID        string
 	AgentVersionID string
-	Stack          uint32
+	Stack          domain.Chips
 	Status         domain.SeatStatus
+	View           domain.ViewLevel
 	CreatedAt      time.Time
 }
 
+// DepartureRecord is a player's stack when they left a table, kept to stop
+// them rejoining the same stakes with fewer chips.
+type DepartureRecord struct {
+	TableID    string
+	UserID     string
+	AgentID    string
+	SmallBlind domain.Chips
+	BigBlind   domain.Chips
+	Stack      domain.Chips
+	LeftAt     time.Time
+}
+
 type Repository interface {
 	UpsertTableRun(record TableRunRecord) error
 	GetTableRun(tableID string) (TableRunRecord, bool, error)
 	GetHand(handID string) (HandRecord, bool, error)
 	CreateHand(record HandRecord) error
 	CompleteHand(handID string, final HandRecord) error
+	AnnotateHand(handID string, annotations []string) error
+	SetHandEquity(handID string, points []equity.StreetEquity) error
 	AppendAction(record ActionRecord) error
 	ListHands(tableID string) ([]HandRecord, error)
 	ListActions(handID string) ([]ActionRecord, error)
+	ListTableActions(tableID string, since time.Time) ([]ActionRecord, error)
 	CreateUser(record UserRecord) error
 	CreateAgent(record AgentRecord) error
 	CreateAgentVersion(record AgentVersionRecord) error
@@ -127,6 +174,21 @@ type Repository interface {
 	ListTables() ([]TableRecord, error)
 	ListSeats(tableID string) ([]SeatRecord, error)
 	GetAgentVersion(versionID string) (AgentVersionRecord, bool, error)
+	GetAgent(agentID string) (AgentRecord, bool, error)
+	SetAgentShowdownPreference(agentID string, pref domain.ShowdownPreference) error
+	SetAgentBuyInPolicy(agentID string, policy *cashier.Policy) error
+	RemoveSeat(tableID string, seatNo domain.SeatNo) (SeatRecord, error)
+	RecordDeparture(record DepartureRecord) error
+	LatestDeparture(userID string, smallBlind, bigBlind domain.Chips) (DepartureRecord, bool, error)
+	// ScrubUser replaces a user's name and its agents' names with alias,
+	// revokes its token and forgets its agents' endpoint URLs. IDs are
+	// kept, so seats, hands and departures still add up.
+	ScrubUser(userID string, alias string) error
+	// PurgeHands deletes the hands that ended before cutoff, with their
+	// actions, and PurgeTelemetry drops the telemetry from those hands'
+	// actions. Each reports how many records it changed.
+	PurgeHands(cutoff time.Time) (int, error)
+	PurgeTelemetry(cutoff time.Time) (int, error)
 }
 
 type inMemoryRepository struct {
@@ -140,6 +202,8 @@ type inMemoryRepository struct {
 	versions  map[string]AgentVersionRecord
 	tables    map[string]TableRecord
 	seats     map[string]map[domain.SeatNo]SeatRecord
+
+	departures []DepartureRecord
 }
 
 func NewInMemoryRepository() Repository {
@@ -195,11 +259,37 @@ func (r *inMemoryRepository) GetHand(handID string) (HandRecord, bool, error) {
 func (r *inMemoryRepository) CompleteHand(handID string, final HandRecord) error {
 	r.mu.Lock()
 	defer r.mu.Unlock()
-	if _, exists := r.hands[handID]; !exists {
+	existing, exists := r.hands[handID]
+	if !exists {
 		return ErrHandNotFound
 	}
 	record := cloneHandRecord(final)
 	record.HandID = handID
+	record.Annotations = existing.Annotations
+	r.hands[handID] = record
+	return nil
+}
+
+func (r *inMemoryRepository) AnnotateHand(handID string, annotations []string) error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	record, exists := r.hands[handID]
+	if !exists {
+		return ErrHandNotFound
+	}
+	record.Annotations = append([]string(nil), annotations...)
+	r.hands[handID] = record
+	return nil
+}
+
+func (r *inMemoryRepository) SetHandEquity(handID string, points []equity.StreetEquity) error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	record, exists := r.hands[handID]
+	if !exists {
+		return ErrHandNotFound
+	}
+	record.Equity = cloneStreetEquity(points)
 	r.hands[handID] = record
 	return nil
 }
@@ -244,6 +334,32 @@ func (r *inMemoryRepository) ListActions(handID string) ([]ActionRecord, error)
 	return out, nil
 }
 
+// ListTableActions returns the actions of every hand at the table that
+// started at or after since, in hand order and each hand's own order.
+func (r *inMemoryRepository) ListTableActions(tableID string, since time.Time) ([]ActionRecord, error) {
+	r.mu.RLock()
+	defer r.mu.RUnlock()
+	hands := make([]HandRecord, 0, len(r.hands))
+	for _, record := range r.hands {
+		if record.TableID == tableID && !record.StartedAt.Before(since) {
+			hands = append(hands, record)
+		}
+	}
+	sort.Slice(hands, func(i, j int) bool {
+		if hands[i].HandNo == hands[j].HandNo {
+			return hands[i].HandID < hands[j].HandID
+		}
+		return hands[i].HandNo < hands[j].HandNo
+	})
+	var out []ActionRecord
+	for _, hand := range hands {
+		for _, record := range r.actions[hand.HandID] {
+			out = append(out, cloneActionRecord(record))
+		}
+	}
+	return out, nil
+}
+
 func (r *inMemoryRepository) CreateUser(record UserRecord) error {
 	r.mu.Lock()
 	defer r.mu.Unlock()
@@ -254,10 +370,14 @@ func (r *inMemoryRepository) CreateUser(record UserRecord) error {
 func (r *inMemoryRepository) CreateAgent(record AgentRecord) error {
 	r.mu.Lock()
 	defer r.mu.Unlock()
-	if _, exists := r.users[record.UserID]; !exists {
+	if user, exists := r.users[record.UserID]; !exists || user.TenantID != record.TenantID {
 		return ErrUserNotFound
 	}
-	r.agents[record.ID] = cloneAgentRecord(record)
+	record = cloneAgentRecord(record)
+	if record.ShowdownPreference == "" {
+		record.ShowdownPreference = domain.ShowdownAutoMuck
+	}
+	r.agents[record.ID] = record
 	return nil
 }
 
@@ -357,6 +477,133 @@ func (r *inMemoryRepository) GetAgentVersion(versionID string) (AgentVersionReco
 	return cloneAgentVersionRecord(record), true, nil
 }
 
+func (r *inMemoryRepository) GetAgent(agentID string) (AgentRecord, bool, error) {
+	r.mu.RLock()
+	defer r.mu.RUnlock()
+	record, ok := r.agents[agentID]
+	if !ok {
+		return AgentRecord{}, false, nil
+	}
+	return cloneAgentRecord(record), true, nil
+}
+
+func (r *inMemoryRepository) SetAgentShowdownPreference(agentID string, pref domain.ShowdownPreference) error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	record, ok := r.agents[agentID]
+	if !ok {
+		return ErrAgentNotFound
+	}
+	record.ShowdownPreference = pref
+	r.agents[agentID] = record
+	return nil
+}
+
+func (r *inMemoryRepository) SetAgentBuyInPolicy(agentID string, policy *cashier.Policy) error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	record, ok := r.agents[agentID]
+	if !ok {
+		return ErrAgentNotFound
+	}
+	record.BuyInPolicy = clonePolicy(policy)
+	r.agents[agentID] = record
+	return nil
+}
+
+func (r *inMemoryRepository) RemoveSeat(tableID string, seatNo domain.SeatNo) (SeatRecord, error) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	record, ok := r.seats[tableID][seatNo]
+	if !ok {
+		return SeatRecord{}, ErrSeatNotFound
+	}
+	delete(r.seats[tableID], seatNo)
+	return cloneSeatRecord(record), nil
+}
+
+func (r *inMemoryRepository) RecordDeparture(record DepartureRecord) error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	r.departures = append(r.departures, record)
+	return nil
+}
+
+func (r *inMemoryRepository) LatestDeparture(userID string, smallBlind, bigBlind domain.Chips) (DepartureRecord, bool, error) {
+	r.mu.RLock()
+	defer r.mu.RUnlock()
+	var latest DepartureRecord
+	found := false
+	for _, record := range r.departures {
+		if record.UserID != userID || record.SmallBlind != smallBlind || record.BigBlind != bigBlind {
+			continue
+		}
+		if !found || record.LeftAt.After(latest.LeftAt) {
+			latest, found = record, true
+		}
+	}
+	return latest, found, nil
+}
+
+func (r *inMemoryRepository) ScrubUser(userID string, alias string) error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	user, exists := r.users[userID]
+	if !exists {
+		return ErrUserNotFound
+	}
+	user.Name, user.Token = alias, ""
+	r.users[userID] = user
+	for agentID, agent := range r.agents {
+		if agent.UserID != userID {
+			continue
+		}
+		agent.Name = alias
+		r.agents[agentID] = agent
+		for versionID, version := range r.versions {
+			if version.AgentID == agentID {
+				version.EndpointURL = ""
+				r.versions[versionID] = version
+			}
+		}
+	}
+	return nil
+}
+
+func (r *inMemoryRepository) PurgeHands(cutoff time.Time) (int, error) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	purged := 0
+	for handID, record := range r.hands {
+		if record.EndedAt == nil || !record.EndedAt.Before(cutoff) {
+			continue
+		}
+		delete(r.hands, handID)
+		delete(r.actions, handID)
+		purged++
+	}
+	return purged, nil
+}
+
+func (r *inMemoryRepository) PurgeTelemetry(cutoff time.Time) (int, error) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	purged := 0
+	for handID, actions := range r.actions {
+		record := r.hands[handID]
+		if record.EndedAt == nil || !record.EndedAt.Before(cutoff) {
+			continue
+		}
+		for i := range actions {
+			if actions[i].Telemetry != nil {
+				actions[i].Telemetry = nil
+				purged++
+			}
+		}
+	}
+	return purged, nil
+}
+
 func cloneTableRunRecord(record TableRunRecord) TableRunRecord {
 	out := record
 	if record.EndedAt != nil {
@@ -370,6 +617,8 @@ func cloneHandRecord(record HandRecord) HandRecord {
 	out := record
 	out.FinalState = cloneHandState(record.FinalState)
 	out.WinnerSummary = clonePotAwards(record.WinnerSummary)
+	out.Annotations = append([]string(nil), record.Annotations...)
+	out.Equity = cloneStreetEquity(record.Equity)
 	if record.EndedAt != nil {
 		endedAt := *record.EndedAt
 		out.EndedAt = &endedAt
@@ -377,12 +626,34 @@ func cloneHandRecord(record HandRecord) HandRecord {
 	return out
 }
 
+func cloneStreetEquity(points []equity.StreetEquity) []equity.StreetEquity {
+	if points == nil {
+		return nil
+	}
+	out := make([]equity.StreetEquity, len(points))
+	for i, point := range points {
+		out[i] = point
+		out[i].Board = append([]domain.Card(nil), point.Board...)
+		out[i].Equity = append([]equity.SeatEquity(nil), point.Equity...)
+	}
+	return out
+}
+
 func cloneActionRecord(record ActionRecord) ActionRecord {
 	out := record
 	if record.Amount != nil {
 		amount := *record.Amount
 		out.Amount = &amount
 	}
+	if record.Telemetry != nil {
+		telemetry := *record.Telemetry
+		if telemetry.Equity != nil {
+			equity := *telemetry.Equity
+			telemetry.Equity = &equity
+		}
+		telemetry.Tags = append([]string(nil), telemetry.Tags...)
+		out.Telemetry = &telemetry
+	}
 	return out
 }
 
@@ -402,7 +673,10 @@ func clonePotAwards(awards []domain.PotAward) []domain.PotAward {
 func cloneHandState(state domain.HandState) domain.HandState {
 	cloned := state
 	cloned.Board = append([]domain.Card(nil), state.Board...)
+	cloned.Runouts = domain.CloneBoards(state.Runouts)
 	cloned.Deck = append([]domain.Card(nil), state.Deck...)
+	cloned.DealSequence = append([]domain.DealtCard(nil), state.DealSequence...)
+	cloned.ShowdownReveals = append([]domain.ShowdownReveal(nil), state.ShowdownReveals...)
 	cloned.Seats = append([]domain.SeatState(nil), state.Seats...)
 	cloned.ShowdownAwards = clonePotAwards(state.ShowdownAwards)
 	if state.LastAggressorSeat != nil {
@@ -426,7 +700,17 @@ func cloneUserRecord(record UserRecord) UserRecord {
 }
 
 func cloneAgentRecord(record AgentRecord) AgentRecord {
-	return record
+	out := record
+	out.BuyInPolicy = clonePolicy(record.BuyInPolicy)
+	return out
+}
+
+func clonePolicy(policy *cashier.Policy) *cashier.Policy {
+	if policy == nil {
+		return nil
+	}
+	out := *policy
+	return &out
 }
 
 func cloneAgentVersionRecord(record AgentVersionRecord) AgentVersionRecord {
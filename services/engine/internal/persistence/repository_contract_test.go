@@ -1,12 +1,14 @@
 package persistence
 
 import (
+	"errors"
 	"fmt"
 	"sync"
 	"testing"
 	"time"
 
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
 )
 
 func runRepositoryContractTests(t *testing.T, mkRepo func(t *testing.T) Repository) {
@@ -78,6 +80,116 @@ func runRepositoryContractTests(t *testing.T, mkRepo func(t *testing.T) Reposito
 		}
 	})
 
+	t.Run("Contract_ListTableActionsLoadsHandsSinceInOrder", func(t *testing.T) {
+		repo := mkRepo(t)
+		ensureTableRunForContract(t, repo, "t1")
+		ensureTableRunForContract(t, repo, "t2")
+		since := time.Now().UTC().Truncate(time.Second)
+		hands := []HandRecord{
+			{HandID: "old", TableID: "t1", HandNo: 1, StartedAt: since.Add(-time.Minute)},
+			{HandID: "h3", TableID: "t1", HandNo: 3, StartedAt: since.Add(2 * time.Minute)},
+			{HandID: "h2", TableID: "t1", HandNo: 2, StartedAt: since},
+			{HandID: "other", TableID: "t2", HandNo: 2, StartedAt: since},
+		}
+		for _, hand := range hands {
+			if err := repo.CreateHand(hand); err != nil {
+				t.Fatalf("CreateHand %s failed: %v", hand.HandID, err)
+			}
+			for i := range 2 {
+				if err := repo.AppendAction(ActionRecord{
+					HandID:     hand.HandID,
+					ActingSeat: 1,
+					Action:     domain.ActionKind(fmt.Sprintf("%s-a%d", hand.HandID, i)),
+					At:         hand.StartedAt,
+				}); err != nil {
+					t.Fatalf("AppendAction %s failed: %v", hand.HandID, err)
+				}
+			}
+		}
+
+		actions, err := repo.ListTableActions("t1", since)
+		if err != nil {
+			t.Fatalf("ListTableActions failed: %v", err)
+		}
+		want := []domain.ActionKind{"h2-a0", "h2-a1", "h3-a0", "h3-a1"}
+		if len(actions) != len(want) {
+			t.Fatalf("expected %d actions, got %d", len(want), len(actions))
+		}
+		for i := range actions {
+			if actions[i].Action != want[i] {
+				t.Fatalf("expected action %q at index %d, got %q", want[i], i, actions[i].Action)
+			}
+		}
+	})
+
+	t.Run("Contract_AppendActionPreservesTiming", func(t *testing.T) {
+		repo := mkRepo(t)
+		ensureTableRunForContract(t, repo, "t1")
+		if err := repo.CreateHand(HandRecord{HandID: "h1", TableID: "t1", HandNo: 1, StartedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateHand failed: %v", err)
+		}
+		receivedAt := time.Now().UTC().Truncate(time.Millisecond)
+		if err := repo.AppendAction(ActionRecord{
+			HandID:         "h1",
+			ActingSeat:     1,
+			Action:         domain.ActionCheck,
+			At:             receivedAt.Add(time.Millisecond),
+			ReceivedAt:     receivedAt,
+			DeadlinePolicy: domain.DeadlineLatencyGrace,
+			ThinkTimeMS:    2150,
+		}); err != nil {
+			t.Fatalf("AppendAction failed: %v", err)
+		}
+
+		actions, err := repo.ListActions("h1")
+		if err != nil {
+			t.Fatalf("ListActions failed: %v", err)
+		}
+		if len(actions) != 1 {
+			t.Fatalf("expected 1 action, got %d", len(actions))
+		}
+		if !actions[0].ReceivedAt.Equal(receivedAt) {
+			t.Fatalf("expected received_at %v, got %v", receivedAt, actions[0].ReceivedAt)
+		}
+		if actions[0].DeadlinePolicy != domain.DeadlineLatencyGrace {
+			t.Fatalf("expected deadline policy %q, got %q", domain.DeadlineLatencyGrace, actions[0].DeadlinePolicy)
+		}
+		if actions[0].ThinkTimeMS != 2150 {
+			t.Fatalf("expected think time 2150ms, got %d", actions[0].ThinkTimeMS)
+		}
+	})
+
+	t.Run("Contract_AppendActionPreservesTelemetry", func(t *testing.T) {
+		repo := mkRepo(t)
+		ensureTableRunForContract(t, repo, "t1")
+		if err := repo.CreateHand(HandRecord{HandID: "h1", TableID: "t1", HandNo: 1, StartedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateHand failed: %v", err)
+		}
+		equity := 0.25
+		telemetry := &domain.Telemetry{Equity: &equity, Tags: []string{"bluff"}}
+		for _, record := range []ActionRecord{
+			{HandID: "h1", ActingSeat: 1, Action: domain.ActionCheck, At: time.Now().UTC()},
+			{HandID: "h1", ActingSeat: 2, Action: domain.ActionCheck, At: time.Now().UTC(), Telemetry: telemetry},
+		} {
+			if err := repo.AppendAction(record); err != nil {
+				t.Fatalf("AppendAction failed: %v", err)
+			}
+		}
+		telemetry.Tags[0] = "mutated"
+
+		actions, err := repo.ListActions("h1")
+		if err != nil {
+			t.Fatalf("ListActions failed: %v", err)
+		}
+		if len(actions) != 2 || actions[0].Telemetry != nil {
+			t.Fatalf("expected the first action to carry no telemetry, got %+v", actions)
+		}
+		got := actions[1].Telemetry
+		if got == nil || got.Equity == nil || *got.Equity != 0.25 || len(got.Tags) != 1 || got.Tags[0] != "bluff" {
+			t.Fatalf("expected stored telemetry, got %+v", got)
+		}
+	})
+
 	t.Run("Contract_CompleteHandUpdatesFinalState", func(t *testing.T) {
 		repo := mkRepo(t)
 		started := time.Now().UTC()
@@ -107,6 +219,13 @@ func runRepositoryContractTests(t *testing.T, mkRepo func(t *testing.T) Reposito
 				ShowdownAwards: []domain.PotAward{{Amount: 100, Seats: []domain.SeatNo{1}}},
 			},
 			WinnerSummary: []domain.PotAward{{Amount: 100, Seats: []domain.SeatNo{1}, Reason: "showdown"}},
+			Equity:        []equity.StreetEquity{{
+				Street:  domain.StreetRiver,
+				Board:   []domain.Card{domain.NewCard(14, domain.SuitSpades)},
+				Runouts: 1,
+				Exact:   true,
+				Equity:  []equity.SeatEquity{{SeatNo: 1, Share: equity.One()}, {SeatNo: 2, Share: equity.Zero()}},
+			}},
 		}
 		if err := repo.CompleteHand("h1", final); err != nil {
 			t.Fatalf("CompleteHand failed: %v", err)
@@ -128,6 +247,9 @@ func runRepositoryContractTests(t *testing.T, mkRepo func(t *testing.T) Reposito
 		if len(hands[0].WinnerSummary) != 1 {
 			t.Fatalf("expected winner summary length 1, got %d", len(hands[0].WinnerSummary))
 		}
+		if points := hands[0].Equity; len(points) != 1 || points[0].Street != domain.StreetRiver || len(points[0].Equity) != 2 || points[0].Equity[0].Share.Cmp(equity.One()) != 0 {
+			t.Fatalf("expected the equity sparkline to round-trip, got %+v", points)
+		}
 	})
 
 	t.Run("Contract_GetHandRoundTrip", func(t *testing.T) {
@@ -168,6 +290,106 @@ func runRepositoryContractTests(t *testing.T, mkRepo func(t *testing.T) Reposito
 		}
 	})
 
+	t.Run("Contract_AnnotateHandSurvivesCompletion", func(t *testing.T) {
+		repo := mkRepo(t)
+		ensureTableRunForContract(t, repo, "t1")
+		record := HandRecord{HandID: "h1", TableID: "t1", HandNo: 1, StartedAt: time.Now().UTC(), Annotations: []string{"imported"}}
+		if err := repo.CreateHand(record); err != nil {
+			t.Fatalf("CreateHand failed: %v", err)
+		}
+		if err := repo.AnnotateHand("h1", []string{"imported", "hero call"}); err != nil {
+			t.Fatalf("AnnotateHand failed: %v", err)
+		}
+		record.Annotations = nil
+		record.FinalPhase = domain.HandPhaseComplete
+		if err := repo.CompleteHand("h1", record); err != nil {
+			t.Fatalf("CompleteHand failed: %v", err)
+		}
+
+		got, ok, err := repo.GetHand("h1")
+		if err != nil || !ok {
+			t.Fatalf("GetHand failed: ok=%v err=%v", ok, err)
+		}
+		if len(got.Annotations) != 2 || got.Annotations[1] != "hero call" {
+			t.Fatalf("expected both annotations kept, got %v", got.Annotations)
+		}
+		if err := repo.AnnotateHand("missing", []string{"note"}); !errors.Is(err, ErrHandNotFound) {
+			t.Fatalf("expected ErrHandNotFound, got %v", err)
+		}
+	})
+
+	t.Run("Contract_SetHandEquityAfterCompletion", func(t *testing.T) {
+		repo := mkRepo(t)
+		ensureTableRunForContract(t, repo, "t1")
+		record := HandRecord{HandID: "h1", TableID: "t1", HandNo: 1, StartedAt: time.Now().UTC()}
+		if err := repo.CreateHand(record); err != nil {
+			t.Fatalf("CreateHand failed: %v", err)
+		}
+		record.FinalPhase = domain.HandPhaseComplete
+		if err := repo.CompleteHand("h1", record); err != nil {
+			t.Fatalf("CompleteHand failed: %v", err)
+		}
+		points := []equity.StreetEquity{{
+			Street:  domain.StreetPreflop,
+			Runouts: 2000,
+			Equity:  []equity.SeatEquity{{SeatNo: 1, Share: equity.One()}, {SeatNo: 2, Share: equity.Zero()}},
+		}}
+		if err := repo.SetHandEquity("h1", points); err != nil {
+			t.Fatalf("SetHandEquity failed: %v", err)
+		}
+
+		got, ok, err := repo.GetHand("h1")
+		if err != nil || !ok {
+			t.Fatalf("GetHand failed: ok=%v err=%v", ok, err)
+		}
+		if len(got.Equity) != 1 || got.Equity[0].Runouts != 2000 || got.Equity[0].Equity[0].Share.Cmp(equity.One()) != 0 {
+			t.Fatalf("expected the stored sparkline, got %+v", got.Equity)
+		}
+		if err := repo.SetHandEquity("missing", points); !errors.Is(err, ErrHandNotFound) {
+			t.Fatalf("expected ErrHandNotFound, got %v", err)
+		}
+	})
+
+	t.Run("Contract_PurgeByHandEndTime", func(t *testing.T) {
+		repo := mkRepo(t)
+		ensureTableRunForContract(t, repo, "t1")
+		cutoff := time.Now().UTC().Truncate(time.Second)
+		old := cutoff.Add(-time.Hour)
+		recent := cutoff.Add(time.Hour)
+		equity := 0.5
+		for i, endedAt := range []*time.Time{&old, &recent, nil} {
+			handID := fmt.Sprintf("h%d", i+1)
+			if err := repo.CreateHand(HandRecord{HandID: handID, TableID: "t1", HandNo: uint64(i + 1), StartedAt: old, EndedAt: endedAt}); err != nil {
+				t.Fatalf("CreateHand failed: %v", err)
+			}
+			if err := repo.AppendAction(ActionRecord{HandID: handID, ActingSeat: 1, Action: domain.ActionCheck, At: old, Telemetry: &domain.Telemetry{Equity: &equity}}); err != nil {
+				t.Fatalf("AppendAction failed: %v", err)
+			}
+		}
+
+		if purged, err := repo.PurgeTelemetry(cutoff); err != nil || purged != 1 {
+			t.Fatalf("expected telemetry purged from one action, got %d err=%v", purged, err)
+		}
+		for handID, kept := range map[string]bool{"h1": false, "h2": true, "h3": true} {
+			actions, err := repo.ListActions(handID)
+			if err != nil || len(actions) != 1 || (actions[0].Telemetry != nil) != kept {
+				t.Fatalf("%s: expected telemetry kept=%v, got %+v err=%v", handID, kept, actions, err)
+			}
+		}
+		if purged, err := repo.PurgeHands(cutoff); err != nil || purged != 1 {
+			t.Fatalf("expected one hand purged, got %d err=%v", purged, err)
+		}
+		if _, ok, _ := repo.GetHand("h1"); ok {
+			t.Fatal("expected h1 purged")
+		}
+		if hands, err := repo.ListHands("t1"); err != nil || len(hands) != 2 {
+			t.Fatalf("expected the recent and live hands kept, got %d err=%v", len(hands), err)
+		}
+		if actions, err := repo.ListActions("h1"); err != nil || len(actions) != 0 {
+			t.Fatalf("expected h1's actions purged, got %+v err=%v", actions, err)
+		}
+	})
+
 	t.Run("Contract_UpsertAndGetTableRun", func(t *testing.T) {
 		repo := mkRepo(t)
 		started := time.Now().UTC()
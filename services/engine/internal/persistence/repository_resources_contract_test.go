@@ -5,6 +5,7 @@ import (
 	"testing"
 	"time"
 
+	"github.com/imaddar/poker-arena/services/engine/internal/cashier"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
 )
 
@@ -42,6 +43,135 @@ func runRepositoryResourcesContractTests(t *testing.T, mkRepo func(t *testing.T)
 		}
 	})
 
+	t.Run("Contract_TenantScopesUsersAgentsAndTables", func(t *testing.T) {
+		repo := mkRepo(t)
+		if err := repo.CreateUser(UserRecord{ID: "u1", TenantID: "club-a", Name: "user", Token: "tok", CreatedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateUser failed: %v", err)
+		}
+		if err := repo.CreateAgent(AgentRecord{ID: "a1", TenantID: "club-b", UserID: "u1", Name: "agent", CreatedAt: time.Now().UTC()}); !errors.Is(err, ErrUserNotFound) {
+			t.Fatalf("expected ErrUserNotFound for a user in another tenant, got %v", err)
+		}
+		if err := repo.CreateAgent(AgentRecord{ID: "a1", TenantID: "club-a", UserID: "u1", Name: "agent", CreatedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateAgent failed: %v", err)
+		}
+		agent, ok, err := repo.GetAgent("a1")
+		if err != nil || !ok || agent.TenantID != "club-a" {
+			t.Fatalf("expected agent in club-a, got %+v ok=%v err=%v", agent, ok, err)
+		}
+
+		if err := repo.CreateTable(TableRecord{ID: "t1", TenantID: "club-a", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "idle", CreatedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateTable failed: %v", err)
+		}
+		table, ok, err := repo.GetTable("t1")
+		if err != nil || !ok || table.TenantID != "club-a" {
+			t.Fatalf("expected table in club-a, got %+v ok=%v err=%v", table, ok, err)
+		}
+		tables, err := repo.ListTables()
+		if err != nil || len(tables) != 1 || tables[0].TenantID != "club-a" {
+			t.Fatalf("expected listed table in club-a, got %+v err=%v", tables, err)
+		}
+	})
+
+	t.Run("Contract_TableRuleFlagsRoundTrip", func(t *testing.T) {
+		repo := mkRepo(t)
+		flags := domain.RuleStraddle | domain.RuleAntes
+		if err := repo.CreateTable(TableRecord{ID: "t1", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, RuleFlags: flags, Status: "idle", CreatedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateTable failed: %v", err)
+		}
+		table, ok, err := repo.GetTable("t1")
+		if err != nil || !ok || table.RuleFlags != flags {
+			t.Fatalf("expected rule flags %v, got %+v ok=%v err=%v", flags.Names(), table, ok, err)
+		}
+		tables, err := repo.ListTables()
+		if err != nil || len(tables) != 1 || tables[0].RuleFlags != flags {
+			t.Fatalf("expected listed rule flags %v, got %+v err=%v", flags.Names(), tables, err)
+		}
+	})
+
+	t.Run("Contract_TableVariantRoundTrip", func(t *testing.T) {
+		repo := mkRepo(t)
+		if err := repo.CreateTable(TableRecord{ID: "t1", Name: "t1", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Variant: domain.VariantRazz, Status: "idle", CreatedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateTable failed: %v", err)
+		}
+		table, ok, err := repo.GetTable("t1")
+		if err != nil || !ok || table.Variant != domain.VariantRazz {
+			t.Fatalf("expected variant %s, got %+v ok=%v err=%v", domain.VariantRazz, table, ok, err)
+		}
+		tables, err := repo.ListTables()
+		if err != nil || len(tables) != 1 || tables[0].Variant != domain.VariantRazz {
+			t.Fatalf("expected listed variant %s, got %+v err=%v", domain.VariantRazz, tables, err)
+		}
+	})
+
+	t.Run("Contract_AgentShowdownPreferenceDefaultsAndUpdates", func(t *testing.T) {
+		repo := mkRepo(t)
+		if err := repo.CreateUser(UserRecord{ID: "u1", Name: "user", Token: "tok", CreatedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateUser failed: %v", err)
+		}
+		if err := repo.CreateAgent(AgentRecord{ID: "a1", UserID: "u1", Name: "agent", CreatedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateAgent failed: %v", err)
+		}
+		agent, ok, err := repo.GetAgent("a1")
+		if err != nil || !ok {
+			t.Fatalf("GetAgent failed: ok=%v err=%v", ok, err)
+		}
+		if agent.ShowdownPreference != domain.ShowdownAutoMuck {
+			t.Fatalf("expected default auto_muck, got %q", agent.ShowdownPreference)
+		}
+
+		if err := repo.SetAgentShowdownPreference("a1", domain.ShowdownAlwaysShow); err != nil {
+			t.Fatalf("SetAgentShowdownPreference failed: %v", err)
+		}
+		agent, _, err = repo.GetAgent("a1")
+		if err != nil {
+			t.Fatalf("GetAgent failed: %v", err)
+		}
+		if agent.ShowdownPreference != domain.ShowdownAlwaysShow {
+			t.Fatalf("expected always_show, got %q", agent.ShowdownPreference)
+		}
+
+		if err := repo.SetAgentShowdownPreference("missing", domain.ShowdownAlwaysShow); !errors.Is(err, ErrAgentNotFound) {
+			t.Fatalf("expected ErrAgentNotFound, got %v", err)
+		}
+		if _, ok, err := repo.GetAgent("missing"); err != nil || ok {
+			t.Fatalf("expected missing agent lookup to miss, ok=%v err=%v", ok, err)
+		}
+	})
+
+	t.Run("Contract_AgentBuyInPolicyRoundTrip", func(t *testing.T) {
+		repo := mkRepo(t)
+		if err := repo.CreateUser(UserRecord{ID: "u1", Name: "user", Token: "tok", CreatedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateUser failed: %v", err)
+		}
+		if err := repo.CreateAgent(AgentRecord{ID: "a1", UserID: "u1", Name: "agent", CreatedAt: time.Now().UTC()}); err != nil {
+			t.Fatalf("CreateAgent failed: %v", err)
+		}
+		agent, _, err := repo.GetAgent("a1")
+		if err != nil || agent.BuyInPolicy != nil {
+			t.Fatalf("expected no buy-in policy by default, got %+v err=%v", agent.BuyInPolicy, err)
+		}
+
+		policy := cashier.Policy{BuyInBB: 100, TopUpBelowBB: 60, Bankroll: 50_000}
+		if err := repo.SetAgentBuyInPolicy("a1", &policy); err != nil {
+			t.Fatalf("SetAgentBuyInPolicy failed: %v", err)
+		}
+		agent, _, err = repo.GetAgent("a1")
+		if err != nil || agent.BuyInPolicy == nil || *agent.BuyInPolicy != policy {
+			t.Fatalf("expected policy %+v, got %+v err=%v", policy, agent.BuyInPolicy, err)
+		}
+
+		if err := repo.SetAgentBuyInPolicy("a1", nil); err != nil {
+			t.Fatalf("SetAgentBuyInPolicy failed: %v", err)
+		}
+		agent, _, err = repo.GetAgent("a1")
+		if err != nil || agent.BuyInPolicy != nil {
+			t.Fatalf("expected cleared policy, got %+v err=%v", agent.BuyInPolicy, err)
+		}
+		if err := repo.SetAgentBuyInPolicy("missing", &policy); !errors.Is(err, ErrAgentNotFound) {
+			t.Fatalf("expected ErrAgentNotFound, got %v", err)
+		}
+	})
+
 	t.Run("Contract_CreateVersionRequiresAgent", func(t *testing.T) {
 		repo := mkRepo(t)
 		if err := repo.CreateAgentVersion(AgentVersionRecord{
@@ -70,6 +200,40 @@ func runRepositoryResourcesContractTests(t *testing.T, mkRepo func(t *testing.T)
 		}
 	})
 
+	t.Run("Contract_ScrubUserKeepsIDs", func(t *testing.T) {
+		repo := mkRepo(t)
+		if err := repo.ScrubUser("missing", "deleted-1"); !errors.Is(err, ErrUserNotFound) {
+			t.Fatalf("expected ErrUserNotFound, got %v", err)
+		}
+		for _, userID := range []string{"u1", "u2"} {
+			if err := repo.CreateUser(UserRecord{ID: userID, Name: "user", Token: "tok", CreatedAt: time.Now().UTC()}); err != nil {
+				t.Fatalf("CreateUser failed: %v", err)
+			}
+			agentID := "agent-" + userID
+			if err := repo.CreateAgent(AgentRecord{ID: agentID, UserID: userID, Name: "Alice's bot", CreatedAt: time.Now().UTC()}); err != nil {
+				t.Fatalf("CreateAgent failed: %v", err)
+			}
+			if err := repo.CreateAgentVersion(AgentVersionRecord{ID: "v-" + userID, AgentID: agentID, Version: 1, EndpointURL: "http://alice.example", CreatedAt: time.Now().UTC()}); err != nil {
+				t.Fatalf("CreateAgentVersion failed: %v", err)
+			}
+		}
+
+		if err := repo.ScrubUser("u1", "deleted-1"); err != nil {
+			t.Fatalf("ScrubUser failed: %v", err)
+		}
+		agent, ok, err := repo.GetAgent("agent-u1")
+		if err != nil || !ok || agent.Name != "deleted-1" || agent.UserID != "u1" {
+			t.Fatalf("expected the agent renamed under its ID, got %+v ok=%v err=%v", agent, ok, err)
+		}
+		version, ok, err := repo.GetAgentVersion("v-u1")
+		if err != nil || !ok || version.EndpointURL != "" || version.AgentID != "agent-u1" {
+			t.Fatalf("expected the endpoint forgotten, got %+v ok=%v err=%v", version, ok, err)
+		}
+		if other, _, _ := repo.GetAgent("agent-u2"); other.Name != "Alice's bot" {
+			t.Fatalf("expected other users' agents untouched, got %+v", other)
+		}
+	})
+
 	t.Run("Contract_CreateTableAndUpsertListSeatsOrdered", func(t *testing.T) {
 		repo := mkRepo(t)
 		if err := repo.CreateUser(UserRecord{ID: "u1", Name: "user", Token: "tok", CreatedAt: time.Now().UTC()}); err != nil {
@@ -131,6 +295,61 @@ func runRepositoryResourcesContractTests(t *testing.T, mkRepo func(t *testing.T)
 		}
 	})
 
+	t.Run("Contract_RemoveSeatAndLatestDeparture", func(t *testing.T) {
+		repo := mkRepo(t)
+		now := time.Now().UTC()
+		if err := repo.CreateUser(UserRecord{ID: "u1", Name: "user", Token: "tok", CreatedAt: now}); err != nil {
+			t.Fatalf("CreateUser failed: %v", err)
+		}
+		if err := repo.CreateAgent(AgentRecord{ID: "a1", UserID: "u1", Name: "agent", CreatedAt: now}); err != nil {
+			t.Fatalf("CreateAgent failed: %v", err)
+		}
+		if err := repo.CreateAgentVersion(AgentVersionRecord{ID: "v1", AgentID: "a1", Version: 1, EndpointURL: "http://a1", CreatedAt: now}); err != nil {
+			t.Fatalf("CreateAgentVersion failed: %v", err)
+		}
+		if err := repo.CreateTable(TableRecord{ID: "t1", Name: "table", MaxSeats: 6, SmallBlind: 50, BigBlind: 100, Status: "idle", CreatedAt: now}); err != nil {
+			t.Fatalf("CreateTable failed: %v", err)
+		}
+		if err := repo.UpsertSeat(SeatRecord{ID: "s1", TableID: "t1", SeatNo: 1, AgentID: "a1", AgentVersionID: "v1", Stack: 7000, Status: domain.SeatStatusActive, CreatedAt: now}); err != nil {
+			t.Fatalf("UpsertSeat failed: %v", err)
+		}
+
+		removed, err := repo.RemoveSeat("t1", 1)
+		if err != nil {
+			t.Fatalf("RemoveSeat failed: %v", err)
+		}
+		if removed.AgentID != "a1" || removed.Stack != 7000 {
+			t.Fatalf("unexpected removed seat: %+v", removed)
+		}
+		if _, err := repo.RemoveSeat("t1", 1); !errors.Is(err, ErrSeatNotFound) {
+			t.Fatalf("expected ErrSeatNotFound, got %v", err)
+		}
+
+		for i, stack := range []domain.Chips{7000, 12000} {
+			if err := repo.RecordDeparture(DepartureRecord{
+				TableID:    "t1",
+				UserID:     "u1",
+				AgentID:    "a1",
+				SmallBlind: 50,
+				BigBlind:   100,
+				Stack:      stack,
+				LeftAt:     now.Add(time.Duration(i) * time.Minute),
+			}); err != nil {
+				t.Fatalf("RecordDeparture failed: %v", err)
+			}
+		}
+		latest, ok, err := repo.LatestDeparture("u1", 50, 100)
+		if err != nil || !ok {
+			t.Fatalf("LatestDeparture failed: ok=%v err=%v", ok, err)
+		}
+		if latest.Stack != 12000 {
+			t.Fatalf("expected latest departure stack 12000, got %d", latest.Stack)
+		}
+		if _, ok, err := repo.LatestDeparture("u1", 100, 200); err != nil || ok {
+			t.Fatalf("expected no departure at other stakes, ok=%v err=%v", ok, err)
+		}
+	})
+
 	t.Run("Contract_ListTablesReturnsSortedByID", func(t *testing.T) {
 		repo := mkRepo(t)
 		now := time.Now().UTC()
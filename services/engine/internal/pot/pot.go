@@ -0,0 +1,196 @@
+// Package pot splits a hand's chips into a main pot and side pots and pays
+// them out at showdown.
+package pot
+
+import (
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// Pot is one pot of a hand. Eligible lists, in seat order, the live seats
+// that put in enough to win it.
+type Pot struct {
+	Amount   domain.Chips    `json:"amount"`
+	Eligible []domain.SeatNo `json:"eligible"`
+}
+
+// Payout is one pot settled at showdown. Winners tie for the pot in seat
+// order, and Chips is each winner's share, with odd chips going to the
+// winners closest to the button's left.
+type Payout struct {
+	Pot     int                            `json:"pot"`
+	Amount  domain.Chips                   `json:"amount"`
+	Winners []domain.SeatNo                `json:"winners"`
+	Chips   map[domain.SeatNo]domain.Chips `json:"chips"`
+}
+
+// Manager holds the pots built from every seat's commitment to a hand.
+type Manager struct {
+	pots []Pot
+}
+
+// NewManager builds the pots from each seat's TotalCommitted. Only live
+// seats, those still active and not folded, open a pot: each distinct amount
+// a live seat committed caps one, the lowest being the main pot. Chips from
+// folded seats are dead money in every pot they reach, and anything a folded
+// seat put in above the highest live commitment joins the last pot.
+func NewManager(seats []domain.SeatState) *Manager {
+	levels := make([]domain.Chips, 0, len(seats))
+	for _, seat := range seats {
+		if isLive(seat) && seat.TotalCommitted > 0 && !slices.Contains(levels, seat.TotalCommitted) {
+			levels = append(levels, seat.TotalCommitted)
+		}
+	}
+	slices.Sort(levels)
+
+	m := &Manager{pots: make([]Pot, 0, len(levels))}
+	prev := domain.Chips(0)
+	for _, level := range levels {
+		var pot Pot
+		for _, seat := range seats {
+			pot.Amount += min(seat.TotalCommitted, level) - min(seat.TotalCommitted, prev)
+			if isLive(seat) && seat.TotalCommitted >= level {
+				pot.Eligible = append(pot.Eligible, seat.SeatNo)
+			}
+		}
+		slices.Sort(pot.Eligible)
+		m.pots = append(m.pots, pot)
+		prev = level
+	}
+
+	var dead domain.Chips
+	for _, seat := range seats {
+		if seat.TotalCommitted > prev {
+			dead += seat.TotalCommitted - prev
+		}
+	}
+	if dead > 0 {
+		if len(m.pots) == 0 {
+			m.pots = append(m.pots, Pot{})
+		}
+		m.pots[len(m.pots)-1].Amount += dead
+	}
+	return m
+}
+
+// AddDead puts chips no seat committed, such as a button ante, into the main
+// pot.
+func (m *Manager) AddDead(amount domain.Chips) {
+	if amount == 0 {
+		return
+	}
+	if len(m.pots) == 0 {
+		m.pots = append(m.pots, Pot{})
+	}
+	m.pots[0].Amount += amount
+}
+
+// Rake takes up to amount out of the pots for the house, main pot first,
+// and returns what it took.
+func (m *Manager) Rake(amount domain.Chips) domain.Chips {
+	var taken domain.Chips
+	for i := range m.pots {
+		cut := min(amount-taken, m.pots[i].Amount)
+		m.pots[i].Amount -= cut
+		taken += cut
+	}
+	return taken
+}
+
+// Share is a copy of m holding run's part of every pot when the hand is run
+// out runs times: each pot splits evenly, with its odd chips in the first
+// run's part.
+func (m *Manager) Share(run, runs int) *Manager {
+	out := &Manager{pots: m.Pots()}
+	for i := range out.pots {
+		amount := out.pots[i].Amount
+		out.pots[i].Amount = amount / domain.Chips(runs)
+		if run == 0 {
+			out.pots[i].Amount += amount % domain.Chips(runs)
+		}
+	}
+	return out
+}
+
+// Pots returns the main pot first, then each side pot.
+func (m *Manager) Pots() []Pot {
+	out := make([]Pot, 0, len(m.pots))
+	for _, pot := range m.pots {
+		out = append(out, Pot{Amount: pot.Amount, Eligible: slices.Clone(pot.Eligible)})
+	}
+	return out
+}
+
+// Total is every chip in the pots.
+func (m *Manager) Total() domain.Chips {
+	var total domain.Chips
+	for _, pot := range m.pots {
+		total += pot.Amount
+	}
+	return total
+}
+
+// EligiblePots lists the indexes of the pots seatNo can win.
+func (m *Manager) EligiblePots(seatNo domain.SeatNo) []int {
+	var out []int
+	for i, pot := range m.pots {
+		if slices.Contains(pot.Eligible, seatNo) {
+			out = append(out, i)
+		}
+	}
+	return out
+}
+
+// Payouts settles every pot that has an eligible seat. compare ranks two
+// eligible seats' hands, returning a positive number when a's is better.
+func (m *Manager) Payouts(button domain.SeatNo, compare func(a, b domain.SeatNo) int) []Payout {
+	out := make([]Payout, 0, len(m.pots))
+	for i, pot := range m.pots {
+		var winners []domain.SeatNo
+		for _, seatNo := range pot.Eligible {
+			if len(winners) == 0 {
+				winners = []domain.SeatNo{seatNo}
+				continue
+			}
+			switch order := compare(seatNo, winners[0]); {
+			case order > 0:
+				winners = []domain.SeatNo{seatNo}
+			case order == 0:
+				winners = append(winners, seatNo)
+			}
+		}
+		if len(winners) == 0 {
+			continue
+		}
+
+		share := pot.Amount / domain.Chips(len(winners))
+		odd := pot.Amount % domain.Chips(len(winners))
+		chips := make(map[domain.SeatNo]domain.Chips, len(winners))
+		for _, seatNo := range winners {
+			chips[seatNo] = share
+		}
+		for _, seatNo := range leftOfButton(button, winners)[:odd] {
+			chips[seatNo]++
+		}
+		out = append(out, Payout{Pot: i, Amount: pot.Amount, Winners: winners, Chips: chips})
+	}
+	return out
+}
+
+func isLive(seat domain.SeatState) bool {
+	return seat.Status == domain.SeatStatusActive && !seat.Folded
+}
+
+// leftOfButton reorders seats, which are in seat order, clockwise from the
+// first seat after the button.
+func leftOfButton(button domain.SeatNo, seats []domain.SeatNo) []domain.SeatNo {
+	start := 0
+	for i, seatNo := range seats {
+		if seatNo > button {
+			start = i
+			break
+		}
+	}
+	return append(slices.Clone(seats[start:]), seats[:start]...)
+}
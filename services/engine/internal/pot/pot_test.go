@@ -0,0 +1,170 @@
+package pot
+
+import (
+	"math/rand"
+	"reflect"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/pot/pottest"
+)
+
+func seatWithCommit(seatNo domain.SeatNo, committed domain.Chips, folded bool) domain.SeatState {
+	return domain.SeatState{SeatNo: seatNo, TotalCommitted: committed, Folded: folded, Status: domain.SeatStatusActive}
+}
+
+func TestManager_BuildsSidePotsForThreeAllInStacks(t *testing.T) {
+	t.Parallel()
+
+	manager := NewManager([]domain.SeatState{
+		seatWithCommit(1, 1000, false),
+		seatWithCommit(2, 250, false),
+		seatWithCommit(3, 600, false),
+		seatWithCommit(4, 150, true),
+	})
+	want := []Pot{
+		{Amount: 250*3 + 150, Eligible: []domain.SeatNo{1, 2, 3}},
+		{Amount: 350 * 2, Eligible: []domain.SeatNo{1, 3}},
+		{Amount: 400, Eligible: []domain.SeatNo{1}},
+	}
+	if got := manager.Pots(); !reflect.DeepEqual(got, want) {
+		t.Fatalf("expected pots %+v, got %+v", want, got)
+	}
+	if manager.Total() != 1000+250+600+150 {
+		t.Fatalf("expected every committed chip in the pots, got %d", manager.Total())
+	}
+	if got := manager.EligiblePots(3); !reflect.DeepEqual(got, []int{0, 1}) {
+		t.Fatalf("expected seat 3 eligible for the main and first side pot, got %v", got)
+	}
+	if got := manager.EligiblePots(4); got != nil {
+		t.Fatalf("expected the folded seat eligible for nothing, got %v", got)
+	}
+
+	// Seat 2 has the best hand and seats 1 and 3 tie behind it.
+	strength := map[domain.SeatNo]int{1: 1, 2: 2, 3: 1}
+	payouts := manager.Payouts(2, func(a, b domain.SeatNo) int { return strength[a] - strength[b] })
+	wantPayouts := []Payout{
+		{Pot: 0, Amount: 900, Winners: []domain.SeatNo{2}, Chips: map[domain.SeatNo]domain.Chips{2: 900}},
+		{Pot: 1, Amount: 700, Winners: []domain.SeatNo{1, 3}, Chips: map[domain.SeatNo]domain.Chips{1: 350, 3: 350}},
+		{Pot: 2, Amount: 400, Winners: []domain.SeatNo{1}, Chips: map[domain.SeatNo]domain.Chips{1: 400}},
+	}
+	if !reflect.DeepEqual(payouts, wantPayouts) {
+		t.Fatalf("expected payouts %+v, got %+v", wantPayouts, payouts)
+	}
+}
+
+func TestManager_DeadMoneyAndOddChips(t *testing.T) {
+	t.Parallel()
+
+	// Seat 3 folded with more in than either live seat, so its last 100
+	// chips have no live seat to contest them and join the last pot.
+	manager := NewManager([]domain.SeatState{
+		seatWithCommit(1, 201, false),
+		seatWithCommit(2, 200, false),
+		seatWithCommit(3, 301, true),
+	})
+	want := []Pot{
+		{Amount: 600, Eligible: []domain.SeatNo{1, 2}},
+		{Amount: 102, Eligible: []domain.SeatNo{1}},
+	}
+	if got := manager.Pots(); !reflect.DeepEqual(got, want) {
+		t.Fatalf("expected pots %+v, got %+v", want, got)
+	}
+
+	tie := func(domain.SeatNo, domain.SeatNo) int { return 0 }
+	manager = NewManager([]domain.SeatState{
+		seatWithCommit(1, 101, false),
+		seatWithCommit(2, 101, false),
+		seatWithCommit(3, 101, false),
+		seatWithCommit(4, 101, false),
+	})
+	chips := manager.Payouts(3, tie)[0].Chips
+	if want := (map[domain.SeatNo]domain.Chips{1: 101, 2: 101, 3: 101, 4: 101}); !reflect.DeepEqual(chips, want) {
+		t.Fatalf("expected an even split, got %v", chips)
+	}
+	manager = NewManager([]domain.SeatState{
+		seatWithCommit(1, 100, false),
+		seatWithCommit(2, 100, false),
+		seatWithCommit(3, 100, false),
+		seatWithCommit(4, 2, true),
+	})
+	chips = manager.Payouts(2, tie)[0].Chips
+	if want := (map[domain.SeatNo]domain.Chips{1: 101, 2: 100, 3: 101}); !reflect.DeepEqual(chips, want) {
+		t.Fatalf("expected odd chips clockwise from the button's left, got %v", chips)
+	}
+}
+
+func TestManager_SplitsAPreflopAllInDeadHeatExactlyAcrossSidePots(t *testing.T) {
+	t.Parallel()
+
+	// Four stacks of different sizes get all-in preflop and every hand
+	// ties; seat 5 folded after putting in 500.
+	seats := []domain.SeatState{
+		seatWithCommit(1, 301, false),
+		seatWithCommit(2, 1000, false),
+		seatWithCommit(3, 650, false),
+		seatWithCommit(4, 1000, false),
+		seatWithCommit(5, 500, true),
+	}
+	tie := func(domain.SeatNo, domain.SeatNo) int { return 0 }
+	want := map[domain.SeatNo]domain.Chips{
+		1: 377,             // main pot of 1505 in four, odd chip to seat 1
+		2: 376 + 416 + 350, // side pot of 1246 in three, odd chip to seat 2
+		3: 376 + 415,
+		4: 376 + 415 + 350,
+	}
+	if got := totalWinnings(NewManager(seats).Payouts(4, tie)); !reflect.DeepEqual(got, want) {
+		t.Fatalf("expected manager winnings %v, got %v", want, got)
+	}
+	if got := pottest.Payouts(seats, 0, 4, tie); !reflect.DeepEqual(got, want) {
+		t.Fatalf("expected oracle winnings %v, got %v", want, got)
+	}
+}
+
+func TestManager_MatchesThePayoutOracle(t *testing.T) {
+	t.Parallel()
+
+	rng := rand.New(rand.NewSource(271))
+	for i := 0; i < 2000; i++ {
+		// Few distinct commitments and strengths make for many side pots
+		// with the same cap and many dead heats.
+		seatNos := rng.Perm(9)[:2+rng.Intn(5)]
+		seats := make([]domain.SeatState, 0, len(seatNos))
+		strength := make(map[domain.SeatNo]int, len(seatNos))
+		var committed domain.Chips
+		for _, n := range seatNos {
+			seat := seatWithCommit(domain.SeatNo(n+1), domain.Chips(rng.Intn(4)*250+rng.Intn(3)), rng.Intn(4) == 0)
+			seats = append(seats, seat)
+			strength[seat.SeatNo] = rng.Intn(3)
+			committed += seat.TotalCommitted
+		}
+		dead := domain.Chips(rng.Intn(3))
+		button := domain.SeatNo(1 + rng.Intn(9))
+		compare := func(a, b domain.SeatNo) int { return strength[a] - strength[b] }
+
+		manager := NewManager(seats)
+		manager.AddDead(dead)
+		got := totalWinnings(manager.Payouts(button, compare))
+		want := pottest.Payouts(seats, dead, button, compare)
+		if !reflect.DeepEqual(got, want) {
+			t.Fatalf("scenario %d: seats %+v, dead %d, button %d, strength %v: expected %v, got %v", i, seats, dead, button, strength, want, got)
+		}
+		var paid domain.Chips
+		for _, chips := range want {
+			paid += chips
+		}
+		if len(want) > 0 && paid != committed+dead {
+			t.Fatalf("scenario %d: expected all %d chips paid out, oracle paid %d", i, committed+dead, paid)
+		}
+	}
+}
+
+func totalWinnings(payouts []Payout) map[domain.SeatNo]domain.Chips {
+	won := map[domain.SeatNo]domain.Chips{}
+	for _, payout := range payouts {
+		for seatNo, chips := range payout.Chips {
+			won[seatNo] += chips
+		}
+	}
+	return won
+}
@@ -0,0 +1,88 @@
+// Package pottest is a reference for testing pot.Manager: a slow payout
+// that is easy to check by hand and shares no code with the package it
+// tests.
+package pottest
+
+import (
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// Payouts pays out a hand one chip layer at a time. The k-th chip every
+// seat committed forms layer k, contested by the live seats, active and
+// not folded, that committed at least k chips. Consecutive layers with the
+// same contestants make one pot, layers no live seat reached join the last
+// pot that has contestants, and dead money, such as a button ante, joins
+// the first. Each pot goes to the seats compare ranks best, dealt one chip
+// at a time clockwise from the button's left, so the odd chips of a split
+// land on the winners closest to it. It returns what each seat wins across
+// every pot; chips with no live seat to contest them are not paid out.
+func Payouts(seats []domain.SeatState, dead domain.Chips, button domain.SeatNo, compare func(a, b domain.SeatNo) int) map[domain.SeatNo]domain.Chips {
+	var top domain.Chips
+	for _, seat := range seats {
+		top = max(top, seat.TotalCommitted)
+	}
+
+	type layeredPot struct {
+		amount      domain.Chips
+		contestants []domain.SeatNo
+	}
+	var pots []layeredPot
+	var uncontested domain.Chips
+	for k := domain.Chips(1); k <= top; k++ {
+		var chips domain.Chips
+		var contestants []domain.SeatNo
+		for _, seat := range seats {
+			if seat.TotalCommitted < k {
+				continue
+			}
+			chips++
+			if seat.Status == domain.SeatStatusActive && !seat.Folded {
+				contestants = append(contestants, seat.SeatNo)
+			}
+		}
+		switch {
+		case len(contestants) == 0:
+			uncontested += chips
+		case len(pots) > 0 && slices.Equal(pots[len(pots)-1].contestants, contestants):
+			pots[len(pots)-1].amount += chips
+		default:
+			pots = append(pots, layeredPot{amount: chips, contestants: contestants})
+		}
+	}
+	if len(pots) == 0 {
+		return map[domain.SeatNo]domain.Chips{}
+	}
+	pots[0].amount += dead
+	pots[len(pots)-1].amount += uncontested
+
+	won := map[domain.SeatNo]domain.Chips{}
+	for _, pot := range pots {
+		var winners []domain.SeatNo
+		for _, seatNo := range pot.contestants {
+			beaten := false
+			for _, other := range pot.contestants {
+				if compare(other, seatNo) > 0 {
+					beaten = true
+				}
+			}
+			if !beaten {
+				winners = append(winners, seatNo)
+			}
+		}
+		// Walk every seat number once, wrapping past the largest, to put
+		// the winners in clockwise order from the button's left.
+		var clockwise []domain.SeatNo
+		for i := 1; i <= 256; i++ {
+			next := button + domain.SeatNo(i)
+			if slices.Contains(winners, next) {
+				clockwise = append(clockwise, next)
+			}
+		}
+		for chip := domain.Chips(0); chip < pot.amount; chip++ {
+			won[clockwise[chip%domain.Chips(len(clockwise))]]++
+		}
+	}
+	return won
+}
@@ -0,0 +1,52 @@
+// Package rake works out the house's cut of a hand under its table's
+// domain.RakeRule. The rules package takes it out of the pots as they are
+// awarded, main pot first, and records it on the hand as RakeTaken, so cash
+// game simulations pay it the way a real card room would.
+package rake
+
+import (
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// Amount is the rake state's rule takes from pot chips awarded at the end
+// of state. Nothing is taken without a rule, from a hand that does not
+// count under the table's QualifiedHandRule, or under NoFlopNoDrop from a
+// hand that ended before the flop; an all-in runout that deals one counts.
+// Chips nobody called are not raked: the part of the biggest commitment to
+// the hand that no other seat matched goes back to its seat whole.
+func Amount(state domain.HandState, pot domain.Chips) domain.Chips {
+	rule := state.Rake
+	if rule == nil || !state.Qualified() {
+		return 0
+	}
+	if rule.NoFlopNoDrop && len(state.Board) < 3 {
+		return 0
+	}
+	pot -= min(pot, uncalled(state.Seats))
+	if pot == 0 {
+		return 0
+	}
+	// Split the pot so multiplying by the basis points cannot overflow.
+	bp := domain.Chips(rule.BasisPoints)
+	whole, rest := pot/domain.MaxRakeBasisPoints, pot%domain.MaxRakeBasisPoints
+	amount := whole*bp + rest*bp/domain.MaxRakeBasisPoints
+	if rule.Cap > 0 {
+		amount = min(amount, rule.Cap)
+	}
+	return amount
+}
+
+// uncalled is how far the biggest commitment to the hand goes past the next
+// biggest, folded seats' included: the chips no other seat called.
+func uncalled(seats []domain.SeatState) domain.Chips {
+	var top, next domain.Chips
+	for _, seat := range seats {
+		switch {
+		case seat.TotalCommitted > top:
+			top, next = seat.TotalCommitted, top
+		case seat.TotalCommitted > next:
+			next = seat.TotalCommitted
+		}
+	}
+	return top - next
+}
@@ -0,0 +1,44 @@
+package rake
+
+import (
+	"math"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestAmount_PercentageCapAndNoFlopNoDrop(t *testing.T) {
+	t.Parallel()
+
+	flop := []domain.Card{domain.NewCard(14, domain.SuitSpades), domain.NewCard(13, domain.SuitSpades), domain.NewCard(2, domain.SuitClubs)}
+	tests := []struct {
+		name          string
+		rule          *domain.RakeRule
+		qualification *domain.QualifiedHandRule
+		board         []domain.Card
+		committed     []domain.Chips
+		pot           domain.Chips
+		want          domain.Chips
+	}{
+		{name: "no rule", pot: 1000, board: flop},
+		{name: "percentage", rule: &domain.RakeRule{BasisPoints: 500}, board: flop, pot: 1000, want: 50},
+		{name: "rounds down", rule: &domain.RakeRule{BasisPoints: 500}, board: flop, pot: 39, want: 1},
+		{name: "capped", rule: &domain.RakeRule{BasisPoints: 500, Cap: 30}, board: flop, pot: 1000, want: 30},
+		{name: "no flop no drop", rule: &domain.RakeRule{BasisPoints: 500, NoFlopNoDrop: true}, pot: 1000},
+		{name: "flop dropped", rule: &domain.RakeRule{BasisPoints: 500, NoFlopNoDrop: true}, board: flop, pot: 1000, want: 50},
+		{name: "preflop raked", rule: &domain.RakeRule{BasisPoints: 500}, pot: 1000, want: 50},
+		{name: "huge pot", rule: &domain.RakeRule{BasisPoints: domain.MaxRakeBasisPoints}, pot: math.MaxUint64, want: math.MaxUint64},
+		{name: "unqualified", rule: &domain.RakeRule{BasisPoints: 500}, qualification: &domain.QualifiedHandRule{SawFlop: true}, pot: 1000},
+		{name: "qualified", rule: &domain.RakeRule{BasisPoints: 500}, qualification: &domain.QualifiedHandRule{SawFlop: true}, board: flop, pot: 1000, want: 50},
+		{name: "uncalled", rule: &domain.RakeRule{BasisPoints: 500}, board: flop, committed: []domain.Chips{600, 200, 100}, pot: 900, want: 25},
+	}
+	for _, tt := range tests {
+		state := domain.HandState{Rake: tt.rule, Qualification: tt.qualification, Board: tt.board}
+		for i, committed := range tt.committed {
+			state.Seats = append(state.Seats, domain.SeatState{SeatNo: domain.SeatNo(i + 1), TotalCommitted: committed})
+		}
+		if got := Amount(state, tt.pot); got != tt.want {
+			t.Fatalf("%s: expected rake %d, got %d", tt.name, tt.want, got)
+		}
+	}
+}
@@ -0,0 +1,77 @@
+// Package retention expires stored arena data by class, so an operator can
+// keep hand histories for years while dropping bot telemetry after weeks.
+// Data is aged from the end of the hand it belongs to; hands still being
+// played are never expired. The engine stores no chat: dealer commentary
+// only goes to the operator's commentary log and overlay, which keep it for
+// as long as the operator configures them to.
+package retention
+
+import (
+	"errors"
+	"fmt"
+	"time"
+)
+
+var ErrInvalidPolicy = errors.New("invalid retention policy")
+
+// Class names a kind of stored data with its own retention period.
+type Class string
+
+const (
+	// ClassHands is hand histories: the final state, the winners and the
+	// action log.
+	ClassHands Class = "hands"
+	// ClassTelemetry is the equity and tags bots report with their
+	// actions.
+	ClassTelemetry Class = "telemetry"
+)
+
+// Policy is how long each class is kept after its hand ends. Zero keeps
+// the class forever.
+type Policy struct {
+	Hands     time.Duration
+	Telemetry time.Duration
+}
+
+func (p Policy) Validate() error {
+	if p.Hands < 0 || p.Telemetry < 0 {
+		return fmt.Errorf("%w: %s kept for %s and %s for %s", ErrInvalidPolicy, ClassHands, p.Hands, ClassTelemetry, p.Telemetry)
+	}
+	return nil
+}
+
+// Store deletes expired data. persistence.Repository implements it.
+type Store interface {
+	PurgeHands(cutoff time.Time) (int, error)
+	PurgeTelemetry(cutoff time.Time) (int, error)
+}
+
+// Report counts what Enforce removed: whole hands, and actions whose
+// telemetry was dropped.
+type Report struct {
+	HandsPurged     int `json:"hands_purged"`
+	TelemetryPurged int `json:"telemetry_purged"`
+}
+
+// Enforce removes the data policy no longer keeps at now.
+func Enforce(store Store, policy Policy, now time.Time) (Report, error) {
+	var report Report
+	if err := policy.Validate(); err != nil {
+		return report, err
+	}
+	if policy.Telemetry > 0 {
+		purged, err := store.PurgeTelemetry(now.Add(-policy.Telemetry))
+		if err != nil {
+			return report, fmt.Errorf("purge %s: %w", ClassTelemetry, err)
+		}
+		report.TelemetryPurged = purged
+	}
+	if policy.Hands > 0 {
+		purged, err := store.PurgeHands(now.Add(-policy.Hands))
+		if err != nil {
+			return report, fmt.Errorf("purge %s: %w", ClassHands, err)
+		}
+		report.HandsPurged = purged
+	}
+	return report, nil
+}
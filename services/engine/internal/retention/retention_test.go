@@ -0,0 +1,57 @@
+package retention
+
+import (
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/persistence"
+)
+
+func TestEnforce_ExpiresEachClassOnItsOwnPeriod(t *testing.T) {
+	t.Parallel()
+
+	now := time.Date(2026, 6, 1, 12, 0, 0, 0, time.UTC)
+	repo := persistence.NewInMemoryRepository()
+	if err := repo.UpsertTableRun(persistence.TableRunRecord{TableID: "table-1", Status: persistence.TableRunStatusCompleted, StartedAt: now.AddDate(0, -3, 0)}); err != nil {
+		t.Fatalf("UpsertTableRun failed: %v", err)
+	}
+	equity := 0.4
+	hands := []struct {
+		id  string
+		age time.Duration
+	}{{"old", 60 * 24 * time.Hour}, {"recent", 10 * 24 * time.Hour}, {"fresh", time.Hour}}
+	for i, hand := range hands {
+		endedAt := now.Add(-hand.age)
+		if err := repo.CreateHand(persistence.HandRecord{HandID: hand.id, TableID: "table-1", HandNo: uint64(i + 1), StartedAt: endedAt, EndedAt: &endedAt}); err != nil {
+			t.Fatalf("CreateHand failed: %v", err)
+		}
+		if err := repo.AppendAction(persistence.ActionRecord{HandID: hand.id, ActingSeat: 1, Action: domain.ActionCheck, At: endedAt, Telemetry: &domain.Telemetry{Equity: &equity}}); err != nil {
+			t.Fatalf("AppendAction failed: %v", err)
+		}
+	}
+
+	report, err := Enforce(repo, Policy{Hands: 30 * 24 * time.Hour, Telemetry: 7 * 24 * time.Hour}, now)
+	if err != nil {
+		t.Fatalf("Enforce failed: %v", err)
+	}
+	if report != (Report{HandsPurged: 1, TelemetryPurged: 2}) {
+		t.Fatalf("expected one hand and two actions' telemetry purged, got %+v", report)
+	}
+	if _, ok, _ := repo.GetHand("old"); ok {
+		t.Fatal("expected the hand past its period deleted")
+	}
+	recent, _ := repo.ListActions("recent")
+	fresh, _ := repo.ListActions("fresh")
+	if len(recent) != 1 || recent[0].Telemetry != nil || len(fresh) != 1 || fresh[0].Telemetry == nil {
+		t.Fatalf("expected only the fresh hand's telemetry kept, got recent=%+v fresh=%+v", recent, fresh)
+	}
+
+	if report, err := Enforce(repo, Policy{}, now.AddDate(10, 0, 0)); err != nil || report != (Report{}) {
+		t.Fatalf("expected the zero policy to keep everything, got %+v err=%v", report, err)
+	}
+	if _, err := Enforce(repo, Policy{Telemetry: -time.Hour}, now); !errors.Is(err, ErrInvalidPolicy) {
+		t.Fatalf("expected ErrInvalidPolicy, got %v", err)
+	}
+}
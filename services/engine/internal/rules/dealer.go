@@ -2,6 +2,10 @@ package rules
 
 import (
 	cryptorand "crypto/rand"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
 	"fmt"
 	"math/big"
 	"math/rand"
@@ -10,6 +14,8 @@ import (
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
 )
 
+var ErrWrongStreet = errors.New("cannot deal street")
+
 type Shuffler interface {
 	Shuffle([]domain.Card) error
 }
@@ -18,6 +24,11 @@ type Dealer interface {
 	InitHand(state domain.HandState) (domain.HandState, error)
 	DealPreflop(state domain.HandState) (domain.HandState, error)
 	DealFlopTurnRiver(state domain.HandState) (domain.HandState, error)
+	// DealStreet burns and deals the board cards for street, which must be
+	// the street after state.Street with the board dealt so far, and moves
+	// the hand to it. A hand with Runouts gets the street on every board,
+	// the first board first.
+	DealStreet(state domain.HandState, street domain.Street) (domain.HandState, error)
 }
 
 type cryptoShuffler struct{}
@@ -35,7 +46,13 @@ func NewCryptoShuffler() Shuffler {
 }
 
 func NewSeededShuffler(seed int64) Shuffler {
-	return seededShuffler{rng: rand.New(rand.NewSource(seed))}
+	return NewRandShuffler(rand.New(rand.NewSource(seed)))
+}
+
+// NewRandShuffler shuffles with rng, for callers that bring their own
+// source. The shuffler is only as safe for concurrent use as rng.
+func NewRandShuffler(rng *rand.Rand) Shuffler {
+	return seededShuffler{rng: rng}
 }
 
 func NewDealer(shuffler Shuffler) Dealer {
@@ -66,20 +83,34 @@ func (s seededShuffler) Shuffle(cards []domain.Card) error {
 }
 
 func (d standardDealer) InitHand(state domain.HandState) (domain.HandState, error) {
-	deck := domain.Standard52Deck().Cards
+	deck := state.DeckSpec.Build().Cards
 	if err := d.shuffler.Shuffle(deck); err != nil {
 		return state, err
 	}
 	state.Deck = deck
+	state.DeckCommitment = CommitDeck(deck)
 	state.NextCardIndex = 0
 	state.Board = make([]domain.Card, 0, 5)
 	state.HoleCards = make([]domain.SeatCards, 0, len(state.Seats))
+	state.DealSequence = make([]domain.DealtCard, 0, 2*len(state.Seats)+8)
 	state.ShowdownAwards = make([]domain.PotAward, 0, 4)
 	return state, nil
 }
 
+// CommitDeck is the SHA-256, in hex, of deck's compact JSON encoding. A hand
+// records it as DeckCommitment for its shuffled deck before any card is
+// dealt, and every seat sees it, so an audit of the archived hand can show
+// it was dealt from that deck. Draw games append reshuffled cards to the
+// deck after the committed ones.
+func CommitDeck(deck []domain.Card) string {
+	// Cards hold only a rank and a suit, so encoding cannot fail.
+	payload, _ := json.Marshal(deck)
+	sum := sha256.Sum256(payload)
+	return hex.EncodeToString(sum[:])
+}
+
 func (d standardDealer) DealPreflop(state domain.HandState) (domain.HandState, error) {
-	if len(state.Deck) != 52 {
+	if len(state.Deck) != state.DeckSpec.Size() {
 		return state, fmt.Errorf("cannot deal preflop: deck size is %d", len(state.Deck))
 	}
 
@@ -88,34 +119,16 @@ func (d standardDealer) DealPreflop(state domain.HandState) (domain.HandState, e
 		return state, fmt.Errorf("cannot deal preflop: active seats=%d", len(ordered))
 	}
 
-	hole := make(map[domain.SeatNo][]domain.Card, len(ordered))
-	for round := 0; round < 2; round++ {
-		for _, seatNo := range ordered {
-			card, err := drawCard(&state)
-			if err != nil {
-				return state, err
-			}
-			hole[seatNo] = append(hole[seatNo], card)
-		}
-	}
-
-	state.HoleCards = make([]domain.SeatCards, 0, len(hole))
-	for _, seatNo := range ordered {
-		state.HoleCards = append(state.HoleCards, domain.SeatCards{
-			SeatNo: seatNo,
-			Cards:  append([]domain.Card(nil), hole[seatNo]...),
-		})
-	}
-	return state, nil
+	return dealHoleCards(state, ordered, 2)
 }
 
 func (d standardDealer) DealFlopTurnRiver(state domain.HandState) (domain.HandState, error) {
-	if len(state.Deck) != 52 {
+	if len(state.Deck) != state.DeckSpec.Size() {
 		return state, fmt.Errorf("cannot deal board: deck size is %d", len(state.Deck))
 	}
 
 	// Burn one card before every post-flop street.
-	if _, err := drawCard(&state); err != nil {
+	if _, err := dealCard(&state, domain.DealTargetBurn, 0); err != nil {
 		return state, err
 	}
 
@@ -130,7 +143,7 @@ func (d standardDealer) DealFlopTurnRiver(state domain.HandState) (domain.HandSt
 	}
 
 	for i := 0; i < draw; i++ {
-		card, err := drawCard(&state)
+		card, err := dealCard(&state, domain.DealTargetBoard, 0)
 		if err != nil {
 			return state, err
 		}
@@ -140,6 +153,74 @@ func (d standardDealer) DealFlopTurnRiver(state domain.HandState) (domain.HandSt
 	return state, nil
 }
 
+func (d standardDealer) DealStreet(state domain.HandState, street domain.Street) (domain.HandState, error) {
+	var from domain.Street
+	var board int
+	switch street {
+	case domain.StreetFlop:
+		from, board = domain.StreetPreflop, 0
+	case domain.StreetTurn:
+		from, board = domain.StreetFlop, 3
+	case domain.StreetRiver:
+		from, board = domain.StreetTurn, 4
+	default:
+		return state, fmt.Errorf("%w: %s has no board cards", ErrWrongStreet, street)
+	}
+	if state.Street != from || len(state.Board) != board {
+		return state, fmt.Errorf("%w: %s from the %s with %d board cards", ErrWrongStreet, street, state.Street, len(state.Board))
+	}
+	for i, runout := range state.Runouts {
+		if len(runout) != board {
+			return state, fmt.Errorf("%w: %s on runout %d with %d board cards", ErrWrongStreet, street, i+2, len(runout))
+		}
+	}
+
+	dealt, err := d.DealFlopTurnRiver(state)
+	if err != nil {
+		return state, err
+	}
+	for i := range dealt.Runouts {
+		if dealt, err = dealRunout(dealt, i, len(dealt.Board)-board); err != nil {
+			return state, err
+		}
+	}
+	dealt.Street = street
+	return dealt, nil
+}
+
+// StartRunouts readies a hand whose players are all in to run the rest of
+// the board out state.RunItTimes times: every extra board starts as a copy
+// of the board dealt so far, and DealStreet deals to each from then on. A
+// hand run once, or one already started, is returned as it is.
+func StartRunouts(state domain.HandState) domain.HandState {
+	if state.RunItTimes < 2 || state.Runouts != nil {
+		return state
+	}
+	state.Runouts = make([][]domain.Card, 0, state.RunItTimes-1)
+	for range state.RunItTimes - 1 {
+		state.Runouts = append(state.Runouts, append(make([]domain.Card, 0, 5), state.Board...))
+	}
+	return state
+}
+
+// dealRunout burns and deals draw cards to the extra board at index i of
+// state.Runouts, marking each in DealSequence with its runout number.
+func dealRunout(state domain.HandState, i int, draw int) (domain.HandState, error) {
+	if _, err := dealCard(&state, domain.DealTargetBurn, 0); err != nil {
+		return state, err
+	}
+	state.DealSequence[len(state.DealSequence)-1].Runout = i + 2
+	for range draw {
+		card, err := dealCard(&state, domain.DealTargetBoard, 0)
+		if err != nil {
+			return state, err
+		}
+		state.DealSequence[len(state.DealSequence)-1].Runout = i + 2
+		state.Runouts[i] = append(state.Runouts[i], card)
+	}
+	return state, nil
+}
+
 func activeSeatsInDealOrder(seats []domain.SeatState, button domain.SeatNo) []domain.SeatNo {
 	ordered := make([]domain.SeatNo, 0, len(seats))
 	for _, seat := range seats {
@@ -152,26 +233,54 @@ func activeSeatsInDealOrder(seats []domain.SeatState, button domain.SeatNo) []do
 		return ordered
 	}
 
-	buttonIdx := 0
+	// The button may be dead, on a seat that is not dealt in, so dealing
+	// starts at the first seat after it rather than after its index.
+	start := 0
 	for i, seatNo := range ordered {
-		if seatNo == button {
-			buttonIdx = i
+		if seatNo > button {
+			start = i
 			break
 		}
 	}
+	return append(append([]domain.SeatNo(nil), ordered[start:]...), ordered[:start]...)
+}
+
+// dealHoleCards deals count cards to each seat in ordered one at a time
+// around the table, as in live dealing; DealSequence keeps the interleaved
+// order for audits.
+func dealHoleCards(state domain.HandState, ordered []domain.SeatNo, count int) (domain.HandState, error) {
+	hole := make(map[domain.SeatNo][]domain.Card, len(ordered))
+	for round := 0; round < count; round++ {
+		for _, seatNo := range ordered {
+			card, err := dealCard(&state, domain.DealTargetHole, seatNo)
+			if err != nil {
+				return state, err
+			}
+			hole[seatNo] = append(hole[seatNo], card)
+		}
+	}
 
-	out := make([]domain.SeatNo, 0, len(ordered))
-	for i := 1; i <= len(ordered); i++ {
-		out = append(out, ordered[(buttonIdx+i)%len(ordered)])
+	state.HoleCards = make([]domain.SeatCards, 0, len(hole))
+	for _, seatNo := range ordered {
+		state.HoleCards = append(state.HoleCards, domain.SeatCards{
+			SeatNo: seatNo,
+			Cards:  append([]domain.Card(nil), hole[seatNo]...),
+		})
 	}
-	return out
+	return state, nil
 }
 
-func drawCard(state *domain.HandState) (domain.Card, error) {
+func dealCard(state *domain.HandState, target domain.DealTarget, seatNo domain.SeatNo) (domain.Card, error) {
 	if state.NextCardIndex >= len(state.Deck) {
 		return domain.Card{}, fmt.Errorf("deck exhausted at card index %d", state.NextCardIndex)
 	}
 	card := state.Deck[state.NextCardIndex]
+	state.DealSequence = append(state.DealSequence, domain.DealtCard{
+		DeckIndex: state.NextCardIndex,
+		Target:    target,
+		SeatNo:    seatNo,
+		Card:      card,
+	})
 	state.NextCardIndex++
 	return card, nil
 }
@@ -1,6 +1,8 @@
 package rules
 
 import (
+	"errors"
+	"math/rand"
 	"reflect"
 	"testing"
 
@@ -25,6 +27,72 @@ func TestStandard52DeckHasUniqueCards(t *testing.T) {
 	}
 }
 
+func TestInitHandShufflesTheDeckSpec(t *testing.T) {
+	t.Parallel()
+
+	spec := &domain.DeckSpec{Decks: 2, Remove: []domain.Card{domain.NewCard(14, domain.SuitSpades)}}
+	state := domain.HandState{
+		ButtonSeat: 1,
+		Street:     domain.StreetPreflop,
+		DeckSpec:   spec,
+		Seats: []domain.SeatState{
+			domain.NewSeatState(1, 100),
+			domain.NewSeatState(2, 100),
+		},
+	}
+	dealer := NewDealer(NewSeededShuffler(244))
+	state, err := dealer.InitHand(state)
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	if len(state.Deck) != 102 {
+		t.Fatalf("expected 102 cards, got %d", len(state.Deck))
+	}
+	copies := 0
+	for _, card := range state.Deck {
+		if card == domain.NewCard(14, domain.SuitSpades) {
+			t.Fatal("expected the ace of spades removed from both decks")
+		}
+		if card == domain.NewCard(14, domain.SuitHearts) {
+			copies++
+		}
+	}
+	if copies != 2 {
+		t.Fatalf("expected two aces of hearts, got %d", copies)
+	}
+	if state, err = dealer.DealPreflop(state); err != nil {
+		t.Fatalf("DealPreflop failed: %v", err)
+	}
+	if _, err := dealer.DealFlopTurnRiver(state); err != nil {
+		t.Fatalf("DealFlopTurnRiver failed: %v", err)
+	}
+}
+
+func TestInitHandCommitsToTheShuffledDeck(t *testing.T) {
+	t.Parallel()
+
+	state, err := NewDealer(NewSeededShuffler(7)).InitHand(domain.HandState{})
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	if len(state.DeckCommitment) != 64 || state.DeckCommitment != CommitDeck(state.Deck) {
+		t.Fatalf("expected the hand to commit to its shuffled deck, got %q", state.DeckCommitment)
+	}
+	other, err := NewDealer(NewSeededShuffler(11)).InitHand(domain.HandState{})
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	if other.DeckCommitment == state.DeckCommitment {
+		t.Fatal("expected different decks to get different commitments")
+	}
+
+	swapped := append([]domain.Card(nil), state.Deck...)
+	swapped[0], swapped[1] = swapped[1], swapped[0]
+	if CommitDeck(swapped) == state.DeckCommitment {
+		t.Fatal("expected reordering the deck to break the commitment")
+	}
+}
+
 func TestSeededShuffleIsDeterministic(t *testing.T) {
 	t.Parallel()
 
@@ -61,6 +129,173 @@ func TestSeededShuffleDiffersBySeed(t *testing.T) {
 	}
 }
 
+func TestDealPreflopDealsOneCardAtATimeLeftOfButton(t *testing.T) {
+	t.Parallel()
+
+	state := domain.HandState{
+		ButtonSeat: 2,
+		Street:     domain.StreetPreflop,
+		Seats: []domain.SeatState{
+			domain.NewSeatState(1, 100),
+			domain.NewSeatState(2, 100),
+			domain.NewSeatState(3, 100),
+		},
+	}
+	dealer := NewDealer(NewSeededShuffler(3))
+	state, err := dealer.InitHand(state)
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	state, err = dealer.DealPreflop(state)
+	if err != nil {
+		t.Fatalf("DealPreflop failed: %v", err)
+	}
+
+	wantSeats := []domain.SeatNo{3, 1, 2, 3, 1, 2}
+	if len(state.DealSequence) != len(wantSeats) {
+		t.Fatalf("expected %d dealt cards, got %d", len(wantSeats), len(state.DealSequence))
+	}
+	for i, dealt := range state.DealSequence {
+		if dealt.DeckIndex != i || dealt.Target != domain.DealTargetHole || dealt.SeatNo != wantSeats[i] {
+			t.Fatalf("deal %d: expected deck index %d to seat %d, got %+v", i, i, wantSeats[i], dealt)
+		}
+		if dealt.Card != state.Deck[i] {
+			t.Fatalf("deal %d: expected card %+v, got %+v", i, state.Deck[i], dealt.Card)
+		}
+	}
+	for _, seatCards := range state.HoleCards {
+		if seatCards.SeatNo == 3 && (seatCards.Cards[0] != state.Deck[0] || seatCards.Cards[1] != state.Deck[3]) {
+			t.Fatalf("expected seat 3 to hold deck cards 0 and 3, got %+v", seatCards.Cards)
+		}
+	}
+
+	state, err = dealer.DealFlopTurnRiver(state)
+	if err != nil {
+		t.Fatalf("DealFlopTurnRiver failed: %v", err)
+	}
+	wantTargets := []domain.DealTarget{domain.DealTargetBurn, domain.DealTargetBoard, domain.DealTargetBoard, domain.DealTargetBoard}
+	flop := state.DealSequence[len(wantSeats):]
+	if len(flop) != len(wantTargets) {
+		t.Fatalf("expected burn and three flop cards, got %+v", flop)
+	}
+	for i, dealt := range flop {
+		if dealt.Target != wantTargets[i] || dealt.DeckIndex != len(wantSeats)+i {
+			t.Fatalf("flop deal %d: unexpected entry %+v", i, dealt)
+		}
+	}
+}
+
+func TestDealStreetMovesTheBoardOneStreetAtATime(t *testing.T) {
+	t.Parallel()
+
+	state := domain.HandState{
+		ButtonSeat: 1,
+		Street:     domain.StreetPreflop,
+		Seats:      []domain.SeatState{domain.NewSeatState(1, 100), domain.NewSeatState(2, 100)},
+	}
+	dealer := NewDealer(NewRandShuffler(rand.New(rand.NewSource(8))))
+	state, err := dealer.InitHand(state)
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	if state, err = dealer.DealPreflop(state); err != nil {
+		t.Fatalf("DealPreflop failed: %v", err)
+	}
+	if _, err := dealer.DealStreet(state, domain.StreetTurn); !errors.Is(err, ErrWrongStreet) {
+		t.Fatalf("expected the turn before the flop to fail, got %v", err)
+	}
+
+	for _, tc := range []struct {
+		street domain.Street
+		board  int
+	}{
+		{street: domain.StreetFlop, board: 3},
+		{street: domain.StreetTurn, board: 4},
+		{street: domain.StreetRiver, board: 5},
+	} {
+		burnIdx := state.NextCardIndex
+		if state, err = dealer.DealStreet(state, tc.street); err != nil {
+			t.Fatalf("DealStreet(%s) failed: %v", tc.street, err)
+		}
+		if state.Street != tc.street || len(state.Board) != tc.board {
+			t.Fatalf("expected the %s with %d board cards, got %s with %v", tc.street, tc.board, state.Street, state.Board)
+		}
+		if burn := state.DealSequence[burnIdx]; burn.Target != domain.DealTargetBurn || burn.DeckIndex != burnIdx {
+			t.Fatalf("expected the %s to start with a burn at deck index %d, got %+v", tc.street, burnIdx, burn)
+		}
+	}
+	if _, err := dealer.DealStreet(state, domain.StreetRiver); !errors.Is(err, ErrWrongStreet) {
+		t.Fatalf("expected a second river to fail, got %v", err)
+	}
+
+	seeded, err := NewDealer(NewSeededShuffler(8)).InitHand(domain.HandState{})
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	if !reflect.DeepEqual(seeded.Deck, state.Deck) {
+		t.Fatal("expected a rand shuffler to match the seeded shuffler for the same source")
+	}
+}
+
+func TestDealStreetDealsEveryRunout(t *testing.T) {
+	t.Parallel()
+
+	state := domain.HandState{
+		ButtonSeat: 1,
+		Street:     domain.StreetPreflop,
+		RunItTimes: 3,
+		Seats:      []domain.SeatState{domain.NewSeatState(1, 100), domain.NewSeatState(2, 100)},
+	}
+	dealer := NewDealer(NewSeededShuffler(8))
+	state, err := dealer.InitHand(state)
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	if state, err = dealer.DealPreflop(state); err != nil {
+		t.Fatalf("DealPreflop failed: %v", err)
+	}
+	if state, err = dealer.DealStreet(state, domain.StreetFlop); err != nil {
+		t.Fatalf("DealStreet(flop) failed: %v", err)
+	}
+
+	state = StartRunouts(state)
+	if len(state.Runouts) != 2 || !reflect.DeepEqual(state.Runouts[1], state.Board) {
+		t.Fatalf("expected two more boards sharing the flop, got %v", state.Runouts)
+	}
+	if again := StartRunouts(state); !reflect.DeepEqual(again.Runouts, state.Runouts) {
+		t.Fatalf("expected runouts started once, got %v", again.Runouts)
+	}
+	for _, street := range []domain.Street{domain.StreetTurn, domain.StreetRiver} {
+		if state, err = dealer.DealStreet(state, street); err != nil {
+			t.Fatalf("DealStreet(%s) failed: %v", street, err)
+		}
+	}
+
+	seen := map[domain.Card]bool{}
+	for _, board := range append([][]domain.Card{state.Board}, state.Runouts...) {
+		if len(board) != 5 || !reflect.DeepEqual(board[:3], state.Board[:3]) {
+			t.Fatalf("expected every board to finish from the shared flop, got %v", board)
+		}
+		for _, card := range board[3:] {
+			if seen[card] {
+				t.Fatalf("%v dealt to two boards", card)
+			}
+			seen[card] = true
+		}
+	}
+	// The river burns and deals one card per board, the first board first.
+	river := state.DealSequence[len(state.DealSequence)-6:]
+	for i, runout := range []int{0, 0, 2, 2, 3, 3} {
+		target := domain.DealTargetBurn
+		if i%2 == 1 {
+			target = domain.DealTargetBoard
+		}
+		if river[i].Runout != runout || river[i].Target != target {
+			t.Fatalf("expected river card %d to be a %s on runout %d, got %+v", i, target, runout, river[i])
+		}
+	}
+}
+
 func cardKey(card domain.Card) string {
 	return string(card.Suit) + "-" + string(rune(card.Rank))
 }
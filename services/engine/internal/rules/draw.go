@@ -0,0 +1,142 @@
+package rules
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var ErrIllegalDraw = errors.New("illegal draw")
+
+// TripleDrawDraws is the number of draw rounds in a triple draw hand.
+const TripleDrawDraws = 3
+
+// DrawDealer deals draw games: five private cards to each seat and no
+// board. Between betting rounds every seat still in the hand draws in turn.
+type DrawDealer interface {
+	InitHand(state domain.HandState) (domain.HandState, error)
+	DealDrawHands(state domain.HandState) (domain.HandState, error)
+	// Draw plays seatNo's draw, a discard or stand_pat action, dealing a
+	// replacement for every card it throws away.
+	Draw(state domain.HandState, seatNo domain.SeatNo, action domain.Action) (domain.HandState, error)
+}
+
+func NewDrawDealer(shuffler Shuffler) DrawDealer {
+	if shuffler == nil {
+		shuffler = NewCryptoShuffler()
+	}
+	return standardDealer{shuffler: shuffler}
+}
+
+// DrawsBefore is how many draws come before the triple draw betting round
+// played on street. Triple draw reuses the hold'em streets for its four
+// betting rounds: preflop before the first draw, then the flop, turn and
+// river after each draw.
+func DrawsBefore(street domain.Street) int {
+	switch street {
+	case domain.StreetFlop:
+		return 1
+	case domain.StreetTurn:
+		return 2
+	case domain.StreetRiver:
+		return TripleDrawDraws
+	default:
+		return 0
+	}
+}
+
+// TripleDrawBetSize is the fixed bet and raise size of the triple draw
+// betting round on street. Triple draw plays fixed limit: the small bet,
+// the big blind, before and after the first draw, and the big bet, twice
+// that, after the second and third.
+func TripleDrawBetSize(street domain.Street, bigBlind domain.Chips) domain.Chips {
+	if DrawsBefore(street) >= 2 {
+		return 2 * bigBlind
+	}
+	return bigBlind
+}
+
+func (d standardDealer) DealDrawHands(state domain.HandState) (domain.HandState, error) {
+	if len(state.Deck) != state.DeckSpec.Size() {
+		return state, fmt.Errorf("cannot deal draw hands: deck size is %d", len(state.Deck))
+	}
+
+	ordered := activeSeatsInDealOrder(state.Seats, state.ButtonSeat)
+	if len(ordered) < 2 {
+		return state, fmt.Errorf("cannot deal draw hands: active seats=%d", len(ordered))
+	}
+
+	return dealHoleCards(state, ordered, 5)
+}
+
+// Draw moves the discards to state.Discards and deals their replacements
+// off the deck in the order they were thrown. When the deck runs out, the
+// discards thrown before this seat's are shuffled into a new stub, appended
+// to the deck so every dealt card keeps its DeckIndex, and no seat draws
+// its own discards back.
+func (d standardDealer) Draw(state domain.HandState, seatNo domain.SeatNo, action domain.Action) (domain.HandState, error) {
+	seatIdx := slices.IndexFunc(state.Seats, func(seat domain.SeatState) bool { return seat.SeatNo == seatNo })
+	if seatIdx < 0 || !state.Seats[seatIdx].IsActive() {
+		return state, fmt.Errorf("%w: seat %d is not in the hand", ErrIllegalDraw, seatNo)
+	}
+	handIdx := slices.IndexFunc(state.HoleCards, func(seatCards domain.SeatCards) bool { return seatCards.SeatNo == seatNo })
+	if handIdx < 0 {
+		return state, fmt.Errorf("%w: seat %d holds no cards", ErrIllegalDraw, seatNo)
+	}
+
+	switch action.Kind {
+	case domain.ActionStandPat:
+		if len(action.Discards) != 0 {
+			return state, fmt.Errorf("%w: standing pat discards nothing, got %d cards", ErrIllegalDraw, len(action.Discards))
+		}
+		return state, nil
+	case domain.ActionDiscard:
+		if len(action.Discards) == 0 {
+			return state, fmt.Errorf("%w: a discard needs at least one card, stand pat instead", ErrIllegalDraw)
+		}
+	default:
+		return state, fmt.Errorf("%w: %s is not a draw", ErrIllegalDraw, action.Kind)
+	}
+
+	kept := slices.Clone(state.HoleCards[handIdx].Cards)
+	for _, card := range action.Discards {
+		at := slices.Index(kept, card)
+		if at < 0 {
+			return state, fmt.Errorf("%w: seat %d does not hold %d of %s", ErrIllegalDraw, seatNo, card.Rank, card.Suit)
+		}
+		kept = slices.Delete(kept, at, at+1)
+	}
+
+	for range action.Discards {
+		if state.NextCardIndex >= len(state.Deck) {
+			if err := d.reshuffleDiscards(&state); err != nil {
+				return state, err
+			}
+		}
+		card, err := dealCard(&state, domain.DealTargetHole, seatNo)
+		if err != nil {
+			return state, err
+		}
+		kept = append(kept, card)
+	}
+
+	state.HoleCards = slices.Clone(state.HoleCards)
+	state.HoleCards[handIdx] = domain.SeatCards{SeatNo: seatNo, Cards: kept}
+	state.Discards = append(slices.Clone(state.Discards), action.Discards...)
+	return state, nil
+}
+
+func (d standardDealer) reshuffleDiscards(state *domain.HandState) error {
+	if len(state.Discards) == 0 {
+		return fmt.Errorf("%w: deck and discards exhausted at card index %d", ErrIllegalDraw, state.NextCardIndex)
+	}
+	stub := slices.Clone(state.Discards)
+	if err := d.shuffler.Shuffle(stub); err != nil {
+		return err
+	}
+	state.Deck = append(slices.Clone(state.Deck), stub...)
+	state.Discards = nil
+	return nil
+}
@@ -0,0 +1,122 @@
+package rules
+
+import (
+	"errors"
+	"slices"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestDrawDealer_DealsFiveCardsAndDraws(t *testing.T) {
+	t.Parallel()
+
+	dealer := NewDrawDealer(NewSeededShuffler(264))
+	state, err := dealer.InitHand(domain.HandState{
+		ButtonSeat: 1,
+		Street:     domain.StreetPreflop,
+		Seats: []domain.SeatState{
+			domain.NewSeatState(1, 100),
+			domain.NewSeatState(2, 100),
+			domain.NewSeatState(3, 100),
+		},
+	})
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	state, err = dealer.DealDrawHands(state)
+	if err != nil {
+		t.Fatalf("DealDrawHands failed: %v", err)
+	}
+	if len(state.HoleCards) != 3 || len(state.DealSequence) != 15 || state.NextCardIndex != 15 {
+		t.Fatalf("expected five cards to each of three seats, got %+v", state.HoleCards)
+	}
+	if first := state.HoleCards[0]; first.SeatNo != 2 || first.Cards[1] != state.Deck[3] {
+		t.Fatalf("expected cards dealt one at a time from left of the button, got %+v", state.HoleCards)
+	}
+
+	hand := state.HoleCards[0].Cards
+	drawn, err := dealer.Draw(state, 2, domain.Action{Kind: domain.ActionDiscard, Discards: []domain.Card{hand[4], hand[1]}})
+	if err != nil {
+		t.Fatalf("Draw failed: %v", err)
+	}
+	want := []domain.Card{hand[0], hand[2], hand[3], state.Deck[15], state.Deck[16]}
+	if got := drawn.HoleCards[0].Cards; !slices.Equal(got, want) {
+		t.Fatalf("expected %v kept and two replacements, got %v", want, got)
+	}
+	if !slices.Equal(drawn.Discards, []domain.Card{hand[4], hand[1]}) || drawn.NextCardIndex != 17 {
+		t.Fatalf("expected the discards mucked and two cards dealt, got %v at %d", drawn.Discards, drawn.NextCardIndex)
+	}
+	if !slices.Equal(state.HoleCards[0].Cards, hand) || state.Discards != nil {
+		t.Fatal("expected Draw not to write through to the hand it was given")
+	}
+
+	if pat, err := dealer.Draw(state, 3, domain.Action{Kind: domain.ActionStandPat}); err != nil || pat.NextCardIndex != 15 {
+		t.Fatalf("expected standing pat to deal nothing, got %d, %v", pat.NextCardIndex, err)
+	}
+	for name, action := range map[string]domain.Action{
+		"card not held":      {Kind: domain.ActionDiscard, Discards: []domain.Card{state.Deck[20]}},
+		"empty discard":      {Kind: domain.ActionDiscard},
+		"stand pat discards": {Kind: domain.ActionStandPat, Discards: hand[:1]},
+		"betting action":     {Kind: domain.ActionCheck},
+	} {
+		if _, err := dealer.Draw(state, 2, action); !errors.Is(err, ErrIllegalDraw) {
+			t.Fatalf("%s: expected ErrIllegalDraw, got %v", name, err)
+		}
+	}
+}
+
+func TestDrawDealer_ReshufflesDiscardsWhenTheDeckRunsOut(t *testing.T) {
+	t.Parallel()
+
+	deck := domain.Standard52Deck().Cards
+	hand := deck[:5]
+	mucked := deck[5:7]
+	state := domain.HandState{
+		Deck:          deck,
+		NextCardIndex: 51,
+		Discards:      mucked,
+		HoleCards:     []domain.SeatCards{{SeatNo: 1, Cards: hand}},
+		Seats:         []domain.SeatState{domain.NewSeatState(1, 100)},
+	}
+
+	drawn, err := NewDrawDealer(NewSeededShuffler(7)).Draw(state, 1, domain.Action{Kind: domain.ActionDiscard, Discards: hand[:3]})
+	if err != nil {
+		t.Fatalf("Draw failed: %v", err)
+	}
+	if len(drawn.Deck) != 54 || drawn.NextCardIndex != 54 {
+		t.Fatalf("expected the two old discards appended as a new stub, got %d cards at %d", len(drawn.Deck), drawn.NextCardIndex)
+	}
+	got := drawn.HoleCards[0].Cards
+	for _, card := range append(slices.Clone(mucked), deck[51]) {
+		if !slices.Contains(got, card) {
+			t.Fatalf("expected %v drawn from the deck and the reshuffled stub, got %v", card, got)
+		}
+	}
+	if !slices.Equal(drawn.Discards, hand[:3]) {
+		t.Fatalf("expected only this draw's discards left mucked, got %v", drawn.Discards)
+	}
+
+	state.Discards = nil
+	if _, err := NewDrawDealer(nil).Draw(state, 1, domain.Action{Kind: domain.ActionDiscard, Discards: hand[:2]}); !errors.Is(err, ErrIllegalDraw) {
+		t.Fatalf("expected ErrIllegalDraw with nothing left to draw, got %v", err)
+	}
+}
+
+func TestTripleDrawBetSize_DoublesAfterTheSecondDraw(t *testing.T) {
+	t.Parallel()
+
+	for street, want := range map[domain.Street]domain.Chips{
+		domain.StreetPreflop: 100,
+		domain.StreetFlop:    100,
+		domain.StreetTurn:    200,
+		domain.StreetRiver:   200,
+	} {
+		if got := TripleDrawBetSize(street, 100); got != want {
+			t.Fatalf("%s: expected %d, got %d", street, want, got)
+		}
+	}
+	if DrawsBefore(domain.StreetRiver) != TripleDrawDraws {
+		t.Fatalf("expected the river round after all %d draws", TripleDrawDraws)
+	}
+}
@@ -18,6 +18,8 @@ const (
 	HandCategoryFullHouse
 	HandCategoryFourOfAKind
 	HandCategoryStraightFlush
+	// HandCategoryFiveOfAKind only occurs with a multi-deck DeckSpec.
+	HandCategoryFiveOfAKind
 )
 
 type HandRank struct {
@@ -31,7 +33,7 @@ func EvaluateBestHand(hole []domain.Card, board []domain.Card) HandRank {
 		return HandRank{}
 	}
 
-	indices := combinations(len(all), 5)
+	indices := fiveCardCombinations(len(all))
 	best := evaluateFiveCards([]domain.Card{all[indices[0][0]], all[indices[0][1]], all[indices[0][2]], all[indices[0][3]], all[indices[0][4]]})
 	for _, c := range indices[1:] {
 		candidate := evaluateFiveCards([]domain.Card{all[c[0]], all[c[1]], all[c[2]], all[c[3]], all[c[4]]})
@@ -42,6 +44,22 @@ func EvaluateBestHand(hole []domain.Card, board []domain.Card) HandRank {
 	return best
 }
 
+// EvaluateBestOfSeven is EvaluateBestHand for a full showdown hand: two
+// hole cards and a five-card board, best five of seven.
+func EvaluateBestOfSeven(cards [7]domain.Card) HandRank {
+	return EvaluateBestHand(cards[:2], cards[2:])
+}
+
+// IsRoyalFlush reports whether the rank is an ace-high straight flush. It is
+// not a category of its own: it is simply the best straight flush, so it
+// needs no special case when ranks are compared.
+func (r HandRank) IsRoyalFlush() bool {
+	return r.Category == HandCategoryStraightFlush && len(r.Tiebreak) > 0 && r.Tiebreak[0] == 14
+}
+
+// CompareHandRank totally orders ranks: by category, then tiebreak ranks in
+// order. It returns a positive number when a is the better hand and zero
+// for a split.
 func CompareHandRank(a HandRank, b HandRank) int {
 	if a.Category > b.Category {
 		return 1
@@ -86,6 +104,9 @@ func evaluateFiveCards(cards []domain.Card) HandRank {
 	isFlush := len(suits) == 1
 	straightHigh, isStraight := straightHighRank(ranks)
 
+	if len(rankCounts) == 1 {
+		return HandRank{Category: HandCategoryFiveOfAKind, Tiebreak: []uint8{ranks[0]}}
+	}
 	if isFlush && isStraight {
 		return HandRank{Category: HandCategoryStraightFlush, Tiebreak: []uint8{straightHigh}}
 	}
@@ -176,6 +197,19 @@ func straightHighRank(ranks []uint8) (uint8, bool) {
 	return unique[0], true
 }
 
+// sevenCardCombinations is combinations(7, 5), built once for the full
+// hold'em hands that equity runouts evaluate by the thousand.
+var sevenCardCombinations = combinations(7, 5)
+
+// fiveCardCombinations is combinations(n, 5), shared rather than rebuilt
+// for seven cards. Callers must not modify it.
+func fiveCardCombinations(n int) [][]int {
+	if n == 7 {
+		return sevenCardCombinations
+	}
+	return combinations(n, 5)
+}
+
 func combinations(n int, choose int) [][]int {
 	out := make([][]int, 0)
 	combo := make([]int, choose)
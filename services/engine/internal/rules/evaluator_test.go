@@ -106,6 +106,19 @@ func TestEvaluateBestHand_BroadwayStraight(t *testing.T) {
 	}
 }
 
+func TestEvaluateBestHand_FiveOfAKindFromMultipleDecks(t *testing.T) {
+	t.Parallel()
+
+	rank := EvaluateBestHand(cards(t, "As", "As"), cards(t, "Ah", "Ad", "Ac", "Ks", "Qs"))
+	if rank.Category != HandCategoryFiveOfAKind || rank.Tiebreak[0] != 14 {
+		t.Fatalf("expected five aces, got %+v", rank)
+	}
+	royal := EvaluateBestHand(cards(t, "As", "Ks"), cards(t, "Qs", "Js", "Ts", "2d", "3c"))
+	if CompareHandRank(rank, royal) <= 0 {
+		t.Fatal("expected five of a kind to beat a royal flush")
+	}
+}
+
 func TestCompareHandRank_TieBreakers(t *testing.T) {
 	t.Parallel()
 
@@ -139,6 +152,29 @@ func TestCompareHandRank_BoardPlayedTie(t *testing.T) {
 	}
 }
 
+func TestEvaluateBestOfSeven_PicksTheBestFiveAndSpotsRoyals(t *testing.T) {
+	t.Parallel()
+
+	var seven [7]domain.Card
+	copy(seven[:], cards(t, "As", "Ks", "Qs", "Js", "Ts", "9s", "2d"))
+	royal := EvaluateBestOfSeven(seven)
+	if !royal.IsRoyalFlush() {
+		t.Fatalf("expected a royal flush, got %+v", royal)
+	}
+
+	copy(seven[:], cards(t, "9s", "2d", "Ks", "Qs", "Js", "Ts", "8s"))
+	kingHigh := EvaluateBestOfSeven(seven)
+	if kingHigh.Category != HandCategoryStraightFlush || kingHigh.IsRoyalFlush() {
+		t.Fatalf("expected a king-high straight flush, got %+v", kingHigh)
+	}
+	if CompareHandRank(royal, kingHigh) <= 0 || CompareHandRank(kingHigh, royal) >= 0 {
+		t.Fatal("expected the royal flush to beat the king-high straight flush")
+	}
+	if want := EvaluateBestHand(seven[:2], seven[2:]); CompareHandRank(kingHigh, want) != 0 {
+		t.Fatalf("expected EvaluateBestOfSeven to match EvaluateBestHand, got %+v and %+v", kingHigh, want)
+	}
+}
+
 func cards(t *testing.T, values ...string) []domain.Card {
 	t.Helper()
 	out := make([]domain.Card, 0, len(values))
@@ -0,0 +1,98 @@
+package rules
+
+import (
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// SizingPreset is a named bet or raise amount a client can render as a
+// button. Amounts use the same convention as the action: the bet size for a
+// bet, the raise-to total for a raise.
+type SizingPreset struct {
+	Label  string       `json:"label"`
+	Amount domain.Chips `json:"amount"`
+}
+
+// SizingHints describes the bet or raise control for the acting seat. Step is
+// the slider increment; Min and Max are always selectable even when they are
+// not a multiple of Step.
+type SizingHints struct {
+	Action    domain.ActionKind `json:"action"`
+	Min       domain.Chips      `json:"min"`
+	Max       domain.Chips      `json:"max"`
+	Step      domain.Chips      `json:"step"`
+	AllInOnly bool              `json:"all_in_only"`
+	Presets   []SizingPreset    `json:"presets"`
+}
+
+var potFractions = []struct {
+	label    string
+	num, den domain.Chips
+}{
+	{label: "third_pot", num: 1, den: 3},
+	{label: "half_pot", num: 1, den: 2},
+	{label: "pot", num: 1, den: 1},
+}
+
+// ActionSizingHints returns slider bounds and pot-fraction presets for a
+// seat, or false when it cannot bet or raise. On a pot-limit table Max stops
+// at the pot limit, and the last preset is "pot" rather than "all_in" when
+// the stack is deeper than that. A fixed-limit table's only size is its
+// next bet, offered as "limit".
+func ActionSizingHints(state domain.HandState, seatNo domain.SeatNo) (SizingHints, bool) {
+	var acting domain.SeatState
+	found := false
+	for _, seat := range state.Seats {
+		if seat.SeatNo == seatNo {
+			acting, found = seat, true
+			break
+		}
+	}
+	if !found || !acting.IsActive() || acting.Stack == 0 || state.RaiseCapReached() {
+		return SizingHints{}, false
+	}
+
+	toCall := domain.Chips(0)
+	if state.CurrentBet > acting.CommittedInRound {
+		toCall = state.CurrentBet - acting.CommittedInRound
+	}
+	if acting.Stack <= toCall {
+		return SizingHints{}, false
+	}
+
+	hints := SizingHints{Action: domain.ActionRaise, Step: max(state.SmallBlind, 1)}
+	// A pot-sized raise first calls, then raises by the pot after the call.
+	base, potAfterCall := state.CurrentBet, state.Pot+toCall
+	hints.Max = acting.CommittedInRound + acting.Stack
+	if state.CurrentBet == 0 {
+		hints.Action = domain.ActionBet
+		base, potAfterCall = 0, state.Pot
+		hints.Max = acting.Stack
+	}
+	allIn, limitLabel := hints.Max, "pot"
+	if state.BettingStructure == domain.BettingFixedLimit {
+		hints.Max, limitLabel = min(hints.Max, state.MinRaiseTo), "limit"
+	} else if limit, ok := state.PotLimit(acting); ok {
+		hints.Max = min(hints.Max, max(limit, state.MinRaiseTo))
+	}
+	hints.Min = min(state.MinRaiseTo, hints.Max)
+	if hints.Min == hints.Max && hints.Max == allIn {
+		hints.AllInOnly = true
+	}
+
+	hints.Presets = make([]SizingPreset, 0, len(potFractions)+1)
+	if !hints.AllInOnly {
+		for _, fraction := range potFractions {
+			amount := base + potAfterCall*fraction.num/fraction.den
+			if amount < hints.Min || amount >= hints.Max {
+				continue
+			}
+			hints.Presets = append(hints.Presets, SizingPreset{Label: fraction.label, Amount: amount})
+		}
+	}
+	maxLabel := "all_in"
+	if hints.Max < allIn {
+		maxLabel = limitLabel
+	}
+	hints.Presets = append(hints.Presets, SizingPreset{Label: maxLabel, Amount: hints.Max})
+	return hints, true
+}
@@ -0,0 +1,125 @@
+package rules
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestActionSizingHints(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		name       string
+		pot        domain.Chips
+		currentBet domain.Chips
+		minRaiseTo domain.Chips
+		stack      domain.Chips
+		committed  domain.Chips
+		structure  domain.BettingStructure
+		want       SizingHints
+	}{
+		{
+			name:       "postflop bet offers pot fractions",
+			pot:        600,
+			minRaiseTo: 100,
+			stack:      5000,
+			want: SizingHints{Action: domain.ActionBet, Min: 100, Max: 5000, Step: 50, Presets: []SizingPreset{
+				{Label: "third_pot", Amount: 200},
+				{Label: "half_pot", Amount: 300},
+				{Label: "pot", Amount: 600},
+				{Label: "all_in", Amount: 5000},
+			}},
+		},
+		{
+			name:       "preflop raise sizes the pot after calling",
+			pot:        150,
+			currentBet: 100,
+			minRaiseTo: 200,
+			stack:      10000,
+			want: SizingHints{Action: domain.ActionRaise, Min: 200, Max: 10000, Step: 50, Presets: []SizingPreset{
+				{Label: "half_pot", Amount: 225},
+				{Label: "pot", Amount: 350},
+				{Label: "all_in", Amount: 10000},
+			}},
+		},
+		{
+			name:       "short stack can only raise all-in",
+			pot:        150,
+			currentBet: 100,
+			minRaiseTo: 200,
+			stack:      120,
+			committed:  30,
+			want: SizingHints{Action: domain.ActionRaise, Min: 150, Max: 150, Step: 50, AllInOnly: true, Presets: []SizingPreset{
+				{Label: "all_in", Amount: 150},
+			}},
+		},
+		{
+			name:       "pot limit stops at a pot-sized raise",
+			pot:        150,
+			currentBet: 100,
+			minRaiseTo: 200,
+			stack:      10000,
+			structure:  domain.BettingPotLimit,
+			want: SizingHints{Action: domain.ActionRaise, Min: 200, Max: 350, Step: 50, Presets: []SizingPreset{
+				{Label: "half_pot", Amount: 225},
+				{Label: "pot", Amount: 350},
+			}},
+		},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+
+			state := hintState(t, tc.pot, tc.currentBet, tc.minRaiseTo)
+			state.Seats[0].Stack = tc.stack
+			state.Seats[0].CommittedInRound = tc.committed
+			state.BettingStructure = tc.structure
+
+			got, ok := ActionSizingHints(state, mustSeatNo(t, 1))
+			if !ok {
+				t.Fatal("expected sizing hints")
+			}
+			if !reflect.DeepEqual(got, tc.want) {
+				t.Fatalf("unexpected hints\nwant=%+v\ngot=%+v", tc.want, got)
+			}
+		})
+	}
+}
+
+func TestActionSizingHints_NoneWhenSeatCannotRaise(t *testing.T) {
+	t.Parallel()
+
+	coveredByCall := hintState(t, 300, 200, 300)
+	coveredByCall.Seats[0].Stack = 200
+	if _, ok := ActionSizingHints(coveredByCall, mustSeatNo(t, 1)); ok {
+		t.Fatal("expected no hints when calling uses the whole stack")
+	}
+
+	capped := hintState(t, 300, 200, 300)
+	capped.RaiseCap = 4
+	capped.StreetBets = 4
+	// The cap only applies while more than two players contest the pot.
+	capped.Seats = append(capped.Seats, domain.SeatState{SeatNo: mustSeatNo(t, 3), Stack: 10000, CommittedInRound: 200, Status: domain.SeatStatusActive})
+	if _, ok := ActionSizingHints(capped, mustSeatNo(t, 1)); ok {
+		t.Fatal("expected no hints on a capped street")
+	}
+}
+
+func hintState(t *testing.T, pot, currentBet, minRaiseTo domain.Chips) domain.HandState {
+	t.Helper()
+	return domain.HandState{
+		SmallBlind: 50,
+		BigBlind:   100,
+		Pot:        pot,
+		CurrentBet: currentBet,
+		MinRaiseTo: minRaiseTo,
+		ActingSeat: mustSeatNo(t, 1),
+		Seats: []domain.SeatState{
+			{SeatNo: mustSeatNo(t, 1), Stack: 10000, Status: domain.SeatStatusActive},
+			{SeatNo: mustSeatNo(t, 2), Stack: 10000, CommittedInRound: currentBet, Status: domain.SeatStatusActive},
+		},
+	}
+}
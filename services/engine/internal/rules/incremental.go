@@ -0,0 +1,64 @@
+package rules
+
+import "github.com/imaddar/poker-arena/services/engine/internal/domain"
+
+// PartialHand is the best five-card hand among the cards a seat can use so
+// far. Adding a card only evaluates the five-card hands that use it: a turn
+// card costs 5 evaluations instead of 6, a river card 15 instead of 21, and
+// a turn evaluation is shared by every river dealt after it. Simulations
+// that iterate many runouts over a fixed flop build one PartialHand per seat
+// and extend it down each branch.
+//
+// A PartialHand is immutable; Add returns a new one, so branches can share
+// their parent.
+type PartialHand struct {
+	cards []domain.Card
+	best  HandRank
+}
+
+// NewPartialHand evaluates hole cards and the board dealt so far.
+func NewPartialHand(hole []domain.Card, board []domain.Card) PartialHand {
+	var hand PartialHand
+	for _, card := range hole {
+		hand = hand.Add(card)
+	}
+	for _, card := range board {
+		hand = hand.Add(card)
+	}
+	return hand
+}
+
+// Add returns the hand with one more card available.
+func (p PartialHand) Add(card domain.Card) PartialHand {
+	next := PartialHand{cards: make([]domain.Card, 0, len(p.cards)+1)}
+	next.cards = append(append(next.cards, p.cards...), card)
+	switch {
+	case len(next.cards) < 5:
+		return next
+	case len(next.cards) == 5:
+		next.best = evaluateFiveCards(next.cards)
+		return next
+	}
+
+	next.best = p.best
+	for _, c := range combinations(len(p.cards), 4) {
+		candidate := evaluateFiveCards([]domain.Card{p.cards[c[0]], p.cards[c[1]], p.cards[c[2]], p.cards[c[3]], card})
+		if CompareHandRank(candidate, next.best) > 0 {
+			next.best = candidate
+		}
+	}
+	return next
+}
+
+// Best is the best five-card hand so far, or the zero HandRank while fewer
+// than five cards are known.
+func (p PartialHand) Best() HandRank {
+	return p.best
+}
+
+func rankFor(ranks map[domain.SeatNo]HandRank, seatNo domain.SeatNo, hole []domain.Card, board []domain.Card) HandRank {
+	if rank, ok := ranks[seatNo]; ok {
+		return rank
+	}
+	return EvaluateBestHand(hole, board)
+}
@@ -0,0 +1,77 @@
+package rules
+
+import (
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestPartialHand_MatchesFullEvaluationOnEveryStreet(t *testing.T) {
+	t.Parallel()
+
+	shuffler := NewSeededShuffler(239)
+	for deal := 0; deal < 500; deal++ {
+		deck := domain.Standard52Deck().Cards
+		if err := shuffler.Shuffle(deck); err != nil {
+			t.Fatalf("Shuffle failed: %v", err)
+		}
+		hole, board := deck[:2], deck[2:7]
+
+		hand := NewPartialHand(hole, board[:3])
+		for street := 3; ; street++ {
+			want := EvaluateBestHand(hole, board[:street])
+			if got := hand.Best(); CompareHandRank(got, want) != 0 {
+				t.Fatalf("deal %d with %d board cards: expected %+v, got %+v", deal, street, want, got)
+			}
+			if street == 5 {
+				break
+			}
+			hand = hand.Add(board[street])
+		}
+	}
+}
+
+func TestPartialHand_BranchesDoNotShareCards(t *testing.T) {
+	t.Parallel()
+
+	flop := NewPartialHand(cards(t, "As", "Ks"), cards(t, "Qs", "Js", "2d"))
+	royal := flop.Add(mustCard(t, "Ts"))
+	brick := flop.Add(mustCard(t, "3c"))
+	if royal.Best().Category != HandCategoryStraightFlush {
+		t.Fatalf("expected a royal flush, got %+v", royal.Best())
+	}
+	if brick.Best().Category != HandCategoryHighCard {
+		t.Fatalf("expected the other branch to stay ace high, got %+v", brick.Best())
+	}
+	if got := NewPartialHand(cards(t, "As", "Ks"), nil).Best(); got.Category != 0 {
+		t.Fatalf("expected no rank before five cards, got %+v", got)
+	}
+}
+
+func TestResolvePotsWithRanks_UsesSuppliedRanks(t *testing.T) {
+	t.Parallel()
+
+	state := showdownState(t, []domain.SeatState{
+		seatWithCommit(t, 1, 900, 100, false),
+		seatWithCommit(t, 2, 900, 100, false),
+	}, []domain.SeatCards{
+		{SeatNo: mustSeatNo(t, 1), Cards: cards(t, "As", "Ah")},
+		{SeatNo: mustSeatNo(t, 2), Cards: cards(t, "Kd", "Kh")},
+	}, cards(t, "2c", "3d", "7h", "9s", "Jc"), mustSeatNo(t, 1), 200)
+
+	ranks := map[domain.SeatNo]HandRank{
+		1: NewPartialHand(cards(t, "As", "Ah"), state.Board).Best(),
+		2: NewPartialHand(cards(t, "Kd", "Kh"), state.Board).Best(),
+	}
+	want, wantAwards, err := ResolvePots(state)
+	if err != nil {
+		t.Fatalf("ResolvePots failed: %v", err)
+	}
+	got, gotAwards, err := ResolvePotsWithRanks(state, ranks)
+	if err != nil {
+		t.Fatalf("ResolvePotsWithRanks failed: %v", err)
+	}
+	if got.Seats[0].Stack != want.Seats[0].Stack || len(gotAwards) != len(wantAwards) || gotAwards[0].Seats[0] != 1 {
+		t.Fatalf("expected the same result as ResolvePots, got %+v", gotAwards)
+	}
+}
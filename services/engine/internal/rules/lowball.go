@@ -0,0 +1,93 @@
+package rules
+
+import (
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// EvaluateDeuceToSeven ranks a five-card deuce-to-seven lowball hand. Aces
+// are always high, so A-2-3-4-5 is ace high rather than a straight, and
+// straights and flushes count against the hand. The rank is the hand's
+// ordinary high-hand rank under those rules; CompareLowballRank orders it.
+// Any other number of cards ranks as the zero HandRank.
+func EvaluateDeuceToSeven(hand []domain.Card) HandRank {
+	if len(hand) != 5 {
+		return HandRank{}
+	}
+	rank := evaluateFiveCards(hand)
+	if len(rank.Tiebreak) == 0 || rank.Tiebreak[0] != 5 {
+		return rank
+	}
+
+	// evaluateFiveCards plays the wheel as a five-high straight.
+	wheel := []uint8{14, 5, 4, 3, 2}
+	switch rank.Category {
+	case HandCategoryStraight:
+		return HandRank{Category: HandCategoryHighCard, Tiebreak: wheel}
+	case HandCategoryStraightFlush:
+		return HandRank{Category: HandCategoryFlush, Tiebreak: wheel}
+	default:
+		return rank
+	}
+}
+
+// CompareLowballRank orders lowball ranks from EvaluateDeuceToSeven or
+// EvaluateAceToFive: the lower hand wins, so 7-5-4-3-2 offsuit is the
+// deuce-to-seven nuts. It returns a positive number when a is the better
+// hand and zero for a split.
+func CompareLowballRank(a HandRank, b HandRank) int {
+	return CompareHandRank(b, a)
+}
+
+// EvaluateAceToFive ranks the best ace-to-five lowball hand among cards,
+// such as a razz seat's seven. Aces are low and straights and flushes do
+// not count, so A-2-3-4-5 is the nuts and pairs are what hurt a hand. The
+// rank orders the hand's pairs and kickers as a high hand would, aces as
+// one; CompareLowballRank orders it. Fewer than five cards rank as the zero
+// HandRank.
+func EvaluateAceToFive(cards []domain.Card) HandRank {
+	if len(cards) < 5 {
+		return HandRank{}
+	}
+
+	var best HandRank
+	for i, c := range combinations(len(cards), 5) {
+		candidate := evaluateAceToFiveCards([]domain.Card{cards[c[0]], cards[c[1]], cards[c[2]], cards[c[3]], cards[c[4]]})
+		if i == 0 || CompareLowballRank(candidate, best) > 0 {
+			best = candidate
+		}
+	}
+	return best
+}
+
+func evaluateAceToFiveCards(cards []domain.Card) HandRank {
+	rankCounts := map[uint8]int{}
+	for _, card := range cards {
+		r := uint8(card.Rank)
+		if r == 14 {
+			r = 1
+		}
+		rankCounts[r]++
+	}
+
+	groups := rankGroups(rankCounts)
+	tiebreak := make([]uint8, 0, len(groups))
+	for _, g := range groups {
+		tiebreak = append(tiebreak, g.rank)
+	}
+	category := HandCategoryHighCard
+	switch {
+	case groups[0].count == 5:
+		category = HandCategoryFiveOfAKind
+	case groups[0].count == 4:
+		category = HandCategoryFourOfAKind
+	case groups[0].count == 3 && groups[1].count == 2:
+		category = HandCategoryFullHouse
+	case groups[0].count == 3:
+		category = HandCategoryThreeOfAKind
+	case groups[0].count == 2 && groups[1].count == 2:
+		category = HandCategoryTwoPair
+	case groups[0].count == 2:
+		category = HandCategoryOnePair
+	}
+	return HandRank{Category: category, Tiebreak: tiebreak}
+}
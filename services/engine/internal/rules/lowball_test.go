@@ -0,0 +1,92 @@
+package rules
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestEvaluateDeuceToSeven_OrdersLowHands(t *testing.T) {
+	t.Parallel()
+
+	// Best first: the wheel is ace high and straights and flushes count.
+	ordered := [][]domain.Card{
+		cards(t, "7s", "5d", "4c", "3h", "2s"),
+		cards(t, "8s", "6d", "4c", "3h", "2s"),
+		cards(t, "Ks", "Qd", "Jc", "9h", "8s"),
+		cards(t, "As", "5d", "4c", "3h", "2s"),
+		cards(t, "2s", "2d", "7c", "5h", "3s"),
+		cards(t, "6s", "5d", "4c", "3h", "2s"),
+		cards(t, "7s", "5s", "4s", "3s", "2s"),
+	}
+	for i := 1; i < len(ordered); i++ {
+		better, worse := EvaluateDeuceToSeven(ordered[i-1]), EvaluateDeuceToSeven(ordered[i])
+		if CompareLowballRank(better, worse) <= 0 || CompareLowballRank(worse, better) >= 0 {
+			t.Fatalf("expected %v to beat %v, got %+v and %+v", ordered[i-1], ordered[i], better, worse)
+		}
+	}
+
+	wheel := EvaluateDeuceToSeven(cards(t, "As", "5s", "4s", "3s", "2s"))
+	if wheel.Category != HandCategoryFlush || wheel.Tiebreak[0] != 14 {
+		t.Fatalf("expected a suited wheel to be an ace-high flush, got %+v", wheel)
+	}
+	if rank := EvaluateDeuceToSeven(cards(t, "7s", "5d", "4c", "3h")); rank.Category != 0 {
+		t.Fatalf("expected a four-card hand to go unranked, got %+v", rank)
+	}
+}
+
+func TestSettleDrawShowdown_LowestHandWins(t *testing.T) {
+	t.Parallel()
+
+	state := showdownState(t, []domain.SeatState{
+		seatWithCommit(t, 1, 900, 100, false),
+		seatWithCommit(t, 2, 900, 100, false),
+	}, []domain.SeatCards{
+		{SeatNo: mustSeatNo(t, 1), Cards: cards(t, "Ks", "Qd", "Jc", "9h", "8s")},
+		{SeatNo: mustSeatNo(t, 2), Cards: cards(t, "6s", "5d", "4c", "3h", "2s")},
+	}, nil, mustSeatNo(t, 1), 200)
+
+	result, err := SettleDrawShowdown(state)
+	if err != nil {
+		t.Fatalf("SettleDrawShowdown failed: %v", err)
+	}
+	if len(result.Awards) != 1 || len(result.Awards[0].Seats) != 1 || result.Awards[0].Seats[0] != 1 || result.Winnings[1] != 200 {
+		t.Fatalf("expected king high to beat the six-high straight, got %+v", result)
+	}
+
+	state.HoleCards[1].Cards = state.HoleCards[1].Cards[:2]
+	if _, err := SettleDrawShowdown(state); err == nil {
+		t.Fatal("expected a two-card hand to be rejected")
+	}
+}
+
+func TestEvaluateAceToFive_PlaysTheBestLowOfSeven(t *testing.T) {
+	t.Parallel()
+
+	wheel := EvaluateAceToFive(cards(t, "Ks", "2d", "As", "3s", "4s", "5s", "2c"))
+	if wheel.Category != HandCategoryHighCard || !slices.Equal(wheel.Tiebreak, []uint8{5, 4, 3, 2, 1}) {
+		t.Fatalf("expected a suited wheel to play as the five-low nuts, got %+v", wheel)
+	}
+
+	// Best first: aces are low and only pairing hurts a hand.
+	ordered := [][]domain.Card{
+		cards(t, "As", "2d", "3c", "4h", "6s"),
+		cards(t, "As", "2d", "3c", "5h", "6s"),
+		cards(t, "8s", "7s", "6s", "5s", "4s"),
+		cards(t, "As", "Ad", "2c", "3h", "4s"),
+		cards(t, "2s", "2d", "Ac", "3h", "4s"),
+		cards(t, "As", "Ad", "2c", "2h", "3s"),
+	}
+	for i := 1; i < len(ordered); i++ {
+		better, worse := EvaluateAceToFive(ordered[i-1]), EvaluateAceToFive(ordered[i])
+		if CompareLowballRank(better, worse) <= 0 {
+			t.Fatalf("expected %v to beat %v, got %+v and %+v", ordered[i-1], ordered[i], better, worse)
+		}
+	}
+
+	paired := EvaluateAceToFive(cards(t, "As", "Ad", "2c", "2h", "3s", "3d", "4c"))
+	if paired.Category != HandCategoryOnePair || !slices.Equal(paired.Tiebreak, []uint8{1, 4, 3, 2}) {
+		t.Fatalf("expected seven cards with three pairs to play a pair of aces, got %+v", paired)
+	}
+}
@@ -2,101 +2,224 @@ package rules
 
 import (
 	"fmt"
-	"sort"
 
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/pot"
+	"github.com/imaddar/poker-arena/services/engine/internal/rake"
 )
 
 func ResolvePots(state domain.HandState) (domain.HandState, []domain.PotAward, error) {
-	if len(state.Board) != 5 {
-		return state, nil, fmt.Errorf("showdown requires 5 board cards, got %d", len(state.Board))
+	return ResolvePotsWithRanks(state, nil)
+}
+
+// ResolvePotsWithRanks is ResolvePots for callers that have already
+// evaluated some seats' hands, such as equity runouts built on PartialHand.
+// Seats missing from ranks are evaluated from their hole cards.
+func ResolvePotsWithRanks(state domain.HandState, ranks map[domain.SeatNo]HandRank) (domain.HandState, []domain.PotAward, error) {
+	result, err := settleShowdown(state, ranks)
+	if err != nil {
+		return state, nil, err
 	}
 
-	holeBySeat := map[domain.SeatNo][]domain.Card{}
-	for _, seatCards := range state.HoleCards {
-		holeBySeat[seatCards.SeatNo] = append([]domain.Card(nil), seatCards.Cards...)
+	seatIdx := make(map[domain.SeatNo]int, len(state.Seats))
+	for i, seat := range state.Seats {
+		seatIdx[seat.SeatNo] = i
+	}
+	for seatNo, chips := range result.Winnings {
+		state.Seats[seatIdx[seatNo]].Stack += chips
 	}
 
-	levels := contributionLevels(state.Seats)
-	if len(levels) == 0 {
-		state.Pot = 0
-		state.ShowdownAwards = nil
-		state.Phase = domain.HandPhaseComplete
-		return state, nil, nil
+	state.Pot = 0
+	state.RakeTaken = result.Rake
+	state.ShowdownAwards = result.Awards
+	if result.Awards != nil {
+		state.ShowdownReveals = sequenceShowdown(state, result.Awards, ranks)
 	}
+	state.Phase = domain.HandPhaseComplete
+	return state, result.Awards, nil
+}
 
-	awards := make([]domain.PotAward, 0, len(levels))
-	prev := uint32(0)
-	for i, level := range levels {
-		if level <= prev {
-			continue
-		}
+// ShowdownResult settles a hand's pots without paying them out. Awards lists
+// each pot with a winner, main pot first; a pot tied between seats is split
+// evenly, with the odd chips going one each to the tied seats closest to the
+// button's left. Winnings is what each winning seat takes across every pot,
+// odd chips included, and Ranks holds the hand of every seat eligible for a
+// pot. Rake is the house's cut under the hand's RakeRule, taken out of the
+// pots before they are split.
+//
+// A hand run out more than once has one RunoutResult per board, the first
+// board first, and its Awards and Winnings add theirs up, each award's
+// reason ending in its run, as in "main_pot_run_2". Ranks is then the
+// hands on the first board.
+type ShowdownResult struct {
+	Awards   []domain.PotAward
+	Winnings map[domain.SeatNo]domain.Chips
+	Ranks    map[domain.SeatNo]HandRank
+	Rake     domain.Chips
+	Runouts  []RunoutResult
+}
 
-		contributors := make([]int, 0, len(state.Seats))
-		for seatIdx, seat := range state.Seats {
-			if seat.TotalCommitted >= level {
-				contributors = append(contributors, seatIdx)
-			}
+// RunoutResult is one board's share of the pots: every pot is split evenly
+// between the runouts, odd chips to the first, and each share goes to the
+// best hands on that board.
+type RunoutResult struct {
+	Board    []domain.Card
+	Awards   []domain.PotAward
+	Winnings map[domain.SeatNo]domain.Chips
+	Ranks    map[domain.SeatNo]HandRank
+}
+
+// SettleShowdown evaluates every seat eligible for a pot in state, which
+// must have its full board, and splits each pot among the best hands.
+// ResolvePots applies the same result to the hand.
+func SettleShowdown(state domain.HandState) (ShowdownResult, error) {
+	return settleShowdown(state, nil)
+}
+
+// SettleDrawShowdown is SettleShowdown for deuce-to-seven draw hands: each
+// seat's five cards are ranked with EvaluateDeuceToSeven and the lowest
+// hand wins. There is no board.
+func SettleDrawShowdown(state domain.HandState) (ShowdownResult, error) {
+	return settlePots(state, 5, CompareLowballRank, func(_ domain.SeatNo, hand []domain.Card) HandRank {
+		return EvaluateDeuceToSeven(hand)
+	})
+}
+
+// SettleRazzShowdown is SettleShowdown for razz: each seat's seven cards
+// are ranked with EvaluateAceToFive and the lowest hand wins.
+func SettleRazzShowdown(state domain.HandState) (ShowdownResult, error) {
+	return settlePots(state, 7, CompareLowballRank, func(_ domain.SeatNo, hand []domain.Card) HandRank {
+		return EvaluateAceToFive(hand)
+	})
+}
+
+func settleShowdown(state domain.HandState, ranks map[domain.SeatNo]HandRank) (ShowdownResult, error) {
+	if len(state.Board) != 5 {
+		return ShowdownResult{}, fmt.Errorf("showdown requires 5 board cards, got %d", len(state.Board))
+	}
+	if len(state.Runouts) > 0 {
+		return settleRunouts(state, ranks)
+	}
+	return settlePots(state, 2, CompareHandRank, func(seatNo domain.SeatNo, hole []domain.Card) HandRank {
+		return rankFor(ranks, seatNo, hole, state.Board)
+	})
+}
+
+// settleRunouts settles a hand run out on the first board and every one of
+// state.Runouts. The rake comes out of the whole pots before they are
+// shared between the boards. ranks only holds for the first board.
+func settleRunouts(state domain.HandState, ranks map[domain.SeatNo]HandRank) (ShowdownResult, error) {
+	boards := append([][]domain.Card{state.Board}, state.Runouts...)
+	manager := newShowdownManager(state)
+	result := ShowdownResult{Winnings: map[domain.SeatNo]domain.Chips{}}
+	result.Rake = manager.Rake(rake.Amount(state, manager.Total()))
+	for run, board := range boards {
+		if len(board) != 5 {
+			return ShowdownResult{}, fmt.Errorf("showdown requires 5 board cards on runout %d, got %d", run+1, len(board))
 		}
-		potAmount := (level - prev) * uint32(len(contributors))
-		prev = level
-		if potAmount == 0 {
-			continue
+		boardRanks := ranks
+		if run > 0 {
+			boardRanks = nil
+		}
+		share := ShowdownResult{Winnings: map[domain.SeatNo]domain.Chips{}, Ranks: make(map[domain.SeatNo]HandRank, len(state.Seats))}
+		err := payPots(&share, state, manager.Share(run, len(boards)), 2, CompareHandRank, func(seatNo domain.SeatNo, hole []domain.Card) HandRank {
+			return rankFor(boardRanks, seatNo, hole, board)
+		})
+		if err != nil {
+			return ShowdownResult{}, err
 		}
 
-		winnerIdxs := make([]int, 0, len(contributors))
-		bestRank := HandRank{}
-		for _, seatIdx := range contributors {
-			seat := state.Seats[seatIdx]
-			if seat.Folded || seat.Status != domain.SeatStatusActive {
-				continue
-			}
-			hole := holeBySeat[seat.SeatNo]
-			if len(hole) != 2 {
-				return state, nil, fmt.Errorf("seat %d missing hole cards", seat.SeatNo)
-			}
-			rank := EvaluateBestHand(hole, state.Board)
-			if len(winnerIdxs) == 0 || CompareHandRank(rank, bestRank) > 0 {
-				bestRank = rank
-				winnerIdxs = []int{seatIdx}
-				continue
-			}
-			if CompareHandRank(rank, bestRank) == 0 {
-				winnerIdxs = append(winnerIdxs, seatIdx)
-			}
+		for i := range share.Awards {
+			share.Awards[i].Reason = fmt.Sprintf("%s_run_%d", share.Awards[i].Reason, run+1)
 		}
-		if len(winnerIdxs) == 0 {
-			continue
+		for seatNo, chips := range share.Winnings {
+			result.Winnings[seatNo] += chips
 		}
-
-		share := potAmount / uint32(len(winnerIdxs))
-		odd := potAmount % uint32(len(winnerIdxs))
-		for _, winner := range winnerIdxs {
-			state.Seats[winner].Stack += share
+		result.Awards = append(result.Awards, share.Awards...)
+		if run == 0 {
+			result.Ranks = share.Ranks
 		}
+		result.Runouts = append(result.Runouts, RunoutResult{
+			Board:    append([]domain.Card(nil), board...),
+			Awards:   share.Awards,
+			Winnings: share.Winnings,
+			Ranks:    share.Ranks,
+		})
+	}
+	return result, nil
+}
 
-		orderedForOdd := orderWinnersForOddChip(state.ButtonSeat, winnerIdxs, state.Seats)
-		for j := uint32(0); j < odd; j++ {
-			state.Seats[orderedForOdd[j]].Stack++
-		}
+// settlePots ranks every seat eligible for a pot from its holeCards private
+// cards and splits each pot among the best hands under compare.
+func settlePots(
+	state domain.HandState,
+	holeCards int,
+	compare func(a HandRank, b HandRank) int,
+	rank func(seatNo domain.SeatNo, hole []domain.Card) HandRank,
+) (ShowdownResult, error) {
+	manager := newShowdownManager(state)
+	result := ShowdownResult{Winnings: map[domain.SeatNo]domain.Chips{}, Ranks: make(map[domain.SeatNo]HandRank, len(state.Seats))}
+	result.Rake = manager.Rake(rake.Amount(state, manager.Total()))
+	if err := payPots(&result, state, manager, holeCards, compare, rank); err != nil {
+		return ShowdownResult{}, err
+	}
+	return result, nil
+}
+
+func newShowdownManager(state domain.HandState) *pot.Manager {
+	manager := pot.NewManager(state.Seats)
+	manager.AddDead(state.DeadMoney)
+	return manager
+}
 
-		winnerSeats := make([]domain.SeatNo, 0, len(winnerIdxs))
-		for _, winner := range winnerIdxs {
-			winnerSeats = append(winnerSeats, state.Seats[winner].SeatNo)
+// payPots splits every pot in manager among the best hands and adds them to
+// result's Awards, Winnings and Ranks.
+func payPots(
+	result *ShowdownResult,
+	state domain.HandState,
+	manager *pot.Manager,
+	holeCards int,
+	compare func(a HandRank, b HandRank) int,
+	rank func(seatNo domain.SeatNo, hole []domain.Card) HandRank,
+) error {
+	holeBySeat := map[domain.SeatNo][]domain.Card{}
+	for _, seatCards := range state.HoleCards {
+		holeBySeat[seatCards.SeatNo] = append([]domain.Card(nil), seatCards.Cards...)
+	}
+
+	pots := manager.Pots()
+	if len(pots) == 0 {
+		return nil
+	}
+
+	for _, p := range pots {
+		for _, seatNo := range p.Eligible {
+			if _, ok := result.Ranks[seatNo]; ok {
+				continue
+			}
+			hole := holeBySeat[seatNo]
+			if len(hole) != holeCards {
+				return fmt.Errorf("seat %d missing hole cards", seatNo)
+			}
+			result.Ranks[seatNo] = rank(seatNo, hole)
 		}
-		sort.Slice(winnerSeats, func(a, b int) bool { return winnerSeats[a] < winnerSeats[b] })
+	}
 
+	payouts := manager.Payouts(state.ButtonSeat, func(a, b domain.SeatNo) int {
+		return compare(result.Ranks[a], result.Ranks[b])
+	})
+	result.Awards = make([]domain.PotAward, 0, len(payouts))
+	for _, payout := range payouts {
+		for seatNo, chips := range payout.Chips {
+			result.Winnings[seatNo] += chips
+		}
 		reason := "main_pot"
-		if i > 0 {
-			reason = fmt.Sprintf("side_pot_%d", i)
+		if payout.Pot > 0 {
+			reason = fmt.Sprintf("side_pot_%d", payout.Pot)
 		}
-		awards = append(awards, domain.PotAward{Amount: potAmount, Seats: winnerSeats, Reason: reason})
+		result.Awards = append(result.Awards, domain.PotAward{Amount: payout.Amount, Seats: payout.Winners, Reason: reason})
 	}
-
-	state.Pot = 0
-	state.ShowdownAwards = awards
-	state.Phase = domain.HandPhaseComplete
-	return state, awards, nil
+	return nil
 }
 
 func AwardUncontested(state domain.HandState) domain.HandState {
@@ -127,7 +250,8 @@ func AwardUncontested(state domain.HandState) domain.HandState {
 		return state
 	}
 
-	amount := state.Pot
+	state.RakeTaken = rake.Amount(state, state.Pot)
+	amount := state.Pot - state.RakeTaken
 	state.Seats[winnerIdx].Stack += amount
 	state.Pot = 0
 	state.ShowdownAwards = []domain.PotAward{{
@@ -138,52 +262,3 @@ func AwardUncontested(state domain.HandState) domain.HandState {
 	state.Phase = domain.HandPhaseComplete
 	return state
 }
-
-func contributionLevels(seats []domain.SeatState) []uint32 {
-	seen := map[uint32]struct{}{}
-	levels := make([]uint32, 0, len(seats))
-	for _, seat := range seats {
-		if seat.TotalCommitted == 0 {
-			continue
-		}
-		if _, ok := seen[seat.TotalCommitted]; ok {
-			continue
-		}
-		seen[seat.TotalCommitted] = struct{}{}
-		levels = append(levels, seat.TotalCommitted)
-	}
-	sort.Slice(levels, func(i, j int) bool { return levels[i] < levels[j] })
-	return levels
-}
-
-func orderWinnersForOddChip(button domain.SeatNo, winnerIdx []int, seats []domain.SeatState) []int {
-	if len(winnerIdx) <= 1 {
-		return winnerIdx
-	}
-	winnerBySeat := make(map[domain.SeatNo]int, len(winnerIdx))
-	orderedSeats := make([]domain.SeatNo, 0, len(winnerIdx))
-	for _, idx := range winnerIdx {
-		seatNo := seats[idx].SeatNo
-		winnerBySeat[seatNo] = idx
-		orderedSeats = append(orderedSeats, seatNo)
-	}
-	sort.Slice(orderedSeats, func(i, j int) bool { return orderedSeats[i] < orderedSeats[j] })
-
-	start := 0
-	for i, seatNo := range orderedSeats {
-		if seatNo > button {
-			start = i
-			break
-		}
-		if i == len(orderedSeats)-1 {
-			start = 0
-		}
-	}
-
-	ordered := make([]int, 0, len(winnerIdx))
-	for i := 0; i < len(orderedSeats); i++ {
-		seatNo := orderedSeats[(start+i)%len(orderedSeats)]
-		ordered = append(ordered, winnerBySeat[seatNo])
-	}
-	return ordered
-}
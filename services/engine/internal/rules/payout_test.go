@@ -62,6 +62,75 @@ func TestResolvePots_SplitPotOddChipLeftOfButton(t *testing.T) {
 	}
 }
 
+func TestSettleShowdown_ReportsPerSeatWinningsWithoutPaying(t *testing.T) {
+	t.Parallel()
+
+	state := showdownState(t, []domain.SeatState{
+		seatWithCommit(t, 1, 900, 101, false),
+		seatWithCommit(t, 2, 900, 101, false),
+		seatWithCommit(t, 3, 900, 101, false),
+	}, []domain.SeatCards{
+		{SeatNo: mustSeatNo(t, 1), Cards: cards(t, "2c", "3d")},
+		{SeatNo: mustSeatNo(t, 2), Cards: cards(t, "2d", "4h")},
+		{SeatNo: mustSeatNo(t, 3), Cards: cards(t, "9c", "8d")},
+	}, cards(t, "As", "Kd", "Qs", "7c", "2s"), mustSeatNo(t, 1), 303)
+
+	result, err := SettleShowdown(state)
+	if err != nil {
+		t.Fatalf("SettleShowdown failed: %v", err)
+	}
+	seat1, seat2, seat3 := mustSeatNo(t, 1), mustSeatNo(t, 2), mustSeatNo(t, 3)
+	if len(result.Winnings) != 2 || result.Winnings[seat1] != 151 || result.Winnings[seat2] != 152 {
+		t.Fatalf("expected the odd chip to seat 2 of a split, got %+v", result.Winnings)
+	}
+	if len(result.Awards) != 1 || result.Awards[0].Amount != 303 || len(result.Awards[0].Seats) != 2 {
+		t.Fatalf("expected one main pot split two ways, got %+v", result.Awards)
+	}
+	if result.Ranks[seat3].Category != HandCategoryHighCard || len(result.Ranks) != 3 {
+		t.Fatalf("expected every eligible seat ranked, got %+v", result.Ranks)
+	}
+	if state.Seats[0].Stack != 900 || state.Pot != 303 {
+		t.Fatalf("expected the hand left unpaid, got %+v", state)
+	}
+}
+
+func TestResolvePots_SplitsEachPotBetweenRunouts(t *testing.T) {
+	t.Parallel()
+
+	state := showdownState(t, []domain.SeatState{
+		seatWithCommit(t, 1, 900, 101, false),
+		seatWithCommit(t, 2, 900, 101, false),
+		seatWithCommit(t, 3, 900, 1, true),
+	}, []domain.SeatCards{
+		{SeatNo: mustSeatNo(t, 1), Cards: cards(t, "As", "Ah")},
+		{SeatNo: mustSeatNo(t, 2), Cards: cards(t, "Kd", "Kh")},
+	}, cards(t, "2c", "3d", "7h", "9s", "Jc"), mustSeatNo(t, 1), 203)
+	state.Runouts = [][]domain.Card{cards(t, "2c", "3d", "7h", "Ks", "4c")}
+
+	result, err := SettleShowdown(state)
+	if err != nil {
+		t.Fatalf("SettleShowdown failed: %v", err)
+	}
+	seat1, seat2 := mustSeatNo(t, 1), mustSeatNo(t, 2)
+	if len(result.Runouts) != 2 || result.Runouts[0].Winnings[seat1] != 102 || result.Runouts[1].Winnings[seat2] != 101 {
+		t.Fatalf("expected each board to win its share, odd chip to the first, got %+v", result.Runouts)
+	}
+	if result.Runouts[1].Ranks[seat2].Category != HandCategoryThreeOfAKind {
+		t.Fatalf("expected the second board ranked on its own cards, got %+v", result.Runouts[1].Ranks)
+	}
+
+	resolved, awards, err := ResolvePots(state)
+	if err != nil {
+		t.Fatalf("ResolvePots failed: %v", err)
+	}
+	if len(awards) != 2 || awards[0].Reason != "main_pot_run_1" || awards[1].Reason != "main_pot_run_2" {
+		t.Fatalf("expected one award per runout, got %+v", awards)
+	}
+	if resolved.Seats[0].Stack != 1002 || resolved.Seats[1].Stack != 1001 || resolved.Pot != 0 {
+		t.Fatalf("expected the pot shared between the runouts, got %+v", resolved.Seats)
+	}
+}
+
 func TestResolvePots_OneSidePot(t *testing.T) {
 	t.Parallel()
 
@@ -108,8 +177,9 @@ func TestResolvePots_MultipleSidePotsAndFoldedExclusion(t *testing.T) {
 		t.Fatalf("ResolvePots failed: %v", err)
 	}
 
-	if len(awards) != 4 {
-		t.Fatalf("expected 4 awards (main+3 sides), got %d", len(awards))
+	// The folded seat's 100 is dead money in the main pot, not a pot of its own.
+	if len(awards) != 3 || awards[0].Amount != 700 || awards[0].Reason != "main_pot" {
+		t.Fatalf("expected a 700 main pot and 2 side pots, got %+v", awards)
 	}
 	if resolved.Seats[0].Stack != 1600 {
 		t.Fatalf("expected strongest live hand to collect all eligible pots, got %d", resolved.Seats[0].Stack)
@@ -119,7 +189,64 @@ func TestResolvePots_MultipleSidePotsAndFoldedExclusion(t *testing.T) {
 	}
 }
 
-func showdownState(t *testing.T, seats []domain.SeatState, hole []domain.SeatCards, board []domain.Card, button domain.SeatNo, pot uint32) domain.HandState {
+func TestResolvePots_LeavesAnUncalledTopSidePotUnraked(t *testing.T) {
+	t.Parallel()
+
+	state := showdownState(t, []domain.SeatState{
+		seatWithCommit(t, 1, 0, 200, false),
+		seatWithCommit(t, 2, 0, 1000, false),
+		seatWithCommit(t, 3, 900, 100, true),
+	}, []domain.SeatCards{
+		{SeatNo: mustSeatNo(t, 1), Cards: cards(t, "As", "Ah")},
+		{SeatNo: mustSeatNo(t, 2), Cards: cards(t, "Kd", "Kh")},
+		{SeatNo: mustSeatNo(t, 3), Cards: cards(t, "Qd", "Qh")},
+	}, cards(t, "2c", "3d", "7h", "9s", "Jc"), mustSeatNo(t, 3), 1300)
+	state.Rake = &domain.RakeRule{BasisPoints: 1000}
+
+	resolved, awards, err := ResolvePots(state)
+	if err != nil {
+		t.Fatalf("ResolvePots failed: %v", err)
+	}
+
+	// Only seat 2 can win the 800 side pot nobody called, so only the 500
+	// main pot is raked.
+	if resolved.RakeTaken != 50 {
+		t.Fatalf("expected 10%% of the 500 called, got rake %d", resolved.RakeTaken)
+	}
+	if len(awards) != 2 || awards[0].Amount != 450 || awards[1].Amount != 800 {
+		t.Fatalf("expected a raked 450 main pot and the whole 800 back, got %+v", awards)
+	}
+	if resolved.Seats[0].Stack != 450 || resolved.Seats[1].Stack != 800 {
+		t.Fatalf("expected stacks 450 and 800, got %d and %d", resolved.Seats[0].Stack, resolved.Seats[1].Stack)
+	}
+}
+
+func TestAwardUncontested_RakesOnlyTheCalledChips(t *testing.T) {
+	t.Parallel()
+
+	state := domain.HandState{
+		Seats: []domain.SeatState{
+			seatWithCommit(t, 1, 700, 300, false),
+			seatWithCommit(t, 2, 900, 100, true),
+		},
+		Pot:   400,
+		Phase: domain.HandPhaseBetting,
+		Rake:  &domain.RakeRule{BasisPoints: 1000},
+	}
+
+	resolved := AwardUncontested(state)
+
+	// The raise to 300 was never called: 200 of it goes back unraked, and
+	// the rake is 10% of the 200 both seats put in.
+	if resolved.RakeTaken != 20 {
+		t.Fatalf("expected 10%% of the 200 called, got rake %d", resolved.RakeTaken)
+	}
+	if len(resolved.ShowdownAwards) != 1 || resolved.ShowdownAwards[0].Amount != 380 || resolved.Seats[0].Stack != 1080 {
+		t.Fatalf("expected seat 1 to take 380, got awards %+v and stack %d", resolved.ShowdownAwards, resolved.Seats[0].Stack)
+	}
+}
+
+func showdownState(t *testing.T, seats []domain.SeatState, hole []domain.SeatCards, board []domain.Card, button domain.SeatNo, pot domain.Chips) domain.HandState {
 	t.Helper()
 	return domain.HandState{
 		ButtonSeat: button,
@@ -131,7 +258,7 @@ func showdownState(t *testing.T, seats []domain.SeatState, hole []domain.SeatCar
 	}
 }
 
-func seatWithCommit(t *testing.T, seat uint8, stack uint32, committed uint32, folded bool) domain.SeatState {
+func seatWithCommit(t *testing.T, seat uint8, stack domain.Chips, committed domain.Chips, folded bool) domain.SeatState {
 	t.Helper()
 	return domain.SeatState{
 		SeatNo:         mustSeatNo(t, seat),
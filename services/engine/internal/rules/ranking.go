@@ -0,0 +1,190 @@
+package rules
+
+import (
+	"errors"
+	"fmt"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// Variant is domain.Variant, named here for the ranking rules.
+type Variant = domain.Variant
+
+const (
+	VariantStandard               = domain.VariantStandard
+	VariantShortDeck              = domain.VariantShortDeck
+	VariantDeuceToSevenTripleDraw = domain.VariantDeuceToSevenTripleDraw
+	VariantRazz                   = domain.VariantRazz
+)
+
+var (
+	ErrUnknownVariant   = domain.ErrUnknownVariant
+	ErrIncompatibleDeck = errors.New("deck spec incompatible with variant")
+)
+
+// RankingRules describes how a variant orders made hands so clients can
+// display or verify the ranking system a table uses.
+type RankingRules struct {
+	Variant        Variant           `json:"variant"`
+	DeckSize       int               `json:"deck_size"`
+	LowestStraight string            `json:"lowest_straight"`
+	Categories     []RankingCategory `json:"categories"`
+}
+
+// Categories are listed strongest first.
+type RankingCategory struct {
+	Category      HandCategory `json:"category"`
+	Name          string       `json:"name"`
+	TiebreakOrder []string     `json:"tiebreak_order"`
+}
+
+var handCategoryNames = map[HandCategory]string{
+	HandCategoryHighCard:      "high_card",
+	HandCategoryOnePair:       "one_pair",
+	HandCategoryTwoPair:       "two_pair",
+	HandCategoryThreeOfAKind:  "three_of_a_kind",
+	HandCategoryStraight:      "straight",
+	HandCategoryFlush:         "flush",
+	HandCategoryFullHouse:     "full_house",
+	HandCategoryFourOfAKind:   "four_of_a_kind",
+	HandCategoryStraightFlush: "straight_flush",
+	HandCategoryFiveOfAKind:   "five_of_a_kind",
+}
+
+var handCategoryTiebreaks = map[HandCategory][]string{
+	HandCategoryHighCard:      {"kicker_1", "kicker_2", "kicker_3", "kicker_4", "kicker_5"},
+	HandCategoryOnePair:       {"pair_rank", "kicker_1", "kicker_2", "kicker_3"},
+	HandCategoryTwoPair:       {"high_pair_rank", "low_pair_rank", "kicker"},
+	HandCategoryThreeOfAKind:  {"trips_rank", "kicker_1", "kicker_2"},
+	HandCategoryStraight:      {"straight_high_card"},
+	HandCategoryFlush:         {"kicker_1", "kicker_2", "kicker_3", "kicker_4", "kicker_5"},
+	HandCategoryFullHouse:     {"trips_rank", "pair_rank"},
+	HandCategoryFourOfAKind:   {"quads_rank", "kicker"},
+	HandCategoryStraightFlush: {"straight_high_card"},
+	HandCategoryFiveOfAKind:   {"quint_rank"},
+}
+
+func (c HandCategory) String() string {
+	if name, ok := handCategoryNames[c]; ok {
+		return name
+	}
+	return fmt.Sprintf("hand_category_%d", uint8(c))
+}
+
+func RankingRulesFor(variant Variant) (RankingRules, error) {
+	switch variant {
+	case VariantStandard:
+		return RankingRules{
+			Variant:        VariantStandard,
+			DeckSize:       52,
+			LowestStraight: "A-2-3-4-5",
+			Categories: rankingCategories(
+				HandCategoryStraightFlush,
+				HandCategoryFourOfAKind,
+				HandCategoryFullHouse,
+				HandCategoryFlush,
+				HandCategoryStraight,
+				HandCategoryThreeOfAKind,
+				HandCategoryTwoPair,
+				HandCategoryOnePair,
+				HandCategoryHighCard,
+			),
+		}, nil
+	case VariantShortDeck:
+		// Short deck strips deuces through fives: flushes become rarer than
+		// full houses and trips rarer than straights.
+		return RankingRules{
+			Variant:        VariantShortDeck,
+			DeckSize:       36,
+			LowestStraight: "A-6-7-8-9",
+			Categories: rankingCategories(
+				HandCategoryStraightFlush,
+				HandCategoryFourOfAKind,
+				HandCategoryFlush,
+				HandCategoryFullHouse,
+				HandCategoryThreeOfAKind,
+				HandCategoryStraight,
+				HandCategoryTwoPair,
+				HandCategoryOnePair,
+				HandCategoryHighCard,
+			),
+		}, nil
+	case VariantDeuceToSevenTripleDraw:
+		// Lowball turns the standard order over: the best hand is the lowest
+		// high card, and the wheel is no straight.
+		return RankingRules{
+			Variant:        VariantDeuceToSevenTripleDraw,
+			DeckSize:       52,
+			LowestStraight: "2-3-4-5-6",
+			Categories: rankingCategories(
+				HandCategoryHighCard,
+				HandCategoryOnePair,
+				HandCategoryTwoPair,
+				HandCategoryThreeOfAKind,
+				HandCategoryStraight,
+				HandCategoryFlush,
+				HandCategoryFullHouse,
+				HandCategoryFourOfAKind,
+				HandCategoryStraightFlush,
+			),
+		}, nil
+	case VariantRazz:
+		// Ace-to-five low ignores straights and flushes, so only the paired
+		// categories rank against the hand.
+		return RankingRules{
+			Variant:        VariantRazz,
+			DeckSize:       52,
+			LowestStraight: "none",
+			Categories: rankingCategories(
+				HandCategoryHighCard,
+				HandCategoryOnePair,
+				HandCategoryTwoPair,
+				HandCategoryThreeOfAKind,
+				HandCategoryFullHouse,
+				HandCategoryFourOfAKind,
+			),
+		}, nil
+	default:
+		return RankingRules{}, fmt.Errorf("%w: %q", ErrUnknownVariant, variant)
+	}
+}
+
+// CheckDeckSpec reports whether variant can be played with spec's deck.
+// Standard hold'em takes any valid spec: removed cards only make some hands
+// impossible, and shuffling decks together adds five of a kind above the
+// straight flush. Short deck needs every deuce through five removed, and the
+// evaluator does not rank short deck hands yet. Triple draw takes any spec
+// as standard play does, since it reshuffles its discards when the deck runs
+// out. Razz needs seven cards for every seat and a burn before each street.
+func CheckDeckSpec(variant Variant, spec *domain.DeckSpec) error {
+	switch variant {
+	case VariantStandard, VariantDeuceToSevenTripleDraw:
+		return nil
+	case VariantRazz:
+		if need := StudCardsNeeded(int(domain.DefaultMaxSeats)); spec.Size() < need {
+			return fmt.Errorf("%w: %s needs %d cards for a full table, the deck has %d", ErrIncompatibleDeck, variant, need, spec.Size())
+		}
+		return nil
+	case VariantShortDeck:
+		for _, card := range spec.Build().Cards {
+			if card.Rank <= 5 {
+				return fmt.Errorf("%w: short deck plays without deuces through fives, found %d of %s", ErrIncompatibleDeck, card.Rank, card.Suit)
+			}
+		}
+		return fmt.Errorf("%w: the evaluator only ranks %s hands", ErrIncompatibleDeck, VariantStandard)
+	default:
+		return fmt.Errorf("%w: %q", ErrUnknownVariant, variant)
+	}
+}
+
+func rankingCategories(order ...HandCategory) []RankingCategory {
+	out := make([]RankingCategory, 0, len(order))
+	for _, category := range order {
+		out = append(out, RankingCategory{
+			Category:      category,
+			Name:          category.String(),
+			TiebreakOrder: append([]string(nil), handCategoryTiebreaks[category]...),
+		})
+	}
+	return out
+}
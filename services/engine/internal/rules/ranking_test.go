@@ -0,0 +1,103 @@
+package rules
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestRankingRulesFor_StandardMatchesEvaluatorOrder(t *testing.T) {
+	t.Parallel()
+
+	rules, err := RankingRulesFor(VariantStandard)
+	if err != nil {
+		t.Fatalf("RankingRulesFor failed: %v", err)
+	}
+	if rules.DeckSize != 52 {
+		t.Fatalf("expected 52-card deck, got %d", rules.DeckSize)
+	}
+	if len(rules.Categories) != int(HandCategoryStraightFlush) {
+		t.Fatalf("expected %d categories, got %d", HandCategoryStraightFlush, len(rules.Categories))
+	}
+	for i := 1; i < len(rules.Categories); i++ {
+		if rules.Categories[i-1].Category <= rules.Categories[i].Category {
+			t.Fatalf("expected strongest-first order matching evaluator, got %v before %v", rules.Categories[i-1].Name, rules.Categories[i].Name)
+		}
+	}
+	for _, category := range rules.Categories {
+		if len(category.TiebreakOrder) == 0 {
+			t.Fatalf("expected tiebreak order for %s", category.Name)
+		}
+	}
+}
+
+func TestRankingRulesFor_ShortDeckRanksFlushAboveFullHouse(t *testing.T) {
+	t.Parallel()
+
+	rules, err := RankingRulesFor(VariantShortDeck)
+	if err != nil {
+		t.Fatalf("RankingRulesFor failed: %v", err)
+	}
+	position := map[HandCategory]int{}
+	for i, category := range rules.Categories {
+		position[category.Category] = i
+	}
+	if position[HandCategoryFlush] >= position[HandCategoryFullHouse] {
+		t.Fatalf("expected flush above full house, got %+v", rules.Categories)
+	}
+	if position[HandCategoryThreeOfAKind] >= position[HandCategoryStraight] {
+		t.Fatalf("expected trips above straight, got %+v", rules.Categories)
+	}
+}
+
+func TestRankingRulesFor_TripleDrawRanksHighCardBest(t *testing.T) {
+	t.Parallel()
+
+	rules, err := RankingRulesFor(VariantDeuceToSevenTripleDraw)
+	if err != nil {
+		t.Fatalf("RankingRulesFor failed: %v", err)
+	}
+	if len(rules.Categories) != int(HandCategoryStraightFlush) {
+		t.Fatalf("expected %d categories, got %d", HandCategoryStraightFlush, len(rules.Categories))
+	}
+	for i := 1; i < len(rules.Categories); i++ {
+		if rules.Categories[i-1].Category >= rules.Categories[i].Category {
+			t.Fatalf("expected the evaluator order reversed, got %v before %v", rules.Categories[i-1].Name, rules.Categories[i].Name)
+		}
+	}
+	if err := CheckDeckSpec(VariantDeuceToSevenTripleDraw, nil); err != nil {
+		t.Fatalf("expected triple draw to play a standard deck, got %v", err)
+	}
+}
+
+func TestRankingRulesFor_UnknownVariantFails(t *testing.T) {
+	t.Parallel()
+
+	if _, err := RankingRulesFor(Variant("badugi")); !errors.Is(err, ErrUnknownVariant) {
+		t.Fatalf("expected ErrUnknownVariant, got %v", err)
+	}
+}
+
+func TestCheckDeckSpec_MatchesSpecToVariant(t *testing.T) {
+	t.Parallel()
+
+	if err := CheckDeckSpec(VariantStandard, &domain.DeckSpec{Decks: 2}); err != nil {
+		t.Fatalf("expected standard play to accept two decks, got %v", err)
+	}
+	if err := CheckDeckSpec(VariantShortDeck, nil); !errors.Is(err, ErrIncompatibleDeck) {
+		t.Fatalf("expected short deck to reject a full deck, got %v", err)
+	}
+	stripped := &domain.DeckSpec{}
+	for _, suit := range []domain.Suit{domain.SuitClubs, domain.SuitDiamonds, domain.SuitHearts, domain.SuitSpades} {
+		for rank := domain.Rank(2); rank <= 5; rank++ {
+			stripped.Remove = append(stripped.Remove, domain.NewCard(rank, suit))
+		}
+	}
+	if err := CheckDeckSpec(VariantShortDeck, stripped); !errors.Is(err, ErrIncompatibleDeck) {
+		t.Fatalf("expected short deck to need its own evaluator, got %v", err)
+	}
+	if err := CheckDeckSpec(Variant("badugi"), nil); !errors.Is(err, ErrUnknownVariant) {
+		t.Fatalf("expected ErrUnknownVariant, got %v", err)
+	}
+}
@@ -0,0 +1,249 @@
+package rules
+
+import (
+	"errors"
+	"fmt"
+	"maps"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var ErrInvalidDealScript = errors.New("invalid deal script")
+
+// DealScriptError pins a bad deal script to the first event that breaks it.
+// Index is the event's position in the script.
+type DealScriptError struct {
+	Index  int
+	Card   domain.Card
+	Reason string
+}
+
+func (e *DealScriptError) Error() string {
+	return fmt.Sprintf("%v: event %d (card %d of %s): %s", ErrInvalidDealScript, e.Index, e.Card.Rank, e.Card.Suit, e.Reason)
+}
+
+func (e *DealScriptError) Unwrap() error {
+	return ErrInvalidDealScript
+}
+
+// CardRepair is a card a repaired script deals in place of one it could not.
+type CardRepair struct {
+	Index int         `json:"index"`
+	From  domain.Card `json:"from"`
+	To    domain.Card `json:"to"`
+}
+
+// ValidateDealScript checks a scripted or imported deal, in the DealSequence
+// form, against the deck spec. Every card must be one the deck holds and not
+// already dealt, and the events must follow the live dealing procedure: deck
+// indexes in order, two hole cards to every seat before the board, and a
+// burn before each of the flop, turn and river, so a board card dealt early
+// is reported rather than shifting the rest of the board.
+func ValidateDealScript(spec *domain.DeckSpec, events []domain.DealtCard) error {
+	if err := validateDealOrder(events); err != nil {
+		return err
+	}
+	if bad, reasons := scriptConflicts(spec, events); len(bad) > 0 {
+		return &DealScriptError{Index: bad[0], Card: events[bad[0]].Card, Reason: reasons[0]}
+	}
+	return nil
+}
+
+// RepairDealScript re-deals every card ValidateDealScript would reject for
+// being impossible or duplicated, drawing replacements from the cards the
+// script leaves in the deck shuffled by seed, so the same script and seed
+// always repair the same way. The result is only an approximation of the
+// original hand. Events out of dealing order cannot be repaired and fail as
+// in ValidateDealScript.
+func RepairDealScript(spec *domain.DeckSpec, events []domain.DealtCard, seed int64) ([]domain.DealtCard, []CardRepair, error) {
+	if err := validateDealOrder(events); err != nil {
+		return nil, nil, err
+	}
+	bad, _ := scriptConflicts(spec, events)
+	repaired := append([]domain.DealtCard(nil), events...)
+	if len(bad) == 0 {
+		return repaired, nil, nil
+	}
+
+	spare := remainingCards(spec, events, bad)
+	if len(spare) < len(bad) {
+		index := bad[len(spare)]
+		return nil, nil, &DealScriptError{Index: index, Card: events[index].Card, Reason: "no card left in the deck to deal instead"}
+	}
+	if err := NewSeededShuffler(seed).Shuffle(spare); err != nil {
+		return nil, nil, err
+	}
+	repairs := make([]CardRepair, 0, len(bad))
+	for i, index := range bad {
+		repaired[index].Card = spare[i]
+		repairs = append(repairs, CardRepair{Index: index, From: events[index].Card, To: spare[i]})
+	}
+	return repaired, repairs, nil
+}
+
+// NewScriptedShuffler deals the script's cards from the top of the deck in
+// event order, followed by the rest of the deck shuffled by seed. Shuffle
+// fails with a DealScriptError if the script does not fit the deck it is
+// given.
+func NewScriptedShuffler(events []domain.DealtCard, seed int64) Shuffler {
+	return scriptedShuffler{events: append([]domain.DealtCard(nil), events...), rest: NewSeededShuffler(seed)}
+}
+
+type scriptedShuffler struct {
+	events []domain.DealtCard
+	rest   Shuffler
+}
+
+func (s scriptedShuffler) Shuffle(cards []domain.Card) error {
+	remaining := make(map[domain.Card]int, len(cards))
+	for _, card := range cards {
+		remaining[card]++
+	}
+	if bad, reasons := cardConflicts(remaining, s.events); len(bad) > 0 {
+		return &DealScriptError{Index: bad[0], Card: s.events[bad[0]].Card, Reason: reasons[0]}
+	}
+
+	rest := make([]domain.Card, 0, len(cards)-len(s.events))
+	for _, card := range cards {
+		if remaining[card] > 0 {
+			remaining[card]--
+			rest = append(rest, card)
+		}
+	}
+	if err := s.rest.Shuffle(rest); err != nil {
+		return err
+	}
+	for i, event := range s.events {
+		cards[i] = event.Card
+	}
+	copy(cards[len(s.events):], rest)
+	return nil
+}
+
+func validateDealOrder(events []domain.DealtCard) error {
+	fail := func(i int, reason string) error {
+		return &DealScriptError{Index: i, Card: events[i].Card, Reason: reason}
+	}
+	hole := make(map[domain.SeatNo]int)
+	// streetCards is how many board cards each street deals after its burn.
+	streetCards := []int{3, 1, 1}
+	burns, board, owed := 0, 0, 0
+	for i, event := range events {
+		if event.DeckIndex != i {
+			return fail(i, fmt.Sprintf("deck index %d out of sequence", event.DeckIndex))
+		}
+		switch event.Target {
+		case domain.DealTargetHole:
+			switch {
+			case event.SeatNo == 0:
+				return fail(i, "hole card dealt to no seat")
+			case burns > 0:
+				return fail(i, fmt.Sprintf("hole card to seat %d after the board was started", event.SeatNo))
+			case hole[event.SeatNo] == 2:
+				return fail(i, fmt.Sprintf("third hole card to seat %d", event.SeatNo))
+			}
+			hole[event.SeatNo]++
+		case domain.DealTargetBurn:
+			if burns == 0 {
+				for _, seatNo := range slices.Sorted(maps.Keys(hole)) {
+					if hole[seatNo] != 2 {
+						return fail(i, fmt.Sprintf("premature burn: seat %d holds one hole card", seatNo))
+					}
+				}
+			}
+			switch {
+			case burns == len(streetCards):
+				return fail(i, "burn after the river")
+			case owed > 0:
+				return fail(i, fmt.Sprintf("burn with %d board card still owed to the street", owed))
+			}
+			owed = streetCards[burns]
+			burns++
+		case domain.DealTargetBoard:
+			if owed == 0 {
+				return fail(i, fmt.Sprintf("premature board card %d: no burn before it", board+1))
+			}
+			owed--
+			board++
+		default:
+			return fail(i, fmt.Sprintf("unknown deal target %q", event.Target))
+		}
+	}
+	return nil
+}
+
+// scriptConflicts lists, in event order, the events whose cards the deck
+// cannot supply, with the reason for each.
+func scriptConflicts(spec *domain.DeckSpec, events []domain.DealtCard) ([]int, []string) {
+	remaining := make(map[domain.Card]int, spec.Size())
+	for _, card := range spec.Build().Cards {
+		remaining[card]++
+	}
+	return cardConflicts(remaining, events)
+}
+
+// cardConflicts consumes each event's card from remaining.
+func cardConflicts(remaining map[domain.Card]int, events []domain.DealtCard) ([]int, []string) {
+	var (
+		bad     []int
+		reasons []string
+	)
+	firstDealt := make(map[domain.Card]int, len(events))
+	for i, event := range events {
+		card := event.Card
+		if remaining[card] > 0 {
+			remaining[card]--
+			if _, ok := firstDealt[card]; !ok {
+				firstDealt[card] = i
+			}
+			continue
+		}
+		bad = append(bad, i)
+		switch first, dealt := firstDealt[card]; {
+		case dealt:
+			reasons = append(reasons, fmt.Sprintf("duplicate card, already dealt at event %d", first))
+		case !isKnownCard(card):
+			reasons = append(reasons, "impossible card")
+		default:
+			reasons = append(reasons, "card is not in the deck")
+		}
+	}
+	return bad, reasons
+}
+
+// remainingCards is the deck in build order less every card the script deals
+// outside bad.
+func remainingCards(spec *domain.DeckSpec, events []domain.DealtCard, bad []int) []domain.Card {
+	skip := make(map[int]bool, len(bad))
+	for _, index := range bad {
+		skip[index] = true
+	}
+	used := make(map[domain.Card]int, len(events))
+	for i, event := range events {
+		if !skip[i] {
+			used[event.Card]++
+		}
+	}
+	var spare []domain.Card
+	for _, card := range spec.Build().Cards {
+		if used[card] > 0 {
+			used[card]--
+			continue
+		}
+		spare = append(spare, card)
+	}
+	return spare
+}
+
+func isKnownCard(card domain.Card) bool {
+	if _, err := domain.NewRank(uint8(card.Rank)); err != nil {
+		return false
+	}
+	switch card.Suit {
+	case domain.SuitClubs, domain.SuitDiamonds, domain.SuitHearts, domain.SuitSpades:
+		return true
+	default:
+		return false
+	}
+}
@@ -0,0 +1,85 @@
+package rules
+
+import (
+	"errors"
+	"strings"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func headsUpScript(cards ...domain.Card) []domain.DealtCard {
+	targets := []struct {
+		target domain.DealTarget
+		seatNo domain.SeatNo
+	}{
+		{domain.DealTargetHole, 2}, {domain.DealTargetHole, 1}, {domain.DealTargetHole, 2}, {domain.DealTargetHole, 1},
+		{domain.DealTargetBurn, 0}, {domain.DealTargetBoard, 0}, {domain.DealTargetBoard, 0}, {domain.DealTargetBoard, 0},
+	}
+	events := make([]domain.DealtCard, 0, len(cards))
+	for i, card := range cards {
+		events = append(events, domain.DealtCard{DeckIndex: i, Target: targets[i].target, SeatNo: targets[i].seatNo, Card: card})
+	}
+	return events
+}
+
+func TestValidateDealScript_NamesTheOffendingEventAndCard(t *testing.T) {
+	t.Parallel()
+
+	ace := domain.NewCard(14, domain.SuitSpades)
+	cards := []domain.Card{
+		ace, domain.NewCard(13, domain.SuitSpades), domain.NewCard(12, domain.SuitSpades), domain.NewCard(11, domain.SuitSpades),
+		domain.NewCard(2, domain.SuitClubs), domain.NewCard(3, domain.SuitClubs), domain.NewCard(4, domain.SuitClubs), domain.NewCard(5, domain.SuitClubs),
+	}
+	if err := ValidateDealScript(nil, headsUpScript(cards...)); err != nil {
+		t.Fatalf("expected a valid script, got %v", err)
+	}
+
+	duplicate := headsUpScript(cards...)
+	duplicate[6].Card = ace
+	impossible := headsUpScript(cards...)
+	impossible[2].Card = domain.NewCard(15, domain.SuitSpades)
+	removed := headsUpScript(cards...)
+	premature := headsUpScript(cards...)
+	premature[4].Target = domain.DealTargetBoard
+	missingHole := headsUpScript(cards...)
+	missingHole[3].Target = domain.DealTargetBurn
+
+	for _, tc := range []struct {
+		name   string
+		spec   *domain.DeckSpec
+		events []domain.DealtCard
+		index  int
+		reason string
+	}{
+		{name: "duplicate", events: duplicate, index: 6, reason: "already dealt at event 0"},
+		{name: "impossible", events: impossible, index: 2, reason: "impossible card"},
+		{name: "removed", spec: &domain.DeckSpec{Remove: []domain.Card{cards[5]}}, events: removed, index: 5, reason: "not in the deck"},
+		{name: "premature board", events: premature, index: 4, reason: "premature board card 1"},
+		{name: "premature burn", events: missingHole, index: 3, reason: "seat 1 holds one hole card"},
+	} {
+		err := ValidateDealScript(tc.spec, tc.events)
+		var scriptErr *DealScriptError
+		if !errors.As(err, &scriptErr) || !errors.Is(err, ErrInvalidDealScript) {
+			t.Fatalf("%s: expected a DealScriptError, got %v", tc.name, err)
+		}
+		if scriptErr.Index != tc.index || scriptErr.Card != tc.events[tc.index].Card || !strings.Contains(err.Error(), tc.reason) {
+			t.Fatalf("%s: expected event %d (%s), got %v", tc.name, tc.index, tc.reason, err)
+		}
+	}
+
+	repaired, repairs, err := RepairDealScript(nil, duplicate, 9)
+	if err != nil || len(repairs) != 1 || repairs[0].Index != 6 || repairs[0].From != ace {
+		t.Fatalf("expected only event 6 repaired, got %+v (%v)", repairs, err)
+	}
+	if err := ValidateDealScript(nil, repaired); err != nil {
+		t.Fatalf("expected the repaired script to validate, got %v", err)
+	}
+	again, _, _ := RepairDealScript(nil, duplicate, 9)
+	if again[6].Card != repaired[6].Card {
+		t.Fatalf("expected the same seed to repair the same way, got %+v and %+v", again[6], repaired[6])
+	}
+	if _, _, err := RepairDealScript(nil, premature, 9); !errors.Is(err, ErrInvalidDealScript) {
+		t.Fatalf("expected a premature board card to stay an error, got %v", err)
+	}
+}
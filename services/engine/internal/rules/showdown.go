@@ -0,0 +1,88 @@
+package rules
+
+import (
+	"sort"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// SequenceShowdown calls on every live seat to show or muck, starting with the
+// last river aggressor (or the first live seat left of the button when the
+// river checked through) and moving clockwise. A seat must show when it wins
+// a pot, opens the showdown, or beats or ties every hand shown before it.
+// Otherwise it follows its ShowdownPreference.
+func SequenceShowdown(state domain.HandState, awards []domain.PotAward) []domain.ShowdownReveal {
+	return sequenceShowdown(state, awards, nil)
+}
+
+func sequenceShowdown(state domain.HandState, awards []domain.PotAward, ranks map[domain.SeatNo]HandRank) []domain.ShowdownReveal {
+	holeBySeat := make(map[domain.SeatNo][]domain.Card, len(state.HoleCards))
+	for _, seatCards := range state.HoleCards {
+		holeBySeat[seatCards.SeatNo] = seatCards.Cards
+	}
+	winners := map[domain.SeatNo]struct{}{}
+	for _, award := range awards {
+		for _, seatNo := range award.Seats {
+			winners[seatNo] = struct{}{}
+		}
+	}
+
+	live := make([]domain.SeatState, 0, len(state.Seats))
+	for _, seat := range state.Seats {
+		if seat.IsActive() && len(holeBySeat[seat.SeatNo]) == 2 {
+			live = append(live, seat)
+		}
+	}
+	if len(live) == 0 {
+		return nil
+	}
+	sort.Slice(live, func(i, j int) bool { return live[i].SeatNo < live[j].SeatNo })
+
+	start := -1
+	if state.LastAggressorSeat != nil {
+		for i, seat := range live {
+			if seat.SeatNo == *state.LastAggressorSeat {
+				start = i
+				break
+			}
+		}
+	}
+	if start == -1 {
+		start = 0
+		for i, seat := range live {
+			if seat.SeatNo > state.ButtonSeat {
+				start = i
+				break
+			}
+		}
+	}
+
+	reveals := make([]domain.ShowdownReveal, 0, len(live))
+	var best HandRank
+	shown := false
+	for i := 0; i < len(live); i++ {
+		seat := live[(start+i)%len(live)]
+		rank := rankFor(ranks, seat.SeatNo, holeBySeat[seat.SeatNo], state.Board)
+		reveal := domain.ShowdownReveal{SeatNo: seat.SeatNo, Action: domain.RevealShow}
+		_, won := winners[seat.SeatNo]
+		switch {
+		case won:
+			reveal.Reason = "winner"
+		case !shown:
+			reveal.Reason = "first_to_show"
+		case CompareHandRank(rank, best) >= 0:
+			reveal.Reason = "best_so_far"
+		case seat.ShowdownPreference == domain.ShowdownAlwaysShow:
+			reveal.Reason = "always_show"
+		default:
+			reveal.Action = domain.RevealMuck
+			reveal.Reason = "auto_muck"
+		}
+		if reveal.Action == domain.RevealShow && (!shown || CompareHandRank(rank, best) > 0) {
+			best = rank
+			shown = true
+		}
+		reveals = append(reveals, reveal)
+	}
+	return reveals
+}
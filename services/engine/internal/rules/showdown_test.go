@@ -0,0 +1,73 @@
+package rules
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestResolvePots_SequencesRevealsByPreference(t *testing.T) {
+	t.Parallel()
+
+	alwaysShow := seatWithCommit(t, 3, 900, 100, false)
+	alwaysShow.ShowdownPreference = domain.ShowdownAlwaysShow
+	state := showdownState(t, []domain.SeatState{
+		seatWithCommit(t, 1, 900, 100, false),
+		seatWithCommit(t, 2, 900, 100, false),
+		alwaysShow,
+		seatWithCommit(t, 4, 900, 100, false),
+	}, []domain.SeatCards{
+		{SeatNo: mustSeatNo(t, 1), Cards: cards(t, "As", "Ah")},
+		{SeatNo: mustSeatNo(t, 2), Cards: cards(t, "Kd", "Kh")},
+		{SeatNo: mustSeatNo(t, 3), Cards: cards(t, "Qd", "Qh")},
+		{SeatNo: mustSeatNo(t, 4), Cards: cards(t, "Kc", "Ks")},
+	}, cards(t, "2c", "3d", "7h", "9s", "Jc"), mustSeatNo(t, 1), 400)
+
+	resolved, _, err := ResolvePots(state)
+	if err != nil {
+		t.Fatalf("ResolvePots failed: %v", err)
+	}
+
+	want := []domain.ShowdownReveal{
+		{SeatNo: mustSeatNo(t, 2), Action: domain.RevealShow, Reason: "first_to_show"},
+		{SeatNo: mustSeatNo(t, 3), Action: domain.RevealShow, Reason: "always_show"},
+		{SeatNo: mustSeatNo(t, 4), Action: domain.RevealShow, Reason: "best_so_far"},
+		{SeatNo: mustSeatNo(t, 1), Action: domain.RevealShow, Reason: "winner"},
+	}
+	if !reflect.DeepEqual(resolved.ShowdownReveals, want) {
+		t.Fatalf("unexpected reveals\nwant=%+v\ngot=%+v", want, resolved.ShowdownReveals)
+	}
+}
+
+func TestSequenceShowdown_RiverAggressorShowsFirstAndLosersMuck(t *testing.T) {
+	t.Parallel()
+
+	state := showdownState(t, []domain.SeatState{
+		seatWithCommit(t, 1, 900, 100, false),
+		seatWithCommit(t, 2, 900, 100, false),
+		seatWithCommit(t, 3, 900, 100, false),
+		seatWithCommit(t, 4, 1000, 0, true),
+	}, []domain.SeatCards{
+		{SeatNo: mustSeatNo(t, 1), Cards: cards(t, "Kd", "Kh")},
+		{SeatNo: mustSeatNo(t, 2), Cards: cards(t, "Qd", "Qh")},
+		{SeatNo: mustSeatNo(t, 3), Cards: cards(t, "As", "Ah")},
+		{SeatNo: mustSeatNo(t, 4), Cards: cards(t, "2d", "2h")},
+	}, cards(t, "2c", "3d", "7h", "9s", "Jc"), mustSeatNo(t, 3), 300)
+	aggressor := mustSeatNo(t, 1)
+	state.LastAggressorSeat = &aggressor
+
+	resolved, _, err := ResolvePots(state)
+	if err != nil {
+		t.Fatalf("ResolvePots failed: %v", err)
+	}
+
+	want := []domain.ShowdownReveal{
+		{SeatNo: mustSeatNo(t, 1), Action: domain.RevealShow, Reason: "first_to_show"},
+		{SeatNo: mustSeatNo(t, 2), Action: domain.RevealMuck, Reason: "auto_muck"},
+		{SeatNo: mustSeatNo(t, 3), Action: domain.RevealShow, Reason: "winner"},
+	}
+	if !reflect.DeepEqual(resolved.ShowdownReveals, want) {
+		t.Fatalf("unexpected reveals\nwant=%+v\ngot=%+v", want, resolved.ShowdownReveals)
+	}
+}
@@ -0,0 +1,160 @@
+package rules
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var ErrNoUpCards = errors.New("no up cards dealt")
+
+// StudDealer deals seven card stud: two cards down and one up on third
+// street, one up on each of fourth through sixth, and the last down on
+// seventh. Each seat's HoleCards hold all its cards in the order dealt; the
+// exposed ones are recorded in DealSequence as domain.DealTargetUp, so every
+// seat can see them.
+type StudDealer interface {
+	InitHand(state domain.HandState) (domain.HandState, error)
+	DealThirdStreet(state domain.HandState) (domain.HandState, error)
+	// DealStudStreet burns and deals the next street, fourth through
+	// seventh, to every seat still in the hand, all-in seats included.
+	DealStudStreet(state domain.HandState) (domain.HandState, error)
+}
+
+func NewStudDealer(shuffler Shuffler) StudDealer {
+	if shuffler == nil {
+		shuffler = NewCryptoShuffler()
+	}
+	return standardDealer{shuffler: shuffler}
+}
+
+// StudCardsNeeded is the most cards a stud hand with seats players can use:
+// seven to each and a burn before every street.
+func StudCardsNeeded(seats int) int {
+	return 7*seats + 5
+}
+
+func (d standardDealer) DealThirdStreet(state domain.HandState) (domain.HandState, error) {
+	if len(state.Deck) != state.DeckSpec.Size() {
+		return state, fmt.Errorf("cannot deal third street: deck size is %d", len(state.Deck))
+	}
+
+	ordered := activeSeatsInDealOrder(state.Seats, state.ButtonSeat)
+	if len(ordered) < 2 {
+		return state, fmt.Errorf("cannot deal third street: active seats=%d", len(ordered))
+	}
+
+	if _, err := dealCard(&state, domain.DealTargetBurn, 0); err != nil {
+		return state, err
+	}
+	state, err := dealHoleCards(state, ordered, 2)
+	if err != nil {
+		return state, err
+	}
+	return dealStudRound(state, ordered, domain.DealTargetUp)
+}
+
+func (d standardDealer) DealStudStreet(state domain.HandState) (domain.HandState, error) {
+	ordered := make([]domain.SeatNo, 0, len(state.HoleCards))
+	held := 0
+	for _, seatCards := range state.HoleCards {
+		idx := slices.IndexFunc(state.Seats, func(seat domain.SeatState) bool { return seat.SeatNo == seatCards.SeatNo })
+		if idx >= 0 && state.Seats[idx].IsActive() {
+			ordered = append(ordered, seatCards.SeatNo)
+			held = len(seatCards.Cards)
+		}
+	}
+	if len(ordered) < 2 {
+		return state, fmt.Errorf("cannot deal stud street: active seats=%d", len(ordered))
+	}
+
+	target := domain.DealTargetUp
+	switch held {
+	case 3, 4, 5:
+	case 6:
+		target = domain.DealTargetHole
+	default:
+		return state, fmt.Errorf("%w: no stud street follows %d cards", ErrWrongStreet, held)
+	}
+
+	if _, err := dealCard(&state, domain.DealTargetBurn, 0); err != nil {
+		return state, err
+	}
+	return dealStudRound(state, ordered, target)
+}
+
+// dealStudRound deals one card to each seat in ordered, adding it to the
+// seat's HoleCards.
+func dealStudRound(state domain.HandState, ordered []domain.SeatNo, target domain.DealTarget) (domain.HandState, error) {
+	state.HoleCards = slices.Clone(state.HoleCards)
+	for _, seatNo := range ordered {
+		card, err := dealCard(&state, target, seatNo)
+		if err != nil {
+			return state, err
+		}
+		idx := slices.IndexFunc(state.HoleCards, func(seatCards domain.SeatCards) bool { return seatCards.SeatNo == seatNo })
+		state.HoleCards[idx].Cards = append(slices.Clone(state.HoleCards[idx].Cards), card)
+	}
+	return state, nil
+}
+
+// UpCards lists the cards seatNo was dealt face up, in the order dealt.
+func UpCards(state domain.HandState, seatNo domain.SeatNo) []domain.Card {
+	var up []domain.Card
+	for _, dealt := range state.DealSequence {
+		if dealt.Target == domain.DealTargetUp && dealt.SeatNo == seatNo {
+			up = append(up, dealt.Card)
+		}
+	}
+	return up
+}
+
+// razzBringInSuits breaks ties between equal up cards: the highest suit
+// brings in.
+var razzBringInSuits = map[domain.Suit]int{
+	domain.SuitClubs:    0,
+	domain.SuitDiamonds: 1,
+	domain.SuitHearts:   2,
+	domain.SuitSpades:   3,
+}
+
+// RazzBringIn is the seat forced to open third street in razz: the one
+// showing the highest up card, kings high and aces low, with ties going to
+// the highest suit, spades down to clubs.
+func RazzBringIn(state domain.HandState) (domain.SeatNo, error) {
+	var (
+		bringIn domain.SeatNo
+		worst   domain.Card
+	)
+	for _, seat := range state.Seats {
+		if !seat.IsActive() {
+			continue
+		}
+		up := UpCards(state, seat.SeatNo)
+		if len(up) == 0 {
+			continue
+		}
+		if bringIn == 0 || razzUpCardAbove(up[0], worst) {
+			bringIn, worst = seat.SeatNo, up[0]
+		}
+	}
+	if bringIn == 0 {
+		return 0, ErrNoUpCards
+	}
+	return bringIn, nil
+}
+
+func razzUpCardAbove(a domain.Card, b domain.Card) bool {
+	low := func(rank domain.Rank) domain.Rank {
+		if rank == 14 {
+			return 1
+		}
+		return rank
+	}
+	if low(a.Rank) != low(b.Rank) {
+		return low(a.Rank) > low(b.Rank)
+	}
+	return razzBringInSuits[a.Suit] > razzBringInSuits[b.Suit]
+}
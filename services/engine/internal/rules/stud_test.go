@@ -0,0 +1,107 @@
+package rules
+
+import (
+	"errors"
+	"slices"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestStudDealer_DealsThirdThroughSeventhStreet(t *testing.T) {
+	t.Parallel()
+
+	dealer := NewStudDealer(NewSeededShuffler(265))
+	state, err := dealer.InitHand(domain.HandState{
+		ButtonSeat: 1,
+		Seats: []domain.SeatState{
+			domain.NewSeatState(1, 100),
+			domain.NewSeatState(2, 100),
+			domain.NewSeatState(3, 100),
+		},
+	})
+	if err != nil {
+		t.Fatalf("InitHand failed: %v", err)
+	}
+	state, err = dealer.DealThirdStreet(state)
+	if err != nil {
+		t.Fatalf("DealThirdStreet failed: %v", err)
+	}
+	deck := state.Deck
+	if got := state.HoleCards[0]; got.SeatNo != 2 || !slices.Equal(got.Cards, []domain.Card{deck[1], deck[4], deck[7]}) {
+		t.Fatalf("expected two down and one up after a burn, got %+v", got)
+	}
+	if up := UpCards(state, 2); !slices.Equal(up, []domain.Card{deck[7]}) {
+		t.Fatalf("expected seat 2 to show %v, got %v", deck[7], up)
+	}
+	if view := state.ForSeat(1); len(UpCards(view, 2)) != 1 {
+		t.Fatal("expected other seats' up cards in every seat's view")
+	}
+
+	state.Seats[2].Folded = true
+	for street := 4; street <= 7; street++ {
+		if state, err = dealer.DealStudStreet(state); err != nil {
+			t.Fatalf("street %d: DealStudStreet failed: %v", street, err)
+		}
+	}
+	if len(state.HoleCards[0].Cards) != 7 || len(state.HoleCards[1].Cards) != 3 || len(state.HoleCards[2].Cards) != 7 {
+		t.Fatalf("expected seven cards to each seat still in, got %+v", state.HoleCards)
+	}
+	if up := UpCards(state, 2); len(up) != 4 {
+		t.Fatalf("expected four up cards, got %v", up)
+	}
+	if last := state.DealSequence[len(state.DealSequence)-1]; last.Target != domain.DealTargetHole || state.NextCardIndex != 22 {
+		t.Fatalf("expected seventh street dealt down at card 22, got %+v at %d", last, state.NextCardIndex)
+	}
+	if _, err := dealer.DealStudStreet(state); !errors.Is(err, ErrWrongStreet) {
+		t.Fatalf("expected ErrWrongStreet after seventh street, got %v", err)
+	}
+}
+
+func TestRazzBringIn_HighestUpCardAcesLow(t *testing.T) {
+	t.Parallel()
+
+	state := domain.HandState{
+		Seats: []domain.SeatState{
+			domain.NewSeatState(1, 100),
+			domain.NewSeatState(2, 100),
+			domain.NewSeatState(3, 100),
+		},
+		DealSequence: []domain.DealtCard{
+			{Target: domain.DealTargetHole, SeatNo: 3, Card: domain.NewCard(13, domain.SuitHearts)},
+			{Target: domain.DealTargetUp, SeatNo: 1, Card: domain.NewCard(13, domain.SuitDiamonds)},
+			{Target: domain.DealTargetUp, SeatNo: 2, Card: domain.NewCard(13, domain.SuitSpades)},
+			{Target: domain.DealTargetUp, SeatNo: 3, Card: domain.NewCard(14, domain.SuitSpades)},
+		},
+	}
+	if seat, err := RazzBringIn(state); err != nil || seat != 2 {
+		t.Fatalf("expected the king of spades to bring in, got seat %d, %v", seat, err)
+	}
+	state.Seats[1].Folded = true
+	if seat, err := RazzBringIn(state); err != nil || seat != 1 {
+		t.Fatalf("expected the king of diamonds over the ace, got seat %d, %v", seat, err)
+	}
+	if _, err := RazzBringIn(domain.HandState{Seats: state.Seats}); !errors.Is(err, ErrNoUpCards) {
+		t.Fatalf("expected ErrNoUpCards, got %v", err)
+	}
+}
+
+func TestSettleRazzShowdown_LowestSevenCardHandWins(t *testing.T) {
+	t.Parallel()
+
+	state := showdownState(t, []domain.SeatState{
+		seatWithCommit(t, 1, 900, 100, false),
+		seatWithCommit(t, 2, 900, 100, false),
+	}, []domain.SeatCards{
+		{SeatNo: mustSeatNo(t, 1), Cards: cards(t, "Ks", "Kd", "As", "2s", "3s", "4s", "7d")},
+		{SeatNo: mustSeatNo(t, 2), Cards: cards(t, "As", "Ad", "2c", "2h", "3c", "3d", "4h")},
+	}, nil, mustSeatNo(t, 1), 200)
+
+	result, err := SettleRazzShowdown(state)
+	if err != nil {
+		t.Fatalf("SettleRazzShowdown failed: %v", err)
+	}
+	if len(result.Awards) != 1 || !slices.Equal(result.Awards[0].Seats, []domain.SeatNo{1}) || result.Winnings[1] != 200 {
+		t.Fatalf("expected seven low to beat a pair of aces, got %+v", result)
+	}
+}
@@ -0,0 +1,195 @@
+package rules
+
+import (
+	"bufio"
+	"encoding/json"
+	"io"
+	"slices"
+	"sort"
+	"strconv"
+	"sync"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+const (
+	// EvalVectorCount is the size of the EvalVectors corpus.
+	EvalVectorCount = 10_000
+	// evalVectorStride spaces the corpus evenly over all C(52,7) hands.
+	evalVectorStride = 133_784_560 / EvalVectorCount
+	// StandardHandValues is how many five-card hands a standard deck can
+	// tell apart.
+	StandardHandValues = 7462
+)
+
+// EvalVector is one seven-card hand and the evaluator's verdict on it, for
+// other evaluator implementations to check themselves against. Cards are
+// written rank then suit, as in "Td" or "As".
+type EvalVector struct {
+	Cards    [7]string `json:"cards"`
+	Category string    `json:"category"`
+	Ordinal  uint16    `json:"ordinal"`
+}
+
+// EvalVectors returns a fixed corpus of EvalVectorCount hands. Number the 52
+// cards 2c 2d 2h 2s 3c ... As from zero; vector i holds the seven-card
+// combination at lexicographic index i*floor(C(52,7)/EvalVectorCount), so
+// any language can rebuild the same hands without sharing a random source.
+func EvalVectors() []EvalVector {
+	vectors := make([]EvalVector, 0, EvalVectorCount)
+	for i := range uint64(EvalVectorCount) {
+		var cards [7]domain.Card
+		var vector EvalVector
+		for j, index := range combinationAt(i*evalVectorStride, 52, 7) {
+			cards[j] = domain.NewCard(domain.Rank(index/4+2), vectorSuits[index%4])
+			vector.Cards[j] = cardCode(cards[j])
+		}
+		rank := EvaluateBestOfSeven(cards)
+		vector.Category = rank.Category.String()
+		vector.Ordinal, _ = RankOrdinal(rank)
+		vectors = append(vectors, vector)
+	}
+	return vectors
+}
+
+// WriteEvalVectors writes EvalVectors as JSON lines.
+func WriteEvalVectors(w io.Writer) error {
+	buffered := bufio.NewWriter(w)
+	encoder := json.NewEncoder(buffered)
+	for _, vector := range EvalVectors() {
+		if err := encoder.Encode(vector); err != nil {
+			return err
+		}
+	}
+	return buffered.Flush()
+}
+
+// RankOrdinal places rank among the StandardHandValues distinct five-card
+// hands of a standard deck, from 1 for seven-five-four-three-two to 7462 for
+// a royal flush, so equal ordinals split and a higher ordinal wins. It
+// reports false for ranks one deck cannot make, such as five of a kind.
+func RankOrdinal(rank HandRank) (uint16, bool) {
+	ordinal, ok := rankOrdinals()[rankKey(rank)]
+	return ordinal, ok
+}
+
+var vectorSuits = [4]domain.Suit{domain.SuitClubs, domain.SuitDiamonds, domain.SuitHearts, domain.SuitSpades}
+
+var rankOrdinals = sync.OnceValue(func() map[string]uint16 {
+	ranks := standardHandRanks()
+	sort.Slice(ranks, func(i, j int) bool { return CompareHandRank(ranks[i], ranks[j]) < 0 })
+	ordinals := make(map[string]uint16, len(ranks))
+	for i, rank := range ranks {
+		ordinals[rankKey(rank)] = uint16(i + 1)
+	}
+	return ordinals
+})
+
+func rankKey(rank HandRank) string {
+	return string(append([]byte{byte(rank.Category)}, rank.Tiebreak...))
+}
+
+// standardHandRanks lists every rank the evaluator can return for five
+// cards from one standard deck.
+func standardHandRanks() []HandRank {
+	ranks := make([]HandRank, 0, StandardHandValues)
+	add := func(category HandCategory, tiebreak ...uint8) {
+		ranks = append(ranks, HandRank{Category: category, Tiebreak: tiebreak})
+	}
+	for high := uint8(5); high <= 14; high++ {
+		add(HandCategoryStraight, high)
+		add(HandCategoryStraightFlush, high)
+	}
+	for _, five := range descendingRanks(nil, 5) {
+		if _, straight := straightHighRank(five); straight {
+			continue
+		}
+		add(HandCategoryHighCard, five...)
+		add(HandCategoryFlush, five...)
+	}
+	for a := uint8(2); a <= 14; a++ {
+		for _, kickers := range descendingRanks([]uint8{a}, 3) {
+			add(HandCategoryOnePair, append([]uint8{a}, kickers...)...)
+		}
+		for _, kickers := range descendingRanks([]uint8{a}, 2) {
+			add(HandCategoryThreeOfAKind, append([]uint8{a}, kickers...)...)
+		}
+		for b := uint8(2); b <= 14; b++ {
+			if a == b {
+				continue
+			}
+			add(HandCategoryFullHouse, a, b)
+			add(HandCategoryFourOfAKind, a, b)
+			if a > b {
+				for _, kicker := range descendingRanks([]uint8{a, b}, 1) {
+					add(HandCategoryTwoPair, a, b, kicker[0])
+				}
+			}
+		}
+	}
+	return ranks
+}
+
+// descendingRanks lists every choice of n distinct ranks outside exclude,
+// each highest first.
+func descendingRanks(exclude []uint8, n int) [][]uint8 {
+	var out [][]uint8
+	var pick func(prefix []uint8, below uint8)
+	pick = func(prefix []uint8, below uint8) {
+		if len(prefix) == n {
+			out = append(out, slices.Clone(prefix))
+			return
+		}
+		for rank := below - 1; rank >= 2; rank-- {
+			if !slices.Contains(exclude, rank) {
+				pick(append(prefix, rank), rank)
+			}
+		}
+	}
+	pick(nil, 15)
+	return out
+}
+
+// combinationAt returns the k-of-n combination at lexicographic index.
+func combinationAt(index uint64, n, k int) []int {
+	out := make([]int, 0, k)
+	for next := 0; len(out) < k; next++ {
+		// Combinations that start with next choose the rest from above it.
+		if skipped := binomial(n-next-1, k-len(out)-1); index >= skipped {
+			index -= skipped
+			continue
+		}
+		out = append(out, next)
+	}
+	return out
+}
+
+func binomial(n, k int) uint64 {
+	if k < 0 || k > n {
+		return 0
+	}
+	result := uint64(1)
+	for i := 1; i <= k; i++ {
+		result = result * uint64(n-k+i) / uint64(i)
+	}
+	return result
+}
+
+func cardCode(card domain.Card) string {
+	var rank string
+	switch card.Rank {
+	case 14:
+		rank = "A"
+	case 13:
+		rank = "K"
+	case 12:
+		rank = "Q"
+	case 11:
+		rank = "J"
+	case 10:
+		rank = "T"
+	default:
+		rank = strconv.Itoa(int(card.Rank))
+	}
+	return rank + string(card.Suit[0])
+}
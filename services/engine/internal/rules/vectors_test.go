@@ -0,0 +1,46 @@
+package rules
+
+import "testing"
+
+func TestRankOrdinal_NumbersEveryStandardHandInOrder(t *testing.T) {
+	t.Parallel()
+
+	ranks := standardHandRanks()
+	if len(ranks) != StandardHandValues || len(rankOrdinals()) != StandardHandValues {
+		t.Fatalf("expected %d distinct hands, got %d ranks and %d ordinals", StandardHandValues, len(ranks), len(rankOrdinals()))
+	}
+	worst, _ := RankOrdinal(EvaluateBestHand(cards(t, "7s", "5d"), cards(t, "4c", "3h", "2s")))
+	best, _ := RankOrdinal(EvaluateBestHand(cards(t, "As", "Ks"), cards(t, "Qs", "Js", "Ts")))
+	if worst != 1 || best != StandardHandValues {
+		t.Fatalf("expected seven high first and a royal flush last, got %d and %d", worst, best)
+	}
+	if _, ok := RankOrdinal(HandRank{Category: HandCategoryFiveOfAKind, Tiebreak: []uint8{14}}); ok {
+		t.Fatal("expected five of a kind to have no standard ordinal")
+	}
+}
+
+func TestEvalVectors_AgreeWithTheEvaluator(t *testing.T) {
+	t.Parallel()
+
+	vectors := EvalVectors()
+	if len(vectors) != EvalVectorCount {
+		t.Fatalf("expected %d vectors, got %d", EvalVectorCount, len(vectors))
+	}
+	if first := vectors[0]; first.Cards != [7]string{"2c", "2d", "2h", "2s", "3c", "3d", "3h"} || first.Category != "four_of_a_kind" {
+		t.Fatalf("expected the corpus to open on the first combination, got %+v", first)
+	}
+	var previous HandRank
+	for i, vector := range vectors {
+		rank := EvaluateBestHand(nil, cards(t, vector.Cards[:]...))
+		if rank.Category.String() != vector.Category || vector.Ordinal == 0 {
+			t.Fatalf("vector %d: expected %s with an ordinal, got %+v", i, rank.Category, vector)
+		}
+		if i > 0 {
+			previousOrdinal, _ := RankOrdinal(previous)
+			if cmp := CompareHandRank(rank, previous); (cmp > 0) != (vector.Ordinal > previousOrdinal) || (cmp == 0) != (vector.Ordinal == previousOrdinal) {
+				t.Fatalf("vector %d: ordinal %d disagrees with the evaluator against ordinal %d", i, vector.Ordinal, previousOrdinal)
+			}
+		}
+		previous = rank
+	}
+}
@@ -0,0 +1,194 @@
+package seatcontrol
+
+import (
+	"errors"
+	"fmt"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+type Transport string
+
+const (
+	TransportHTTP      Transport = "http"
+	TransportWebSocket Transport = "websocket"
+	TransportGRPC      Transport = "grpc"
+)
+
+var (
+	ErrUnknownTransport   = errors.New("unknown transport")
+	ErrMissingController  = errors.New("controller id is required")
+	ErrStaleController    = errors.New("controller has been superseded")
+	ErrNoActiveController = errors.New("seat has no active controller")
+)
+
+type Controller struct {
+	TableID      string        `json:"table_id"`
+	SeatNo       domain.SeatNo `json:"seat_no"`
+	ControllerID string        `json:"controller_id"`
+	Transport    Transport     `json:"transport"`
+	Generation   uint64        `json:"generation"`
+	ClaimedAt    time.Time     `json:"claimed_at"`
+}
+
+type HandoffEvent struct {
+	TableID string        `json:"table_id"`
+	SeatNo  domain.SeatNo `json:"seat_no"`
+	From    *Controller   `json:"from,omitempty"`
+	To      *Controller   `json:"to,omitempty"`
+	At      time.Time     `json:"at"`
+}
+
+type seatKey struct {
+	tableID string
+	seatNo  domain.SeatNo
+}
+
+// Registry enforces a single active controller per seat. A new claim on a
+// seat supersedes the previous controller and records a handoff event, so a
+// bot can reconnect over a different transport with the same seat token.
+type Registry struct {
+	mu          sync.Mutex
+	now         func() time.Time
+	active      map[seatKey]Controller
+	generations map[seatKey]uint64
+	events      map[string][]HandoffEvent
+}
+
+func NewRegistry() *Registry {
+	return &Registry{
+		now:         func() time.Time { return time.Now().UTC() },
+		active:      make(map[seatKey]Controller),
+		generations: make(map[seatKey]uint64),
+		events:      make(map[string][]HandoffEvent),
+	}
+}
+
+func ParseTransport(raw string) (Transport, error) {
+	switch transport := Transport(strings.ToLower(strings.TrimSpace(raw))); transport {
+	case TransportHTTP, TransportWebSocket, TransportGRPC:
+		return transport, nil
+	default:
+		return "", fmt.Errorf("%w: %q", ErrUnknownTransport, raw)
+	}
+}
+
+// Claim makes controllerID the active controller for the seat. Re-claiming
+// with the same controller and transport is idempotent and does not emit an
+// event.
+func (r *Registry) Claim(tableID string, seatNo domain.SeatNo, transport Transport, controllerID string) (Controller, *HandoffEvent, error) {
+	transport, err := ParseTransport(string(transport))
+	if err != nil {
+		return Controller{}, nil, err
+	}
+	controllerID = strings.TrimSpace(controllerID)
+	if controllerID == "" {
+		return Controller{}, nil, ErrMissingController
+	}
+
+	r.mu.Lock()
+	defer r.mu.Unlock()
+
+	key := seatKey{tableID: tableID, seatNo: seatNo}
+	previous, hadPrevious := r.active[key]
+	if hadPrevious && previous.ControllerID == controllerID && previous.Transport == transport {
+		return previous, nil, nil
+	}
+
+	r.generations[key]++
+	next := Controller{
+		TableID:      tableID,
+		SeatNo:       seatNo,
+		ControllerID: controllerID,
+		Transport:    transport,
+		Generation:   r.generations[key],
+		ClaimedAt:    r.now(),
+	}
+	r.active[key] = next
+
+	event := HandoffEvent{
+		TableID: tableID,
+		SeatNo:  seatNo,
+		To:      copyController(next),
+		At:      next.ClaimedAt,
+	}
+	if hadPrevious {
+		event.From = copyController(previous)
+	}
+	r.events[tableID] = append(r.events[tableID], event)
+	return next, &event, nil
+}
+
+// Validate reports whether generation still identifies the seat's active
+// controller; superseded controllers must stop acting for the seat.
+func (r *Registry) Validate(tableID string, seatNo domain.SeatNo, generation uint64) error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+
+	current, ok := r.active[seatKey{tableID: tableID, seatNo: seatNo}]
+	if !ok {
+		return ErrNoActiveController
+	}
+	if current.Generation != generation {
+		return fmt.Errorf("%w: generation %d, active %d", ErrStaleController, generation, current.Generation)
+	}
+	return nil
+}
+
+func (r *Registry) Release(tableID string, seatNo domain.SeatNo, generation uint64) error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+
+	key := seatKey{tableID: tableID, seatNo: seatNo}
+	current, ok := r.active[key]
+	if !ok {
+		return ErrNoActiveController
+	}
+	if current.Generation != generation {
+		return fmt.Errorf("%w: generation %d, active %d", ErrStaleController, generation, current.Generation)
+	}
+	delete(r.active, key)
+	r.events[tableID] = append(r.events[tableID], HandoffEvent{
+		TableID: tableID,
+		SeatNo:  seatNo,
+		From:    copyController(current),
+		At:      r.now(),
+	})
+	return nil
+}
+
+func (r *Registry) Active(tableID string, seatNo domain.SeatNo) (Controller, bool) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+
+	current, ok := r.active[seatKey{tableID: tableID, seatNo: seatNo}]
+	return current, ok
+}
+
+// CountByTransport returns the number of seats with an active controller on
+// each transport.
+func (r *Registry) CountByTransport() map[Transport]int {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+
+	counts := map[Transport]int{TransportHTTP: 0, TransportWebSocket: 0, TransportGRPC: 0}
+	for _, controller := range r.active {
+		counts[controller.Transport]++
+	}
+	return counts
+}
+
+func (r *Registry) Events(tableID string) []HandoffEvent {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+
+	return append([]HandoffEvent(nil), r.events[tableID]...)
+}
+
+func copyController(controller Controller) *Controller {
+	out := controller
+	return &out
+}
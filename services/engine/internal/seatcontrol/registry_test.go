@@ -0,0 +1,119 @@
+package seatcontrol
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestRegistryClaim_HandoffSupersedesPreviousController(t *testing.T) {
+	t.Parallel()
+
+	registry := NewRegistry()
+	first, event, err := registry.Claim("table-1", 1, TransportWebSocket, "bot-a")
+	if err != nil {
+		t.Fatalf("Claim failed: %v", err)
+	}
+	if event == nil || event.From != nil || event.To == nil || event.To.ControllerID != "bot-a" {
+		t.Fatalf("expected initial claim event, got %+v", event)
+	}
+
+	second, event, err := registry.Claim("table-1", 1, TransportGRPC, "bot-a-restarted")
+	if err != nil {
+		t.Fatalf("Claim failed: %v", err)
+	}
+	if second.Generation != first.Generation+1 {
+		t.Fatalf("expected generation %d, got %d", first.Generation+1, second.Generation)
+	}
+	if event == nil || event.From == nil || event.From.Transport != TransportWebSocket || event.To.Transport != TransportGRPC {
+		t.Fatalf("expected websocket->grpc handoff event, got %+v", event)
+	}
+
+	if err := registry.Validate("table-1", 1, first.Generation); !errors.Is(err, ErrStaleController) {
+		t.Fatalf("expected ErrStaleController for superseded controller, got %v", err)
+	}
+	if err := registry.Validate("table-1", 1, second.Generation); err != nil {
+		t.Fatalf("expected active controller to validate, got %v", err)
+	}
+
+	active, ok := registry.Active("table-1", 1)
+	if !ok || active.ControllerID != "bot-a-restarted" {
+		t.Fatalf("expected restarted bot to be active, got %+v ok=%v", active, ok)
+	}
+	if events := registry.Events("table-1"); len(events) != 2 {
+		t.Fatalf("expected 2 handoff events, got %d", len(events))
+	}
+}
+
+func TestRegistryClaim_ReclaimBySameControllerIsIdempotent(t *testing.T) {
+	t.Parallel()
+
+	registry := NewRegistry()
+	first, _, err := registry.Claim("table-1", 2, TransportHTTP, "bot-b")
+	if err != nil {
+		t.Fatalf("Claim failed: %v", err)
+	}
+	again, event, err := registry.Claim("table-1", 2, TransportHTTP, "bot-b")
+	if err != nil {
+		t.Fatalf("Claim failed: %v", err)
+	}
+	if event != nil {
+		t.Fatalf("expected no event for idempotent claim, got %+v", event)
+	}
+	if again.Generation != first.Generation {
+		t.Fatalf("expected generation to stay %d, got %d", first.Generation, again.Generation)
+	}
+}
+
+func TestRegistryClaim_SeatsAreIndependent(t *testing.T) {
+	t.Parallel()
+
+	registry := NewRegistry()
+	if _, _, err := registry.Claim("table-1", 1, TransportHTTP, "bot-a"); err != nil {
+		t.Fatalf("Claim failed: %v", err)
+	}
+	seat2, _, err := registry.Claim("table-1", 2, TransportHTTP, "bot-b")
+	if err != nil {
+		t.Fatalf("Claim failed: %v", err)
+	}
+	if seat2.Generation != 1 {
+		t.Fatalf("expected independent generation for seat 2, got %d", seat2.Generation)
+	}
+	if _, ok := registry.Active("table-2", 1); ok {
+		t.Fatal("expected no controller on another table")
+	}
+}
+
+func TestRegistryRelease(t *testing.T) {
+	t.Parallel()
+
+	registry := NewRegistry()
+	controller, _, err := registry.Claim("table-1", 1, TransportWebSocket, "bot-a")
+	if err != nil {
+		t.Fatalf("Claim failed: %v", err)
+	}
+	if err := registry.Release("table-1", 1, controller.Generation+1); !errors.Is(err, ErrStaleController) {
+		t.Fatalf("expected ErrStaleController for wrong generation, got %v", err)
+	}
+	if err := registry.Release("table-1", 1, controller.Generation); err != nil {
+		t.Fatalf("Release failed: %v", err)
+	}
+	if err := registry.Validate("table-1", 1, controller.Generation); !errors.Is(err, ErrNoActiveController) {
+		t.Fatalf("expected ErrNoActiveController after release, got %v", err)
+	}
+	events := registry.Events("table-1")
+	if len(events) != 2 || events[1].To != nil || events[1].From == nil {
+		t.Fatalf("expected release event, got %+v", events)
+	}
+}
+
+func TestRegistryClaim_RejectsInvalidInput(t *testing.T) {
+	t.Parallel()
+
+	registry := NewRegistry()
+	if _, _, err := registry.Claim("table-1", 1, Transport("carrier-pigeon"), "bot-a"); !errors.Is(err, ErrUnknownTransport) {
+		t.Fatalf("expected ErrUnknownTransport, got %v", err)
+	}
+	if _, _, err := registry.Claim("table-1", 1, TransportHTTP, " "); !errors.Is(err, ErrMissingController) {
+		t.Fatalf("expected ErrMissingController, got %v", err)
+	}
+}
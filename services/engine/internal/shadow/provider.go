@@ -0,0 +1,223 @@
+package shadow
+
+import (
+	"context"
+	"errors"
+	"slices"
+	"sync"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/statemachine"
+	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+)
+
+var ErrShadowMisconfigured = errors.New("shadow provider misconfigured")
+
+// MaxCandidateWait caps how long a candidate may answer a decision whose
+// context has no deadline of its own.
+const MaxCandidateWait = 10 * time.Second
+
+// MaxDivergedDecisions caps how many diverging decisions a report lists;
+// older ones drop out of the list but stay in its counts.
+const MaxDivergedDecisions = 500
+
+// Provider forwards every decision to the live provider and, for shadowed
+// seats, asks the candidate for the same seat's view of the hand. Only the
+// live action is returned to the runner, as soon as it is in; the
+// candidate's answer is folded into the report whenever it arrives, so a
+// long shadow run keeps only counts and the latest divergences.
+type Provider struct {
+	live   tablerunner.ActionProvider
+	shadow tablerunner.ActionProvider
+	seats  map[domain.SeatNo]struct{}
+
+	// answering counts the candidates still answering.
+	answering sync.WaitGroup
+
+	mu       sync.Mutex
+	report   Report
+	compared int
+}
+
+type Decision struct {
+	HandID      string         `json:"hand_id"`
+	HandNo      uint64         `json:"hand_no"`
+	Street      domain.Street  `json:"street"`
+	Seat        domain.SeatNo  `json:"seat"`
+	Live        *domain.Action `json:"live,omitempty"`
+	LiveError   string         `json:"live_error,omitempty"`
+	Shadow      *domain.Action `json:"shadow,omitempty"`
+	ShadowError string         `json:"shadow_error,omitempty"`
+	Diverged    bool           `json:"diverged"`
+}
+
+type Report struct {
+	Decisions      int        `json:"decisions"`
+	Agreements     int        `json:"agreements"`
+	Divergences    int        `json:"divergences"`
+	KindMismatches int        `json:"kind_mismatches"`
+	LiveErrors     int        `json:"live_errors"`
+	ShadowErrors   int        `json:"shadow_errors"`
+	DivergenceRate float64    `json:"divergence_rate"`
+	Diverged       []Decision `json:"diverged"`
+}
+
+type shadowResult struct {
+	action domain.Action
+	err    error
+}
+
+func NewProvider(live tablerunner.ActionProvider, candidate tablerunner.ActionProvider, seats []domain.SeatNo) (*Provider, error) {
+	if live == nil || candidate == nil {
+		return nil, ErrShadowMisconfigured
+	}
+	if len(seats) == 0 {
+		return nil, ErrShadowMisconfigured
+	}
+	shadowed := make(map[domain.SeatNo]struct{}, len(seats))
+	for _, seat := range seats {
+		shadowed[seat] = struct{}{}
+	}
+	return &Provider{
+		live:   live,
+		shadow: candidate,
+		seats:  shadowed,
+	}, nil
+}
+
+func (p *Provider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	if _, ok := p.seats[state.ActingSeat]; !ok {
+		return p.live.NextAction(ctx, state)
+	}
+
+	// Query the candidate concurrently and never wait on it, so shadowing
+	// adds nothing to the live seat's decision time. The candidate sees only
+	// what the acting seat may.
+	decision := Decision{
+		HandID: state.HandID,
+		HandNo: state.HandNo,
+		Street: state.Street,
+		Seat:   state.ActingSeat,
+	}
+	lives := make(chan shadowResult, 1)
+	shadowState := state.ForSeat(state.ActingSeat)
+	candidateCtx, cancel := candidateContext(ctx)
+	p.answering.Add(1)
+	go func() {
+		defer p.answering.Done()
+		defer cancel()
+		action, err := p.shadow.NextAction(candidateCtx, shadowState)
+		decision.settle(shadowState, <-lives, shadowResult{action: action, err: err})
+		p.record(decision)
+	}()
+
+	liveAction, liveErr := p.live.NextAction(ctx, state)
+	lives <- shadowResult{action: liveAction, err: liveErr}
+	return liveAction, liveErr
+}
+
+// candidateContext detaches the candidate from the live decision's
+// cancellation, which comes as soon as the live action is in, but keeps the
+// decision's deadline, or MaxCandidateWait when it has none.
+func candidateContext(ctx context.Context) (context.Context, context.CancelFunc) {
+	deadline, ok := ctx.Deadline()
+	if !ok {
+		deadline = time.Now().Add(MaxCandidateWait)
+	}
+	return context.WithDeadline(context.WithoutCancel(ctx), deadline)
+}
+
+// settle records both answers to the decision asked in state. An all-in is
+// recorded as the call, bet or raise it stands for, so it compares equal to
+// that action sent outright.
+func (d *Decision) settle(state domain.HandState, live shadowResult, candidate shadowResult) {
+	if live.err != nil {
+		d.LiveError = live.err.Error()
+	} else {
+		d.Live = copyAction(normalize(state, live.action))
+	}
+	if candidate.err != nil {
+		d.ShadowError = candidate.err.Error()
+	} else {
+		d.Shadow = copyAction(normalize(state, candidate.action))
+	}
+	d.Diverged = d.Live != nil && d.Shadow != nil && !sameAction(*d.Live, *d.Shadow)
+}
+
+// normalize rewrites an all-in as statemachine.NormalizeAction does, keeping
+// an all-in the state does not allow as it was sent.
+func normalize(state domain.HandState, action domain.Action) domain.Action {
+	if normalized, err := statemachine.NormalizeAction(state, action); err == nil {
+		return normalized
+	}
+	return action
+}
+
+// record folds a settled decision into the running report.
+func (p *Provider) record(decision Decision) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+
+	p.report.Decisions++
+	switch {
+	case decision.LiveError != "":
+		p.report.LiveErrors++
+		if decision.ShadowError != "" {
+			p.report.ShadowErrors++
+		}
+		return
+	case decision.ShadowError != "":
+		p.report.ShadowErrors++
+		return
+	}
+
+	p.compared++
+	if !decision.Diverged {
+		p.report.Agreements++
+		return
+	}
+	p.report.Divergences++
+	if decision.Live.Kind != decision.Shadow.Kind {
+		p.report.KindMismatches++
+	}
+	if len(p.report.Diverged) == MaxDivergedDecisions {
+		p.report.Diverged = slices.Delete(p.report.Diverged, 0, 1)
+	}
+	p.report.Diverged = append(p.report.Diverged, decision)
+}
+
+// Report waits for any candidate still answering and summarizes every
+// shadowed decision so far. Diverged lists the latest MaxDivergedDecisions
+// divergences in the order their candidates answered.
+func (p *Provider) Report() Report {
+	p.answering.Wait()
+	p.mu.Lock()
+	defer p.mu.Unlock()
+
+	report := p.report
+	report.Diverged = append([]Decision{}, p.report.Diverged...)
+	if p.compared > 0 {
+		report.DivergenceRate = float64(report.Divergences) / float64(p.compared)
+	}
+	return report
+}
+
+func sameAction(a domain.Action, b domain.Action) bool {
+	if a.Kind != b.Kind {
+		return false
+	}
+	if a.Amount == nil || b.Amount == nil {
+		return a.Amount == nil && b.Amount == nil
+	}
+	return *a.Amount == *b.Amount
+}
+
+func copyAction(action domain.Action) *domain.Action {
+	out := action
+	if action.Amount != nil {
+		amount := *action.Amount
+		out.Amount = &amount
+	}
+	return &out
+}
@@ -0,0 +1,295 @@
+package shadow
+
+import (
+	"context"
+	"errors"
+	"sync"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+)
+
+type scriptedProvider struct {
+	mu    sync.Mutex
+	steps []scriptedStep
+	calls int
+}
+
+type scriptedStep struct {
+	action domain.Action
+	err    error
+}
+
+func (p *scriptedProvider) NextAction(_ context.Context, _ domain.HandState) (domain.Action, error) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	if p.calls >= len(p.steps) {
+		return domain.Action{}, errors.New("script exhausted")
+	}
+	step := p.steps[p.calls]
+	p.calls++
+	return step.action, step.err
+}
+
+func (p *scriptedProvider) callCount() int {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	return p.calls
+}
+
+func mustAction(t *testing.T, kind domain.ActionKind, amount *domain.Chips) domain.Action {
+	t.Helper()
+	action, err := domain.NewAction(kind, amount)
+	if err != nil {
+		t.Fatalf("NewAction failed: %v", err)
+	}
+	return action
+}
+
+func actingState(seat domain.SeatNo) domain.HandState {
+	return domain.HandState{
+		HandID:     "hand-1",
+		HandNo:     1,
+		Street:     domain.StreetPreflop,
+		ActingSeat: seat,
+	}
+}
+
+func TestProvider_ReturnsLiveActionAndRecordsDivergence(t *testing.T) {
+	t.Parallel()
+
+	raiseTo := domain.Chips(300)
+	live := &scriptedProvider{steps: []scriptedStep{
+		{action: mustAction(t, domain.ActionCall, nil)},
+		{action: mustAction(t, domain.ActionCheck, nil)},
+	}}
+	candidate := &scriptedProvider{steps: []scriptedStep{
+		{action: mustAction(t, domain.ActionRaise, &raiseTo)},
+		{action: mustAction(t, domain.ActionCheck, nil)},
+	}}
+	provider, err := NewProvider(live, candidate, []domain.SeatNo{1})
+	if err != nil {
+		t.Fatalf("NewProvider failed: %v", err)
+	}
+
+	action, err := provider.NextAction(context.Background(), actingState(1))
+	if err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+	if action.Kind != domain.ActionCall {
+		t.Fatalf("expected live call to be applied, got %q", action.Kind)
+	}
+	if _, err := provider.NextAction(context.Background(), actingState(1)); err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+
+	report := provider.Report()
+	if report.Decisions != 2 || report.Agreements != 1 || report.Divergences != 1 || report.KindMismatches != 1 {
+		t.Fatalf("unexpected report counts: %+v", report)
+	}
+	if report.DivergenceRate != 0.5 {
+		t.Fatalf("expected divergence rate 0.5, got %v", report.DivergenceRate)
+	}
+	if len(report.Diverged) != 1 || report.Diverged[0].Shadow == nil || report.Diverged[0].Shadow.Kind != domain.ActionRaise {
+		t.Fatalf("expected raise divergence entry, got %+v", report.Diverged)
+	}
+}
+
+func TestProvider_UnshadowedSeatsSkipCandidate(t *testing.T) {
+	t.Parallel()
+
+	live := &scriptedProvider{steps: []scriptedStep{{action: mustAction(t, domain.ActionFold, nil)}}}
+	candidate := &scriptedProvider{}
+	provider, err := NewProvider(live, candidate, []domain.SeatNo{2})
+	if err != nil {
+		t.Fatalf("NewProvider failed: %v", err)
+	}
+
+	if _, err := provider.NextAction(context.Background(), actingState(1)); err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+	if candidate.callCount() != 0 {
+		t.Fatalf("expected candidate not to be called, got %d calls", candidate.callCount())
+	}
+	if report := provider.Report(); report.Decisions != 0 {
+		t.Fatalf("expected no recorded decisions, got %+v", report)
+	}
+}
+
+func TestProvider_CandidateErrorsDoNotAffectLiveDecision(t *testing.T) {
+	t.Parallel()
+
+	live := &scriptedProvider{steps: []scriptedStep{{action: mustAction(t, domain.ActionCheck, nil)}}}
+	candidate := &scriptedProvider{steps: []scriptedStep{{err: errors.New("candidate down")}}}
+	provider, err := NewProvider(live, candidate, []domain.SeatNo{1})
+	if err != nil {
+		t.Fatalf("NewProvider failed: %v", err)
+	}
+
+	action, err := provider.NextAction(context.Background(), actingState(1))
+	if err != nil {
+		t.Fatalf("expected live decision despite candidate error, got %v", err)
+	}
+	if action.Kind != domain.ActionCheck {
+		t.Fatalf("expected check, got %q", action.Kind)
+	}
+	report := provider.Report()
+	if report.ShadowErrors != 1 || report.Divergences != 0 || report.DivergenceRate != 0 {
+		t.Fatalf("unexpected report: %+v", report)
+	}
+}
+
+// heldCandidate answers only once released, and keeps the state it was asked
+// about.
+type heldCandidate struct {
+	release chan struct{}
+	asked   chan domain.HandState
+}
+
+func (p *heldCandidate) NextAction(_ context.Context, state domain.HandState) (domain.Action, error) {
+	p.asked <- state
+	<-p.release
+	return domain.NewAction(domain.ActionFold, nil)
+}
+
+func TestProvider_ReturnsLiveActionWithoutWaitingOnTheCandidate(t *testing.T) {
+	t.Parallel()
+
+	live := &scriptedProvider{steps: []scriptedStep{{action: mustAction(t, domain.ActionCall, nil)}}}
+	candidate := &heldCandidate{release: make(chan struct{}), asked: make(chan domain.HandState, 1)}
+	provider, err := NewProvider(live, candidate, []domain.SeatNo{1})
+	if err != nil {
+		t.Fatalf("NewProvider failed: %v", err)
+	}
+	state := actingState(1)
+	state.Deck = []domain.Card{domain.NewCard(2, domain.SuitClubs)}
+	state.HoleCards = []domain.SeatCards{
+		{SeatNo: 1, Cards: []domain.Card{domain.NewCard(14, domain.SuitSpades), domain.NewCard(13, domain.SuitSpades)}},
+		{SeatNo: 2, Cards: []domain.Card{domain.NewCard(7, domain.SuitHearts), domain.NewCard(2, domain.SuitDiamonds)}},
+	}
+
+	action, err := provider.NextAction(context.Background(), state)
+	if err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+	if action.Kind != domain.ActionCall {
+		t.Fatalf("expected the live call while the candidate is still thinking, got %q", action.Kind)
+	}
+	asked := <-candidate.asked
+	if len(asked.Deck) != 0 || len(asked.HoleCards[1].Cards) != 0 || len(asked.HoleCards[0].Cards) != 2 {
+		t.Fatalf("expected the candidate to see only seat 1's view, got deck %v hole cards %+v", asked.Deck, asked.HoleCards)
+	}
+
+	close(candidate.release)
+	report := provider.Report()
+	if report.Decisions != 1 || report.Divergences != 1 || report.Diverged[0].Shadow.Kind != domain.ActionFold {
+		t.Fatalf("expected the candidate's late fold to be recorded, got %+v", report)
+	}
+}
+
+func TestProvider_ComparesAnAllInAsTheActionItStandsFor(t *testing.T) {
+	t.Parallel()
+
+	allInTo := domain.Chips(500)
+	live := &scriptedProvider{steps: []scriptedStep{{action: mustAction(t, domain.ActionAllIn, nil)}}}
+	candidate := &scriptedProvider{steps: []scriptedStep{{action: mustAction(t, domain.ActionRaise, &allInTo)}}}
+	provider, err := NewProvider(live, candidate, []domain.SeatNo{1})
+	if err != nil {
+		t.Fatalf("NewProvider failed: %v", err)
+	}
+	state := actingState(1)
+	state.CurrentBet = 100
+	state.Seats = []domain.SeatState{domain.NewSeatState(1, 500), domain.NewSeatState(2, 400)}
+	state.Seats[1].CommittedInRound = 100
+
+	action, err := provider.NextAction(context.Background(), state)
+	if err != nil {
+		t.Fatalf("NextAction failed: %v", err)
+	}
+	if action.Kind != domain.ActionAllIn {
+		t.Fatalf("expected the live all-in to be applied as sent, got %q", action.Kind)
+	}
+	report := provider.Report()
+	if report.Agreements != 1 || report.Divergences != 0 {
+		t.Fatalf("expected an all-in to agree with the raise it stands for, got %+v", report)
+	}
+}
+
+func TestProvider_KeepsCountsButOnlyTheLatestDivergences(t *testing.T) {
+	t.Parallel()
+
+	decisions := MaxDivergedDecisions + 5
+	live := &scriptedProvider{}
+	candidate := &scriptedProvider{}
+	for range decisions {
+		live.steps = append(live.steps, scriptedStep{action: mustAction(t, domain.ActionFold, nil)})
+		candidate.steps = append(candidate.steps, scriptedStep{action: mustAction(t, domain.ActionCall, nil)})
+	}
+	provider, err := NewProvider(live, candidate, []domain.SeatNo{1})
+	if err != nil {
+		t.Fatalf("NewProvider failed: %v", err)
+	}
+	for range decisions {
+		if _, err := provider.NextAction(context.Background(), actingState(1)); err != nil {
+			t.Fatalf("NextAction failed: %v", err)
+		}
+	}
+
+	report := provider.Report()
+	if report.Decisions != decisions || report.Divergences != decisions || report.DivergenceRate != 1 {
+		t.Fatalf("expected every decision counted, got decisions=%d divergences=%d rate=%v", report.Decisions, report.Divergences, report.DivergenceRate)
+	}
+	if len(report.Diverged) != MaxDivergedDecisions {
+		t.Fatalf("expected %d listed divergences, got %d", MaxDivergedDecisions, len(report.Diverged))
+	}
+}
+
+func TestProvider_WorksInsideRunner(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	seat1, err := domain.NewSeatNo(1, cfg.MaxSeats)
+	if err != nil {
+		t.Fatalf("NewSeatNo failed: %v", err)
+	}
+	seat2, err := domain.NewSeatNo(2, cfg.MaxSeats)
+	if err != nil {
+		t.Fatalf("NewSeatNo failed: %v", err)
+	}
+
+	foldEverything := &scriptedProvider{}
+	for i := 0; i < 8; i++ {
+		foldEverything.steps = append(foldEverything.steps, scriptedStep{action: mustAction(t, domain.ActionFold, nil)})
+	}
+	callEverything := &scriptedProvider{}
+	for i := 0; i < 8; i++ {
+		callEverything.steps = append(callEverything.steps, scriptedStep{action: mustAction(t, domain.ActionCall, nil)})
+	}
+	provider, err := NewProvider(foldEverything, callEverything, []domain.SeatNo{seat1, seat2})
+	if err != nil {
+		t.Fatalf("NewProvider failed: %v", err)
+	}
+
+	runner := tablerunner.New(provider, tablerunner.RunnerConfig{})
+	result, err := runner.RunHand(context.Background(), tablerunner.RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: seat1,
+		Seats: []domain.SeatState{
+			domain.NewSeatState(seat1, cfg.StartingStack),
+			domain.NewSeatState(seat2, cfg.StartingStack),
+		},
+		Config: cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+	if result.ActionCount != 1 {
+		t.Fatalf("expected the live fold to end the hand after one action, got %d", result.ActionCount)
+	}
+	report := provider.Report()
+	if report.Decisions != 1 || report.Divergences != 1 {
+		t.Fatalf("expected one diverging decision, got %+v", report)
+	}
+}
@@ -0,0 +1,331 @@
+// Package sidemarket runs a play-money prediction market for spectators on
+// all-in hands. Spectators stake virtual points on which seat wins the main
+// pot; the runout settles the market pari-mutuel. Points never touch table
+// chips.
+package sidemarket
+
+import (
+	"errors"
+	"fmt"
+	"sort"
+	"sync"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/equity"
+)
+
+const DefaultStartingPoints uint32 = 1_000
+
+var (
+	ErrMarketNotFound     = errors.New("market not found")
+	ErrMarketClosed       = errors.New("market is closed")
+	ErrUnknownOutcome     = errors.New("seat is not contesting this market")
+	ErrInvalidStake       = errors.New("stake must be greater than zero")
+	ErrInsufficientPoints = errors.New("insufficient points")
+	ErrMissingSpectator   = errors.New("spectator id is required")
+)
+
+type MarketStatus string
+
+const (
+	MarketOpen    MarketStatus = "open"
+	MarketSettled MarketStatus = "settled"
+	MarketVoid    MarketStatus = "void"
+)
+
+type EventKind string
+
+const (
+	EventMarketOpened  EventKind = "market_opened"
+	EventWagerPlaced   EventKind = "wager_placed"
+	EventMarketSettled EventKind = "market_settled"
+	EventMarketVoided  EventKind = "market_voided"
+)
+
+type Wager struct {
+	SpectatorID string        `json:"spectator_id"`
+	SeatNo      domain.SeatNo `json:"seat_no"`
+	Points      uint32        `json:"points"`
+	PlacedAt    time.Time     `json:"placed_at"`
+}
+
+type Payout struct {
+	SpectatorID string `json:"spectator_id"`
+	Points      uint32 `json:"points"`
+}
+
+type Market struct {
+	ID           string          `json:"id"`
+	TableID      string          `json:"table_id"`
+	HandID       string          `json:"hand_id"`
+	HandNo       uint64          `json:"hand_no"`
+	Contenders   []domain.SeatNo `json:"contenders"`
+	Status       MarketStatus    `json:"status"`
+	Wagers       []Wager         `json:"wagers"`
+	WinningSeats []domain.SeatNo `json:"winning_seats,omitempty"`
+	Payouts      []Payout        `json:"payouts,omitempty"`
+	OpenedAt     time.Time       `json:"opened_at"`
+	ClosedAt     *time.Time      `json:"closed_at,omitempty"`
+}
+
+// Event is emitted for every market state change, in order, per table.
+type Event struct {
+	Kind     EventKind `json:"kind"`
+	MarketID string    `json:"market_id"`
+	TableID  string    `json:"table_id"`
+	Wager    *Wager    `json:"wager,omitempty"`
+	Payouts  []Payout  `json:"payouts,omitempty"`
+	At       time.Time `json:"at"`
+}
+
+type Account struct {
+	SpectatorID string `json:"spectator_id"`
+	Points      uint32 `json:"points"`
+}
+
+// Book holds every market and spectator account. New spectators are granted
+// StartingPoints on first use.
+type Book struct {
+	StartingPoints uint32
+
+	mu       sync.Mutex
+	now      func() time.Time
+	markets  map[string]*Market
+	byTable  map[string][]string
+	events   map[string][]Event
+	accounts map[string]uint32
+}
+
+func NewBook() *Book {
+	return &Book{
+		StartingPoints: DefaultStartingPoints,
+		now:            func() time.Time { return time.Now().UTC() },
+		markets:        make(map[string]*Market),
+		byTable:        make(map[string][]string),
+		events:         make(map[string][]Event),
+		accounts:       make(map[string]uint32),
+	}
+}
+
+// Open starts a market for an all-in hand. Opening the same hand twice
+// returns the existing market.
+func (b *Book) Open(state domain.HandState) (Market, bool) {
+	if !state.AllInLocked() {
+		return Market{}, false
+	}
+	contenders := make([]domain.SeatNo, 0, len(state.Seats))
+	for _, seat := range state.Seats {
+		if seat.IsActive() {
+			contenders = append(contenders, seat.SeatNo)
+		}
+	}
+	sort.Slice(contenders, func(i, j int) bool { return contenders[i] < contenders[j] })
+
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	if existing, ok := b.markets[state.HandID]; ok {
+		return cloneMarket(*existing), true
+	}
+	market := &Market{
+		ID:         state.HandID,
+		TableID:    state.TableID,
+		HandID:     state.HandID,
+		HandNo:     state.HandNo,
+		Contenders: contenders,
+		Status:     MarketOpen,
+		Wagers:     []Wager{},
+		OpenedAt:   b.now(),
+	}
+	b.markets[market.ID] = market
+	b.byTable[market.TableID] = append(b.byTable[market.TableID], market.ID)
+	b.emit(Event{Kind: EventMarketOpened, MarketID: market.ID, TableID: market.TableID, At: market.OpenedAt})
+	return cloneMarket(*market), true
+}
+
+func (b *Book) PlaceWager(marketID, spectatorID string, seatNo domain.SeatNo, points uint32) (Wager, error) {
+	if spectatorID == "" {
+		return Wager{}, ErrMissingSpectator
+	}
+	if points == 0 {
+		return Wager{}, ErrInvalidStake
+	}
+
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	market, ok := b.markets[marketID]
+	if !ok {
+		return Wager{}, fmt.Errorf("%w: %s", ErrMarketNotFound, marketID)
+	}
+	if market.Status != MarketOpen {
+		return Wager{}, fmt.Errorf("%w: %s is %s", ErrMarketClosed, marketID, market.Status)
+	}
+	if !containsSeat(market.Contenders, seatNo) {
+		return Wager{}, fmt.Errorf("%w: seat %d", ErrUnknownOutcome, seatNo)
+	}
+	balance := b.balanceLocked(spectatorID)
+	if balance < points {
+		return Wager{}, fmt.Errorf("%w: have %d, staked %d", ErrInsufficientPoints, balance, points)
+	}
+
+	b.accounts[spectatorID] = balance - points
+	wager := Wager{SpectatorID: spectatorID, SeatNo: seatNo, Points: points, PlacedAt: b.now()}
+	market.Wagers = append(market.Wagers, wager)
+	b.emit(Event{Kind: EventWagerPlaced, MarketID: market.ID, TableID: market.TableID, Wager: &wager, At: wager.PlacedAt})
+	return wager, nil
+}
+
+// Settle closes the hand's market using the main pot winners of the final
+// state. The whole pool is shared among wagers on any winning seat in
+// proportion to stake, with leftover points assigned deterministically by
+// equity.SplitChips. If nobody backed a winner, every stake is refunded.
+func (b *Book) Settle(final domain.HandState) (Market, bool, error) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	market, ok := b.markets[final.HandID]
+	if !ok || market.Status != MarketOpen {
+		return Market{}, false, nil
+	}
+
+	winners := mainPotWinners(final.ShowdownAwards)
+	if len(winners) == 0 {
+		b.voidLocked(market)
+		return cloneMarket(*market), true, nil
+	}
+
+	pool := uint32(0)
+	backing := uint64(0)
+	winning := make([]Wager, 0, len(market.Wagers))
+	for _, wager := range market.Wagers {
+		pool += wager.Points
+		if containsSeat(winners, wager.SeatNo) {
+			backing += uint64(wager.Points)
+			winning = append(winning, wager)
+		}
+	}
+
+	payouts := make([]Payout, 0, len(market.Wagers))
+	if backing == 0 {
+		for _, wager := range market.Wagers {
+			payouts = append(payouts, Payout{SpectatorID: wager.SpectatorID, Points: wager.Points})
+		}
+	} else {
+		shares := make([]equity.Ratio, 0, len(winning))
+		for _, wager := range winning {
+			share, err := equity.FromCounts(uint64(wager.Points), backing)
+			if err != nil {
+				return Market{}, false, err
+			}
+			shares = append(shares, share)
+		}
+		amounts, err := equity.SplitChips(pool, shares)
+		if err != nil {
+			return Market{}, false, err
+		}
+		for i, wager := range winning {
+			payouts = append(payouts, Payout{SpectatorID: wager.SpectatorID, Points: amounts[i]})
+		}
+	}
+	for _, payout := range payouts {
+		b.accounts[payout.SpectatorID] = b.balanceLocked(payout.SpectatorID) + payout.Points
+	}
+
+	closedAt := b.now()
+	market.Status = MarketSettled
+	market.WinningSeats = winners
+	market.Payouts = payouts
+	market.ClosedAt = &closedAt
+	b.emit(Event{Kind: EventMarketSettled, MarketID: market.ID, TableID: market.TableID, Payouts: payouts, At: closedAt})
+	return cloneMarket(*market), true, nil
+}
+
+// VoidTable refunds every open market on a table, e.g. when its run stops
+// before the hand finishes.
+func (b *Book) VoidTable(tableID string) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	for _, id := range b.byTable[tableID] {
+		if market := b.markets[id]; market.Status == MarketOpen {
+			b.voidLocked(market)
+		}
+	}
+}
+
+func (b *Book) Markets(tableID string) []Market {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	out := make([]Market, 0, len(b.byTable[tableID]))
+	for _, id := range b.byTable[tableID] {
+		out = append(out, cloneMarket(*b.markets[id]))
+	}
+	return out
+}
+
+func (b *Book) Events(tableID string) []Event {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	return append([]Event(nil), b.events[tableID]...)
+}
+
+func (b *Book) Account(spectatorID string) Account {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+	return Account{SpectatorID: spectatorID, Points: b.balanceLocked(spectatorID)}
+}
+
+func (b *Book) voidLocked(market *Market) {
+	payouts := make([]Payout, 0, len(market.Wagers))
+	for _, wager := range market.Wagers {
+		b.accounts[wager.SpectatorID] = b.balanceLocked(wager.SpectatorID) + wager.Points
+		payouts = append(payouts, Payout{SpectatorID: wager.SpectatorID, Points: wager.Points})
+	}
+	closedAt := b.now()
+	market.Status = MarketVoid
+	market.Payouts = payouts
+	market.ClosedAt = &closedAt
+	b.emit(Event{Kind: EventMarketVoided, MarketID: market.ID, TableID: market.TableID, Payouts: payouts, At: closedAt})
+}
+
+func (b *Book) balanceLocked(spectatorID string) uint32 {
+	balance, ok := b.accounts[spectatorID]
+	if !ok {
+		balance = b.StartingPoints
+		b.accounts[spectatorID] = balance
+	}
+	return balance
+}
+
+func (b *Book) emit(event Event) {
+	b.events[event.TableID] = append(b.events[event.TableID], event)
+}
+
+// mainPotWinners returns the seats awarded the first pot, which every
+// contender is eligible for.
+func mainPotWinners(awards []domain.PotAward) []domain.SeatNo {
+	if len(awards) == 0 {
+		return nil
+	}
+	return append([]domain.SeatNo(nil), awards[0].Seats...)
+}
+
+func containsSeat(seats []domain.SeatNo, seatNo domain.SeatNo) bool {
+	for _, seat := range seats {
+		if seat == seatNo {
+			return true
+		}
+	}
+	return false
+}
+
+func cloneMarket(market Market) Market {
+	out := market
+	out.Contenders = append([]domain.SeatNo(nil), market.Contenders...)
+	out.Wagers = append([]Wager{}, market.Wagers...)
+	out.WinningSeats = append([]domain.SeatNo(nil), market.WinningSeats...)
+	out.Payouts = append([]Payout(nil), market.Payouts...)
+	if market.ClosedAt != nil {
+		closedAt := *market.ClosedAt
+		out.ClosedAt = &closedAt
+	}
+	return out
+}
@@ -0,0 +1,147 @@
+package sidemarket
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestBook_SettlesPariMutuelOnRunout(t *testing.T) {
+	t.Parallel()
+
+	book := NewBook()
+	state := allInState(t)
+	market, ok := book.Open(state)
+	if !ok {
+		t.Fatal("expected market to open for all-in hand")
+	}
+	if !reflect.DeepEqual(market.Contenders, []domain.SeatNo{1, 2}) {
+		t.Fatalf("unexpected contenders: %v", market.Contenders)
+	}
+
+	placeWager(t, book, market.ID, "alice", 1, 100)
+	placeWager(t, book, market.ID, "bob", 1, 200)
+	placeWager(t, book, market.ID, "carol", 2, 101)
+
+	final := state
+	final.Phase = domain.HandPhaseComplete
+	final.ShowdownAwards = []domain.PotAward{{Amount: 2000, Seats: []domain.SeatNo{1}, Reason: "main_pot"}}
+	settled, ok, err := book.Settle(final)
+	if err != nil || !ok {
+		t.Fatalf("Settle failed: ok=%v err=%v", ok, err)
+	}
+	if settled.Status != MarketSettled {
+		t.Fatalf("expected settled market, got %s", settled.Status)
+	}
+
+	// Pool of 401 split 1:2; the leftover point goes to the larger remainder.
+	want := map[string]uint32{"alice": 900 + 134, "bob": 800 + 267, "carol": 899}
+	for spectator, points := range want {
+		if got := book.Account(spectator).Points; got != points {
+			t.Fatalf("%s balance: want %d, got %d", spectator, points, got)
+		}
+	}
+
+	if _, err := book.PlaceWager(market.ID, "alice", 1, 10); !errors.Is(err, ErrMarketClosed) {
+		t.Fatalf("expected ErrMarketClosed, got %v", err)
+	}
+	kinds := make([]EventKind, 0, 5)
+	for _, event := range book.Events(state.TableID) {
+		kinds = append(kinds, event.Kind)
+	}
+	wantKinds := []EventKind{EventMarketOpened, EventWagerPlaced, EventWagerPlaced, EventWagerPlaced, EventMarketSettled}
+	if !reflect.DeepEqual(kinds, wantKinds) {
+		t.Fatalf("unexpected events: %v", kinds)
+	}
+}
+
+func TestBook_RefundsWhenRunStopsOrNobodyBackedWinner(t *testing.T) {
+	t.Parallel()
+
+	book := NewBook()
+	state := allInState(t)
+	market, _ := book.Open(state)
+	placeWager(t, book, market.ID, "alice", 2, 250)
+
+	final := state
+	final.ShowdownAwards = []domain.PotAward{{Amount: 2000, Seats: []domain.SeatNo{1}, Reason: "main_pot"}}
+	if _, _, err := book.Settle(final); err != nil {
+		t.Fatalf("Settle failed: %v", err)
+	}
+	if got := book.Account("alice").Points; got != DefaultStartingPoints {
+		t.Fatalf("expected refund, got %d", got)
+	}
+
+	next := allInState(t)
+	next.HandID = "hand-2"
+	second, _ := book.Open(next)
+	placeWager(t, book, second.ID, "alice", 1, 400)
+	book.VoidTable(next.TableID)
+	markets := book.Markets(next.TableID)
+	if markets[1].Status != MarketVoid {
+		t.Fatalf("expected void market, got %s", markets[1].Status)
+	}
+	if got := book.Account("alice").Points; got != DefaultStartingPoints {
+		t.Fatalf("expected refund after void, got %d", got)
+	}
+}
+
+func TestBook_RejectsInvalidWagers(t *testing.T) {
+	t.Parallel()
+
+	book := NewBook()
+	market, _ := book.Open(allInState(t))
+
+	if _, err := book.PlaceWager(market.ID, "alice", 3, 10); !errors.Is(err, ErrUnknownOutcome) {
+		t.Fatalf("expected ErrUnknownOutcome, got %v", err)
+	}
+	if _, err := book.PlaceWager(market.ID, "alice", 1, DefaultStartingPoints+1); !errors.Is(err, ErrInsufficientPoints) {
+		t.Fatalf("expected ErrInsufficientPoints, got %v", err)
+	}
+	if _, err := book.PlaceWager(market.ID, "alice", 1, 0); !errors.Is(err, ErrInvalidStake) {
+		t.Fatalf("expected ErrInvalidStake, got %v", err)
+	}
+	if _, err := book.PlaceWager("missing", "alice", 1, 10); !errors.Is(err, ErrMarketNotFound) {
+		t.Fatalf("expected ErrMarketNotFound, got %v", err)
+	}
+
+	notLocked := allInState(t)
+	notLocked.Seats[0].Stack = 500
+	notLocked.CurrentBet = 1500
+	if _, ok := book.Open(notLocked); ok {
+		t.Fatal("expected no market while a seat can still act")
+	}
+}
+
+func placeWager(t *testing.T, book *Book, marketID, spectatorID string, seatNo domain.SeatNo, points uint32) {
+	t.Helper()
+	if _, err := book.PlaceWager(marketID, spectatorID, seatNo, points); err != nil {
+		t.Fatalf("PlaceWager failed: %v", err)
+	}
+}
+
+func allInState(t *testing.T) domain.HandState {
+	t.Helper()
+	seats := make([]domain.SeatState, 0, 3)
+	for _, no := range []uint8{1, 2, 3} {
+		seatNo, err := domain.NewSeatNo(no, 6)
+		if err != nil {
+			t.Fatalf("NewSeatNo failed: %v", err)
+		}
+		seats = append(seats, domain.NewSeatState(seatNo, 0))
+	}
+	seats[0].CommittedInRound = 1000
+	seats[1].CommittedInRound = 1000
+	seats[2].Stack = 1000
+	seats[2].Folded = true
+	return domain.HandState{
+		HandID:     "hand-1",
+		TableID:    "table-1",
+		HandNo:     1,
+		Phase:      domain.HandPhaseBetting,
+		CurrentBet: 1000,
+		Seats:      seats,
+	}
+}
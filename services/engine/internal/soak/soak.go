@@ -0,0 +1,356 @@
+// Package soak load-tests the engine with synthetic bots. It seats K bots
+// across M tables, plays every table concurrently through one runner and
+// reports throughput and decision latency, so operators can size a
+// deployment before real competitions. Bots answer in process or, with the
+// loopback transport, over the HTTP bot protocol on 127.0.0.1 so latency
+// includes encoding and the network stack.
+package soak
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"math/rand"
+	"net"
+	"net/http"
+	"slices"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/agentclient"
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/statemachine"
+	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+	"github.com/imaddar/poker-arena/services/engine/pkg/agentprotocol"
+)
+
+var ErrInvalidConfig = errors.New("invalid soak config")
+
+type Transport string
+
+const (
+	TransportInProcess Transport = "inproc"
+	TransportLoopback  Transport = "loopback"
+)
+
+// Config sizes a soak run. Bots are dealt round-robin across Tables, so
+// every table must end up with between two bots and Table.MaxSeats. Seed
+// fixes each bot's decisions; the deck is shuffled as in production.
+type Config struct {
+	Bots          int                `json:"bots"`
+	Tables        int                `json:"tables"`
+	HandsPerTable int                `json:"hands_per_table"`
+	Transport     Transport          `json:"transport"`
+	Seed          int64              `json:"seed"`
+	Table         domain.TableConfig `json:"table"`
+}
+
+func DefaultConfig() Config {
+	return Config{
+		Bots:          12,
+		Tables:        2,
+		HandsPerTable: 100,
+		Transport:     TransportInProcess,
+		Table:         domain.DefaultV0TableConfig(),
+	}
+}
+
+func (c Config) Validate() error {
+	if c.Tables <= 0 {
+		return fmt.Errorf("%w: tables must be positive, got %d", ErrInvalidConfig, c.Tables)
+	}
+	if c.HandsPerTable <= 0 {
+		return fmt.Errorf("%w: hands per table must be positive, got %d", ErrInvalidConfig, c.HandsPerTable)
+	}
+	if c.Bots < 2*c.Tables || c.Bots > c.Tables*int(c.Table.MaxSeats) {
+		return fmt.Errorf("%w: %d bots cannot seat %d tables of 2..%d", ErrInvalidConfig, c.Bots, c.Tables, c.Table.MaxSeats)
+	}
+	if c.Transport != TransportInProcess && c.Transport != TransportLoopback {
+		return fmt.Errorf("%w: unknown transport %q", ErrInvalidConfig, c.Transport)
+	}
+	return nil
+}
+
+// Latency summarizes how long the runner waited on bot decisions.
+type Latency struct {
+	Decisions int     `json:"decisions"`
+	MeanMS    float64 `json:"mean_ms"`
+	P50MS     float64 `json:"p50_ms"`
+	P95MS     float64 `json:"p95_ms"`
+	P99MS     float64 `json:"p99_ms"`
+	MaxMS     float64 `json:"max_ms"`
+}
+
+// Report is the outcome of a soak run. A table that stops early, e.g. once
+// too few bots have chips, keeps the hands it completed and its error is
+// listed in TableErrors.
+type Report struct {
+	Bots             int               `json:"bots"`
+	Tables           int               `json:"tables"`
+	Transport        Transport         `json:"transport"`
+	HandsRequested   int               `json:"hands_requested"`
+	HandsCompleted   int               `json:"hands_completed"`
+	Actions          int               `json:"actions"`
+	Fallbacks        int               `json:"fallbacks"`
+	ElapsedMS        float64           `json:"elapsed_ms"`
+	HandsPerSecond   float64           `json:"hands_per_second"`
+	ActionsPerSecond float64           `json:"actions_per_second"`
+	DecisionLatency  Latency           `json:"decision_latency"`
+	TableErrors      map[string]string `json:"table_errors,omitempty"`
+}
+
+// Run plays cfg.HandsPerTable hands on every table and reports the load. It
+// fails only when the run cannot be set up; errors from individual tables
+// are reported.
+func Run(ctx context.Context, cfg Config) (Report, error) {
+	if err := cfg.Validate(); err != nil {
+		return Report{}, err
+	}
+
+	tables := seatBots(cfg)
+	var provider tablerunner.ActionProvider = inProcessProvider{bots: tables.bySeat}
+	if cfg.Transport == TransportLoopback {
+		server, err := serveLoopback(tables.bySeat)
+		if err != nil {
+			return Report{}, err
+		}
+		defer server.Close()
+		provider = agentclient.ActionProvider{
+			Client:           agentclient.New(time.Duration(cfg.Table.ActionTimeoutMS) * time.Millisecond),
+			Endpoints:        server,
+			DefaultTimeoutMS: cfg.Table.ActionTimeoutMS,
+		}
+	}
+	timed := &timedProvider{inner: provider}
+	runner := tablerunner.New(timed, tablerunner.RunnerConfig{})
+
+	report := Report{
+		Bots:           cfg.Bots,
+		Tables:         cfg.Tables,
+		Transport:      cfg.Transport,
+		HandsRequested: cfg.Tables * cfg.HandsPerTable,
+	}
+	var (
+		mu sync.Mutex
+		wg sync.WaitGroup
+	)
+	start := time.Now()
+	for _, t := range tables.tables {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			result, err := runner.RunTable(ctx, tablerunner.RunTableInput{
+				TableID:      t.id,
+				StartingHand: 1,
+				HandsToRun:   cfg.HandsPerTable,
+				ButtonSeat:   t.seats[0].SeatNo,
+				Seats:        t.seats,
+				Config:       cfg.Table,
+			})
+			mu.Lock()
+			defer mu.Unlock()
+			report.HandsCompleted += result.HandsCompleted
+			report.Actions += result.TotalActions
+			report.Fallbacks += result.TotalFallbacks
+			if err != nil {
+				if report.TableErrors == nil {
+					report.TableErrors = make(map[string]string)
+				}
+				report.TableErrors[t.id] = err.Error()
+			}
+		}()
+	}
+	wg.Wait()
+	elapsed := time.Since(start)
+
+	report.ElapsedMS = milliseconds(elapsed)
+	if seconds := elapsed.Seconds(); seconds > 0 {
+		report.HandsPerSecond = float64(report.HandsCompleted) / seconds
+		report.ActionsPerSecond = float64(report.Actions) / seconds
+	}
+	report.DecisionLatency = timed.summary()
+	return report, nil
+}
+
+type table struct {
+	id    string
+	seats []domain.SeatState
+}
+
+type seatKey struct {
+	tableID string
+	seatNo  domain.SeatNo
+}
+
+type seating struct {
+	tables []table
+	bySeat map[seatKey]*bot
+}
+
+// seatBots deals bot k to table k mod cfg.Tables, filling seats from 1.
+func seatBots(cfg Config) seating {
+	out := seating{
+		tables: make([]table, cfg.Tables),
+		bySeat: make(map[seatKey]*bot, cfg.Bots),
+	}
+	for i := range out.tables {
+		out.tables[i].id = fmt.Sprintf("soak-table-%d", i+1)
+	}
+	for k := range cfg.Bots {
+		t := &out.tables[k%cfg.Tables]
+		seatNo := domain.SeatNo(len(t.seats) + 1)
+		t.seats = append(t.seats, domain.NewSeatState(seatNo, cfg.Table.StartingStack))
+		b := &bot{id: strconv.Itoa(k + 1), rng: rand.New(rand.NewSource(cfg.Seed + int64(k)))}
+		out.bySeat[seatKey{tableID: t.id, seatNo: seatNo}] = b
+	}
+	return out
+}
+
+// bot checks when it can and otherwise calls nine times in ten, folding the
+// rest, so tables play long hands without busting each other quickly.
+type bot struct {
+	id string
+
+	mu  sync.Mutex
+	rng *rand.Rand
+}
+
+func (b *bot) decide(legal []string) string {
+	if slices.Contains(legal, string(domain.ActionCheck)) {
+		return string(domain.ActionCheck)
+	}
+	b.mu.Lock()
+	roll := b.rng.Intn(10)
+	b.mu.Unlock()
+	if roll > 0 && slices.Contains(legal, string(domain.ActionCall)) {
+		return string(domain.ActionCall)
+	}
+	return string(domain.ActionFold)
+}
+
+type inProcessProvider struct {
+	bots map[seatKey]*bot
+}
+
+func (p inProcessProvider) NextAction(_ context.Context, state domain.HandState) (domain.Action, error) {
+	b, ok := p.bots[seatKey{tableID: state.TableID, seatNo: state.ActingSeat}]
+	if !ok {
+		return domain.Action{}, fmt.Errorf("no bot at %s seat %d", state.TableID, state.ActingSeat)
+	}
+	legal, err := statemachine.LegalActions(state, state.ActingSeat)
+	if err != nil {
+		return domain.Action{}, err
+	}
+	kinds := make([]string, 0, len(legal.Kinds))
+	for _, kind := range legal.Kinds {
+		kinds = append(kinds, string(kind))
+	}
+	return domain.NewAction(domain.ActionKind(b.decide(kinds)), nil)
+}
+
+// loopbackServer serves every bot over the bot protocol at /bots/{id} on a
+// 127.0.0.1 port.
+type loopbackServer struct {
+	baseURL string
+	server  *http.Server
+	bySeat  map[seatKey]*bot
+	byID    map[string]*bot
+}
+
+func serveLoopback(bots map[seatKey]*bot) (*loopbackServer, error) {
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		return nil, fmt.Errorf("listen on loopback: %w", err)
+	}
+	s := &loopbackServer{
+		baseURL: "http://" + listener.Addr().String(),
+		bySeat:  bots,
+		byID:    make(map[string]*bot, len(bots)),
+	}
+	for _, b := range bots {
+		s.byID[b.id] = b
+	}
+	s.server = &http.Server{Handler: http.HandlerFunc(s.serveDecision)}
+	go func() { _ = s.server.Serve(listener) }()
+	return s, nil
+}
+
+func (s *loopbackServer) Close() error {
+	return s.server.Close()
+}
+
+func (s *loopbackServer) EndpointForSeat(state domain.HandState, seat domain.SeatNo) (string, error) {
+	b, ok := s.bySeat[seatKey{tableID: state.TableID, seatNo: seat}]
+	if !ok {
+		return "", fmt.Errorf("no bot at %s seat %d", state.TableID, seat)
+	}
+	return s.baseURL + "/bots/" + b.id, nil
+}
+
+func (s *loopbackServer) serveDecision(w http.ResponseWriter, r *http.Request) {
+	b, ok := s.byID[strings.TrimPrefix(r.URL.Path, "/bots/")]
+	if !ok || r.Method != http.MethodPost {
+		http.NotFound(w, r)
+		return
+	}
+	var req agentprotocol.DecisionRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		http.Error(w, err.Error(), http.StatusBadRequest)
+		return
+	}
+	w.Header().Set("Content-Type", "application/json")
+	_ = json.NewEncoder(w).Encode(agentprotocol.DecisionResponse{Action: b.decide(req.LegalActions)})
+}
+
+// timedProvider records how long each decision took, fallbacks included.
+type timedProvider struct {
+	inner tablerunner.ActionProvider
+
+	mu      sync.Mutex
+	samples []time.Duration
+}
+
+func (p *timedProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	start := time.Now()
+	action, err := p.inner.NextAction(ctx, state)
+	elapsed := time.Since(start)
+	p.mu.Lock()
+	p.samples = append(p.samples, elapsed)
+	p.mu.Unlock()
+	return action, err
+}
+
+func (p *timedProvider) summary() Latency {
+	p.mu.Lock()
+	samples := slices.Clone(p.samples)
+	p.mu.Unlock()
+	if len(samples) == 0 {
+		return Latency{}
+	}
+	slices.Sort(samples)
+	var total time.Duration
+	for _, sample := range samples {
+		total += sample
+	}
+	return Latency{
+		Decisions: len(samples),
+		MeanMS:    milliseconds(total / time.Duration(len(samples))),
+		P50MS:     milliseconds(percentile(samples, 50)),
+		P95MS:     milliseconds(percentile(samples, 95)),
+		P99MS:     milliseconds(percentile(samples, 99)),
+		MaxMS:     milliseconds(samples[len(samples)-1]),
+	}
+}
+
+// percentile is the nearest-rank percentile of sorted.
+func percentile(sorted []time.Duration, p int) time.Duration {
+	rank := (p*len(sorted) + 99) / 100
+	return sorted[max(rank, 1)-1]
+}
+
+func milliseconds(d time.Duration) float64 {
+	return float64(d) / float64(time.Millisecond)
+}
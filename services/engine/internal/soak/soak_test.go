@@ -0,0 +1,57 @@
+package soak
+
+import (
+	"context"
+	"errors"
+	"testing"
+)
+
+func TestRun_PlaysEveryTableOverEachTransport(t *testing.T) {
+	t.Parallel()
+
+	for _, transport := range []Transport{TransportInProcess, TransportLoopback} {
+		cfg := DefaultConfig()
+		cfg.Bots = 5
+		cfg.Tables = 2
+		cfg.HandsPerTable = 10
+		cfg.Transport = transport
+
+		report, err := Run(context.Background(), cfg)
+		if err != nil {
+			t.Fatalf("%s: Run failed: %v", transport, err)
+		}
+		if len(report.TableErrors) != 0 {
+			t.Fatalf("%s: expected every table to finish, got %v", transport, report.TableErrors)
+		}
+		if report.HandsRequested != 20 || report.HandsCompleted != 20 {
+			t.Fatalf("%s: expected 20 of 20 hands, got %d of %d", transport, report.HandsCompleted, report.HandsRequested)
+		}
+		if report.Fallbacks != 0 {
+			t.Fatalf("%s: expected every bot to answer, got %d fallbacks", transport, report.Fallbacks)
+		}
+		latency := report.DecisionLatency
+		if latency.Decisions != report.Actions || latency.P50MS > latency.P99MS || latency.P99MS > latency.MaxMS {
+			t.Fatalf("%s: expected a latency sample per action in order, got %+v for %d actions", transport, latency, report.Actions)
+		}
+		if report.HandsPerSecond <= 0 {
+			t.Fatalf("%s: expected positive throughput, got %+v", transport, report)
+		}
+	}
+}
+
+func TestConfig_RejectsTablesTheBotsCannotSeat(t *testing.T) {
+	t.Parallel()
+
+	for name, mutate := range map[string]func(*Config){
+		"one bot per table": func(c *Config) { c.Bots, c.Tables = 3, 2 },
+		"overfull tables":   func(c *Config) { c.Bots, c.Tables = 13, 2 },
+		"no hands":          func(c *Config) { c.HandsPerTable = 0 },
+		"unknown transport": func(c *Config) { c.Transport = "carrier-pigeon" },
+	} {
+		cfg := DefaultConfig()
+		mutate(&cfg)
+		if _, err := Run(context.Background(), cfg); !errors.Is(err, ErrInvalidConfig) {
+			t.Fatalf("%s: expected ErrInvalidConfig, got %v", name, err)
+		}
+	}
+}
@@ -0,0 +1,145 @@
+package statemachine
+
+import (
+	"fmt"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// LegalActionSet is everything one seat may do at its decision. Bets are
+// sized by amount and raises by the total raised to; the minimums drop to
+// the seat's all-in when it cannot afford a full bet or raise. On a
+// pot-limit table the maximums stop at the pot limit, and on a fixed-limit
+// one they equal the minimums; moving all in is only offered when the
+// all-in is within them.
+type LegalActionSet struct {
+	SeatNo     domain.SeatNo       `json:"seat_no"`
+	Kinds      []domain.ActionKind `json:"kinds"`
+	ToCall     domain.Chips        `json:"to_call"`
+	MinBet     domain.Chips        `json:"min_bet,omitempty"`
+	MaxBet     domain.Chips        `json:"max_bet,omitempty"`
+	MinRaiseTo domain.Chips        `json:"min_raise_to,omitempty"`
+	MaxRaiseTo domain.Chips        `json:"max_raise_to,omitempty"`
+}
+
+func (s LegalActionSet) Allows(kind domain.ActionKind) bool {
+	return slices.Contains(s.Kinds, kind)
+}
+
+// LegalActions lists what seatNo may do in the current betting round. Only
+// the acting seat has a decision; asking for any other fails with
+// ErrNotActingSeat.
+func LegalActions(state domain.HandState, seatNo domain.SeatNo) (LegalActionSet, error) {
+	if state.Phase == domain.HandPhaseComplete || state.Phase == domain.HandPhaseShowdown {
+		return LegalActionSet{}, ErrHandAlreadyComplete
+	}
+	if seatNo != state.ActingSeat {
+		return LegalActionSet{}, fmt.Errorf("%w: seat %d asked, seat %d is acting", ErrNotActingSeat, seatNo, state.ActingSeat)
+	}
+	idx := seatIndex(state.Seats, seatNo)
+	if idx < 0 {
+		return LegalActionSet{}, ErrInvalidTransition
+	}
+	seat := state.Seats[idx]
+	if !isEligibleToAct(seat) {
+		return LegalActionSet{}, ErrNotActingSeat
+	}
+
+	set := LegalActionSet{SeatNo: seatNo, Kinds: []domain.ActionKind{domain.ActionFold}, ToCall: computeToCall(seat, state.CurrentBet)}
+	switch {
+	case set.ToCall > 0:
+		set.Kinds = append(set.Kinds, domain.ActionCall)
+	case state.CurrentBet == 0:
+		set.Kinds = append(set.Kinds, domain.ActionCheck, domain.ActionBet)
+		set.MinBet = min(state.MinRaiseTo, seat.Stack)
+		set.MaxBet = maxRaiseTo(state, seat)
+		if seat.CommittedInRound+seat.Stack <= set.MaxBet {
+			set.Kinds = append(set.Kinds, domain.ActionAllIn)
+		}
+		return set, nil
+	default:
+		// The option: a big blind or straddle that has only been called may
+		// be checked or raised.
+		set.Kinds = append(set.Kinds, domain.ActionCheck)
+	}
+
+	minRaiseTo, canRaise := MinRaiseTo(state, seatNo)
+	if canRaise {
+		set.Kinds = append(set.Kinds, domain.ActionRaise)
+		set.MaxRaiseTo = maxRaiseTo(state, seat)
+		set.MinRaiseTo = minRaiseTo
+	}
+	if (canRaise && seat.CommittedInRound+seat.Stack <= set.MaxRaiseTo) || seat.Stack <= set.ToCall {
+		set.Kinds = append(set.Kinds, domain.ActionAllIn)
+	}
+	return set, nil
+}
+
+// maxRaiseTo is the largest total seat may bet or raise to: its all-in,
+// or less where the table's betting structure limits it. A fixed-limit
+// bet or raise is exactly one bet, and a pot-limit one goes up to the pot
+// limit, though never less than a full bet or raise.
+func maxRaiseTo(state domain.HandState, seat domain.SeatState) domain.Chips {
+	allIn := seat.CommittedInRound + seat.Stack
+	if state.BettingStructure == domain.BettingFixedLimit {
+		return min(allIn, state.MinRaiseTo)
+	}
+	limit, ok := state.PotLimit(seat)
+	if !ok {
+		return allIn
+	}
+	return min(allIn, max(limit, state.MinRaiseTo))
+}
+
+// limitExceeded is the error for a bet or raise past maxRaiseTo.
+func limitExceeded(state domain.HandState) error {
+	if state.BettingStructure == domain.BettingFixedLimit {
+		return ErrFixedLimitExceeded
+	}
+	return ErrPotLimitExceeded
+}
+
+// MinRaiseTo is the smallest total seatNo may raise to against the current
+// bet: a full raise, the size of the last full bet or raise on top of it, or
+// the seat's all-in when it cannot afford that. It reports false when the
+// seat may only call or fold, as on a capped fixed-limit street or when the
+// seat has acted and faces no more than an incomplete all-in raise since.
+func MinRaiseTo(state domain.HandState, seatNo domain.SeatNo) (domain.Chips, bool) {
+	if state.Phase == domain.HandPhaseComplete || state.Phase == domain.HandPhaseShowdown || state.CurrentBet == 0 {
+		return 0, false
+	}
+	idx := seatIndex(state.Seats, seatNo)
+	if idx < 0 {
+		return 0, false
+	}
+	seat := state.Seats[idx]
+	if !isEligibleToAct(seat) || seat.Stack <= computeToCall(seat, state.CurrentBet) {
+		return 0, false
+	}
+	if state.RaiseCapReached() || !bettingReopened(state, seat) {
+		return 0, false
+	}
+	return min(state.MinRaiseTo, seat.CommittedInRound+seat.Stack), true
+}
+
+// bettingReopened reports whether seat may raise again. An all-in raise
+// short of a full raise does not reopen the betting to seats that have
+// already acted, unless such raises add up to a full raise over what the
+// seat last put in. Hands under rules before domain.RulesV2 reopen the
+// betting on any raise.
+func bettingReopened(state domain.HandState, seat domain.SeatState) bool {
+	if !state.RulesVersion.FullRaiseRule() {
+		return true
+	}
+	return !seat.HasActedThisRound || state.CurrentBet-seat.CommittedInRound >= state.LastFullRaise
+}
+
+// ApplySeatAction applies action on behalf of seatNo, refusing any seat but
+// the acting one.
+func ApplySeatAction(state domain.HandState, seatNo domain.SeatNo, action domain.Action) (domain.HandState, error) {
+	if seatNo != state.ActingSeat {
+		return domain.HandState{}, fmt.Errorf("%w: seat %d acted, seat %d is acting", ErrNotActingSeat, seatNo, state.ActingSeat)
+	}
+	return ApplyAction(state, action)
+}
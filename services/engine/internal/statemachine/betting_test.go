@@ -0,0 +1,156 @@
+package statemachine
+
+import (
+	"errors"
+	"slices"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestLegalActionsFacingTheBigBlind(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	state := startedFourSeatHand(t)
+
+	legal, err := LegalActions(state, state.ActingSeat)
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	wantKinds := []domain.ActionKind{domain.ActionFold, domain.ActionCall, domain.ActionRaise, domain.ActionAllIn}
+	if !slices.Equal(legal.Kinds, wantKinds) || legal.Allows(domain.ActionCheck) {
+		t.Fatalf("expected %v, got %v", wantKinds, legal.Kinds)
+	}
+	if legal.ToCall != cfg.BigBlind || legal.MinRaiseTo != 2*cfg.BigBlind || legal.MaxRaiseTo != cfg.StartingStack {
+		t.Fatalf("unexpected amounts %+v", legal)
+	}
+
+	other := state.ButtonSeat
+	if _, err := LegalActions(state, other); !errors.Is(err, ErrNotActingSeat) {
+		t.Fatalf("expected ErrNotActingSeat for seat %d, got %v", other, err)
+	}
+	if _, err := ApplySeatAction(state, other, mustAction(t, domain.ActionCall, nil)); !errors.Is(err, ErrNotActingSeat) {
+		t.Fatalf("expected ErrNotActingSeat applying for seat %d, got %v", other, err)
+	}
+	next, err := ApplySeatAction(state, state.ActingSeat, mustAction(t, domain.ActionCall, nil))
+	if err != nil {
+		t.Fatalf("ApplySeatAction failed: %v", err)
+	}
+	if next.ActingSeat == state.ActingSeat {
+		t.Fatalf("expected the action to move on, got seat %d still acting", next.ActingSeat)
+	}
+}
+
+func TestLegalActionsGiveTheBigBlindItsOption(t *testing.T) {
+	t.Parallel()
+
+	engine, cfg := newHeadsUpEngine(t)
+	if err := engine.Apply(mustAction(t, domain.ActionCall, nil)); err != nil {
+		t.Fatalf("call failed: %v", err)
+	}
+	state := engine.State()
+
+	legal, err := LegalActions(state, state.ActingSeat)
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	wantKinds := []domain.ActionKind{domain.ActionFold, domain.ActionCheck, domain.ActionRaise, domain.ActionAllIn}
+	if !slices.Equal(legal.Kinds, wantKinds) || legal.ToCall != 0 {
+		t.Fatalf("expected the big blind to check or raise its option, got %+v", legal)
+	}
+	if legal.MinRaiseTo != 2*cfg.BigBlind || legal.MaxRaiseTo != cfg.StartingStack {
+		t.Fatalf("unexpected raise amounts %+v", legal)
+	}
+	raiseTo := legal.MinRaiseTo
+	if _, err := ApplySeatAction(state, state.ActingSeat, mustAction(t, domain.ActionRaise, &raiseTo)); err != nil {
+		t.Fatalf("expected the offered raise to apply, got %v", err)
+	}
+}
+
+func TestLegalActionsOpeningAPostflopStreet(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	state := startedTwoSeatPostFlopRound(t, domain.StreetFlop)
+
+	legal, err := LegalActions(state, state.ActingSeat)
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	wantKinds := []domain.ActionKind{domain.ActionFold, domain.ActionCheck, domain.ActionBet, domain.ActionAllIn}
+	if !slices.Equal(legal.Kinds, wantKinds) || legal.MinBet != cfg.BigBlind || legal.MaxBet != cfg.StartingStack || legal.ToCall != 0 {
+		t.Fatalf("unexpected legal actions %+v", legal)
+	}
+
+	small := cfg.BigBlind / 2
+	if _, err := ApplySeatAction(state, state.ActingSeat, mustAction(t, domain.ActionBet, &small)); !errors.Is(err, ErrIllegalAction) {
+		t.Fatalf("expected ErrIllegalAction for a bet below the big blind, got %v", err)
+	}
+
+	setSeatStack(t, &state, state.ActingSeat, small)
+	legal, err = LegalActions(state, state.ActingSeat)
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	if legal.MinBet != small || legal.MaxBet != small {
+		t.Fatalf("expected a short stack to bet only all-in, got %+v", legal)
+	}
+	if _, err := ApplySeatAction(state, state.ActingSeat, mustAction(t, domain.ActionBet, &small)); err != nil {
+		t.Fatalf("expected a short all-in bet to be legal, got %v", err)
+	}
+
+	state.Phase = domain.HandPhaseComplete
+	if _, err := LegalActions(state, state.ActingSeat); !errors.Is(err, ErrHandAlreadyComplete) {
+		t.Fatalf("expected ErrHandAlreadyComplete, got %v", err)
+	}
+}
+
+func TestLegalActionsCapPotLimitRaisesAtThePot(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.BettingStructure = domain.BettingPotLimit
+	state, err := StartNewHand(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      mustSeats(t, cfg, 1, 2, 3, 4),
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+
+	// Facing the big blind, a pot raise calls 100 into a pot of 150 and
+	// raises by the 250 that makes.
+	legal, err := LegalActions(state, state.ActingSeat)
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	wantKinds := []domain.ActionKind{domain.ActionFold, domain.ActionCall, domain.ActionRaise}
+	if !slices.Equal(legal.Kinds, wantKinds) || legal.MinRaiseTo != 200 || legal.MaxRaiseTo != 350 {
+		t.Fatalf("expected raises from 200 to 350 and no all-in, got %+v", legal)
+	}
+	over := legal.MaxRaiseTo + 1
+	if _, err := ApplyAction(state, mustAction(t, domain.ActionRaise, &over)); !errors.Is(err, ErrPotLimitExceeded) {
+		t.Fatalf("expected ErrPotLimitExceeded raising to %d, got %v", over, err)
+	}
+	if _, err := ApplyAction(state, mustAction(t, domain.ActionAllIn, nil)); !errors.Is(err, ErrPotLimitExceeded) {
+		t.Fatalf("expected ErrPotLimitExceeded moving all in, got %v", err)
+	}
+	state, err = ApplyAction(state, mustAction(t, domain.ActionRaise, &legal.MaxRaiseTo))
+	if err != nil {
+		t.Fatalf("expected a pot-sized raise to be legal, got %v", err)
+	}
+
+	// The next seat calls 350 into a pot of 500, so may raise to 1200.
+	if legal, err = LegalActions(state, state.ActingSeat); err != nil || legal.MaxRaiseTo != 1200 {
+		t.Fatalf("expected a re-raise up to 1200, got %+v (%v)", legal, err)
+	}
+	// A stack inside the pot limit may still move all in.
+	setSeatStack(t, &state, state.ActingSeat, 1000)
+	if legal, err = LegalActions(state, state.ActingSeat); err != nil || legal.MaxRaiseTo != 1000 || !legal.Allows(domain.ActionAllIn) {
+		t.Fatalf("expected an all-in to 1000 within the limit, got %+v (%v)", legal, err)
+	}
+}
@@ -0,0 +1,143 @@
+package statemachine
+
+import (
+	"fmt"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+// HandOutcome says how a completed hand was decided.
+type HandOutcome string
+
+const (
+	// HandOutcomeUncontested means everyone else folded and the last seat
+	// took the pot without a showdown, so no hole cards were shown.
+	HandOutcomeUncontested HandOutcome = "uncontested"
+	// HandOutcomeShowdown means two or more seats saw the hand through and
+	// the pots went to the best hands.
+	HandOutcomeShowdown HandOutcome = "showdown"
+)
+
+// HandEngine owns one hand and drives it from the blinds to the award.
+// Callers feed it the acting seat's actions; it posts blinds and deals,
+// closes each betting street, awards the pot when everyone else folds, runs
+// the board out when the players are all-in, and settles the showdown.
+// Every change is recorded as a HandEvent; see Events and ReplayHand.
+type HandEngine struct {
+	state  domain.HandState
+	events []HandEvent
+	// owed holds the events a replayed start or action caused that the log
+	// has yet to show.
+	owed []HandEvent
+}
+
+// NewHandEngine posts the blinds and deals the hole cards. A hand that is
+// already decided, such as one where the blinds put everyone all-in, is
+// played to completion before it returns.
+func NewHandEngine(input StartNewHandInput) (*HandEngine, error) {
+	state, events, err := startHand(input)
+	if err != nil {
+		return nil, err
+	}
+	numberEvents(events, 0)
+	return &HandEngine{state: state, events: events}, nil
+}
+
+// Apply plays action for the acting seat. On an error the hand is left as
+// it was.
+func (e *HandEngine) Apply(action domain.Action) error {
+	if len(e.owed) > 0 {
+		return fmt.Errorf("%w: replay is waiting for %s", ErrEventMismatch, e.owed[0].Kind)
+	}
+	next, events, err := takeAction(e.state, action)
+	if err != nil {
+		return err
+	}
+	numberEvents(events, len(e.events))
+	e.state = next
+	e.events = append(e.events, events...)
+	return nil
+}
+
+// State returns a copy of the hand as it stands.
+func (e *HandEngine) State() domain.HandState {
+	return cloneState(e.state)
+}
+
+// LegalActions lists what the acting seat may do.
+func (e *HandEngine) LegalActions() (LegalActionSet, error) {
+	return LegalActions(e.state, e.state.ActingSeat)
+}
+
+// Done reports whether the pots have been awarded.
+func (e *HandEngine) Done() bool {
+	return e.state.Phase == domain.HandPhaseComplete
+}
+
+// Outcome reports how the hand was decided, or false while it is still
+// being played.
+func (e *HandEngine) Outcome() (HandOutcome, bool) {
+	return Outcome(e.state)
+}
+
+// Outcome reports how a completed hand was decided. It reports false for a
+// hand that is not yet complete.
+func Outcome(state domain.HandState) (HandOutcome, bool) {
+	if state.Phase != domain.HandPhaseComplete {
+		return "", false
+	}
+	if countNonFoldedActiveSeats(state.Seats) <= 1 {
+		return HandOutcomeUncontested, true
+	}
+	return HandOutcomeShowdown, true
+}
+
+// FinishHand settles a hand that has reached showdown: betting can close
+// with players all-in before the river, so any board cards still owed are
+// dealt first and the pots are then awarded. Hands in any other phase are
+// returned unchanged.
+func FinishHand(state domain.HandState) (domain.HandState, error) {
+	if state.Phase != domain.HandPhaseShowdown {
+		return state, nil
+	}
+	next := state
+	for {
+		dealt, ok, err := RunOutStreet(next)
+		if err != nil {
+			return state, err
+		}
+		if !ok {
+			break
+		}
+		next = dealt
+	}
+	resolved, _, err := rules.ResolvePots(next)
+	if err != nil {
+		return state, err
+	}
+	return resolved, nil
+}
+
+// RunOutStreet deals the next street of a hand whose betting closed before
+// the river, so drivers can report the runout one street at a time. It
+// reports false, with state unchanged, once the board is complete or when
+// the hand is not at showdown. A hand whose table runs it more than once
+// deals each street on every board.
+func RunOutStreet(state domain.HandState) (domain.HandState, bool, error) {
+	if state.Phase != domain.HandPhaseShowdown || state.Street == domain.StreetRiver {
+		return state, false, nil
+	}
+	street := domain.StreetFlop
+	switch state.Street {
+	case domain.StreetFlop:
+		street = domain.StreetTurn
+	case domain.StreetTurn:
+		street = domain.StreetRiver
+	}
+	dealt, err := rules.NewDealer(nil).DealStreet(rules.StartRunouts(cloneState(state)), street)
+	if err != nil {
+		return state, false, err
+	}
+	return dealt, true, nil
+}
@@ -0,0 +1,289 @@
+package statemachine
+
+import (
+	"encoding/json"
+	"errors"
+	"slices"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+func newHeadsUpEngine(t *testing.T) (*HandEngine, domain.TableConfig) {
+	t.Helper()
+
+	cfg := domain.DefaultV0TableConfig()
+	engine, err := NewHandEngine(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      mustSeats(t, cfg, 1, 2),
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+		Shuffler:   rules.NewSeededShuffler(7),
+		HandID:     "hand-1",
+	})
+	if err != nil {
+		t.Fatalf("NewHandEngine failed: %v", err)
+	}
+	return engine, cfg
+}
+
+func TestHandEngine_RunsOutThePreflopAllInAndAwardsThePot(t *testing.T) {
+	t.Parallel()
+
+	engine, cfg := newHeadsUpEngine(t)
+	if engine.Done() || engine.State().ActingSeat != mustSeatNo(t, cfg, 1) {
+		t.Fatalf("expected the button to act first heads-up, got %+v", engine.State())
+	}
+	if err := engine.Apply(mustAction(t, domain.ActionAllIn, nil)); err != nil {
+		t.Fatalf("all-in failed: %v", err)
+	}
+	legal, err := engine.LegalActions()
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	if !legal.Allows(domain.ActionCall) || legal.Allows(domain.ActionRaise) {
+		t.Fatalf("expected only a call or fold against the all-in, got %+v", legal.Kinds)
+	}
+	if err := engine.Apply(mustAction(t, domain.ActionCall, nil)); err != nil {
+		t.Fatalf("call failed: %v", err)
+	}
+
+	state := engine.State()
+	if !engine.Done() || state.Phase != domain.HandPhaseComplete {
+		t.Fatalf("expected the hand complete, got phase %q", state.Phase)
+	}
+	if len(state.Board) != 5 || state.Street != domain.StreetRiver {
+		t.Fatalf("expected the board run out to the river, got %d cards on the %s", len(state.Board), state.Street)
+	}
+	burns := 0
+	for _, dealt := range state.DealSequence {
+		if dealt.Target == domain.DealTargetBurn {
+			burns++
+		}
+	}
+	if burns != 3 {
+		t.Fatalf("expected a burn before each street, got %d", burns)
+	}
+	if len(state.ShowdownAwards) == 0 || state.ShowdownAwards[0].Amount != 2*cfg.StartingStack {
+		t.Fatalf("expected both stacks in the main pot, got %+v", state.ShowdownAwards)
+	}
+	if chipTotal(state) != 2*cfg.StartingStack {
+		t.Fatalf("expected chips conserved, got %d", chipTotal(state))
+	}
+	if outcome, ok := engine.Outcome(); !ok || outcome != HandOutcomeShowdown {
+		t.Fatalf("expected a showdown outcome, got %q (%v)", outcome, ok)
+	}
+}
+
+func TestHandEngine_AwardsTheFoldAndRejectsLaterActions(t *testing.T) {
+	t.Parallel()
+
+	engine, cfg := newHeadsUpEngine(t)
+	if _, ok := engine.Outcome(); ok {
+		t.Fatalf("expected no outcome before the hand is decided")
+	}
+	before := engine.State()
+	if err := engine.Apply(mustAction(t, domain.ActionCheck, nil)); !errors.Is(err, ErrIllegalAction) {
+		t.Fatalf("expected checking into the big blind to be illegal, got %v", err)
+	}
+	if after := engine.State(); after.ActingSeat != before.ActingSeat || after.Pot != before.Pot {
+		t.Fatalf("expected an illegal action to leave the hand as it was, got %+v", after)
+	}
+
+	if err := engine.Apply(mustAction(t, domain.ActionFold, nil)); err != nil {
+		t.Fatalf("fold failed: %v", err)
+	}
+	state := engine.State()
+	if !engine.Done() || len(state.Board) != 0 {
+		t.Fatalf("expected the hand over without a board, got phase %q board %v", state.Phase, state.Board)
+	}
+	if bb := findSeat(t, state, mustSeatNo(t, cfg, 2)); bb.Stack != cfg.StartingStack+cfg.SmallBlind {
+		t.Fatalf("expected the big blind to win the small blind, got stack %d", bb.Stack)
+	}
+	if outcome, ok := engine.Outcome(); !ok || outcome != HandOutcomeUncontested {
+		t.Fatalf("expected an uncontested outcome, got %q (%v)", outcome, ok)
+	}
+	if len(state.ShowdownReveals) != 0 {
+		t.Fatalf("expected no hole cards shown for an uncontested pot, got %+v", state.ShowdownReveals)
+	}
+	if err := engine.Apply(mustAction(t, domain.ActionCall, nil)); !errors.Is(err, ErrHandAlreadyComplete) {
+		t.Fatalf("expected ErrHandAlreadyComplete, got %v", err)
+	}
+}
+
+func TestHandEngine_RecordsAndReplaysItsEventLog(t *testing.T) {
+	t.Parallel()
+
+	engine, cfg := newHeadsUpEngine(t)
+	for _, kind := range []domain.ActionKind{domain.ActionCall, domain.ActionCheck, domain.ActionFold} {
+		if err := engine.Apply(mustAction(t, kind, nil)); err != nil {
+			t.Fatalf("%s failed: %v", kind, err)
+		}
+	}
+
+	events := engine.Events()
+	var kinds []HandEventKind
+	for i, event := range events {
+		if event.Seq != i {
+			t.Fatalf("expected event %d numbered %d, got %d", i, i, event.Seq)
+		}
+		kinds = append(kinds, event.Kind)
+	}
+	want := []HandEventKind{
+		HandEventStarted, HandEventBlindsPosted, HandEventCardsDealt,
+		HandEventActionTaken, HandEventActionTaken, HandEventStreetAdvanced, HandEventCardsDealt,
+		HandEventActionTaken, HandEventPotAwarded, HandEventCompleted,
+	}
+	if !slices.Equal(kinds, want) {
+		t.Fatalf("expected events %v, got %v", want, kinds)
+	}
+	if posts := events[1].Posts; len(posts) != 2 || posts[0].Amount != cfg.SmallBlind || posts[1].Amount != cfg.BigBlind {
+		t.Fatalf("expected both blinds posted, got %+v", posts)
+	}
+	if events[5].Street != domain.StreetFlop || len(events[6].Cards) != 4 {
+		t.Fatalf("expected a burn and the flop, got %+v then %+v", events[5], events[6])
+	}
+
+	encoded, err := json.Marshal(events)
+	if err != nil {
+		t.Fatalf("marshal events failed: %v", err)
+	}
+	var log []HandEvent
+	if err := json.Unmarshal(encoded, &log); err != nil {
+		t.Fatalf("unmarshal events failed: %v", err)
+	}
+	replayed, err := ReplayHand(log)
+	if err != nil {
+		t.Fatalf("ReplayHand failed: %v", err)
+	}
+	live, state := engine.State(), replayed.State()
+	if !replayed.Done() || !slices.Equal(state.Board, live.Board) || !slices.Equal(state.DealSequence, live.DealSequence) {
+		t.Fatalf("expected the replayed hand to match, got %+v", state)
+	}
+	for _, seat := range live.Seats {
+		if got := findSeat(t, state, seat.SeatNo); got.Stack != seat.Stack {
+			t.Fatalf("expected seat %d replayed with %d, got %d", seat.SeatNo, seat.Stack, got.Stack)
+		}
+	}
+	if len(replayed.Events()) != len(events) {
+		t.Fatalf("expected the replayed log to have %d events, got %d", len(events), len(replayed.Events()))
+	}
+}
+
+func TestHandEngine_TakesRakeAsThePotIsAwarded(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.Rake = &domain.RakeRule{BasisPoints: 500, Cap: 7, NoFlopNoDrop: true}
+	play := func(kinds ...domain.ActionKind) *HandEngine {
+		t.Helper()
+		engine, err := NewHandEngine(StartNewHandInput{
+			TableID:    "table-1",
+			HandNo:     1,
+			Seats:      mustSeats(t, cfg, 1, 2),
+			ButtonSeat: mustSeatNo(t, cfg, 1),
+			Config:     cfg,
+			Shuffler:   rules.NewSeededShuffler(7),
+			HandID:     "hand-1",
+		})
+		if err != nil {
+			t.Fatalf("NewHandEngine failed: %v", err)
+		}
+		for _, kind := range kinds {
+			if err := engine.Apply(mustAction(t, kind, nil)); err != nil {
+				t.Fatalf("%s failed: %v", kind, err)
+			}
+		}
+		return engine
+	}
+
+	if state := play(domain.ActionFold).State(); state.RakeTaken != 0 || state.ShowdownAwards[0].Amount != cfg.SmallBlind+cfg.BigBlind {
+		t.Fatalf("expected no flop, no drop, got rake %d and awards %+v", state.RakeTaken, state.ShowdownAwards)
+	}
+
+	engine := play(domain.ActionCall, domain.ActionCheck, domain.ActionFold)
+	state := engine.State()
+	if state.RakeTaken != 7 || state.ShowdownAwards[0].Amount != 2*cfg.BigBlind-7 {
+		t.Fatalf("expected 5%% of the pot capped at 7, got rake %d and awards %+v", state.RakeTaken, state.ShowdownAwards)
+	}
+	var stacks domain.Chips
+	for _, seat := range state.Seats {
+		stacks += seat.Stack
+	}
+	if stacks != 2*cfg.StartingStack-7 {
+		t.Fatalf("expected the rake to leave the table, stacks hold %d", stacks)
+	}
+	events := engine.Events()
+	rake := events[len(events)-3]
+	if rake.Kind != HandEventRakeTaken || rake.Rake != 7 || events[len(events)-2].Kind != HandEventPotAwarded {
+		t.Fatalf("expected the rake recorded just before the award, got %+v", events[len(events)-3:])
+	}
+	if _, err := ReplayHand(events); err != nil {
+		t.Fatalf("ReplayHand failed: %v", err)
+	}
+}
+
+func TestHandEngine_RunsAnAllInOutOnEveryBoard(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.RunItTimes = 3
+	engine, err := NewHandEngine(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      mustSeats(t, cfg, 1, 2),
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+		Shuffler:   rules.NewSeededShuffler(7),
+		HandID:     "hand-1",
+	})
+	if err != nil {
+		t.Fatalf("NewHandEngine failed: %v", err)
+	}
+	for _, kind := range []domain.ActionKind{domain.ActionAllIn, domain.ActionCall} {
+		if err := engine.Apply(mustAction(t, kind, nil)); err != nil {
+			t.Fatalf("%s failed: %v", kind, err)
+		}
+	}
+
+	state := engine.State()
+	if len(state.Board) != 5 || len(state.Runouts) != 2 || len(state.Runouts[0]) != 5 || len(state.Runouts[1]) != 5 {
+		t.Fatalf("expected three full boards, got %v and %v", state.Board, state.Runouts)
+	}
+	var awarded domain.Chips
+	for _, award := range state.ShowdownAwards {
+		awarded += award.Amount
+	}
+	if len(state.ShowdownAwards) != 3 || state.ShowdownAwards[2].Reason != "main_pot_run_3" || awarded != 2*cfg.StartingStack {
+		t.Fatalf("expected the pot split between three runouts, got %+v", state.ShowdownAwards)
+	}
+	if _, err := ReplayHand(engine.Events()); err != nil {
+		t.Fatalf("ReplayHand failed: %v", err)
+	}
+}
+
+func TestReplayHand_RejectsAlteredLogs(t *testing.T) {
+	t.Parallel()
+
+	engine, _ := newHeadsUpEngine(t)
+	if err := engine.Apply(mustAction(t, domain.ActionFold, nil)); err != nil {
+		t.Fatalf("fold failed: %v", err)
+	}
+	events := engine.Events()
+
+	inflated := slices.Clone(events)
+	award := inflated[len(inflated)-2]
+	award.Awards = []domain.PotAward{{Amount: award.Awards[0].Amount + 1, Seats: award.Awards[0].Seats, Reason: award.Awards[0].Reason}}
+	inflated[len(inflated)-2] = award
+	for name, log := range map[string][]HandEvent{
+		"inflated award": inflated,
+		"cut short":      events[:len(events)-1],
+		"no start":       events[1:],
+	} {
+		if _, err := ReplayHand(log); !errors.Is(err, ErrEventMismatch) {
+			t.Fatalf("%s: expected ErrEventMismatch, got %v", name, err)
+		}
+	}
+}
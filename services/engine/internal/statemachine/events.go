@@ -0,0 +1,269 @@
+package statemachine
+
+import (
+	"fmt"
+	"reflect"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/blinds"
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+// HandEventKind names one change to a hand.
+type HandEventKind string
+
+const (
+	// HandEventStarted opens every log. It carries what the hand was set
+	// up from, the shuffled deck included.
+	HandEventStarted HandEventKind = "hand_started"
+	// HandEventBlindsPosted lists the blinds and antes each seat posted.
+	HandEventBlindsPosted HandEventKind = "blinds_posted"
+	// HandEventCardsDealt lists cards dealt, burns included, in deal order.
+	HandEventCardsDealt HandEventKind = "cards_dealt"
+	// HandEventActionTaken is the acting seat's action.
+	HandEventActionTaken HandEventKind = "action_taken"
+	// HandEventStreetAdvanced moves the hand to the next street, before its
+	// cards are dealt.
+	HandEventStreetAdvanced HandEventKind = "street_advanced"
+	// HandEventRakeTaken is the house's cut of the pots, taken just before
+	// they are paid out.
+	HandEventRakeTaken HandEventKind = "rake_taken"
+	// HandEventPotAwarded lists the pots paid out and who won them.
+	HandEventPotAwarded HandEventKind = "pot_awarded"
+	// HandEventCompleted closes the log of a finished hand.
+	HandEventCompleted HandEventKind = "hand_completed"
+)
+
+// HandEvent is one entry in a hand's append-only log. Seq numbers the log
+// from zero, and only the fields for Kind are set.
+type HandEvent struct {
+	Seq     int                `json:"seq"`
+	Kind    HandEventKind      `json:"kind"`
+	Start   *HandStart         `json:"start,omitempty"`
+	Posts   []BlindPost        `json:"posts,omitempty"`
+	Cards   []domain.DealtCard `json:"cards,omitempty"`
+	Seat    domain.SeatNo      `json:"seat,omitempty"`
+	Action  *domain.Action     `json:"action,omitempty"`
+	Street  domain.Street      `json:"street,omitempty"`
+	Rake    domain.Chips       `json:"rake,omitempty"`
+	Awards  []domain.PotAward  `json:"awards,omitempty"`
+	Outcome HandOutcome        `json:"outcome,omitempty"`
+}
+
+// HandStart is StartNewHandInput as it was dealt: Deck replaces the
+// shuffler, so replaying the hand deals the same cards.
+type HandStart struct {
+	TableID    string             `json:"table_id"`
+	HandNo     uint64             `json:"hand_no"`
+	HandID     string             `json:"hand_id"`
+	ButtonSeat domain.SeatNo      `json:"button_seat"`
+	Seats      []domain.SeatState `json:"seats"`
+	Config     domain.TableConfig `json:"config"`
+	Positions  *blinds.Positions  `json:"positions,omitempty"`
+	Deck       []domain.Card      `json:"deck"`
+}
+
+// BlindPost is what one seat put in before the deal, blinds and antes
+// together.
+type BlindPost struct {
+	SeatNo domain.SeatNo `json:"seat_no"`
+	Amount domain.Chips  `json:"amount"`
+}
+
+// ReplayHand rebuilds a hand from its log. Each recorded change is checked
+// against the rules as it is replayed, so a log that was altered or cut
+// short is rejected with ErrEventMismatch.
+func ReplayHand(events []HandEvent) (*HandEngine, error) {
+	engine := &HandEngine{}
+	for _, event := range events {
+		if err := engine.ApplyEvent(event); err != nil {
+			return nil, err
+		}
+	}
+	if len(engine.owed) > 0 {
+		return nil, fmt.Errorf("%w: log ends before event %d (%s)", ErrEventMismatch, engine.owed[0].Seq, engine.owed[0].Kind)
+	}
+	return engine, nil
+}
+
+// ApplyEvent plays the next event of a hand's log. Starting the hand and
+// taking an action drive the hand; every other kind records what they
+// changed, and must be exactly what the hand now shows. On an error the
+// hand is left as it was.
+func (e *HandEngine) ApplyEvent(event HandEvent) error {
+	switch event.Kind {
+	case HandEventStarted, HandEventActionTaken:
+		if len(e.owed) > 0 {
+			return fmt.Errorf("%w: got %s at %d, want %s", ErrEventMismatch, event.Kind, event.Seq, e.owed[0].Kind)
+		}
+	default:
+		if len(e.owed) == 0 || !reflect.DeepEqual(event, e.owed[0]) {
+			return fmt.Errorf("%w: %s at %d does not follow from the hand", ErrEventMismatch, event.Kind, event.Seq)
+		}
+		e.events = append(e.events, event)
+		e.owed = e.owed[1:]
+		return nil
+	}
+	if event.Seq != len(e.events) {
+		return fmt.Errorf("%w: %s at %d, want %d", ErrEventMismatch, event.Kind, event.Seq, len(e.events))
+	}
+
+	var (
+		state  domain.HandState
+		events []HandEvent
+		err    error
+	)
+	switch {
+	case event.Kind == HandEventStarted && len(e.events) == 0 && event.Start != nil:
+		state, events, err = startHand(event.Start.input())
+	case event.Kind == HandEventActionTaken && len(e.events) > 0 && event.Action != nil:
+		if event.Seat != e.state.ActingSeat {
+			return fmt.Errorf("%w: seat %d acted, seat %d was to act", ErrEventMismatch, event.Seat, e.state.ActingSeat)
+		}
+		state, events, err = takeAction(e.state, *event.Action)
+	default:
+		return fmt.Errorf("%w: %s at %d", ErrEventMismatch, event.Kind, event.Seq)
+	}
+	if err != nil {
+		return err
+	}
+	numberEvents(events, len(e.events))
+	e.state = state
+	e.events = append(e.events, event)
+	e.owed = events[1:]
+	return nil
+}
+
+// Events returns a copy of the hand's log so far.
+func (e *HandEngine) Events() []HandEvent {
+	return slices.Clone(e.events)
+}
+
+func (s HandStart) input() StartNewHandInput {
+	return StartNewHandInput{
+		TableID:    s.TableID,
+		HandNo:     s.HandNo,
+		Seats:      slices.Clone(s.Seats),
+		ButtonSeat: s.ButtonSeat,
+		Config:     s.Config,
+		Shuffler:   deckShuffler(s.Deck),
+		HandID:     s.HandID,
+		Positions:  s.Positions,
+	}
+}
+
+// deckShuffler deals a recorded deck in its recorded order.
+type deckShuffler []domain.Card
+
+func (d deckShuffler) Shuffle(cards []domain.Card) error {
+	if len(cards) != len(d) {
+		return fmt.Errorf("%w: recorded deck has %d cards, hand needs %d", ErrEventMismatch, len(d), len(cards))
+	}
+	copy(cards, d)
+	return nil
+}
+
+// startHand deals a new hand and plays it on as far as it goes without an
+// action, returning the events that got it there.
+func startHand(input StartNewHandInput) (domain.HandState, []HandEvent, error) {
+	state, err := StartNewHand(input)
+	if err != nil {
+		return domain.HandState{}, nil, err
+	}
+	events := []HandEvent{{Kind: HandEventStarted, Start: &HandStart{
+		TableID:    state.TableID,
+		HandNo:     state.HandNo,
+		HandID:     state.HandID,
+		ButtonSeat: input.ButtonSeat,
+		Seats:      slices.Clone(input.Seats),
+		Config:     input.Config,
+		Positions:  input.Positions,
+		Deck:       slices.Clone(state.Deck),
+	}}}
+
+	var posts []BlindPost
+	for _, seat := range state.Seats {
+		idx := seatIndex(input.Seats, seat.SeatNo)
+		if idx >= 0 && seat.Stack < input.Seats[idx].Stack {
+			posts = append(posts, BlindPost{SeatNo: seat.SeatNo, Amount: input.Seats[idx].Stack - seat.Stack})
+		}
+	}
+	if len(posts) > 0 {
+		events = append(events, HandEvent{Kind: HandEventBlindsPosted, Posts: posts})
+	}
+	// The deal changed an empty hand on its first street; a hand the blinds
+	// decide is paid out here too.
+	events = append(events, changeEvents(domain.HandState{Street: state.Street}, state)...)
+	return finishWithEvents(state, events)
+}
+
+// takeAction plays action for the acting seat and the hand on from there,
+// returning the events it caused, the action first.
+func takeAction(state domain.HandState, action domain.Action) (domain.HandState, []HandEvent, error) {
+	next, err := ApplyAction(state, action)
+	if err != nil {
+		return state, nil, err
+	}
+	events := []HandEvent{{Kind: HandEventActionTaken, Seat: state.ActingSeat, Action: &action}}
+	events = append(events, changeEvents(state, next)...)
+	return finishWithEvents(next, events)
+}
+
+// finishWithEvents is FinishHand, recording each street it runs out and the
+// award, and closes the log once the hand is complete.
+func finishWithEvents(state domain.HandState, events []HandEvent) (domain.HandState, []HandEvent, error) {
+	for {
+		dealt, ok, err := RunOutStreet(state)
+		if err != nil {
+			return state, nil, err
+		}
+		if !ok {
+			break
+		}
+		events = append(events, changeEvents(state, dealt)...)
+		state = dealt
+	}
+	if state.Phase == domain.HandPhaseShowdown {
+		resolved, _, err := rules.ResolvePots(cloneState(state))
+		if err != nil {
+			return state, nil, err
+		}
+		events = append(events, changeEvents(state, resolved)...)
+		state = resolved
+	}
+	if outcome, ok := Outcome(state); ok {
+		events = append(events, HandEvent{Kind: HandEventCompleted, Outcome: outcome})
+	}
+	return state, events, nil
+}
+
+// changeEvents describes what moved the hand from prev to next besides an
+// action: a new street, the cards dealt, any rake and any pots awarded.
+func changeEvents(prev domain.HandState, next domain.HandState) []HandEvent {
+	var events []HandEvent
+	if next.Street != prev.Street {
+		events = append(events, HandEvent{Kind: HandEventStreetAdvanced, Street: next.Street})
+	}
+	if dealt := next.DealSequence[len(prev.DealSequence):]; len(dealt) > 0 {
+		events = append(events, HandEvent{Kind: HandEventCardsDealt, Cards: slices.Clone(dealt)})
+	}
+	completed := next.Phase == domain.HandPhaseComplete && prev.Phase != domain.HandPhaseComplete
+	if completed && next.RakeTaken > 0 {
+		events = append(events, HandEvent{Kind: HandEventRakeTaken, Rake: next.RakeTaken})
+	}
+	if completed && len(next.ShowdownAwards) > 0 {
+		awards := make([]domain.PotAward, 0, len(next.ShowdownAwards))
+		for _, award := range next.ShowdownAwards {
+			awards = append(awards, domain.PotAward{Amount: award.Amount, Seats: slices.Clone(award.Seats), Reason: award.Reason})
+		}
+		events = append(events, HandEvent{Kind: HandEventPotAwarded, Awards: awards})
+	}
+	return events
+}
+
+func numberEvents(events []HandEvent, from int) {
+	for i := range events {
+		events[i].Seq = from + i
+	}
+}
@@ -0,0 +1,136 @@
+package statemachine
+
+import (
+	"fmt"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/invariants"
+)
+
+// HandReplay is a hand rebuilt from its event log with the state after every
+// event kept, so a hand reported from production can be stepped through in
+// a test. The events a start or action caused share the state it led to.
+type HandReplay struct {
+	events []HandEvent
+	states []domain.HandState
+}
+
+// NewHandReplay replays events as ReplayHand does and checks the hand's
+// invariants after each one: no chips appear or vanish other than the rake,
+// the deck is the table's deck, every dealt card came off it once, the
+// board fits the street, and only a seat that can act is asked to. A broken
+// invariant is reported with ErrInvariantViolated and the event that broke
+// it.
+func NewHandReplay(events []HandEvent) (*HandReplay, error) {
+	engine := &HandEngine{}
+	replay := &HandReplay{
+		events: make([]HandEvent, 0, len(events)),
+		states: make([]domain.HandState, 0, len(events)),
+	}
+	var checker invariants.Checker
+	for _, event := range events {
+		if err := engine.ApplyEvent(event); err != nil {
+			return nil, err
+		}
+		if event.Kind == HandEventStarted {
+			checker = invariants.NewChecker(event.Start.Seats)
+		}
+		state := engine.State()
+		if err := checkHandInvariants(state, checker); err != nil {
+			return nil, fmt.Errorf("after event %d (%s): %w", event.Seq, event.Kind, err)
+		}
+		replay.events = append(replay.events, event)
+		replay.states = append(replay.states, state)
+	}
+	if len(engine.owed) > 0 {
+		return nil, fmt.Errorf("%w: log ends before event %d (%s)", ErrEventMismatch, engine.owed[0].Seq, engine.owed[0].Kind)
+	}
+	return replay, nil
+}
+
+// Len is the number of events replayed.
+func (r *HandReplay) Len() int {
+	return len(r.events)
+}
+
+// StateAt returns a copy of the hand as it stood after event seq, or false
+// past the end of the log.
+func (r *HandReplay) StateAt(seq int) (domain.HandState, bool) {
+	if seq < 0 || seq >= len(r.states) {
+		return domain.HandState{}, false
+	}
+	return cloneState(r.states[seq]), true
+}
+
+// Final returns a copy of the hand after the last event.
+func (r *HandReplay) Final() domain.HandState {
+	if len(r.states) == 0 {
+		return domain.HandState{}
+	}
+	return cloneState(r.states[len(r.states)-1])
+}
+
+// Events returns a copy of the replayed log.
+func (r *HandReplay) Events() []HandEvent {
+	return slices.Clone(r.events)
+}
+
+// checkHandInvariants reports the first way state is not a hand the rules
+// could have produced. The chip rules are checker's, as the runner checks
+// them live; the deck, board and turn rules are the replay's own.
+func checkHandInvariants(state domain.HandState, checker invariants.Checker) error {
+	if err := checker.Check(state); err != nil {
+		return fmt.Errorf("%w: %w", ErrInvariantViolated, err)
+	}
+
+	built := state.DeckSpec.Build().Cards
+	if len(state.Deck) < len(built) || !sameCards(state.Deck[:len(built)], built) {
+		return fmt.Errorf("%w: deck is not the table's %d cards", ErrInvariantViolated, len(built))
+	}
+	dealt := make(map[int]bool, len(state.DealSequence))
+	for _, card := range state.DealSequence {
+		if card.DeckIndex < 0 || card.DeckIndex >= len(state.Deck) || state.Deck[card.DeckIndex] != card.Card || dealt[card.DeckIndex] {
+			return fmt.Errorf("%w: %v dealt from deck index %d", ErrInvariantViolated, card.Card, card.DeckIndex)
+		}
+		dealt[card.DeckIndex] = true
+	}
+
+	if want := boardCards(state.Street); len(state.Board) != want {
+		return fmt.Errorf("%w: %d board cards on the %s, want %d", ErrInvariantViolated, len(state.Board), state.Street, want)
+	}
+	if state.Phase == domain.HandPhaseBetting {
+		idx := seatIndex(state.Seats, state.ActingSeat)
+		if idx < 0 || !isEligibleToAct(state.Seats[idx]) {
+			return fmt.Errorf("%w: seat %d to act cannot act", ErrInvariantViolated, state.ActingSeat)
+		}
+	}
+	return nil
+}
+
+func sameCards(a []domain.Card, b []domain.Card) bool {
+	counts := make(map[domain.Card]int, len(a))
+	for _, card := range a {
+		counts[card]++
+	}
+	for _, card := range b {
+		if counts[card] == 0 {
+			return false
+		}
+		counts[card]--
+	}
+	return len(a) == len(b)
+}
+
+func boardCards(street domain.Street) int {
+	switch street {
+	case domain.StreetFlop:
+		return 3
+	case domain.StreetTurn:
+		return 4
+	case domain.StreetRiver:
+		return 5
+	default:
+		return 0
+	}
+}
@@ -0,0 +1,101 @@
+package statemachine
+
+import (
+	"errors"
+	"slices"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/invariants"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+func TestHandReplay_StepsThroughTheHand(t *testing.T) {
+	t.Parallel()
+
+	engine, cfg := newHeadsUpEngine(t)
+	for _, kind := range []domain.ActionKind{domain.ActionCall, domain.ActionCheck, domain.ActionFold} {
+		if err := engine.Apply(mustAction(t, kind, nil)); err != nil {
+			t.Fatalf("%s failed: %v", kind, err)
+		}
+	}
+
+	replay, err := NewHandReplay(engine.Events())
+	if err != nil {
+		t.Fatalf("NewHandReplay failed: %v", err)
+	}
+	if replay.Len() != len(engine.Events()) {
+		t.Fatalf("expected %d events replayed, got %d", len(engine.Events()), replay.Len())
+	}
+	if blinds, ok := replay.StateAt(1); !ok || blinds.Pot != cfg.SmallBlind+cfg.BigBlind || blinds.Street != domain.StreetPreflop {
+		t.Fatalf("expected the blinds in the pot preflop, got %+v", blinds)
+	}
+	if flop, ok := replay.StateAt(4); !ok || flop.Street != domain.StreetFlop || len(flop.Board) != 3 || flop.Pot != 2*cfg.BigBlind {
+		t.Fatalf("expected the flop dealt after the check, got %+v", flop)
+	}
+	final := replay.Final()
+	if final.Phase != domain.HandPhaseComplete || findSeat(t, final, mustSeatNo(t, cfg, 1)).Stack != cfg.StartingStack+cfg.BigBlind {
+		t.Fatalf("expected the button to take the pot, got %+v", final)
+	}
+	if _, ok := replay.StateAt(replay.Len()); ok {
+		t.Fatal("expected no state past the end of the log")
+	}
+}
+
+func TestHandReplay_CountsTheRakeAsChipsTaken(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.Rake = &domain.RakeRule{BasisPoints: 500, Cap: 7}
+	engine, err := NewHandEngine(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      mustSeats(t, cfg, 1, 2),
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+		Shuffler:   rules.NewSeededShuffler(7),
+		HandID:     "hand-1",
+	})
+	if err != nil {
+		t.Fatalf("NewHandEngine failed: %v", err)
+	}
+	for _, kind := range []domain.ActionKind{domain.ActionCall, domain.ActionCheck, domain.ActionFold} {
+		if err := engine.Apply(mustAction(t, kind, nil)); err != nil {
+			t.Fatalf("%s failed: %v", kind, err)
+		}
+	}
+
+	replay, err := NewHandReplay(engine.Events())
+	if err != nil {
+		t.Fatalf("expected the raked hand to replay, got %v", err)
+	}
+	if final := replay.Final(); final.RakeTaken != 7 || final.Phase != domain.HandPhaseComplete {
+		t.Fatalf("expected the hand to end with 7 raked, got rake %d phase %s", final.RakeTaken, final.Phase)
+	}
+}
+
+func TestHandReplay_ReportsBrokenInvariants(t *testing.T) {
+	t.Parallel()
+
+	engine, _ := newHeadsUpEngine(t)
+	events := engine.Events()
+	start := *events[0].Start
+	start.Deck = slices.Clone(start.Deck)
+	start.Deck[1] = start.Deck[0]
+	events[0].Start = &start
+	if _, err := NewHandReplay(events); !errors.Is(err, ErrInvariantViolated) {
+		t.Fatalf("expected a deck with a repeated card to be rejected, got %v", err)
+	}
+
+	state := engine.State()
+	checker := invariants.NewChecker(engine.Events()[0].Start.Seats)
+	if err := checkHandInvariants(state, checker); err != nil {
+		t.Fatalf("expected the live hand to hold, got %v", err)
+	}
+	state.Seats[0].Stack -= 10
+	err := checkHandInvariants(state, checker)
+	var violation *invariants.InvariantViolation
+	if !errors.Is(err, ErrInvariantViolated) || !errors.As(err, &violation) || violation.Rule != invariants.RuleChipsConserved {
+		t.Fatalf("expected missing chips to be caught as a conservation violation, got %v", err)
+	}
+}
@@ -5,6 +5,7 @@ import (
 	"fmt"
 	"sort"
 
+	"github.com/imaddar/poker-arena/services/engine/internal/blinds"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
 	"github.com/imaddar/poker-arena/services/engine/internal/rules"
 )
@@ -16,6 +17,11 @@ var (
 	ErrHandAlreadyComplete = errors.New("hand already complete")
 	ErrNoActiveSeats       = errors.New("hand has no active seats")
 	ErrInvalidTransition   = errors.New("invalid hand transition")
+	ErrRaiseCapReached     = errors.New("raise cap reached for street")
+	ErrPotLimitExceeded    = errors.New("bet exceeds the pot limit")
+	ErrFixedLimitExceeded  = errors.New("bet exceeds the fixed limit")
+	ErrEventMismatch       = errors.New("hand event does not match the hand")
+	ErrInvariantViolated   = errors.New("hand invariant violated")
 )
 
 type StartNewHandInput struct {
@@ -25,46 +31,34 @@ type StartNewHandInput struct {
 	ButtonSeat domain.SeatNo
 	Config     domain.TableConfig
 	Shuffler   rules.Shuffler
+	// HandID, when set, replaces the randomly generated hand ID. Together
+	// with a seeded Shuffler it keeps hand setup free of OS entropy.
+	HandID string
+	// Positions, when set, places the blinds, such as from blinds.Advance on
+	// a dead-button table, and its Button replaces ButtonSeat. Otherwise
+	// blinds.Assign places them from ButtonSeat.
+	Positions *blinds.Positions
 }
 
 func StartNewHand(input StartNewHandInput) (domain.HandState, error) {
 	seats := append([]domain.SeatState(nil), input.Seats...)
 	sortSeats(seats)
 
-	activeSeats := countNonFoldedActiveSeats(seats)
-	if activeSeats == 0 {
+	if countNonFoldedActiveSeats(seats) == 0 {
 		return domain.HandState{}, ErrNoActiveSeats
 	}
 
-	var (
-		sbSeat domain.SeatNo
-		bbSeat domain.SeatNo
-		ok     bool
-	)
-	if activeSeats == 2 {
-		buttonIdx := seatIndex(seats, input.ButtonSeat)
-		if buttonIdx >= 0 && isActiveSeat(seats[buttonIdx]) {
-			sbSeat = input.ButtonSeat
-		} else {
-			sbSeat, ok = nextSeat(seats, input.ButtonSeat, false, isActiveSeat)
-			if !ok {
-				return domain.HandState{}, ErrNoActiveSeats
-			}
-		}
-		bbSeat, ok = nextSeat(seats, sbSeat, false, isActiveSeat)
-		if !ok {
-			return domain.HandState{}, ErrNoActiveSeats
-		}
+	var positions blinds.Positions
+	if input.Positions != nil {
+		positions = *input.Positions
 	} else {
-		sbSeat, ok = nextSeat(seats, input.ButtonSeat, false, isActiveSeat)
-		if !ok {
-			return domain.HandState{}, ErrNoActiveSeats
-		}
-		bbSeat, ok = nextSeat(seats, sbSeat, false, isActiveSeat)
-		if !ok {
-			return domain.HandState{}, ErrNoActiveSeats
+		assigned, err := blinds.Assign(seats, input.ButtonSeat)
+		if err != nil {
+			return domain.HandState{}, fmt.Errorf("%w: %v", ErrNoActiveSeats, err)
 		}
+		positions = assigned
 	}
+	sbSeat, bbSeat := positions.SmallBlind, positions.BigBlind
 
 	actingSeat, ok := nextSeat(seats, bbSeat, false, isEligibleToAct)
 	if !ok {
@@ -72,14 +66,15 @@ func StartNewHand(input StartNewHandInput) (domain.HandState, error) {
 		actingSeat = sbSeat
 	}
 
-	state, err := domain.NewHandState(
-		input.TableID,
-		input.HandNo,
-		input.ButtonSeat,
-		actingSeat,
-		seats,
-		input.Config,
+	var (
+		state domain.HandState
+		err   error
 	)
+	if input.HandID != "" {
+		state, err = domain.NewHandStateWithID(input.HandID, input.TableID, input.HandNo, positions.Button, actingSeat, seats, input.Config)
+	} else {
+		state, err = domain.NewHandState(input.TableID, input.HandNo, positions.Button, actingSeat, seats, input.Config)
+	}
 	if err != nil {
 		return domain.HandState{}, err
 	}
@@ -96,13 +91,27 @@ func StartNewHand(input StartNewHandInput) (domain.HandState, error) {
 
 	state.Phase = domain.HandPhaseBetting
 	state.ActionOrderStartSeat = actingSeat
-	postSB := postBlind(&state, sbSeat, input.Config.SmallBlind)
+	var postSB domain.Chips
+	if !positions.DeadSmallBlind {
+		postSB = postBlind(&state, sbSeat, input.Config.SmallBlind)
+	}
 	postBB := postBlind(&state, bbSeat, input.Config.BigBlind)
 	state.CurrentBet = postBB
 	state.LastFullRaise = input.Config.BigBlind
 	state.MinRaiseTo = state.CurrentBet + state.LastFullRaise
+	// The big blind counts as the first bet of the preflop street.
+	state.StreetBets = 1
 	bb := bbSeat
 	state.LastAggressorSeat = &bb
+	postMissedBlinds(&state, positions, input.Config)
+	// Preflop action starts left of the last blind posted, the straddle
+	// when there is one.
+	lastBlind := bbSeat
+	if straddler, ok := postStraddle(&state, positions, input.Config); ok {
+		lastBlind = straddler
+	}
+	blinds.MarkMissed(state.Seats, positions.Button)
+	postAntes(&state, positions, input.Config)
 
 	if postSB == 0 && postBB == 0 {
 		return domain.HandState{}, fmt.Errorf("%w: failed to post blinds", ErrInvalidTransition)
@@ -112,8 +121,12 @@ func StartNewHand(input StartNewHandInput) (domain.HandState, error) {
 		state = rules.AwardUncontested(state)
 		return state, nil
 	}
+	if state.AllInLocked() {
+		state.Phase = domain.HandPhaseShowdown
+		return state, nil
+	}
 
-	if nextActor, ok := nextSeat(state.Seats, bbSeat, false, isEligibleToAct); ok {
+	if nextActor, ok := nextSeat(state.Seats, lastBlind, false, isEligibleToAct); ok {
 		state.ActingSeat = nextActor
 		state.ActionOrderStartSeat = nextActor
 		return state, nil
@@ -141,6 +154,14 @@ func ApplyAction(state domain.HandState, action domain.Action) (domain.HandState
 		return domain.HandState{}, ErrNotActingSeat
 	}
 
+	if action.Kind == domain.ActionAllIn {
+		normalized, err := NormalizeAction(next, action)
+		if err != nil {
+			return domain.HandState{}, err
+		}
+		action = normalized
+	}
+
 	toCall := computeToCall(next.Seats[actingIdx], next.CurrentBet)
 
 	switch action.Kind {
@@ -149,12 +170,12 @@ func ApplyAction(state domain.HandState, action domain.Action) (domain.HandState
 		next.Seats[actingIdx].HasActedThisRound = true
 	case domain.ActionCheck:
 		if toCall != 0 {
-			return domain.HandState{}, ErrIllegalAction
+			return domain.HandState{}, fmt.Errorf("%w: cannot check facing %d to call", ErrIllegalAction, toCall)
 		}
 		next.Seats[actingIdx].HasActedThisRound = true
 	case domain.ActionCall:
 		if toCall == 0 {
-			return domain.HandState{}, ErrIllegalAction
+			return domain.HandState{}, fmt.Errorf("%w: nothing to call", ErrIllegalAction)
 		}
 		pay := min(toCall, next.Seats[actingIdx].Stack)
 		next.Seats[actingIdx].Stack -= pay
@@ -163,37 +184,79 @@ func ApplyAction(state domain.HandState, action domain.Action) (domain.HandState
 		next.Seats[actingIdx].HasActedThisRound = true
 		next.Pot += pay
 	case domain.ActionBet:
-		if next.CurrentBet != 0 || action.Amount == nil || *action.Amount == 0 {
-			return domain.HandState{}, ErrIllegalAction
+		if next.CurrentBet != 0 {
+			return domain.HandState{}, fmt.Errorf("%w: cannot bet into a bet of %d, raise instead", ErrIllegalAction, next.CurrentBet)
+		}
+		if action.Amount == nil || *action.Amount == 0 {
+			return domain.HandState{}, fmt.Errorf("%w: bet needs a positive amount", ErrIllegalAction)
+		}
+		if next.RaiseCapReached() {
+			return domain.HandState{}, ErrRaiseCapReached
 		}
 		if *action.Amount > next.Seats[actingIdx].Stack {
-			return domain.HandState{}, ErrInsufficientChips
+			return domain.HandState{}, fmt.Errorf("%w: bet of %d exceeds stack of %d", ErrInsufficientChips, *action.Amount, next.Seats[actingIdx].Stack)
+		}
+		if *action.Amount < next.MinRaiseTo && *action.Amount != next.Seats[actingIdx].Stack {
+			return domain.HandState{}, fmt.Errorf("%w: bet of %d is below the minimum bet of %d and is not all-in", ErrIllegalAction, *action.Amount, next.MinRaiseTo)
+		}
+		if limit := maxRaiseTo(next, next.Seats[actingIdx]); *action.Amount > limit {
+			return domain.HandState{}, fmt.Errorf("%w: bet of %d is over the limit of %d", limitExceeded(next), *action.Amount, limit)
 		}
 		amount := *action.Amount
+		isShortAllIn := amount < next.MinRaiseTo
 		next.Seats[actingIdx].Stack -= amount
 		next.Seats[actingIdx].TotalCommitted += amount
 		next.Seats[actingIdx].CommittedInRound += amount
 		next.Pot += amount
 		next.CurrentBet = next.Seats[actingIdx].CommittedInRound
-		next.LastFullRaise = amount
-		next.MinRaiseTo = next.CurrentBet + next.LastFullRaise
+		fixedBet, fixedLimit := next.FixedLimitBet()
+		switch {
+		case isShortAllIn && fixedLimit:
+			// An all-in short of the fixed bet is not a bet toward the cap;
+			// the next seat may complete it to the full bet.
+			next.MinRaiseTo = fixedBet
+		case isShortAllIn:
+			// The full bet size still sets the minimum raise.
+			next.MinRaiseTo = next.CurrentBet + next.LastFullRaise
+		default:
+			next.LastFullRaise = amount
+			next.MinRaiseTo = next.CurrentBet + next.LastFullRaise
+			next.StreetBets = 1
+		}
 		markRoundResponsePending(next.Seats, actingIdx)
 		seat := next.Seats[actingIdx].SeatNo
 		next.LastAggressorSeat = &seat
 	case domain.ActionRaise:
-		if next.CurrentBet == 0 || action.Amount == nil {
-			return domain.HandState{}, ErrIllegalAction
+		if next.CurrentBet == 0 {
+			return domain.HandState{}, fmt.Errorf("%w: nothing to raise, bet instead", ErrIllegalAction)
+		}
+		if action.Amount == nil {
+			return domain.HandState{}, fmt.Errorf("%w: raise needs a raise-to amount", ErrIllegalAction)
+		}
+		if next.RaiseCapReached() {
+			return domain.HandState{}, ErrRaiseCapReached
 		}
 		raiseTo := *action.Amount
-		if raiseTo <= next.CurrentBet || raiseTo < next.MinRaiseTo {
-			return domain.HandState{}, ErrIllegalAction
+		if raiseTo <= next.CurrentBet {
+			return domain.HandState{}, fmt.Errorf("%w: raise to %d must exceed the current bet of %d", ErrIllegalAction, raiseTo, next.CurrentBet)
+		}
+		if !bettingReopened(next, next.Seats[actingIdx]) {
+			return domain.HandState{}, fmt.Errorf("%w: an incomplete all-in raise did not reopen the betting, call or fold", ErrIllegalAction)
 		}
 		if raiseTo <= next.Seats[actingIdx].CommittedInRound {
-			return domain.HandState{}, ErrIllegalAction
+			return domain.HandState{}, fmt.Errorf("%w: raise to %d must exceed the %d already committed", ErrIllegalAction, raiseTo, next.Seats[actingIdx].CommittedInRound)
 		}
 		delta := raiseTo - next.Seats[actingIdx].CommittedInRound
 		if delta > next.Seats[actingIdx].Stack {
-			return domain.HandState{}, ErrInsufficientChips
+			return domain.HandState{}, fmt.Errorf("%w: raise to %d needs %d more chips, stack is %d", ErrInsufficientChips, raiseTo, delta, next.Seats[actingIdx].Stack)
+		}
+		// A raise below the minimum is only allowed when it puts the seat all-in.
+		isShortAllIn := raiseTo < next.MinRaiseTo
+		if isShortAllIn && delta != next.Seats[actingIdx].Stack {
+			return domain.HandState{}, fmt.Errorf("%w: raise to %d is below the minimum raise to %d and is not all-in", ErrIllegalAction, raiseTo, next.MinRaiseTo)
+		}
+		if limit := maxRaiseTo(next, next.Seats[actingIdx]); raiseTo > limit {
+			return domain.HandState{}, fmt.Errorf("%w: raise to %d is over the limit of %d", limitExceeded(next), raiseTo, limit)
 		}
 		previousBet := next.CurrentBet
 		next.Seats[actingIdx].Stack -= delta
@@ -201,9 +264,22 @@ func ApplyAction(state domain.HandState, action domain.Action) (domain.HandState
 		next.Seats[actingIdx].CommittedInRound += delta
 		next.Pot += delta
 		next.CurrentBet = raiseTo
-		next.LastFullRaise = raiseTo - previousBet
+		switch {
+		case isShortAllIn && next.RulesVersion.FullRaiseRule():
+			// An incomplete raise does not reopen the betting: seats that
+			// have acted owe a call but may not raise again.
+			next.Seats[actingIdx].HasActedThisRound = true
+		case isShortAllIn:
+			// Before RulesV2 any raise reopens the betting.
+			markRoundResponsePending(next.Seats, actingIdx)
+		default:
+			// Completing a short all-in bet raises by less than the full
+			// bet it completes, which stays the full raise.
+			next.LastFullRaise = max(next.LastFullRaise, raiseTo-previousBet)
+			next.StreetBets++
+			markRoundResponsePending(next.Seats, actingIdx)
+		}
 		next.MinRaiseTo = next.CurrentBet + next.LastFullRaise
-		markRoundResponsePending(next.Seats, actingIdx)
 		seat := next.Seats[actingIdx].SeatNo
 		next.LastAggressorSeat = &seat
 	default:
@@ -236,7 +312,49 @@ func ApplyAction(state domain.HandState, action domain.Action) (domain.HandState
 	return next, nil
 }
 
-func postBlind(state *domain.HandState, seatNo domain.SeatNo, amount uint32) uint32 {
+// ValidateAction dry-runs action against state without applying it. It
+// returns the action as ApplyAction would play it, with an all-in shortcut
+// made concrete, or the error ApplyAction would return.
+func ValidateAction(state domain.HandState, action domain.Action) (domain.Action, error) {
+	if _, err := ApplyAction(state, action); err != nil {
+		return domain.Action{}, err
+	}
+	return NormalizeAction(state, action)
+}
+
+// NormalizeAction rewrites an all-in shortcut into the concrete call, bet or
+// raise it stands for, keeping any telemetry. Other actions are returned
+// unchanged.
+func NormalizeAction(state domain.HandState, action domain.Action) (domain.Action, error) {
+	if action.Kind != domain.ActionAllIn {
+		return action, nil
+	}
+	if action.Amount != nil {
+		return domain.Action{}, ErrIllegalAction
+	}
+
+	actingIdx := seatIndex(state.Seats, state.ActingSeat)
+	if actingIdx < 0 {
+		return domain.Action{}, ErrInvalidTransition
+	}
+	seat := state.Seats[actingIdx]
+	if seat.Stack == 0 {
+		return domain.Action{}, ErrInsufficientChips
+	}
+
+	if state.CurrentBet == 0 {
+		amount := seat.Stack
+		return domain.Action{Kind: domain.ActionBet, Amount: &amount, Telemetry: action.Telemetry}, nil
+	}
+
+	allInTo := seat.CommittedInRound + seat.Stack
+	if allInTo <= state.CurrentBet || state.RaiseCapReached() || !bettingReopened(state, seat) {
+		return domain.Action{Kind: domain.ActionCall, Telemetry: action.Telemetry}, nil
+	}
+	return domain.Action{Kind: domain.ActionRaise, Amount: &allInTo, Telemetry: action.Telemetry}, nil
+}
+
+func postBlind(state *domain.HandState, seatNo domain.SeatNo, amount domain.Chips) domain.Chips {
 	idx := seatIndex(state.Seats, seatNo)
 	if idx < 0 || !isActiveSeat(state.Seats[idx]) {
 		return 0
@@ -249,8 +367,110 @@ func postBlind(state *domain.HandState, seatNo domain.SeatNo, amount uint32) uin
 	return post
 }
 
+// postMissedBlinds settles what each seat dealt in owes for the blinds it
+// missed sitting out. The big blind owes nothing more. Any other seat posts
+// a missed big blind live, bringing its bet up to the big blind, and a
+// missed small blind dead, as money no seat committed.
+func postMissedBlinds(state *domain.HandState, positions blinds.Positions, config domain.TableConfig) {
+	for i := range state.Seats {
+		seat := state.Seats[i]
+		if !isActiveSeat(seat) || !seat.OwesBlinds() {
+			continue
+		}
+		if seat.SeatNo != positions.BigBlind {
+			if seat.MissedBigBlind && seat.CommittedInRound < config.BigBlind {
+				postBlind(state, seat.SeatNo, config.BigBlind-seat.CommittedInRound)
+			}
+			if seat.MissedSmallBlind {
+				post := min(state.Seats[i].Stack, config.SmallBlind)
+				state.Seats[i].Stack -= post
+				state.DeadMoney += post
+				state.Pot += post
+			}
+		}
+		state.Seats[i].MissedSmallBlind = false
+		state.Seats[i].MissedBigBlind = false
+	}
+}
+
+// postStraddle posts a live straddle for the seat in the table's straddle
+// position, if it asked to, is not in a blind, has posted nothing else and
+// can cover the whole straddle. The straddle plays as a raise to its
+// amount: it is the bet to call, the size of the smallest raise and, like
+// the big blind, gets the option to raise when the action comes back to it.
+func postStraddle(state *domain.HandState, positions blinds.Positions, config domain.TableConfig) (domain.SeatNo, bool) {
+	amount := config.StraddleAmount()
+	if amount == 0 {
+		return 0, false
+	}
+	straddler := positions.Button
+	if config.Straddle == domain.StraddleUTG {
+		utg, ok := nextSeat(state.Seats, positions.BigBlind, false, isActiveSeat)
+		if !ok {
+			return 0, false
+		}
+		straddler = utg
+	}
+	if straddler == positions.SmallBlind || straddler == positions.BigBlind {
+		return 0, false
+	}
+	idx := seatIndex(state.Seats, straddler)
+	if idx < 0 {
+		return 0, false
+	}
+	seat := state.Seats[idx]
+	if !isActiveSeat(seat) || !seat.Straddle || seat.CommittedInRound > 0 || seat.Stack < amount {
+		return 0, false
+	}
+	postBlind(state, straddler, amount)
+	state.CurrentBet = amount
+	state.LastFullRaise = amount
+	state.MinRaiseTo = 2 * amount
+	state.StreetBets++
+	aggressor, straddled := straddler, straddler
+	state.LastAggressorSeat = &aggressor
+	state.StraddleSeat = &straddled
+	return straddler, true
+}
+
+// postAntes collects the table's ante after the blinds, so a short stack's
+// blind stays live. Per-seat antes count in each seat's TotalCommitted; a
+// button ante covers every seat in the hand and is dead money no seat
+// committed, so it never forms a side pot.
+func postAntes(state *domain.HandState, positions blinds.Positions, config domain.TableConfig) {
+	if config.Ante == 0 {
+		return
+	}
+	if config.AnteMode == domain.AnteButton {
+		payer := positions.Button
+		if positions.DeadButton {
+			payer = positions.BigBlind
+		}
+		idx := seatIndex(state.Seats, payer)
+		if idx < 0 || !isActiveSeat(state.Seats[idx]) {
+			return
+		}
+		post := min(state.Seats[idx].Stack, config.Ante*domain.Chips(countNonFoldedActiveSeats(state.Seats)))
+		state.Seats[idx].Stack -= post
+		state.DeadMoney += post
+		state.Pot += post
+		return
+	}
+	for i := range state.Seats {
+		if !isActiveSeat(state.Seats[i]) {
+			continue
+		}
+		post := min(state.Seats[i].Stack, config.Ante)
+		state.Seats[i].Stack -= post
+		state.Seats[i].TotalCommitted += post
+		state.Pot += post
+	}
+}
+
 func isBettingRoundClosed(state domain.HandState) bool {
-	if countEligibleToActSeats(state.Seats) <= 1 {
+	// A lone seat with chips behind still has to answer a bet it has not
+	// matched; once it has, nobody is left to act.
+	if state.AllInLocked() {
 		return true
 	}
 
@@ -275,33 +495,42 @@ func advanceStreet(state *domain.HandState) error {
 		state.Seats[i].HasActedThisRound = false
 	}
 	state.CurrentBet = 0
+	riverAggressor := state.LastAggressorSeat
 	state.LastAggressorSeat = nil
 	state.LastFullRaise = state.BigBlind
 	state.MinRaiseTo = state.BigBlind
+	state.StreetBets = 0
+
+	// With at most one seat left holding chips there is nobody to bet
+	// against, so the hand goes to showdown and FinishHand or the runner
+	// deals the rest of the board without asking for actions.
+	if state.Street != domain.StreetRiver && state.AllInLocked() {
+		state.Phase = domain.HandPhaseShowdown
+		return nil
+	}
 
 	switch state.Street {
 	case domain.StreetPreflop:
-		dealt, err := rules.NewDealer(nil).DealFlopTurnRiver(*state)
+		dealt, err := rules.NewDealer(nil).DealStreet(*state, domain.StreetFlop)
 		if err != nil {
 			return err
 		}
 		*state = dealt
-		state.Street = domain.StreetFlop
 	case domain.StreetFlop:
-		dealt, err := rules.NewDealer(nil).DealFlopTurnRiver(*state)
+		dealt, err := rules.NewDealer(nil).DealStreet(*state, domain.StreetTurn)
 		if err != nil {
 			return err
 		}
 		*state = dealt
-		state.Street = domain.StreetTurn
 	case domain.StreetTurn:
-		dealt, err := rules.NewDealer(nil).DealFlopTurnRiver(*state)
+		dealt, err := rules.NewDealer(nil).DealStreet(*state, domain.StreetRiver)
 		if err != nil {
 			return err
 		}
 		*state = dealt
-		state.Street = domain.StreetRiver
 	case domain.StreetRiver:
+		// Kept so showdown sequencing knows who was called.
+		state.LastAggressorSeat = riverAggressor
 		state.Phase = domain.HandPhaseShowdown
 		return nil
 	default:
@@ -309,6 +538,12 @@ func advanceStreet(state *domain.HandState) error {
 		return nil
 	}
 
+	// Fixed-limit bets double on the turn.
+	if size, ok := state.FixedLimitBet(); ok {
+		state.LastFullRaise = size
+		state.MinRaiseTo = size
+	}
+
 	start, ok := nextSeat(state.Seats, state.ButtonSeat, false, isEligibleToAct)
 	if !ok {
 		if countNonFoldedActiveSeats(state.Seats) <= 1 {
@@ -395,17 +630,7 @@ func countNonFoldedActiveSeats(seats []domain.SeatState) int {
 	return count
 }
 
-func countEligibleToActSeats(seats []domain.SeatState) int {
-	count := 0
-	for _, seat := range seats {
-		if isEligibleToAct(seat) {
-			count++
-		}
-	}
-	return count
-}
-
-func computeToCall(seat domain.SeatState, currentBet uint32) uint32 {
+func computeToCall(seat domain.SeatState, currentBet domain.Chips) domain.Chips {
 	if currentBet <= seat.CommittedInRound {
 		return 0
 	}
@@ -423,7 +648,11 @@ func cloneState(state domain.HandState) domain.HandState {
 	cloned := state
 	cloned.Seats = append([]domain.SeatState(nil), state.Seats...)
 	cloned.Board = append([]domain.Card(nil), state.Board...)
+	cloned.Runouts = domain.CloneBoards(state.Runouts)
 	cloned.Deck = append([]domain.Card(nil), state.Deck...)
+	cloned.Discards = append([]domain.Card(nil), state.Discards...)
+	cloned.DealSequence = append([]domain.DealtCard(nil), state.DealSequence...)
+	cloned.ShowdownReveals = append([]domain.ShowdownReveal(nil), state.ShowdownReveals...)
 	cloned.HoleCards = make([]domain.SeatCards, 0, len(state.HoleCards))
 	for _, seatCards := range state.HoleCards {
 		cloned.HoleCards = append(cloned.HoleCards, domain.SeatCards{
@@ -446,7 +675,7 @@ func cloneState(state domain.HandState) domain.HandState {
 	return cloned
 }
 
-func min(a uint32, b uint32) uint32 {
+func min(a domain.Chips, b domain.Chips) domain.Chips {
 	if a < b {
 		return a
 	}
@@ -2,6 +2,7 @@ package statemachine
 
 import (
 	"errors"
+	"fmt"
 	"testing"
 
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
@@ -217,6 +218,186 @@ func TestStartNewHandHandlesShortStackBlindPosting(t *testing.T) {
 	}
 }
 
+func TestStartNewHandCollectsPerSeatAntesAfterTheBlinds(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.Ante = 10
+	seats := mustSeats(t, cfg, 1, 2, 3, 4)
+	seats[1].Stack = 55
+
+	state, err := StartNewHand(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      seats,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+
+	if want := cfg.SmallBlind + cfg.BigBlind + 3*cfg.Ante + 5; state.Pot != want || state.DeadMoney != 0 {
+		t.Fatalf("expected pot %d from the blinds and antes, got %d (dead %d)", want, state.Pot, state.DeadMoney)
+	}
+	if sb := findSeat(t, state, mustSeatNo(t, cfg, 2)); sb.CommittedInRound != cfg.SmallBlind || sb.TotalCommitted != 55 || sb.Stack != 0 {
+		t.Fatalf("expected the short small blind to post in full and ante what was left, got %+v", sb)
+	}
+	utg := findSeat(t, state, mustSeatNo(t, cfg, 4))
+	if utg.CommittedInRound != 0 || utg.TotalCommitted != cfg.Ante || state.CurrentBet != cfg.BigBlind {
+		t.Fatalf("expected the ante to stay out of the bet to call, got %+v facing %d", utg, state.CurrentBet)
+	}
+	if state.ActingSeat != utg.SeatNo || chipTotal(state) != 3*cfg.StartingStack+55 {
+		t.Fatalf("expected seat 4 to act with chips conserved, got seat %d and %d chips", state.ActingSeat, chipTotal(state))
+	}
+}
+
+func TestHandEngineAwardsAButtonAnteInTheMainPot(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.Ante = 10
+	cfg.AnteMode = domain.AnteButton
+	engine, err := NewHandEngine(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      mustSeats(t, cfg, 1, 2),
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+		Shuffler:   rules.NewSeededShuffler(3),
+		HandID:     "hand-1",
+	})
+	if err != nil {
+		t.Fatalf("NewHandEngine failed: %v", err)
+	}
+	state := engine.State()
+	button := findSeat(t, state, mustSeatNo(t, cfg, 1))
+	if state.DeadMoney != 2*cfg.Ante || button.TotalCommitted != cfg.SmallBlind || button.Stack != cfg.StartingStack-cfg.SmallBlind-2*cfg.Ante {
+		t.Fatalf("expected the button to ante for both seats as dead money, got dead %d and %+v", state.DeadMoney, button)
+	}
+
+	if err := engine.Apply(mustAction(t, domain.ActionAllIn, nil)); err != nil {
+		t.Fatalf("all-in failed: %v", err)
+	}
+	if err := engine.Apply(mustAction(t, domain.ActionCall, nil)); err != nil {
+		t.Fatalf("call failed: %v", err)
+	}
+	state = engine.State()
+	var awarded domain.Chips
+	for _, award := range state.ShowdownAwards {
+		awarded += award.Amount
+	}
+	// The big blind keeps the 20 chips the button's stack could not cover.
+	if !engine.Done() || awarded != 2*cfg.StartingStack-2*cfg.Ante || chipTotal(state) != 2*cfg.StartingStack {
+		t.Fatalf("expected both stacks and the ante awarded at showdown, got %+v", state.ShowdownAwards)
+	}
+}
+
+func TestStartNewHandSettlesMissedBlinds(t *testing.T) {
+	t.Parallel()
+
+	// Seats 1 and 4 are back from sitting out owing both blinds, and seat
+	// 5 is still sitting out.
+	cfg := domain.DefaultV0TableConfig()
+	seats := mustSeats(t, cfg, 1, 2, 3, 4, 5)
+	for _, i := range []int{0, 3} {
+		seats[i].MissedSmallBlind, seats[i].MissedBigBlind = true, true
+	}
+	seats[4].Status = domain.SeatStatusSittingOut
+
+	state, err := StartNewHand(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      seats,
+		ButtonSeat: mustSeatNo(t, cfg, 3),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+
+	bb := findSeat(t, state, mustSeatNo(t, cfg, 1))
+	if bb.CommittedInRound != cfg.BigBlind || bb.Stack != cfg.StartingStack-cfg.BigBlind || bb.OwesBlinds() {
+		t.Fatalf("expected the big blind to owe nothing more, got %+v", bb)
+	}
+	sb := findSeat(t, state, mustSeatNo(t, cfg, 4))
+	if sb.CommittedInRound != cfg.BigBlind || sb.Stack != cfg.StartingStack-cfg.BigBlind-cfg.SmallBlind || sb.OwesBlinds() {
+		t.Fatalf("expected the small blind to post the big blind live and the small blind dead, got %+v", sb)
+	}
+	if state.DeadMoney != cfg.SmallBlind || state.Pot != 2*cfg.BigBlind+cfg.SmallBlind || chipTotal(state) != 5*cfg.StartingStack {
+		t.Fatalf("expected the missed small blind posted dead, got pot %d (dead %d)", state.Pot, state.DeadMoney)
+	}
+	if out := findSeat(t, state, mustSeatNo(t, cfg, 5)); !out.MissedBigBlind || out.MissedSmallBlind {
+		t.Fatalf("expected the seat sitting out to miss the big blind, got %+v", out)
+	}
+	if state.ActingSeat != mustSeatNo(t, cfg, 2) {
+		t.Fatalf("expected seat 2 to act first, got %d", state.ActingSeat)
+	}
+}
+
+func TestStartNewHandPostsStraddles(t *testing.T) {
+	t.Parallel()
+
+	start := func(cfg domain.TableConfig, straddler uint8, seatNumbers ...uint8) domain.HandState {
+		t.Helper()
+		seats := mustSeats(t, cfg, seatNumbers...)
+		for i := range seats {
+			seats[i].Straddle = seats[i].SeatNo == mustSeatNo(t, cfg, straddler)
+		}
+		state, err := StartNewHand(StartNewHandInput{
+			TableID:    "table-1",
+			HandNo:     1,
+			Seats:      seats,
+			ButtonSeat: mustSeatNo(t, cfg, 1),
+			Config:     cfg,
+		})
+		if err != nil {
+			t.Fatalf("StartNewHand failed: %v", err)
+		}
+		return state
+	}
+
+	// Seat 4 is under the gun behind the blinds in seats 2 and 3.
+	cfg := domain.DefaultV0TableConfig()
+	cfg.Straddle = domain.StraddleUTG
+	state := start(cfg, 4, 1, 2, 3, 4)
+	utg := mustSeatNo(t, cfg, 4)
+	if state.StraddleSeat == nil || *state.StraddleSeat != utg || findSeat(t, state, utg).CommittedInRound != 2*cfg.BigBlind {
+		t.Fatalf("expected seat 4 to straddle for two big blinds, got %+v", state)
+	}
+	if state.CurrentBet != 2*cfg.BigBlind || state.MinRaiseTo != 4*cfg.BigBlind || state.ActingSeat != mustSeatNo(t, cfg, 1) {
+		t.Fatalf("expected the button to face the straddle with a min raise to 4bb, got bet %d min %d acting %d", state.CurrentBet, state.MinRaiseTo, state.ActingSeat)
+	}
+	for range 3 {
+		next, err := ApplyAction(state, mustAction(t, domain.ActionCall, nil))
+		if err != nil {
+			t.Fatalf("call failed: %v", err)
+		}
+		state = next
+	}
+	if state.Street != domain.StreetPreflop || state.ActingSeat != utg {
+		t.Fatalf("expected the straddle to get its option, got street %q acting %d", state.Street, state.ActingSeat)
+	}
+	legal, err := LegalActions(state, utg)
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	if !legal.Allows(domain.ActionCheck) || !legal.Allows(domain.ActionRaise) || legal.MinRaiseTo != 4*cfg.BigBlind {
+		t.Fatalf("expected the straddle to check or raise its option to 4bb, got %+v", legal)
+	}
+
+	// A button straddle moves the first action to the small blind, and is
+	// never posted heads-up, where the button is the small blind.
+	cfg.Straddle, cfg.StraddleMultiple = domain.StraddleButton, 3
+	state = start(cfg, 1, 1, 2, 3, 4)
+	if state.CurrentBet != 3*cfg.BigBlind || state.ActingSeat != mustSeatNo(t, cfg, 2) {
+		t.Fatalf("expected a 3bb button straddle with the small blind to act, got bet %d acting %d", state.CurrentBet, state.ActingSeat)
+	}
+	if state = start(cfg, 1, 1, 2); state.StraddleSeat != nil || state.CurrentBet != cfg.BigBlind {
+		t.Fatalf("expected no straddle heads-up, got %+v", state)
+	}
+}
+
 func TestStartNewHandRejectsNoActiveSeats(t *testing.T) {
 	t.Parallel()
 
@@ -380,6 +561,505 @@ func TestApplyActionPreservesChipConservation(t *testing.T) {
 	}
 }
 
+func TestNormalizeActionAllIn(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	allIn := mustAction(t, domain.ActionAllIn, nil)
+
+	facingBet := startedFourSeatHand(t)
+	normalized, err := NormalizeAction(facingBet, allIn)
+	if err != nil {
+		t.Fatalf("NormalizeAction failed: %v", err)
+	}
+	if normalized.Kind != domain.ActionRaise || normalized.Amount == nil || *normalized.Amount != cfg.StartingStack {
+		t.Fatalf("expected raise to %d, got %+v", cfg.StartingStack, normalized)
+	}
+
+	shortStack := startedFourSeatHand(t)
+	setSeatStack(t, &shortStack, shortStack.ActingSeat, 60)
+	normalized, err = NormalizeAction(shortStack, allIn)
+	if err != nil {
+		t.Fatalf("NormalizeAction failed: %v", err)
+	}
+	if normalized.Kind != domain.ActionCall || normalized.Amount != nil {
+		t.Fatalf("expected call for stack below the current bet, got %+v", normalized)
+	}
+
+	unopened := startedTwoSeatPostFlopRound(t, domain.StreetFlop)
+	normalized, err = NormalizeAction(unopened, allIn)
+	if err != nil {
+		t.Fatalf("NormalizeAction failed: %v", err)
+	}
+	if normalized.Kind != domain.ActionBet || normalized.Amount == nil || *normalized.Amount != cfg.StartingStack {
+		t.Fatalf("expected bet of %d, got %+v", cfg.StartingStack, normalized)
+	}
+
+	call := mustAction(t, domain.ActionCall, nil)
+	normalized, err = NormalizeAction(facingBet, call)
+	if err != nil {
+		t.Fatalf("NormalizeAction failed: %v", err)
+	}
+	if normalized.Kind != domain.ActionCall {
+		t.Fatalf("expected non all-in action to pass through, got %+v", normalized)
+	}
+}
+
+func TestApplyActionAllInShoveCommitsWholeStack(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	state := startedFourSeatHand(t)
+	acting := state.ActingSeat
+	initialTotal := chipTotal(state)
+
+	next, err := ApplyAction(state, mustAction(t, domain.ActionAllIn, nil))
+	if err != nil {
+		t.Fatalf("all-in failed: %v", err)
+	}
+	seat := findSeat(t, next, acting)
+	if seat.Stack != 0 || seat.CommittedInRound != cfg.StartingStack {
+		t.Fatalf("expected whole stack committed, got %+v", seat)
+	}
+	if next.CurrentBet != cfg.StartingStack {
+		t.Fatalf("expected current bet %d, got %d", cfg.StartingStack, next.CurrentBet)
+	}
+	if chipTotal(next) != initialTotal {
+		t.Fatalf("chip conservation violated: want %d got %d", initialTotal, chipTotal(next))
+	}
+}
+
+func TestApplyActionSkipsBettingOnceOnlyTheCoveringStackHasChips(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	seats := mustSeats(t, cfg, 1, 2)
+	seats[0].Stack = cfg.StartingStack / 4
+	state, err := StartNewHand(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      seats,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+	state, err = ApplyAction(state, mustAction(t, domain.ActionAllIn, nil))
+	if err != nil {
+		t.Fatalf("all-in failed: %v", err)
+	}
+	state, err = ApplyAction(state, mustAction(t, domain.ActionCall, nil))
+	if err != nil {
+		t.Fatalf("call failed: %v", err)
+	}
+	if state.Phase != domain.HandPhaseShowdown || len(state.Board) != 0 {
+		t.Fatalf("expected showdown before the flop with no one left to bet, got phase %q and board %v", state.Phase, state.Board)
+	}
+
+	// A button whose small blind puts it all-in leaves the big blind nothing
+	// to decide.
+	seats = mustSeats(t, cfg, 1, 2)
+	seats[0].Stack = cfg.SmallBlind
+	state, err = StartNewHand(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     2,
+		Seats:      seats,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+	if state.Phase != domain.HandPhaseShowdown {
+		t.Fatalf("expected the blinds to close the betting, got phase %q", state.Phase)
+	}
+}
+
+func TestApplyActionAcceptsShortAllInRaise(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	state := startedFourSeatHand(t)
+	acting := state.ActingSeat
+	setSeatStack(t, &state, acting, 150)
+
+	next, err := ApplyAction(state, mustAction(t, domain.ActionAllIn, nil))
+	if err != nil {
+		t.Fatalf("short all-in failed: %v", err)
+	}
+	if next.CurrentBet != 150 {
+		t.Fatalf("expected current bet 150, got %d", next.CurrentBet)
+	}
+	if next.LastFullRaise != cfg.BigBlind {
+		t.Fatalf("expected incomplete raise to keep last full raise %d, got %d", cfg.BigBlind, next.LastFullRaise)
+	}
+	if next.MinRaiseTo != 150+cfg.BigBlind {
+		t.Fatalf("expected min raise to %d, got %d", 150+cfg.BigBlind, next.MinRaiseTo)
+	}
+	if seat := findSeat(t, next, acting); seat.Stack != 0 {
+		t.Fatalf("expected acting seat all-in, got stack %d", seat.Stack)
+	}
+}
+
+func TestApplyActionRejectsShortRaiseThatIsNotAllIn(t *testing.T) {
+	t.Parallel()
+
+	state := startedFourSeatHand(t)
+	raiseTo := domain.Chips(150)
+	_, err := ApplyAction(state, mustAction(t, domain.ActionRaise, &raiseTo))
+	if !errors.Is(err, ErrIllegalAction) {
+		t.Fatalf("expected ErrIllegalAction, got %v", err)
+	}
+}
+
+func TestApplyActionIncompleteAllInRaiseDoesNotReopenBetting(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	utg, button, sb, bb := mustSeatNo(t, cfg, 4), mustSeatNo(t, cfg, 1), mustSeatNo(t, cfg, 2), mustSeatNo(t, cfg, 3)
+	state := startedFourSeatHand(t)
+	setSeatStack(t, &state, button, 400)
+	setSeatStack(t, &state, sb, 500)
+	raiseTo := domain.Chips(300)
+	state, err := ApplyAction(state, mustAction(t, domain.ActionRaise, &raiseTo))
+	if err != nil {
+		t.Fatalf("raise failed: %v", err)
+	}
+	// The button's all-in to 400 is 100 over a full raise of 200.
+	state, err = ApplyAction(state, mustAction(t, domain.ActionAllIn, nil))
+	if err != nil {
+		t.Fatalf("short all-in failed: %v", err)
+	}
+	if state.LastFullRaise != 200 || state.MinRaiseTo != 600 {
+		t.Fatalf("expected the full raise of 200 kept, min raise to 600, got %d and %d", state.LastFullRaise, state.MinRaiseTo)
+	}
+
+	locked, err := ApplyAction(state, mustAction(t, domain.ActionFold, nil))
+	if err != nil {
+		t.Fatalf("fold failed: %v", err)
+	}
+	if minRaise, ok := MinRaiseTo(locked, bb); !ok || minRaise != 600 {
+		t.Fatalf("expected the big blind, yet to act, to raise to at least 600, got %d (%v)", minRaise, ok)
+	}
+	locked, err = ApplyAction(locked, mustAction(t, domain.ActionCall, nil))
+	if err != nil {
+		t.Fatalf("call failed: %v", err)
+	}
+	if locked.ActingSeat != utg {
+		t.Fatalf("expected the opener to owe a call, got seat %d acting", locked.ActingSeat)
+	}
+	if _, ok := MinRaiseTo(locked, utg); ok {
+		t.Fatalf("expected the opener not to be allowed to raise the incomplete all-in")
+	}
+	legal, err := LegalActions(locked, utg)
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	if legal.Allows(domain.ActionRaise) || legal.Allows(domain.ActionAllIn) || !legal.Allows(domain.ActionCall) {
+		t.Fatalf("expected only call or fold, got %+v", legal.Kinds)
+	}
+	reraise := domain.Chips(1000)
+	if _, err := ApplyAction(locked, mustAction(t, domain.ActionRaise, &reraise)); !errors.Is(err, ErrIllegalAction) {
+		t.Fatalf("expected ErrIllegalAction, got %v", err)
+	}
+	if normalized, err := NormalizeAction(locked, mustAction(t, domain.ActionAllIn, nil)); err != nil || normalized.Kind != domain.ActionCall {
+		t.Fatalf("expected all-in to normalize to a call, got %+v (%v)", normalized, err)
+	}
+
+	// A second short all-in to 550 takes the opener 250 past their 300,
+	// more than a full raise, so the betting reopens for them.
+	reopened, err := ApplyAction(state, mustAction(t, domain.ActionAllIn, nil))
+	if err != nil {
+		t.Fatalf("small blind all-in failed: %v", err)
+	}
+	reopened, err = ApplyAction(reopened, mustAction(t, domain.ActionCall, nil))
+	if err != nil {
+		t.Fatalf("call failed: %v", err)
+	}
+	if minRaise, ok := MinRaiseTo(reopened, utg); !ok || minRaise != 750 {
+		t.Fatalf("expected the opener to raise to at least 750, got %d (%v)", minRaise, ok)
+	}
+}
+
+func TestApplyActionUnderRulesV1LetsAnIncompleteAllInReopenBetting(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	utg, button, sb := mustSeatNo(t, cfg, 4), mustSeatNo(t, cfg, 1), mustSeatNo(t, cfg, 2)
+	state := startedFourSeatHand(t)
+	if state.RulesVersion != domain.CurrentRulesVersion {
+		t.Fatalf("expected a new hand on the current rules, got %d", state.RulesVersion)
+	}
+	state.RulesVersion = domain.RulesV1
+	setSeatStack(t, &state, button, 400)
+	setSeatStack(t, &state, sb, 500)
+	raiseTo := domain.Chips(300)
+	steps := []struct {
+		kind   domain.ActionKind
+		amount *domain.Chips
+	}{
+		{kind: domain.ActionRaise, amount: &raiseTo},
+		{kind: domain.ActionAllIn},
+		{kind: domain.ActionFold},
+		{kind: domain.ActionCall},
+	}
+	for _, step := range steps {
+		var err error
+		if state, err = ApplyAction(state, mustAction(t, step.kind, step.amount)); err != nil {
+			t.Fatalf("%s failed: %v", step.kind, err)
+		}
+	}
+	if state.ActingSeat != utg {
+		t.Fatalf("expected the opener to act, got seat %d", state.ActingSeat)
+	}
+	if minRaise, ok := MinRaiseTo(state, utg); !ok || minRaise != 600 {
+		t.Fatalf("expected the pinned rules to let the opener raise to 600, got %d (%v)", minRaise, ok)
+	}
+}
+
+func TestValidateActionNormalizesWithoutApplying(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	state := startedFourSeatHand(t)
+	pot := state.Pot
+
+	action, err := ValidateAction(state, mustAction(t, domain.ActionAllIn, nil))
+	if err != nil {
+		t.Fatalf("ValidateAction failed: %v", err)
+	}
+	if action.Kind != domain.ActionRaise || action.Amount == nil || *action.Amount != cfg.StartingStack {
+		t.Fatalf("expected a raise to %d, got %+v", cfg.StartingStack, action)
+	}
+	if state.Pot != pot || findSeat(t, state, state.ActingSeat).Stack != cfg.StartingStack {
+		t.Fatalf("expected the state to be left untouched, got %+v", state)
+	}
+
+	raiseTo := cfg.BigBlind + cfg.BigBlind/2
+	_, err = ValidateAction(state, mustAction(t, domain.ActionRaise, &raiseTo))
+	want := fmt.Sprintf("illegal action: raise to %d is below the minimum raise to %d and is not all-in", raiseTo, state.MinRaiseTo)
+	if !errors.Is(err, ErrIllegalAction) || err.Error() != want {
+		t.Fatalf("expected %q, got %v", want, err)
+	}
+}
+
+func TestApplyActionEnforcesFixedLimitRaiseCap(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.BettingStructure = domain.BettingFixedLimit
+	state, err := StartNewHand(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      mustSeats(t, cfg, 1, 2, 3, 4),
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+	if state.RaiseCap != domain.DefaultFixedLimitRaiseCap || state.StreetBets != 1 {
+		t.Fatalf("expected cap %d with the big blind as first bet, got cap=%d bets=%d", domain.DefaultFixedLimitRaiseCap, state.RaiseCap, state.StreetBets)
+	}
+
+	for _, raiseTo := range []domain.Chips{200, 300, 400} {
+		amount := raiseTo
+		state, err = ApplyAction(state, mustAction(t, domain.ActionRaise, &amount))
+		if err != nil {
+			t.Fatalf("raise to %d failed: %v", raiseTo, err)
+		}
+	}
+	if !state.RaiseCapReached() || state.StreetBets != 4 {
+		t.Fatalf("expected street capped after 4 bets, got bets=%d", state.StreetBets)
+	}
+
+	raiseTo := domain.Chips(500)
+	if _, err := ApplyAction(state, mustAction(t, domain.ActionRaise, &raiseTo)); !errors.Is(err, ErrRaiseCapReached) {
+		t.Fatalf("expected ErrRaiseCapReached, got %v", err)
+	}
+	normalized, err := NormalizeAction(state, mustAction(t, domain.ActionAllIn, nil))
+	if err != nil {
+		t.Fatalf("NormalizeAction failed: %v", err)
+	}
+	if normalized.Kind != domain.ActionCall {
+		t.Fatalf("expected capped all-in to normalize to call, got %+v", normalized)
+	}
+	state, err = ApplyAction(state, mustAction(t, domain.ActionCall, nil))
+	if err != nil {
+		t.Fatalf("call at cap failed: %v", err)
+	}
+	for state.Street == domain.StreetPreflop {
+		state, err = ApplyAction(state, mustAction(t, domain.ActionCall, nil))
+		if err != nil {
+			t.Fatalf("closing call failed: %v", err)
+		}
+	}
+	if state.StreetBets != 0 || state.RaiseCapReached() {
+		t.Fatalf("expected bet count reset on new street, got %d", state.StreetBets)
+	}
+}
+
+func TestApplyActionShortAllInBetLetsTheNextSeatCompleteAFixedLimitBet(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.BettingStructure = domain.BettingFixedLimit
+	state := limpedThreeWayFlop(t, cfg)
+	bettor := state.ActingSeat
+	setSeatStack(t, &state, bettor, 40)
+
+	allIn := domain.Chips(40)
+	state, err := ApplyAction(state, mustAction(t, domain.ActionBet, &allIn))
+	if err != nil {
+		t.Fatalf("short all-in bet failed: %v", err)
+	}
+	if state.CurrentBet != 40 || state.StreetBets != 0 || state.LastFullRaise != cfg.BigBlind || state.MinRaiseTo != cfg.BigBlind {
+		t.Fatalf("expected a 40 all-in that leaves the %d bet to complete, got bet=%d bets=%d full=%d min=%d", cfg.BigBlind, state.CurrentBet, state.StreetBets, state.LastFullRaise, state.MinRaiseTo)
+	}
+	legal, err := LegalActions(state, state.ActingSeat)
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	if !legal.Allows(domain.ActionRaise) || legal.MinRaiseTo != cfg.BigBlind || legal.MaxRaiseTo != cfg.BigBlind {
+		t.Fatalf("expected the next seat to complete the bet to %d, got %+v", cfg.BigBlind, legal)
+	}
+	state, err = ApplyAction(state, mustAction(t, domain.ActionRaise, &legal.MinRaiseTo))
+	if err != nil {
+		t.Fatalf("completing the bet failed: %v", err)
+	}
+	if state.StreetBets != 1 || state.LastFullRaise != cfg.BigBlind || state.MinRaiseTo != 2*cfg.BigBlind {
+		t.Fatalf("expected the completed bet to count as the first bet, got bets=%d full=%d min=%d", state.StreetBets, state.LastFullRaise, state.MinRaiseTo)
+	}
+}
+
+func TestApplyActionShortAllInBetKeepsTheNoLimitMinimumRaise(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	state := limpedThreeWayFlop(t, cfg)
+	setSeatStack(t, &state, state.ActingSeat, 40)
+
+	allIn := domain.Chips(40)
+	state, err := ApplyAction(state, mustAction(t, domain.ActionBet, &allIn))
+	if err != nil {
+		t.Fatalf("short all-in bet failed: %v", err)
+	}
+	if state.LastFullRaise != cfg.BigBlind || state.MinRaiseTo != 40+cfg.BigBlind {
+		t.Fatalf("expected a full bet's raise over the all-in, got full=%d min=%d", state.LastFullRaise, state.MinRaiseTo)
+	}
+	legal, err := LegalActions(state, state.ActingSeat)
+	if err != nil {
+		t.Fatalf("LegalActions failed: %v", err)
+	}
+	if !legal.Allows(domain.ActionRaise) || legal.MinRaiseTo != 40+cfg.BigBlind {
+		t.Fatalf("expected a minimum raise to %d, got %+v", 40+cfg.BigBlind, legal)
+	}
+	short := 2 * allIn
+	if _, err := ApplyAction(state, mustAction(t, domain.ActionRaise, &short)); !errors.Is(err, ErrIllegalAction) {
+		t.Fatalf("expected a raise of less than a full bet to be refused, got %v", err)
+	}
+}
+
+func TestApplyActionRaiseCapIsLiftedHeadsUp(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.BettingStructure = domain.BettingFixedLimit
+	cfg.RaiseCap = 2
+	state, err := StartNewHand(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      mustSeats(t, cfg, 1, 2),
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+
+	for _, raiseTo := range []domain.Chips{200, 300, 400} {
+		amount := raiseTo
+		state, err = ApplyAction(state, mustAction(t, domain.ActionRaise, &amount))
+		if err != nil {
+			t.Fatalf("heads-up raise to %d failed: %v", raiseTo, err)
+		}
+	}
+	if state.StreetBets != 4 || state.RaiseCapReached() {
+		t.Fatalf("expected uncapped heads-up betting, got bets=%d capped=%v", state.StreetBets, state.RaiseCapReached())
+	}
+}
+
+func TestApplyActionSizesFixedLimitBetsByStreet(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.BettingStructure = domain.BettingFixedLimit
+	state, err := StartNewHand(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      mustSeats(t, cfg, 1, 2),
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+	legal, err := LegalActions(state, state.ActingSeat)
+	if err != nil || legal.MinRaiseTo != 200 || legal.MaxRaiseTo != 200 || legal.Allows(domain.ActionAllIn) {
+		t.Fatalf("expected a single preflop raise to 200 and no all-in, got %+v (%v)", legal, err)
+	}
+	for _, kind := range []domain.ActionKind{domain.ActionCall, domain.ActionCheck} {
+		if state, err = ApplyAction(state, mustAction(t, kind, nil)); err != nil {
+			t.Fatalf("preflop %s failed: %v", kind, err)
+		}
+	}
+
+	// The flop is played in small bets.
+	if legal, err = LegalActions(state, state.ActingSeat); err != nil || legal.MinBet != 100 || legal.MaxBet != 100 {
+		t.Fatalf("expected a flop bet of exactly 100, got %+v (%v)", legal, err)
+	}
+	big := domain.Chips(200)
+	if _, err := ApplyAction(state, mustAction(t, domain.ActionBet, &big)); !errors.Is(err, ErrFixedLimitExceeded) {
+		t.Fatalf("expected ErrFixedLimitExceeded for a big bet on the flop, got %v", err)
+	}
+	if _, err := ApplyAction(state, mustAction(t, domain.ActionAllIn, nil)); !errors.Is(err, ErrFixedLimitExceeded) {
+		t.Fatalf("expected ErrFixedLimitExceeded moving all in, got %v", err)
+	}
+	for range 2 {
+		if state, err = ApplyAction(state, mustAction(t, domain.ActionCheck, nil)); err != nil {
+			t.Fatalf("flop check failed: %v", err)
+		}
+	}
+
+	// The turn doubles to big bets, and a raise is one more of them.
+	if state.Street != domain.StreetTurn {
+		t.Fatalf("expected the turn, got %s", state.Street)
+	}
+	if state, err = ApplyAction(state, mustAction(t, domain.ActionBet, &big)); err != nil {
+		t.Fatalf("turn bet of %d failed: %v", big, err)
+	}
+	if legal, err = LegalActions(state, state.ActingSeat); err != nil || legal.MinRaiseTo != 400 || legal.MaxRaiseTo != 400 {
+		t.Fatalf("expected a turn raise to exactly 400, got %+v (%v)", legal, err)
+	}
+	over := domain.Chips(500)
+	if _, err := ApplyAction(state, mustAction(t, domain.ActionRaise, &over)); !errors.Is(err, ErrFixedLimitExceeded) {
+		t.Fatalf("expected ErrFixedLimitExceeded raising to %d, got %v", over, err)
+	}
+}
+
+func setSeatStack(t *testing.T, state *domain.HandState, seatNo domain.SeatNo, stack domain.Chips) {
+	t.Helper()
+
+	for i := range state.Seats {
+		if state.Seats[i].SeatNo == seatNo {
+			state.Seats[i].Stack = stack
+			return
+		}
+	}
+	t.Fatalf("seat %d not found", seatNo)
+}
+
 func startedFourSeatHand(t *testing.T) domain.HandState {
 	t.Helper()
 
@@ -398,6 +1078,33 @@ func startedFourSeatHand(t *testing.T) domain.HandState {
 	return state
 }
 
+// limpedThreeWayFlop deals a three-seat hand under cfg in which every seat
+// limps and the big blind checks, leaving the small blind first to act on
+// the flop.
+func limpedThreeWayFlop(t *testing.T, cfg domain.TableConfig) domain.HandState {
+	t.Helper()
+
+	state, err := StartNewHand(StartNewHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		Seats:      mustSeats(t, cfg, 1, 2, 3),
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("StartNewHand failed: %v", err)
+	}
+	for _, kind := range []domain.ActionKind{domain.ActionCall, domain.ActionCall, domain.ActionCheck} {
+		if state, err = ApplyAction(state, mustAction(t, kind, nil)); err != nil {
+			t.Fatalf("preflop %s failed: %v", kind, err)
+		}
+	}
+	if state.Street != domain.StreetFlop || state.ActingSeat != mustSeatNo(t, cfg, 2) {
+		t.Fatalf("expected the small blind to open the flop, got street %q acting %d", state.Street, state.ActingSeat)
+	}
+	return state
+}
+
 func startedTwoSeatPostFlopRound(t *testing.T, street domain.Street) domain.HandState {
 	t.Helper()
 
@@ -458,7 +1165,7 @@ func findSeat(t *testing.T, state domain.HandState, seatNo domain.SeatNo) domain
 	return domain.SeatState{}
 }
 
-func mustAction(t *testing.T, kind domain.ActionKind, amount *uint32) domain.Action {
+func mustAction(t *testing.T, kind domain.ActionKind, amount *domain.Chips) domain.Action {
 	t.Helper()
 
 	action, err := domain.NewAction(kind, amount)
@@ -468,7 +1175,7 @@ func mustAction(t *testing.T, kind domain.ActionKind, amount *uint32) domain.Act
 	return action
 }
 
-func chipTotal(state domain.HandState) uint32 {
+func chipTotal(state domain.HandState) domain.Chips {
 	total := state.Pot
 	for _, seat := range state.Seats {
 		total += seat.Stack
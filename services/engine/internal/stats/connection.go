@@ -0,0 +1,142 @@
+package stats
+
+import (
+	"sort"
+	"sync"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// ConnectionHealth summarizes a seat's recent connection quality for other
+// players, so a run of timeouts can be read as connectivity rather than
+// strategy.
+type ConnectionHealth string
+
+const (
+	ConnectionGood     ConnectionHealth = "good"
+	ConnectionDegraded ConnectionHealth = "degraded"
+	ConnectionPoor     ConnectionHealth = "poor"
+)
+
+const (
+	// connectionWindow is how many of a seat's latest decisions decide its
+	// health.
+	connectionWindow = 20
+	// poorConnectionMisses is how many missed deadlines inside the window
+	// make a connection poor; a single miss or late arrival is degraded.
+	poorConnectionMisses = 3
+)
+
+// ConnectionQuality is one seat's connection telemetry. PingMS is the
+// smoothed response time of delivered decisions and JitterMS its smoothed
+// variation between consecutive decisions, both in the style of RFC 6298 and
+// RFC 3550. The counters cover every decision the tracker has seen.
+type ConnectionQuality struct {
+	SeatNo          domain.SeatNo    `json:"seat_no"`
+	Decisions       int              `json:"decisions"`
+	MissedDeadlines int              `json:"missed_deadlines"`
+	LateDecisions   int              `json:"late_decisions"`
+	PingMS          uint64           `json:"ping_ms"`
+	JitterMS        uint64           `json:"jitter_ms"`
+	Health          ConnectionHealth `json:"health"`
+}
+
+type seatConnection struct {
+	quality ConnectionQuality
+	ping    time.Duration
+	jitter  time.Duration
+	last    time.Duration
+	sampled bool
+	recent  []domain.DeadlinePolicy
+}
+
+// ConnectionTracker accumulates per-seat connection telemetry for one table.
+// It is safe for concurrent use.
+type ConnectionTracker struct {
+	mu    sync.Mutex
+	seats map[domain.SeatNo]*seatConnection
+}
+
+func NewConnectionTracker() *ConnectionTracker {
+	return &ConnectionTracker{seats: make(map[domain.SeatNo]*seatConnection)}
+}
+
+// Observe records one decision by seat: how the engine judged its arrival
+// and, when it arrived, how long it took.
+func (t *ConnectionTracker) Observe(seat domain.SeatNo, policy domain.DeadlinePolicy, responseTime time.Duration) ConnectionQuality {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	conn, ok := t.seats[seat]
+	if !ok {
+		conn = &seatConnection{quality: ConnectionQuality{SeatNo: seat}}
+		t.seats[seat] = conn
+	}
+
+	conn.quality.Decisions++
+	switch policy {
+	case domain.DeadlineNoDecision:
+		conn.quality.MissedDeadlines++
+	case domain.DeadlineLatencyGrace:
+		conn.quality.LateDecisions++
+	}
+	if policy != domain.DeadlineNoDecision {
+		responseTime = max(responseTime, 0)
+		if !conn.sampled {
+			conn.ping = responseTime
+			conn.sampled = true
+		} else {
+			conn.jitter += (absDuration(responseTime-conn.last) - conn.jitter) / 16
+			conn.ping += (responseTime - conn.ping) / 8
+		}
+		conn.last = responseTime
+		conn.quality.PingMS = uint64(conn.ping.Milliseconds())
+		conn.quality.JitterMS = uint64(conn.jitter.Milliseconds())
+	}
+
+	conn.recent = append(conn.recent, policy)
+	if len(conn.recent) > connectionWindow {
+		conn.recent = conn.recent[1:]
+	}
+	conn.quality.Health = connectionHealth(conn.recent)
+	return conn.quality
+}
+
+// Qualities returns every observed seat ordered by seat number.
+func (t *ConnectionTracker) Qualities() []ConnectionQuality {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	out := make([]ConnectionQuality, 0, len(t.seats))
+	for _, conn := range t.seats {
+		out = append(out, conn.quality)
+	}
+	sort.Slice(out, func(i, j int) bool { return out[i].SeatNo < out[j].SeatNo })
+	return out
+}
+
+func connectionHealth(recent []domain.DeadlinePolicy) ConnectionHealth {
+	missed, late := 0, 0
+	for _, policy := range recent {
+		switch policy {
+		case domain.DeadlineNoDecision:
+			missed++
+		case domain.DeadlineLatencyGrace:
+			late++
+		}
+	}
+	switch {
+	case missed >= poorConnectionMisses:
+		return ConnectionPoor
+	case missed > 0 || late > 0:
+		return ConnectionDegraded
+	default:
+		return ConnectionGood
+	}
+}
+
+func absDuration(d time.Duration) time.Duration {
+	if d < 0 {
+		return -d
+	}
+	return d
+}
@@ -0,0 +1,41 @@
+package stats
+
+import (
+	"testing"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestConnectionTracker_SmoothsPingAndGradesHealth(t *testing.T) {
+	t.Parallel()
+
+	tracker := NewConnectionTracker()
+	tracker.Observe(1, domain.DeadlineOnTime, 100*time.Millisecond)
+	quality := tracker.Observe(1, domain.DeadlineOnTime, 200*time.Millisecond)
+	want := ConnectionQuality{SeatNo: 1, Decisions: 2, PingMS: 112, JitterMS: 6, Health: ConnectionGood}
+	if quality != want {
+		t.Fatalf("expected %+v, got %+v", want, quality)
+	}
+
+	if got := tracker.Observe(2, domain.DeadlineLatencyGrace, time.Second); got.Health != ConnectionDegraded || got.LateDecisions != 1 {
+		t.Fatalf("expected a late decision to degrade the seat, got %+v", got)
+	}
+	for i := 0; i < poorConnectionMisses; i++ {
+		quality = tracker.Observe(2, domain.DeadlineNoDecision, 0)
+	}
+	if quality.Health != ConnectionPoor || quality.MissedDeadlines != poorConnectionMisses || quality.PingMS != 1000 {
+		t.Fatalf("expected repeated misses to make the seat poor, got %+v", quality)
+	}
+	for i := 0; i < connectionWindow; i++ {
+		quality = tracker.Observe(2, domain.DeadlineOnTime, time.Second)
+	}
+	if quality.Health != ConnectionGood || quality.MissedDeadlines != poorConnectionMisses {
+		t.Fatalf("expected the seat to recover once the misses leave the window, got %+v", quality)
+	}
+
+	qualities := tracker.Qualities()
+	if len(qualities) != 2 || qualities[0].SeatNo != 1 || qualities[1].SeatNo != 2 {
+		t.Fatalf("expected both seats ordered by seat number, got %+v", qualities)
+	}
+}
@@ -0,0 +1,181 @@
+package stats
+
+import (
+	"math"
+	"slices"
+	"sort"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// ActionClass abstracts a decision to what it does to the betting.
+type ActionClass string
+
+const (
+	ActionClassFold       ActionClass = "fold"
+	ActionClassPassive    ActionClass = "passive"
+	ActionClassAggressive ActionClass = "aggressive"
+)
+
+// DecisionNode is where a decision sits in the abstracted game tree: the
+// street, the bets and raises made on it so far (preflop counts the big
+// blind, and three or more are one node), and whether the seat faced a bet.
+type DecisionNode struct {
+	Street domain.Street `json:"street"`
+	Bets   uint8         `json:"bets"`
+	Facing bool          `json:"facing_bet"`
+}
+
+// Decision is one observed choice, with the pot before it and the amount
+// the seat faced, both in big blinds.
+type Decision struct {
+	Node     DecisionNode
+	Class    ActionClass
+	PotBB    float64
+	ToCallBB float64
+}
+
+// NodeReport is a bot's play at one node. BestResponseBB is what a bluff
+// with any two cards against it earns per decision there, and is zero where
+// the bot defends often enough.
+type NodeReport struct {
+	DecisionNode
+	Decisions      int     `json:"decisions"`
+	Fold           float64 `json:"fold"`
+	Passive        float64 `json:"passive"`
+	Aggressive     float64 `json:"aggressive"`
+	EntropyBits    float64 `json:"entropy_bits"`
+	BestResponseBB float64 `json:"best_response_bb"`
+}
+
+// BotReport estimates how predictable and how exploitable a bot's observed
+// play is. ActionEntropyBits is the Shannon entropy of its fold, passive and
+// aggressive frequencies at each node, averaged over its decisions: zero
+// for a bot that always does the same thing in a spot, log2(3) at most.
+// ExploitabilityBBPer100 is what the simple best response, bluffing every
+// node where the bot folds to bets more than the pot odds allow, would win
+// per 100 hands. Bets made by the blinds alone are never bluffed at.
+type BotReport struct {
+	BotID                  string       `json:"bot_id"`
+	Hands                  int          `json:"hands"`
+	Decisions              int          `json:"decisions"`
+	ActionEntropyBits      float64      `json:"action_entropy_bits"`
+	ExploitabilityBBPer100 float64      `json:"exploitability_bb_per_100"`
+	Nodes                  []NodeReport `json:"nodes"`
+}
+
+// ObserveDecision files the acting seat's action in state under its node.
+// It reports false when state has no acting seat or no big blind to scale by.
+func ObserveDecision(state domain.HandState, action domain.Action) (Decision, bool) {
+	if state.BigBlind == 0 {
+		return Decision{}, false
+	}
+	var seat domain.SeatState
+	found := false
+	for _, candidate := range state.Seats {
+		if candidate.SeatNo == state.ActingSeat {
+			seat, found = candidate, true
+			break
+		}
+	}
+	if !found {
+		return Decision{}, false
+	}
+	var toCall domain.Chips
+	if state.CurrentBet > seat.CommittedInRound {
+		toCall = state.CurrentBet - seat.CommittedInRound
+	}
+
+	decision := Decision{
+		Node:     DecisionNode{Street: state.Street, Bets: min(state.StreetBets, 3), Facing: toCall > 0},
+		Class:    ActionClassPassive,
+		PotBB:    float64(state.Pot) / float64(state.BigBlind),
+		ToCallBB: float64(toCall) / float64(state.BigBlind),
+	}
+	switch action.Kind {
+	case domain.ActionFold:
+		decision.Class = ActionClassFold
+	case domain.ActionBet, domain.ActionRaise:
+		decision.Class = ActionClassAggressive
+	case domain.ActionAllIn:
+		// An all-in that cannot cover the bet only calls it.
+		if seat.Stack > toCall {
+			decision.Class = ActionClassAggressive
+		}
+	}
+	return decision, true
+}
+
+var streetOrder = []domain.Street{domain.StreetPreflop, domain.StreetFlop, domain.StreetTurn, domain.StreetRiver}
+
+type nodeCounts struct {
+	total    int
+	byClass  map[ActionClass]int
+	potBB    float64
+	toCallBB float64
+}
+
+// AnalyzeBot reports on botID's decisions over the hands it played.
+func AnalyzeBot(botID string, hands int, decisions []Decision) BotReport {
+	counts := make(map[DecisionNode]*nodeCounts)
+	for _, decision := range decisions {
+		c, ok := counts[decision.Node]
+		if !ok {
+			c = &nodeCounts{byClass: make(map[ActionClass]int, 3)}
+			counts[decision.Node] = c
+		}
+		c.total++
+		c.byClass[decision.Class]++
+		c.potBB += decision.PotBB
+		c.toCallBB += decision.ToCallBB
+	}
+
+	report := BotReport{BotID: botID, Hands: hands, Decisions: len(decisions), Nodes: make([]NodeReport, 0, len(counts))}
+	for node, c := range counts {
+		n := float64(c.total)
+		entry := NodeReport{
+			DecisionNode: node,
+			Decisions:    c.total,
+			Fold:         float64(c.byClass[ActionClassFold]) / n,
+			Passive:      float64(c.byClass[ActionClassPassive]) / n,
+			Aggressive:   float64(c.byClass[ActionClassAggressive]) / n,
+		}
+		for _, p := range []float64{entry.Fold, entry.Passive, entry.Aggressive} {
+			if p > 0 {
+				entry.EntropyBits -= p * math.Log2(p)
+			}
+		}
+		if node.Facing && (node.Street != domain.StreetPreflop || node.Bets > 1) {
+			// The bluffer risks the bet the bot faced to win the pot as it
+			// stood before that bet.
+			bet := c.toCallBB / n
+			won := c.potBB/n - bet
+			entry.BestResponseBB = max(0, entry.Fold*won-(1-entry.Fold)*bet)
+		}
+		report.Nodes = append(report.Nodes, entry)
+	}
+	sort.Slice(report.Nodes, func(i, j int) bool {
+		a, b := report.Nodes[i], report.Nodes[j]
+		if a.Street != b.Street {
+			return slices.Index(streetOrder, a.Street) < slices.Index(streetOrder, b.Street)
+		}
+		if a.Bets != b.Bets {
+			return a.Bets < b.Bets
+		}
+		return !a.Facing && b.Facing
+	})
+
+	// Summing in node order keeps reports identical across runs.
+	var entropy, bestResponse float64
+	for _, node := range report.Nodes {
+		entropy += node.EntropyBits * float64(node.Decisions)
+		bestResponse += node.BestResponseBB * float64(node.Decisions)
+	}
+	if len(decisions) > 0 {
+		report.ActionEntropyBits = entropy / float64(len(decisions))
+	}
+	if hands > 0 {
+		report.ExploitabilityBBPer100 = bestResponse / float64(hands) * 100
+	}
+	return report
+}
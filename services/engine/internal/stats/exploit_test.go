@@ -0,0 +1,89 @@
+package stats
+
+import (
+	"math"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestObserveDecision_FilesTheActionUnderItsNode(t *testing.T) {
+	t.Parallel()
+
+	state := domain.HandState{
+		Street:     domain.StreetFlop,
+		BigBlind:   100,
+		Pot:        600,
+		CurrentBet: 200,
+		StreetBets: 1,
+		ActingSeat: 2,
+		Seats: []domain.SeatState{
+			{SeatNo: 1, Stack: 5000, CommittedInRound: 200},
+			{SeatNo: 2, Stack: 150},
+		},
+	}
+	decision, ok := ObserveDecision(state, domain.Action{Kind: domain.ActionAllIn})
+	if !ok {
+		t.Fatalf("expected the decision to be observed")
+	}
+	want := Decision{Node: DecisionNode{Street: domain.StreetFlop, Bets: 1, Facing: true}, Class: ActionClassPassive, PotBB: 6, ToCallBB: 2}
+	if decision != want {
+		t.Fatalf("expected an all-in short of the bet to count as a call %+v, got %+v", want, decision)
+	}
+
+	state.Seats[1].Stack = 5000
+	if decision, _ := ObserveDecision(state, domain.Action{Kind: domain.ActionAllIn}); decision.Class != ActionClassAggressive {
+		t.Fatalf("expected an all-in over the bet to be aggressive, got %q", decision.Class)
+	}
+	state.BigBlind = 0
+	if _, ok := ObserveDecision(state, domain.Action{Kind: domain.ActionFold}); ok {
+		t.Fatalf("expected no observation without a big blind")
+	}
+}
+
+func TestAnalyzeBot_ScoresEntropyAndTheBluffingBestResponse(t *testing.T) {
+	t.Parallel()
+
+	facingFlopBet := DecisionNode{Street: domain.StreetFlop, Bets: 1, Facing: true}
+	facingBlind := DecisionNode{Street: domain.StreetPreflop, Bets: 1, Facing: true}
+	decisions := []Decision{
+		{Node: facingFlopBet, Class: ActionClassFold, PotBB: 6, ToCallBB: 2},
+		{Node: facingFlopBet, Class: ActionClassFold, PotBB: 6, ToCallBB: 2},
+		{Node: facingFlopBet, Class: ActionClassFold, PotBB: 6, ToCallBB: 2},
+		{Node: facingFlopBet, Class: ActionClassPassive, PotBB: 6, ToCallBB: 2},
+		{Node: facingBlind, Class: ActionClassFold, PotBB: 1.5, ToCallBB: 1},
+		{Node: facingBlind, Class: ActionClassFold, PotBB: 1.5, ToCallBB: 1},
+	}
+	report := AnalyzeBot("alpha", 10, decisions)
+
+	if len(report.Nodes) != 2 || report.Nodes[0].DecisionNode != facingBlind || report.Nodes[1].DecisionNode != facingFlopBet {
+		t.Fatalf("expected the preflop node before the flop node, got %+v", report.Nodes)
+	}
+	// Folding the blinds away is not a bluff anyone made.
+	if node := report.Nodes[0]; node.Fold != 1 || node.EntropyBits != 0 || node.BestResponseBB != 0 {
+		t.Fatalf("unexpected preflop node %+v", node)
+	}
+	// Folding 3 in 4 to a 2bb bet into 4bb: a bluff wins 4 three times and
+	// loses 2 once, 2.5bb a decision.
+	flop := report.Nodes[1]
+	flopEntropy := -(0.75*math.Log2(0.75) + 0.25*math.Log2(0.25))
+	if flop.Fold != 0.75 || flop.Passive != 0.25 || math.Abs(flop.EntropyBits-flopEntropy) > 1e-9 || math.Abs(flop.BestResponseBB-2.5) > 1e-9 {
+		t.Fatalf("unexpected flop node %+v", flop)
+	}
+	if math.Abs(report.ActionEntropyBits-flopEntropy*4/6) > 1e-9 {
+		t.Fatalf("expected entropy averaged over decisions %f, got %f", flopEntropy*4/6, report.ActionEntropyBits)
+	}
+	if math.Abs(report.ExploitabilityBBPer100-100) > 1e-9 {
+		t.Fatalf("expected 100bb/100 exploitability, got %f", report.ExploitabilityBBPer100)
+	}
+
+	// A bot that defends at pot odds gives the bluffer nothing.
+	defends := []Decision{
+		{Node: facingFlopBet, Class: ActionClassFold, PotBB: 6, ToCallBB: 2},
+		{Node: facingFlopBet, Class: ActionClassPassive, PotBB: 6, ToCallBB: 2},
+		{Node: facingFlopBet, Class: ActionClassAggressive, PotBB: 6, ToCallBB: 2},
+	}
+	if report := AnalyzeBot("beta", 10, defends); report.ExploitabilityBBPer100 != 0 || math.Abs(report.ActionEntropyBits-math.Log2(3)) > 1e-9 {
+		t.Fatalf("expected an unexploitable, maximally mixed bot, got %+v", report)
+	}
+}
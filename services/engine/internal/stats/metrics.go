@@ -0,0 +1,135 @@
+package stats
+
+import (
+	"errors"
+	"sort"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+const DefaultMetricsWindow = time.Hour
+
+var ErrInvalidWindow = errors.New("metrics window must be positive")
+
+type HandObservation struct {
+	StartedAt      time.Time
+	FinalState     domain.HandState
+	PreflopActions []domain.ActionKind
+}
+
+// WindowMetrics summarizes the hands started in one window. QualifiedHands
+// counts those meeting the table's domain.QualifiedHandRule.
+type WindowMetrics struct {
+	WindowStart       time.Time `json:"window_start"`
+	WindowEnd         time.Time `json:"window_end"`
+	Hands             int       `json:"hands"`
+	QualifiedHands    int       `json:"qualified_hands"`
+	AvgPotBB          float64   `json:"avg_pot_bb"`
+	ThreeBetFrequency float64   `json:"three_bet_frequency"`
+	ShowdownRate      float64   `json:"showdown_rate"`
+}
+
+type TableMetrics struct {
+	WindowSeconds int64           `json:"window_seconds"`
+	Overall       WindowMetrics   `json:"overall"`
+	Windows       []WindowMetrics `json:"windows"`
+	Stalling      []StallingScore `json:"stalling,omitempty"`
+}
+
+type accumulator struct {
+	hands          int
+	qualifiedHands int
+	potBBTotal     float64
+	openRaised     int
+	threeBet       int
+	wentToShowdown int
+}
+
+func AggregateTableMetrics(observations []HandObservation, window time.Duration) (TableMetrics, error) {
+	if window <= 0 {
+		return TableMetrics{}, ErrInvalidWindow
+	}
+
+	sorted := append([]HandObservation(nil), observations...)
+	sort.SliceStable(sorted, func(i, j int) bool {
+		return sorted[i].StartedAt.Before(sorted[j].StartedAt)
+	})
+
+	result := TableMetrics{
+		WindowSeconds: int64(window / time.Second),
+		Windows:       []WindowMetrics{},
+	}
+	var overall accumulator
+	var current accumulator
+	var currentStart time.Time
+	for i, obs := range sorted {
+		start := obs.StartedAt.UTC().Truncate(window)
+		if i > 0 && !start.Equal(currentStart) {
+			result.Windows = append(result.Windows, current.metrics(currentStart, currentStart.Add(window)))
+			current = accumulator{}
+		}
+		currentStart = start
+		current.add(obs)
+		overall.add(obs)
+	}
+	if len(sorted) > 0 {
+		result.Windows = append(result.Windows, current.metrics(currentStart, currentStart.Add(window)))
+		first := sorted[0].StartedAt.UTC().Truncate(window)
+		result.Overall = overall.metrics(first, currentStart.Add(window))
+	}
+	return result, nil
+}
+
+func (a *accumulator) add(obs HandObservation) {
+	a.hands++
+	if obs.FinalState.Qualified() {
+		a.qualifiedHands++
+	}
+	if obs.FinalState.BigBlind > 0 {
+		a.potBBTotal += float64(obs.FinalState.TotalPot()) / float64(obs.FinalState.BigBlind)
+	}
+
+	raises := 0
+	for _, kind := range obs.PreflopActions {
+		if kind == domain.ActionBet || kind == domain.ActionRaise {
+			raises++
+		}
+	}
+	if raises >= 1 {
+		a.openRaised++
+	}
+	if raises >= 2 {
+		a.threeBet++
+	}
+
+	if wentToShowdown(obs.FinalState) {
+		a.wentToShowdown++
+	}
+}
+
+func (a accumulator) metrics(start time.Time, end time.Time) WindowMetrics {
+	out := WindowMetrics{
+		WindowStart:    start,
+		WindowEnd:      end,
+		Hands:          a.hands,
+		QualifiedHands: a.qualifiedHands,
+	}
+	if a.hands > 0 {
+		out.AvgPotBB = a.potBBTotal / float64(a.hands)
+		out.ShowdownRate = float64(a.wentToShowdown) / float64(a.hands)
+	}
+	if a.openRaised > 0 {
+		out.ThreeBetFrequency = float64(a.threeBet) / float64(a.openRaised)
+	}
+	return out
+}
+
+func wentToShowdown(state domain.HandState) bool {
+	for _, award := range state.ShowdownAwards {
+		if award.Reason != "uncontested" {
+			return true
+		}
+	}
+	return false
+}
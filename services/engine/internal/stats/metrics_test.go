@@ -0,0 +1,130 @@
+package stats
+
+import (
+	"errors"
+	"math"
+	"testing"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func observation(startedAt time.Time, pot domain.Chips, reason string, preflop ...domain.ActionKind) HandObservation {
+	return HandObservation{
+		StartedAt: startedAt,
+		FinalState: domain.HandState{
+			BigBlind: 100,
+			Seats: []domain.SeatState{
+				{SeatNo: 1, TotalCommitted: pot / 2},
+				{SeatNo: 2, TotalCommitted: pot - pot/2},
+			},
+			ShowdownAwards: []domain.PotAward{{Amount: pot, Seats: []domain.SeatNo{1}, Reason: reason}},
+		},
+		PreflopActions: preflop,
+	}
+}
+
+func assertFloat(t *testing.T, name string, got float64, want float64) {
+	t.Helper()
+	if math.Abs(got-want) > 1e-9 {
+		t.Fatalf("expected %s=%v, got %v", name, want, got)
+	}
+}
+
+func TestAggregateTableMetrics_BucketsByWindow(t *testing.T) {
+	t.Parallel()
+
+	base := time.Date(2026, 1, 1, 10, 0, 0, 0, time.UTC)
+	observations := []HandObservation{
+		observation(base.Add(70*time.Minute), 1000, "main_pot", domain.ActionRaise, domain.ActionCall),
+		observation(base.Add(5*time.Minute), 200, "uncontested", domain.ActionFold),
+		observation(base.Add(10*time.Minute), 600, "main_pot", domain.ActionRaise, domain.ActionRaise, domain.ActionCall),
+	}
+
+	metrics, err := AggregateTableMetrics(observations, time.Hour)
+	if err != nil {
+		t.Fatalf("AggregateTableMetrics failed: %v", err)
+	}
+	if metrics.WindowSeconds != 3600 {
+		t.Fatalf("expected window_seconds=3600, got %d", metrics.WindowSeconds)
+	}
+	if len(metrics.Windows) != 2 {
+		t.Fatalf("expected 2 windows, got %d", len(metrics.Windows))
+	}
+
+	first := metrics.Windows[0]
+	if !first.WindowStart.Equal(base) || first.Hands != 2 {
+		t.Fatalf("unexpected first window: %+v", first)
+	}
+	assertFloat(t, "avg_pot_bb", first.AvgPotBB, 4)
+	assertFloat(t, "three_bet_frequency", first.ThreeBetFrequency, 1)
+	assertFloat(t, "showdown_rate", first.ShowdownRate, 0.5)
+
+	second := metrics.Windows[1]
+	if !second.WindowStart.Equal(base.Add(time.Hour)) || second.Hands != 1 {
+		t.Fatalf("unexpected second window: %+v", second)
+	}
+	assertFloat(t, "three_bet_frequency", second.ThreeBetFrequency, 0)
+
+	if metrics.Overall.Hands != 3 {
+		t.Fatalf("expected overall hands=3, got %d", metrics.Overall.Hands)
+	}
+	assertFloat(t, "overall avg_pot_bb", metrics.Overall.AvgPotBB, 6)
+	assertFloat(t, "overall three_bet_frequency", metrics.Overall.ThreeBetFrequency, 0.5)
+	if !metrics.Overall.WindowEnd.Equal(base.Add(2 * time.Hour)) {
+		t.Fatalf("expected overall window end at %v, got %v", base.Add(2*time.Hour), metrics.Overall.WindowEnd)
+	}
+}
+
+func TestAggregateTableMetrics_CountsQualifiedHands(t *testing.T) {
+	t.Parallel()
+
+	base := time.Date(2026, 1, 1, 10, 0, 0, 0, time.UTC)
+	observations := []HandObservation{
+		observation(base, 1000, "main_pot"),
+		observation(base.Add(time.Minute), 200, "uncontested"),
+		observation(base.Add(2*time.Minute), 600, "main_pot"),
+	}
+	rule := &domain.QualifiedHandRule{MinPot: 500}
+	for i := range observations {
+		observations[i].FinalState.Qualification = rule
+	}
+	observations[2].FinalState.Board = []domain.Card{{Rank: 2, Suit: domain.SuitClubs}, {Rank: 3, Suit: domain.SuitClubs}, {Rank: 4, Suit: domain.SuitClubs}}
+
+	metrics, err := AggregateTableMetrics(observations, time.Hour)
+	if err != nil {
+		t.Fatalf("AggregateTableMetrics failed: %v", err)
+	}
+	if metrics.Overall.Hands != 3 || metrics.Overall.QualifiedHands != 2 {
+		t.Fatalf("expected 2 of 3 hands to reach the min pot, got %+v", metrics.Overall)
+	}
+
+	rule.SawFlop = true
+	metrics, err = AggregateTableMetrics(observations, time.Hour)
+	if err != nil {
+		t.Fatalf("AggregateTableMetrics failed: %v", err)
+	}
+	if metrics.Overall.QualifiedHands != 1 {
+		t.Fatalf("expected only the hand that saw a flop to qualify, got %d", metrics.Overall.QualifiedHands)
+	}
+}
+
+func TestAggregateTableMetrics_EmptyInput(t *testing.T) {
+	t.Parallel()
+
+	metrics, err := AggregateTableMetrics(nil, time.Hour)
+	if err != nil {
+		t.Fatalf("AggregateTableMetrics failed: %v", err)
+	}
+	if len(metrics.Windows) != 0 || metrics.Overall.Hands != 0 {
+		t.Fatalf("expected empty metrics, got %+v", metrics)
+	}
+}
+
+func TestAggregateTableMetrics_RejectsNonPositiveWindow(t *testing.T) {
+	t.Parallel()
+
+	if _, err := AggregateTableMetrics(nil, 0); !errors.Is(err, ErrInvalidWindow) {
+		t.Fatalf("expected ErrInvalidWindow, got %v", err)
+	}
+}
@@ -0,0 +1,116 @@
+package stats
+
+import (
+	"sort"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+const (
+	// DefaultOpponentStatsHands is how many of a table's latest hands
+	// opponent stats cover when the caller does not ask for a window.
+	DefaultOpponentStatsHands = 100
+	// MaxOpponentStatsHands caps the window a caller may ask for.
+	MaxOpponentStatsHands = 1000
+)
+
+// SeatAction is one public decision in a hand.
+type SeatAction struct {
+	SeatNo domain.SeatNo
+	Kind   domain.ActionKind
+}
+
+// OpponentHand is what opponent stats read from a completed hand: the seats
+// dealt in and the preflop decisions in order. Blinds are not decisions.
+type OpponentHand struct {
+	Seats   []domain.SeatNo
+	Preflop []SeatAction
+}
+
+// OpponentStats are the standard preflop tendencies of one seat, built only
+// from actions every player at the table saw. VPIP and PFR are shares of the
+// hands the seat was dealt into; ThreeBet is the share of spots facing a
+// single raise that the seat re-raised.
+type OpponentStats struct {
+	SeatNo        domain.SeatNo `json:"seat_no"`
+	Hands         int           `json:"hands"`
+	VPIP          float64       `json:"vpip"`
+	PFR           float64       `json:"pfr"`
+	ThreeBet      float64       `json:"three_bet"`
+	ThreeBetSpots int           `json:"three_bet_spots"`
+}
+
+type opponentCounts struct {
+	hands     int
+	voluntary int
+	raised    int
+	threeBet  int
+	spots     int
+}
+
+// AggregateOpponentStats summarizes every seat dealt into hands, ordered by
+// seat number.
+func AggregateOpponentStats(hands []OpponentHand) []OpponentStats {
+	counts := make(map[domain.SeatNo]*opponentCounts)
+	for _, hand := range hands {
+		voluntary := make(map[domain.SeatNo]bool)
+		raised := make(map[domain.SeatNo]bool)
+		threeBet := make(map[domain.SeatNo]bool)
+		faced := make(map[domain.SeatNo]bool)
+		raises := 0
+		for _, action := range hand.Preflop {
+			if raises == 1 {
+				faced[action.SeatNo] = true
+			}
+			switch action.Kind {
+			case domain.ActionCall:
+				voluntary[action.SeatNo] = true
+			case domain.ActionBet, domain.ActionRaise, domain.ActionAllIn:
+				voluntary[action.SeatNo] = true
+				raised[action.SeatNo] = true
+				if raises == 1 {
+					threeBet[action.SeatNo] = true
+				}
+				raises++
+			}
+		}
+
+		for _, seat := range hand.Seats {
+			c, ok := counts[seat]
+			if !ok {
+				c = &opponentCounts{}
+				counts[seat] = c
+			}
+			c.hands++
+			if voluntary[seat] {
+				c.voluntary++
+			}
+			if raised[seat] {
+				c.raised++
+			}
+			if faced[seat] {
+				c.spots++
+			}
+			if threeBet[seat] {
+				c.threeBet++
+			}
+		}
+	}
+
+	out := make([]OpponentStats, 0, len(counts))
+	for seat, c := range counts {
+		entry := OpponentStats{
+			SeatNo:        seat,
+			Hands:         c.hands,
+			VPIP:          float64(c.voluntary) / float64(c.hands),
+			PFR:           float64(c.raised) / float64(c.hands),
+			ThreeBetSpots: c.spots,
+		}
+		if c.spots > 0 {
+			entry.ThreeBet = float64(c.threeBet) / float64(c.spots)
+		}
+		out = append(out, entry)
+	}
+	sort.Slice(out, func(i, j int) bool { return out[i].SeatNo < out[j].SeatNo })
+	return out
+}
@@ -0,0 +1,54 @@
+package stats
+
+import (
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestAggregateOpponentStats_CountsPreflopTendencies(t *testing.T) {
+	t.Parallel()
+
+	hands := []OpponentHand{
+		{
+			Seats: []domain.SeatNo{1, 2, 3},
+			Preflop: []SeatAction{
+				{SeatNo: 1, Kind: domain.ActionRaise},
+				{SeatNo: 2, Kind: domain.ActionRaise},
+				{SeatNo: 3, Kind: domain.ActionFold},
+				{SeatNo: 1, Kind: domain.ActionCall},
+			},
+		},
+		{
+			Seats: []domain.SeatNo{1, 2, 3},
+			Preflop: []SeatAction{
+				{SeatNo: 1, Kind: domain.ActionCall},
+				{SeatNo: 2, Kind: domain.ActionRaise},
+				{SeatNo: 3, Kind: domain.ActionCall},
+				{SeatNo: 1, Kind: domain.ActionFold},
+			},
+		},
+		{
+			Seats:   []domain.SeatNo{2, 3},
+			Preflop: []SeatAction{{SeatNo: 2, Kind: domain.ActionFold}},
+		},
+	}
+
+	got := AggregateOpponentStats(hands)
+	want := []OpponentStats{
+		{SeatNo: 1, Hands: 2, VPIP: 1, PFR: 0.5, ThreeBet: 0, ThreeBetSpots: 1},
+		{SeatNo: 2, Hands: 3, VPIP: 2.0 / 3, PFR: 2.0 / 3, ThreeBet: 1, ThreeBetSpots: 1},
+		{SeatNo: 3, Hands: 3, VPIP: 1.0 / 3, PFR: 0, ThreeBet: 0, ThreeBetSpots: 1},
+	}
+	if len(got) != len(want) {
+		t.Fatalf("expected %d seats, got %+v", len(want), got)
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Fatalf("seat %d: expected %+v, got %+v", want[i].SeatNo, want[i], got[i])
+		}
+	}
+	if empty := AggregateOpponentStats(nil); len(empty) != 0 {
+		t.Fatalf("expected no stats without hands, got %+v", empty)
+	}
+}
@@ -0,0 +1,69 @@
+package stats
+
+import "math"
+
+// DefaultEffectSizeBBPer100 is the win rate gap, in big blinds per 100
+// hands, a run is sized to tell apart from noise when none is given.
+const DefaultEffectSizeBBPer100 = 5.0
+
+// z95 is the two-sided 95% normal quantile.
+const z95 = 1.96
+
+// Significance says how far a seat's win rate over a run can be trusted.
+// StdDevBBPer100 is the spread of its results over 100 hands, and the
+// confidence interval is the normal 95% interval on BBPer100.
+// HandsToResolve is how many hands narrow that interval to within
+// EffectSizeBBPer100 either side, and AdditionalHands how many more than
+// were played that takes. The spread needs at least two hands; with fewer
+// only Hands and BBPer100 are set.
+type Significance struct {
+	Hands              int     `json:"hands"`
+	BBPer100           float64 `json:"bb_per_100"`
+	StdDevBBPer100     float64 `json:"std_dev_bb_per_100"`
+	CI95LowBBPer100    float64 `json:"ci95_low_bb_per_100"`
+	CI95HighBBPer100   float64 `json:"ci95_high_bb_per_100"`
+	EffectSizeBBPer100 float64 `json:"effect_size_bb_per_100"`
+	HandsToResolve     int     `json:"hands_to_resolve"`
+	AdditionalHands    int     `json:"additional_hands"`
+}
+
+// MeasureSignificance summarizes a seat's result in each hand, in big
+// blinds. A non-positive effectBBPer100 uses DefaultEffectSizeBBPer100.
+func MeasureSignificance(resultsBB []float64, effectBBPer100 float64) Significance {
+	if effectBBPer100 <= 0 {
+		effectBBPer100 = DefaultEffectSizeBBPer100
+	}
+	sig := Significance{Hands: len(resultsBB), EffectSizeBBPer100: effectBBPer100}
+	if len(resultsBB) == 0 {
+		return sig
+	}
+
+	var sum float64
+	for _, result := range resultsBB {
+		sum += result
+	}
+	n := float64(len(resultsBB))
+	mean := sum / n
+	sig.BBPer100 = mean * 100
+	if len(resultsBB) < 2 {
+		return sig
+	}
+
+	var squares float64
+	for _, result := range resultsBB {
+		squares += (result - mean) * (result - mean)
+	}
+	stdDev := math.Sqrt(squares / (n - 1))
+	sig.StdDevBBPer100 = stdDev * 10
+	margin := z95 * stdDev * 100 / math.Sqrt(n)
+	sig.CI95LowBBPer100 = sig.BBPer100 - margin
+	sig.CI95HighBBPer100 = sig.BBPer100 + margin
+
+	needed := math.Ceil(math.Pow(z95*stdDev*100/effectBBPer100, 2))
+	if needed > math.MaxInt32 {
+		needed = math.MaxInt32
+	}
+	sig.HandsToResolve = max(int(needed), 1)
+	sig.AdditionalHands = max(sig.HandsToResolve-len(resultsBB), 0)
+	return sig
+}
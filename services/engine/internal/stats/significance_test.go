@@ -0,0 +1,31 @@
+package stats
+
+import (
+	"math"
+	"testing"
+)
+
+func TestMeasureSignificance_ReportsSpreadIntervalAndHandsNeeded(t *testing.T) {
+	t.Parallel()
+
+	sig := MeasureSignificance([]float64{2, -2, 2, -2, 2, -2, 2, -2}, 50)
+	if sig.Hands != 8 || sig.BBPer100 != 0 {
+		t.Fatalf("expected a break-even run over 8 hands, got %+v", sig)
+	}
+	if math.Abs(sig.StdDevBBPer100-21.38) > 0.01 {
+		t.Fatalf("expected a spread of 21.38 bb/100, got %f", sig.StdDevBBPer100)
+	}
+	if math.Abs(sig.CI95HighBBPer100-148.16) > 0.01 || sig.CI95LowBBPer100 != -sig.CI95HighBBPer100 {
+		t.Fatalf("expected a 95%% interval of +-148.16 bb/100, got %f..%f", sig.CI95LowBBPer100, sig.CI95HighBBPer100)
+	}
+	if sig.HandsToResolve != 71 || sig.AdditionalHands != 63 {
+		t.Fatalf("expected 71 hands to resolve 50 bb/100, 63 more, got %d and %d", sig.HandsToResolve, sig.AdditionalHands)
+	}
+
+	if sig := MeasureSignificance([]float64{1.5}, 0); sig.BBPer100 != 150 || sig.StdDevBBPer100 != 0 || sig.EffectSizeBBPer100 != DefaultEffectSizeBBPer100 {
+		t.Fatalf("expected one hand to give only a win rate, got %+v", sig)
+	}
+	if sig := MeasureSignificance(nil, 5); sig.Hands != 0 || sig.BBPer100 != 0 {
+		t.Fatalf("expected an empty run to report nothing, got %+v", sig)
+	}
+}
@@ -0,0 +1,138 @@
+package stats
+
+import (
+	"errors"
+	"fmt"
+	"sort"
+	"sync"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+var ErrInvalidStallingPolicy = errors.New("invalid stalling policy")
+
+// StallingPolicy decides when a seat is suspected of slow-rolling or
+// stalling: it must have made at least MinDecisions trivial decisions, and
+// more than FlagRatio of them must have used over SlowFraction of the
+// deadline. PenaltyMS, when set, is taken from a flagged seat's think-time
+// budget every hand.
+type StallingPolicy struct {
+	SlowFraction float64 `json:"slow_fraction"`
+	MinDecisions int     `json:"min_decisions"`
+	FlagRatio    float64 `json:"flag_ratio"`
+	PenaltyMS    uint64  `json:"penalty_ms,omitempty"`
+}
+
+func DefaultStallingPolicy() StallingPolicy {
+	return StallingPolicy{SlowFraction: 0.9, MinDecisions: 10, FlagRatio: 0.5}
+}
+
+func (p StallingPolicy) Validate() error {
+	if p.SlowFraction <= 0 || p.SlowFraction > 1 {
+		return fmt.Errorf("%w: slow_fraction must be in (0, 1], got %v", ErrInvalidStallingPolicy, p.SlowFraction)
+	}
+	if p.MinDecisions <= 0 {
+		return fmt.Errorf("%w: min_decisions must be positive", ErrInvalidStallingPolicy)
+	}
+	if p.FlagRatio < 0 || p.FlagRatio >= 1 {
+		return fmt.Errorf("%w: flag_ratio must be in [0, 1), got %v", ErrInvalidStallingPolicy, p.FlagRatio)
+	}
+	return nil
+}
+
+// IsTrivialDecision reports whether the acting seat faces no bet, or can
+// only fold or call so that calling closes the action.
+func IsTrivialDecision(state domain.HandState) bool {
+	for _, seat := range state.Seats {
+		if seat.SeatNo != state.ActingSeat {
+			continue
+		}
+		if state.CurrentBet <= seat.CommittedInRound {
+			return true
+		}
+		_, canSize := rules.ActionSizingHints(state, seat.SeatNo)
+		return !canSize
+	}
+	return false
+}
+
+type StallingScore struct {
+	SeatNo               domain.SeatNo `json:"seat_no"`
+	TrivialDecisions     int           `json:"trivial_decisions"`
+	SlowTrivialDecisions int           `json:"slow_trivial_decisions"`
+	Score                float64       `json:"score"`
+	Flagged              bool          `json:"flagged"`
+}
+
+// StallingTracker accumulates per-seat decision timings for one table. It is
+// safe for concurrent use.
+type StallingTracker struct {
+	policy StallingPolicy
+
+	mu     sync.Mutex
+	scores map[domain.SeatNo]*StallingScore
+}
+
+func NewStallingTracker(policy StallingPolicy) (*StallingTracker, error) {
+	if err := policy.Validate(); err != nil {
+		return nil, err
+	}
+	return &StallingTracker{policy: policy, scores: make(map[domain.SeatNo]*StallingScore)}, nil
+}
+
+// Observe records one decision by the acting seat of state. It returns the
+// seat's updated score and whether this decision got the seat flagged.
+// Decisions without a deadline, or that were not trivial, are not counted.
+func (t *StallingTracker) Observe(state domain.HandState, thinkTime time.Duration, deadline time.Duration) (StallingScore, bool) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	score, ok := t.scores[state.ActingSeat]
+	if !ok {
+		score = &StallingScore{SeatNo: state.ActingSeat}
+		t.scores[state.ActingSeat] = score
+	}
+	if deadline <= 0 || !IsTrivialDecision(state) {
+		return *score, false
+	}
+
+	score.TrivialDecisions++
+	if float64(thinkTime) > t.policy.SlowFraction*float64(deadline) {
+		score.SlowTrivialDecisions++
+	}
+	score.Score = float64(score.SlowTrivialDecisions) / float64(score.TrivialDecisions)
+	wasFlagged := score.Flagged
+	score.Flagged = score.TrivialDecisions >= t.policy.MinDecisions && score.Score > t.policy.FlagRatio
+	return *score, score.Flagged && !wasFlagged
+}
+
+// SetPolicy swaps the policy; flags are re-evaluated on each seat's next
+// trivial decision.
+func (t *StallingTracker) SetPolicy(policy StallingPolicy) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	t.policy = policy
+}
+
+// PenaltyMS is the think-time penalty for seat under the policy.
+func (t *StallingTracker) PenaltyMS(seat domain.SeatNo) uint64 {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	if score, ok := t.scores[seat]; ok && score.Flagged {
+		return t.policy.PenaltyMS
+	}
+	return 0
+}
+
+// Scores returns every observed seat ordered by seat number.
+func (t *StallingTracker) Scores() []StallingScore {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	out := make([]StallingScore, 0, len(t.scores))
+	for _, score := range t.scores {
+		out = append(out, *score)
+	}
+	sort.Slice(out, func(i, j int) bool { return out[i].SeatNo < out[j].SeatNo })
+	return out
+}
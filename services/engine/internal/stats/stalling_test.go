@@ -0,0 +1,113 @@
+package stats
+
+import (
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// decisionState has seat 1 to act with a 1000 stack, facing currentBet.
+func decisionState(currentBet domain.Chips) domain.HandState {
+	return domain.HandState{
+		SmallBlind: 10,
+		BigBlind:   20,
+		ActingSeat: 1,
+		CurrentBet: currentBet,
+		MinRaiseTo: currentBet * 2,
+		Pot:        currentBet * 2,
+		Seats: []domain.SeatState{
+			{SeatNo: 1, Stack: 1000, Status: domain.SeatStatusActive},
+			{SeatNo: 2, Stack: 1000, CommittedInRound: currentBet, Status: domain.SeatStatusActive},
+		},
+	}
+}
+
+func newStallingTracker(t *testing.T, policy StallingPolicy) *StallingTracker {
+	t.Helper()
+	tracker, err := NewStallingTracker(policy)
+	if err != nil {
+		t.Fatalf("NewStallingTracker failed: %v", err)
+	}
+	return tracker
+}
+
+func TestStallingTracker_FlagsSlowTrivialDecisions(t *testing.T) {
+	t.Parallel()
+
+	policy := StallingPolicy{SlowFraction: 0.9, MinDecisions: 3, FlagRatio: 0.5, PenaltyMS: 250}
+	tracker := newStallingTracker(t, policy)
+	deadline := time.Second
+
+	for i := 0; i < 2; i++ {
+		if _, flagged := tracker.Observe(decisionState(0), 950*time.Millisecond, deadline); flagged {
+			t.Fatalf("expected no flag before %d decisions", policy.MinDecisions)
+		}
+	}
+	if tracker.PenaltyMS(1) != 0 {
+		t.Fatal("expected no penalty before the seat is flagged")
+	}
+	score, flagged := tracker.Observe(decisionState(0), 990*time.Millisecond, deadline)
+	if !flagged || !score.Flagged {
+		t.Fatalf("expected the third slow check to flag the seat, got %+v", score)
+	}
+	assertFloat(t, "score", score.Score, 1)
+	if tracker.PenaltyMS(1) != 250 {
+		t.Fatalf("expected a 250ms penalty, got %d", tracker.PenaltyMS(1))
+	}
+	if _, flagged := tracker.Observe(decisionState(0), 990*time.Millisecond, deadline); flagged {
+		t.Fatal("expected the flag to be reported only once")
+	}
+}
+
+func TestStallingTracker_IgnoresNonTrivialAndQuickDecisions(t *testing.T) {
+	t.Parallel()
+
+	tracker := newStallingTracker(t, StallingPolicy{SlowFraction: 0.9, MinDecisions: 2, FlagRatio: 0.5})
+	deadline := time.Second
+
+	// Facing a raise with chips behind is a real decision, however long it takes.
+	tracker.Observe(decisionState(100), 999*time.Millisecond, deadline)
+	tracker.Observe(decisionState(0), 100*time.Millisecond, deadline)
+	score, flagged := tracker.Observe(decisionState(0), 200*time.Millisecond, deadline)
+	if flagged || score.Flagged {
+		t.Fatalf("expected quick checks not to flag the seat, got %+v", score)
+	}
+	if score.TrivialDecisions != 2 || score.SlowTrivialDecisions != 0 {
+		t.Fatalf("expected two quick trivial decisions, got %+v", score)
+	}
+	if scores := tracker.Scores(); len(scores) != 1 || scores[0].SeatNo != 1 {
+		t.Fatalf("expected one scored seat, got %+v", scores)
+	}
+}
+
+func TestIsTrivialDecision_CallThatCannotRaise(t *testing.T) {
+	t.Parallel()
+
+	state := decisionState(100)
+	state.Seats[0].Stack = 80
+	if !IsTrivialDecision(state) {
+		t.Fatal("expected a call-or-fold spot to be trivial")
+	}
+	if IsTrivialDecision(decisionState(100)) {
+		t.Fatal("expected a spot with a legal raise to be non-trivial")
+	}
+}
+
+func TestStallingPolicy_Validate(t *testing.T) {
+	t.Parallel()
+
+	if err := DefaultStallingPolicy().Validate(); err != nil {
+		t.Fatalf("expected default policy to validate, got %v", err)
+	}
+	for _, policy := range []StallingPolicy{
+		{SlowFraction: 0, MinDecisions: 10, FlagRatio: 0.5},
+		{SlowFraction: 0.9, MinDecisions: 0, FlagRatio: 0.5},
+		{SlowFraction: 0.9, MinDecisions: 10, FlagRatio: 1},
+	} {
+		if err := policy.Validate(); !errors.Is(err, ErrInvalidStallingPolicy) {
+			t.Fatalf("expected ErrInvalidStallingPolicy for %+v, got %v", policy, err)
+		}
+	}
+}
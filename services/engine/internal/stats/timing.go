@@ -0,0 +1,67 @@
+package stats
+
+import (
+	"sort"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+// TimedDecision is how long a seat took over one decision.
+type TimedDecision struct {
+	Street    domain.Street
+	SeatNo    domain.SeatNo
+	ThinkTime time.Duration
+}
+
+// StreetTiming aggregates one seat's decisions on one street of a hand, for
+// timing-tell research and compute-budget audits.
+type StreetTiming struct {
+	Street    domain.Street `json:"street"`
+	SeatNo    domain.SeatNo `json:"seat_no"`
+	Decisions int           `json:"decisions"`
+	TotalMS   uint64        `json:"total_ms"`
+	MeanMS    uint64        `json:"mean_ms"`
+	MaxMS     uint64        `json:"max_ms"`
+}
+
+var streetOrder = map[domain.Street]int{
+	domain.StreetPreflop: 0,
+	domain.StreetFlop:    1,
+	domain.StreetTurn:    2,
+	domain.StreetRiver:   3,
+}
+
+// StreetTimingProfile groups decisions by street and seat, ordered preflop
+// to river and then by seat.
+func StreetTimingProfile(decisions []TimedDecision) []StreetTiming {
+	type key struct {
+		street domain.Street
+		seat   domain.SeatNo
+	}
+	index := make(map[key]int)
+	var out []StreetTiming
+	for _, decision := range decisions {
+		k := key{street: decision.Street, seat: decision.SeatNo}
+		i, ok := index[k]
+		if !ok {
+			i = len(out)
+			index[k] = i
+			out = append(out, StreetTiming{Street: decision.Street, SeatNo: decision.SeatNo})
+		}
+		ms := uint64(max(decision.ThinkTime, 0).Milliseconds())
+		out[i].Decisions++
+		out[i].TotalMS += ms
+		out[i].MaxMS = max(out[i].MaxMS, ms)
+	}
+	for i := range out {
+		out[i].MeanMS = out[i].TotalMS / uint64(out[i].Decisions)
+	}
+	sort.SliceStable(out, func(i, j int) bool {
+		if out[i].Street != out[j].Street {
+			return streetOrder[out[i].Street] < streetOrder[out[j].Street]
+		}
+		return out[i].SeatNo < out[j].SeatNo
+	})
+	return out
+}
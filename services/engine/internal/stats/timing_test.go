@@ -0,0 +1,37 @@
+package stats
+
+import (
+	"testing"
+	"time"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestStreetTimingProfile_GroupsByStreetAndSeat(t *testing.T) {
+	t.Parallel()
+
+	profile := StreetTimingProfile([]TimedDecision{
+		{Street: domain.StreetPreflop, SeatNo: 2, ThinkTime: 300 * time.Millisecond},
+		{Street: domain.StreetPreflop, SeatNo: 1, ThinkTime: 120 * time.Millisecond},
+		{Street: domain.StreetFlop, SeatNo: 1, ThinkTime: 1500 * time.Millisecond},
+		{Street: domain.StreetPreflop, SeatNo: 2, ThinkTime: 100 * time.Millisecond},
+		{Street: domain.StreetFlop, SeatNo: 1, ThinkTime: 500 * time.Millisecond},
+	})
+
+	want := []StreetTiming{
+		{Street: domain.StreetPreflop, SeatNo: 1, Decisions: 1, TotalMS: 120, MeanMS: 120, MaxMS: 120},
+		{Street: domain.StreetPreflop, SeatNo: 2, Decisions: 2, TotalMS: 400, MeanMS: 200, MaxMS: 300},
+		{Street: domain.StreetFlop, SeatNo: 1, Decisions: 2, TotalMS: 2000, MeanMS: 1000, MaxMS: 1500},
+	}
+	if len(profile) != len(want) {
+		t.Fatalf("expected %d rows, got %+v", len(want), profile)
+	}
+	for i := range want {
+		if profile[i] != want[i] {
+			t.Fatalf("row %d: expected %+v, got %+v", i, want[i], profile[i])
+		}
+	}
+	if got := StreetTimingProfile(nil); got != nil {
+		t.Fatalf("expected no rows without decisions, got %+v", got)
+	}
+}
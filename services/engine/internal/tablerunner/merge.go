@@ -0,0 +1,202 @@
+package tablerunner
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var (
+	ErrMergeNotNeeded  = errors.New("tables are not short enough to merge")
+	ErrMergeMismatched = errors.New("tables cannot merge")
+	ErrMergeTableFull  = errors.New("merged table has no room")
+)
+
+// MergePolicy decides whether the players at a table being broken up must
+// move.
+type MergePolicy string
+
+const (
+	// MergeForce moves every player. It is the default.
+	MergeForce MergePolicy = "force"
+	// MergeOffer moves only the players MergeConfig.Accept agrees for; the
+	// rest stay where they are.
+	MergeOffer MergePolicy = "offer"
+)
+
+// MergeConfig sets when two short-handed cash tables merge. Both must seat
+// fewer than Threshold players. Accept is asked about each move under
+// MergeOffer, and OnMoved hears about each move made; both are called with
+// the two sessions locked and must not use them.
+type MergeConfig struct {
+	Threshold int
+	Policy    MergePolicy
+	Accept    func(SeatMove) bool
+	OnMoved   func(SeatMove)
+}
+
+// SeatMove is a player moved from one table to another. Stack and the
+// blinds the seat owes travel with it.
+type SeatMove struct {
+	Player    string
+	FromTable string
+	FromSeat  domain.SeatNo
+	ToTable   string
+	ToSeat    domain.SeatNo
+	Stack     domain.Chips
+}
+
+// MergeTables breaks up the shorter of two cash tables playing the same game
+// at the same stakes, or b if they are level, and moves its players between
+// hands to the other table's open seats, lowest first. Each moved seat keeps
+// its stack, its sitting-out status and any blinds it missed, so it owes the
+// same blinds at its new table, and a seat that has asked to sit in still
+// will. A player moved does not count as leaving under the ratholing rule.
+// The moves are planned before any is made: if the players moving do not all
+// fit, nobody moves. The ledgers move the chips before the seats do, and a
+// ledger that refuses a move leaves both tables as they were.
+func MergeTables(a, b *TableSession, cfg MergeConfig) ([]SeatMove, error) {
+	if a == b || a.tableID == b.tableID {
+		return nil, fmt.Errorf("%w: %s cannot merge with itself", ErrMergeMismatched, a.tableID)
+	}
+	if !sameGame(a.config, b.config) {
+		return nil, fmt.Errorf("%w: %s and %s play different stakes or rules", ErrMergeMismatched, a.tableID, b.tableID)
+	}
+	// Lock in table order so two merges of the same pair cannot deadlock.
+	first, second := a, b
+	if second.tableID < first.tableID {
+		first, second = second, first
+	}
+	first.mu.Lock()
+	defer first.mu.Unlock()
+	second.mu.Lock()
+	defer second.mu.Unlock()
+	if a.playing || b.playing {
+		return nil, ErrHandAlreadyLive
+	}
+	if len(a.seats) >= cfg.Threshold || len(b.seats) >= cfg.Threshold {
+		return nil, fmt.Errorf("%w: %s seats %d and %s seats %d, threshold %d", ErrMergeNotNeeded, a.tableID, len(a.seats), b.tableID, len(b.seats), cfg.Threshold)
+	}
+
+	from, into := b, a
+	if len(a.seats) < len(b.seats) {
+		from, into = a, b
+	}
+	open := into.openSeats()
+	var moves []SeatMove
+	for _, seat := range from.seats {
+		move := SeatMove{
+			Player:    from.players[seat.SeatNo],
+			FromTable: from.tableID,
+			FromSeat:  seat.SeatNo,
+			ToTable:   into.tableID,
+			Stack:     seat.Stack,
+		}
+		if cfg.Policy == MergeOffer && (cfg.Accept == nil || !cfg.Accept(move)) {
+			continue
+		}
+		if len(open) == 0 {
+			return nil, fmt.Errorf("%w: %s cannot seat the players from %s", ErrMergeTableFull, into.tableID, from.tableID)
+		}
+		for taken, name := range into.players {
+			if move.Player != "" && name == move.Player {
+				return nil, fmt.Errorf("%w: %s at seat %d of %s", ErrAlreadySeated, name, taken, into.tableID)
+			}
+		}
+		move.ToSeat, open = open[0], open[1:]
+		moves = append(moves, move)
+	}
+
+	if err := moveLedgers(from, into, moves); err != nil {
+		return nil, err
+	}
+	for _, move := range moves {
+		idx := slices.IndexFunc(from.seats, func(state domain.SeatState) bool { return state.SeatNo == move.FromSeat })
+		seat := from.seats[idx]
+		from.seats = slices.Delete(from.seats, idx, idx+1)
+		from.vacated = append(from.vacated, seat)
+		post, returning := from.returning[move.FromSeat]
+		delete(from.returning, move.FromSeat)
+		delete(from.players, move.FromSeat)
+
+		seat.SeatNo = move.ToSeat
+		at := slices.IndexFunc(into.seats, func(state domain.SeatState) bool { return state.SeatNo > seat.SeatNo })
+		if at < 0 {
+			at = len(into.seats)
+		}
+		into.seats = slices.Insert(into.seats, at, seat)
+		into.vacated = slices.DeleteFunc(into.vacated, func(state domain.SeatState) bool { return state.SeatNo == seat.SeatNo })
+		if returning {
+			into.returning[seat.SeatNo] = post
+		}
+		if move.Player != "" {
+			into.players[seat.SeatNo] = move.Player
+		}
+		if cfg.OnMoved != nil {
+			cfg.OnMoved(move)
+		}
+	}
+	return moves, nil
+}
+
+// sameGame reports whether two tables play the same game at the same
+// stakes, so a player moved between them keeps playing what they sat down
+// to.
+func sameGame(a, b domain.TableConfig) bool {
+	if (a.Rake == nil) != (b.Rake == nil) || (a.Rake != nil && *a.Rake != *b.Rake) {
+		return false
+	}
+	return a.SmallBlind == b.SmallBlind && a.BigBlind == b.BigBlind && a.Ante == b.Ante &&
+		a.BettingStructure == b.BettingStructure &&
+		a.RulesVersion.Effective() == b.RulesVersion.Effective() &&
+		a.Variant.Effective() == b.Variant.Effective() &&
+		a.RunItTimes == b.RunItTimes &&
+		a.Straddle == b.Straddle && a.StraddleMultiple == b.StraddleMultiple
+}
+
+// moveLedgers cashes each moving seat out of from's ledger and buys it into
+// into's with the same stack. If either ledger refuses, the moves already
+// recorded are reversed, so both ledgers still match their tables' seats.
+func moveLedgers(from, into *TableSession, moves []SeatMove) error {
+	out, in := from.runner.config.Ledger, into.runner.config.Ledger
+	var cashedOut, boughtIn []SeatMove
+	undo := func() {
+		for _, move := range slices.Backward(boughtIn) {
+			_, _ = in.CashOut(move.ToSeat)
+		}
+		for _, move := range slices.Backward(cashedOut) {
+			_ = out.Open([]domain.SeatState{{SeatNo: move.FromSeat, Stack: move.Stack}})
+		}
+	}
+	for _, move := range moves {
+		if out != nil {
+			if _, err := out.CashOut(move.FromSeat); err != nil {
+				undo()
+				return err
+			}
+			cashedOut = append(cashedOut, move)
+		}
+		if in != nil {
+			if err := in.Open([]domain.SeatState{{SeatNo: move.ToSeat, Stack: move.Stack}}); err != nil {
+				undo()
+				return err
+			}
+			boughtIn = append(boughtIn, move)
+		}
+	}
+	return nil
+}
+
+// openSeats lists the table's empty seats in order.
+func (s *TableSession) openSeats() []domain.SeatNo {
+	var open []domain.SeatNo
+	for n := range s.config.MaxSeats {
+		seat := domain.SeatNo(n + 1)
+		if !slices.ContainsFunc(s.seats, func(state domain.SeatState) bool { return state.SeatNo == seat }) {
+			open = append(open, seat)
+		}
+	}
+	return open
+}
@@ -0,0 +1,129 @@
+package tablerunner
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"sync"
+	"time"
+)
+
+var ErrInvalidPacing = errors.New("invalid pacing")
+
+// Pacing limits how fast RunTable deals, on top of the NextHand policy.
+// HandsPerHour spaces the start of each hand at least an hour/HandsPerHour
+// after the previous one. Each Cooldown pauses dealing for the last
+// DurationMS of every EveryMS period, counted from the start of the run, so
+// {every_ms: 3600000, duration_ms: 300000} breaks for five minutes each hour.
+// A hand still live when a cooldown begins is played out.
+type Pacing struct {
+	HandsPerHour uint32     `json:"hands_per_hour,omitempty"`
+	Cooldowns    []Cooldown `json:"cooldowns,omitempty"`
+}
+
+type Cooldown struct {
+	EveryMS    uint64 `json:"every_ms"`
+	DurationMS uint64 `json:"duration_ms"`
+}
+
+func (p *Pacing) Validate() error {
+	if p == nil {
+		return nil
+	}
+	for i, cooldown := range p.Cooldowns {
+		if cooldown.DurationMS == 0 || cooldown.DurationMS >= cooldown.EveryMS {
+			return fmt.Errorf("%w: cooldown %d must be shorter than its %dms period and not empty, got %dms", ErrInvalidPacing, i, cooldown.EveryMS, cooldown.DurationMS)
+		}
+	}
+	return nil
+}
+
+// NextDeal is the earliest time at or after now that a run started at start
+// may deal its next hand, the last one having been dealt at last, which is
+// zero before the first hand.
+func (p *Pacing) NextDeal(start, last, now time.Time) time.Time {
+	if p == nil {
+		return now
+	}
+	at := now
+	if p.HandsPerHour > 0 && !last.IsZero() {
+		if earliest := last.Add(time.Hour / time.Duration(p.HandsPerHour)); earliest.After(at) {
+			at = earliest
+		}
+	}
+	// Every cooldown ends on a multiple of its period, so a time clear of all
+	// of them exists and pushing past each one in turn reaches it.
+	for moved := true; moved; {
+		moved = false
+		for _, cooldown := range p.Cooldowns {
+			every := time.Duration(cooldown.EveryMS) * time.Millisecond
+			into := at.Sub(start) % every
+			if into >= every-time.Duration(cooldown.DurationMS)*time.Millisecond {
+				at = at.Add(every - into)
+				moved = true
+			}
+		}
+	}
+	return at
+}
+
+// Gate lets an operator pause a table between hands. While it is paused
+// RunTable holds before its next deal until it is resumed or the run's
+// context ends; a hand live when the table is paused is played out. The
+// zero Gate is open.
+type Gate struct {
+	mu sync.Mutex
+	// resumed is set while the gate is paused and closed when it resumes.
+	resumed chan struct{}
+}
+
+// Pause closes the gate, reporting false if it was already paused.
+func (g *Gate) Pause() bool {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+	if g.resumed != nil {
+		return false
+	}
+	g.resumed = make(chan struct{})
+	return true
+}
+
+// Resume opens the gate, reporting false if it was not paused.
+func (g *Gate) Resume() bool {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+	if g.resumed == nil {
+		return false
+	}
+	close(g.resumed)
+	g.resumed = nil
+	return true
+}
+
+// Paused reports whether the gate is holding the table.
+func (g *Gate) Paused() bool {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+	return g.resumed != nil
+}
+
+// wait blocks while the gate is paused or until ctx ends; the caller checks
+// ctx afterwards. A nil gate never holds.
+func (g *Gate) wait(ctx context.Context) {
+	if g == nil {
+		return
+	}
+	for {
+		g.mu.Lock()
+		resumed := g.resumed
+		g.mu.Unlock()
+		if resumed == nil {
+			return
+		}
+		select {
+		case <-ctx.Done():
+			return
+		case <-resumed:
+		}
+	}
+}
@@ -4,8 +4,15 @@ import (
 	"context"
 	"errors"
 	"fmt"
+	"math"
+	"sync"
+	"time"
 
+	"github.com/imaddar/poker-arena/services/engine/internal/blinds"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/ids"
+	"github.com/imaddar/poker-arena/services/engine/internal/invariants"
+	"github.com/imaddar/poker-arena/services/engine/internal/ledger"
 	"github.com/imaddar/poker-arena/services/engine/internal/rules"
 	"github.com/imaddar/poker-arena/services/engine/internal/statemachine"
 )
@@ -14,38 +21,170 @@ const defaultMaxActionsPerHand = 512
 
 var (
 	ErrActionLimitExceeded     = errors.New("action limit exceeded")
+	ErrRaiseWarLimit           = errors.New("raise war limit reached")
 	ErrRunnerMisconfigured     = errors.New("runner misconfigured")
 	ErrContextCancelled        = errors.New("runner context cancelled")
 	ErrInvalidHandsToRun       = errors.New("hands to run must be greater than zero")
 	ErrInsufficientActiveSeats = errors.New("insufficient active seats to start hand")
+	ErrThinkTimeExhausted      = errors.New("seat think-time budget exhausted")
+	ErrActionDeadlineMissed    = errors.New("action deadline missed")
+	ErrHandAlreadyLive         = errors.New("table already has a live hand")
+)
+
+// NextHandPolicy decides when RunTable deals each hand after the first.
+type NextHandPolicy string
+
+const (
+	// NextHandImmediate deals as soon as the previous hand completes. It is
+	// the default.
+	NextHandImmediate NextHandPolicy = "immediate"
+	// NextHandAfterDelay waits RunnerConfig.InterHandDelay between hands.
+	NextHandAfterDelay NextHandPolicy = "delay"
+	// NextHandOnTrigger waits for RunnerConfig.NextHandTrigger.
+	NextHandOnTrigger NextHandPolicy = "operator"
 )
 
 type ActionProvider interface {
 	NextAction(ctx context.Context, state domain.HandState) (domain.Action, error)
 }
 
+// ActionReviser is implemented by providers for human seats. On tables with
+// an undo window the runner offers each staged decision back until the
+// window closes. ReviseAction blocks until the player replaces the action
+// (changed is true) or confirms it or ctx ends (changed is false).
+type ActionReviser interface {
+	ReviseAction(ctx context.Context, state domain.HandState, staged domain.Action) (replacement domain.Action, changed bool, err error)
+}
+
+// LatencyReporter is implemented by providers that can measure how long a
+// seat's decisions take to reach the table. On tables with a latency
+// compensation cap the runner extends each seat's deadline by its
+// OneWayLatency, up to the cap.
+type LatencyReporter interface {
+	OneWayLatency(state domain.HandState, seat domain.SeatNo) time.Duration
+}
+
 type RunHandInput struct {
 	TableID    string
 	HandNo     uint64
 	ButtonSeat domain.SeatNo
 	Seats      []domain.SeatState
 	Config     domain.TableConfig
+	// Positions, when set, places the blinds instead of ButtonSeat; RunTable
+	// sets it on tables with TableConfig.DeadButton.
+	Positions *blinds.Positions
 }
 
 type RunnerConfig struct {
 	MaxActionsPerHand int
-	OnHandComplete    func(HandSummary)
-	OnHandStart       func(input RunHandInput, initial domain.HandState)
-	OnAction          func(handNo uint64, state domain.HandState, action domain.Action, isFallback bool)
+	// MaxRaisesPerStreet caps bets and raises on one street, counting the
+	// opening bet or big blind, so bots min-raising each other cannot hang a
+	// simulation. A raise past the cap stops the hand with ErrRaiseWarLimit,
+	// or, with RaiseWarAllIn, is played as the seat's all-in. Zero means no
+	// cap.
+	MaxRaisesPerStreet int
+	RaiseWarAllIn      bool
+	// Shuffler is shared across hands; nil uses the crypto shuffler.
+	Shuffler rules.Shuffler
+	// HandIDs names each hand; nil uses random hex IDs.
+	HandIDs        ids.Generator
+	OnHandComplete func(HandSummary)
+	OnHandStart    func(input RunHandInput, initial domain.HandState)
+	OnAction       func(handNo uint64, state domain.HandState, action domain.Action, isFallback bool, timing ActionTiming)
+
+	// OnAllIn fires at most once per hand, when betting closes with two or
+	// more players all-in (or all but one all-in) before the runout.
+	OnAllIn func(handNo uint64, state domain.HandState)
+	// OnRunout fires for each street dealt after betting closes before the
+	// river, with state.Street the street just dealt. Nobody is asked to act
+	// during the runout.
+	OnRunout func(handNo uint64, state domain.HandState)
+	// ThinkTimePenaltyMS is deducted from a seat's think-time budget at the
+	// start of each hand on budgeted tables.
+	ThinkTimePenaltyMS func(seat domain.SeatNo) uint64
+	// TopUp returns chips to add to a seat between hands, after handNo and
+	// before the next deal. A busted seat that is topped up plays on.
+	TopUp func(handNo uint64, seat domain.SeatState, config domain.TableConfig) domain.Chips
+	// NextHand, InterHandDelay and NextHandTrigger pace RunTable between
+	// hands; see NextHandPolicy. Cancelling the context ends the wait.
+	NextHand        NextHandPolicy
+	InterHandDelay  time.Duration
+	NextHandTrigger <-chan struct{}
+	// Pacing rate-limits dealing and schedules cooldowns; nil deals as soon
+	// as NextHand allows.
+	Pacing *Pacing
+	// Gate, when set, holds RunTable before each deal while it is paused.
+	Gate *Gate
+	// OnTimedOut fires when a seat misses its action deadline, after the
+	// table's TimeoutAction has been played for it.
+	OnTimedOut func(TimedOut)
+	// Ledger, when set, records every chip movement. RunTable opens it with
+	// the starting seats and reconciles it at the end; a hand whose chips do
+	// not balance stops the run with an error wrapping ledger.ErrUnbalanced.
+	// Callers of RunHand open the seats themselves.
+	Ledger *ledger.Ledger
+	// CheckInvariants is a debug mode that checks the hand after every
+	// deal, action and payout. A hand that breaks one stops with an
+	// *invariants.InvariantViolation, with FinalState the state that broke
+	// it.
+	CheckInvariants bool
+}
+
+// TimedOut reports a seat whose turn timer ran out. Consecutive counts its
+// timeouts in a row, across hands; SitsOut is set when that reaches the
+// table's SitOutAfterTimeouts, and the seat sits out once the hand ends.
+type TimedOut struct {
+	HandNo      uint64
+	Seat        domain.SeatNo
+	Action      domain.Action
+	Consecutive uint8
+	SitsOut     bool
+}
+
+// ActionTiming is the server-side view of when a decision arrived and how it
+// was judged against the seat's deadline. Deadline is zero when the seat had
+// no time left to spend. LatencyCompensation is how far the deadline was
+// extended for the seat's latency; ThinkTime leaves it out.
+type ActionTiming struct {
+	ReceivedAt          time.Time
+	Policy              domain.DeadlinePolicy
+	ThinkTime           time.Duration
+	Deadline            time.Duration
+	LatencyCompensation time.Duration
 }
 
 type Runner struct {
 	provider ActionProvider
 	config   RunnerConfig
+	live     *liveTables
 }
 
+// liveTables is shared by copies of a Runner so that at most one hand per
+// table is live at once.
+type liveTables struct {
+	mu     sync.Mutex
+	tables map[string]struct{}
+}
+
+func (l *liveTables) acquire(tableID string) (release func(), err error) {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	if _, live := l.tables[tableID]; live {
+		return nil, fmt.Errorf("%w: %s", ErrHandAlreadyLive, tableID)
+	}
+	l.tables[tableID] = struct{}{}
+	return func() {
+		l.mu.Lock()
+		defer l.mu.Unlock()
+		delete(l.tables, tableID)
+	}, nil
+}
+
+// RunHandResult reports how far the hand got. Outcome is set once the hand
+// completes.
 type RunHandResult struct {
 	FinalState    domain.HandState
+	Outcome       statemachine.HandOutcome
 	ActionCount   int
 	FallbackCount int
 }
@@ -62,6 +201,7 @@ type RunTableInput struct {
 type HandSummary struct {
 	HandNo        uint64
 	FinalPhase    domain.HandPhase
+	Outcome       statemachine.HandOutcome
 	ActionCount   int
 	FallbackCount int
 	FinalState    domain.HandState
@@ -80,6 +220,7 @@ func New(provider ActionProvider, config RunnerConfig) Runner {
 	return Runner{
 		provider: provider,
 		config:   config,
+		live:     &liveTables{tables: make(map[string]struct{})},
 	}
 }
 
@@ -92,15 +233,31 @@ func (r Runner) RunTable(ctx context.Context, input RunTableInput) (RunTableResu
 	if r.provider == nil {
 		return result, ErrRunnerMisconfigured
 	}
+	if err := r.checkNextHandPolicy(); err != nil {
+		return result, err
+	}
 	if err := checkContext(ctx); err != nil {
 		return result, err
 	}
 
-	seats := prepareSeatsForNextHand(input.Seats)
+	seats := fillTimeBanks(prepareSeatsForNextHand(input.Seats), input.Config)
+	if r.config.Ledger != nil {
+		if err := r.config.Ledger.Open(seats); err != nil {
+			return result, err
+		}
+	}
 	button := input.ButtonSeat
+	var previous *blinds.Positions
 	result.HandSummaries = make([]HandSummary, 0, input.HandsToRun)
+	runStart := time.Now()
+	var lastDeal time.Time
 
 	for i := 0; i < input.HandsToRun; i++ {
+		if i > 0 {
+			r.waitForNextHand(ctx)
+		}
+		r.config.Gate.wait(ctx)
+		sleep(ctx, time.Until(r.config.Pacing.NextDeal(runStart, lastDeal, time.Now())))
 		if err := checkContext(ctx); err != nil {
 			result.FinalButton = button
 			result.FinalSeats = cloneSeats(seats)
@@ -114,6 +271,12 @@ func (r Runner) RunTable(ctx context.Context, input RunTableInput) (RunTableResu
 		}
 
 		currentButton, err := normalizeButton(button, seats)
+		var positions *blinds.Positions
+		if err == nil && input.Config.DeadButton {
+			var placed blinds.Positions
+			placed, err = placeDeadButton(previous, currentButton, seats)
+			positions, currentButton = &placed, placed.Button
+		}
 		if err != nil {
 			result.FinalButton = button
 			result.FinalSeats = cloneSeats(seats)
@@ -121,12 +284,14 @@ func (r Runner) RunTable(ctx context.Context, input RunTableInput) (RunTableResu
 		}
 
 		handNo := input.StartingHand + uint64(i)
+		lastDeal = time.Now()
 		handResult, err := r.RunHand(ctx, RunHandInput{
 			TableID:    input.TableID,
 			HandNo:     handNo,
 			ButtonSeat: currentButton,
 			Seats:      cloneSeats(seats),
 			Config:     input.Config,
+			Positions:  positions,
 		})
 		if err != nil {
 			result.FinalButton = currentButton
@@ -140,6 +305,7 @@ func (r Runner) RunTable(ctx context.Context, input RunTableInput) (RunTableResu
 		result.HandSummaries = append(result.HandSummaries, HandSummary{
 			HandNo:        handNo,
 			FinalPhase:    handResult.FinalState.Phase,
+			Outcome:       handResult.Outcome,
 			ActionCount:   handResult.ActionCount,
 			FallbackCount: handResult.FallbackCount,
 			FinalState:    cloneHandState(handResult.FinalState),
@@ -148,7 +314,16 @@ func (r Runner) RunTable(ctx context.Context, input RunTableInput) (RunTableResu
 			r.config.OnHandComplete(result.HandSummaries[len(result.HandSummaries)-1])
 		}
 
-		seats = prepareSeatsForNextHand(handResult.FinalState.Seats)
+		previous = positions
+		seats = handResult.FinalState.Seats
+		if r.config.TopUp != nil && i+1 < input.HandsToRun {
+			if seats, err = r.topUpSeats(handNo, seats, input.Config); err != nil {
+				result.FinalButton = currentButton
+				result.FinalSeats = cloneSeats(seats)
+				return result, err
+			}
+		}
+		seats = refillTimeBanks(prepareSeatsForNextHand(seats), input.Config)
 		nextButton, err := nextButtonSeat(currentButton, seats)
 		if err != nil {
 			result.FinalButton = currentButton
@@ -160,6 +335,11 @@ func (r Runner) RunTable(ctx context.Context, input RunTableInput) (RunTableResu
 
 	result.FinalButton = button
 	result.FinalSeats = cloneSeats(seats)
+	if r.config.Ledger != nil {
+		if err := r.config.Ledger.Reconcile(seats); err != nil {
+			return result, err
+		}
+	}
 	return result, nil
 }
 
@@ -169,38 +349,108 @@ func (r Runner) RunHand(ctx context.Context, input RunHandInput) (RunHandResult,
 	if r.provider == nil {
 		return result, ErrRunnerMisconfigured
 	}
+	if r.live != nil {
+		release, err := r.live.acquire(input.TableID)
+		if err != nil {
+			return result, err
+		}
+		defer release()
+	}
 
 	maxActions := r.config.MaxActionsPerHand
 	if maxActions <= 0 {
 		maxActions = defaultMaxActionsPerHand
 	}
 
+	var handID string
+	if r.config.HandIDs != nil {
+		var err error
+		if handID, err = r.config.HandIDs.NewID(); err != nil {
+			return result, fmt.Errorf("generate hand id: %w", err)
+		}
+	}
+
 	state, err := statemachine.StartNewHand(statemachine.StartNewHandInput{
 		TableID:    input.TableID,
 		HandNo:     input.HandNo,
 		Seats:      input.Seats,
 		ButtonSeat: input.ButtonSeat,
 		Config:     input.Config,
+		Shuffler:   r.config.Shuffler,
+		HandID:     handID,
+		Positions:  input.Positions,
 	})
 	if err != nil {
 		return result, err
 	}
+	var checker *invariants.Checker
+	if r.config.CheckInvariants {
+		started := invariants.NewChecker(input.Seats)
+		checker = &started
+	}
+	if err := checkInvariants(checker, state); err != nil {
+		result.FinalState = state
+		return result, err
+	}
+	if r.config.Ledger != nil {
+		if err := r.config.Ledger.PostBlinds(state); err != nil {
+			return result, err
+		}
+	}
+	if r.config.ThinkTimePenaltyMS != nil && state.ThinkTimeBudgetMS > 0 {
+		for i := range state.Seats {
+			penalty := r.config.ThinkTimePenaltyMS(state.Seats[i].SeatNo)
+			state.Seats[i].ThinkTimeRemainingMS -= min(penalty, state.Seats[i].ThinkTimeRemainingMS)
+		}
+	}
 	if r.config.OnHandStart != nil {
 		r.config.OnHandStart(input, cloneHandState(state))
 	}
 	result.FinalState = state
 
+	allInNotified := false
 	for {
+		if !allInNotified && r.config.OnAllIn != nil && state.AllInLocked() {
+			allInNotified = true
+			r.config.OnAllIn(input.HandNo, cloneHandState(state))
+		}
 		if isTerminal(state) {
-			if state.Phase == domain.HandPhaseShowdown {
-				resolved, _, err := rules.ResolvePots(state)
+			for {
+				dealt, ok, err := statemachine.RunOutStreet(state)
 				if err != nil {
 					result.FinalState = state
 					return result, err
 				}
-				state = resolved
+				if !ok {
+					break
+				}
+				state = dealt
+				if err := checkInvariants(checker, state); err != nil {
+					result.FinalState = state
+					return result, err
+				}
+				if r.config.OnRunout != nil {
+					r.config.OnRunout(input.HandNo, cloneHandState(state))
+				}
 			}
-			result.FinalState = state
+			finished, err := statemachine.FinishHand(state)
+			if err != nil {
+				result.FinalState = state
+				return result, err
+			}
+			state = finished
+			if err := checkInvariants(checker, state); err != nil {
+				result.FinalState = state
+				return result, err
+			}
+			if r.config.Ledger != nil {
+				if err := r.config.Ledger.SettleHand(state); err != nil {
+					result.FinalState = state
+					return result, err
+				}
+			}
+			result.Outcome, _ = statemachine.Outcome(state)
+			result.FinalState = sitOutTimedOutSeats(state, input.Config.SitOutAfterTimeouts)
 			return result, nil
 		}
 
@@ -209,22 +459,42 @@ func (r Runner) RunHand(ctx context.Context, input RunHandInput) (RunHandResult,
 			return result, err
 		}
 
-		action, err := r.provider.NextAction(ctx, state)
+		action, timing, err := r.nextAction(ctx, &state, input.Config.ActionTimeoutMS)
+		timedOut := errors.Is(err, ErrActionDeadlineMissed) || errors.Is(err, ErrThinkTimeExhausted)
+		consecutive := countTimeout(&state, timedOut)
 		if err != nil {
 			if err := checkContext(ctx); err != nil {
 				result.FinalState = state
 				return result, err
 			}
 
+			fallback := domain.TimeoutCheckFold
+			if timedOut {
+				fallback = input.Config.TimeoutAction
+			}
 			callbackState := cloneHandState(state)
 			var fallbackAction domain.Action
-			state, fallbackAction, err = r.applyFallback(state)
+			state, fallbackAction, err = r.applyFallback(state, fallback)
 			if err != nil {
 				result.FinalState = state
 				return result, fmt.Errorf("apply fallback after provider error: %w", err)
 			}
+			if err := checkInvariants(checker, state); err != nil {
+				result.FinalState = state
+				return result, err
+			}
 			if r.config.OnAction != nil {
-				r.config.OnAction(input.HandNo, callbackState, fallbackAction, true)
+				r.config.OnAction(input.HandNo, callbackState, fallbackAction, true, timing)
+			}
+			if timedOut && r.config.OnTimedOut != nil {
+				limit := input.Config.SitOutAfterTimeouts
+				r.config.OnTimedOut(TimedOut{
+					HandNo:      input.HandNo,
+					Seat:        callbackState.ActingSeat,
+					Action:      fallbackAction,
+					Consecutive: consecutive,
+					SitsOut:     limit > 0 && consecutive >= limit,
+				})
 			}
 
 			result.ActionCount++
@@ -242,7 +512,16 @@ func (r Runner) RunHand(ctx context.Context, input RunHandInput) (RunHandResult,
 			return result, err
 		}
 
+		admitted, ok := r.admitAction(state, action)
+		if !ok {
+			result.FinalState = state
+			return result, fmt.Errorf("%w: %d bets on the %s (max %d)", ErrRaiseWarLimit, state.StreetBets, state.Street, r.config.MaxRaisesPerStreet)
+		}
+		action = admitted
 		nextState, err := statemachine.ApplyAction(state, action)
+		if err == nil {
+			action, nextState = r.holdForUndo(ctx, state, input.Config.UndoWindowMS, action, nextState)
+		}
 		if err != nil {
 			if err := checkContext(ctx); err != nil {
 				result.FinalState = state
@@ -251,13 +530,17 @@ func (r Runner) RunHand(ctx context.Context, input RunHandInput) (RunHandResult,
 
 			callbackState := cloneHandState(state)
 			var fallbackAction domain.Action
-			state, fallbackAction, err = r.applyFallback(state)
+			state, fallbackAction, err = r.applyFallback(state, domain.TimeoutCheckFold)
 			if err != nil {
 				result.FinalState = state
 				return result, fmt.Errorf("apply fallback after illegal action: %w", err)
 			}
+			if err := checkInvariants(checker, state); err != nil {
+				result.FinalState = state
+				return result, err
+			}
 			if r.config.OnAction != nil {
-				r.config.OnAction(input.HandNo, callbackState, fallbackAction, true)
+				r.config.OnAction(input.HandNo, callbackState, fallbackAction, true, timing)
 			}
 
 			result.ActionCount++
@@ -272,8 +555,18 @@ func (r Runner) RunHand(ctx context.Context, input RunHandInput) (RunHandResult,
 
 		callbackState := cloneHandState(state)
 		state = nextState
+		if err := checkInvariants(checker, state); err != nil {
+			result.FinalState = state
+			return result, err
+		}
+		if r.config.Ledger != nil {
+			if err := r.config.Ledger.RecordBets(state); err != nil {
+				result.FinalState = state
+				return result, err
+			}
+		}
 		if r.config.OnAction != nil {
-			r.config.OnAction(input.HandNo, callbackState, action, false)
+			r.config.OnAction(input.HandNo, callbackState, action, false, timing)
 		}
 		result.ActionCount++
 		result.FinalState = state
@@ -284,11 +577,199 @@ func (r Runner) RunHand(ctx context.Context, input RunHandInput) (RunHandResult,
 	}
 }
 
-func (r Runner) applyFallback(state domain.HandState) (domain.HandState, domain.Action, error) {
-	checkAction := fallbackActionCheck()
-	nextState, err := statemachine.ApplyAction(state, checkAction)
+// checkInvariants checks state against checker; a nil checker, when
+// CheckInvariants is off, checks nothing.
+func checkInvariants(checker *invariants.Checker, state domain.HandState) error {
+	if checker == nil {
+		return nil
+	}
+	return checker.Check(state)
+}
+
+// admitAction readies a decision for the state machine. It records the
+// concrete form of shortcut actions such as all-in and applies the raise-war
+// cap: a bet or raise past it is played as the seat's all-in under
+// RaiseWarAllIn, and is refused otherwise.
+func (r Runner) admitAction(state domain.HandState, action domain.Action) (domain.Action, bool) {
+	if normalized, err := statemachine.NormalizeAction(state, action); err == nil {
+		action = normalized
+	}
+	if !r.breaksRaiseWarLimit(state, action) {
+		return action, true
+	}
+	if !r.config.RaiseWarAllIn {
+		return action, false
+	}
+	if allIn, err := statemachine.NormalizeAction(state, domain.Action{Kind: domain.ActionAllIn}); err == nil {
+		action = allIn
+	}
+	return action, true
+}
+
+// breaksRaiseWarLimit reports whether action would be one bet or raise more
+// than MaxRaisesPerStreet allows on the current street.
+func (r Runner) breaksRaiseWarLimit(state domain.HandState, action domain.Action) bool {
+	if r.config.MaxRaisesPerStreet <= 0 {
+		return false
+	}
+	if action.Kind != domain.ActionBet && action.Kind != domain.ActionRaise {
+		return false
+	}
+	return int(state.StreetBets) >= r.config.MaxRaisesPerStreet
+}
+
+// nextAction asks the provider for the acting seat's decision. Under a
+// per-hand think-time budget the request is bounded by the seat's remaining
+// budget, and the time taken is charged to the seat whatever the outcome.
+// When the table sets a latency grace window, a decision arriving up to that
+// long after the deadline is still honored; the fixed per-action timeout is
+// the deadline for tables without a budget, extended by the seat's time
+// bank, which pays for any time past the timeout. A seat that gives no
+// decision in time gets an error wrapping ErrActionDeadlineMissed or
+// ErrThinkTimeExhausted.
+func (r Runner) nextAction(ctx context.Context, state *domain.HandState, actionTimeoutMS uint64) (domain.Action, ActionTiming, error) {
+	idx := -1
+	for i, seat := range state.Seats {
+		if seat.SeatNo == state.ActingSeat {
+			idx = i
+			break
+		}
+	}
+	budgeted := state.ThinkTimeBudgetMS > 0 && idx != -1
+
+	var deadlineMS, bankMS uint64
+	switch {
+	case budgeted:
+		deadlineMS = state.Seats[idx].ThinkTimeRemainingMS
+		if deadlineMS == 0 {
+			now := time.Now()
+			err := fmt.Errorf("%w: seat %d", ErrThinkTimeExhausted, state.ActingSeat)
+			return domain.Action{}, receivedTiming(now, now, 0, err), err
+		}
+	default:
+		deadlineMS = actionTimeoutMS
+		if deadlineMS > 0 && idx != -1 {
+			bankMS = state.Seats[idx].TimeBankMS
+		}
+	}
+	if deadlineMS == 0 {
+		started := time.Now()
+		action, err := r.provider.NextAction(ctx, *state)
+		return action, receivedTiming(started, time.Now(), actionTimeoutMS, err), err
+	}
+
+	var compensationMS uint64
+	if reporter, ok := r.provider.(LatencyReporter); ok && state.LatencyCompensationCapMS > 0 {
+		latency := max(reporter.OneWayLatency(*state, state.ActingSeat), 0)
+		compensationMS = min(uint64(latency.Milliseconds()), state.LatencyCompensationCapMS)
+	}
+
+	window := deadlineMS + bankMS + state.LatencyGraceMS
+	callCtx, cancel := context.WithTimeout(ctx, time.Duration(window+compensationMS)*time.Millisecond)
+	defer cancel()
+
+	started := time.Now()
+	action, err := r.provider.NextAction(callCtx, *state)
+	receivedAt := time.Now()
+	timing := receivedTiming(started, receivedAt, deadlineMS+bankMS, err)
+	// The seat is judged, and charged, only for the time its decision was
+	// not in transit.
+	timing.LatencyCompensation = time.Duration(compensationMS) * time.Millisecond
+	timing.ThinkTime -= min(timing.ThinkTime, timing.LatencyCompensation)
+	spent := uint64(receivedAt.Sub(started).Milliseconds())
+	spent -= min(spent, compensationMS)
+	if spent < deadlineMS {
+		if budgeted {
+			state.Seats[idx].ThinkTimeRemainingMS = deadlineMS - spent
+		}
+		return action, timing, err
+	}
+	if bankMS > 0 {
+		state.Seats[idx].TimeBankMS -= min(spent-deadlineMS, bankMS)
+		if spent < deadlineMS+bankMS {
+			if err == nil {
+				timing.Policy = domain.DeadlineTimeBank
+			}
+			return action, timing, err
+		}
+	}
+
+	if budgeted {
+		state.Seats[idx].ThinkTimeRemainingMS = 0
+	}
+	if err == nil && spent < window {
+		timing.Policy = domain.DeadlineLatencyGrace
+		return action, timing, nil
+	}
+	timing.Policy = domain.DeadlineNoDecision
+	cause := ErrActionDeadlineMissed
+	if budgeted {
+		cause = ErrThinkTimeExhausted
+	}
 	if err == nil {
-		return nextState, checkAction, nil
+		err = fmt.Errorf("%w: seat %d answered after %dms", cause, state.ActingSeat, spent)
+	} else {
+		err = fmt.Errorf("%w: seat %d: %w", cause, state.ActingSeat, err)
+	}
+	return domain.Action{}, timing, err
+}
+
+// holdForUndo is the staging half of a two-phase commit. On tables with an
+// undo window a legal decision from an ActionReviser is held until the window
+// closes, and the last legal replacement is what gets committed and
+// broadcast. Replacements go through admitAction like any decision, and
+// illegal ones, or ones past the raise-war cap, are ignored. Other
+// providers' decisions, and every decision on tables without a window,
+// commit at once.
+func (r Runner) holdForUndo(ctx context.Context, state domain.HandState, windowMS uint64, staged domain.Action, next domain.HandState) (domain.Action, domain.HandState) {
+	reviser, ok := r.provider.(ActionReviser)
+	if !ok || windowMS == 0 {
+		return staged, next
+	}
+	windowCtx, cancel := context.WithTimeout(ctx, time.Duration(windowMS)*time.Millisecond)
+	defer cancel()
+
+	for windowCtx.Err() == nil {
+		replacement, changed, err := reviser.ReviseAction(windowCtx, cloneHandState(state), staged)
+		if err != nil || !changed {
+			break
+		}
+		replacement, ok := r.admitAction(state, replacement)
+		if !ok {
+			continue
+		}
+		if revised, err := statemachine.ApplyAction(state, replacement); err == nil {
+			staged, next = replacement, revised
+		}
+	}
+	return staged, next
+}
+
+func receivedTiming(started time.Time, receivedAt time.Time, deadlineMS uint64, err error) ActionTiming {
+	timing := ActionTiming{
+		ReceivedAt: receivedAt.UTC(),
+		Policy:     domain.DeadlineOnTime,
+		ThinkTime:  receivedAt.Sub(started),
+		Deadline:   time.Duration(deadlineMS) * time.Millisecond,
+	}
+	if err != nil {
+		timing.Policy = domain.DeadlineNoDecision
+	}
+	return timing
+}
+
+// applyFallback plays the default decision for a seat that gave no usable
+// one: check when that is legal, otherwise fold, or always fold under
+// TimeoutFold.
+func (r Runner) applyFallback(state domain.HandState, play domain.TimeoutAction) (domain.HandState, domain.Action, error) {
+	var err error
+	if play != domain.TimeoutFold {
+		checkAction := fallbackActionCheck()
+		var nextState domain.HandState
+		nextState, err = statemachine.ApplyAction(state, checkAction)
+		if err == nil {
+			return nextState, checkAction, nil
+		}
 	}
 
 	foldAction := fallbackActionFold()
@@ -300,6 +781,65 @@ func (r Runner) applyFallback(state domain.HandState) (domain.HandState, domain.
 	return nextState, foldAction, nil
 }
 
+// countTimeout adds a missed deadline to the acting seat's streak, or clears
+// the streak after any other outcome, and returns the new streak.
+func countTimeout(state *domain.HandState, timedOut bool) uint8 {
+	for i := range state.Seats {
+		seat := &state.Seats[i]
+		if seat.SeatNo != state.ActingSeat {
+			continue
+		}
+		switch {
+		case !timedOut:
+			seat.ConsecutiveTimeouts = 0
+		case seat.ConsecutiveTimeouts < math.MaxUint8:
+			seat.ConsecutiveTimeouts++
+		}
+		return seat.ConsecutiveTimeouts
+	}
+	return 0
+}
+
+// sitOutTimedOutSeats sits out, at the end of a hand, each active seat whose
+// timeout streak reached limit, and clears the streak so a seat that comes
+// back starts afresh. A zero limit keeps everyone in.
+func sitOutTimedOutSeats(state domain.HandState, limit uint8) domain.HandState {
+	if limit == 0 {
+		return state
+	}
+	for i := range state.Seats {
+		seat := &state.Seats[i]
+		if seat.Status == domain.SeatStatusActive && seat.ConsecutiveTimeouts >= limit {
+			seat.Status = domain.SeatStatusSittingOut
+			seat.ConsecutiveTimeouts = 0
+		}
+	}
+	return state
+}
+
+// fillTimeBanks gives every seat the table's full time bank at the start of
+// a run.
+func fillTimeBanks(seats []domain.SeatState, config domain.TableConfig) []domain.SeatState {
+	for i := range seats {
+		seats[i].TimeBankMS = config.TimeBankMS
+	}
+	return seats
+}
+
+// refillTimeBanks replenishes every seat's time bank between hands under
+// the table's TimeBankRefill.
+func refillTimeBanks(seats []domain.SeatState, config domain.TableConfig) []domain.SeatState {
+	for i := range seats {
+		switch config.TimeBankRefill {
+		case domain.TimeBankRefillPerHand:
+			seats[i].TimeBankMS = min(seats[i].TimeBankMS+config.TimeBankRefillMS, config.TimeBankMS)
+		case domain.TimeBankRefillFull:
+			seats[i].TimeBankMS = config.TimeBankMS
+		}
+	}
+	return seats
+}
+
 func fallbackActionCheck() domain.Action {
 	action, _ := domain.NewAction(domain.ActionCheck, nil)
 	return action
@@ -310,6 +850,49 @@ func fallbackActionFold() domain.Action {
 	return action
 }
 
+func (r Runner) checkNextHandPolicy() error {
+	switch r.config.NextHand {
+	case "", NextHandImmediate, NextHandAfterDelay:
+	case NextHandOnTrigger:
+		if r.config.NextHandTrigger == nil {
+			return fmt.Errorf("%w: next hand policy %q needs a trigger", ErrRunnerMisconfigured, r.config.NextHand)
+		}
+	default:
+		return fmt.Errorf("%w: unknown next hand policy %q", ErrRunnerMisconfigured, r.config.NextHand)
+	}
+	if err := r.config.Pacing.Validate(); err != nil {
+		return fmt.Errorf("%w: %v", ErrRunnerMisconfigured, err)
+	}
+	return nil
+}
+
+// waitForNextHand blocks until the next hand may be dealt or ctx ends; the
+// caller checks ctx afterwards.
+func (r Runner) waitForNextHand(ctx context.Context) {
+	switch r.config.NextHand {
+	case NextHandAfterDelay:
+		sleep(ctx, r.config.InterHandDelay)
+	case NextHandOnTrigger:
+		select {
+		case <-ctx.Done():
+		case <-r.config.NextHandTrigger:
+		}
+	}
+}
+
+// sleep waits for d or until ctx ends.
+func sleep(ctx context.Context, d time.Duration) {
+	if d <= 0 {
+		return
+	}
+	timer := time.NewTimer(d)
+	defer timer.Stop()
+	select {
+	case <-ctx.Done():
+	case <-timer.C:
+	}
+}
+
 func checkContext(ctx context.Context) error {
 	select {
 	case <-ctx.Done():
@@ -323,6 +906,27 @@ func isTerminal(state domain.HandState) bool {
 	return state.Phase == domain.HandPhaseComplete || state.Phase == domain.HandPhaseShowdown
 }
 
+func (r Runner) topUpSeats(handNo uint64, seats []domain.SeatState, config domain.TableConfig) ([]domain.SeatState, error) {
+	topped := cloneSeats(seats)
+	for i := range topped {
+		amount := r.config.TopUp(handNo, topped[i], config)
+		if amount == 0 {
+			continue
+		}
+		stack := topped[i].Stack.SaturatingAdd(amount)
+		if r.config.Ledger != nil {
+			if err := r.config.Ledger.Rebuy(handNo, topped[i].SeatNo, stack-topped[i].Stack); err != nil {
+				return seats, err
+			}
+		}
+		topped[i].Stack = stack
+		if topped[i].Status == domain.SeatStatusBusted {
+			topped[i].Status = domain.SeatStatusActive
+		}
+	}
+	return topped, nil
+}
+
 func prepareSeatsForNextHand(seats []domain.SeatState) []domain.SeatState {
 	prepared := cloneSeats(seats)
 	for i := range prepared {
@@ -355,17 +959,9 @@ func normalizeButton(current domain.SeatNo, seats []domain.SeatState) (domain.Se
 }
 
 func nextButtonSeat(current domain.SeatNo, seats []domain.SeatState) (domain.SeatNo, error) {
-	if len(seats) == 0 {
-		return 0, ErrInsufficientActiveSeats
-	}
-
-	ordered := cloneSeats(seats)
-	sortSeatsBySeatNo(ordered)
-	start := 0
 	found := false
-	for i, seat := range ordered {
+	for _, seat := range seats {
 		if seat.SeatNo == current {
-			start = i
 			found = true
 			break
 		}
@@ -373,15 +969,29 @@ func nextButtonSeat(current domain.SeatNo, seats []domain.SeatState) (domain.Sea
 	if !found {
 		return 0, ErrInsufficientActiveSeats
 	}
-
-	for i := 1; i <= len(ordered); i++ {
-		candidate := ordered[(start+i)%len(ordered)]
-		if candidate.Status != domain.SeatStatusBusted && candidate.Stack > 0 {
-			return candidate.SeatNo, nil
-		}
+	next, err := blinds.NextButton(seats, current)
+	if err != nil {
+		return 0, ErrInsufficientActiveSeats
 	}
+	return next, nil
+}
 
-	return 0, ErrInsufficientActiveSeats
+// placeDeadButton seats the first hand's blinds around button and later
+// hands' under the dead-button rule from the previous hand's positions.
+func placeDeadButton(previous *blinds.Positions, button domain.SeatNo, seats []domain.SeatState) (blinds.Positions, error) {
+	var (
+		positions blinds.Positions
+		err       error
+	)
+	if previous == nil {
+		positions, err = blinds.Assign(seats, button)
+	} else {
+		positions, err = blinds.Advance(*previous, seats)
+	}
+	if err != nil {
+		return blinds.Positions{}, fmt.Errorf("%w: %v", ErrInsufficientActiveSeats, err)
+	}
+	return positions, nil
 }
 
 func isButtonEligible(seatNo domain.SeatNo, seats []domain.SeatState) bool {
@@ -401,7 +1011,10 @@ func cloneHandState(state domain.HandState) domain.HandState {
 	cloned := state
 	cloned.Seats = cloneSeats(state.Seats)
 	cloned.Board = append([]domain.Card(nil), state.Board...)
+	cloned.Runouts = domain.CloneBoards(state.Runouts)
 	cloned.Deck = append([]domain.Card(nil), state.Deck...)
+	cloned.DealSequence = append([]domain.DealtCard(nil), state.DealSequence...)
+	cloned.ShowdownReveals = append([]domain.ShowdownReveal(nil), state.ShowdownReveals...)
 	cloned.HoleCards = make([]domain.SeatCards, 0, len(state.HoleCards))
 	for _, seatCards := range state.HoleCards {
 		cloned.HoleCards = append(cloned.HoleCards, domain.SeatCards{
@@ -423,13 +1036,3 @@ func cloneHandState(state domain.HandState) domain.HandState {
 	}
 	return cloned
 }
-
-func sortSeatsBySeatNo(seats []domain.SeatState) {
-	for i := 0; i < len(seats); i++ {
-		for j := i + 1; j < len(seats); j++ {
-			if seats[j].SeatNo < seats[i].SeatNo {
-				seats[i], seats[j] = seats[j], seats[i]
-			}
-		}
-	}
-}
@@ -4,9 +4,15 @@ import (
 	"context"
 	"errors"
 	"fmt"
+	"slices"
 	"testing"
+	"time"
 
+	"github.com/imaddar/poker-arena/services/engine/internal/blinds"
 	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/ids"
+	"github.com/imaddar/poker-arena/services/engine/internal/invariants"
+	"github.com/imaddar/poker-arena/services/engine/internal/ledger"
 	"github.com/imaddar/poker-arena/services/engine/internal/statemachine"
 )
 
@@ -71,6 +77,393 @@ func TestRunHand_UsesFallbackWhenProviderErrors(t *testing.T) {
 	}
 }
 
+func TestRunHand_ThinkTimeBudgetIsSharedAcrossDecisions(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.ThinkTimeBudgetMS = 30
+	stalling := mustSeatNo(t, cfg, 2)
+	provider := &stallingProvider{seat: stalling, calls: map[domain.SeatNo]int{}}
+	runner := New(provider, RunnerConfig{})
+
+	result, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+
+	if provider.calls[stalling] != 1 {
+		t.Fatalf("expected the stalling seat to be asked once before its budget ran out, got %d", provider.calls[stalling])
+	}
+	if result.FallbackCount < 2 {
+		t.Fatalf("expected fallbacks for every decision after the budget ran out, got %d", result.FallbackCount)
+	}
+	for _, seat := range result.FinalState.Seats {
+		if seat.SeatNo == stalling && seat.ThinkTimeRemainingMS != 0 {
+			t.Fatalf("expected stalling seat budget exhausted, got %dms", seat.ThinkTimeRemainingMS)
+		}
+		if seat.SeatNo != stalling && seat.ThinkTimeRemainingMS == 0 {
+			t.Fatal("expected prompt seat to keep part of its budget")
+		}
+	}
+}
+
+func TestRunHand_AppliesThinkTimePenaltyAtHandStart(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.ThinkTimeBudgetMS = 1000
+	penalized := mustSeatNo(t, cfg, 2)
+	budgets := map[domain.SeatNo]uint64{}
+	runner := New(deterministicProvider{}, RunnerConfig{
+		ThinkTimePenaltyMS: func(seat domain.SeatNo) uint64 {
+			if seat == penalized {
+				return 400
+			}
+			return 0
+		},
+		OnHandStart: func(_ RunHandInput, initial domain.HandState) {
+			for _, seat := range initial.Seats {
+				budgets[seat.SeatNo] = seat.ThinkTimeRemainingMS
+			}
+		},
+	})
+
+	if _, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	}); err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+
+	if budgets[penalized] != 600 || budgets[mustSeatNo(t, cfg, 1)] != 1000 {
+		t.Fatalf("expected only the penalized seat to start with 600ms, got %+v", budgets)
+	}
+}
+
+func TestRunHand_HonorsLateDecisionWithinLatencyGrace(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.ActionTimeoutMS = 10
+	cfg.LatencyGraceMS = 500
+	slow := mustSeatNo(t, cfg, 2)
+	provider := &delayedProvider{seat: slow, delay: 40 * time.Millisecond}
+	policies := map[domain.SeatNo][]domain.DeadlinePolicy{}
+	runner := New(provider, RunnerConfig{
+		OnAction: func(_ uint64, state domain.HandState, _ domain.Action, _ bool, timing ActionTiming) {
+			if timing.ReceivedAt.IsZero() {
+				t.Fatal("expected a receive timestamp on every action")
+			}
+			policies[state.ActingSeat] = append(policies[state.ActingSeat], timing.Policy)
+		},
+	})
+
+	result, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+
+	if result.FallbackCount != 0 {
+		t.Fatalf("expected late decisions inside the grace window to be honored, got %d fallbacks", result.FallbackCount)
+	}
+	if len(policies[slow]) == 0 {
+		t.Fatal("expected the slow seat to act")
+	}
+	for _, policy := range policies[slow] {
+		if policy != domain.DeadlineLatencyGrace {
+			t.Fatalf("expected slow seat decisions marked %q, got %q", domain.DeadlineLatencyGrace, policy)
+		}
+	}
+	for seat, seatPolicies := range policies {
+		if seat == slow {
+			continue
+		}
+		for _, policy := range seatPolicies {
+			if policy != domain.DeadlineOnTime {
+				t.Fatalf("expected prompt seat decisions marked %q, got %q", domain.DeadlineOnTime, policy)
+			}
+		}
+	}
+}
+
+func TestRunHand_CompensatesMeasuredLatencyUpToTheCap(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.ActionTimeoutMS = 20
+	cfg.LatencyCompensationCapMS = 100
+	distant := mustSeatNo(t, cfg, 2)
+	provider := &distantProvider{
+		delayedProvider: &delayedProvider{seat: distant, delay: 60 * time.Millisecond},
+		latency:         150 * time.Millisecond,
+	}
+	timings := map[domain.SeatNo][]ActionTiming{}
+	runner := New(provider, RunnerConfig{
+		OnAction: func(_ uint64, state domain.HandState, _ domain.Action, _ bool, timing ActionTiming) {
+			timings[state.ActingSeat] = append(timings[state.ActingSeat], timing)
+		},
+	})
+
+	result, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+
+	if result.FallbackCount != 0 || len(timings[distant]) == 0 {
+		t.Fatalf("expected the distant seat to act in time, got %d fallbacks", result.FallbackCount)
+	}
+	for _, timing := range timings[distant] {
+		if timing.Policy != domain.DeadlineOnTime || timing.LatencyCompensation != 100*time.Millisecond || timing.ThinkTime != 0 {
+			t.Fatalf("expected on-time decisions compensated by the 100ms cap, got %+v", timing)
+		}
+	}
+	for _, timing := range timings[mustSeatNo(t, cfg, 1)] {
+		if timing.LatencyCompensation != 0 {
+			t.Fatalf("expected the nearby seat uncompensated, got %+v", timing)
+		}
+	}
+}
+
+func TestRunTable_TimeoutsPlayTheDefaultActionAndSitTheSeatOut(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.ActionTimeoutMS = 20
+	cfg.TimeoutAction = domain.TimeoutFold
+	cfg.SitOutAfterTimeouts = 2
+	stalling := mustSeatNo(t, cfg, 3)
+	provider := &stallingProvider{seat: stalling, calls: map[domain.SeatNo]int{}}
+	var events []TimedOut
+	runner := New(provider, RunnerConfig{
+		OnTimedOut: func(event TimedOut) {
+			events = append(events, event)
+		},
+	})
+
+	result, err := runner.RunTable(context.Background(), RunTableInput{
+		TableID:      "table-1",
+		StartingHand: 1,
+		HandsToRun:   3,
+		ButtonSeat:   mustSeatNo(t, cfg, 1),
+		Seats:        activeSeats(t, cfg, 1, 2, 3),
+		Config:       cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunTable failed: %v", err)
+	}
+
+	if len(events) != 2 || provider.calls[stalling] != 2 {
+		t.Fatalf("expected the stalling seat to time out in two hands and then sit out, got %d calls and %+v", provider.calls[stalling], events)
+	}
+	for i, event := range events {
+		if event.Seat != stalling || event.HandNo != uint64(i+1) || event.Action.Kind != domain.ActionFold || event.Consecutive != uint8(i+1) {
+			t.Fatalf("unexpected timeout event %d: %+v", i, event)
+		}
+	}
+	if events[0].SitsOut || !events[1].SitsOut {
+		t.Fatalf("expected only the second timeout to sit the seat out, got %+v", events)
+	}
+	for _, seat := range result.FinalSeats {
+		if seat.SeatNo == stalling && (seat.Status != domain.SeatStatusSittingOut || seat.ConsecutiveTimeouts != 0) {
+			t.Fatalf("expected the stalling seat sitting out with its streak cleared, got %+v", seat)
+		}
+	}
+	if result.TotalFallbacks != 2 {
+		t.Fatalf("expected one fallback per timeout, got %d", result.TotalFallbacks)
+	}
+}
+
+func TestRunHand_SlowDecisionsSpendTheTimeBank(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.ActionTimeoutMS = 20
+	cfg.TimeBankMS = 1000
+	slow := mustSeatNo(t, cfg, 2)
+	seats := activeSeats(t, cfg, 1, 2)
+	seats[1].TimeBankMS = cfg.TimeBankMS
+	provider := &delayedProvider{seat: slow, delay: 40 * time.Millisecond}
+	var policies []domain.DeadlinePolicy
+	runner := New(provider, RunnerConfig{
+		OnAction: func(_ uint64, state domain.HandState, _ domain.Action, _ bool, timing ActionTiming) {
+			if state.ActingSeat == slow {
+				policies = append(policies, timing.Policy)
+			}
+		},
+	})
+
+	result, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      seats,
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+
+	if result.FallbackCount != 0 || len(policies) == 0 {
+		t.Fatalf("expected the slow seat's decisions to be honored, got %d fallbacks over %d decisions", result.FallbackCount, len(policies))
+	}
+	for _, policy := range policies {
+		if policy != domain.DeadlineTimeBank {
+			t.Fatalf("expected slow decisions marked %q, got %q", domain.DeadlineTimeBank, policy)
+		}
+	}
+	for _, seat := range result.FinalState.Seats {
+		spent := seat.SeatNo == slow && (seat.TimeBankMS == 0 || seat.TimeBankMS >= cfg.TimeBankMS)
+		if spent || (seat.SeatNo != slow && seat.TimeBankMS != 0) {
+			t.Fatalf("expected only the slow seat to draw on its bank, got %+v", seat)
+		}
+	}
+}
+
+func TestRefillTimeBanks_FollowsTheTablePolicy(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.TimeBankMS = 1000
+	cfg.TimeBankRefillMS = 300
+	for policy, want := range map[domain.TimeBankRefill][]uint64{
+		"":                           {0, 900},
+		domain.TimeBankRefillPerHand: {300, 1000},
+		domain.TimeBankRefillFull:    {1000, 1000},
+	} {
+		cfg.TimeBankRefill = policy
+		seats := activeSeats(t, cfg, 1, 2)
+		seats[1].TimeBankMS = 900
+		refilled := refillTimeBanks(seats, cfg)
+		if refilled[0].TimeBankMS != want[0] || refilled[1].TimeBankMS != want[1] {
+			t.Fatalf("%q: expected banks %v, got %d and %d", policy, want, refilled[0].TimeBankMS, refilled[1].TimeBankMS)
+		}
+	}
+	if seats := fillTimeBanks(activeSeats(t, cfg, 1), cfg); seats[0].TimeBankMS != 1000 {
+		t.Fatalf("expected a run to start with a full bank, got %d", seats[0].TimeBankMS)
+	}
+}
+
+func TestRunHand_CommitsRevisedActionAfterUndoWindow(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.UndoWindowMS = 20
+	provider := &undoingProvider{
+		scriptedProvider: newScriptedProvider(actionFold(t), actionFold(t)),
+		undo:             mustAction(t, domain.ActionCall, nil),
+	}
+	var committed []domain.ActionKind
+	runner := New(provider, RunnerConfig{
+		OnAction: func(_ uint64, _ domain.HandState, action domain.Action, _ bool, _ ActionTiming) {
+			committed = append(committed, action.Kind)
+		},
+	})
+
+	result, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+	// The small blind's fold is taken back, so only the call is broadcast.
+	if len(committed) != 2 || committed[0] != domain.ActionCall || committed[1] != domain.ActionFold {
+		t.Fatalf("expected call then fold to be committed, got %v", committed)
+	}
+	if provider.offers != 3 {
+		t.Fatalf("expected the replacement to be offered back once more, got %d offers", provider.offers)
+	}
+	if result.FinalState.Seats[0].Stack != cfg.StartingStack+cfg.BigBlind {
+		t.Fatalf("expected seat 1 to win the big blind, got stack %d", result.FinalState.Seats[0].Stack)
+	}
+
+	cfg.UndoWindowMS = 0
+	provider = &undoingProvider{
+		scriptedProvider: newScriptedProvider(actionFold(t)),
+		undo:             mustAction(t, domain.ActionCall, nil),
+	}
+	result, err = New(provider, RunnerConfig{}).RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+	if provider.offers != 0 || result.ActionCount != 1 {
+		t.Fatalf("expected the fold to commit at once without a window, got offers=%d actions=%d", provider.offers, result.ActionCount)
+	}
+}
+
+func TestRunHand_HoldsRevisedActionsToTheRaiseWarCap(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.UndoWindowMS = 20
+	raiseTo := domain.Chips(400)
+	for _, allIn := range []bool{false, true} {
+		provider := &undoingProvider{
+			scriptedProvider: newScriptedProvider(actionCall(t), actionFold(t)),
+			undo:             mustAction(t, domain.ActionRaise, &raiseTo),
+		}
+		var committed []domain.Action
+		runner := New(provider, RunnerConfig{
+			MaxRaisesPerStreet: 1,
+			RaiseWarAllIn:      allIn,
+			OnAction: func(_ uint64, _ domain.HandState, action domain.Action, _ bool, _ ActionTiming) {
+				committed = append(committed, action)
+			},
+		})
+
+		_, err := runner.RunHand(context.Background(), RunHandInput{
+			TableID:    "table-1",
+			HandNo:     1,
+			ButtonSeat: mustSeatNo(t, cfg, 1),
+			Seats:      activeSeats(t, cfg, 1, 2),
+			Config:     cfg,
+		})
+		if err != nil {
+			t.Fatalf("RunHand failed: %v", err)
+		}
+		if len(committed) != 2 {
+			t.Fatalf("expected two committed actions, got %+v", committed)
+		}
+		first := committed[0]
+		if !allIn && first.Kind != domain.ActionCall {
+			t.Fatalf("expected the revised raise past the cap to be ignored, got %+v", first)
+		}
+		if allIn && (first.Kind != domain.ActionRaise || first.Amount == nil || *first.Amount != cfg.StartingStack) {
+			t.Fatalf("expected the revised raise past the cap to be played as an all-in, got %+v", first)
+		}
+	}
+}
+
 func TestRunHand_UsesFallbackWhenProviderReturnsIllegalAction(t *testing.T) {
 	t.Parallel()
 
@@ -143,6 +536,43 @@ func TestRunHand_InvokesOnHandStartOnce(t *testing.T) {
 	}
 }
 
+func TestRunHand_SkipsADeadSmallBlind(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	seats := activeSeats(t, cfg, 1, 2, 3, 4)
+	seats[1].Stack = 0
+	seats[1].Status = domain.SeatStatusBusted
+	var initial domain.HandState
+	runner := New(newScriptedProvider(), RunnerConfig{
+		OnHandStart: func(_ RunHandInput, state domain.HandState) {
+			initial = state
+		},
+	})
+
+	_, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID: "table-1",
+		HandNo:  1,
+		Seats:   seats,
+		Config:  cfg,
+		Positions: &blinds.Positions{
+			Button:         mustSeatNo(t, cfg, 1),
+			SmallBlind:     mustSeatNo(t, cfg, 2),
+			BigBlind:       mustSeatNo(t, cfg, 3),
+			DeadSmallBlind: true,
+		},
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+	if initial.Pot != cfg.BigBlind || initial.ButtonSeat != mustSeatNo(t, cfg, 1) {
+		t.Fatalf("expected only the big blind posted with the button on seat 1, got pot %d button %d", initial.Pot, initial.ButtonSeat)
+	}
+	if initial.ActingSeat != mustSeatNo(t, cfg, 4) {
+		t.Fatalf("expected seat 4 to act first, got %d", initial.ActingSeat)
+	}
+}
+
 func TestRunHand_InvokesOnActionForNormalActions(t *testing.T) {
 	t.Parallel()
 
@@ -151,7 +581,7 @@ func TestRunHand_InvokesOnActionForNormalActions(t *testing.T) {
 	fallbackCalls := 0
 
 	runner := New(newScriptedProvider(actionCall(t), actionFold(t)), RunnerConfig{
-		OnAction: func(_ uint64, _ domain.HandState, _ domain.Action, isFallback bool) {
+		OnAction: func(_ uint64, _ domain.HandState, _ domain.Action, isFallback bool, _ ActionTiming) {
 			if isFallback {
 				fallbackCalls++
 				return
@@ -179,6 +609,43 @@ func TestRunHand_InvokesOnActionForNormalActions(t *testing.T) {
 	}
 }
 
+func TestRunHand_RecordsNormalizedAllInAction(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	var recorded []domain.Action
+
+	runner := New(newScriptedProvider(scriptedStep{action: mustAction(t, domain.ActionAllIn, nil)}, actionFold(t)), RunnerConfig{
+		OnAction: func(_ uint64, _ domain.HandState, action domain.Action, isFallback bool, _ ActionTiming) {
+			if isFallback {
+				t.Fatalf("unexpected fallback for %+v", action)
+			}
+			recorded = append(recorded, action)
+		},
+	})
+
+	result, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+
+	if len(recorded) != 2 {
+		t.Fatalf("expected 2 recorded actions, got %d", len(recorded))
+	}
+	if recorded[0].Kind != domain.ActionRaise || recorded[0].Amount == nil || *recorded[0].Amount != cfg.StartingStack {
+		t.Fatalf("expected all-in recorded as raise to %d, got %+v", cfg.StartingStack, recorded[0])
+	}
+	if result.FallbackCount != 0 {
+		t.Fatalf("expected no fallbacks, got %d", result.FallbackCount)
+	}
+}
+
 func TestRunHand_InvokesOnActionForFallbackActions(t *testing.T) {
 	t.Parallel()
 
@@ -188,7 +655,7 @@ func TestRunHand_InvokesOnActionForFallbackActions(t *testing.T) {
 	var fallbackAction domain.Action
 
 	runner := New(newScriptedProvider(scriptedStep{err: errors.New("provider failed")}), RunnerConfig{
-		OnAction: func(_ uint64, _ domain.HandState, action domain.Action, isFallback bool) {
+		OnAction: func(_ uint64, _ domain.HandState, action domain.Action, isFallback bool, _ ActionTiming) {
 			if isFallback {
 				fallbackCalls++
 				fallbackAction = action
@@ -246,30 +713,147 @@ func TestRunHand_StopsOnActionLimit(t *testing.T) {
 	}
 }
 
-func TestRunHand_RespectsContextCancellation(t *testing.T) {
+func TestRunHand_StopsRaiseWarAtStreetLimit(t *testing.T) {
 	t.Parallel()
 
 	cfg := domain.DefaultV0TableConfig()
-	runner := New(newScriptedProvider(actionCall(t)), RunnerConfig{})
-	ctx, cancel := context.WithCancel(context.Background())
-	cancel()
+	runner := New(&minRaisingProvider{}, RunnerConfig{MaxRaisesPerStreet: 4})
 
-	_, err := runner.RunHand(ctx, RunHandInput{
+	result, err := runner.RunHand(context.Background(), RunHandInput{
 		TableID:    "table-1",
 		HandNo:     1,
 		ButtonSeat: mustSeatNo(t, cfg, 1),
 		Seats:      activeSeats(t, cfg, 1, 2),
 		Config:     cfg,
 	})
-	if !errors.Is(err, ErrContextCancelled) {
-		t.Fatalf("expected ErrContextCancelled, got %v", err)
+	if !errors.Is(err, ErrRaiseWarLimit) {
+		t.Fatalf("expected ErrRaiseWarLimit, got %v", err)
 	}
-	if !errors.Is(err, context.Canceled) {
-		t.Fatalf("expected context.Canceled, got %v", err)
+	if result.FinalState.StreetBets != 4 || result.ActionCount != 3 {
+		t.Fatalf("expected the hand to stop after the fourth bet, got %d bets and %d actions", result.FinalState.StreetBets, result.ActionCount)
 	}
 }
 
-func TestRunHand_PropagatesStartNewHandValidationError(t *testing.T) {
+func TestRunHand_RunsOutTheBoardAfterACalledPreflopAllIn(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	allIns := 0
+	runner := New(newScriptedProvider(scriptedStep{action: mustAction(t, domain.ActionAllIn, nil)}, actionCall(t)), RunnerConfig{
+		OnAllIn: func(uint64, domain.HandState) { allIns++ },
+	})
+
+	result, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+	if allIns != 1 {
+		t.Fatalf("expected OnAllIn once, got %d", allIns)
+	}
+	if result.FinalState.Phase != domain.HandPhaseComplete || len(result.FinalState.Board) != 5 {
+		t.Fatalf("expected a five-card showdown, got phase=%s board=%v", result.FinalState.Phase, result.FinalState.Board)
+	}
+	if len(result.FinalState.ShowdownAwards) == 0 || result.FinalState.ShowdownAwards[0].Amount != 2*cfg.StartingStack {
+		t.Fatalf("expected both stacks awarded, got %+v", result.FinalState.ShowdownAwards)
+	}
+}
+
+func TestRunHand_RunsOutWithoutPromptingTheCoveringStack(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	seats := activeSeats(t, cfg, 1, 2)
+	seats[0].Stack = cfg.StartingStack / 4
+	var runout []domain.Street
+	runner := New(newScriptedProvider(scriptedStep{action: mustAction(t, domain.ActionAllIn, nil)}, actionCall(t)), RunnerConfig{
+		OnRunout: func(_ uint64, state domain.HandState) { runout = append(runout, state.Street) },
+	})
+
+	result, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      seats,
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+	if result.ActionCount != 2 || result.FallbackCount != 0 {
+		t.Fatalf("expected only the shove and the call, got %d actions and %d fallbacks", result.ActionCount, result.FallbackCount)
+	}
+	if !slices.Equal(runout, []domain.Street{domain.StreetFlop, domain.StreetTurn, domain.StreetRiver}) {
+		t.Fatalf("expected a runout event per street, got %v", runout)
+	}
+	if result.FinalState.Phase != domain.HandPhaseComplete || len(result.FinalState.Board) != 5 {
+		t.Fatalf("expected a five-card showdown, got phase=%s board=%v", result.FinalState.Phase, result.FinalState.Board)
+	}
+}
+
+func TestRunHand_ResolvesRaiseWarWithForcedAllIn(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	var actions []domain.Action
+	runner := New(&minRaisingProvider{}, RunnerConfig{
+		MaxRaisesPerStreet: 4,
+		RaiseWarAllIn:      true,
+		OnAction: func(_ uint64, _ domain.HandState, action domain.Action, _ bool, _ ActionTiming) {
+			actions = append(actions, action)
+		},
+	})
+
+	result, err := runner.RunHand(context.Background(), RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+	if len(actions) != 5 {
+		t.Fatalf("expected three raises, a forced all-in and a fold, got %+v", actions)
+	}
+	if forced := actions[3]; forced.Kind != domain.ActionRaise || forced.Amount == nil || *forced.Amount != cfg.StartingStack {
+		t.Fatalf("expected the fourth raise to be played as an all-in, got %+v", forced)
+	}
+	if actions[4].Kind != domain.ActionFold || result.FinalState.Phase != domain.HandPhaseComplete {
+		t.Fatalf("expected the all-in to take the pot, got %+v phase=%s", actions[4], result.FinalState.Phase)
+	}
+}
+
+func TestRunHand_RespectsContextCancellation(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	runner := New(newScriptedProvider(actionCall(t)), RunnerConfig{})
+	ctx, cancel := context.WithCancel(context.Background())
+	cancel()
+
+	_, err := runner.RunHand(ctx, RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	})
+	if !errors.Is(err, ErrContextCancelled) {
+		t.Fatalf("expected ErrContextCancelled, got %v", err)
+	}
+	if !errors.Is(err, context.Canceled) {
+		t.Fatalf("expected context.Canceled, got %v", err)
+	}
+}
+
+func TestRunHand_PropagatesStartNewHandValidationError(t *testing.T) {
 	t.Parallel()
 
 	cfg := domain.DefaultV0TableConfig()
@@ -380,7 +964,7 @@ func TestRunTable_CarriesForwardSeatStacks(t *testing.T) {
 	runner := New(newScriptedProvider(), RunnerConfig{})
 
 	initialSeats := activeSeats(t, cfg, 1, 2)
-	initialStacks := map[domain.SeatNo]uint32{
+	initialStacks := map[domain.SeatNo]domain.Chips{
 		initialSeats[0].SeatNo: initialSeats[0].Stack,
 		initialSeats[1].SeatNo: initialSeats[1].Stack,
 	}
@@ -501,6 +1085,387 @@ func TestRunTable_PreservesChipConservationAcrossHands(t *testing.T) {
 	}
 }
 
+func TestRunTable_AppliesTopUpsBetweenHands(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	var startTotals []domain.Chips
+	var calls []uint64
+	runner := New(&deterministicProvider{}, RunnerConfig{
+		OnHandStart: func(_ RunHandInput, initial domain.HandState) {
+			startTotals = append(startTotals, chipTotal(initial))
+		},
+		TopUp: func(handNo uint64, seat domain.SeatState, _ domain.TableConfig) domain.Chips {
+			calls = append(calls, handNo)
+			if seat.SeatNo == 1 {
+				return 500
+			}
+			return 0
+		},
+	})
+
+	result, err := runner.RunTable(context.Background(), RunTableInput{
+		TableID:      "table-1",
+		StartingHand: 1,
+		HandsToRun:   3,
+		ButtonSeat:   mustSeatNo(t, cfg, 1),
+		Seats:        activeSeats(t, cfg, 1, 2),
+		Config:       cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunTable failed: %v", err)
+	}
+
+	base := cfg.StartingStack * 2
+	if len(startTotals) != 3 || startTotals[0] != base || startTotals[1] != base+500 || startTotals[2] != base+1000 {
+		t.Fatalf("expected each later hand to start 500 chips deeper, got %v", startTotals)
+	}
+	// Two seats asked after hands 1 and 2; never after the last hand.
+	if len(calls) != 4 || calls[3] != 2 {
+		t.Fatalf("unexpected top-up calls %v", calls)
+	}
+	var final domain.Chips
+	for _, seat := range result.FinalSeats {
+		final += seat.Stack
+	}
+	if final != base+1000 {
+		t.Fatalf("expected final seats to hold %d, got %d", base+1000, final)
+	}
+}
+
+func TestRunTable_LedgerBalancesEveryChipMovement(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	book := ledger.New()
+	runner := New(&minRaisingProvider{}, RunnerConfig{
+		Ledger: book,
+		TopUp: func(_ uint64, seat domain.SeatState, config domain.TableConfig) domain.Chips {
+			return config.StartingStack.SaturatingSub(seat.Stack)
+		},
+	})
+
+	result, err := runner.RunTable(context.Background(), RunTableInput{
+		TableID:      "table-1",
+		StartingHand: 1,
+		HandsToRun:   3,
+		ButtonSeat:   mustSeatNo(t, cfg, 1),
+		Seats:        activeSeats(t, cfg, 1, 2, 3),
+		Config:       cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunTable failed: %v", err)
+	}
+
+	kinds := map[ledger.EntryKind]int{}
+	for _, entry := range book.Entries() {
+		kinds[entry.Kind]++
+	}
+	if kinds[ledger.EntryBuyIn] != 3 || kinds[ledger.EntryBlind] != 6 || kinds[ledger.EntryBet] == 0 || kinds[ledger.EntryAward] == 0 || kinds[ledger.EntryRebuy] == 0 {
+		t.Fatalf("expected buy-ins, blinds, bets, awards and rebuys, got %v", kinds)
+	}
+	if err := book.Reconcile(result.FinalSeats); err != nil {
+		t.Fatalf("expected the session to reconcile, got %v", err)
+	}
+}
+
+func TestRunTable_CheckInvariantsPassesHonestHands(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	runner := New(&minRaisingProvider{}, RunnerConfig{
+		CheckInvariants: true,
+		TopUp: func(_ uint64, seat domain.SeatState, config domain.TableConfig) domain.Chips {
+			return config.StartingStack.SaturatingSub(seat.Stack)
+		},
+	})
+
+	result, err := runner.RunTable(context.Background(), RunTableInput{
+		TableID:      "table-1",
+		StartingHand: 1,
+		HandsToRun:   5,
+		ButtonSeat:   mustSeatNo(t, cfg, 1),
+		Seats:        activeSeats(t, cfg, 1, 2, 3),
+		Config:       cfg,
+	})
+	var violation *invariants.InvariantViolation
+	if errors.As(err, &violation) {
+		t.Fatalf("expected honest hands to hold every invariant, got %v", violation)
+	}
+	if err != nil {
+		t.Fatalf("RunTable failed: %v", err)
+	}
+	if result.HandsCompleted != 5 {
+		t.Fatalf("expected 5 hands completed, got %d", result.HandsCompleted)
+	}
+}
+
+func TestRunTable_NamesHandsWithInjectedGenerator(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	runner := New(&deterministicProvider{}, RunnerConfig{HandIDs: ids.NewSequence()})
+
+	result, err := runner.RunTable(context.Background(), RunTableInput{
+		TableID:      "table-1",
+		StartingHand: 1,
+		HandsToRun:   2,
+		ButtonSeat:   mustSeatNo(t, cfg, 1),
+		Seats:        activeSeats(t, cfg, 1, 2),
+		Config:       cfg,
+	})
+	if err != nil {
+		t.Fatalf("RunTable failed: %v", err)
+	}
+	for i, summary := range result.HandSummaries {
+		if want := ids.ReservedTestUUID(uint64(i + 1)); summary.FinalState.HandID != want {
+			t.Fatalf("hand %d: expected id %s, got %s", i+1, want, summary.FinalState.HandID)
+		}
+	}
+}
+
+func TestRunTable_WaitsForOperatorTriggerBetweenHands(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	trigger := make(chan struct{})
+	started := make(chan uint64, 2)
+	runner := New(&deterministicProvider{}, RunnerConfig{
+		NextHand:        NextHandOnTrigger,
+		NextHandTrigger: trigger,
+		OnHandStart: func(input RunHandInput, _ domain.HandState) {
+			started <- input.HandNo
+		},
+	})
+	input := RunTableInput{
+		TableID:      "table-1",
+		StartingHand: 1,
+		HandsToRun:   2,
+		ButtonSeat:   mustSeatNo(t, cfg, 1),
+		Seats:        activeSeats(t, cfg, 1, 2),
+		Config:       cfg,
+	}
+
+	done := make(chan error, 1)
+	go func() {
+		_, err := runner.RunTable(context.Background(), input)
+		done <- err
+	}()
+	if handNo := <-started; handNo != 1 {
+		t.Fatalf("expected hand 1 first, got %d", handNo)
+	}
+	select {
+	case handNo := <-started:
+		t.Fatalf("hand %d was dealt before the operator triggered it", handNo)
+	case <-time.After(50 * time.Millisecond):
+	}
+	trigger <- struct{}{}
+	if handNo := <-started; handNo != 2 {
+		t.Fatalf("expected hand 2 after the trigger, got %d", handNo)
+	}
+	if err := <-done; err != nil {
+		t.Fatalf("RunTable failed: %v", err)
+	}
+}
+
+func TestRunTable_HoldsDealingWhileTheGateIsPaused(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	gate := &Gate{}
+	started := make(chan uint64, 2)
+	runner := New(&deterministicProvider{}, RunnerConfig{
+		Gate: gate,
+		OnHandStart: func(input RunHandInput, _ domain.HandState) {
+			started <- input.HandNo
+		},
+		OnHandComplete: func(summary HandSummary) {
+			if summary.HandNo == 1 && !gate.Pause() {
+				t.Errorf("expected the gate to pause")
+			}
+		},
+	})
+	input := RunTableInput{
+		TableID:      "table-1",
+		StartingHand: 1,
+		HandsToRun:   2,
+		ButtonSeat:   mustSeatNo(t, cfg, 1),
+		Seats:        activeSeats(t, cfg, 1, 2),
+		Config:       cfg,
+	}
+
+	done := make(chan error, 1)
+	go func() {
+		_, err := runner.RunTable(context.Background(), input)
+		done <- err
+	}()
+	if handNo := <-started; handNo != 1 {
+		t.Fatalf("expected hand 1 first, got %d", handNo)
+	}
+	select {
+	case handNo := <-started:
+		t.Fatalf("hand %d was dealt while the table was paused", handNo)
+	case <-time.After(50 * time.Millisecond):
+	}
+	if !gate.Paused() || gate.Pause() {
+		t.Fatalf("expected the gate to stay paused")
+	}
+	if !gate.Resume() || gate.Resume() {
+		t.Fatalf("expected the gate to resume once")
+	}
+	if handNo := <-started; handNo != 2 {
+		t.Fatalf("expected hand 2 after resuming, got %d", handNo)
+	}
+	if err := <-done; err != nil {
+		t.Fatalf("RunTable failed: %v", err)
+	}
+}
+
+func TestRunTable_DelaysNextHandAndStopsWaitingOnCancel(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	input := RunTableInput{
+		TableID:      "table-1",
+		StartingHand: 1,
+		HandsToRun:   2,
+		ButtonSeat:   mustSeatNo(t, cfg, 1),
+		Seats:        activeSeats(t, cfg, 1, 2),
+		Config:       cfg,
+	}
+	var startedAt []time.Time
+	runner := New(&deterministicProvider{}, RunnerConfig{
+		NextHand:       NextHandAfterDelay,
+		InterHandDelay: 30 * time.Millisecond,
+		OnHandStart: func(RunHandInput, domain.HandState) {
+			startedAt = append(startedAt, time.Now())
+		},
+	})
+	if _, err := runner.RunTable(context.Background(), input); err != nil {
+		t.Fatalf("RunTable failed: %v", err)
+	}
+	if len(startedAt) != 2 || startedAt[1].Sub(startedAt[0]) < 30*time.Millisecond {
+		t.Fatalf("expected the second hand at least 30ms after the first, got %v", startedAt)
+	}
+
+	ctx, cancel := context.WithCancel(context.Background())
+	waiting := New(&deterministicProvider{}, RunnerConfig{
+		NextHand:        NextHandOnTrigger,
+		NextHandTrigger: make(chan struct{}),
+		OnHandComplete:  func(HandSummary) { cancel() },
+	})
+	result, err := waiting.RunTable(ctx, input)
+	if !errors.Is(err, ErrContextCancelled) || result.HandsCompleted != 1 {
+		t.Fatalf("expected cancel to end the wait after one hand, got %d hands err=%v", result.HandsCompleted, err)
+	}
+
+	misconfigured := New(&deterministicProvider{}, RunnerConfig{NextHand: NextHandOnTrigger})
+	if _, err := misconfigured.RunTable(context.Background(), input); !errors.Is(err, ErrRunnerMisconfigured) {
+		t.Fatalf("expected ErrRunnerMisconfigured without a trigger, got %v", err)
+	}
+}
+
+func TestPacing_SpacesHandsAndSkipsCooldowns(t *testing.T) {
+	t.Parallel()
+
+	start := time.Date(2026, 1, 1, 12, 0, 0, 0, time.UTC)
+	pacing := &Pacing{
+		HandsPerHour: 60,
+		Cooldowns:    []Cooldown{{EveryMS: uint64(time.Hour / time.Millisecond), DurationMS: uint64(5 * time.Minute / time.Millisecond)}},
+	}
+	if got := pacing.NextDeal(start, time.Time{}, start); !got.Equal(start) {
+		t.Fatalf("expected the first hand dealt at once, got %v", got)
+	}
+	last := start.Add(10 * time.Minute)
+	if got := pacing.NextDeal(start, last, last.Add(time.Second)); !got.Equal(last.Add(time.Minute)) {
+		t.Fatalf("expected 60 hands an hour to wait a minute after the last deal, got %v", got)
+	}
+	last = start.Add(54*time.Minute + 30*time.Second)
+	if got := pacing.NextDeal(start, last, last); !got.Equal(start.Add(time.Hour)) {
+		t.Fatalf("expected the cooldown from minute 55 to hold the deal to the hour, got %v", got)
+	}
+	var unpaced *Pacing
+	if got := unpaced.NextDeal(start, last, last); !got.Equal(last) {
+		t.Fatalf("expected no pacing to deal at once, got %v", got)
+	}
+
+	for _, cooldown := range []Cooldown{{EveryMS: 1000}, {EveryMS: 1000, DurationMS: 1000}} {
+		if err := (&Pacing{Cooldowns: []Cooldown{cooldown}}).Validate(); !errors.Is(err, ErrInvalidPacing) {
+			t.Fatalf("expected ErrInvalidPacing for %+v, got %v", cooldown, err)
+		}
+	}
+}
+
+func TestRunTable_PacesHandsPerHour(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	input := RunTableInput{
+		TableID:      "table-1",
+		StartingHand: 1,
+		HandsToRun:   2,
+		ButtonSeat:   mustSeatNo(t, cfg, 1),
+		Seats:        activeSeats(t, cfg, 1, 2),
+		Config:       cfg,
+	}
+	// 120,000 hands an hour is one every 30ms.
+	runner := New(&deterministicProvider{}, RunnerConfig{Pacing: &Pacing{HandsPerHour: 120_000}})
+	begin := time.Now()
+	result, err := runner.RunTable(context.Background(), input)
+	if err != nil {
+		t.Fatalf("RunTable failed: %v", err)
+	}
+	if elapsed := time.Since(begin); result.HandsCompleted != 2 || elapsed < 30*time.Millisecond {
+		t.Fatalf("expected two hands dealt at least 30ms apart, got %d in %v", result.HandsCompleted, elapsed)
+	}
+
+	misconfigured := New(&deterministicProvider{}, RunnerConfig{Pacing: &Pacing{Cooldowns: []Cooldown{{EveryMS: 10}}}})
+	if _, err := misconfigured.RunTable(context.Background(), input); !errors.Is(err, ErrRunnerMisconfigured) {
+		t.Fatalf("expected ErrRunnerMisconfigured for an empty cooldown, got %v", err)
+	}
+}
+
+func TestRunHand_RejectsSecondLiveHandOnTable(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	input := RunHandInput{
+		TableID:    "table-1",
+		HandNo:     1,
+		ButtonSeat: mustSeatNo(t, cfg, 1),
+		Seats:      activeSeats(t, cfg, 1, 2),
+		Config:     cfg,
+	}
+	var nested, otherTable error
+	var runner Runner
+	runner = New(&deterministicProvider{}, RunnerConfig{
+		OnHandStart: func(started RunHandInput, _ domain.HandState) {
+			if started.HandNo != 1 {
+				return
+			}
+			second := input
+			second.HandNo = 2
+			_, nested = runner.RunHand(context.Background(), second)
+			second.TableID = "table-2"
+			_, otherTable = runner.RunHand(context.Background(), second)
+		},
+	})
+
+	if _, err := runner.RunHand(context.Background(), input); err != nil {
+		t.Fatalf("RunHand failed: %v", err)
+	}
+	if !errors.Is(nested, ErrHandAlreadyLive) {
+		t.Fatalf("expected ErrHandAlreadyLive for a second hand on table-1, got %v", nested)
+	}
+	if otherTable != nil {
+		t.Fatalf("expected another table to deal freely, got %v", otherTable)
+	}
+	input.HandNo = 3
+	if _, err := runner.RunHand(context.Background(), input); err != nil {
+		t.Fatalf("expected the table to deal again once its hand ended, got %v", err)
+	}
+}
+
 func TestRunTable_CompletesOneHundredHandsWithDeterministicProvider(t *testing.T) {
 	t.Parallel()
 
@@ -560,7 +1525,7 @@ func TestRunTable_OneHundredHandsPreserveChipConservation(t *testing.T) {
 		assertUniqueCards(t, summary.FinalState)
 	}
 
-	final := uint32(0)
+	final := domain.Chips(0)
 	for _, seat := range result.FinalSeats {
 		final += seat.Stack
 	}
@@ -569,6 +1534,352 @@ func TestRunTable_OneHundredHandsPreserveChipConservation(t *testing.T) {
 	}
 }
 
+func TestTableSession_BustsSeatsOutOverConsecutiveHands(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	session, err := NewTableSession(New(&shovingProvider{}, RunnerConfig{}), TableSessionInput{
+		TableID:     "table-1",
+		FirstHandNo: 5,
+		ButtonSeat:  mustSeatNo(t, cfg, 1),
+		Seats:       activeSeats(t, cfg, 1, 2, 3),
+		Config:      cfg,
+	})
+	if err != nil {
+		t.Fatalf("NewTableSession failed: %v", err)
+	}
+
+	var busted []domain.SeatNo
+	for i := 0; len(session.Seats()) > 1; i++ {
+		if i == 20 {
+			t.Fatalf("expected one seat left within 20 hands, got %+v", session.Seats())
+		}
+		hand, err := session.PlayHand(context.Background())
+		if err != nil {
+			t.Fatalf("PlayHand failed: %v", err)
+		}
+		if hand.HandNo != uint64(5+i) || session.NextHandNo() != hand.HandNo+1 {
+			t.Fatalf("expected hand %d, got %d with %d next", 5+i, hand.HandNo, session.NextHandNo())
+		}
+		busted = append(busted, hand.Busted...)
+		var total domain.Chips
+		for _, seat := range session.Seats() {
+			if seat.Stack == 0 || slices.Contains(busted, seat.SeatNo) {
+				t.Fatalf("expected busted seats to leave the table, got %+v", seat)
+			}
+			total += seat.Stack
+		}
+		if total != 3*cfg.StartingStack {
+			t.Fatalf("expected the stacks carried over to hold %d, got %d", 3*cfg.StartingStack, total)
+		}
+	}
+	if len(busted) != 2 {
+		t.Fatalf("expected two seats to bust, got %v", busted)
+	}
+	if _, err := session.PlayHand(context.Background()); !errors.Is(err, ErrInsufficientActiveSeats) {
+		t.Fatalf("expected ErrInsufficientActiveSeats with one seat left, got %v", err)
+	}
+}
+
+func TestTableSession_MovesTheButtonAndSeatsReturningPlayers(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	seats := activeSeats(t, cfg, 1, 2, 3)
+	seats[2].Status = domain.SeatStatusSittingOut
+	session, err := NewTableSession(New(&deterministicProvider{}, RunnerConfig{}), TableSessionInput{
+		TableID:     "table-1",
+		FirstHandNo: 1,
+		ButtonSeat:  mustSeatNo(t, cfg, 1),
+		Seats:       seats,
+		Config:      cfg,
+	})
+	if err != nil {
+		t.Fatalf("NewTableSession failed: %v", err)
+	}
+	dealtIn := func(hand SessionHand, seat domain.SeatNo) bool {
+		return slices.ContainsFunc(hand.FinalState.HoleCards, func(cards domain.SeatCards) bool { return cards.SeatNo == seat })
+	}
+
+	hand, err := session.PlayHand(context.Background())
+	if err != nil {
+		t.Fatalf("PlayHand failed: %v", err)
+	}
+	if dealtIn(hand, 3) {
+		t.Fatal("expected the seat sitting out not to be dealt in")
+	}
+	if session.Button() != mustSeatNo(t, cfg, 2) {
+		t.Fatalf("expected the button to move to seat 2, got %d", session.Button())
+	}
+
+	if err := session.SitIn(3, true); err != nil {
+		t.Fatalf("SitIn failed: %v", err)
+	}
+	hand, err = session.PlayHand(context.Background())
+	if err != nil {
+		t.Fatalf("PlayHand failed: %v", err)
+	}
+	if hand.HandNo != 2 || !dealtIn(hand, 3) {
+		t.Fatalf("expected seat 3 dealt back in to hand 2, got hand %d with %+v", hand.HandNo, hand.FinalState.HoleCards)
+	}
+	if err := session.SitIn(mustSeatNo(t, cfg, 6), true); !errors.Is(err, ErrSeatNotAtTable) {
+		t.Fatalf("expected ErrSeatNotAtTable for an empty seat, got %v", err)
+	}
+}
+
+func TestTableSession_ReturningSeatWaitsForTheBigBlind(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	seats := activeSeats(t, cfg, 1, 2, 3, 4)
+	seats[2].Status = domain.SeatStatusSittingOut
+	session, err := NewTableSession(New(&deterministicProvider{}, RunnerConfig{}), TableSessionInput{
+		TableID:     "table-1",
+		FirstHandNo: 1,
+		ButtonSeat:  mustSeatNo(t, cfg, 1),
+		Seats:       seats,
+		Config:      cfg,
+	})
+	if err != nil {
+		t.Fatalf("NewTableSession failed: %v", err)
+	}
+	seat3 := func() domain.SeatState {
+		return session.Seats()[slices.IndexFunc(session.Seats(), func(seat domain.SeatState) bool { return seat.SeatNo == 3 })]
+	}
+
+	// The big blind passes seat 3 in hand 1, with the button on seat 1, and
+	// reaches it again in hand 5.
+	if _, err := session.PlayHand(context.Background()); err != nil {
+		t.Fatalf("PlayHand failed: %v", err)
+	}
+	if !seat3().MissedBigBlind {
+		t.Fatalf("expected seat 3 to miss the big blind, got %+v", seat3())
+	}
+	if err := session.SitIn(3, false); err != nil {
+		t.Fatalf("SitIn failed: %v", err)
+	}
+	for handNo := uint64(2); handNo <= 5; handNo++ {
+		hand, err := session.PlayHand(context.Background())
+		if err != nil {
+			t.Fatalf("PlayHand failed: %v", err)
+		}
+		dealt := slices.ContainsFunc(hand.FinalState.HoleCards, func(cards domain.SeatCards) bool { return cards.SeatNo == 3 })
+		if dealt != (handNo == 5) {
+			t.Fatalf("expected seat 3 dealt in only on the big blind in hand 5, got dealt=%t in hand %d", dealt, handNo)
+		}
+	}
+	if seat := seat3(); seat.OwesBlinds() || seat.Status != domain.SeatStatusActive {
+		t.Fatalf("expected seat 3 back in owing nothing, got %+v", seat)
+	}
+}
+
+func TestTableSession_JoinsAndCashesOutPlayersBetweenHands(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	cfg.MinBuyIn, cfg.MaxBuyIn = 4_000, 8_000
+	book := ledger.New()
+	session, err := NewTableSession(New(&deterministicProvider{}, RunnerConfig{Ledger: book}), TableSessionInput{
+		TableID:     "table-1",
+		FirstHandNo: 1,
+		ButtonSeat:  mustSeatNo(t, cfg, 1),
+		Seats:       activeSeats(t, cfg, 1, 2),
+		Players:     map[domain.SeatNo]string{1: "alice", 2: "bob"},
+		Config:      cfg,
+	})
+	if err != nil {
+		t.Fatalf("NewTableSession failed: %v", err)
+	}
+
+	if stack, err := session.Leave(1); err != nil || stack != cfg.StartingStack {
+		t.Fatalf("expected seat 1 cashed out for %d, got %d, %v", cfg.StartingStack, stack, err)
+	}
+	if err := session.Join(1, "alice", cfg.MaxBuyIn); !errors.Is(err, ErrBuyInOutOfRange) {
+		t.Fatalf("expected a returning player held to their previous stack, got %v", err)
+	}
+	if err := session.Join(1, "alice", cfg.StartingStack); err != nil {
+		t.Fatalf("expected a returning player to bring back their stack past the maximum, got %v", err)
+	}
+	for _, tc := range []struct {
+		seat   domain.SeatNo
+		player string
+		buyIn  domain.Chips
+		want   error
+	}{
+		{3, "carol", cfg.MinBuyIn - 1, ErrBuyInOutOfRange},
+		{3, "carol", cfg.MaxBuyIn + 1, ErrBuyInOutOfRange},
+		{2, "carol", cfg.MinBuyIn, ErrSeatTaken},
+		{3, "bob", cfg.MinBuyIn, ErrAlreadySeated},
+	} {
+		if err := session.Join(tc.seat, tc.player, tc.buyIn); !errors.Is(err, tc.want) {
+			t.Fatalf("expected %v joining seat %d as %s with %d, got %v", tc.want, tc.seat, tc.player, tc.buyIn, err)
+		}
+	}
+	if err := session.Join(3, "carol", cfg.MinBuyIn); err != nil {
+		t.Fatalf("Join failed: %v", err)
+	}
+
+	hand, err := session.PlayHand(context.Background())
+	if err != nil {
+		t.Fatalf("PlayHand failed: %v", err)
+	}
+	if len(hand.FinalState.HoleCards) != 3 {
+		t.Fatalf("expected the joined seat dealt in, got %+v", hand.FinalState.HoleCards)
+	}
+	if err := book.Reconcile(session.Seats()); err != nil {
+		t.Fatalf("expected the session to reconcile, got %v", err)
+	}
+	if _, err := session.Leave(3); err != nil {
+		t.Fatalf("Leave failed: %v", err)
+	}
+	var held domain.Chips
+	for _, seat := range session.Seats() {
+		held += seat.Stack
+	}
+	if err := book.Reconcile(session.Seats()); err != nil || book.Balance(ledger.AccountBank) != held {
+		t.Fatalf("expected the cash-out taken out of play, got %d issued for %d held, %v", book.Balance(ledger.AccountBank), held, err)
+	}
+	if _, err := session.Leave(3); !errors.Is(err, ErrSeatNotAtTable) {
+		t.Fatalf("expected ErrSeatNotAtTable leaving twice, got %v", err)
+	}
+}
+
+func TestMergeTables_MovesShortTablePlayersWithTheirStacksAndBlinds(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	open := func(tableID string, seats []domain.SeatState, players map[domain.SeatNo]string) (*TableSession, *ledger.Ledger) {
+		book := ledger.New()
+		session, err := NewTableSession(New(&deterministicProvider{}, RunnerConfig{Ledger: book}), TableSessionInput{
+			TableID:     tableID,
+			FirstHandNo: 1,
+			ButtonSeat:  seats[0].SeatNo,
+			Seats:       seats,
+			Players:     players,
+			Config:      cfg,
+		})
+		if err != nil {
+			t.Fatalf("NewTableSession failed: %v", err)
+		}
+		return session, book
+	}
+	full, fullBook := open("table-1", activeSeats(t, cfg, 1, 2, 3), map[domain.SeatNo]string{1: "alice", 2: "bob", 3: "carol"})
+	short := activeSeats(t, cfg, 2, 5)
+	short[0].Stack = 7_500
+	short[1].Status = domain.SeatStatusSittingOut
+	short[1].MissedBigBlind = true
+	dwindling, dwindlingBook := open("table-2", short, map[domain.SeatNo]string{2: "dave", 5: "erin"})
+
+	if _, err := MergeTables(full, dwindling, MergeConfig{Threshold: 3}); !errors.Is(err, ErrMergeNotNeeded) {
+		t.Fatalf("expected ErrMergeNotNeeded with three seated at table-1, got %v", err)
+	}
+	other := cfg
+	other.SmallBlind, other.BigBlind = cfg.SmallBlind*2, cfg.BigBlind*2
+	higher, err := NewTableSession(New(&deterministicProvider{}, RunnerConfig{}), TableSessionInput{TableID: "table-3", Seats: activeSeats(t, other, 1), Config: other})
+	if err != nil {
+		t.Fatalf("NewTableSession failed: %v", err)
+	}
+	if _, err := MergeTables(dwindling, higher, MergeConfig{Threshold: 5}); !errors.Is(err, ErrMergeMismatched) {
+		t.Fatalf("expected ErrMergeMismatched across stakes, got %v", err)
+	}
+
+	// Offered the move, erin stays put; then the table is broken up.
+	var notified []SeatMove
+	moves, err := MergeTables(full, dwindling, MergeConfig{
+		Threshold: 5,
+		Policy:    MergeOffer,
+		Accept:    func(move SeatMove) bool { return move.Player == "dave" },
+		OnMoved:   func(move SeatMove) { notified = append(notified, move) },
+	})
+	if err != nil {
+		t.Fatalf("MergeTables failed: %v", err)
+	}
+	want := SeatMove{Player: "dave", FromTable: "table-2", FromSeat: 2, ToTable: "table-1", ToSeat: 4, Stack: 7_500}
+	if len(moves) != 1 || moves[0] != want || len(notified) != 1 {
+		t.Fatalf("expected only dave moved to seat 4, got %+v (notified %+v)", moves, notified)
+	}
+	moves, err = MergeTables(full, dwindling, MergeConfig{Threshold: 5, OnMoved: func(move SeatMove) { notified = append(notified, move) }})
+	if err != nil {
+		t.Fatalf("MergeTables failed: %v", err)
+	}
+	if len(moves) != 1 || moves[0].Player != "erin" || moves[0].ToSeat != 5 || len(notified) != 2 {
+		t.Fatalf("expected erin moved to seat 5, got %+v (notified %+v)", moves, notified)
+	}
+
+	if len(dwindling.Seats()) != 0 || len(full.Seats()) != 5 {
+		t.Fatalf("expected every player at table-1, got %+v and %+v", full.Seats(), dwindling.Seats())
+	}
+	erin := full.Seats()[4]
+	if erin.SeatNo != 5 || erin.Stack != cfg.StartingStack || erin.Status != domain.SeatStatusSittingOut || !erin.MissedBigBlind {
+		t.Fatalf("expected erin to keep their stack and the big blind they owe, got %+v", erin)
+	}
+	if err := full.Join(6, "dave", cfg.StartingStack); !errors.Is(err, ErrAlreadySeated) {
+		t.Fatalf("expected dave seated at table-1, got %v", err)
+	}
+	if err := fullBook.Reconcile(full.Seats()); err != nil {
+		t.Fatalf("expected table-1 to reconcile, got %v", err)
+	}
+	if err := dwindlingBook.Reconcile(dwindling.Seats()); err != nil {
+		t.Fatalf("expected table-2 to reconcile, got %v", err)
+	}
+	hand, err := full.PlayHand(context.Background())
+	if err != nil {
+		t.Fatalf("PlayHand failed: %v", err)
+	}
+	if len(hand.FinalState.HoleCards) != 4 {
+		t.Fatalf("expected the moved players dealt in but erin, got %+v", hand.FinalState.HoleCards)
+	}
+}
+
+func TestMergeTables_RefusesOtherRulesAndUndoesARefusedLedgerMove(t *testing.T) {
+	t.Parallel()
+
+	cfg := domain.DefaultV0TableConfig()
+	open := func(tableID string, config domain.TableConfig, book *ledger.Ledger, seats ...uint8) *TableSession {
+		session, err := NewTableSession(New(&deterministicProvider{}, RunnerConfig{Ledger: book}), TableSessionInput{
+			TableID: tableID,
+			Seats:   activeSeats(t, config, seats...),
+			Config:  config,
+		})
+		if err != nil {
+			t.Fatalf("NewTableSession failed: %v", err)
+		}
+		return session
+	}
+	short := open("table-1", cfg, nil, 1)
+	variants := map[string]func(*domain.TableConfig){
+		"rules version": func(c *domain.TableConfig) { c.RulesVersion = domain.RulesV1 },
+		"rake":          func(c *domain.TableConfig) { c.Rake = &domain.RakeRule{BasisPoints: 500} },
+		"run it twice":  func(c *domain.TableConfig) { c.RunItTimes = 2 },
+		"straddle":      func(c *domain.TableConfig) { c.Straddle = domain.StraddleUTG },
+	}
+	for name, change := range variants {
+		other := cfg
+		change(&other)
+		if _, err := MergeTables(short, open("table-2", other, nil, 1), MergeConfig{Threshold: 5}); !errors.Is(err, ErrMergeMismatched) {
+			t.Fatalf("%s: expected ErrMergeMismatched, got %v", name, err)
+		}
+	}
+
+	// Seat 3's account at table-3 is already open with other chips, so the
+	// move there is refused and seat 2 goes back to table-4's books.
+	intoBook, fromBook := ledger.New(), ledger.New()
+	into := open("table-3", cfg, intoBook, 1, 2)
+	from := open("table-4", cfg, fromBook, 2)
+	if err := intoBook.Open([]domain.SeatState{domain.NewSeatState(3, 1)}); err != nil {
+		t.Fatalf("Open failed: %v", err)
+	}
+	if _, err := MergeTables(into, from, MergeConfig{Threshold: 5}); !errors.Is(err, ledger.ErrUnbalanced) {
+		t.Fatalf("expected the ledger to refuse the move, got %v", err)
+	}
+	if len(into.Seats()) != 2 || len(from.Seats()) != 1 {
+		t.Fatalf("expected nobody moved, got %+v and %+v", into.Seats(), from.Seats())
+	}
+	if err := fromBook.Reconcile(from.Seats()); err != nil {
+		t.Fatalf("expected table-4's books to match its seats, got %v", err)
+	}
+}
+
 type scriptedProvider struct {
 	steps []scriptedStep
 	i     int
@@ -576,6 +1887,42 @@ type scriptedProvider struct {
 
 type deterministicProvider struct{}
 
+// shovingProvider moves all-in whenever it is asked to act.
+type shovingProvider struct{}
+
+// minRaisingProvider min-raises whenever it can cover the raise and folds
+// otherwise, so two of them raise each other until someone is all-in.
+type minRaisingProvider struct{}
+
+// stallingProvider never answers for seat and plays deterministically for
+// everyone else.
+type stallingProvider struct {
+	seat  domain.SeatNo
+	calls map[domain.SeatNo]int
+}
+
+// delayedProvider answers for seat after delay and plays deterministically
+// for everyone else.
+type delayedProvider struct {
+	seat  domain.SeatNo
+	delay time.Duration
+}
+
+// distantProvider is a delayedProvider whose slow seat is latency away
+// from the table.
+type distantProvider struct {
+	*delayedProvider
+	latency time.Duration
+}
+
+// undoingProvider replaces its first staged decision with undo and lets
+// every later one stand until the undo window closes.
+type undoingProvider struct {
+	*scriptedProvider
+	undo   domain.Action
+	offers int
+}
+
 type scriptedStep struct {
 	action domain.Action
 	err    error
@@ -594,6 +1941,22 @@ func (p *scriptedProvider) NextAction(_ context.Context, _ domain.HandState) (do
 	return step.action, step.err
 }
 
+func (p *distantProvider) OneWayLatency(_ domain.HandState, seat domain.SeatNo) time.Duration {
+	if seat == p.seat {
+		return p.latency
+	}
+	return 0
+}
+
+func (p *undoingProvider) ReviseAction(ctx context.Context, _ domain.HandState, staged domain.Action) (domain.Action, bool, error) {
+	p.offers++
+	if p.offers == 1 {
+		return p.undo, true, nil
+	}
+	<-ctx.Done()
+	return staged, false, nil
+}
+
 func (p *deterministicProvider) NextAction(_ context.Context, state domain.HandState) (domain.Action, error) {
 	var actingSeat *domain.SeatState
 	for i := range state.Seats {
@@ -621,6 +1984,47 @@ func (p *deterministicProvider) NextAction(_ context.Context, state domain.HandS
 	return action, nil
 }
 
+func (p *shovingProvider) NextAction(_ context.Context, _ domain.HandState) (domain.Action, error) {
+	return domain.NewAction(domain.ActionAllIn, nil)
+}
+
+func (p *minRaisingProvider) NextAction(_ context.Context, state domain.HandState) (domain.Action, error) {
+	for _, seat := range state.Seats {
+		if seat.SeatNo != state.ActingSeat {
+			continue
+		}
+		if seat.CommittedInRound+seat.Stack < state.MinRaiseTo {
+			return domain.NewAction(domain.ActionFold, nil)
+		}
+		raiseTo := state.MinRaiseTo
+		if state.CurrentBet == 0 {
+			return domain.NewAction(domain.ActionBet, &raiseTo)
+		}
+		return domain.NewAction(domain.ActionRaise, &raiseTo)
+	}
+	return domain.Action{}, fmt.Errorf("acting seat %d not found", state.ActingSeat)
+}
+
+func (p *stallingProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	p.calls[state.ActingSeat]++
+	if state.ActingSeat == p.seat {
+		<-ctx.Done()
+		return domain.Action{}, ctx.Err()
+	}
+	return (&deterministicProvider{}).NextAction(ctx, state)
+}
+
+func (p *delayedProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	if state.ActingSeat == p.seat {
+		select {
+		case <-time.After(p.delay):
+		case <-ctx.Done():
+			return domain.Action{}, ctx.Err()
+		}
+	}
+	return (&deterministicProvider{}).NextAction(ctx, state)
+}
+
 func actionCall(t *testing.T) scriptedStep {
 	t.Helper()
 	a := mustAction(t, domain.ActionCall, nil)
@@ -639,7 +2043,7 @@ func actionFold(t *testing.T) scriptedStep {
 	return scriptedStep{action: a}
 }
 
-func mustAction(t *testing.T, kind domain.ActionKind, amount *uint32) domain.Action {
+func mustAction(t *testing.T, kind domain.ActionKind, amount *domain.Chips) domain.Action {
 	t.Helper()
 	a, err := domain.NewAction(kind, amount)
 	if err != nil {
@@ -666,7 +2070,7 @@ func mustSeatNo(t *testing.T, cfg domain.TableConfig, n uint8) domain.SeatNo {
 	return seatNo
 }
 
-func chipTotal(state domain.HandState) uint32 {
+func chipTotal(state domain.HandState) domain.Chips {
 	total := state.Pot
 	for _, seat := range state.Seats {
 		total += seat.Stack
@@ -0,0 +1,403 @@
+package tablerunner
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"maps"
+	"slices"
+	"sync"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/blinds"
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var (
+	ErrSeatNotAtTable  = errors.New("seat is not at the table")
+	ErrSeatTaken       = errors.New("seat is taken")
+	ErrAlreadySeated   = errors.New("player is already at the table")
+	ErrBuyInOutOfRange = errors.New("buy-in out of range")
+)
+
+// TableSessionInput starts a session. Players names who sits in each of
+// Seats, so the ratholing rule can follow them if they leave; a seat left
+// out of it has no one to follow.
+type TableSessionInput struct {
+	TableID     string
+	FirstHandNo uint64
+	ButtonSeat  domain.SeatNo
+	Seats       []domain.SeatState
+	Players     map[domain.SeatNo]string
+	Config      domain.TableConfig
+}
+
+// SessionHand is a hand played by a TableSession. Busted lists the seats
+// that lost their last chip in it and have left the table.
+type SessionHand struct {
+	HandSummary
+	Busted []domain.SeatNo
+}
+
+// TableSession is a cash game played one hand at a time, for callers that
+// decide between hands whether to deal another. Stacks carry over from
+// hand to hand, the button moves on after each one, hands are numbered
+// consecutively from FirstHandNo, and a seat that busts, and is not topped
+// up, leaves the table. A seat sitting out, for example after too many
+// timeouts, is dealt back in once it asks to sit in, paying for any blinds
+// it missed. Between hands players may join an open seat and leave with
+// their stack.
+type TableSession struct {
+	runner  Runner
+	tableID string
+	config  domain.TableConfig
+
+	mu        sync.Mutex
+	seats     []domain.SeatState
+	vacated   []domain.SeatState
+	button    domain.SeatNo
+	positions *blinds.Positions
+	handNo    uint64
+	// returning holds the seats that have asked to sit in, and whether
+	// each will post the blinds it missed rather than wait for the big
+	// blind.
+	returning map[domain.SeatNo]bool
+	playing   bool
+	players   map[domain.SeatNo]string
+	// departed is the stack each player last left the table with.
+	departed map[string]domain.Chips
+}
+
+// NewTableSession seats input.Seats for a session dealt by runner. The
+// runner's Ledger, if any, is opened with them.
+func NewTableSession(runner Runner, input TableSessionInput) (*TableSession, error) {
+	if runner.provider == nil {
+		return nil, ErrRunnerMisconfigured
+	}
+	seats := fillTimeBanks(prepareSeatsForNextHand(input.Seats), input.Config)
+	if runner.config.Ledger != nil {
+		if err := runner.config.Ledger.Open(seats); err != nil {
+			return nil, err
+		}
+	}
+	session := &TableSession{
+		runner:    runner,
+		tableID:   input.TableID,
+		config:    input.Config,
+		button:    input.ButtonSeat,
+		handNo:    input.FirstHandNo,
+		returning: make(map[domain.SeatNo]bool),
+		players:   maps.Clone(input.Players),
+		departed:  make(map[string]domain.Chips),
+	}
+	if session.players == nil {
+		session.players = make(map[domain.SeatNo]string)
+	}
+	session.seats, session.vacated = removeBusted(seats)
+	if err := session.release(session.vacated); err != nil {
+		return nil, err
+	}
+	return session, nil
+}
+
+// PlayHand deals and plays the next hand. A hand that fails leaves the
+// session as it was.
+func (s *TableSession) PlayHand(ctx context.Context) (SessionHand, error) {
+	s.mu.Lock()
+	if s.playing {
+		s.mu.Unlock()
+		return SessionHand{}, ErrHandAlreadyLive
+	}
+	s.playing = true
+	defer func() {
+		s.mu.Lock()
+		defer s.mu.Unlock()
+		s.playing = false
+	}()
+	seats := cloneSeats(s.seats)
+	returning := maps.Clone(s.returning)
+	button, previous, handNo := s.button, s.positions, s.handNo
+	vacated := cloneSeats(s.vacated)
+	s.mu.Unlock()
+
+	var returned []domain.SeatNo
+	for i := range seats {
+		if post, ok := returning[seats[i].SeatNo]; ok && (post || !seats[i].OwesBlinds()) {
+			seats[i].Status = domain.SeatStatusActive
+			returned = append(returned, seats[i].SeatNo)
+		}
+	}
+	// A seat that owes blinds and will not post them waits to be dealt in
+	// on the big blind.
+	for i := range seats {
+		if post, ok := returning[seats[i].SeatNo]; !ok || post || !seats[i].OwesBlinds() {
+			continue
+		}
+		trial := cloneSeats(seats)
+		trial[i].Status = domain.SeatStatusActive
+		if s.bigBlind(button, previous, trial, vacated) == trial[i].SeatNo {
+			seats = trial
+			returned = append(returned, seats[i].SeatNo)
+		}
+	}
+
+	if countActivePlayableSeats(seats) < int(s.config.MinPlayersToStart) {
+		return SessionHand{}, ErrInsufficientActiveSeats
+	}
+	current, positions, err := s.place(button, previous, seats, vacated)
+	if err != nil {
+		return SessionHand{}, err
+	}
+
+	handResult, err := s.runner.RunHand(ctx, RunHandInput{
+		TableID:    s.tableID,
+		HandNo:     handNo,
+		ButtonSeat: current,
+		Seats:      cloneSeats(seats),
+		Config:     s.config,
+		Positions:  positions,
+	})
+	if err != nil {
+		return SessionHand{}, err
+	}
+	hand := SessionHand{HandSummary: HandSummary{
+		HandNo:        handNo,
+		FinalPhase:    handResult.FinalState.Phase,
+		Outcome:       handResult.Outcome,
+		ActionCount:   handResult.ActionCount,
+		FallbackCount: handResult.FallbackCount,
+		FinalState:    cloneHandState(handResult.FinalState),
+	}}
+	if s.runner.config.OnHandComplete != nil {
+		s.runner.config.OnHandComplete(hand.HandSummary)
+	}
+
+	seats = handResult.FinalState.Seats
+	if s.runner.config.TopUp != nil {
+		if seats, err = s.runner.topUpSeats(handNo, seats, s.config); err != nil {
+			return hand, err
+		}
+	}
+	seats = refillTimeBanks(prepareSeatsForNextHand(seats), s.config)
+	// The button moves on before busted seats leave, as it may be on one
+	// of them.
+	next, err := nextButtonSeat(current, seats)
+	if err != nil {
+		next = current
+	}
+	seats, busted := removeBusted(seats)
+	for _, seat := range busted {
+		hand.Busted = append(hand.Busted, seat.SeatNo)
+	}
+
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if err := s.release(busted); err != nil {
+		return hand, err
+	}
+	for _, seat := range returned {
+		delete(s.returning, seat)
+	}
+	s.seats = seats
+	s.vacated = append(s.vacated, busted...)
+	s.button = next
+	s.positions = positions
+	s.handNo = handNo + 1
+	return hand, nil
+}
+
+// SitIn deals seat back in from the next hand if it is sitting out. A seat
+// that missed blinds while out either posts them in its first hand back, if
+// post is set, or waits until it would be dealt in on the big blind.
+func (s *TableSession) SitIn(seat domain.SeatNo, post bool) error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	idx := slices.IndexFunc(s.seats, func(state domain.SeatState) bool { return state.SeatNo == seat })
+	if idx < 0 {
+		return fmt.Errorf("%w: seat %d", ErrSeatNotAtTable, seat)
+	}
+	if s.seats[idx].Status == domain.SeatStatusSittingOut {
+		s.returning[seat] = post
+	}
+	return nil
+}
+
+// Straddle sets whether seat posts the table's straddle whenever a hand
+// finds it in the straddle position. Like joining, it takes effect between
+// hands.
+func (s *TableSession) Straddle(seat domain.SeatNo, on bool) error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.playing {
+		return ErrHandAlreadyLive
+	}
+	idx := slices.IndexFunc(s.seats, func(state domain.SeatState) bool { return state.SeatNo == seat })
+	if idx < 0 {
+		return fmt.Errorf("%w: seat %d", ErrSeatNotAtTable, seat)
+	}
+	s.seats[idx].Straddle = on
+	return nil
+}
+
+// Join seats player at an open seat from the next hand with buyIn chips,
+// which must lie within the table's MinBuyIn and MaxBuyIn. Under the
+// ratholing rule a player who left earlier in the session must bring back
+// at least the stack they left with, even past MaxBuyIn. Players join
+// between hands.
+func (s *TableSession) Join(seat domain.SeatNo, player string, buyIn domain.Chips) error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.playing {
+		return ErrHandAlreadyLive
+	}
+	if _, err := domain.NewSeatNo(uint8(seat), s.config.MaxSeats); err != nil {
+		return err
+	}
+	if slices.ContainsFunc(s.seats, func(state domain.SeatState) bool { return state.SeatNo == seat }) {
+		return fmt.Errorf("%w: seat %d", ErrSeatTaken, seat)
+	}
+	for taken, name := range s.players {
+		if name == player {
+			return fmt.Errorf("%w: %s at seat %d", ErrAlreadySeated, player, taken)
+		}
+	}
+	minimum, maximum := s.config.MinBuyIn, s.config.MaxBuyIn
+	if left, ok := s.departed[player]; ok {
+		minimum = max(minimum, left)
+		if maximum > 0 {
+			maximum = max(maximum, left)
+		}
+	}
+	if buyIn == 0 || buyIn < minimum || (maximum > 0 && buyIn > maximum) {
+		if maximum > 0 {
+			return fmt.Errorf("%w: %d is not within %d..=%d", ErrBuyInOutOfRange, buyIn, minimum, maximum)
+		}
+		return fmt.Errorf("%w: %d is below %d", ErrBuyInOutOfRange, buyIn, max(minimum, 1))
+	}
+
+	joined := fillTimeBanks([]domain.SeatState{domain.NewSeatState(seat, buyIn)}, s.config)
+	if s.runner.config.Ledger != nil {
+		if err := s.runner.config.Ledger.Open(joined); err != nil {
+			return err
+		}
+	}
+	at := slices.IndexFunc(s.seats, func(state domain.SeatState) bool { return state.SeatNo > seat })
+	if at < 0 {
+		at = len(s.seats)
+	}
+	s.seats = slices.Insert(s.seats, at, joined[0])
+	s.vacated = slices.DeleteFunc(s.vacated, func(state domain.SeatState) bool { return state.SeatNo == seat })
+	if player != "" {
+		s.players[seat] = player
+		delete(s.departed, player)
+	}
+	return nil
+}
+
+// Leave cashes seat out between hands, returning the stack it leaves with.
+// The seat is open from the next hand.
+func (s *TableSession) Leave(seat domain.SeatNo) (domain.Chips, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.playing {
+		return 0, ErrHandAlreadyLive
+	}
+	idx := slices.IndexFunc(s.seats, func(state domain.SeatState) bool { return state.SeatNo == seat })
+	if idx < 0 {
+		return 0, fmt.Errorf("%w: seat %d", ErrSeatNotAtTable, seat)
+	}
+	left := s.seats[idx]
+	if s.runner.config.Ledger != nil {
+		if _, err := s.runner.config.Ledger.CashOut(seat); err != nil {
+			return 0, err
+		}
+	}
+	if player, ok := s.players[seat]; ok {
+		s.departed[player] = left.Stack
+		delete(s.players, seat)
+	}
+	s.seats = slices.Delete(s.seats, idx, idx+1)
+	s.vacated = append(s.vacated, left)
+	delete(s.returning, seat)
+	return left.Stack, nil
+}
+
+// Seats are the seats still at the table, as the next hand will find them.
+func (s *TableSession) Seats() []domain.SeatState {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return cloneSeats(s.seats)
+}
+
+// Button is the seat the next hand's button moves from.
+func (s *TableSession) Button() domain.SeatNo {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return s.button
+}
+
+// NextHandNo is the number the next hand will be dealt under.
+func (s *TableSession) NextHandNo() uint64 {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return s.handNo
+}
+
+// place finds the button for a hand dealt to seats and, on a dead-button
+// table, the blinds.
+func (s *TableSession) place(button domain.SeatNo, previous *blinds.Positions, seats []domain.SeatState, vacated []domain.SeatState) (domain.SeatNo, *blinds.Positions, error) {
+	current, err := normalizeButton(button, seats)
+	if err != nil || !s.config.DeadButton {
+		return current, nil, err
+	}
+	// The dead button and small blind may still fall on a seat that has
+	// left the table.
+	placed, err := placeDeadButton(previous, current, append(cloneSeats(seats), vacated...))
+	if err != nil {
+		return 0, nil, err
+	}
+	return placed.Button, &placed, nil
+}
+
+// bigBlind is the seat that would post the big blind in a hand dealt to
+// seats, or zero if the hand could not be dealt.
+func (s *TableSession) bigBlind(button domain.SeatNo, previous *blinds.Positions, seats []domain.SeatState, vacated []domain.SeatState) domain.SeatNo {
+	current, positions, err := s.place(button, previous, seats, vacated)
+	if err != nil {
+		return 0
+	}
+	if positions != nil {
+		return positions.BigBlind
+	}
+	assigned, err := blinds.Assign(seats, current)
+	if err != nil {
+		return 0
+	}
+	return assigned.BigBlind
+}
+
+// release frees the seats of players who have busted, closing their ledger
+// accounts. With nothing left they owe nothing under the ratholing rule.
+func (s *TableSession) release(busted []domain.SeatState) error {
+	for _, seat := range busted {
+		if s.runner.config.Ledger != nil {
+			if _, err := s.runner.config.Ledger.CashOut(seat.SeatNo); err != nil {
+				return err
+			}
+		}
+		delete(s.players, seat.SeatNo)
+	}
+	return nil
+}
+
+// removeBusted splits seats into those still at the table and those that
+// have busted.
+func removeBusted(seats []domain.SeatState) (kept []domain.SeatState, busted []domain.SeatState) {
+	for _, seat := range seats {
+		if seat.Status == domain.SeatStatusBusted {
+			busted = append(busted, seat)
+			continue
+		}
+		kept = append(kept, seat)
+	}
+	return kept, busted
+}
@@ -0,0 +1,283 @@
+package tournament
+
+import (
+	"crypto/hmac"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"math"
+	"slices"
+	"strings"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+var (
+	ErrInvalidIntervention = errors.New("invalid director intervention")
+	ErrAuditKeyMissing     = errors.New("director interventions need an audit key")
+	ErrAuditTampered       = errors.New("audit trail does not verify")
+	ErrSitAndGoNotRunning  = errors.New("sit-and-go is not running")
+)
+
+// InterventionKind names a manual action by the tournament director.
+type InterventionKind string
+
+const (
+	InterventionStackAdjustment    InterventionKind = "stack_adjustment"
+	InterventionMissedRoundPenalty InterventionKind = "missed_round_penalty"
+	InterventionDisqualification   InterventionKind = "disqualification"
+)
+
+// AutomaticDirector signs the interventions the arena queues itself, such as
+// disqualifying a bot stuck in a crash loop.
+const AutomaticDirector = "arena"
+
+// ForfeitPolicy is what a disqualification does with the player's stack.
+type ForfeitPolicy string
+
+const (
+	// ForfeitRemove takes the stack out of play before the next hand. It is
+	// the default.
+	ForfeitRemove ForfeitPolicy = "remove"
+	// ForfeitBlindOff leaves the stack at the table, posting blinds and
+	// folding every hand until it is gone, so the seats and blinds everyone
+	// else faces do not change mid-level.
+	ForfeitBlindOff ForfeitPolicy = "blind_off"
+)
+
+func (p ForfeitPolicy) validate() error {
+	switch p {
+	case "", ForfeitRemove, ForfeitBlindOff:
+		return nil
+	default:
+		return fmt.Errorf("%w: unknown forfeit policy %q", ErrInvalidIntervention, p)
+	}
+}
+
+// Intervention is a director's ruling against one player. Chips is the
+// signed change a stack adjustment makes; Rounds is how many orbits a
+// missed-round penalty charges, each costing the current small and big
+// blind. Forfeit only applies to a disqualification.
+type Intervention struct {
+	Kind     InterventionKind `json:"kind"`
+	PlayerID string           `json:"player_id"`
+	Director string           `json:"director"`
+	Reason   string           `json:"reason"`
+	Chips    int64            `json:"chips,omitempty"`
+	Rounds   int              `json:"rounds,omitempty"`
+	Forfeit  ForfeitPolicy    `json:"forfeit,omitempty"`
+}
+
+func (iv Intervention) Validate() error {
+	if iv.PlayerID == "" {
+		return ErrMissingPlayerID
+	}
+	if strings.TrimSpace(iv.Director) == "" || strings.TrimSpace(iv.Reason) == "" {
+		return fmt.Errorf("%w: director and reason are required", ErrInvalidIntervention)
+	}
+	if iv.Forfeit != "" && iv.Kind != InterventionDisqualification {
+		return fmt.Errorf("%w: only a disqualification takes a forfeit policy", ErrInvalidIntervention)
+	}
+	switch iv.Kind {
+	case InterventionStackAdjustment:
+		if iv.Chips == 0 || iv.Chips < -math.MaxUint32 || iv.Chips > math.MaxUint32 || iv.Rounds != 0 {
+			return fmt.Errorf("%w: a stack adjustment needs a non-zero chips change and no rounds", ErrInvalidIntervention)
+		}
+	case InterventionMissedRoundPenalty:
+		if iv.Rounds <= 0 || iv.Chips != 0 {
+			return fmt.Errorf("%w: a missed-round penalty needs a positive rounds count and no chips", ErrInvalidIntervention)
+		}
+	case InterventionDisqualification:
+		if iv.Chips != 0 || iv.Rounds != 0 {
+			return fmt.Errorf("%w: a disqualification takes no chips or rounds", ErrInvalidIntervention)
+		}
+		return iv.Forfeit.validate()
+	default:
+		return fmt.Errorf("%w: unknown kind %q", ErrInvalidIntervention, iv.Kind)
+	}
+	return nil
+}
+
+// AuditEvent records an intervention as it took effect, after HandNo and
+// before the next hand. Delta is the chip change actually made, which a
+// penalty caps at the player's stack. Events are chained: each Signature is
+// an HMAC-SHA256 over the event including the previous event's signature, so
+// editing, dropping or reordering an event breaks every later signature.
+type AuditEvent struct {
+	Seq           int              `json:"seq"`
+	TournamentID  string           `json:"tournament_id"`
+	HandNo        uint64           `json:"hand_no"`
+	Kind          InterventionKind `json:"kind"`
+	PlayerID      string           `json:"player_id"`
+	Director      string           `json:"director"`
+	Reason        string           `json:"reason"`
+	Forfeit       ForfeitPolicy    `json:"forfeit,omitempty"`
+	Delta         int64            `json:"delta"`
+	StackAfter    domain.Chips     `json:"stack_after"`
+	PrevSignature string           `json:"prev_signature,omitempty"`
+	Signature     string           `json:"signature"`
+}
+
+// VerifyAuditTrail checks every signature in events and the chain between
+// them.
+func VerifyAuditTrail(key []byte, events []AuditEvent) error {
+	prev := ""
+	for i, event := range events {
+		want := signAuditEvent(key, event)
+		if event.Seq != i+1 || event.PrevSignature != prev || !hmac.Equal([]byte(event.Signature), []byte(want)) {
+			return fmt.Errorf("%w: event %d", ErrAuditTampered, i+1)
+		}
+		prev = event.Signature
+	}
+	return nil
+}
+
+func signAuditEvent(key []byte, event AuditEvent) string {
+	event.Signature = ""
+	// The event holds only strings and numbers, so encoding cannot fail.
+	payload, _ := json.Marshal(event)
+	mac := hmac.New(sha256.New, key)
+	mac.Write(payload)
+	return hex.EncodeToString(mac.Sum(nil))
+}
+
+// Intervene queues a director's ruling. It takes effect between hands, so a
+// hand in progress always finishes on the chips it started with.
+func (s *SitAndGo) Intervene(iv Intervention) error {
+	if err := iv.Validate(); err != nil {
+		return err
+	}
+	if len(s.AuditKey) == 0 {
+		return ErrAuditKeyMissing
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if !s.started || s.finished {
+		return ErrSitAndGoNotRunning
+	}
+	if _, ok := s.seatLocked(iv.PlayerID); !ok {
+		return fmt.Errorf("%w: %s", ErrPlayerNotFound, iv.PlayerID)
+	}
+	s.pending = append(s.pending, iv)
+	return nil
+}
+
+// AuditTrail returns the signed interventions in the order they took effect.
+func (s *SitAndGo) AuditTrail() []AuditEvent {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return append([]AuditEvent(nil), s.audit...)
+}
+
+// applyInterventions settles queued rulings after handNo. A player a penalty
+// felts is eliminated in the next place down; a disqualified player forfeits
+// any place, even one already decided, and their chips leave play or are
+// blinded off per the ruling's ForfeitPolicy. Players already out are only
+// affected by disqualification.
+func (s *SitAndGo) applyInterventions(seats []domain.SeatState, level int, handNo uint64, result *SitAndGoResult) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	for _, iv := range s.pending {
+		seatNo, _ := s.seatLocked(iv.PlayerID)
+		seat := &seats[seatNo-1]
+		before := seat.Stack
+		switch {
+		case iv.Kind == InterventionDisqualification:
+			if iv.Forfeit != ForfeitBlindOff {
+				seat.Stack = 0
+			}
+			s.disqualified[iv.PlayerID] = true
+			delete(result.Places, iv.PlayerID)
+			if !slices.Contains(result.Disqualified, iv.PlayerID) {
+				result.Disqualified = append(result.Disqualified, iv.PlayerID)
+			}
+			s.events = append(s.events, SitAndGoEvent{Kind: SitAndGoEventDisqualified, HandNo: handNo, PlayerID: iv.PlayerID, SeatNo: seatNo})
+		case before == 0:
+			// Players already out keep their result.
+		case iv.Kind == InterventionStackAdjustment:
+			if iv.Chips < 0 {
+				seat.Stack = before.SaturatingSub(domain.Chips(-iv.Chips))
+			} else {
+				seat.Stack = before.SaturatingAdd(domain.Chips(iv.Chips))
+			}
+		case iv.Kind == InterventionMissedRoundPenalty:
+			blinds := s.Config.Levels[level]
+			charge := domain.Chips(iv.Rounds) * (blinds.SmallBlind + blinds.BigBlind)
+			seat.Stack = before.SaturatingSub(charge)
+		}
+		if iv.Kind != InterventionDisqualification && before > 0 && seat.Stack == 0 {
+			place := s.countContendersLocked(seats) + 1
+			result.Places[iv.PlayerID] = place
+			s.events = append(s.events, SitAndGoEvent{Kind: SitAndGoEventEliminated, HandNo: handNo, PlayerID: iv.PlayerID, SeatNo: seatNo, Place: place})
+		}
+
+		event := AuditEvent{
+			Seq:          len(s.audit) + 1,
+			TournamentID: s.ID,
+			HandNo:       handNo,
+			Kind:         iv.Kind,
+			PlayerID:     iv.PlayerID,
+			Director:     iv.Director,
+			Reason:       iv.Reason,
+			Forfeit:      iv.Forfeit,
+			Delta:        int64(seat.Stack) - int64(before),
+			StackAfter:   seat.Stack,
+		}
+		if len(s.audit) > 0 {
+			event.PrevSignature = s.audit[len(s.audit)-1].Signature
+		}
+		event.Signature = signAuditEvent(s.AuditKey, event)
+		s.audit = append(s.audit, event)
+	}
+	s.pending = nil
+}
+
+// countFallback tracks each bot's fallback actions in a row and queues a
+// disqualification once one reaches DisqualifyAfterFallbacks, as a bot
+// stuck in a crash loop does.
+func (s *SitAndGo) countFallback(seatNo domain.SeatNo, isFallback bool) {
+	limit := s.Config.DisqualifyAfterFallbacks
+	if limit == 0 {
+		return
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	playerID, ok := s.playerLocked(seatNo)
+	if !ok || s.disqualified[playerID] {
+		return
+	}
+	if !isFallback {
+		s.fallbacks[playerID] = 0
+		return
+	}
+	s.fallbacks[playerID]++
+	if s.fallbacks[playerID] == limit {
+		s.pending = append(s.pending, Intervention{
+			Kind:     InterventionDisqualification,
+			PlayerID: playerID,
+			Director: AutomaticDirector,
+			Reason:   fmt.Sprintf("%d fallback actions in a row", limit),
+			Forfeit:  s.Config.Forfeit,
+		})
+	}
+}
+
+// finishIfSettled closes the tournament to interventions unless some are
+// still queued, in which case the loop settles them first.
+func (s *SitAndGo) finishIfSettled() bool {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if len(s.pending) > 0 {
+		return false
+	}
+	s.finished = true
+	return true
+}
+
+func (s *SitAndGo) markFinished() {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	s.finished = true
+}
@@ -0,0 +1,198 @@
+package tournament
+
+import (
+	"context"
+	"errors"
+	"reflect"
+	"sync"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+)
+
+func TestSitAndGo_DirectorInterventionsAreAuditedAndReflectedInResults(t *testing.T) {
+	t.Parallel()
+
+	config := sitAndGoConfig()
+	config.Seats = 3
+	config.MaxHands = 2
+	sng := newSitAndGo(t, config, "alice", "bob", "carol")
+	sng.AuditKey = []byte("director-key")
+	for _, playerID := range []string{"alice", "bob", "carol"} {
+		if err := sng.Connect(playerID); err != nil {
+			t.Fatalf("Connect failed: %v", err)
+		}
+	}
+
+	rulings := []Intervention{
+		{Kind: InterventionDisqualification, PlayerID: "carol", Director: "td-1", Reason: "collusion"},
+		{Kind: InterventionMissedRoundPenalty, PlayerID: "bob", Director: "td-1", Reason: "left the table", Rounds: 1},
+		{Kind: InterventionStackAdjustment, PlayerID: "alice", Director: "td-1", Reason: "soft play", Chips: -50},
+	}
+	var interveneErrs []error
+	provider := interveningProvider{once: &sync.Once{}, intervene: func() {
+		for _, ruling := range rulings {
+			interveneErrs = append(interveneErrs, sng.Intervene(ruling))
+		}
+	}}
+	result, err := sng.Run(context.Background(), provider)
+	if !errors.Is(err, ErrHandLimitReached) {
+		t.Fatalf("expected ErrHandLimitReached, got %v", err)
+	}
+	for _, err := range interveneErrs {
+		if err != nil {
+			t.Fatalf("Intervene failed: %v", err)
+		}
+	}
+
+	trail := sng.AuditTrail()
+	if len(trail) != len(rulings) {
+		t.Fatalf("expected %d audit events, got %+v", len(rulings), trail)
+	}
+	for i, event := range trail {
+		if event.HandNo != 1 || event.Kind != rulings[i].Kind || event.PlayerID != rulings[i].PlayerID || event.Director != "td-1" {
+			t.Fatalf("event %d does not match its ruling after hand 1: %+v", i+1, event)
+		}
+	}
+	if trail[0].StackAfter != 0 || trail[0].Delta >= 0 || trail[1].Delta != -30 || trail[2].Delta != -50 {
+		t.Fatalf("unexpected chip changes %+v", trail)
+	}
+	delta := trail[0].Delta + trail[1].Delta + trail[2].Delta
+	if total := int64(result.Stacks["alice"]) + int64(result.Stacks["bob"]); total != 3000+delta || result.Stacks["carol"] != 0 {
+		t.Fatalf("expected the rulings to leave %d chips in play, got %+v", 3000+delta, result.Stacks)
+	}
+	if !reflect.DeepEqual(result.Disqualified, []string{"carol"}) {
+		t.Fatalf("expected carol disqualified, got %+v", result.Disqualified)
+	}
+	if _, placed := result.Places["carol"]; placed {
+		t.Fatalf("expected a disqualified player to have no place, got %+v", result.Places)
+	}
+
+	if err := VerifyAuditTrail(sng.AuditKey, trail); err != nil {
+		t.Fatalf("VerifyAuditTrail failed: %v", err)
+	}
+	edited := append([]AuditEvent(nil), trail...)
+	edited[1].Delta = -3
+	if err := VerifyAuditTrail(sng.AuditKey, edited); !errors.Is(err, ErrAuditTampered) {
+		t.Fatalf("expected an edited event to fail, got %v", err)
+	}
+	if err := VerifyAuditTrail(sng.AuditKey, trail[1:]); !errors.Is(err, ErrAuditTampered) {
+		t.Fatalf("expected a dropped event to fail, got %v", err)
+	}
+	if err := VerifyAuditTrail([]byte("other-key"), trail); !errors.Is(err, ErrAuditTampered) {
+		t.Fatalf("expected the wrong key to fail, got %v", err)
+	}
+
+	if err := sng.Intervene(rulings[2]); !errors.Is(err, ErrSitAndGoNotRunning) {
+		t.Fatalf("expected ErrSitAndGoNotRunning once the run ended, got %v", err)
+	}
+}
+
+func TestIntervention_Validate(t *testing.T) {
+	t.Parallel()
+
+	for _, iv := range []Intervention{
+		{Kind: InterventionStackAdjustment, PlayerID: "alice", Director: "td-1", Reason: "r"},
+		{Kind: InterventionMissedRoundPenalty, PlayerID: "alice", Director: "td-1", Reason: "r", Chips: 10, Rounds: 1},
+		{Kind: InterventionDisqualification, PlayerID: "alice", Director: "td-1"},
+		{Kind: InterventionDisqualification, PlayerID: "alice", Director: "td-1", Reason: "r", Forfeit: "refund"},
+		{Kind: InterventionStackAdjustment, PlayerID: "alice", Director: "td-1", Reason: "r", Chips: 10, Forfeit: ForfeitRemove},
+		{Kind: "warning", PlayerID: "alice", Director: "td-1", Reason: "r"},
+	} {
+		if err := iv.Validate(); !errors.Is(err, ErrInvalidIntervention) {
+			t.Fatalf("expected ErrInvalidIntervention for %+v, got %v", iv, err)
+		}
+	}
+
+	sng := newSitAndGo(t, sitAndGoConfig(), "alice", "bob")
+	ruling := Intervention{Kind: InterventionDisqualification, PlayerID: "alice", Director: "td-1", Reason: "r"}
+	if err := sng.Intervene(ruling); !errors.Is(err, ErrAuditKeyMissing) {
+		t.Fatalf("expected ErrAuditKeyMissing, got %v", err)
+	}
+	sng.AuditKey = []byte("director-key")
+	if err := sng.Intervene(ruling); !errors.Is(err, ErrSitAndGoNotRunning) {
+		t.Fatalf("expected ErrSitAndGoNotRunning before the start, got %v", err)
+	}
+}
+
+func TestSitAndGo_DisqualifiesACrashLoopingBotAndBlindsItOff(t *testing.T) {
+	t.Parallel()
+
+	config := sitAndGoConfig()
+	config.Seats = 3
+	config.MaxHands = 3
+	config.DisqualifyAfterFallbacks = 1
+	config.Forfeit = ForfeitBlindOff
+	sng := newSitAndGo(t, config, "alice", "bob", "carol")
+	for _, playerID := range []string{"alice", "bob", "carol"} {
+		if err := sng.Connect(playerID); err != nil {
+			t.Fatalf("Connect failed: %v", err)
+		}
+	}
+	if _, err := sng.Run(context.Background(), crashingProvider{seat: 3}); !errors.Is(err, ErrAuditKeyMissing) {
+		t.Fatalf("expected ErrAuditKeyMissing without an audit key, got %v", err)
+	}
+
+	sng = newSitAndGo(t, config, "alice", "bob", "carol")
+	sng.AuditKey = []byte("director-key")
+	for _, playerID := range []string{"alice", "bob", "carol"} {
+		if err := sng.Connect(playerID); err != nil {
+			t.Fatalf("Connect failed: %v", err)
+		}
+	}
+	result, err := sng.Run(context.Background(), crashingProvider{seat: 3})
+	if !errors.Is(err, ErrHandLimitReached) {
+		t.Fatalf("expected ErrHandLimitReached, got %v", err)
+	}
+
+	trail := sng.AuditTrail()
+	if len(trail) != 1 {
+		t.Fatalf("expected one automatic ruling, got %+v", trail)
+	}
+	if ruling := trail[0]; ruling.HandNo != 1 || ruling.PlayerID != "carol" || ruling.Director != AutomaticDirector || ruling.Forfeit != ForfeitBlindOff || ruling.Delta != 0 {
+		t.Fatalf("expected carol disqualified after hand 1 keeping her stack, got %+v", ruling)
+	}
+	if !reflect.DeepEqual(result.Disqualified, []string{"carol"}) || result.Stacks["carol"] == 0 {
+		t.Fatalf("expected carol disqualified with chips still at the table, got %+v", result)
+	}
+	forfeitFolds := 0
+	for _, event := range sng.Events() {
+		if event.Kind == SitAndGoEventForfeitFold {
+			if event.PlayerID != "carol" || event.HandNo < 2 {
+				t.Fatalf("expected only carol folded after her disqualification, got %+v", event)
+			}
+			forfeitFolds++
+		}
+	}
+	if forfeitFolds == 0 {
+		t.Fatal("expected carol's hands killed once she was disqualified")
+	}
+	if total := result.Stacks["alice"] + result.Stacks["bob"] + result.Stacks["carol"]; total != 3000 {
+		t.Fatalf("expected blinding off to keep every chip in play, got %+v", result.Stacks)
+	}
+}
+
+// crashingProvider fails every decision for seat and otherwise plays like
+// callingProvider.
+type crashingProvider struct {
+	seat domain.SeatNo
+}
+
+func (p crashingProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	if state.ActingSeat == p.seat {
+		return domain.Action{}, errors.New("bot crashed")
+	}
+	return callingProvider{}.NextAction(ctx, state)
+}
+
+// interveningProvider runs intervene on its first decision, mid-hand, and
+// otherwise plays like callingProvider.
+type interveningProvider struct {
+	once      *sync.Once
+	intervene func()
+}
+
+func (p interveningProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	p.once.Do(p.intervene)
+	return callingProvider{}.NextAction(ctx, state)
+}
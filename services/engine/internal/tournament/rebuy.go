@@ -0,0 +1,282 @@
+// Package tournament tracks multi-table tournament entries and prize pool
+// accounting independently of the hands being played, runs single-table
+// sit-and-gos and schedules recurring tournaments from templates.
+package tournament
+
+import (
+	"errors"
+	"fmt"
+	"sort"
+)
+
+var (
+	ErrInvalidConfig      = errors.New("invalid tournament config")
+	ErrAlreadyRegistered  = errors.New("player already registered")
+	ErrPlayerNotFound     = errors.New("player not registered")
+	ErrRebuyPeriodClosed  = errors.New("rebuy period is closed")
+	ErrStackAboveRebuyCap = errors.New("stack is above the rebuy threshold")
+	ErrRebuyLimitReached  = errors.New("rebuy limit reached")
+	ErrAddOnUnavailable   = errors.New("add-on is only available at the break")
+	ErrAddOnTaken         = errors.New("add-on already taken")
+)
+
+// RebuyConfig describes a classic rebuy tournament. Rebuys are allowed for
+// blind levels 1..RebuyLevels and the first RebuyHands hands, whichever of
+// the two are set, while a stack is at or below RebuyThreshold; a felted
+// player may take a double rebuy when AllowDoubleRebuy is set. The first
+// break taken in the last rebuy level, or once the rebuy hands have been
+// played, offers one add-on per player and closes rebuys.
+type RebuyConfig struct {
+	BuyIn            uint32 `json:"buy_in"`
+	StartingStack    uint32 `json:"starting_stack"`
+	RebuyCost        uint32 `json:"rebuy_cost"`
+	RebuyChips       uint32 `json:"rebuy_chips"`
+	RebuyThreshold   uint32 `json:"rebuy_threshold"`
+	RebuyLevels      uint32 `json:"rebuy_levels"`
+	AllowDoubleRebuy bool   `json:"allow_double_rebuy"`
+	AddOnCost        uint32 `json:"add_on_cost"`
+	AddOnChips       uint32 `json:"add_on_chips"`
+	RebuyHands       uint32 `json:"rebuy_hands,omitempty"`
+	// MaxRebuys caps each player's rebuys, a double rebuy counting as two;
+	// zero is unlimited.
+	MaxRebuys uint32 `json:"max_rebuys,omitempty"`
+}
+
+func (c RebuyConfig) Validate() error {
+	if c.BuyIn == 0 || c.StartingStack == 0 {
+		return fmt.Errorf("%w: buy_in and starting_stack must be positive", ErrInvalidConfig)
+	}
+	rebuys := c.RebuyLevels > 0 || c.RebuyHands > 0
+	if rebuys && (c.RebuyCost == 0 || c.RebuyChips == 0) {
+		return fmt.Errorf("%w: rebuy_cost and rebuy_chips must be positive when rebuys are enabled", ErrInvalidConfig)
+	}
+	if (c.AddOnCost == 0) != (c.AddOnChips == 0) {
+		return fmt.Errorf("%w: add_on_cost and add_on_chips must both be set or both be zero", ErrInvalidConfig)
+	}
+	if c.AddOnChips > 0 && !rebuys {
+		return fmt.Errorf("%w: the add-on break follows the rebuy period", ErrInvalidConfig)
+	}
+	return nil
+}
+
+type PurchaseKind string
+
+const (
+	PurchaseBuyIn       PurchaseKind = "buy_in"
+	PurchaseRebuy       PurchaseKind = "rebuy"
+	PurchaseDoubleRebuy PurchaseKind = "double_rebuy"
+	PurchaseAddOn       PurchaseKind = "add_on"
+)
+
+type TournamentEventKind string
+
+const (
+	TournamentEventRebuyTaken TournamentEventKind = "rebuy_taken"
+	TournamentEventAddOnTaken TournamentEventKind = "add_on_taken"
+)
+
+// TournamentEvent announces a rebuy or add-on with the player's rebuy count
+// and the prize pool once it is paid in.
+type TournamentEvent struct {
+	Kind      TournamentEventKind `json:"kind"`
+	Purchase  Purchase            `json:"purchase"`
+	Rebuys    uint32              `json:"rebuys"`
+	PrizePool uint64              `json:"prize_pool"`
+}
+
+// Purchase is one entry into the prize pool.
+type Purchase struct {
+	PlayerID string       `json:"player_id"`
+	Kind     PurchaseKind `json:"kind"`
+	Level    uint32       `json:"level"`
+	Cost     uint32       `json:"cost"`
+	Chips    uint32       `json:"chips"`
+}
+
+type Entry struct {
+	PlayerID   string `json:"player_id"`
+	Rebuys     uint32 `json:"rebuys"`
+	AddOn      bool   `json:"add_on"`
+	EntryCost  uint64 `json:"entry_cost"`
+	ChipsAdded uint64 `json:"chips_added"`
+}
+
+// Tournament is driven by a single tournament loop and is not safe for
+// concurrent use.
+type Tournament struct {
+	ID     string
+	Config RebuyConfig
+
+	level   uint32
+	hands   uint64
+	onBreak bool
+	// addOnLevel is the level whose break offered the add-on and closed
+	// rebuys; zero until that break.
+	addOnLevel uint32
+	entries    map[string]*Entry
+	purchases  []Purchase
+	events     []TournamentEvent
+}
+
+func NewTournament(id string, config RebuyConfig) (*Tournament, error) {
+	if err := config.Validate(); err != nil {
+		return nil, err
+	}
+	return &Tournament{
+		ID:      id,
+		Config:  config,
+		level:   1,
+		entries: make(map[string]*Entry),
+	}, nil
+}
+
+func (t *Tournament) Level() uint32 {
+	return t.level
+}
+
+// AdvanceLevel moves to the next blind level, ending any break.
+func (t *Tournament) AdvanceLevel() {
+	t.level++
+	t.onBreak = false
+}
+
+// EndHand counts a played hand towards RebuyHands. Players who busted in
+// the hand should take their rebuys first.
+func (t *Tournament) EndHand() {
+	t.hands++
+}
+
+// StartBreak begins a break. The first break in the last rebuy level, or
+// once the rebuy hands have been played, is the add-on break.
+func (t *Tournament) StartBreak() {
+	t.onBreak = true
+	if t.addOnLevel == 0 && t.rebuyPeriodEnding() {
+		t.addOnLevel = t.level
+	}
+}
+
+// EndBreak resumes play at the current level.
+func (t *Tournament) EndBreak() {
+	t.onBreak = false
+}
+
+func (t *Tournament) RebuyOpen() bool {
+	if (t.Config.RebuyLevels == 0 && t.Config.RebuyHands == 0) || t.addOnLevel > 0 {
+		return false
+	}
+	levelsOpen := t.Config.RebuyLevels == 0 || t.level <= t.Config.RebuyLevels
+	handsOpen := t.Config.RebuyHands == 0 || t.hands < uint64(t.Config.RebuyHands)
+	return levelsOpen && handsOpen
+}
+
+func (t *Tournament) Register(playerID string) (Purchase, error) {
+	if _, ok := t.entries[playerID]; ok {
+		return Purchase{}, fmt.Errorf("%w: %s", ErrAlreadyRegistered, playerID)
+	}
+	t.entries[playerID] = &Entry{PlayerID: playerID}
+	return t.record(PurchaseBuyIn, playerID, t.Config.BuyIn, t.Config.StartingStack), nil
+}
+
+// Rebuy buys chips for a player whose stack is at or below the threshold.
+// A felted player takes a double rebuy when the config allows it.
+func (t *Tournament) Rebuy(playerID string, stack uint32) (Purchase, error) {
+	entry, ok := t.entries[playerID]
+	if !ok {
+		return Purchase{}, fmt.Errorf("%w: %s", ErrPlayerNotFound, playerID)
+	}
+	if !t.RebuyOpen() {
+		return Purchase{}, fmt.Errorf("%w: level %d", ErrRebuyPeriodClosed, t.level)
+	}
+	if stack > t.Config.RebuyThreshold {
+		return Purchase{}, fmt.Errorf("%w: %d > %d", ErrStackAboveRebuyCap, stack, t.Config.RebuyThreshold)
+	}
+	limit := t.Config.MaxRebuys
+	if limit > 0 && entry.Rebuys >= limit {
+		return Purchase{}, fmt.Errorf("%w: %s has taken %d", ErrRebuyLimitReached, playerID, entry.Rebuys)
+	}
+	// A double rebuy that would pass the limit becomes a single one.
+	if stack == 0 && t.Config.AllowDoubleRebuy && (limit == 0 || entry.Rebuys+2 <= limit) {
+		entry.Rebuys += 2
+		return t.record(PurchaseDoubleRebuy, playerID, 2*t.Config.RebuyCost, 2*t.Config.RebuyChips), nil
+	}
+	entry.Rebuys++
+	return t.record(PurchaseRebuy, playerID, t.Config.RebuyCost, t.Config.RebuyChips), nil
+}
+
+// AddOn sells the single add-on during the add-on break.
+func (t *Tournament) AddOn(playerID string) (Purchase, error) {
+	entry, ok := t.entries[playerID]
+	if !ok {
+		return Purchase{}, fmt.Errorf("%w: %s", ErrPlayerNotFound, playerID)
+	}
+	if t.Config.AddOnChips == 0 || !t.addOnBreak() {
+		return Purchase{}, ErrAddOnUnavailable
+	}
+	if entry.AddOn {
+		return Purchase{}, fmt.Errorf("%w: %s", ErrAddOnTaken, playerID)
+	}
+	entry.AddOn = true
+	return t.record(PurchaseAddOn, playerID, t.Config.AddOnCost, t.Config.AddOnChips), nil
+}
+
+func (t *Tournament) PrizePool() uint64 {
+	total := uint64(0)
+	for _, purchase := range t.purchases {
+		total += uint64(purchase.Cost)
+	}
+	return total
+}
+
+func (t *Tournament) Entry(playerID string) (Entry, bool) {
+	entry, ok := t.entries[playerID]
+	if !ok {
+		return Entry{}, false
+	}
+	return *entry, true
+}
+
+// Entries returns every entry ordered by player ID.
+func (t *Tournament) Entries() []Entry {
+	out := make([]Entry, 0, len(t.entries))
+	for _, entry := range t.entries {
+		out = append(out, *entry)
+	}
+	sort.Slice(out, func(i, j int) bool { return out[i].PlayerID < out[j].PlayerID })
+	return out
+}
+
+// Purchases returns the prize pool ledger in purchase order.
+func (t *Tournament) Purchases() []Purchase {
+	return append([]Purchase(nil), t.purchases...)
+}
+
+// Events returns the rebuys and add-ons taken, in order.
+func (t *Tournament) Events() []TournamentEvent {
+	return append([]TournamentEvent(nil), t.events...)
+}
+
+func (t *Tournament) addOnBreak() bool {
+	return t.onBreak && t.addOnLevel > 0 && t.level == t.addOnLevel
+}
+
+// rebuyPeriodEnding reports whether this is the last rebuy level or the
+// rebuy hands have all been played.
+func (t *Tournament) rebuyPeriodEnding() bool {
+	return (t.Config.RebuyLevels > 0 && t.level >= t.Config.RebuyLevels) ||
+		(t.Config.RebuyHands > 0 && t.hands >= uint64(t.Config.RebuyHands))
+}
+
+func (t *Tournament) record(kind PurchaseKind, playerID string, cost, chips uint32) Purchase {
+	purchase := Purchase{PlayerID: playerID, Kind: kind, Level: t.level, Cost: cost, Chips: chips}
+	t.purchases = append(t.purchases, purchase)
+	entry := t.entries[playerID]
+	entry.EntryCost += uint64(cost)
+	entry.ChipsAdded += uint64(chips)
+	switch kind {
+	case PurchaseRebuy, PurchaseDoubleRebuy:
+		t.events = append(t.events, TournamentEvent{Kind: TournamentEventRebuyTaken, Purchase: purchase, Rebuys: entry.Rebuys, PrizePool: t.PrizePool()})
+	case PurchaseAddOn:
+		t.events = append(t.events, TournamentEvent{Kind: TournamentEventAddOnTaken, Purchase: purchase, Rebuys: entry.Rebuys, PrizePool: t.PrizePool()})
+	}
+	return purchase
+}
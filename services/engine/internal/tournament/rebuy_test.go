@@ -0,0 +1,182 @@
+package tournament
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+)
+
+func TestTournament_RebuyPeriodAndAddOnBreak(t *testing.T) {
+	t.Parallel()
+
+	tourney := newRebuyTournament(t)
+	register(t, tourney, "alice", "bob")
+
+	purchase, err := tourney.Rebuy("alice", 0)
+	if err != nil {
+		t.Fatalf("Rebuy failed: %v", err)
+	}
+	if purchase.Kind != PurchaseDoubleRebuy || purchase.Cost != 200 || purchase.Chips != 20_000 {
+		t.Fatalf("unexpected double rebuy: %+v", purchase)
+	}
+	if _, err := tourney.Rebuy("bob", 8_000); err != nil {
+		t.Fatalf("Rebuy failed: %v", err)
+	}
+	if _, err := tourney.Rebuy("bob", 12_000); !errors.Is(err, ErrStackAboveRebuyCap) {
+		t.Fatalf("expected ErrStackAboveRebuyCap, got %v", err)
+	}
+	if _, err := tourney.AddOn("bob"); !errors.Is(err, ErrAddOnUnavailable) {
+		t.Fatalf("expected ErrAddOnUnavailable before the break, got %v", err)
+	}
+
+	tourney.AdvanceLevel()
+	tourney.StartBreak()
+	if tourney.RebuyOpen() {
+		t.Fatal("expected rebuys to close at the add-on break")
+	}
+	if _, err := tourney.Rebuy("bob", 0); !errors.Is(err, ErrRebuyPeriodClosed) {
+		t.Fatalf("expected ErrRebuyPeriodClosed, got %v", err)
+	}
+	if _, err := tourney.AddOn("bob"); err != nil {
+		t.Fatalf("AddOn failed: %v", err)
+	}
+	if _, err := tourney.AddOn("bob"); !errors.Is(err, ErrAddOnTaken) {
+		t.Fatalf("expected ErrAddOnTaken, got %v", err)
+	}
+
+	tourney.AdvanceLevel()
+	if _, err := tourney.AddOn("alice"); !errors.Is(err, ErrAddOnUnavailable) {
+		t.Fatalf("expected ErrAddOnUnavailable after the break, got %v", err)
+	}
+
+	if got := tourney.PrizePool(); got != 100+100+200+100+150 {
+		t.Fatalf("unexpected prize pool %d", got)
+	}
+	want := []Entry{
+		{PlayerID: "alice", Rebuys: 2, EntryCost: 300, ChipsAdded: 30_000},
+		{PlayerID: "bob", Rebuys: 1, AddOn: true, EntryCost: 350, ChipsAdded: 35_000},
+	}
+	if got := tourney.Entries(); !reflect.DeepEqual(got, want) {
+		t.Fatalf("unexpected entries\nwant=%+v\ngot=%+v", want, got)
+	}
+}
+
+func TestTournament_SingleRebuyWhenDoubleDisabled(t *testing.T) {
+	t.Parallel()
+
+	config := rebuyConfig()
+	config.AllowDoubleRebuy = false
+	tourney, err := NewTournament("t-1", config)
+	if err != nil {
+		t.Fatalf("NewTournament failed: %v", err)
+	}
+	register(t, tourney, "alice")
+
+	purchase, err := tourney.Rebuy("alice", 0)
+	if err != nil {
+		t.Fatalf("Rebuy failed: %v", err)
+	}
+	if purchase.Kind != PurchaseRebuy || purchase.Chips != 10_000 {
+		t.Fatalf("unexpected rebuy: %+v", purchase)
+	}
+	if _, err := tourney.Rebuy("carol", 0); !errors.Is(err, ErrPlayerNotFound) {
+		t.Fatalf("expected ErrPlayerNotFound, got %v", err)
+	}
+}
+
+func TestTournament_RebuyWindowByHandsAndLimit(t *testing.T) {
+	t.Parallel()
+
+	config := rebuyConfig()
+	config.RebuyLevels = 0
+	config.RebuyHands = 3
+	config.MaxRebuys = 3
+	tourney, err := NewTournament("t-1", config)
+	if err != nil {
+		t.Fatalf("NewTournament failed: %v", err)
+	}
+	register(t, tourney, "alice", "bob")
+
+	if _, err := tourney.Rebuy("alice", 0); err != nil {
+		t.Fatalf("Rebuy failed: %v", err)
+	}
+	tourney.EndHand()
+	// One rebuy left, so felting again buys a single rebuy.
+	purchase, err := tourney.Rebuy("alice", 0)
+	if err != nil {
+		t.Fatalf("Rebuy failed: %v", err)
+	}
+	if purchase.Kind != PurchaseRebuy {
+		t.Fatalf("expected a single rebuy at the limit, got %+v", purchase)
+	}
+	if _, err := tourney.Rebuy("alice", 0); !errors.Is(err, ErrRebuyLimitReached) {
+		t.Fatalf("expected ErrRebuyLimitReached, got %v", err)
+	}
+
+	// A break before the rebuy hands are played is not the add-on break.
+	tourney.StartBreak()
+	if _, err := tourney.AddOn("bob"); !errors.Is(err, ErrAddOnUnavailable) {
+		t.Fatalf("expected ErrAddOnUnavailable, got %v", err)
+	}
+	tourney.EndBreak()
+	tourney.EndHand()
+	tourney.EndHand()
+	if tourney.RebuyOpen() {
+		t.Fatal("expected rebuys to close after three hands")
+	}
+	tourney.StartBreak()
+	if _, err := tourney.AddOn("bob"); err != nil {
+		t.Fatalf("AddOn failed: %v", err)
+	}
+
+	want := []TournamentEvent{
+		{Kind: TournamentEventRebuyTaken, Purchase: Purchase{PlayerID: "alice", Kind: PurchaseDoubleRebuy, Level: 1, Cost: 200, Chips: 20_000}, Rebuys: 2, PrizePool: 400},
+		{Kind: TournamentEventRebuyTaken, Purchase: Purchase{PlayerID: "alice", Kind: PurchaseRebuy, Level: 1, Cost: 100, Chips: 10_000}, Rebuys: 3, PrizePool: 500},
+		{Kind: TournamentEventAddOnTaken, Purchase: Purchase{PlayerID: "bob", Kind: PurchaseAddOn, Level: 1, Cost: 150, Chips: 15_000}, PrizePool: 650},
+	}
+	if got := tourney.Events(); !reflect.DeepEqual(got, want) {
+		t.Fatalf("unexpected events\nwant=%+v\ngot=%+v", want, got)
+	}
+}
+
+func TestRebuyConfig_ValidateRejectsIncompleteAddOn(t *testing.T) {
+	t.Parallel()
+
+	config := rebuyConfig()
+	config.AddOnChips = 0
+	if err := config.Validate(); !errors.Is(err, ErrInvalidConfig) {
+		t.Fatalf("expected ErrInvalidConfig, got %v", err)
+	}
+}
+
+func rebuyConfig() RebuyConfig {
+	return RebuyConfig{
+		BuyIn:            100,
+		StartingStack:    10_000,
+		RebuyCost:        100,
+		RebuyChips:       10_000,
+		RebuyThreshold:   10_000,
+		RebuyLevels:      2,
+		AllowDoubleRebuy: true,
+		AddOnCost:        150,
+		AddOnChips:       15_000,
+	}
+}
+
+func newRebuyTournament(t *testing.T) *Tournament {
+	t.Helper()
+	tourney, err := NewTournament("t-1", rebuyConfig())
+	if err != nil {
+		t.Fatalf("NewTournament failed: %v", err)
+	}
+	return tourney
+}
+
+func register(t *testing.T, tourney *Tournament, playerIDs ...string) {
+	t.Helper()
+	for _, playerID := range playerIDs {
+		if _, err := tourney.Register(playerID); err != nil {
+			t.Fatalf("Register failed: %v", err)
+		}
+	}
+}
@@ -0,0 +1,347 @@
+package tournament
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+)
+
+// MaxCalendarSpan bounds a single Calendar query.
+const MaxCalendarSpan = 92 * 24 * time.Hour
+
+const scheduledIDLayout = "20060102T1504"
+
+var (
+	ErrTemplateExists     = errors.New("template already exists")
+	ErrTournamentNotFound = errors.New("scheduled tournament not found")
+	ErrRegistrationClosed = errors.New("registration is not open")
+)
+
+type Recurrence string
+
+const (
+	RecurrenceDaily  Recurrence = "daily"
+	RecurrenceWeekly Recurrence = "weekly"
+)
+
+// Template schedules a recurring sit-and-go, such as a daily 18:00 UTC
+// turbo or a Sunday major. Each occurrence is its own tournament, with
+// registration open from RegistrationOpensMS before the start until
+// LateRegistrationMS after it.
+type Template struct {
+	ID         string     `json:"id"`
+	Name       string     `json:"name"`
+	Recurrence Recurrence `json:"recurrence"`
+	// Weekday names the day a weekly template runs, such as "sunday".
+	Weekday string `json:"weekday,omitempty"`
+	// StartTime is the UTC time of day play starts, as HH:MM.
+	StartTime           string         `json:"start_time"`
+	RegistrationOpensMS uint64         `json:"registration_opens_ms"`
+	LateRegistrationMS  uint64         `json:"late_registration_ms,omitempty"`
+	Config              SitAndGoConfig `json:"config"`
+}
+
+func (t Template) Validate() error {
+	if t.ID == "" || strings.ContainsAny(t.ID, "@/") {
+		return fmt.Errorf("%w: id must be set and contain no '@' or '/'", ErrInvalidConfig)
+	}
+	if _, err := time.Parse("15:04", t.StartTime); err != nil {
+		return fmt.Errorf("%w: start_time must be HH:MM, got %q", ErrInvalidConfig, t.StartTime)
+	}
+	switch t.Recurrence {
+	case RecurrenceDaily:
+		if t.Weekday != "" {
+			return fmt.Errorf("%w: a daily template has no weekday", ErrInvalidConfig)
+		}
+	case RecurrenceWeekly:
+		if _, ok := parseWeekday(t.Weekday); !ok {
+			return fmt.Errorf("%w: unknown weekday %q", ErrInvalidConfig, t.Weekday)
+		}
+	default:
+		return fmt.Errorf("%w: recurrence must be %q or %q", ErrInvalidConfig, RecurrenceDaily, RecurrenceWeekly)
+	}
+	if t.RegistrationOpensMS == 0 && t.LateRegistrationMS == 0 {
+		return fmt.Errorf("%w: registration must be open for some time", ErrInvalidConfig)
+	}
+	return t.Config.Validate()
+}
+
+// starts returns the template's start times from from through to.
+func (t Template) starts(from, to time.Time) []time.Time {
+	clock, _ := time.Parse("15:04", t.StartTime)
+	from = from.UTC()
+	start := time.Date(from.Year(), from.Month(), from.Day(), clock.Hour(), clock.Minute(), 0, 0, time.UTC)
+	days := 1
+	if t.Recurrence == RecurrenceWeekly {
+		weekday, _ := parseWeekday(t.Weekday)
+		for start.Weekday() != weekday {
+			start = start.AddDate(0, 0, 1)
+		}
+		days = 7
+	}
+	for start.Before(from) {
+		start = start.AddDate(0, 0, days)
+	}
+	var out []time.Time
+	for ; !start.After(to); start = start.AddDate(0, 0, days) {
+		out = append(out, start)
+	}
+	return out
+}
+
+func (t Template) opensBefore() time.Duration {
+	return time.Duration(t.RegistrationOpensMS) * time.Millisecond
+}
+
+func (t Template) lateRegistration() time.Duration {
+	return time.Duration(t.LateRegistrationMS) * time.Millisecond
+}
+
+type ScheduleStatus string
+
+const (
+	ScheduleStatusScheduled          ScheduleStatus = "scheduled"
+	ScheduleStatusRegistrationOpen   ScheduleStatus = "registration_open"
+	ScheduleStatusRegistrationClosed ScheduleStatus = "registration_closed"
+)
+
+// ScheduledTournament is one occurrence of a Template.
+type ScheduledTournament struct {
+	ID                   string         `json:"id"`
+	TemplateID           string         `json:"template_id"`
+	Name                 string         `json:"name"`
+	StartsAt             time.Time      `json:"starts_at"`
+	RegistrationOpensAt  time.Time      `json:"registration_opens_at"`
+	RegistrationClosesAt time.Time      `json:"registration_closes_at"`
+	Status               ScheduleStatus `json:"status"`
+	Seats                uint8          `json:"seats"`
+	Players              []string       `json:"players"`
+}
+
+type ScheduleEventKind string
+
+const (
+	ScheduleEventRegistrationOpened ScheduleEventKind = "registration_opened"
+	// ScheduleEventRegistrationClosed carries the final player list.
+	ScheduleEventRegistrationClosed ScheduleEventKind = "registration_closed"
+)
+
+// ScheduleEvent reports a registration window opening or closing. At is
+// when it did, which may be before the Tick that noticed it.
+type ScheduleEvent struct {
+	Kind         ScheduleEventKind `json:"kind"`
+	TournamentID string            `json:"tournament_id"`
+	At           time.Time         `json:"at"`
+	Players      []string          `json:"players,omitempty"`
+}
+
+// Scheduler turns templates into a calendar of tournaments and opens and
+// closes their registration as Run ticks. It is safe for concurrent use.
+type Scheduler struct {
+	mu        sync.Mutex
+	now       func() time.Time
+	templates map[string]Template
+	players   map[string][]string
+	// announced is the status each tournament was last ticked in, for
+	// tournaments whose registration had not closed by the previous tick.
+	announced map[string]ScheduleStatus
+	lastTick  time.Time
+}
+
+func NewScheduler() *Scheduler {
+	now := func() time.Time { return time.Now().UTC() }
+	return &Scheduler{
+		now:       now,
+		templates: make(map[string]Template),
+		players:   make(map[string][]string),
+		announced: make(map[string]ScheduleStatus),
+		lastTick:  now(),
+	}
+}
+
+func (s *Scheduler) AddTemplate(template Template) error {
+	if err := template.Validate(); err != nil {
+		return err
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if _, ok := s.templates[template.ID]; ok {
+		return fmt.Errorf("%w: %s", ErrTemplateExists, template.ID)
+	}
+	s.templates[template.ID] = template
+	return nil
+}
+
+// Templates returns every template ordered by ID.
+func (s *Scheduler) Templates() []Template {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	out := make([]Template, 0, len(s.templates))
+	for _, template := range s.templates {
+		out = append(out, template)
+	}
+	sort.Slice(out, func(i, j int) bool { return out[i].ID < out[j].ID })
+	return out
+}
+
+// Calendar returns the tournaments starting from from through to, in start
+// order.
+func (s *Scheduler) Calendar(from, to time.Time) ([]ScheduledTournament, error) {
+	if to.Before(from) || to.Sub(from) > MaxCalendarSpan {
+		return nil, fmt.Errorf("%w: calendar span must be 0..%s", ErrInvalidConfig, MaxCalendarSpan)
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	now := s.now()
+	out := []ScheduledTournament{}
+	for _, template := range s.templates {
+		for _, start := range template.starts(from, to) {
+			out = append(out, s.scheduled(template, start, now))
+		}
+	}
+	sort.Slice(out, func(i, j int) bool {
+		if !out[i].StartsAt.Equal(out[j].StartsAt) {
+			return out[i].StartsAt.Before(out[j].StartsAt)
+		}
+		return out[i].ID < out[j].ID
+	})
+	return out, nil
+}
+
+// Register enters playerID in a tournament whose registration is open.
+func (s *Scheduler) Register(tournamentID, playerID string) (ScheduledTournament, error) {
+	if playerID == "" {
+		return ScheduledTournament{}, ErrMissingPlayerID
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	template, start, ok := s.lookup(tournamentID)
+	if !ok {
+		return ScheduledTournament{}, fmt.Errorf("%w: %s", ErrTournamentNotFound, tournamentID)
+	}
+	scheduled := s.scheduled(template, start, s.now())
+	switch {
+	case scheduled.Status != ScheduleStatusRegistrationOpen:
+		return scheduled, fmt.Errorf("%w: %s is %s", ErrRegistrationClosed, tournamentID, scheduled.Status)
+	case len(scheduled.Players) >= int(scheduled.Seats):
+		return scheduled, ErrSitAndGoFull
+	}
+	for _, registered := range scheduled.Players {
+		if registered == playerID {
+			return scheduled, fmt.Errorf("%w: %s", ErrAlreadyRegistered, playerID)
+		}
+	}
+	s.players[tournamentID] = append(s.players[tournamentID], playerID)
+	scheduled.Players = append(scheduled.Players, playerID)
+	return scheduled, nil
+}
+
+// Tick opens and closes the registration windows that have come round
+// since the previous tick and reports them in time order.
+func (s *Scheduler) Tick(now time.Time) []ScheduleEvent {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	var events []ScheduleEvent
+	for _, template := range s.templates {
+		// Windows that had closed by the previous tick were reported then.
+		from := s.lastTick.Add(time.Nanosecond - template.lateRegistration())
+		for _, start := range template.starts(from, now.Add(template.opensBefore())) {
+			scheduled := s.scheduled(template, start, now)
+			previous := s.announced[scheduled.ID]
+			if scheduled.Status != ScheduleStatusScheduled && (previous == "" || previous == ScheduleStatusScheduled) {
+				events = append(events, ScheduleEvent{Kind: ScheduleEventRegistrationOpened, TournamentID: scheduled.ID, At: scheduled.RegistrationOpensAt})
+			}
+			if scheduled.Status == ScheduleStatusRegistrationClosed && previous != ScheduleStatusRegistrationClosed {
+				events = append(events, ScheduleEvent{
+					Kind:         ScheduleEventRegistrationClosed,
+					TournamentID: scheduled.ID,
+					At:           scheduled.RegistrationClosesAt,
+					Players:      scheduled.Players,
+				})
+			}
+			s.announced[scheduled.ID] = scheduled.Status
+		}
+	}
+	for id, status := range s.announced {
+		if status == ScheduleStatusRegistrationClosed {
+			delete(s.announced, id)
+		}
+	}
+	s.lastTick = now
+	sort.SliceStable(events, func(i, j int) bool { return events[i].At.Before(events[j].At) })
+	return events
+}
+
+// Run ticks every interval until ctx is done, passing each event to
+// onEvent.
+func (s *Scheduler) Run(ctx context.Context, every time.Duration, onEvent func(ScheduleEvent)) {
+	ticker := time.NewTicker(every)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+			for _, event := range s.Tick(s.now()) {
+				onEvent(event)
+			}
+		}
+	}
+}
+
+func (s *Scheduler) scheduled(template Template, start, now time.Time) ScheduledTournament {
+	id := template.ID + "@" + start.Format(scheduledIDLayout)
+	scheduled := ScheduledTournament{
+		ID:                   id,
+		TemplateID:           template.ID,
+		Name:                 template.Name,
+		StartsAt:             start,
+		RegistrationOpensAt:  start.Add(-template.opensBefore()),
+		RegistrationClosesAt: start.Add(template.lateRegistration()),
+		Seats:                template.Config.Seats,
+		Players:              append([]string{}, s.players[id]...),
+	}
+	switch {
+	case now.Before(scheduled.RegistrationOpensAt):
+		scheduled.Status = ScheduleStatusScheduled
+	case now.Before(scheduled.RegistrationClosesAt):
+		scheduled.Status = ScheduleStatusRegistrationOpen
+	default:
+		scheduled.Status = ScheduleStatusRegistrationClosed
+	}
+	return scheduled
+}
+
+// lookup finds the template and start time a tournament ID names.
+func (s *Scheduler) lookup(tournamentID string) (Template, time.Time, bool) {
+	templateID, at, ok := strings.Cut(tournamentID, "@")
+	if !ok {
+		return Template{}, time.Time{}, false
+	}
+	template, ok := s.templates[templateID]
+	if !ok {
+		return Template{}, time.Time{}, false
+	}
+	start, err := time.Parse(scheduledIDLayout, at)
+	if err != nil {
+		return Template{}, time.Time{}, false
+	}
+	starts := template.starts(start, start)
+	if len(starts) != 1 {
+		return Template{}, time.Time{}, false
+	}
+	return template, start, true
+}
+
+func parseWeekday(name string) (time.Weekday, bool) {
+	for day := time.Sunday; day <= time.Saturday; day++ {
+		if strings.EqualFold(day.String(), name) {
+			return day, true
+		}
+	}
+	return 0, false
+}
@@ -0,0 +1,102 @@
+package tournament
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+	"time"
+)
+
+func TestScheduler_CalendarRegistrationAndTicks(t *testing.T) {
+	t.Parallel()
+
+	now := time.Date(2026, 6, 5, 16, 0, 0, 0, time.UTC) // a Friday
+	scheduler := NewScheduler()
+	scheduler.now = func() time.Time { return now }
+	scheduler.lastTick = now
+	daily := Template{
+		ID:                  "daily-turbo",
+		Name:                "Daily Turbo",
+		Recurrence:          RecurrenceDaily,
+		StartTime:           "18:00",
+		RegistrationOpensMS: uint64(time.Hour.Milliseconds()),
+		LateRegistrationMS:  uint64((10 * time.Minute).Milliseconds()),
+		Config:              sitAndGoConfig(),
+	}
+	weekly := Template{
+		ID:                  "sunday-major",
+		Name:                "Sunday Major",
+		Recurrence:          RecurrenceWeekly,
+		Weekday:             "sunday",
+		StartTime:           "20:00",
+		RegistrationOpensMS: uint64((24 * time.Hour).Milliseconds()),
+		Config:              sitAndGoConfig(),
+	}
+	for _, template := range []Template{daily, weekly} {
+		if err := scheduler.AddTemplate(template); err != nil {
+			t.Fatalf("AddTemplate failed: %v", err)
+		}
+	}
+	if err := scheduler.AddTemplate(daily); !errors.Is(err, ErrTemplateExists) {
+		t.Fatalf("expected ErrTemplateExists, got %v", err)
+	}
+	weekly.ID, weekly.Weekday = "someday-major", "someday"
+	if err := scheduler.AddTemplate(weekly); !errors.Is(err, ErrInvalidConfig) {
+		t.Fatalf("expected ErrInvalidConfig, got %v", err)
+	}
+
+	calendar, err := scheduler.Calendar(now, now.Add(72*time.Hour))
+	if err != nil {
+		t.Fatalf("Calendar failed: %v", err)
+	}
+	var ids []string
+	for _, scheduled := range calendar {
+		ids = append(ids, scheduled.ID)
+		if scheduled.Status != ScheduleStatusScheduled {
+			t.Fatalf("expected nothing open yet, got %+v", scheduled)
+		}
+	}
+	want := []string{"daily-turbo@20260605T1800", "daily-turbo@20260606T1800", "daily-turbo@20260607T1800", "sunday-major@20260607T2000"}
+	if !reflect.DeepEqual(ids, want) {
+		t.Fatalf("unexpected calendar\nwant=%v\ngot=%v", want, ids)
+	}
+
+	tonight := "daily-turbo@20260605T1800"
+	if _, err := scheduler.Register(tonight, "alice"); !errors.Is(err, ErrRegistrationClosed) {
+		t.Fatalf("expected ErrRegistrationClosed before registration opens, got %v", err)
+	}
+	if _, err := scheduler.Register("daily-turbo@20260605T1700", "alice"); !errors.Is(err, ErrTournamentNotFound) {
+		t.Fatalf("expected ErrTournamentNotFound, got %v", err)
+	}
+	now = now.Add(90 * time.Minute)
+	if _, err := scheduler.Register(tonight, "alice"); err != nil {
+		t.Fatalf("Register failed: %v", err)
+	}
+	if _, err := scheduler.Register(tonight, "alice"); !errors.Is(err, ErrAlreadyRegistered) {
+		t.Fatalf("expected ErrAlreadyRegistered, got %v", err)
+	}
+	if _, err := scheduler.Register(tonight, "bob"); err != nil {
+		t.Fatalf("Register failed: %v", err)
+	}
+	if _, err := scheduler.Register(tonight, "carol"); !errors.Is(err, ErrSitAndGoFull) {
+		t.Fatalf("expected ErrSitAndGoFull, got %v", err)
+	}
+
+	opened := []ScheduleEvent{{Kind: ScheduleEventRegistrationOpened, TournamentID: tonight, At: time.Date(2026, 6, 5, 17, 0, 0, 0, time.UTC)}}
+	if got := scheduler.Tick(now); !reflect.DeepEqual(got, opened) {
+		t.Fatalf("unexpected events\nwant=%+v\ngot=%+v", opened, got)
+	}
+	now = now.Add(45 * time.Minute)
+	closed := []ScheduleEvent{{
+		Kind:         ScheduleEventRegistrationClosed,
+		TournamentID: tonight,
+		At:           time.Date(2026, 6, 5, 18, 10, 0, 0, time.UTC),
+		Players:      []string{"alice", "bob"},
+	}}
+	if got := scheduler.Tick(now); !reflect.DeepEqual(got, closed) {
+		t.Fatalf("unexpected events\nwant=%+v\ngot=%+v", closed, got)
+	}
+	if got := scheduler.Tick(now); len(got) != 0 {
+		t.Fatalf("expected each window reported once, got %+v", got)
+	}
+}
@@ -0,0 +1,559 @@
+package tournament
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"sort"
+	"sync"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+)
+
+const DefaultSitAndGoMaxHands = 1000
+
+var (
+	ErrSitAndGoFull      = errors.New("sit-and-go is full")
+	ErrSitAndGoNotFull   = errors.New("sit-and-go starts once every seat is registered")
+	ErrSitAndGoStarted   = errors.New("sit-and-go already started")
+	ErrHandLimitReached  = errors.New("sit-and-go hand limit reached")
+	ErrMissingPlayerID   = errors.New("player id is required")
+	ErrInvalidBlindLevel = errors.New("invalid blind level")
+)
+
+type BlindLevel struct {
+	SmallBlind domain.Chips `json:"small_blind"`
+	BigBlind   domain.Chips `json:"big_blind"`
+}
+
+// SitAndGoConfig describes a single-table tournament that starts as soon as
+// every seat is registered. Blinds move up every HandsPerLevel hands and stay
+// at the last level once the schedule runs out.
+type SitAndGoConfig struct {
+	Seats         uint8        `json:"seats"`
+	StartingStack domain.Chips `json:"starting_stack"`
+	Levels        []BlindLevel `json:"levels"`
+	HandsPerLevel int          `json:"hands_per_level"`
+	// MaxHands stops a run that cannot finish, such as a table where nobody
+	// connected; zero means DefaultSitAndGoMaxHands.
+	MaxHands int `json:"max_hands,omitempty"`
+	// DisqualifyAfterFallbacks disqualifies a player whose bot needed that
+	// many fallback actions in a row; zero never does. Forfeit is the policy
+	// those disqualifications apply. Both need the SitAndGo's AuditKey.
+	DisqualifyAfterFallbacks int           `json:"disqualify_after_fallbacks,omitempty"`
+	Forfeit                  ForfeitPolicy `json:"forfeit,omitempty"`
+	// Rebuys makes this a rebuy tournament. Bots cannot ask for chips, so a
+	// player who busts while rebuys are open rebuys straight away and every
+	// player still in takes the add-on. The table breaks between blind
+	// levels and once the rebuy hands have been played. Its StartingStack
+	// must match the sit-and-go's.
+	Rebuys *RebuyConfig `json:"rebuys,omitempty"`
+	// RulesVersion pins the game rules every hand of the tournament plays
+	// under. NewSitAndGo pins an unset version to the current one, so an
+	// engine upgrade mid-tournament never changes the game.
+	RulesVersion domain.RulesVersion `json:"rules_version,omitempty"`
+}
+
+func (c SitAndGoConfig) Validate() error {
+	if c.Seats < 2 || c.Seats > domain.DefaultMaxSeats {
+		return fmt.Errorf("%w: seats must be in range 2..=%d, got %d", ErrInvalidConfig, domain.DefaultMaxSeats, c.Seats)
+	}
+	if c.StartingStack == 0 {
+		return fmt.Errorf("%w: starting_stack must be positive", ErrInvalidConfig)
+	}
+	if len(c.Levels) == 0 {
+		return fmt.Errorf("%w: at least one blind level is required", ErrInvalidConfig)
+	}
+	for i, level := range c.Levels {
+		if level.SmallBlind == 0 || level.BigBlind < level.SmallBlind {
+			return fmt.Errorf("%w: level %d is %d/%d", ErrInvalidBlindLevel, i+1, level.SmallBlind, level.BigBlind)
+		}
+	}
+	if c.HandsPerLevel <= 0 {
+		return fmt.Errorf("%w: hands_per_level must be positive", ErrInvalidConfig)
+	}
+	if c.MaxHands < 0 {
+		return fmt.Errorf("%w: max_hands must not be negative", ErrInvalidConfig)
+	}
+	if c.DisqualifyAfterFallbacks < 0 {
+		return fmt.Errorf("%w: disqualify_after_fallbacks must not be negative", ErrInvalidConfig)
+	}
+	if err := c.Forfeit.validate(); err != nil {
+		return fmt.Errorf("%w: %v", ErrInvalidConfig, err)
+	}
+	if err := c.RulesVersion.Validate(); err != nil {
+		return fmt.Errorf("%w: %w", ErrInvalidConfig, err)
+	}
+	if c.Rebuys != nil {
+		if err := c.Rebuys.Validate(); err != nil {
+			return err
+		}
+		if domain.Chips(c.Rebuys.StartingStack) != c.StartingStack {
+			return fmt.Errorf("%w: rebuys starting_stack %d does not match %d", ErrInvalidConfig, c.Rebuys.StartingStack, c.StartingStack)
+		}
+	}
+	return nil
+}
+
+type SitAndGoEventKind string
+
+const (
+	SitAndGoEventConnected SitAndGoEventKind = "player_connected"
+	SitAndGoEventStarted   SitAndGoEventKind = "started"
+	// SitAndGoEventAbsent marks a registered player with no connection when
+	// the tournament starts; their stack is blinded off until they connect.
+	SitAndGoEventAbsent     SitAndGoEventKind = "player_absent"
+	SitAndGoEventLevel      SitAndGoEventKind = "level_started"
+	SitAndGoEventBlindedOff SitAndGoEventKind = "blinded_off"
+	SitAndGoEventAbsentFold SitAndGoEventKind = "absent_fold"
+	SitAndGoEventEliminated SitAndGoEventKind = "eliminated"
+	SitAndGoEventFinished   SitAndGoEventKind = "finished"
+	// SitAndGoEventDisqualified is a director's ruling; the signed detail
+	// is in the audit trail.
+	SitAndGoEventDisqualified SitAndGoEventKind = "disqualified"
+	// SitAndGoEventForfeitFold kills the hand of a player disqualified with
+	// ForfeitBlindOff.
+	SitAndGoEventForfeitFold SitAndGoEventKind = "forfeit_fold"
+	// SitAndGoEventRebuyTaken and SitAndGoEventAddOnTaken carry the chips
+	// bought as Amount.
+	SitAndGoEventRebuyTaken SitAndGoEventKind = "rebuy_taken"
+	SitAndGoEventAddOnTaken SitAndGoEventKind = "add_on_taken"
+)
+
+type SitAndGoEvent struct {
+	Kind     SitAndGoEventKind `json:"kind"`
+	HandNo   uint64            `json:"hand_no,omitempty"`
+	PlayerID string            `json:"player_id,omitempty"`
+	SeatNo   domain.SeatNo     `json:"seat_no,omitempty"`
+	Amount   domain.Chips      `json:"amount,omitempty"`
+	Level    int               `json:"level,omitempty"`
+	Place    int               `json:"place,omitempty"`
+}
+
+type SitAndGoResult struct {
+	HandsPlayed int
+	Stacks      map[string]domain.Chips
+	// Places holds finishing positions decided so far; the winner gets 1.
+	Places map[string]int
+	// Disqualified players have no place, whenever they were removed, and
+	// do not count towards anyone else's.
+	Disqualified []string
+	// PrizePool is the buy-ins, rebuys and add-ons paid into a rebuy
+	// tournament; zero otherwise.
+	PrizePool uint64
+}
+
+// SitAndGo seats players in registration order. Connect may be called while
+// Run is in progress; everything else is meant for the tournament loop.
+type SitAndGo struct {
+	ID     string
+	Config SitAndGoConfig
+	// Shuffler is shared across hands; nil uses the crypto shuffler.
+	Shuffler rules.Shuffler
+	// AuditKey signs the director's audit trail; interventions are refused
+	// without one.
+	AuditKey []byte
+
+	mu           sync.Mutex
+	players      []string
+	connected    map[string]bool
+	started      bool
+	finished     bool
+	events       []SitAndGoEvent
+	pending      []Intervention
+	audit        []AuditEvent
+	disqualified map[string]bool
+	fallbacks    map[string]int
+	// rebuys is the prize pool ledger when Config.Rebuys is set.
+	rebuys *Tournament
+}
+
+func NewSitAndGo(id string, config SitAndGoConfig) (*SitAndGo, error) {
+	if err := config.Validate(); err != nil {
+		return nil, err
+	}
+	config.RulesVersion = config.RulesVersion.Effective()
+	sng := &SitAndGo{
+		ID:           id,
+		Config:       config,
+		players:      make([]string, 0, config.Seats),
+		connected:    make(map[string]bool, config.Seats),
+		disqualified: make(map[string]bool),
+		fallbacks:    make(map[string]int),
+	}
+	if config.Rebuys != nil {
+		rebuys, err := NewTournament(id, *config.Rebuys)
+		if err != nil {
+			return nil, err
+		}
+		sng.rebuys = rebuys
+	}
+	return sng, nil
+}
+
+// Register takes the next free seat for playerID.
+func (s *SitAndGo) Register(playerID string) (domain.SeatNo, error) {
+	if playerID == "" {
+		return 0, ErrMissingPlayerID
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.started {
+		return 0, ErrSitAndGoStarted
+	}
+	for _, registered := range s.players {
+		if registered == playerID {
+			return 0, fmt.Errorf("%w: %s", ErrAlreadyRegistered, playerID)
+		}
+	}
+	if len(s.players) >= int(s.Config.Seats) {
+		return 0, ErrSitAndGoFull
+	}
+	if s.rebuys != nil {
+		if _, err := s.rebuys.Register(playerID); err != nil {
+			return 0, err
+		}
+	}
+	s.players = append(s.players, playerID)
+	return domain.SeatNo(len(s.players)), nil
+}
+
+func (s *SitAndGo) Full() bool {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return len(s.players) == int(s.Config.Seats)
+}
+
+// Connect marks a registered player as present. From their next decision on
+// the provider acts for them instead of their hand being folded.
+func (s *SitAndGo) Connect(playerID string) error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	seatNo, ok := s.seatLocked(playerID)
+	if !ok {
+		return fmt.Errorf("%w: %s", ErrPlayerNotFound, playerID)
+	}
+	if s.connected[playerID] {
+		return nil
+	}
+	s.connected[playerID] = true
+	s.events = append(s.events, SitAndGoEvent{Kind: SitAndGoEventConnected, PlayerID: playerID, SeatNo: seatNo})
+	return nil
+}
+
+// Events returns the tournament log in the order it was written.
+func (s *SitAndGo) Events() []SitAndGoEvent {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return append([]SitAndGoEvent(nil), s.events...)
+}
+
+// Run plays hands until one player holds every chip. Seats whose player
+// never connected are dealt in, post their blinds and have their hand killed
+// when the action reaches them, so an absent player is blinded off rather
+// than stalling the table.
+func (s *SitAndGo) Run(ctx context.Context, provider tablerunner.ActionProvider) (SitAndGoResult, error) {
+	result := SitAndGoResult{Stacks: make(map[string]domain.Chips), Places: make(map[string]int)}
+
+	s.mu.Lock()
+	if s.started {
+		s.mu.Unlock()
+		return result, ErrSitAndGoStarted
+	}
+	if len(s.players) < int(s.Config.Seats) {
+		s.mu.Unlock()
+		return result, fmt.Errorf("%w: %d of %d seats taken", ErrSitAndGoNotFull, len(s.players), s.Config.Seats)
+	}
+	if s.Config.DisqualifyAfterFallbacks > 0 && len(s.AuditKey) == 0 {
+		s.mu.Unlock()
+		return result, ErrAuditKeyMissing
+	}
+	s.started = true
+	players := append([]string(nil), s.players...)
+	seats := make([]domain.SeatState, 0, len(players))
+	s.events = append(s.events, SitAndGoEvent{Kind: SitAndGoEventStarted})
+	for i, playerID := range players {
+		seatNo := domain.SeatNo(i + 1)
+		seats = append(seats, domain.NewSeatState(seatNo, s.Config.StartingStack))
+		if !s.connected[playerID] {
+			s.events = append(s.events, SitAndGoEvent{Kind: SitAndGoEventAbsent, PlayerID: playerID, SeatNo: seatNo})
+		}
+	}
+	s.mu.Unlock()
+	defer s.markFinished()
+	if s.rebuys != nil {
+		result.PrizePool = s.rebuys.PrizePool()
+	}
+
+	maxHands := s.Config.MaxHands
+	if maxHands == 0 {
+		maxHands = DefaultSitAndGoMaxHands
+	}
+	runner := tablerunner.New(absentSeatProvider{sng: s, inner: provider}, tablerunner.RunnerConfig{
+		Shuffler:    s.Shuffler,
+		OnHandStart: s.recordBlindsOff,
+		OnAction: func(_ uint64, state domain.HandState, _ domain.Action, isFallback bool, _ tablerunner.ActionTiming) {
+			s.countFallback(state.ActingSeat, isFallback)
+		},
+	})
+
+	button := domain.SeatNo(1)
+	level := -1
+	for {
+		s.applyInterventions(seats, max(level, 0), uint64(result.HandsPlayed), &result)
+		alive := s.countContenders(seats)
+		if alive <= 1 {
+			if !s.finishIfSettled() {
+				continue
+			}
+			for i, seat := range seats {
+				if seat.Stack > 0 && !s.isDisqualified(players[i]) {
+					result.Places[players[i]] = 1
+					s.record(SitAndGoEvent{Kind: SitAndGoEventFinished, PlayerID: players[i], SeatNo: seat.SeatNo, Place: 1})
+				}
+			}
+			fillStacks(result.Stacks, players, seats)
+			return result, nil
+		}
+		if result.HandsPlayed >= maxHands {
+			fillStacks(result.Stacks, players, seats)
+			return result, fmt.Errorf("%w: %d hands", ErrHandLimitReached, result.HandsPlayed)
+		}
+
+		next := min(result.HandsPlayed/s.Config.HandsPerLevel, len(s.Config.Levels)-1)
+		if s.rebuys != nil && level >= 0 && (next != level || result.HandsPlayed == int(s.Config.Rebuys.RebuyHands)) {
+			s.takeBreak(seats, players, next != level, &result)
+		}
+		if next != level {
+			level = next
+			s.record(SitAndGoEvent{Kind: SitAndGoEventLevel, HandNo: uint64(result.HandsPlayed + 1), Level: level + 1})
+		}
+
+		before := make([]domain.Chips, len(seats))
+		for i, seat := range seats {
+			before[i] = seat.Stack
+		}
+		played, err := runner.RunTable(ctx, tablerunner.RunTableInput{
+			TableID:      s.ID,
+			StartingHand: uint64(result.HandsPlayed + 1),
+			HandsToRun:   1,
+			ButtonSeat:   button,
+			Seats:        seats,
+			Config:       s.Config.tableConfig(level),
+		})
+		if err != nil {
+			fillStacks(result.Stacks, players, seats)
+			return result, err
+		}
+		result.HandsPlayed++
+		seats = played.FinalSeats
+		button = played.FinalButton
+		if s.rebuys != nil {
+			s.rebuyBusted(seats, players, before, &result)
+		}
+
+		// Players busting in the same hand are ranked by their starting stack.
+		// A disqualified stack blinded off to nothing takes no place.
+		busted := make([]int, 0, len(seats))
+		for i, seat := range seats {
+			if before[i] > 0 && seat.Stack == 0 && !s.isDisqualified(players[i]) {
+				busted = append(busted, i)
+			}
+		}
+		sort.SliceStable(busted, func(a, b int) bool { return before[busted[a]] < before[busted[b]] })
+		place := alive
+		for _, i := range busted {
+			result.Places[players[i]] = place
+			s.record(SitAndGoEvent{
+				Kind:     SitAndGoEventEliminated,
+				HandNo:   uint64(result.HandsPlayed),
+				PlayerID: players[i],
+				SeatNo:   seats[i].SeatNo,
+				Place:    place,
+			})
+			place--
+		}
+	}
+}
+
+// rebuyBusted rebuys every player who busted in the hand just played while
+// rebuys are open, then counts the hand towards the rebuy period. A player
+// past MaxRebuys busts as usual.
+func (s *SitAndGo) rebuyBusted(seats []domain.SeatState, players []string, before []domain.Chips, result *SitAndGoResult) {
+	for i := range seats {
+		if before[i] == 0 || seats[i].Stack > 0 || s.isDisqualified(players[i]) || !s.rebuys.RebuyOpen() {
+			continue
+		}
+		purchase, err := s.rebuys.Rebuy(players[i], 0)
+		if err != nil {
+			continue
+		}
+		seats[i].Stack = domain.Chips(purchase.Chips)
+		seats[i].Status = domain.SeatStatusActive
+		s.record(SitAndGoEvent{
+			Kind:     SitAndGoEventRebuyTaken,
+			HandNo:   uint64(result.HandsPlayed),
+			PlayerID: players[i],
+			SeatNo:   seats[i].SeatNo,
+			Amount:   seats[i].Stack,
+		})
+	}
+	s.rebuys.EndHand()
+	result.PrizePool = s.rebuys.PrizePool()
+}
+
+// takeBreak breaks play before the next hand. At the add-on break every
+// player still in takes the add-on.
+func (s *SitAndGo) takeBreak(seats []domain.SeatState, players []string, levelUp bool, result *SitAndGoResult) {
+	s.rebuys.StartBreak()
+	if s.rebuys.addOnBreak() && s.Config.Rebuys.AddOnChips > 0 {
+		for i := range seats {
+			if seats[i].Stack == 0 || s.isDisqualified(players[i]) {
+				continue
+			}
+			purchase, err := s.rebuys.AddOn(players[i])
+			if err != nil {
+				continue
+			}
+			seats[i].Stack += domain.Chips(purchase.Chips)
+			s.record(SitAndGoEvent{
+				Kind:     SitAndGoEventAddOnTaken,
+				HandNo:   uint64(result.HandsPlayed + 1),
+				PlayerID: players[i],
+				SeatNo:   seats[i].SeatNo,
+				Amount:   domain.Chips(purchase.Chips),
+			})
+		}
+	}
+	if levelUp {
+		s.rebuys.AdvanceLevel()
+	} else {
+		s.rebuys.EndBreak()
+	}
+	result.PrizePool = s.rebuys.PrizePool()
+}
+
+func (c SitAndGoConfig) tableConfig(level int) domain.TableConfig {
+	cfg := domain.DefaultV0TableConfig()
+	cfg.MaxSeats = c.Seats
+	cfg.MinPlayersToStart = 2
+	cfg.StartingStack = c.StartingStack
+	cfg.SmallBlind = c.Levels[level].SmallBlind
+	cfg.BigBlind = c.Levels[level].BigBlind
+	cfg.RulesVersion = c.RulesVersion
+	return cfg
+}
+
+func (s *SitAndGo) recordBlindsOff(_ tablerunner.RunHandInput, initial domain.HandState) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	for _, seat := range initial.Seats {
+		playerID, ok := s.playerLocked(seat.SeatNo)
+		if !ok || (s.connected[playerID] && !s.disqualified[playerID]) || seat.TotalCommitted == 0 {
+			continue
+		}
+		s.events = append(s.events, SitAndGoEvent{
+			Kind:     SitAndGoEventBlindedOff,
+			HandNo:   initial.HandNo,
+			PlayerID: playerID,
+			SeatNo:   seat.SeatNo,
+			Amount:   seat.TotalCommitted,
+		})
+	}
+}
+
+func (s *SitAndGo) record(event SitAndGoEvent) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	s.events = append(s.events, event)
+}
+
+// foldedPlayer reports the player seated at seatNo when their hand is to be
+// killed, with the event that records the fold: they have not connected, or
+// they were disqualified and are being blinded off.
+func (s *SitAndGo) foldedPlayer(seatNo domain.SeatNo) (string, SitAndGoEventKind, bool) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	playerID, ok := s.playerLocked(seatNo)
+	switch {
+	case !ok:
+		return "", "", false
+	case s.disqualified[playerID]:
+		return playerID, SitAndGoEventForfeitFold, true
+	case !s.connected[playerID]:
+		return playerID, SitAndGoEventAbsentFold, true
+	default:
+		return "", "", false
+	}
+}
+
+func (s *SitAndGo) isDisqualified(playerID string) bool {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return s.disqualified[playerID]
+}
+
+// countContenders counts the seats still playing for a place: those with
+// chips whose player has not been disqualified.
+func (s *SitAndGo) countContenders(seats []domain.SeatState) int {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return s.countContendersLocked(seats)
+}
+
+func (s *SitAndGo) countContendersLocked(seats []domain.SeatState) int {
+	count := 0
+	for _, seat := range seats {
+		if playerID, ok := s.playerLocked(seat.SeatNo); ok && seat.Stack > 0 && !s.disqualified[playerID] {
+			count++
+		}
+	}
+	return count
+}
+
+func (s *SitAndGo) playerLocked(seatNo domain.SeatNo) (string, bool) {
+	idx := int(seatNo) - 1
+	if idx < 0 || idx >= len(s.players) {
+		return "", false
+	}
+	return s.players[idx], true
+}
+
+func (s *SitAndGo) seatLocked(playerID string) (domain.SeatNo, bool) {
+	for i, registered := range s.players {
+		if registered == playerID {
+			return domain.SeatNo(i + 1), true
+		}
+	}
+	return 0, false
+}
+
+// absentSeatProvider kills the hand of any seat whose player has not
+// connected or is being blinded off after a disqualification, and defers to
+// inner for everyone else.
+type absentSeatProvider struct {
+	sng   *SitAndGo
+	inner tablerunner.ActionProvider
+}
+
+func (p absentSeatProvider) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	playerID, kind, folded := p.sng.foldedPlayer(state.ActingSeat)
+	if !folded {
+		return p.inner.NextAction(ctx, state)
+	}
+	p.sng.record(SitAndGoEvent{
+		Kind:     kind,
+		HandNo:   state.HandNo,
+		PlayerID: playerID,
+		SeatNo:   state.ActingSeat,
+	})
+	return domain.NewAction(domain.ActionFold, nil)
+}
+
+func fillStacks(stacks map[string]domain.Chips, players []string, seats []domain.SeatState) {
+	for i, seat := range seats {
+		stacks[players[i]] = seat.Stack
+	}
+}
@@ -0,0 +1,238 @@
+package tournament
+
+import (
+	"context"
+	"errors"
+	"reflect"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+)
+
+func TestSitAndGo_BlindsOffAbsentPlayer(t *testing.T) {
+	t.Parallel()
+
+	config := sitAndGoConfig()
+	config.MaxHands = 4
+	sng := newSitAndGo(t, config, "alice", "bob")
+	if err := sng.Connect("alice"); err != nil {
+		t.Fatalf("Connect failed: %v", err)
+	}
+
+	result, err := sng.Run(context.Background(), callingProvider{})
+	if !errors.Is(err, ErrHandLimitReached) {
+		t.Fatalf("expected ErrHandLimitReached, got %v", err)
+	}
+	if result.HandsPlayed != 4 {
+		t.Fatalf("expected 4 hands, got %d", result.HandsPlayed)
+	}
+	// Bob alternates between the big and small blind and folds each time.
+	if result.Stacks["alice"] != 1060 || result.Stacks["bob"] != 940 {
+		t.Fatalf("unexpected stacks %+v", result.Stacks)
+	}
+
+	want := []SitAndGoEvent{
+		{Kind: SitAndGoEventConnected, PlayerID: "alice", SeatNo: 1},
+		{Kind: SitAndGoEventStarted},
+		{Kind: SitAndGoEventAbsent, PlayerID: "bob", SeatNo: 2},
+		{Kind: SitAndGoEventLevel, HandNo: 1, Level: 1},
+	}
+	for handNo, blind := range []domain.Chips{20, 10, 20, 10} {
+		want = append(want,
+			SitAndGoEvent{Kind: SitAndGoEventBlindedOff, HandNo: uint64(handNo + 1), PlayerID: "bob", SeatNo: 2, Amount: blind},
+			SitAndGoEvent{Kind: SitAndGoEventAbsentFold, HandNo: uint64(handNo + 1), PlayerID: "bob", SeatNo: 2},
+		)
+	}
+	if got := sng.Events(); !reflect.DeepEqual(got, want) {
+		t.Fatalf("unexpected events\nwant=%+v\ngot=%+v", want, got)
+	}
+}
+
+func TestSitAndGo_FinishesOnceAbsentPlayerIsBlindedOut(t *testing.T) {
+	t.Parallel()
+
+	config := sitAndGoConfig()
+	config.StartingStack = 300
+	config.Levels = []BlindLevel{{SmallBlind: 25, BigBlind: 50}, {SmallBlind: 50, BigBlind: 100}}
+	config.HandsPerLevel = 3
+	sng := newSitAndGo(t, config, "alice", "bob")
+	sng.Shuffler = rules.NewSeededShuffler(7)
+	if err := sng.Connect("alice"); err != nil {
+		t.Fatalf("Connect failed: %v", err)
+	}
+
+	result, err := sng.Run(context.Background(), callingProvider{})
+	if err != nil {
+		t.Fatalf("Run failed: %v", err)
+	}
+
+	places := []int{result.Places["alice"], result.Places["bob"]}
+	if !reflect.DeepEqual(places, []int{1, 2}) && !reflect.DeepEqual(places, []int{2, 1}) {
+		t.Fatalf("expected one winner and one runner-up, got %+v", result.Places)
+	}
+	if total := result.Stacks["alice"] + result.Stacks["bob"]; total != 600 {
+		t.Fatalf("expected chips conserved, got %d", total)
+	}
+	events := sng.Events()
+	if events[len(events)-1].Kind != SitAndGoEventFinished {
+		t.Fatalf("expected the log to end with %q, got %+v", SitAndGoEventFinished, events[len(events)-1])
+	}
+	seen := map[SitAndGoEventKind]int{}
+	for _, event := range events {
+		seen[event.Kind]++
+	}
+	if seen[SitAndGoEventLevel] != 2 || seen[SitAndGoEventEliminated] != 1 {
+		t.Fatalf("expected two levels and one elimination, got %+v", seen)
+	}
+}
+
+func TestSitAndGo_RegistrationRules(t *testing.T) {
+	t.Parallel()
+
+	sng := newSitAndGo(t, sitAndGoConfig(), "alice")
+	if _, err := sng.Register("alice"); !errors.Is(err, ErrAlreadyRegistered) {
+		t.Fatalf("expected ErrAlreadyRegistered, got %v", err)
+	}
+	if _, err := sng.Run(context.Background(), callingProvider{}); !errors.Is(err, ErrSitAndGoNotFull) {
+		t.Fatalf("expected ErrSitAndGoNotFull, got %v", err)
+	}
+	if err := sng.Connect("carol"); !errors.Is(err, ErrPlayerNotFound) {
+		t.Fatalf("expected ErrPlayerNotFound, got %v", err)
+	}
+
+	seatNo, err := sng.Register("bob")
+	if err != nil {
+		t.Fatalf("Register failed: %v", err)
+	}
+	if seatNo != 2 || !sng.Full() {
+		t.Fatalf("expected bob in seat 2 of a full table, got seat %d", seatNo)
+	}
+	if _, err := sng.Register("carol"); !errors.Is(err, ErrSitAndGoFull) {
+		t.Fatalf("expected ErrSitAndGoFull, got %v", err)
+	}
+}
+
+func TestSitAndGo_RebuysBustedPlayersAndSellsTheAddOn(t *testing.T) {
+	t.Parallel()
+
+	config := sitAndGoConfig()
+	config.Levels = []BlindLevel{{SmallBlind: 10, BigBlind: 20}, {SmallBlind: 20, BigBlind: 40}}
+	config.HandsPerLevel = 2
+	rebuys := rebuyConfig()
+	rebuys.StartingStack = 1000
+	rebuys.RebuyChips = 1000
+	rebuys.RebuyThreshold = 1000
+	rebuys.RebuyLevels = 1
+	rebuys.AllowDoubleRebuy = false
+	rebuys.MaxRebuys = 1
+	config.Rebuys = &rebuys
+	sng := newSitAndGo(t, config, "alice", "bob")
+	sng.Shuffler = rules.NewSeededShuffler(7)
+	for _, playerID := range []string{"alice", "bob"} {
+		if err := sng.Connect(playerID); err != nil {
+			t.Fatalf("Connect failed: %v", err)
+		}
+	}
+
+	result, err := sng.Run(context.Background(), allInProvider{})
+	if err != nil {
+		t.Fatalf("Run failed: %v", err)
+	}
+	// Every hand is all-in, so someone busts in the first rebuy level
+	// unless both of its hands are chopped.
+	taken := map[SitAndGoEventKind]map[string]int{SitAndGoEventRebuyTaken: {}, SitAndGoEventAddOnTaken: {}}
+	for _, event := range sng.Events() {
+		if players, ok := taken[event.Kind]; ok {
+			players[event.PlayerID]++
+			if event.HandNo > 3 {
+				t.Fatalf("expected rebuys and add-ons to stop with the first level, got %+v", event)
+			}
+		}
+	}
+	rebuysTaken := len(taken[SitAndGoEventRebuyTaken])
+	addOns := len(taken[SitAndGoEventAddOnTaken])
+	if rebuysTaken == 0 || taken[SitAndGoEventRebuyTaken]["alice"] > 1 || taken[SitAndGoEventRebuyTaken]["bob"] > 1 {
+		t.Fatalf("expected at most one rebuy each, got %+v", taken)
+	}
+	if want := 2000 + domain.Chips(rebuysTaken)*1000 + domain.Chips(addOns)*15_000; result.Stacks["alice"]+result.Stacks["bob"] != want {
+		t.Fatalf("expected %d chips in play, got %+v", want, result.Stacks)
+	}
+	if want := uint64(200 + rebuysTaken*100 + addOns*150); result.PrizePool != want {
+		t.Fatalf("expected a prize pool of %d, got %d", want, result.PrizePool)
+	}
+	places := []int{result.Places["alice"], result.Places["bob"]}
+	if !reflect.DeepEqual(places, []int{1, 2}) && !reflect.DeepEqual(places, []int{2, 1}) {
+		t.Fatalf("expected one winner and one runner-up, got %+v", result.Places)
+	}
+}
+
+func TestSitAndGoConfig_RejectsInvalidBlindLevel(t *testing.T) {
+	t.Parallel()
+
+	config := sitAndGoConfig()
+	config.Levels = append(config.Levels, BlindLevel{SmallBlind: 40, BigBlind: 20})
+	if err := config.Validate(); !errors.Is(err, ErrInvalidBlindLevel) {
+		t.Fatalf("expected ErrInvalidBlindLevel, got %v", err)
+	}
+}
+
+func TestNewSitAndGo_PinsTheRulesVersion(t *testing.T) {
+	t.Parallel()
+
+	sng, err := NewSitAndGo("sng-1", sitAndGoConfig())
+	if err != nil {
+		t.Fatalf("NewSitAndGo failed: %v", err)
+	}
+	if sng.Config.RulesVersion != domain.CurrentRulesVersion || sng.Config.tableConfig(0).RulesVersion != domain.CurrentRulesVersion {
+		t.Fatalf("expected the tournament pinned to rules version %d, got %d", domain.CurrentRulesVersion, sng.Config.RulesVersion)
+	}
+
+	config := sitAndGoConfig()
+	config.RulesVersion = domain.CurrentRulesVersion + 1
+	if err := config.Validate(); !errors.Is(err, ErrInvalidConfig) || !errors.Is(err, domain.ErrUnknownRulesVersion) {
+		t.Fatalf("expected an unknown rules version rejected, got %v", err)
+	}
+}
+
+// callingProvider calls any bet and otherwise checks.
+type callingProvider struct{}
+
+func (callingProvider) NextAction(_ context.Context, state domain.HandState) (domain.Action, error) {
+	for _, seat := range state.Seats {
+		if seat.SeatNo == state.ActingSeat && state.CurrentBet > seat.CommittedInRound {
+			return domain.NewAction(domain.ActionCall, nil)
+		}
+	}
+	return domain.NewAction(domain.ActionCheck, nil)
+}
+
+// allInProvider moves all in at every decision.
+type allInProvider struct{}
+
+func (allInProvider) NextAction(context.Context, domain.HandState) (domain.Action, error) {
+	return domain.NewAction(domain.ActionAllIn, nil)
+}
+
+func sitAndGoConfig() SitAndGoConfig {
+	return SitAndGoConfig{
+		Seats:         2,
+		StartingStack: 1000,
+		Levels:        []BlindLevel{{SmallBlind: 10, BigBlind: 20}},
+		HandsPerLevel: 10,
+	}
+}
+
+func newSitAndGo(t *testing.T, config SitAndGoConfig, playerIDs ...string) *SitAndGo {
+	t.Helper()
+	sng, err := NewSitAndGo("sng-1", config)
+	if err != nil {
+		t.Fatalf("NewSitAndGo failed: %v", err)
+	}
+	for _, playerID := range playerIDs {
+		if _, err := sng.Register(playerID); err != nil {
+			t.Fatalf("Register failed: %v", err)
+		}
+	}
+	return sng
+}
@@ -0,0 +1,216 @@
+// Package training runs self-play for reinforcement learning loops: one
+// Learner bot plays heads-up against frozen checkpoints of itself. The
+// learner is checkpointed before the first hand and again every
+// CheckpointEvery hands, each checkpoint joining the rotation of opponents,
+// and the arena tracks how the learner fares against each one, so a loop
+// can tell whether it is still beating its past selves.
+package training
+
+import (
+	"context"
+	"errors"
+	"fmt"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/rules"
+	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+)
+
+const (
+	tableID      = "training"
+	learnerSeat  = domain.SeatNo(1)
+	opponentSeat = domain.SeatNo(2)
+)
+
+var (
+	ErrInvalidConfig    = errors.New("invalid training config")
+	ErrCheckpointFailed = errors.New("learner checkpoint failed")
+)
+
+// Learner is the bot being trained. Checkpoint freezes its current policy:
+// the provider it returns must keep playing as the learner does now,
+// however the learner changes afterwards.
+type Learner interface {
+	tablerunner.ActionProvider
+	Checkpoint() (tablerunner.ActionProvider, error)
+}
+
+// Observer is implemented by learners that train as they play. Observe
+// receives every completed hand, with the learner in seat.
+type Observer interface {
+	Observe(final domain.HandState, seat domain.SeatNo)
+}
+
+// Config sizes a training run. Every hand is dealt from Table's starting
+// stacks, with the button alternating between the learner and its
+// opponent. MaxCheckpoints retires the oldest checkpoint once more are in
+// rotation; zero keeps them all. Seed fixes the deal.
+type Config struct {
+	Hands           int                `json:"hands"`
+	CheckpointEvery int                `json:"checkpoint_every"`
+	MaxCheckpoints  int                `json:"max_checkpoints,omitempty"`
+	Seed            int64              `json:"seed"`
+	Table           domain.TableConfig `json:"table"`
+}
+
+func (c Config) Validate() error {
+	if c.Hands <= 0 {
+		return fmt.Errorf("%w: hands must be positive, got %d", ErrInvalidConfig, c.Hands)
+	}
+	if c.CheckpointEvery <= 0 {
+		return fmt.Errorf("%w: checkpoint_every must be positive, got %d", ErrInvalidConfig, c.CheckpointEvery)
+	}
+	if c.MaxCheckpoints < 0 {
+		return fmt.Errorf("%w: max_checkpoints cannot be negative, got %d", ErrInvalidConfig, c.MaxCheckpoints)
+	}
+	return c.Table.Validate()
+}
+
+// CheckpointStats is the learner's record against one checkpoint.
+// AfterHand is how many hands the learner had played when it was frozen. A
+// hand counts as a win or a loss by the learner's net chips; WinRate is
+// wins over hands and BBPer100 the learner's net chips in big blinds per
+// 100 hands.
+type CheckpointStats struct {
+	ID        int     `json:"id"`
+	AfterHand int     `json:"after_hand"`
+	Hands     int     `json:"hands"`
+	Wins      int     `json:"wins"`
+	Losses    int     `json:"losses"`
+	NetChips  int64   `json:"net_chips"`
+	WinRate   float64 `json:"win_rate"`
+	BBPer100  float64 `json:"bb_per_100"`
+	Retired   bool    `json:"retired,omitempty"`
+}
+
+// Report is the outcome of a training run, with every checkpoint in the
+// order it was taken.
+type Report struct {
+	Hands       int               `json:"hands"`
+	Checkpoints []CheckpointStats `json:"checkpoints"`
+}
+
+type checkpoint struct {
+	provider tablerunner.ActionProvider
+	stats    CheckpointStats
+}
+
+// Run plays cfg.Hands hands of learner against its checkpoints, rotating
+// through the ones in play hand by hand. A hand that fails stops the run
+// with the report so far.
+func Run(ctx context.Context, learner Learner, cfg Config) (Report, error) {
+	if learner == nil {
+		return Report{}, fmt.Errorf("%w: learner is required", ErrInvalidConfig)
+	}
+	if err := cfg.Validate(); err != nil {
+		return Report{}, err
+	}
+
+	seats := &seatProviders{learner: learner}
+	runner := tablerunner.New(seats, tablerunner.RunnerConfig{Shuffler: rules.NewSeededShuffler(cfg.Seed)})
+	var checkpoints []*checkpoint
+	var active []*checkpoint
+	register := func(afterHand int) error {
+		provider, err := learner.Checkpoint()
+		if err != nil {
+			return fmt.Errorf("%w: after hand %d: %w", ErrCheckpointFailed, afterHand, err)
+		}
+		taken := &checkpoint{provider: provider, stats: CheckpointStats{ID: len(checkpoints), AfterHand: afterHand}}
+		checkpoints = append(checkpoints, taken)
+		active = append(active, taken)
+		if cfg.MaxCheckpoints > 0 && len(active) > cfg.MaxCheckpoints {
+			active[0].stats.Retired = true
+			active = active[1:]
+		}
+		return nil
+	}
+	report := func(hands int) Report {
+		out := Report{Hands: hands, Checkpoints: make([]CheckpointStats, 0, len(checkpoints))}
+		for _, taken := range checkpoints {
+			out.Checkpoints = append(out.Checkpoints, taken.stats.withRates(cfg.Table.BigBlind))
+		}
+		return out
+	}
+
+	if err := register(0); err != nil {
+		return Report{}, err
+	}
+	for hand := 1; hand <= cfg.Hands; hand++ {
+		opponent := active[(hand-1)%len(active)]
+		seats.opponent = opponent.provider
+		button := learnerSeat
+		if hand%2 == 0 {
+			button = opponentSeat
+		}
+		result, err := runner.RunHand(ctx, tablerunner.RunHandInput{
+			TableID:    tableID,
+			HandNo:     uint64(hand),
+			ButtonSeat: button,
+			Seats: []domain.SeatState{
+				domain.NewSeatState(learnerSeat, cfg.Table.StartingStack),
+				domain.NewSeatState(opponentSeat, cfg.Table.StartingStack),
+			},
+			Config: cfg.Table,
+		})
+		if err != nil {
+			return report(hand - 1), fmt.Errorf("hand %d: %w", hand, err)
+		}
+
+		if observer, ok := learner.(Observer); ok {
+			observer.Observe(result.FinalState, learnerSeat)
+		}
+		opponent.stats.record(learnerNet(result.FinalState, cfg.Table.StartingStack))
+		if hand%cfg.CheckpointEvery == 0 && hand < cfg.Hands {
+			if err := register(hand); err != nil {
+				return report(hand), err
+			}
+		}
+	}
+	return report(cfg.Hands), nil
+}
+
+// learnerNet is what the learner won or lost in a hand it started with stack.
+func learnerNet(final domain.HandState, stack domain.Chips) int64 {
+	for _, seat := range final.Seats {
+		if seat.SeatNo == learnerSeat {
+			return int64(seat.Stack) - int64(stack)
+		}
+	}
+	return 0
+}
+
+func (s *CheckpointStats) record(net int64) {
+	s.Hands++
+	s.NetChips += net
+	switch {
+	case net > 0:
+		s.Wins++
+	case net < 0:
+		s.Losses++
+	}
+}
+
+func (s CheckpointStats) withRates(bigBlind domain.Chips) CheckpointStats {
+	if s.Hands == 0 {
+		return s
+	}
+	s.WinRate = float64(s.Wins) / float64(s.Hands)
+	if bigBlind > 0 {
+		s.BBPer100 = float64(s.NetChips) / float64(bigBlind) / float64(s.Hands) * 100
+	}
+	return s
+}
+
+// seatProviders asks the learner to act for its seat and the current
+// checkpoint for the other.
+type seatProviders struct {
+	learner  tablerunner.ActionProvider
+	opponent tablerunner.ActionProvider
+}
+
+func (s *seatProviders) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	if state.ActingSeat == learnerSeat {
+		return s.learner.NextAction(ctx, state)
+	}
+	return s.opponent.NextAction(ctx, state)
+}
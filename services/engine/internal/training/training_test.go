@@ -0,0 +1,85 @@
+package training
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/imaddar/poker-arena/services/engine/internal/domain"
+	"github.com/imaddar/poker-arena/services/engine/internal/tablerunner"
+)
+
+func TestRun_RotatesCheckpointsAndTracksWinRates(t *testing.T) {
+	t.Parallel()
+
+	// The learner folds to any bet until it has seen ten hands, then moves
+	// all in every time, which beats every checkpoint that still folds.
+	learner := &switchingLearner{switchAfter: 10}
+	report, err := Run(context.Background(), learner, Config{
+		Hands:           30,
+		CheckpointEvery: 10,
+		MaxCheckpoints:  2,
+		Seed:            7,
+		Table:           domain.DefaultV0TableConfig(),
+	})
+	if err != nil {
+		t.Fatalf("Run failed: %v", err)
+	}
+	if report.Hands != 30 || len(report.Checkpoints) != 3 || learner.observed != 30 {
+		t.Fatalf("expected 30 hands over 3 checkpoints, got %+v (observed %d)", report, learner.observed)
+	}
+
+	first, second, third := report.Checkpoints[0], report.Checkpoints[1], report.Checkpoints[2]
+	if first.AfterHand != 0 || second.AfterHand != 10 || third.AfterHand != 20 {
+		t.Fatalf("expected checkpoints every 10 hands, got %+v", report.Checkpoints)
+	}
+	if !first.Retired || second.Retired || third.Retired {
+		t.Fatalf("expected only the oldest checkpoint retired, got %+v", report.Checkpoints)
+	}
+	// The first checkpoint plays the first ten hands alone, then every
+	// other hand until the third is taken.
+	if first.Hands != 15 || second.Hands != 10 || third.Hands != 5 {
+		t.Fatalf("unexpected rotation %+v", report.Checkpoints)
+	}
+	// Folding against itself, the learner loses the small blind on the
+	// button and wins it in the big blind; all in, it wins every hand.
+	if first.Wins != 10 || first.Losses != 5 || first.NetChips <= 0 || first.WinRate != 10.0/15 {
+		t.Fatalf("unexpected record against the first checkpoint %+v", first)
+	}
+
+	if _, err := Run(context.Background(), learner, Config{Hands: 10, Table: domain.DefaultV0TableConfig()}); !errors.Is(err, ErrInvalidConfig) {
+		t.Fatalf("expected ErrInvalidConfig without checkpoint_every, got %v", err)
+	}
+}
+
+type switchingLearner struct {
+	switchAfter int
+	observed    int
+}
+
+func (l *switchingLearner) NextAction(ctx context.Context, state domain.HandState) (domain.Action, error) {
+	return policy(l.observed >= l.switchAfter).NextAction(ctx, state)
+}
+
+func (l *switchingLearner) Checkpoint() (tablerunner.ActionProvider, error) {
+	return policy(l.observed >= l.switchAfter), nil
+}
+
+func (l *switchingLearner) Observe(domain.HandState, domain.SeatNo) {
+	l.observed++
+}
+
+// policy moves all in when set and otherwise checks, folding to any bet.
+type policy bool
+
+func (p policy) NextAction(_ context.Context, state domain.HandState) (domain.Action, error) {
+	if p {
+		return domain.NewAction(domain.ActionAllIn, nil)
+	}
+	for _, seat := range state.Seats {
+		if seat.SeatNo == state.ActingSeat && state.CurrentBet > seat.CommittedInRound {
+			return domain.NewAction(domain.ActionFold, nil)
+		}
+	}
+	return domain.NewAction(domain.ActionCheck, nil)
+}
@@ -0,0 +1,61 @@
+// Code generated by protocolgen from schemas/agent-protocol-v1.json. DO NOT EDIT.
+
+// Package agentprotocol holds the agent protocol types for Go bots.
+package agentprotocol
+
+type DecisionRequest struct {
+	ActionDeadlineMS int64            `json:"action_deadline_ms"`
+	Analytics        *ViewAnalytics   `json:"analytics,omitempty"`
+	Bets             map[string]int64 `json:"bets,omitempty"`
+	Board            []string         `json:"board"`
+	HandID           string           `json:"hand_id"`
+	HoleCards        []string         `json:"hole_cards"`
+	LegalActions     []string         `json:"legal_actions"`
+	MinRaiseTo       *int64           `json:"min_raise_to,omitempty"`
+	Pot              int64            `json:"pot"`
+	ProtocolVersion  int64            `json:"protocol_version"`
+	RuleFlags        []string         `json:"rule_flags,omitempty"`
+	Seat             int64            `json:"seat"`
+	SizingHints      *SizingHints     `json:"sizing_hints,omitempty"`
+	Stacks           map[string]int64 `json:"stacks,omitempty"`
+	TableID          string           `json:"table_id"`
+	TimeBankMS       *int64           `json:"time_bank_ms,omitempty"`
+	ToCall           int64            `json:"to_call"`
+	View             *string          `json:"view,omitempty"`
+}
+
+type DecisionResponse struct {
+	Action    string     `json:"action"`
+	Amount    *int64     `json:"amount,omitempty"`
+	Telemetry *Telemetry `json:"telemetry,omitempty"`
+}
+
+type SizingHints struct {
+	Action    string         `json:"action"`
+	AllInOnly bool           `json:"all_in_only"`
+	Max       int64          `json:"max"`
+	Min       int64          `json:"min"`
+	Presets   []SizingPreset `json:"presets"`
+	Step      int64          `json:"step"`
+}
+
+type SizingPreset struct {
+	Amount int64  `json:"amount"`
+	Label  string `json:"label"`
+}
+
+type Telemetry struct {
+	Equity *float64 `json:"equity,omitempty"`
+	Tags   []string `json:"tags,omitempty"`
+}
+
+type ViewAnalytics struct {
+	ButtonSeat     int64   `json:"button_seat"`
+	EffectiveStack int64   `json:"effective_stack"`
+	HandCategory   *string `json:"hand_category,omitempty"`
+	PlayersInHand  int64   `json:"players_in_hand"`
+	PotOdds        float64 `json:"pot_odds"`
+	StackToPot     float64 `json:"stack_to_pot"`
+	Street         string  `json:"street"`
+	StreetBets     int64   `json:"street_bets"`
+}